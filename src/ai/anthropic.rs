@@ -1,7 +1,11 @@
 use async_trait::async_trait;
+use tracing::{debug, warn};
 use super::models::{AIProvider, AIError};
+use super::tools::{ToolExecutor, MAX_TOOL_ROUNDS};
 use reqwest::Client;
-use serde_json::json;
+use serde_json::{json, Value};
+
+const TOOL_MODEL: &str = "claude-3-5-sonnet-20240620";
 
 pub struct AnthropicProvider {
     api_key: String,
@@ -16,29 +20,114 @@ impl AnthropicProvider {
         }
     }
 
-    async fn generate_response_with_model(&self, prompt: &str, model: &str) -> Result<String, AIError> {
+    async fn send(&self, body: &Value) -> Result<Value, AIError> {
         let response = self.client.post("https://api.anthropic.com/v1/messages")
-            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("x-api-key", &self.api_key)
             .header("Content-Type", "application/json")
             .header("anthropic-version", "2023-06-01")
-            .json(&json!({
-                "model": model,
-                "messages": [{"role": "user", "content": prompt}],
-                "max_tokens": 1000
-            }))
+            .json(body)
             .send()
             .await
             .map_err(|e| AIError::NetworkError(e.to_string()))?;
 
-        let response_json: serde_json::Value = response.json()
+        let response_json: Value = response.json()
             .await
             .map_err(|e| AIError::InvalidResponse(e.to_string()))?;
 
+        if let Some(error) = response_json["error"]["message"].as_str() {
+            return Err(AIError::APIError(error.to_string()));
+        }
+        Ok(response_json)
+    }
+
+    async fn generate_response_with_model(&self, prompt: &str, model: &str) -> Result<String, AIError> {
+        let response_json = self.send(&json!({
+            "model": model,
+            "messages": [{"role": "user", "content": prompt}],
+            "max_tokens": 1000
+        })).await?;
+
         response_json["content"][0]["text"]
             .as_str()
             .map(|s| s.trim().to_string())
             .ok_or_else(|| AIError::InvalidResponse("No content in response".to_string()))
     }
+
+    // image is a data URI; only the base64 payload is sent
+    pub async fn generate_vision_response(&self, prompt: &str, image: &str) -> Result<String, AIError> {
+        let data = image.split_once(',').map_or(image, |(_, data)| data);
+        let response_json = self.send(&json!({
+            "model": TOOL_MODEL,
+            "messages": [{
+                "role": "user",
+                "content": [
+                    {"type": "image", "source": {"type": "base64", "media_type": "image/jpeg", "data": data}},
+                    {"type": "text", "text": prompt}
+                ]
+            }],
+            "max_tokens": 500
+        })).await?;
+
+        response_json["content"][0]["text"]
+            .as_str()
+            .map(|s| s.trim().to_string())
+            .ok_or_else(|| AIError::InvalidResponse("No content in response".to_string()))
+    }
+
+    // Runs the tool-use loop: every tool_use block is executed and its result sent back
+    // until the model answers with plain text
+    pub async fn generate_with_tools(&self, prompt: &str, tools: &dyn ToolExecutor) -> Result<String, AIError> {
+        let definitions = tools.definitions();
+        let mut messages = vec![json!({"role": "user", "content": prompt})];
+
+        for round in 0..=MAX_TOOL_ROUNDS {
+            let mut body = json!({
+                "model": TOOL_MODEL,
+                "messages": messages,
+                "max_tokens": 1000
+            });
+            // Out of rounds: leave the tools off so the model has to answer with what it has
+            if round < MAX_TOOL_ROUNDS {
+                body["tools"] = json!(definitions);
+            }
+            let response_json = self.send(&body).await?;
+            let content = response_json["content"].as_array().cloned().unwrap_or_default();
+
+            if response_json["stop_reason"].as_str() != Some("tool_use") {
+                let text: Vec<&str> = content.iter()
+                    .filter(|block| block["type"] == "text")
+                    .filter_map(|block| block["text"].as_str())
+                    .collect();
+                if text.is_empty() {
+                    return Err(AIError::InvalidResponse("No content in response".to_string()));
+                }
+                return Ok(text.join("\n").trim().to_string());
+            }
+
+            let mut results = Vec::new();
+            for block in content.iter().filter(|block| block["type"] == "tool_use") {
+                let name = block["name"].as_str().unwrap_or_default();
+                debug!("AI called tool {} with {}", name, block["input"]);
+                let (output, is_error) = match tools.call(name, &block["input"]).await {
+                    Ok(output) => (output, false),
+                    Err(e) => {
+                        warn!("AI tool {} failed: {}", name, e);
+                        (e, true)
+                    }
+                };
+                results.push(json!({
+                    "type": "tool_result",
+                    "tool_use_id": block["id"],
+                    "content": output,
+                    "is_error": is_error
+                }));
+            }
+            messages.push(json!({"role": "assistant", "content": content}));
+            messages.push(json!({"role": "user", "content": results}));
+        }
+
+        Err(AIError::InvalidResponse("The model kept calling tools without answering".to_string()))
+    }
 }
 
 #[async_trait]
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use tokio::sync::RwLock;
 use std::sync::Arc;
-use log::{debug};
+use tracing::{debug, warn};
 use crate::ai::{SearchProvider, WebSearchClient};
+use crate::output_filter::{self, OutputFilter};
 use super::models::{AIProvider, AIError};
 use super::openai::OpenAIProvider;
 use super::anthropic::AnthropicProvider;
 use super::xai::XAIProvider;
+use super::tools::ToolExecutor;
 
 pub struct AIClient {
     openai_provider: Option<OpenAIProvider>,
@@ -15,6 +17,7 @@ pub struct AIClient {
     web_search_client: WebSearchClient,
     chat_history: Arc<RwLock<String>>,
     message_buffer: Arc<RwLock<HashMap<String, String>>>,
+    output_filter: Arc<OutputFilter>,
 }
 
 impl AIClient {
@@ -37,9 +40,16 @@ impl AIClient {
             ),
             chat_history: Arc::new(RwLock::new(String::new())),
             message_buffer: Arc::new(RwLock::new(HashMap::new())),
+            output_filter: Arc::new(OutputFilter::default()),
         }
     }
 
+    // The filter whose settings decide whether responses get a moderation pass
+    pub fn with_output_filter(mut self, output_filter: Arc<OutputFilter>) -> Self {
+        self.output_filter = output_filter;
+        self
+    }
+
     pub async fn update_chat_history(&self, message: &str) {
         let mut history = self.chat_history.write().await;
         history.push_str(message);
@@ -64,10 +74,40 @@ impl AIClient {
     pub async fn generate_response_with_history(&self, prompt: &str) -> Result<String, AIError> {
         let history = self.chat_history.read().await;
         let full_prompt = format!("{}\n\n{}", *history, prompt);
-        self.generate_response(&full_prompt).await
+        let response = self.generate_response(&full_prompt).await?;
+        self.moderate(response).await
     }
 
     pub async fn generate_response_without_history(&self, prompt: &str) -> Result<String, AIError> {
+        let response = self.generate_unmoderated(prompt).await?;
+        self.moderate(response).await
+    }
+
+    // Lets the model look things up through the given tools; providers without tool use
+    // just answer the prompt as before
+    pub async fn generate_response_with_tools(&self, prompt: &str, tools: &dyn ToolExecutor) -> Result<String, AIError> {
+        let response = match &self.anthropic_provider {
+            Some(provider) => provider.generate_with_tools(prompt, tools).await?,
+            None => self.generate_unmoderated(prompt).await?,
+        };
+        self.moderate(response).await
+    }
+
+    // Second opinion on anything a model wrote before it can reach chat, when enabled
+    async fn moderate(&self, response: String) -> Result<String, AIError> {
+        if !self.output_filter.ai_moderation_enabled() {
+            return Ok(response);
+        }
+
+        let verdict = self.generate_unmoderated(&output_filter::moderation_prompt(&response)).await?;
+        if output_filter::is_flagged(&verdict) {
+            warn!("AI moderation blocked a response: {}", response);
+            return Err(AIError::InvalidResponse("The response was blocked by the content filter".to_string()));
+        }
+        Ok(response)
+    }
+
+    async fn generate_unmoderated(&self, prompt: &str) -> Result<String, AIError> {
         if let Some(provider) = &self.openai_provider {
             provider.generate_response_without_history(prompt).await
         } else if let Some(provider) = &self.anthropic_provider {
@@ -121,15 +161,28 @@ impl AIClient {
             );
 
             // Generate response using OpenAI
-            provider.generate_web_search_response(&full_prompt).await
+            let response = provider.generate_web_search_response(&full_prompt).await?;
+            self.moderate(response).await
         } else {
             Err(AIError::APIError("OpenAI provider not available for web search".to_string()))
         }
     }
 
-    pub async fn generate_grok_response(&self, prompt: &str) -> Result<String, AIError> {
+    pub async fn generate_vision_response(&self, prompt: &str, image: &str) -> Result<String, AIError> {
+        let response = if let Some(provider) = &self.openai_provider {
+            provider.generate_vision_response(prompt, image).await?
+        } else if let Some(provider) = &self.anthropic_provider {
+            provider.generate_vision_response(prompt, image).await?
+        } else {
+            return Err(AIError::APIError("No AI provider available".to_string()));
+        };
+        self.moderate(response).await
+    }
+
+    pub async fn generate_grok_response(&self, prompt: &str, system_prompt: &str) -> Result<String, AIError> {
         if let Some(provider) = &self.xai_provider {
-            provider.generate_grok_response(prompt).await
+            let response = provider.generate_grok_response(prompt, system_prompt).await?;
+            self.moderate(response).await
         } else {
             Err(AIError::APIError("XAI provider not available for Grok responses".to_string()))
         }
@@ -4,7 +4,13 @@ mod openai;
 mod anthropic;
 mod xai;
 mod web_search;
+pub mod translate;
+pub mod trivia;
+pub mod prompts;
+pub mod tools;
 
 pub use models::{AIProvider, AIError};
 pub use client::AIClient;
-pub use web_search::{WebSearchClient, WebSearchResult, SearchProvider};
\ No newline at end of file
+pub use web_search::{WebSearchClient, WebSearchResult, SearchProvider};
+pub use translate::Translation;
+pub use tools::{ToolDefinition, ToolExecutor};
\ No newline at end of file
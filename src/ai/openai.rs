@@ -46,6 +46,35 @@ impl OpenAIProvider {
 
         self.generate_response_with_model(&prompt, "gpt-4", 250).await
     }
+
+    // image is a data URI, as OBS screenshots come back
+    pub async fn generate_vision_response(&self, prompt: &str, image: &str) -> Result<String, AIError> {
+        let response = self.client.post("https://api.openai.com/v1/chat/completions")
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&json!({
+                "model": "gpt-4o",
+                "messages": [{
+                    "role": "user",
+                    "content": [
+                        {"type": "text", "text": prompt},
+                        {"type": "image_url", "image_url": {"url": image, "detail": "low"}}
+                    ]
+                }],
+                "max_tokens": 250,
+            }))
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        let response_json: serde_json::Value = response.json()
+            .await
+            .map_err(|e| AIError::InvalidResponse(e.to_string()))?;
+
+        response_json["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| AIError::InvalidResponse("No content in response".to_string()))
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::RwLock;
+use chrono::{DateTime, Utc};
+use tracing::{error, info};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const MAX_VERSIONS: usize = 20;
+
+// Placeholders every prompt can use; anything the caller doesn't know is filled with "unknown"
+pub const COMMON_VARIABLES: &[&str] = &["username", "game", "world"];
+
+// (key, description, extra placeholders, default text)
+const BUILTIN_PROMPTS: &[(&str, &str, &[&str], &str)] = &[
+    (
+        "redeem.ask_ai",
+        "Ask AI redeem (playful); the viewer's question is appended",
+        &[],
+        "You are an entertaining chatbot. Your owner is the cute and funny catgirl named kittyn. \
+         Answer questions humorously and in a playful manner. \
+         We live on Twitch in a cozy but high-tech corner of the metaverse. \
+         Be friendly and love the chat who asks you these questions. \
+         Treat chat like they were your own children. ",
+    ),
+    (
+        "redeem.serious_ai",
+        "Get AI answer redeem; the viewer's question is appended",
+        &[],
+        "You are a knowledgeable AI assistant. Provide a concise, accurate, and informative answer \
+         to the following question. Your response should be clear and fit within a single chat message. ",
+    ),
+    (
+        "redeem.grok",
+        "Ask Grok redeem system prompt",
+        &[],
+        "You are Grok, an AI assistant who can access real-time data from the web and X. \
+         Provide clear, informative, and occasionally witty responses based on current information.",
+    ),
+    (
+        "redeem.world_react",
+        "AI reacts to the world redeem; sent with a screenshot of the stream",
+        &["request"],
+        "You are a playful chatbot watching kittyn's stream with the chat. This is a screenshot of \
+         what's on stream right now ({game}, world: {world}). {username} asked you to react to it: {request} \
+         Describe or riff on what you see in one or two short, funny sentences that fit in a chat message. \
+         Only mention things you can actually see.",
+    ),
+    (
+        "command.vrc",
+        "!vrc group invite message",
+        &["link"],
+        "Generate a friendly and inviting message to encourage Twitch viewers to join our VRChat community group. \
+         Feel free to mention: VR, technology, cute & funny anime, catgirls, foxgirls, \
+         catboys, foxboys, 3D art or living in the matrix. \
+         Don't use the word viewers.  If anything, say chatters or everyone. Good vibes. Good vibes. \
+         The message should be brief (1-2 sentences) and include the following VRChat group link: {link}. \
+         Make sure the tone is casual and welcoming.",
+    ),
+    (
+        "command.discord",
+        "!discord invite message",
+        &["link"],
+        "Generate a friendly and inviting message to encourage Twitch viewers to join our Discord community. \
+         Feel free to mention: VR, technology, cute & funny anime, catgirls, foxgirls, \
+         catboys, foxboys, 3D art or living in the matrix. \
+         Don't use the word viewers.  If anything, say chatters or everyone. Good vibes. Good vibes. \
+         The message should be brief (1-2 sentences) and include the following Discord link: {link}. \
+         Make sure the tone is casual and welcoming.",
+    ),
+    (
+        "chat.faq",
+        "Picks the FAQ entry that answers a chat question; must reply with a number",
+        &["faq", "question"],
+        "You match questions from Twitch chat to a list of frequently asked questions. \
+         Here is the list, numbered:\n{faq}\n\
+         {username} asked: {question}\n\
+         If one of the entries clearly answers this question, reply with only its number. \
+         If none does, or it isn't really a question, reply with 0. Never reply with anything else.",
+    ),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PromptVersion {
+    pub text: String,
+    pub saved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PromptInfo {
+    pub key: String,
+    pub description: String,
+    pub variables: Vec<String>,
+    pub default_text: String,
+    pub text: String,
+    // Oldest first; the last one is the text in use
+    pub versions: Vec<PromptVersion>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PromptFile {
+    prompts: HashMap<String, Vec<PromptVersion>>,
+}
+
+struct PromptStore {
+    path: String,
+    file: PromptFile,
+    world: Option<String>,
+}
+
+// The saved prompt versions and the world they mention, owned by TwitchManager
+pub struct Prompts {
+    store: RwLock<PromptStore>,
+}
+
+fn builtin(key: &str) -> Option<&'static (&'static str, &'static str, &'static [&'static str], &'static str)> {
+    BUILTIN_PROMPTS.iter().find(|(k, _, _, _)| *k == key)
+}
+
+impl Prompts {
+    pub fn new(path: &str) -> Self {
+        Self {
+            store: RwLock::new(PromptStore {
+                path: path.to_string(),
+                file: PromptFile::default(),
+                world: None,
+            }),
+        }
+    }
+
+    pub fn load(path: &str) -> Self {
+        let file = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                error!("Failed to parse {}, using default prompts: {}", path, e);
+                PromptFile::default()
+            }),
+            Err(_) => PromptFile::default(),
+        };
+        let prompts = Self::new(path);
+        prompts.store.write().unwrap().file = file;
+        prompts
+    }
+
+    // Kept up to date by the VRChat manager so prompts can mention the world
+    pub fn set_world(&self, world: Option<String>) {
+        self.store.write().unwrap().world = world;
+    }
+
+    pub fn current_world(&self) -> Option<String> {
+        self.store.read().unwrap().world.clone()
+    }
+
+    pub fn get(&self, key: &str) -> String {
+        let store = self.store.read().unwrap();
+        match store.file.prompts.get(key).and_then(|versions| versions.last()) {
+            Some(version) => version.text.clone(),
+            None => builtin(key).map(|(_, _, _, text)| text.to_string()).unwrap_or_default(),
+        }
+    }
+
+    pub fn list(&self) -> Vec<PromptInfo> {
+        let store = self.store.read().unwrap();
+        BUILTIN_PROMPTS.iter()
+            .map(|(key, description, extra, default_text)| {
+                let versions = store.file.prompts.get(*key).cloned().unwrap_or_default();
+                PromptInfo {
+                    key: key.to_string(),
+                    description: description.to_string(),
+                    variables: COMMON_VARIABLES.iter().chain(extra.iter()).map(|v| v.to_string()).collect(),
+                    default_text: default_text.to_string(),
+                    text: versions.last().map_or_else(|| default_text.to_string(), |v| v.text.clone()),
+                    versions,
+                }
+            })
+            .collect()
+    }
+
+    // Fills {placeholders}; the world comes from the store and unknown common values become "unknown"
+    pub fn render_text(&self, template: &str, vars: &[(&str, &str)]) -> String {
+        let world = self.current_world();
+        let mut text = vars.iter().fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        });
+        if let Some(world) = world {
+            text = text.replace("{world}", &world);
+        }
+        COMMON_VARIABLES.iter().fold(text, |text, name| text.replace(&format!("{{{}}}", name), "unknown"))
+    }
+
+    pub fn render(&self, key: &str, vars: &[(&str, &str)]) -> String {
+        self.render_text(&self.get(key), vars)
+    }
+
+    // Saves the text as the newest version of the prompt
+    pub fn save(&self, key: &str, text: &str) -> Result<(), String> {
+        if builtin(key).is_none() {
+            return Err(format!("Unknown prompt {}", key));
+        }
+        if text.trim().is_empty() {
+            return Err("Prompt can't be empty".to_string());
+        }
+
+        let mut store = self.store.write().unwrap();
+        let versions = store.file.prompts.entry(key.to_string()).or_default();
+        if versions.last().map_or(false, |v| v.text == text) {
+            return Ok(());
+        }
+        versions.push(PromptVersion {
+            text: text.to_string(),
+            saved_at: Utc::now(),
+        });
+        if versions.len() > MAX_VERSIONS {
+            let excess = versions.len() - MAX_VERSIONS;
+            versions.drain(..excess);
+        }
+        info!("Saved new version of prompt {}", key);
+        write_file(&store)
+    }
+
+    // Re-saves an older version so the history stays linear
+    pub fn revert(&self, key: &str, version: usize) -> Result<(), String> {
+        let text = {
+            let store = self.store.read().unwrap();
+            store.file.prompts.get(key)
+                .and_then(|versions| versions.get(version))
+                .map(|v| v.text.clone())
+                .ok_or_else(|| format!("{} has no version {}", key, version))?
+        };
+        self.save(key, &text)
+    }
+}
+
+fn write_file(store: &PromptStore) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&store.file).map_err(|e| e.to_string())?;
+    fs::write(&store.path, json).map_err(|e| {
+        error!("Failed to save prompts to {}: {}", store.path, e);
+        format!("Failed to save {}", store.path)
+    })
+}
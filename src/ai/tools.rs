@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+
+// How many times a model may call tools before it has to answer
+pub const MAX_TOOL_ROUNDS: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolDefinition {
+    pub name: String,
+    pub description: String,
+    pub input_schema: Value,
+}
+
+// Whitelisted bot actions a model can call while it writes a response. Errors are handed
+// back to the model as the tool result rather than failing the whole response.
+#[async_trait]
+pub trait ToolExecutor: Send + Sync {
+    fn definitions(&self) -> Vec<ToolDefinition>;
+    async fn call(&self, name: &str, input: &Value) -> Result<String, String>;
+}
@@ -0,0 +1,67 @@
+use super::client::AIClient;
+use super::models::AIError;
+
+// VRChat's chatbox cuts messages off at 144 characters
+pub const CHATBOX_MAX_CHARS: usize = 144;
+const CHAT_MAX_CHARS: usize = 450;
+
+#[derive(Debug, Clone)]
+pub struct Translation {
+    pub source_language: String,
+    pub target_language: String,
+    pub text: String,
+}
+
+impl Translation {
+    pub fn chat_message(&self) -> String {
+        truncate_chars(&format!("[{} → {}] {}", self.source_language, self.target_language, self.text), CHAT_MAX_CHARS)
+    }
+
+    pub fn chatbox_message(&self) -> String {
+        truncate_chars(&self.text, CHATBOX_MAX_CHARS)
+    }
+}
+
+// Translates text into the target language, detecting the source language along the way
+pub async fn translate(
+    ai_client: &AIClient,
+    text: &str,
+    target_language: &str,
+    max_input_chars: usize,
+) -> Result<Translation, AIError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AIError::InvalidResponse("Nothing to translate".to_string()));
+    }
+    if text.chars().count() > max_input_chars {
+        return Err(AIError::InvalidResponse(format!("Message is too long to translate (max {} characters)", max_input_chars)));
+    }
+
+    let prompt = format!(
+        "Detect the language of the message below and translate it into {}. \
+         Reply with exactly one line in the form LANGUAGE|TRANSLATION, where LANGUAGE is the English name \
+         of the detected source language and TRANSLATION is the translated message. \
+         Keep the tone, slang and emotes of the original and add nothing else.\n\nMessage: {}",
+        target_language, text
+    );
+
+    let response = ai_client.generate_response_without_history(&prompt).await?;
+    let (source_language, translated) = response
+        .trim()
+        .split_once('|')
+        .ok_or_else(|| AIError::ParseError(format!("Unexpected translation response: {}", response)))?;
+
+    Ok(Translation {
+        source_language: source_language.trim().to_string(),
+        target_language: target_language.to_string(),
+        text: translated.trim().replace('\n', " "),
+    })
+}
+
+pub fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", truncated.trim_end())
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use super::client::AIClient;
+use super::models::AIError;
+
+// Generated answers are checked more strictly than chat guesses
+const FACT_CHECK_SIMILARITY: f64 = 0.92;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriviaQuestion {
+    pub topic: String,
+    pub question: String,
+    pub answer: String,
+}
+
+// Lowercase, no punctuation or leading article, so "The Legend of Zelda!" matches "legend of zelda"
+pub fn normalize_answer(text: &str) -> String {
+    let cleaned: String = text
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let words: Vec<&str> = cleaned.split_whitespace().collect();
+    match words.split_first() {
+        Some((first, rest)) if !rest.is_empty() && matches!(*first, "the" | "a" | "an") => rest.join(" "),
+        _ => words.join(" "),
+    }
+}
+
+pub fn is_correct(guess: &str, answer: &str, min_similarity: f64) -> bool {
+    let guess = normalize_answer(guess);
+    let answer = normalize_answer(answer);
+    if guess.is_empty() || answer.is_empty() {
+        return false;
+    }
+    guess == answer || strsim::jaro_winkler(&guess, &answer) >= min_similarity
+}
+
+// Asks for a question about the topic, then has the model answer it blind and only keeps
+// the question if both answers agree
+pub async fn generate_question(ai_client: &AIClient, topic: &str, avoid: &[String]) -> Result<TriviaQuestion, AIError> {
+    let avoid_list = if avoid.is_empty() {
+        String::new()
+    } else {
+        format!(" Don't reuse any of these questions: {}", avoid.join(" / "))
+    };
+    let prompt = format!(
+        "Write one fun trivia question about {} for a Twitch chat. The answer must be a well-known, \
+         verifiable fact of one to four words. Reply with exactly one line in the form QUESTION|ANSWER \
+         and nothing else.{}",
+        topic, avoid_list
+    );
+
+    let response = ai_client.generate_response_without_history(&prompt).await?;
+    let (question, answer) = response
+        .trim()
+        .split_once('|')
+        .ok_or_else(|| AIError::ParseError(format!("Unexpected trivia response: {}", response)))?;
+    let question = question.trim().replace('\n', " ");
+    let answer = answer.trim().to_string();
+    if question.is_empty() || answer.is_empty() {
+        return Err(AIError::InvalidResponse("Empty trivia question or answer".to_string()));
+    }
+
+    let check_prompt = format!(
+        "Answer this trivia question in as few words as possible. Reply with only the answer, \
+         or UNKNOWN if you aren't sure.\n\nQuestion: {}",
+        question
+    );
+    let check = ai_client.generate_response_without_history(&check_prompt).await?;
+    if !is_correct(check.trim(), &answer, FACT_CHECK_SIMILARITY) {
+        return Err(AIError::InvalidResponse(format!(
+            "Couldn't verify the answer to \"{}\" ({} vs {})",
+            question, answer, check.trim()
+        )));
+    }
+
+    Ok(TriviaQuestion {
+        topic: topic.to_string(),
+        question,
+        answer,
+    })
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
-use log::{debug, error};
+use tracing::{debug, error};
 use std::error::Error;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
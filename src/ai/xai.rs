@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use super::models::{AIProvider, AIError};
 use reqwest::Client;
 use serde_json::json;
-use log::{debug, error};
+use tracing::{debug, error};
 
 pub struct XAIProvider {
     api_key: String,
@@ -72,13 +72,10 @@ impl XAIProvider {
             .ok_or_else(|| AIError::InvalidResponse("No content in response".to_string()))
     }
 
-    pub async fn generate_grok_response(&self, prompt: &str) -> Result<String, AIError> {
-        const SYSTEM_PROMPT: &str = "You are Grok, an AI assistant who can access real-time data from the web and X. \
-                                   Provide clear, informative, and occasionally witty responses based on current information.";
-
+    pub async fn generate_grok_response(&self, prompt: &str, system_prompt: &str) -> Result<String, AIError> {
         self.generate_response_with_model(
             prompt,
-            Some(SYSTEM_PROMPT),
+            Some(system_prompt),
             Some(0.7)
         ).await
     }
@@ -0,0 +1,161 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{debug, error, warn};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::{sleep, Duration, Instant};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    // Keep sending; receivers that fall behind skip the oldest messages
+    DropOldest,
+    // Wait for receivers to catch up (up to block_timeout_ms) before sending
+    Block,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BroadcastChannelConfig {
+    pub capacity: usize,
+    pub overflow_policy: OverflowPolicy,
+    pub block_timeout_ms: u64,
+}
+
+impl BroadcastChannelConfig {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            overflow_policy: OverflowPolicy::DropOldest,
+            block_timeout_ms: 5000,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ChannelMetrics {
+    pub sent: AtomicU64,
+    pub lag_events: AtomicU64,
+    pub lagged_messages: AtomicU64,
+    pub blocked_sends: AtomicU64,
+    pub block_timeouts: AtomicU64,
+}
+
+impl ChannelMetrics {
+    pub fn snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "sent": self.sent.load(Ordering::Relaxed),
+            "lag_events": self.lag_events.load(Ordering::Relaxed),
+            "lagged_messages": self.lagged_messages.load(Ordering::Relaxed),
+            "blocked_sends": self.blocked_sends.load(Ordering::Relaxed),
+            "block_timeouts": self.block_timeouts.load(Ordering::Relaxed),
+        })
+    }
+}
+
+pub struct MonitoredSender<T> {
+    name: String,
+    sender: broadcast::Sender<T>,
+    config: BroadcastChannelConfig,
+    metrics: Arc<ChannelMetrics>,
+}
+
+impl<T: Clone> Clone for MonitoredSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            sender: self.sender.clone(),
+            config: self.config.clone(),
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+impl<T: Clone> MonitoredSender<T> {
+    pub fn new(name: &str, config: &BroadcastChannelConfig) -> Self {
+        let (sender, _) = broadcast::channel(config.capacity.max(1));
+        Self {
+            name: name.to_string(),
+            sender,
+            config: config.clone(),
+            metrics: Arc::new(ChannelMetrics::default()),
+        }
+    }
+
+    pub fn subscribe(&self) -> MonitoredReceiver<T> {
+        MonitoredReceiver {
+            name: self.name.clone(),
+            receiver: self.sender.subscribe(),
+            metrics: self.metrics.clone(),
+        }
+    }
+
+    // The channel's name and counters, for the dashboard
+    pub fn metrics_snapshot(&self) -> (String, serde_json::Value) {
+        (self.name.clone(), self.metrics.snapshot())
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    // Returns the number of receivers the value was delivered to
+    pub async fn send(&self, value: T) -> usize {
+        if self.sender.len() >= self.config.capacity {
+            match self.config.overflow_policy {
+                OverflowPolicy::DropOldest => {
+                    debug!("Broadcast channel '{}' is full, oldest message will be dropped for slow receivers", self.name);
+                }
+                OverflowPolicy::Block => self.wait_for_capacity().await,
+            }
+        }
+
+        match self.sender.send(value) {
+            Ok(receivers) => {
+                self.metrics.sent.fetch_add(1, Ordering::Relaxed);
+                receivers
+            }
+            Err(_) => {
+                debug!("No receivers for broadcast channel '{}'", self.name);
+                0
+            }
+        }
+    }
+
+    async fn wait_for_capacity(&self) {
+        self.metrics.blocked_sends.fetch_add(1, Ordering::Relaxed);
+        let deadline = Instant::now() + Duration::from_millis(self.config.block_timeout_ms);
+
+        while self.sender.len() >= self.config.capacity {
+            if Instant::now() >= deadline {
+                self.metrics.block_timeouts.fetch_add(1, Ordering::Relaxed);
+                error!("Broadcast channel '{}' still full after {}ms, sending anyway", self.name, self.config.block_timeout_ms);
+                return;
+            }
+            sleep(Duration::from_millis(5)).await;
+        }
+    }
+}
+
+pub struct MonitoredReceiver<T> {
+    name: String,
+    receiver: broadcast::Receiver<T>,
+    metrics: Arc<ChannelMetrics>,
+}
+
+impl<T: Clone> MonitoredReceiver<T> {
+    // Returns None once the channel is closed; lag is recorded and skipped instead of ending the loop
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(value) => return Some(value),
+                Err(RecvError::Lagged(skipped)) => {
+                    self.metrics.lag_events.fetch_add(1, Ordering::Relaxed);
+                    self.metrics.lagged_messages.fetch_add(skipped, Ordering::Relaxed);
+                    warn!("Receiver on broadcast channel '{}' lagged and skipped {} messages", self.name, skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
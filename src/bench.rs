@@ -0,0 +1,312 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::task::JoinHandle;
+use twitch_irc::message::{IRCMessage, ServerMessage};
+use crate::config::Config;
+use crate::obs::OBSManager;
+use crate::output_filter::OutputFilter;
+use crate::paths;
+use crate::storage::StorageClient;
+use crate::tasks::TaskRegistry;
+use crate::twitch::irc::{KeywordTriggerManager, MessageHandler, TwitchIRCManager};
+use crate::twitch::models::{Redemption, RedemptionStatus};
+use crate::twitch::redeems::RedeemManager;
+use crate::twitch::TwitchManager;
+use crate::web_ui::websocket::DashboardState;
+
+// Load test for the chat and redemption pipeline, built with --features bench:
+//
+//     mewbot bench --messages-per-sec 50 --redemptions-per-min 30 --duration-secs 60
+//
+// Synthetic chat goes through the real MessageHandler and made-up redemptions through the real
+// RedeemManager. Twitch, OBS and VRChat are stand-ins: nothing logs in, API calls fail straight
+// away, and chat the bot would send is counted instead of sent.
+
+const BENCH_CHANNEL: &str = "benchchannel";
+// How long to wait for work still running when the load stops
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+const CHAT_LINES: &[&str] = &[
+    "hello chat",
+    "that was amazing LUL",
+    "gg",
+    "what world is this?",
+    "how long have you been streaming today",
+    "ok that jump scare got me 😱",
+];
+// Commands that run without Twitch, OBS or VRChat, plus a couple that fail fast on the API
+const COMMAND_LINES: &[&str] = &["!ping", "!calc 6*7", "!uptime", "!lurk", "!commands", "!isitfriday", "!followage"];
+const REWARDS: &[&str] = &["toss pillo", "snowball", "cream pie", "gib cookie"];
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub messages_per_sec: u32,
+    pub redemptions_per_min: u32,
+    pub duration_secs: u64,
+    // Distinct synthetic chatters; spreads messages over the handler's workers like real chat
+    pub chatters: u32,
+    // Share of chat messages that are commands, from 0 to 1
+    pub command_share: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            messages_per_sec: 20,
+            redemptions_per_min: 30,
+            duration_secs: 30,
+            chatters: 200,
+            command_share: 0.2,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LatencyReport {
+    pub name: String,
+    pub count: usize,
+    pub errors: usize,
+    pub per_sec: f64,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencyReport {
+    fn new(name: &str, mut samples: Vec<Duration>, errors: usize, elapsed: Duration) -> Self {
+        samples.sort();
+        // Nearest-rank percentile
+        let percentile = |q: f64| {
+            if samples.is_empty() {
+                return Duration::ZERO;
+            }
+            let rank = ((samples.len() as f64) * q).ceil().max(1.0) as usize;
+            samples[rank.min(samples.len()) - 1]
+        };
+        Self {
+            name: name.to_string(),
+            count: samples.len(),
+            errors,
+            per_sec: samples.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+            max: samples.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub elapsed: Duration,
+    pub messages: LatencyReport,
+    pub redemptions: LatencyReport,
+    // Chat messages the bot tried to send
+    pub bot_messages: u64,
+}
+
+// Latencies are measured from when the item was generated, so time spent queued behind a busy worker counts
+#[derive(Default)]
+struct Samples {
+    latencies: Vec<Duration>,
+    errors: usize,
+}
+
+struct Pipeline {
+    twitch_manager: Arc<TwitchManager>,
+    message_handler: Arc<MessageHandler>,
+    // Messages the bot tried to send, which the IRC manager counts and drops
+    bot_messages: Arc<AtomicU64>,
+}
+
+async fn build_pipeline(config: Config, storage_path: &std::path::Path) -> Result<Pipeline, Box<dyn std::error::Error + Send + Sync>> {
+    let mut config = config;
+    config.twitch_channel_to_join = Some(BENCH_CHANNEL.to_string());
+    let shared_config = Arc::new(RwLock::new(config.clone()));
+    let config = Arc::new(config);
+
+    let storage = Arc::new(RwLock::new(StorageClient::new(storage_path)?));
+    let (websocket_tx, mut websocket_rx) = mpsc::unbounded_channel();
+    // Nobody watches the dashboard during a bench
+    tokio::spawn(async move { while websocket_rx.recv().await.is_some() {} });
+
+    let dashboard_state = Arc::new(RwLock::new(DashboardState::default()));
+    let tasks = Arc::new(TaskRegistry::new());
+    let bot_messages = Arc::new(AtomicU64::new(0));
+    let irc_manager = Arc::new(TwitchIRCManager::new(
+        websocket_tx.clone(),
+        Arc::new(RwLock::new(config.social_links.clone())),
+        dashboard_state,
+        config.clone(),
+        tasks.clone(),
+        Arc::new(OutputFilter::new(&config.output_filter)),
+    ).capture_output(bot_messages.clone()));
+    let twitch_manager = Arc::new(TwitchManager::offline(config, storage.clone(), irc_manager, tasks));
+
+    let redeem_manager = RedeemManager::new(
+        twitch_manager.clone(),
+        Arc::new(crate::ai::AIClient::new(None, None, None, None, None, None)),
+    );
+    *twitch_manager.redeem_manager.write().await = Some(redeem_manager);
+
+    let message_handler = Arc::new(MessageHandler::new(
+        shared_config,
+        twitch_manager.clone(),
+        storage,
+        websocket_tx.clone(),
+        Arc::new(Mutex::new(None)),
+        None,
+        None,
+        Arc::new(OBSManager::new(websocket_tx, twitch_manager.connections.clone())),
+        Arc::new(KeywordTriggerManager::new(&paths::resolve(paths::KEYWORD_TRIGGERS))),
+    ));
+
+    Ok(Pipeline { twitch_manager, message_handler, bot_messages })
+}
+
+// A PRIVMSG as Twitch would send it, parsed by the same code as real chat
+fn chat_message(chatter: u32, text: &str, subscriber: bool) -> Option<ServerMessage> {
+    let raw = format!(
+        "@badge-info=;badges={badges};color=#9146FF;display-name=Chatter{n};emotes=;first-msg=0;flags=;\
+         id={id};mod=0;returning-chatter=0;room-id=1;subscriber={sub};tmi-sent-ts={ts};turbo=0;\
+         user-id={user_id};user-type= :chatter{n}!chatter{n}@chatter{n}.tmi.twitch.tv PRIVMSG #{channel} :{text}",
+        badges = if subscriber { "subscriber/1" } else { "" },
+        n = chatter,
+        id = uuid::Uuid::new_v4(),
+        sub = subscriber as u8,
+        ts = chrono::Utc::now().timestamp_millis(),
+        user_id = 100_000 + chatter,
+        channel = BENCH_CHANNEL,
+        text = text,
+    );
+    ServerMessage::try_from(IRCMessage::parse(&raw).ok()?).ok()
+}
+
+fn redemption(number: u64, chatter: u32, reward: &str) -> Redemption {
+    Redemption {
+        id: format!("bench-{}", number),
+        broadcaster_id: "1".to_string(),
+        user_id: (100_000 + chatter).to_string(),
+        user_name: format!("Chatter{}", chatter),
+        reward_id: String::new(),
+        reward_title: reward.to_string(),
+        user_input: Some("hello from the bench".to_string()),
+        status: RedemptionStatus::Unfulfilled,
+    }
+}
+
+// Calls `spawn_one` at `per_sec` until the deadline and returns the spawned work
+async fn generate<F>(per_sec: f64, deadline: Instant, mut spawn_one: F) -> Vec<JoinHandle<()>>
+where
+    F: FnMut(u64) -> JoinHandle<()>,
+{
+    let mut handles = Vec::new();
+    if per_sec <= 0.0 {
+        return handles;
+    }
+    let mut ticker = tokio::time::interval(Duration::from_secs_f64(1.0 / per_sec));
+    let mut number = 0;
+    while Instant::now() < deadline {
+        ticker.tick().await;
+        handles.push(spawn_one(number));
+        number += 1;
+    }
+    handles
+}
+
+pub async fn run(config: Config, options: BenchOptions) -> Result<BenchReport, Box<dyn std::error::Error + Send + Sync>> {
+    let storage_path = std::env::temp_dir().join(format!("mewbot_bench_{}.db", std::process::id()));
+    let pipeline = build_pipeline(config, &storage_path).await?;
+
+    let chatters = options.chatters.max(1);
+    let messages = Arc::new(std::sync::Mutex::new(Samples::default()));
+    let redemptions = Arc::new(std::sync::Mutex::new(Samples::default()));
+    let started = Instant::now();
+    let deadline = started + Duration::from_secs(options.duration_secs);
+
+    let chat_load = {
+        let handler = pipeline.message_handler.clone();
+        let samples = messages.clone();
+        generate(options.messages_per_sec as f64, deadline, move |_| {
+            let (chatter, text, subscriber) = {
+                let mut rng = rand::thread_rng();
+                let lines = if rng.gen_bool(options.command_share.clamp(0.0, 1.0)) { COMMAND_LINES } else { CHAT_LINES };
+                (rng.gen_range(0..chatters), *lines.choose(&mut rng).unwrap_or(&"hi"), rng.gen_bool(0.3))
+            };
+            let handler = handler.clone();
+            let samples = samples.clone();
+            let generated = Instant::now();
+            tokio::spawn(async move {
+                let result = match chat_message(chatter, text, subscriber) {
+                    Some(message) => handler.handle_message(message).await.map_err(|e| e.to_string()),
+                    None => Err("couldn't build a chat message".to_string()),
+                };
+                let mut samples = samples.lock().unwrap();
+                samples.latencies.push(generated.elapsed());
+                if result.is_err() {
+                    samples.errors += 1;
+                }
+            })
+        })
+    };
+
+    let redemption_load = {
+        let twitch_manager = pipeline.twitch_manager.clone();
+        let samples = redemptions.clone();
+        generate(options.redemptions_per_min as f64 / 60.0, deadline, move |number| {
+            let (chatter, reward) = {
+                let mut rng = rand::thread_rng();
+                (rng.gen_range(0..chatters), *REWARDS.choose(&mut rng).unwrap_or(&"snowball"))
+            };
+            let twitch_manager = twitch_manager.clone();
+            let samples = samples.clone();
+            let generated = Instant::now();
+            tokio::spawn(async move {
+                let redemption = redemption(number, chatter, reward);
+                let success = match twitch_manager.get_redeem_manager().read().await.as_ref() {
+                    Some(redeem_manager) => redeem_manager.handle_redemption(&redemption).await.success,
+                    None => false,
+                };
+                let mut samples = samples.lock().unwrap();
+                samples.latencies.push(generated.elapsed());
+                if !success {
+                    samples.errors += 1;
+                }
+            })
+        })
+    };
+
+    let (chat_handles, redemption_handles) = tokio::join!(chat_load, redemption_load);
+    let drain = futures::future::join_all(chat_handles.into_iter().chain(redemption_handles));
+    if tokio::time::timeout(DRAIN_TIMEOUT, drain).await.is_err() {
+        eprintln!("Some work was still running {}s after the load stopped; it isn't counted.", DRAIN_TIMEOUT.as_secs());
+    }
+    let elapsed = started.elapsed();
+
+    let _ = std::fs::remove_file(&storage_path);
+
+    let messages = std::mem::take(&mut *messages.lock().unwrap());
+    let redemptions = std::mem::take(&mut *redemptions.lock().unwrap());
+    Ok(BenchReport {
+        elapsed,
+        messages: LatencyReport::new("chat messages", messages.latencies, messages.errors, elapsed),
+        redemptions: LatencyReport::new("redemptions", redemptions.latencies, redemptions.errors, elapsed),
+        bot_messages: pipeline.bot_messages.load(Ordering::Relaxed),
+    })
+}
+
+pub fn format_report(report: &BenchReport) -> String {
+    let line = |r: &LatencyReport| format!(
+        "{:14} {:>7} done {:>6} errors {:>8.1}/s   p50 {:>8.2?}  p95 {:>8.2?}  p99 {:>8.2?}  max {:>8.2?}",
+        r.name, r.count, r.errors, r.per_sec, r.p50, r.p95, r.p99, r.max
+    );
+    format!(
+        "Ran for {:.1?}\n{}\n{}\nBot chat messages (not sent): {}",
+        report.elapsed, line(&report.messages), line(&report.redemptions), report.bot_messages
+    )
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc, Duration};
 use tokio::sync::RwLock;
 use std::sync::Arc;
-use log::info;
+use tracing::info;
 
 pub struct BotStatus {
     start_time: DateTime<Utc>,
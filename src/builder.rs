@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::config::Config;
+use crate::hooks::{BotEvent, EventHook};
+use crate::twitch::irc::command_system::CommandPlugin;
+use crate::BotClients;
+
+// Which parts of the bot to start. Twitch chat and the message handler always run.
+#[derive(Debug, Clone)]
+pub struct Subsystems {
+    pub web_ui: bool,
+    pub discord: bool,
+    pub vrchat: bool,
+    pub obs: bool,
+    pub eventsub: bool,
+    pub redeems: bool,
+}
+
+impl Default for Subsystems {
+    fn default() -> Self {
+        Self {
+            web_ui: true,
+            discord: true,
+            vrchat: true,
+            obs: true,
+            eventsub: true,
+            redeems: true,
+        }
+    }
+}
+
+impl Subsystems {
+    pub fn twitch_only() -> Self {
+        Self {
+            web_ui: false,
+            discord: false,
+            vrchat: false,
+            obs: false,
+            eventsub: true,
+            redeems: true,
+        }
+    }
+}
+
+// Entry point for embedding the bot in another program:
+//
+//     let bot = MewBotBuilder::new()
+//         .config(config)
+//         .subsystems(Subsystems::twitch_only())
+//         .on_event(|event| println!("{:?}", event))
+//         .plugin(MyCommands)
+//         .build()
+//         .await?;
+//     bot.run().await?;
+pub struct MewBotBuilder {
+    config: Option<Arc<RwLock<Config>>>,
+    subsystems: Subsystems,
+    hooks: Vec<EventHook>,
+    plugins: Vec<Box<dyn CommandPlugin>>,
+}
+
+impl Default for MewBotBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MewBotBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            subsystems: Subsystems::default(),
+            hooks: Vec::new(),
+            plugins: Vec::new(),
+        }
+    }
+
+    // Uses this config instead of loading mewbot.conf
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(Arc::new(RwLock::new(config)));
+        self
+    }
+
+    // Shares a config the caller keeps a handle to, e.g. to edit it while the bot runs
+    pub fn shared_config(mut self, config: Arc<RwLock<Config>>) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn subsystems(mut self, subsystems: Subsystems) -> Self {
+        self.subsystems = subsystems;
+        self
+    }
+
+    pub fn on_event<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&BotEvent) + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    // Adds chat commands of your own; build() fails if one clashes with another command.
+    // Plugins can also be registered or removed while running, with twitch_manager.commands.register_plugin.
+    pub fn plugin<P: CommandPlugin + 'static>(mut self, plugin: P) -> Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    pub async fn build(self) -> Result<MewBot, Box<dyn std::error::Error + Send + Sync>> {
+        let config = match self.config {
+            Some(config) => config,
+            None => Arc::new(RwLock::new(Config::new()?)),
+        };
+
+        let clients = crate::init_with(config.clone(), &self.subsystems).await?;
+        for plugin in &self.plugins {
+            clients.twitch_manager.commands.register_plugin(plugin.as_ref())?;
+        }
+        for hook in self.hooks {
+            clients.twitch_manager.add_event_hook(hook);
+        }
+
+        Ok(MewBot {
+            clients,
+            config,
+            subsystems: self.subsystems,
+        })
+    }
+}
+
+pub struct MewBot {
+    clients: BotClients,
+    config: Arc<RwLock<Config>>,
+    subsystems: Subsystems,
+}
+
+impl MewBot {
+    pub fn clients(&self) -> &BotClients {
+        &self.clients
+    }
+
+    pub fn config(&self) -> Arc<RwLock<Config>> {
+        self.config.clone()
+    }
+
+    // Runs until every handler stops or Ctrl+C is received, then shuts down gracefully
+    pub async fn run(self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        crate::run_with(self.clients, self.config, &self.subsystems).await
+    }
+}
@@ -0,0 +1,527 @@
+use rand::Rng;
+
+// Chat can't do much harm with a parser, but deep nesting could still blow the stack
+// (each level of parentheses takes three: expression, unary and power)
+const MAX_DEPTH: usize = 192;
+const MAX_DICE: u32 = 100;
+const MAX_SIDES: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dimension {
+    Length,
+    Mass,
+    Temperature,
+    Volume,
+    Speed,
+    Time,
+    Data,
+}
+
+// value in the base unit = value * factor + offset (the offset is only for temperatures)
+struct Unit {
+    names: &'static [&'static str],
+    dimension: Dimension,
+    factor: f64,
+    offset: f64,
+}
+
+const fn unit(names: &'static [&'static str], dimension: Dimension, factor: f64) -> Unit {
+    Unit { names, dimension, factor, offset: 0.0 }
+}
+
+const UNITS: &[Unit] = &[
+    unit(&["m", "meter", "meters", "metre", "metres"], Dimension::Length, 1.0),
+    unit(&["km", "kilometer", "kilometers", "kilometre", "kilometres"], Dimension::Length, 1000.0),
+    unit(&["cm", "centimeter", "centimeters", "centimetre", "centimetres"], Dimension::Length, 0.01),
+    unit(&["mm", "millimeter", "millimeters", "millimetre", "millimetres"], Dimension::Length, 0.001),
+    unit(&["mi", "mile", "miles"], Dimension::Length, 1609.344),
+    unit(&["yd", "yard", "yards"], Dimension::Length, 0.9144),
+    unit(&["ft", "foot", "feet"], Dimension::Length, 0.3048),
+    unit(&["in", "inch", "inches"], Dimension::Length, 0.0254),
+    unit(&["kg", "kilogram", "kilograms", "kilo", "kilos"], Dimension::Mass, 1.0),
+    unit(&["g", "gram", "grams"], Dimension::Mass, 0.001),
+    unit(&["mg", "milligram", "milligrams"], Dimension::Mass, 0.000001),
+    unit(&["t", "tonne", "tonnes"], Dimension::Mass, 1000.0),
+    unit(&["lb", "lbs", "pound", "pounds"], Dimension::Mass, 0.45359237),
+    unit(&["oz", "ounce", "ounces"], Dimension::Mass, 0.028349523125),
+    unit(&["st", "stone", "stones"], Dimension::Mass, 6.35029318),
+    unit(&["k", "kelvin"], Dimension::Temperature, 1.0),
+    Unit { names: &["c", "°c", "celsius"], dimension: Dimension::Temperature, factor: 1.0, offset: 273.15 },
+    Unit { names: &["f", "°f", "fahrenheit"], dimension: Dimension::Temperature, factor: 5.0 / 9.0, offset: 459.67 * 5.0 / 9.0 },
+    unit(&["l", "liter", "liters", "litre", "litres"], Dimension::Volume, 1.0),
+    unit(&["ml", "milliliter", "milliliters", "millilitre", "millilitres"], Dimension::Volume, 0.001),
+    unit(&["gal", "gallon", "gallons"], Dimension::Volume, 3.785411784),
+    unit(&["qt", "quart", "quarts"], Dimension::Volume, 0.946352946),
+    unit(&["pt", "pint", "pints"], Dimension::Volume, 0.473176473),
+    unit(&["cup", "cups"], Dimension::Volume, 0.2365882365),
+    unit(&["floz"], Dimension::Volume, 0.0295735295625),
+    unit(&["tbsp"], Dimension::Volume, 0.01478676478125),
+    unit(&["tsp"], Dimension::Volume, 0.00492892159375),
+    unit(&["m/s"], Dimension::Speed, 1.0),
+    unit(&["km/h", "kph", "kmh"], Dimension::Speed, 1.0 / 3.6),
+    unit(&["mph"], Dimension::Speed, 0.44704),
+    unit(&["kn", "knot", "knots"], Dimension::Speed, 0.514444),
+    unit(&["s", "sec", "secs", "second", "seconds"], Dimension::Time, 1.0),
+    unit(&["min", "mins", "minute", "minutes"], Dimension::Time, 60.0),
+    unit(&["h", "hr", "hrs", "hour", "hours"], Dimension::Time, 3600.0),
+    unit(&["day", "days"], Dimension::Time, 86400.0),
+    unit(&["week", "weeks"], Dimension::Time, 604800.0),
+    unit(&["yr", "year", "years"], Dimension::Time, 31557600.0),
+    unit(&["b", "byte", "bytes"], Dimension::Data, 1.0),
+    unit(&["kb"], Dimension::Data, 1e3),
+    unit(&["mb"], Dimension::Data, 1e6),
+    unit(&["gb"], Dimension::Data, 1e9),
+    unit(&["tb"], Dimension::Data, 1e12),
+    unit(&["kib"], Dimension::Data, 1024.0),
+    unit(&["mib"], Dimension::Data, 1048576.0),
+    unit(&["gib"], Dimension::Data, 1073741824.0),
+];
+
+fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.trim().to_lowercase();
+    UNITS.iter().find(|unit| unit.names.contains(&name.as_str()))
+}
+
+// "5mi in km", "(3+2) ft to m"; None when the input isn't shaped like a conversion
+fn split_conversion(input: &str) -> Option<(&str, &'static Unit, &'static Unit)> {
+    // Matched on the input's own bytes: lowercasing can change the length of non-ASCII text, so
+    // offsets into a lowercased copy aren't safe to slice the input with
+    let split = [" in ", " to "].iter()
+        .filter_map(|word| {
+            input.as_bytes().windows(word.len())
+                .rposition(|window| window.eq_ignore_ascii_case(word.as_bytes()))
+                .map(|at| (at, word.len()))
+        })
+        .max()?;
+    let (left, target) = (&input[..split.0], &input[split.0 + split.1..]);
+    let target = find_unit(target)?;
+
+    let left = left.trim_end();
+    let name_start = left.char_indices().rev()
+        .take_while(|(_, c)| c.is_alphabetic() || *c == '/' || *c == '°')
+        .last()
+        .map(|(i, _)| i)?;
+    let source = find_unit(&left[name_start..])?;
+    Some((&left[..name_start], source, target))
+}
+
+pub fn calculate(input: &str) -> Result<String, String> {
+    if let Some((expression, source, target)) = split_conversion(input) {
+        if source.dimension != target.dimension {
+            return Err(format!("Can't convert {} to {}", source.names[0], target.names[0]));
+        }
+        let value = evaluate(expression)?;
+        let converted = (value * source.factor + source.offset - target.offset) / target.factor;
+        return Ok(format!("{} {} = {} {}", format_number(value), source.names[0], format_number(converted), target.names[0]));
+    }
+    evaluate(input).map(|value| format!("{} = {}", input.trim(), format_number(value)))
+}
+
+pub fn evaluate(input: &str) -> Result<f64, String> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err("There's nothing to calculate".to_string());
+    }
+    let mut parser = Parser { tokens, position: 0, depth: 0 };
+    let value = parser.expression()?;
+    if parser.position < parser.tokens.len() {
+        return Err("Unexpected input after the expression".to_string());
+    }
+    let value = value.resolve();
+    if value.is_nan() {
+        Err("The result is undefined".to_string())
+    } else if value.is_infinite() {
+        Err("The result is too big".to_string())
+    } else {
+        Ok(value)
+    }
+}
+
+// Whole numbers without a decimal point, everything else to at most 6 decimals
+pub fn format_number(value: f64) -> String {
+    if value.abs() >= 1e15 || (value != 0.0 && value.abs() < 1e-6) {
+        return format!("{:e}", value);
+    }
+    if value.fract() == 0.0 {
+        return format!("{}", value as i64);
+    }
+    let formatted = format!("{:.6}", value);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Power,
+    Percent,
+    LeftParen,
+    RightParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '0'..='9' | '.' => {
+                let mut number = String::from(ch);
+                while let Some(&next) = chars.peek() {
+                    if next.is_ascii_digit() || next == '.' {
+                        number.push(next);
+                        chars.next();
+                    } else if next == ',' || next == '_' {
+                        // Thousands separators like 1,000,000
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(number.parse().map_err(|_| format!("Invalid number: {}", number))?));
+            }
+            c if c.is_alphabetic() => {
+                let mut name = String::from(c);
+                while let Some(&next) = chars.peek() {
+                    if next.is_alphanumeric() {
+                        name.push(next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                // "20% of 150" is just multiplication
+                if name.eq_ignore_ascii_case("of") {
+                    tokens.push(Token::Multiply);
+                } else {
+                    tokens.push(Token::Name(name.to_lowercase()));
+                }
+            }
+            '+' => tokens.push(Token::Plus),
+            '-' | '−' => tokens.push(Token::Minus),
+            '*' | '×' => tokens.push(Token::Multiply),
+            '/' | '÷' => tokens.push(Token::Divide),
+            '^' => tokens.push(Token::Power),
+            '%' => tokens.push(Token::Percent),
+            '(' => tokens.push(Token::LeftParen),
+            ')' => tokens.push(Token::RightParen),
+            c if c.is_whitespace() => {}
+            _ => return Err(format!("Invalid character: {}", ch)),
+        }
+    }
+    Ok(tokens)
+}
+
+// A number that remembers it was written as a percentage, so "80 + 10%" can mean 88
+#[derive(Debug, Clone, Copy)]
+struct Value {
+    number: f64,
+    percent: bool,
+}
+
+impl Value {
+    fn plain(number: f64) -> Self {
+        Self { number, percent: false }
+    }
+
+    fn resolve(self) -> f64 {
+        if self.percent { self.number / 100.0 } else { self.number }
+    }
+}
+
+// Recursive descent, lowest precedence first: + -, then * /, unary minus, ^ (right associative),
+// then a trailing % and finally numbers, constants, functions and parentheses
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next_is(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Every parser that can recurse goes through here, so "((((", "----" and "2^2^2" are all capped
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("The expression is nested too deeply".to_string());
+        }
+        Ok(())
+    }
+
+    fn expression(&mut self) -> Result<Value, String> {
+        self.enter()?;
+        let mut value = self.term()?;
+        loop {
+            let subtract = if self.next_is(&Token::Plus) {
+                false
+            } else if self.next_is(&Token::Minus) {
+                true
+            } else {
+                break;
+            };
+            let right = self.term()?;
+            let left = value.resolve();
+            // A bare percentage on the right is a percentage of the left
+            let amount = if right.percent { left * right.number / 100.0 } else { right.number };
+            value = Value::plain(if subtract { left - amount } else { left + amount });
+        }
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<Value, String> {
+        let mut value = self.unary()?;
+        loop {
+            let divide = if self.next_is(&Token::Multiply) {
+                false
+            } else if self.next_is(&Token::Divide) {
+                true
+            } else if matches!(self.peek(), Some(Token::LeftParen) | Some(Token::Name(_))) {
+                // Implicit multiplication, like 2(3+4) or 2pi
+                false
+            } else {
+                break;
+            };
+            let right = self.unary()?.resolve();
+            let left = value.resolve();
+            value = Value::plain(if divide {
+                if right == 0.0 {
+                    return Err("Division by zero".to_string());
+                }
+                left / right
+            } else {
+                left * right
+            });
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<Value, String> {
+        self.enter()?;
+        let value = if self.next_is(&Token::Minus) {
+            let value = self.unary()?;
+            Value { number: -value.number, percent: value.percent }
+        } else if self.next_is(&Token::Plus) {
+            self.unary()?
+        } else {
+            self.power()?
+        };
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn power(&mut self) -> Result<Value, String> {
+        self.enter()?;
+        let mut value = self.postfix()?;
+        if self.next_is(&Token::Power) {
+            let exponent = self.unary()?.resolve();
+            value = Value::plain(value.resolve().powf(exponent));
+        }
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn postfix(&mut self) -> Result<Value, String> {
+        let value = self.primary()?;
+        if self.next_is(&Token::Percent) {
+            return Ok(Value { number: value.resolve(), percent: true });
+        }
+        Ok(value)
+    }
+
+    fn primary(&mut self) -> Result<Value, String> {
+        let token = self.peek().cloned().ok_or("The expression ends too early")?;
+        self.position += 1;
+        match token {
+            Token::Number(number) => Ok(Value::plain(number)),
+            Token::LeftParen => {
+                let value = self.expression()?;
+                if !self.next_is(&Token::RightParen) {
+                    return Err("Missing a closing parenthesis".to_string());
+                }
+                Ok(value)
+            }
+            Token::Name(name) => match constant(&name) {
+                Some(value) => Ok(Value::plain(value)),
+                None => {
+                    if !self.next_is(&Token::LeftParen) {
+                        return Err(format!("Unknown name: {}", name));
+                    }
+                    let argument = self.expression()?.resolve();
+                    if !self.next_is(&Token::RightParen) {
+                        return Err("Missing a closing parenthesis".to_string());
+                    }
+                    apply_function(&name, argument).map(Value::plain)
+                }
+            },
+            other => Err(format!("Unexpected {:?}", other)),
+        }
+    }
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" | "π" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        "phi" | "φ" => Some((1.0 + 5.0_f64.sqrt()) / 2.0),
+        "tau" | "τ" => Some(std::f64::consts::TAU),
+        _ => None,
+    }
+}
+
+fn apply_function(name: &str, argument: f64) -> Result<f64, String> {
+    Ok(match name {
+        "sqrt" => argument.sqrt(),
+        "abs" => argument.abs(),
+        "round" => argument.round(),
+        "floor" => argument.floor(),
+        "ceil" => argument.ceil(),
+        "sin" => argument.sin(),
+        "cos" => argument.cos(),
+        "tan" => argument.tan(),
+        "ln" => argument.ln(),
+        "log" => argument.log10(),
+        _ => return Err(format!("Unknown function: {}", name)),
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct DiceRoll {
+    pub sides: u32,
+    pub results: Vec<u32>,
+    pub negative: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct RollResult {
+    pub dice: Vec<DiceRoll>,
+    pub modifier: i64,
+    pub total: i64,
+}
+
+// "3d6+2", "d20", "2d8+1d4-1"; a bare number of sides like "20" rolls one die
+pub fn roll(spec: &str, rng: &mut impl Rng) -> Result<RollResult, String> {
+    let spec: String = spec.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_lowercase();
+    let spec = if spec.is_empty() { "d20".to_string() } else { spec };
+    if spec.chars().all(|c| c.is_ascii_digit()) {
+        return roll(&format!("d{}", spec), rng);
+    }
+
+    // Split into signed terms: "2d8+1d4-1" is +2d8, +1d4 and -1
+    let mut terms = Vec::new();
+    let mut negative = false;
+    let mut start = 0;
+    for (i, c) in spec.char_indices() {
+        if c == '+' || c == '-' {
+            if i > start {
+                terms.push((negative, &spec[start..i]));
+            } else if i > 0 {
+                return Err(format!("Couldn't read the dice \"{}\"", spec));
+            }
+            negative = c == '-';
+            start = i + 1;
+        }
+    }
+    if start >= spec.len() {
+        return Err(format!("Couldn't read the dice \"{}\"", spec));
+    }
+    terms.push((negative, &spec[start..]));
+
+    let mut dice = Vec::new();
+    let mut modifier: i64 = 0;
+    let mut total_dice = 0;
+    for (negative, term) in terms {
+        match term.split_once('d') {
+            Some((count, sides)) => {
+                let count: u32 = if count.is_empty() { 1 } else { count.parse().map_err(|_| format!("Couldn't read \"{}\"", term))? };
+                let sides: u32 = sides.parse().map_err(|_| format!("Couldn't read \"{}\"", term))?;
+                if count == 0 || sides < 2 {
+                    return Err(format!("\"{}\" isn't a die you can roll", term));
+                }
+                total_dice += count;
+                if total_dice > MAX_DICE || sides > MAX_SIDES {
+                    return Err(format!("That's too many dice, the limit is {}d{}", MAX_DICE, MAX_SIDES));
+                }
+                let results = (0..count).map(|_| rng.gen_range(1..=sides)).collect();
+                dice.push(DiceRoll { sides, results, negative });
+            }
+            None => {
+                let value: i64 = term.parse().map_err(|_| format!("Couldn't read \"{}\"", term))?;
+                if value > 1_000_000 {
+                    return Err("That modifier is too big".to_string());
+                }
+                modifier += if negative { -value } else { value };
+            }
+        }
+    }
+    if dice.is_empty() {
+        return Err("There are no dice in that roll, try something like 2d6".to_string());
+    }
+
+    let rolled: i64 = dice.iter()
+        .map(|die| {
+            let sum: i64 = die.results.iter().map(|&result| result as i64).sum();
+            if die.negative { -sum } else { sum }
+        })
+        .sum();
+    Ok(RollResult { dice, modifier, total: rolled + modifier })
+}
+
+pub fn describe_roll(spec: &str, result: &RollResult) -> String {
+    let mut parts = Vec::new();
+    for (i, die) in result.dice.iter().enumerate() {
+        let results: Vec<String> = die.results.iter().map(u32::to_string).collect();
+        let sign = if die.negative { "- " } else if i > 0 { "+ " } else { "" };
+        parts.push(format!("{}[{}]", sign, results.join(", ")));
+    }
+    if result.modifier != 0 {
+        parts.push(format!("{} {}", if result.modifier < 0 { "-" } else { "+" }, result.modifier.abs()));
+    }
+    let spec = match spec.trim() {
+        "" => "d20".to_string(),
+        sides if sides.chars().all(|c| c.is_ascii_digit()) => format!("d{}", sides),
+        spec => spec.to_string(),
+    };
+    format!("🎲 {}: {} = {}", spec, parts.join(" "), result.total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_units() {
+        assert_eq!(calculate("5 km IN m").unwrap(), "5 km = 5000 m");
+    }
+
+    #[test]
+    fn non_ascii_input_does_not_panic() {
+        // 'İ' lowercases to two characters, which used to shift the split offsets
+        assert!(calculate("İ in €").is_err());
+        assert!(calculate("İİİ to m").is_err());
+        assert!(calculate("5 İ in km").is_err());
+    }
+
+    #[test]
+    fn deep_unary_and_power_chains_are_refused() {
+        assert!(evaluate(&format!("{}1", "-".repeat(100_000))).is_err());
+        assert!(evaluate(&vec!["2"; 100_000].join("^")).is_err());
+        assert_eq!(evaluate("--2^2").unwrap(), 4.0);
+    }
+}
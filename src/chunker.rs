@@ -0,0 +1,103 @@
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+
+// Twitch counts characters, not bytes
+pub const TWITCH_MAX_CHARS: usize = 500;
+// Gap between the parts of a long message so they arrive in order and don't trip the rate limit
+pub const CHUNK_DELAY: Duration = Duration::from_millis(500);
+
+// Where the first piece of `text` should end so it's at most `max_chars` long, and where the rest starts.
+// Never cuts inside a grapheme, so emoji and accented letters stay whole. Prefers the end of a sentence
+// in the second half of the piece, then the last space, and only cuts a word that's too long by itself.
+pub fn next_break(text: &str, max_chars: usize) -> (usize, usize) {
+    let mut chars = 0;
+    let mut hard_end = 0;
+    let mut last_space = None;
+    let mut last_sentence = None;
+    let mut previous = "";
+    for (index, grapheme) in text.grapheme_indices(true) {
+        let len = grapheme.chars().count();
+        if chars + len > max_chars {
+            let end = match (last_sentence, last_space) {
+                (Some((end, at)), _) if at * 2 >= max_chars => end,
+                (_, Some(end)) => end,
+                _ if hard_end > 0 => hard_end,
+                // A single grapheme longer than the limit still has to go somewhere
+                _ => grapheme.len(),
+            };
+            let rest = end + text[end..].len() - text[end..].trim_start().len();
+            return (end, rest);
+        }
+        if grapheme.chars().all(char::is_whitespace) && index > 0 {
+            last_space = Some(index);
+            if matches!(previous, "." | "!" | "?") {
+                last_sentence = Some((index, chars));
+            }
+        }
+        chars += len;
+        hard_end = index + grapheme.len();
+        previous = grapheme;
+    }
+    (text.len(), text.len())
+}
+
+fn split(text: &str, max_chars: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let (end, next) = next_break(rest, max_chars.max(1));
+        pieces.push(rest[..end].trim_end().to_string());
+        rest = &rest[next..];
+    }
+    pieces
+}
+
+// Splits text into chat-sized messages. When it takes more than one, each gets a " (2/3)" marker,
+// which counts towards the limit.
+pub fn chunk(text: &str, max_chars: usize) -> Vec<String> {
+    if text.trim().chars().count() <= max_chars {
+        return vec![text.trim().to_string()];
+    }
+    // The marker is wider once there are ten or more pieces, which can change how many there are
+    let mut digits = 1;
+    loop {
+        let marker_chars = 4 + 2 * digits;
+        let pieces = split(text, max_chars.saturating_sub(marker_chars));
+        if pieces.len() < 10usize.pow(digits as u32) {
+            let total = pieces.len();
+            return pieces.into_iter()
+                .enumerate()
+                .map(|(i, piece)| format!("{} ({}/{})", piece, i + 1, total))
+                .collect();
+        }
+        digits += 1;
+    }
+}
+
+pub fn chat_chunks(text: &str) -> Vec<String> {
+    chunk(text, TWITCH_MAX_CHARS)
+}
+
+// The first message and what's left over, e.g. for !continue
+pub fn split_first(text: &str, max_chars: usize) -> (String, Option<String>) {
+    let text = text.trim();
+    let (end, rest) = next_break(text, max_chars);
+    let remainder = text[rest..].trim();
+    (text[..end].trim_end().to_string(), (!remainder.is_empty()).then(|| remainder.to_string()))
+}
+
+// Callers add "..." and " (Use !continue to see more)" to the first part
+const CONTINUE_SUFFIX_CHARS: usize = 31;
+
+// An AI answer as the first message plus the rest kept for !continue
+pub fn split_response(response: String) -> (String, Option<String>) {
+    if response.chars().count() <= TWITCH_MAX_CHARS {
+        return (response, None);
+    }
+    // Replace line breaks with a special marker
+    let processed = response.replace('\n', " ↵ ");
+    match split_first(&processed, TWITCH_MAX_CHARS - CONTINUE_SUFFIX_CHARS) {
+        (first_part, Some(second_part)) => (format!("{}...", first_part), Some(second_part)),
+        (first_part, None) => (first_part, None),
+    }
+}
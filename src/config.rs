@@ -1,12 +1,19 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::sync::Arc;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use log::{error, info, LevelFilter};
+use tracing::{error, info, warn};
+use tracing::level_filters::LevelFilter;
 use reqwest::Client;
 use tokio::sync::RwLock;
 use crate::vrchat::VRChatClient;
+use crate::backpressure::BroadcastChannelConfig;
+use crate::osc::OSCConfig;
+use crate::twitch::models::OBSSourceAction;
+use crate::paths;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SocialLinks {
@@ -32,6 +39,653 @@ pub struct OBSManagerConfig {
     pub instance2: Option<OBSInstanceConfig>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRelayMode {
+    Off,
+    Mentions,
+    Moderators,
+    All,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatRelayConfig {
+    pub mode: ChatRelayMode,
+    pub min_interval_secs: u64,
+    pub play_sound: bool,
+    pub blocked_words: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BroadcastConfig {
+    pub irc: BroadcastChannelConfig,
+    pub dashboard: BroadcastChannelConfig,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MessageWorkerConfig {
+    // Chat is sharded across workers by user, so each user's messages stay in order
+    pub workers: usize,
+    // Cap on concurrently running AI/API-heavy commands
+    pub max_heavy_commands: usize,
+    // Messages a worker can have waiting before reading new chat waits for it
+    #[serde(default = "default_worker_queue_size")]
+    pub queue_size: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdBreakConfig {
+    // Show "Ads: 90s remaining" in the VRChat chatbox during ad breaks
+    pub chatbox_countdown: bool,
+    pub update_interval_secs: u64,
+    // Bool avatar parameter held true for the length of the ad break
+    pub avatar_parameter: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RaidModeConfig {
+    pub enabled: bool,
+    // Raids smaller than this are greeted as usual
+    pub min_viewers: u64,
+    pub duration_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEventType {
+    Follow,
+    Subscribe,
+    Resub,
+    GiftSub,
+    Raid,
+    Cheer,
+    // Amount in whole units of the campaign's currency, {message} is the charity's name
+    CharityDonation,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertAction {
+    // Templates support {user}, {amount}, {tier} and {message} placeholders
+    Chat { template: String },
+    Discord { channel_id: String, template: String },
+    Osc(OSCConfig),
+    ObsSource(OBSSourceAction),
+    // TTS and overlay alerts are pushed to the dashboard websocket for browser sources to play
+    Tts { template: String },
+    Overlay { template: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertRoute {
+    pub event: AlertEventType,
+    // Sub tier to match ("1000", "2000", "3000"); any tier when unset
+    #[serde(default)]
+    pub tier: Option<String>,
+    // Inclusive bucket on the event amount: raid viewers, bits, gifted subs, resub months or donation amount
+    #[serde(default)]
+    pub min_amount: Option<u64>,
+    #[serde(default)]
+    pub max_amount: Option<u64>,
+    pub actions: Vec<AlertAction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RewardAnnouncementConfig {
+    // Announce rewards created outside the bot in chat and Discord; the dashboard is always notified
+    pub enabled: bool,
+    pub discord_channel_id: Option<String>,
+    // How often Twitch is checked for new rewards
+    pub poll_interval_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectionAlertConfig {
+    pub enabled: bool,
+    // Alert when a connection drops this many times within an hour
+    pub flaps_per_hour: u32,
+    pub dashboard: bool,
+    // Discord user to DM about flapping connections
+    pub discord_user_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AvatarChangeConfig {
+    pub enabled: bool,
+    // VRChat sends /avatar/change to its OSC output port
+    pub osc_listen_addr: String,
+    pub announce_in_chat: bool,
+    pub announce_on_dashboard: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IgnoreListConfig {
+    // Logins that never trigger commands or count towards leaderboards, on top of !ignore
+    pub users: Vec<String>,
+    // Ignore users as soon as they're permanently banned
+    pub ignore_banned: bool,
+}
+
+// Streamer.bot-style websocket at /compat, for automation written against Streamer.bot or SAMMI
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CompatApiConfig {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatterDropConfig {
+    pub enabled: bool,
+    pub interval_minutes: u64,
+    // Chance multipliers relative to a regular viewer; the highest matching role applies
+    pub subscriber_weight: f64,
+    pub vip_weight: f64,
+    pub moderator_weight: f64,
+    // Bot-tracked points given to the winner, 0 to only announce
+    pub points: u64,
+    // Bool avatar parameter pulsed on for a few seconds when someone wins
+    pub celebration_parameter: Option<String>,
+    // Supports {user}, {points} and {total} placeholders
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ObsPreviewConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub image_width: u32,
+    // JPEG quality, 0-100
+    pub image_quality: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamHealthConfig {
+    pub enabled: bool,
+    pub poll_interval_secs: u64,
+    // Thresholds are per poll interval, as a share of the frames in it
+    pub max_dropped_frames_percent: f64,
+    pub max_encoder_lag_percent: f64,
+    pub max_render_lag_percent: f64,
+    pub min_bitrate_kbps: f64,
+    // Minimum time between repeats of the same alert
+    pub alert_cooldown_secs: u64,
+    // Discord user to DM about stream health alerts
+    pub discord_user_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BotVoiceConfig {
+    // Built-in packs are default, formal, cute and sarcastic
+    pub active: String,
+    // Custom packs, or overrides for built-in ones: pack name -> template key -> template
+    #[serde(default)]
+    pub packs: HashMap<String, HashMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HypeDecayCurve {
+    // Drains from full to empty over decay_secs
+    Linear,
+    // Halves every decay_secs
+    Exponential,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HypeMeterConfig {
+    pub enabled: bool,
+    // Float avatar parameter driven between 0.0 and 1.0
+    pub parameter: String,
+    // Amounts that fill the meter on their own
+    pub bits_for_full: u64,
+    pub raid_viewers_for_full: u64,
+    // Added per tier 1 sub; tier 2 counts double and tier 3 five times
+    pub sub_value: f32,
+    pub decay_curve: HypeDecayCurve,
+    pub decay_secs: f32,
+    pub update_interval_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TriviaConfig {
+    pub answer_secs: u64,
+    pub points: u64,
+    // 0.0-1.0, how close an answer has to be to count (Jaro-Winkler after normalizing)
+    pub answer_similarity: f64,
+    // Verified questions, used to avoid repeats and when the AI can't come up with one
+    pub question_cache_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OutputFilterConfig {
+    pub enabled: bool,
+    // Any word containing one of these is masked in chat, chatbox and TTS output
+    pub deny_words: Vec<String>,
+    // Whole words that are never masked, for false positives like "scunthorpe"
+    pub allow_words: Vec<String>,
+    // Has the AI check every AI response before it's used; costs one extra request each
+    pub ai_moderation: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorldReactConfig {
+    // Seconds between reactions for the whole channel, and for any one viewer
+    pub cooldown_secs: u64,
+    pub user_cooldown_secs: u64,
+    // Holds each reaction until the broadcaster approves it with !react approve
+    pub require_approval: bool,
+    pub image_width: u32,
+    pub image_quality: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct StreamProfile {
+    pub name: String,
+    // Switched to automatically when the stream category matches one of these
+    #[serde(default)]
+    pub games: Vec<String>,
+    // Only these rewards stay up while the profile is active; empty leaves them all alone
+    #[serde(default)]
+    pub redeems: Vec<String>,
+    // Scheduled jobs that don't run while the profile is active
+    #[serde(default)]
+    pub paused_jobs: Vec<String>,
+    // VRChat avatar id whose OSC profile should be used
+    #[serde(default)]
+    pub osc_avatar: Option<String>,
+    // Only a reminder on the dashboard; the bot can't switch scene collections
+    #[serde(default)]
+    pub obs_scene_collection: Option<String>,
+    #[serde(default)]
+    pub bot_voice: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProfilesConfig {
+    pub auto_switch: bool,
+    // Set by !profile and restored on startup
+    pub active: Option<String>,
+    pub profiles: Vec<StreamProfile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HappyHourConfig {
+    // Reward titles to discount
+    pub rewards: Vec<String>,
+    pub discount_percent: u32,
+    // Cron expression for automatic happy hours, e.g. "0 20 * * 5" for Fridays at 8pm
+    pub schedule: Option<String>,
+    pub scheduled_duration_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommandAbuseConfig {
+    pub enabled: bool,
+    // Failed or denied commands within the window that earn a soft-ban
+    pub max_failures: usize,
+    pub window_secs: u64,
+    // Commands from soft-banned users are ignored for this long; mods are never soft-banned
+    pub soft_ban_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiAccessConfig {
+    // Requests from other machines need an API token from the dashboard. Localhost doesn't, unless
+    // web_ui_public_url is set or the request came through a proxy (`mewbot admin-token` makes one)
+    pub remote_requires_token: bool,
+    // Sanitized mode for overlays on an untrusted network: while remote_requires_token is on, other
+    // machines without a token can still read the overlay routes and nothing else
+    #[serde(default)]
+    pub anonymous_overlays: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteControlConfig {
+    pub enabled: bool,
+    // Pairing codes from the dashboard QR code expire after this long
+    pub pairing_secs: u64,
+    // A paired phone has to scan a new code after this long
+    pub session_hours: u64,
+    // Address the phone should open, like "http://192.168.1.20:3333". Empty uses the dashboard's address.
+    pub public_url: String,
+    pub preset_messages: Vec<String>,
+    pub ad_length_secs: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TtsModerationConfig {
+    pub enabled: bool,
+    // TTS waits on the dashboard this long before playing, so mods can reject it; 0 plays right away
+    pub hold_secs: u64,
+    // Nothing plays until a mod approves it on the dashboard
+    pub require_approval: bool,
+    // TTS containing any of these is rejected outright
+    pub blocked_words: Vec<String>,
+    // Voices viewers can't pick with "<voice>: <message>"
+    pub blocked_voices: Vec<String>,
+    // Refund the channel points when a TTS redeem is rejected
+    pub refund_rejected: bool,
+    pub max_chars: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommandMetricsConfig {
+    // Commands taking longer than this are logged with who ran them and where
+    pub slow_command_ms: u64,
+    // Same for the whole chat message, including logging, relays and triggers
+    pub slow_message_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TelemetryConfig {
+    // OTLP gRPC collector to send spans to, e.g. http://localhost:4317; needs a build with the otlp feature
+    pub otlp_endpoint: Option<String>,
+    pub service_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusPageConfig {
+    pub enabled: bool,
+    // How long the Twitch lookups are reused, and how long browsers and Discord may cache the page
+    pub cache_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RaidTrainConfig {
+    pub enabled: bool,
+    // The team in raid order, this channel included; logins or channel URLs
+    pub channels: Vec<String>,
+    // Raid when the countdown runs out unless a mod cancels; otherwise a mod has to confirm the suggestion
+    pub auto_raid: bool,
+    // Length of that countdown, or how long a suggestion waits for confirmation
+    pub confirm_secs: u64,
+    pub discord_channel_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SuggestionsConfig {
+    pub enabled: bool,
+    // Suggestions a viewer can have waiting in the queue at once
+    pub max_pending_per_user: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WatchQueueConfig {
+    pub enabled: bool,
+    // Videos a viewer can have waiting at once
+    pub max_per_user: usize,
+    // Time between a viewer's requests
+    pub user_cooldown_secs: u64,
+    pub max_queue_length: usize,
+    // Show the video being watched in the VRChat chatbox
+    pub send_to_chatbox: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BitsVoteConfig {
+    pub default_duration_secs: u64,
+    // Int avatar parameter set to the winning option's number when a vote ends, 0 for a tie or no votes
+    pub osc_parameter: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PredictionMetric {
+    Viewers,
+    Followers,
+    // Current price of the coin game reward
+    CoinPot,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PredictionResolver {
+    // Matched case-insensitively against the title of the open prediction
+    pub title_contains: String,
+    pub metric: PredictionMetric,
+    pub threshold: u64,
+    // Outcome number (from 1) that wins as soon as the metric reaches the threshold
+    pub outcome_if_met: usize,
+    // Wins if the stream ends before that; the prediction is left for a mod otherwise
+    #[serde(default)]
+    pub outcome_if_offline: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PredictionsConfig {
+    pub enabled: bool,
+    pub check_interval_secs: u64,
+    pub resolvers: Vec<PredictionResolver>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VoiceCommand {
+    // Any of these after the wake word runs the command, e.g. "switch to brb"
+    pub phrases: Vec<String>,
+    // A dashboard control message, the same module/action/data the dashboard websocket takes
+    pub module: String,
+    pub action: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+    // Waits for "<wake word>, confirm" before running, for anything that's hard to undo live
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VoiceCommandsConfig {
+    // Experimental: a local speech recognizer posts what it heard to /api/voice/heard
+    pub enabled: bool,
+    // Only speech starting with this is treated as a command, like "bot, switch to brb"
+    pub wake_word: String,
+    // How long a command waiting for confirmation stays open
+    pub confirm_secs: u64,
+    pub commands: Vec<VoiceCommand>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RedemptionAnnouncementConfig {
+    pub enabled: bool,
+    // Defaults for rewards without their own templates; empty means no message. Placeholders: {user},
+    // {reward}, {input}, {message} (what the reward's handler said), {number} and {position} (in the queue)
+    pub success: String,
+    pub failure: String,
+    pub queued: String,
+    // Queued redemptions given back when the stream ends
+    #[serde(default = "default_refunded_announcement")]
+    pub refunded: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PremiereConfig {
+    pub countdown_secs: u64,
+    // OBS instance and scene to cut to at zero; an empty scene leaves OBS alone
+    pub obs_instance: String,
+    pub content_scene: String,
+    // Stream title once it starts; empty keeps the current one
+    pub title: String,
+    pub send_to_discord: bool,
+    pub send_to_chatbox: bool,
+    pub discord_channel_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FaqConfig {
+    pub enabled: bool,
+    // Time before the same answer is given again; an entry can set its own
+    pub cooldown_secs: u64,
+    // Only messages that look like questions are matched
+    pub questions_only: bool,
+    // Questions no pattern matches go to the AI, which can only pick one of the existing answers
+    pub ai_fallback: bool,
+    // Time between AI lookups, so a busy chat doesn't ask about every message
+    pub ai_cooldown_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TickerConfig {
+    pub enabled: bool,
+    // How long each line stays on the overlay
+    pub rotate_secs: u64,
+    pub show_latest_follower: bool,
+    // Biggest cheer since the stream went live
+    pub show_top_cheer: bool,
+    pub show_social_links: bool,
+    // Text file kept up to date by a now-playing tool (Tuna, Snip and the like); empty to skip
+    pub now_playing_file: String,
+    pub custom_lines: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CharityConfig {
+    // Built-in chat thanks for donations no alert route matches
+    pub announce_donations: bool,
+    // Chat messages when a charity campaign starts and ends
+    pub announce_campaign: bool,
+    // Percent of the target at which progress is called out in chat; empty to stay quiet
+    pub milestone_percents: Vec<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SoundboardConfig {
+    pub enabled: bool,
+    // Between any two sounds from chat, on top of each sound's own cooldown
+    pub global_cooldown_secs: u64,
+    // Sounds with a measured loudness are turned up or down to this (RMS dBFS)
+    pub target_loudness_db: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChatRateConfig {
+    // Verified bot accounts can send far more; leave off unless Twitch has verified the bot account,
+    // or messages past the normal limits are dropped and the account can be locked out of chat
+    pub verified_bot: bool,
+    // Kept below Twitch's limits by this much, for messages sent from elsewhere on the same account
+    pub headroom_percent: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FollowGuardConfig {
+    pub enabled: bool,
+    // This many follows inside window_secs is treated as a follow-bot attack; 0 turns the check off
+    pub follow_threshold: u32,
+    // Or this many chat joins. Twitch batches joins and stops sending them for very big chats; 0 turns it off
+    pub join_threshold: u32,
+    pub window_secs: u64,
+    // Protection stays on this long after the last burst
+    pub duration_mins: u64,
+    // Follows during an attack get one summary when it ends instead of an alert each
+    pub pause_follow_alerts: bool,
+    // Turns on followers-only chat, put back the way it was afterwards
+    pub followers_only: bool,
+    // How long chatters must have followed to talk while followers-only chat is on
+    pub followers_only_minutes: u64,
+    // Discord channel to tell mods in when protection starts and ends
+    pub discord_channel_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamSegmentsConfig {
+    pub enabled: bool,
+    // Drops a VOD marker at every game or world change, for editors
+    pub create_markers: bool,
+    // Includes the time spent in each game and world in the end-of-stream recap
+    pub in_recap: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyRoute {
+    Off,
+    Desktop,
+    Telegram,
+    Both,
+}
+
+impl NotifyRoute {
+    pub fn desktop(self) -> bool {
+        matches!(self, NotifyRoute::Desktop | NotifyRoute::Both)
+    }
+
+    pub fn telegram(self) -> bool {
+        matches!(self, NotifyRoute::Telegram | NotifyRoute::Both)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationForwardingConfig {
+    pub enabled: bool,
+    // From @BotFather. The bot can only message chats that have messaged it first
+    pub telegram_bot_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    // Where each kind of event goes; desktop notifications need the desktop-notifications feature
+    pub raids: NotifyRoute,
+    pub failing_subsystems: NotifyRoute,
+    pub redeem_reviews: NotifyRoute,
+    // Raids smaller than this aren't forwarded
+    pub raid_min_viewers: u64,
+    // Rewards to forward when they're left for the streamer to fulfill; empty forwards every one
+    pub review_rewards: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UiTheme {
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UiThemeConfig {
+    pub theme: UiTheme,
+    // Hex color like "#9146FF", used for highlights in the dashboard and public pages
+    pub accent_color: String,
+    // Set when a logo is uploaded from the dashboard
+    pub logo_path: Option<String>,
+    // Markdown files in here are served as public pages at /pages/<file name>
+    pub pages_dir: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TranslationConfig {
+    // Used by the redeem and by !translate without a language
+    pub default_language: String,
+    // Also show translations in the VRChat chatbox
+    pub send_to_chatbox: bool,
+    pub max_input_chars: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MilestonesConfig {
+    pub enabled: bool,
+    pub follower_thresholds: Vec<u64>,
+    pub sub_thresholds: Vec<u64>,
+    pub hours_streamed_thresholds: Vec<u64>,
+    // Bool avatar parameter pulsed on for a few seconds when a milestone is reached
+    pub confetti_parameter: String,
+    pub discord_channel_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AlertRoutingConfig {
+    // The first matching route replaces the built-in alert; events with no match keep the built-in behavior
+    pub routes: Vec<AlertRoute>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandTier {
+    Subscriber,
+    Vip,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommandTierConfig {
+    // Command name (e.g. "!xmas") to the tier required to use it
+    pub tiers: HashMap<String, CommandTier>,
+    // Supports {user}, {command} and {sub_link} placeholders
+    pub subscriber_denial_message: String,
+    pub vip_denial_message: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub twitch_bot_username: Option<String>,
@@ -57,6 +711,12 @@ pub struct Config {
     pub log_level: LevelFilter,
     pub web_ui_host: Option<String>,
     pub web_ui_port: Option<u16>,
+    // Address viewers use to reach the public pages, e.g. "https://bot.example.com"; defaults to host:port
+    #[serde(default)]
+    pub web_ui_public_url: Option<String>,
+    // Where Twitch sends the broadcaster back after authorizing the bot, served by the web UI
+    #[serde(default = "default_twitch_redirect_path")]
+    pub twitch_redirect_path: String,
     #[serde(default = "default_additional_streams")]
     pub additional_streams: Vec<String>,
     #[serde(default)]
@@ -64,115 +724,925 @@ pub struct Config {
     #[serde(default)]
     pub obs_manager: OBSManagerConfig,
     pub discord_announcement_channel_id: Option<String>,
+    #[serde(default)]
+    pub discord_modlog_channel_id: Option<String>,
+    #[serde(default)]
+    pub discord_clips_channel_id: Option<String>,
+    #[serde(default)]
+    pub chat_relay: ChatRelayConfig,
+    #[serde(default)]
+    pub command_tiers: CommandTierConfig,
+    #[serde(default)]
+    pub raid_mode: RaidModeConfig,
+    #[serde(default)]
+    pub ad_break: AdBreakConfig,
+    #[serde(default)]
+    pub broadcast: BroadcastConfig,
+    #[serde(default)]
+    pub message_workers: MessageWorkerConfig,
+    #[serde(default)]
+    pub alert_routing: AlertRoutingConfig,
+    #[serde(default)]
+    pub milestones: MilestonesConfig,
+    #[serde(default)]
+    pub translation: TranslationConfig,
+    #[serde(default)]
+    pub ui_theme: UiThemeConfig,
+    #[serde(default)]
+    pub reward_announcements: RewardAnnouncementConfig,
+    #[serde(default)]
+    pub connection_alerts: ConnectionAlertConfig,
+    #[serde(default)]
+    pub avatar_changes: AvatarChangeConfig,
+    #[serde(default)]
+    pub compat_api: CompatApiConfig,
+    #[serde(default)]
+    pub ignore_list: IgnoreListConfig,
+    #[serde(default)]
+    pub chatter_drops: ChatterDropConfig,
+    #[serde(default)]
+    pub obs_preview: ObsPreviewConfig,
+    #[serde(default)]
+    pub stream_health: StreamHealthConfig,
+    #[serde(default)]
+    pub hype_meter: HypeMeterConfig,
+    #[serde(default)]
+    pub bot_voice: BotVoiceConfig,
+    #[serde(default)]
+    pub trivia: TriviaConfig,
+    #[serde(default)]
+    pub output_filter: OutputFilterConfig,
+    #[serde(default)]
+    pub world_react: WorldReactConfig,
+    #[serde(default)]
+    pub profiles: ProfilesConfig,
+    #[serde(default)]
+    pub happy_hour: HappyHourConfig,
+    #[serde(default)]
+    pub command_abuse: CommandAbuseConfig,
+    #[serde(default)]
+    pub api_access: ApiAccessConfig,
+    #[serde(default)]
+    pub remote_control: RemoteControlConfig,
+    #[serde(default)]
+    pub tts_moderation: TtsModerationConfig,
+    #[serde(default)]
+    pub command_metrics: CommandMetricsConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub status_page: StatusPageConfig,
+    #[serde(default)]
+    pub raid_train: RaidTrainConfig,
+    #[serde(default)]
+    pub suggestions: SuggestionsConfig,
+    #[serde(default)]
+    pub watch_queue: WatchQueueConfig,
+    #[serde(default)]
+    pub bits_vote: BitsVoteConfig,
+    #[serde(default)]
+    pub predictions: PredictionsConfig,
+    #[serde(default)]
+    pub voice_commands: VoiceCommandsConfig,
+    #[serde(default)]
+    pub redemption_announcements: RedemptionAnnouncementConfig,
+    #[serde(default)]
+    pub premiere: PremiereConfig,
+    #[serde(default)]
+    pub faq: FaqConfig,
+    #[serde(default)]
+    pub ticker: TickerConfig,
+    #[serde(default)]
+    pub charity: CharityConfig,
+    #[serde(default)]
+    pub soundboard: SoundboardConfig,
+    #[serde(default)]
+    pub chat_rate: ChatRateConfig,
+    #[serde(default)]
+    pub follow_guard: FollowGuardConfig,
+    #[serde(default)]
+    pub stream_segments: StreamSegmentsConfig,
+    #[serde(default)]
+    pub notification_forwarding: NotificationForwardingConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            twitch_bot_username: None,
+            twitch_user_id: None,
+            twitch_channel_to_join: None,
+            twitch_client_id: None,
+            twitch_client_secret: None,
+            twitch_bot_oauth_token: None,
+            twitch_broadcaster_oauth_token: None,
+            twitch_access_token: None,
+            twitch_refresh_token: None,
+            vrchat_auth_cookie: None,
+            discord_token: None,
+            discord_client_id: None,
+            discord_guild_id: None,
+            openai_secret: None,
+            anthropic_secret: None,
+            xai_secret: None,
+            google_search_api_key: None,
+            google_search_cx: None,
+            bing_search_api_key: None,
+            log_level: LevelFilter::INFO,
+            web_ui_host: None,
+            web_ui_port: None,
+            web_ui_public_url: None,
+            twitch_redirect_path: default_twitch_redirect_path(),
+            additional_streams: vec![],
+            social_links: SocialLinks::default(),
+            obs_manager: OBSManagerConfig::default(),
+            discord_announcement_channel_id: None,
+            discord_modlog_channel_id: None,
+            discord_clips_channel_id: None,
+            chat_relay: ChatRelayConfig::default(),
+            command_tiers: CommandTierConfig::default(),
+            raid_mode: RaidModeConfig::default(),
+            ad_break: AdBreakConfig::default(),
+            broadcast: BroadcastConfig::default(),
+            message_workers: MessageWorkerConfig::default(),
+            alert_routing: AlertRoutingConfig::default(),
+            milestones: MilestonesConfig::default(),
+            translation: TranslationConfig::default(),
+            ui_theme: UiThemeConfig::default(),
+            reward_announcements: RewardAnnouncementConfig::default(),
+            connection_alerts: ConnectionAlertConfig::default(),
+            avatar_changes: AvatarChangeConfig::default(),
+            compat_api: CompatApiConfig::default(),
+            ignore_list: IgnoreListConfig::default(),
+            chatter_drops: ChatterDropConfig::default(),
+            obs_preview: ObsPreviewConfig::default(),
+            stream_health: StreamHealthConfig::default(),
+            hype_meter: HypeMeterConfig::default(),
+            bot_voice: BotVoiceConfig::default(),
+            trivia: TriviaConfig::default(),
+            output_filter: OutputFilterConfig::default(),
+            world_react: WorldReactConfig::default(),
+            profiles: ProfilesConfig::default(),
+            happy_hour: HappyHourConfig::default(),
+            command_abuse: CommandAbuseConfig::default(),
+            api_access: ApiAccessConfig::default(),
+            remote_control: RemoteControlConfig::default(),
+            tts_moderation: TtsModerationConfig::default(),
+            command_metrics: CommandMetricsConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            status_page: StatusPageConfig::default(),
+            raid_train: RaidTrainConfig::default(),
+            suggestions: SuggestionsConfig::default(),
+            watch_queue: WatchQueueConfig::default(),
+            bits_vote: BitsVoteConfig::default(),
+            predictions: PredictionsConfig::default(),
+            voice_commands: VoiceCommandsConfig::default(),
+            redemption_announcements: RedemptionAnnouncementConfig::default(),
+            premiere: PremiereConfig::default(),
+            faq: FaqConfig::default(),
+            ticker: TickerConfig::default(),
+            charity: CharityConfig::default(),
+            soundboard: SoundboardConfig::default(),
+            chat_rate: ChatRateConfig::default(),
+            follow_guard: FollowGuardConfig::default(),
+            stream_segments: StreamSegmentsConfig::default(),
+            notification_forwarding: NotificationForwardingConfig::default(),
+        }
+    }
+}
+
+fn default_additional_streams() -> Vec<String> {
+    vec!["".to_string(); 4]
+}
+
+fn default_worker_queue_size() -> usize {
+    500
+}
+
+fn default_twitch_redirect_path() -> String {
+    "/auth/twitch/callback".to_string()
+}
+
+fn default_refunded_announcement() -> String {
+    "@{user} the stream ended before your {reward} came up, so your points were refunded.".to_string()
+}
+
+// Custom serialization for LevelFilter, written the same way config files always had it
+mod log_level_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use tracing::level_filters::LevelFilter;
+
+    pub fn serialize<S>(level: &LevelFilter, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let name = match *level {
+            LevelFilter::ERROR => "Error",
+            LevelFilter::WARN => "Warn",
+            LevelFilter::INFO => "Info",
+            LevelFilter::DEBUG => "Debug",
+            LevelFilter::TRACE => "Trace",
+            _ => "Off",
+        };
+        serializer.serialize_str(name)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<LevelFilter, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.to_uppercase().as_str() {
+            "ERROR" => Ok(LevelFilter::ERROR),
+            "WARN" => Ok(LevelFilter::WARN),
+            "INFO" => Ok(LevelFilter::INFO),
+            "DEBUG" => Ok(LevelFilter::DEBUG),
+            "TRACE" => Ok(LevelFilter::TRACE),
+            _ => Ok(LevelFilter::INFO), // Default to Info if unrecognized
+        }
+    }
+}
+
+impl Default for SocialLinks {
+    fn default() -> Self {
+        SocialLinks {
+            discord: None,
+            xdotcom: None,
+            vrchat_group: None,
+            business_url: None,
+        }
+    }
+}
+impl Default for OBSInstanceConfig {
+    fn default() -> Self {
+        Self {
+            ip: "127.0.0.1".to_string(),
+            port: 4455,
+            auth_required: false,
+            password: None,
+            use_ssl: false,
+        }
+    }
+}
+impl Default for OBSManagerConfig {
+    fn default() -> Self {
+        Self {
+            is_dual_pc_setup: false,
+            instance1: OBSInstanceConfig::default(),
+            instance2: None,
+        }
+    }
+}
+impl Default for ChatRelayConfig {
+    fn default() -> Self {
+        Self {
+            mode: ChatRelayMode::Off,
+            min_interval_secs: 5,
+            play_sound: false,
+            blocked_words: Vec::new(),
+        }
+    }
+}
+
+impl Default for BroadcastConfig {
+    fn default() -> Self {
+        Self {
+            irc: BroadcastChannelConfig::with_capacity(1000),
+            dashboard: BroadcastChannelConfig::with_capacity(100),
+        }
+    }
+}
+
+impl Default for MessageWorkerConfig {
+    fn default() -> Self {
+        Self {
+            workers: 4,
+            max_heavy_commands: 2,
+            queue_size: default_worker_queue_size(),
+        }
+    }
+}
+
+impl Default for AdBreakConfig {
+    fn default() -> Self {
+        Self {
+            chatbox_countdown: true,
+            update_interval_secs: 10,
+            avatar_parameter: "/avatar/parameters/AdBreak".to_string(),
+        }
+    }
+}
+
+impl Default for UiThemeConfig {
+    fn default() -> Self {
+        Self {
+            theme: UiTheme::Dark,
+            accent_color: "#9146FF".to_string(),
+            logo_path: None,
+            pages_dir: "pages".to_string(),
+        }
+    }
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            default_language: "English".to_string(),
+            send_to_chatbox: true,
+            max_input_chars: 300,
+        }
+    }
+}
+
+impl Default for MilestonesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            follower_thresholds: vec![50, 100, 250, 500, 1000, 2500, 5000, 10000],
+            sub_thresholds: vec![10, 25, 50, 100, 250, 500, 1000],
+            hours_streamed_thresholds: vec![10, 50, 100, 250, 500, 1000],
+            confetti_parameter: "/avatar/parameters/Confetti".to_string(),
+            discord_channel_id: None,
+        }
+    }
+}
+
+impl Default for RewardAnnouncementConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            discord_channel_id: None,
+            poll_interval_secs: 300,
+        }
+    }
+}
+
+impl Default for ConnectionAlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            flaps_per_hour: 5,
+            dashboard: true,
+            discord_user_id: None,
+        }
+    }
+}
+
+impl Default for AvatarChangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            osc_listen_addr: "127.0.0.1:9001".to_string(),
+            announce_in_chat: false,
+            announce_on_dashboard: true,
+        }
+    }
+}
+
+impl Default for IgnoreListConfig {
+    fn default() -> Self {
+        Self {
+            users: ["nightbot", "streamelements", "streamlabs", "moobot", "fossabot", "soundalerts", "sery_bot"]
+                .iter()
+                .map(|user| user.to_string())
+                .collect(),
+            ignore_banned: true,
+        }
+    }
+}
+
+impl Default for ChatterDropConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: 15,
+            subscriber_weight: 2.0,
+            vip_weight: 2.0,
+            moderator_weight: 1.0,
+            points: 100,
+            celebration_parameter: None,
+            message: "🎁 Active chatter drop! {user} wins {points} points ({total} total). Thanks for hanging out in chat!".to_string(),
+        }
+    }
+}
+
+impl Default for ObsPreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 5,
+            image_width: 640,
+            image_quality: 60,
+        }
+    }
+}
+
+impl Default for StreamHealthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval_secs: 10,
+            max_dropped_frames_percent: 1.0,
+            max_encoder_lag_percent: 1.0,
+            max_render_lag_percent: 1.0,
+            min_bitrate_kbps: 1000.0,
+            alert_cooldown_secs: 300,
+            discord_user_id: None,
+        }
+    }
+}
+
+impl Default for HypeMeterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            parameter: "/avatar/parameters/Hype".to_string(),
+            bits_for_full: 1000,
+            raid_viewers_for_full: 100,
+            sub_value: 0.2,
+            decay_curve: HypeDecayCurve::Exponential,
+            decay_secs: 20.0,
+            update_interval_ms: 250,
+        }
+    }
 }
 
-impl Default for Config {
+impl Default for BotVoiceConfig {
     fn default() -> Self {
         Self {
-            twitch_bot_username: None,
-            twitch_user_id: None,
-            twitch_channel_to_join: None,
-            twitch_client_id: None,
-            twitch_client_secret: None,
-            twitch_bot_oauth_token: None,
-            twitch_broadcaster_oauth_token: None,
-            twitch_access_token: None,
-            twitch_refresh_token: None,
-            vrchat_auth_cookie: None,
-            discord_token: None,
-            discord_client_id: None,
-            discord_guild_id: None,
-            openai_secret: None,
-            anthropic_secret: None,
-            xai_secret: None,
-            google_search_api_key: None,
-            google_search_cx: None,
-            bing_search_api_key: None,
-            log_level: log::LevelFilter::Info,
-            web_ui_host: None,
-            web_ui_port: None,
-            additional_streams: vec![],
-            social_links: SocialLinks::default(),
-            obs_manager: OBSManagerConfig::default(),
-            discord_announcement_channel_id: None,
+            active: "default".to_string(),
+            packs: HashMap::new(),
         }
     }
 }
 
-fn default_additional_streams() -> Vec<String> {
-    vec!["".to_string(); 4]
+impl Default for TriviaConfig {
+    fn default() -> Self {
+        Self {
+            answer_secs: 30,
+            points: 50,
+            answer_similarity: 0.9,
+            question_cache_path: "trivia_questions.json".to_string(),
+        }
+    }
 }
 
-// Custom serialization for log::LevelFilter
-mod log_level_serde {
-    use serde::{Deserialize, Deserializer, Serializer};
-    use log::LevelFilter;
+impl Default for OutputFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            deny_words: Vec::new(),
+            allow_words: Vec::new(),
+            ai_moderation: false,
+        }
+    }
+}
 
-    pub fn serialize<S>(level: &LevelFilter, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_str(&format!("{:?}", level))
+impl Default for WorldReactConfig {
+    fn default() -> Self {
+        Self {
+            cooldown_secs: 120,
+            user_cooldown_secs: 600,
+            require_approval: false,
+            image_width: 1280,
+            image_quality: 70,
+        }
     }
+}
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<LevelFilter, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        match s.to_uppercase().as_str() {
-            "ERROR" => Ok(LevelFilter::Error),
-            "WARN" => Ok(LevelFilter::Warn),
-            "INFO" => Ok(LevelFilter::Info),
-            "DEBUG" => Ok(LevelFilter::Debug),
-            "TRACE" => Ok(LevelFilter::Trace),
-            _ => Ok(LevelFilter::Info), // Default to Info if unrecognized
+impl Default for ProfilesConfig {
+    fn default() -> Self {
+        Self {
+            auto_switch: true,
+            active: None,
+            profiles: Vec::new(),
         }
     }
 }
 
-impl Default for SocialLinks {
+impl Default for HappyHourConfig {
     fn default() -> Self {
-        SocialLinks {
-            discord: None,
-            xdotcom: None,
-            vrchat_group: None,
-            business_url: None,
+        Self {
+            rewards: Vec::new(),
+            discount_percent: 50,
+            schedule: None,
+            scheduled_duration_secs: 3600,
         }
     }
 }
-impl Default for OBSInstanceConfig {
+
+impl Default for CommandAbuseConfig {
     fn default() -> Self {
         Self {
-            ip: "127.0.0.1".to_string(),
-            port: 4455,
-            auth_required: false,
-            password: None,
-            use_ssl: false,
+            enabled: true,
+            max_failures: 5,
+            window_secs: 60,
+            soft_ban_secs: 600,
         }
     }
 }
-impl Default for OBSManagerConfig {
+
+impl Default for ApiAccessConfig {
     fn default() -> Self {
         Self {
-            is_dual_pc_setup: false,
-            instance1: OBSInstanceConfig::default(),
-            instance2: None,
+            remote_requires_token: true,
+            anonymous_overlays: false,
         }
     }
 }
 
-impl Config {
-    const CONFIG_PATH: &'static str = "C:\\Users\\kittyn\\RustroverProjects\\mewbot\\target\\debug\\mewbot.conf";
+impl Default for RemoteControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            pairing_secs: 120,
+            session_hours: 12,
+            public_url: String::new(),
+            preset_messages: vec![
+                "Be right back!".to_string(),
+                "Brb, fixing my tracking".to_string(),
+                "Thanks for hanging out, everyone!".to_string(),
+            ],
+            ad_length_secs: 90,
+        }
+    }
+}
+
+impl Default for TtsModerationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            hold_secs: 10,
+            require_approval: false,
+            blocked_words: Vec::new(),
+            blocked_voices: Vec::new(),
+            refund_rejected: true,
+            max_chars: 300,
+        }
+    }
+}
+
+impl Default for CommandMetricsConfig {
+    fn default() -> Self {
+        Self {
+            slow_command_ms: 1000,
+            slow_message_ms: 2000,
+        }
+    }
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            service_name: "mewbot".to_string(),
+        }
+    }
+}
+
+impl Default for StatusPageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cache_secs: 60,
+        }
+    }
+}
+
+impl Default for RaidTrainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channels: Vec::new(),
+            auto_raid: false,
+            confirm_secs: 60,
+            discord_channel_id: None,
+        }
+    }
+}
+
+impl Default for SuggestionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_pending_per_user: 2,
+        }
+    }
+}
+
+impl Default for WatchQueueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_per_user: 2,
+            user_cooldown_secs: 300,
+            max_queue_length: 50,
+            send_to_chatbox: true,
+        }
+    }
+}
+
+impl Default for BitsVoteConfig {
+    fn default() -> Self {
+        Self {
+            default_duration_secs: 180,
+            osc_parameter: "/avatar/parameters/BitsVote".to_string(),
+        }
+    }
+}
+
+impl Default for PredictionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            check_interval_secs: 30,
+            resolvers: Vec::new(),
+        }
+    }
+}
+
+impl Default for VoiceCommandsConfig {
+    fn default() -> Self {
+        let scene = |phrases: &[&str], scene_name: &str| VoiceCommand {
+            phrases: phrases.iter().map(|p| p.to_string()).collect(),
+            module: "obs".to_string(),
+            action: "change_scene".to_string(),
+            data: serde_json::json!({ "instance_name": "Instance1", "scene_name": scene_name }),
+            confirm: false,
+        };
+        Self {
+            enabled: false,
+            wake_word: "bot".to_string(),
+            confirm_secs: 15,
+            commands: vec![
+                scene(&["switch to brb", "be right back"], "BRB"),
+                scene(&["switch to main", "i'm back"], "Main"),
+                VoiceCommand {
+                    phrases: vec!["hide the camera".to_string()],
+                    module: "obs".to_string(),
+                    action: "toggle_source".to_string(),
+                    data: serde_json::json!({
+                        "instance_name": "Instance1",
+                        "scene_name": "Main",
+                        "source_name": "Camera",
+                        "enabled": false,
+                    }),
+                    confirm: true,
+                },
+            ],
+        }
+    }
+}
+
+impl Default for RedemptionAnnouncementConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            success: "{message}".to_string(),
+            failure: String::new(),
+            queued: String::new(),
+            refunded: default_refunded_announcement(),
+        }
+    }
+}
+
+impl Default for PremiereConfig {
+    fn default() -> Self {
+        Self {
+            countdown_secs: 300,
+            obs_instance: "Instance1".to_string(),
+            content_scene: String::new(),
+            title: String::new(),
+            send_to_discord: true,
+            send_to_chatbox: true,
+            discord_channel_id: None,
+        }
+    }
+}
+
+impl Default for FaqConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cooldown_secs: 300,
+            questions_only: true,
+            ai_fallback: false,
+            ai_cooldown_secs: 30,
+        }
+    }
+}
+
+impl Default for TickerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rotate_secs: 8,
+            show_latest_follower: true,
+            show_top_cheer: true,
+            show_social_links: true,
+            now_playing_file: String::new(),
+            custom_lines: Vec::new(),
+        }
+    }
+}
+
+impl Default for CharityConfig {
+    fn default() -> Self {
+        Self {
+            announce_donations: true,
+            announce_campaign: true,
+            milestone_percents: vec![25, 50, 75, 100],
+        }
+    }
+}
+
+impl Default for SoundboardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            global_cooldown_secs: 5,
+            target_loudness_db: -20.0,
+        }
+    }
+}
+
+impl Default for ChatRateConfig {
+    fn default() -> Self {
+        Self {
+            verified_bot: false,
+            headroom_percent: 10,
+        }
+    }
+}
+
+impl Default for FollowGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            follow_threshold: 20,
+            join_threshold: 0,
+            window_secs: 60,
+            duration_mins: 10,
+            pause_follow_alerts: true,
+            followers_only: false,
+            followers_only_minutes: 10,
+            discord_channel_id: None,
+        }
+    }
+}
+
+impl Default for StreamSegmentsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            create_markers: true,
+            in_recap: true,
+        }
+    }
+}
+
+impl Default for NotificationForwardingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            telegram_bot_token: None,
+            telegram_chat_id: None,
+            raids: NotifyRoute::Both,
+            failing_subsystems: NotifyRoute::Both,
+            redeem_reviews: NotifyRoute::Desktop,
+            raid_min_viewers: 50,
+            review_rewards: Vec::new(),
+        }
+    }
+}
+
+impl Default for RaidModeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_viewers: 20,
+            duration_secs: 180,
+        }
+    }
+}
+
+impl Default for CommandTierConfig {
+    fn default() -> Self {
+        Self {
+            tiers: HashMap::new(),
+            subscriber_denial_message: "@{user}, {command} is for subscribers only! Subscribe at {sub_link}".to_string(),
+            vip_denial_message: "@{user}, {command} is for VIPs only.".to_string(),
+        }
+    }
+}
+
+// Any config field can be set from the environment, winning over the file. MEWBOT_<FIELD> sets a
+// top-level field and `__` steps into a section, e.g. MEWBOT_TWITCH_CHANNEL_TO_JOIN=kittyn or
+// MEWBOT_WATCH_QUEUE__MAX_PER_USER=3. Values are read as JSON unless the field is a string, so numbers,
+// booleans and lists work as well. Overrides only live in memory: saving keeps whatever the file had
+// for those fields, so secrets passed in this way never land on disk.
+pub const ENV_PREFIX: &str = "MEWBOT_";
+
+fn env_overrides() -> Vec<(String, String)> {
+    let mut overrides: Vec<(String, String)> = std::env::vars()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX) && !paths::is_path_var(name))
+        .collect();
+    overrides.sort();
+    overrides
+}
+
+fn env_keys(name: &str) -> Vec<String> {
+    name[ENV_PREFIX.len()..].split("__").map(str::to_lowercase).collect()
+}
+
+// Fields that can't be left empty, for when there's no terminal to prompt for them
+const REQUIRED_FIELDS: [&str; 6] = [
+    "twitch_bot_username",
+    "twitch_bot_oauth_token",
+    "twitch_broadcaster_oauth_token",
+    "twitch_channel_to_join",
+    "twitch_client_id",
+    "twitch_client_secret",
+];
+
+fn field_mut<'a>(tree: &'a mut serde_json::Value, keys: &[String]) -> Option<&'a mut serde_json::Value> {
+    keys.iter().try_fold(tree, |node, key| node.as_object_mut()?.get_mut(key))
+}
 
+impl Config {
     pub fn new() -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
-        if Path::new(Self::CONFIG_PATH).exists() {
-            let mut config: Config = toml::from_str(&fs::read_to_string(Self::CONFIG_PATH)?)?;
-            config.prompt_for_missing_fields()?;
+        let path = paths::resolve(paths::CONFIG_FILE);
+        let interactive = io::stdin().is_terminal();
+        if Path::new(&path).exists() {
+            let config: Config = toml::from_str(&fs::read_to_string(&path)?)?;
+            let mut config = config.with_env_overrides()?;
+            config.fill_missing_fields(interactive)?;
             Ok(config)
-        } else {
+        } else if !env_overrides().is_empty() {
+            // A container may be configured through the environment alone
+            let mut config = Config::default().with_env_overrides()?;
+            config.fill_missing_fields(interactive)?;
+            config.save()?;
+            Ok(config)
+        } else if interactive {
             Self::initial_setup()
+        } else {
+            Err(format!(
+                "No config file at {} and no terminal to run the setup in. Create one, or configure mewbot through {}* environment variables",
+                path, ENV_PREFIX
+            ).into())
+        }
+    }
+
+    // Without a terminal (a container, a service) prompting would just read EOF, so fail with the
+    // list of what's missing instead
+    fn fill_missing_fields(&mut self, interactive: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if interactive {
+            return self.prompt_for_missing_fields();
+        }
+        let tree = serde_json::to_value(&*self)?;
+        let missing: Vec<&str> = REQUIRED_FIELDS.iter()
+            .copied()
+            .filter(|field| tree.get(field).map_or(true, |value| value.is_null()))
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Missing config fields and no terminal to ask for them: {}. Set them in the config file or as {}<FIELD> environment variables",
+                missing.join(", "), ENV_PREFIX
+            ).into())
+        }
+    }
+
+    pub fn with_env_overrides(self) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let overrides = env_overrides();
+        if overrides.is_empty() {
+            return Ok(self);
+        }
+
+        let mut tree = serde_json::to_value(&self)?;
+        for (name, raw) in overrides {
+            let keys = env_keys(&name);
+            let parsed = match field_mut(&mut tree, &keys) {
+                Some(field) if field.is_string() => None,
+                Some(_) => serde_json::from_str::<serde_json::Value>(&raw).ok(),
+                None => {
+                    warn!("{} doesn't match any config field, ignoring it", name);
+                    continue;
+                }
+            };
+            if let Some(field) = field_mut(&mut tree, &keys) {
+                *field = parsed.clone().unwrap_or_else(|| serde_json::Value::String(raw.clone()));
+            }
+            // An unset field could want a string that happens to look like a number, like a Discord id
+            if parsed.is_some() && serde_json::from_value::<Config>(tree.clone()).is_err() {
+                if let Some(field) = field_mut(&mut tree, &keys) {
+                    *field = serde_json::Value::String(raw);
+                }
+            }
         }
+        serde_json::from_value(tree).map_err(|e| format!("Config from the environment is invalid: {}", e).into())
     }
 
     fn prompt_for_missing_fields(&mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -397,7 +1867,8 @@ impl Config {
         println!("3. Click on 'Register Your Application'");
         println!("4. Fill in the required fields:");
         println!("   - Name: Choose a name for your application");
-        println!("   - OAuth Redirect URLs: http://localhost:3000");
+        println!("   - OAuth Redirect URLs: http://localhost:3333{}", default_twitch_redirect_path());
+        println!("     (use the Web UI port you pick below if it isn't 3333)");
         println!("   - Category: Chat Bot");
         println!("5. Click 'Create'");
         println!("6. On the next page, you'll see your Client ID and you can generate a Client Secret");
@@ -578,13 +2049,65 @@ impl Config {
             google_search_api_key: if google_api.is_empty() { None } else { Some(google_api) },
             google_search_cx: if google_cx.is_empty() { None } else { Some(google_cx) },
             bing_search_api_key: if bing_api.is_empty() { None } else { Some(bing_api) },
-            log_level: LevelFilter::Trace,
+            log_level: LevelFilter::TRACE,
             web_ui_host,
             web_ui_port,
+            web_ui_public_url: None,
+            twitch_redirect_path: default_twitch_redirect_path(),
             additional_streams,
             social_links,
             obs_manager,
             discord_announcement_channel_id: Some(discord_announcement_channel_id),
+            discord_modlog_channel_id: None,
+            discord_clips_channel_id: None,
+            chat_relay: ChatRelayConfig::default(),
+            command_tiers: CommandTierConfig::default(),
+            raid_mode: RaidModeConfig::default(),
+            ad_break: AdBreakConfig::default(),
+            broadcast: BroadcastConfig::default(),
+            message_workers: MessageWorkerConfig::default(),
+            alert_routing: AlertRoutingConfig::default(),
+            milestones: MilestonesConfig::default(),
+            translation: TranslationConfig::default(),
+            ui_theme: UiThemeConfig::default(),
+            reward_announcements: RewardAnnouncementConfig::default(),
+            connection_alerts: ConnectionAlertConfig::default(),
+            avatar_changes: AvatarChangeConfig::default(),
+            compat_api: CompatApiConfig::default(),
+            ignore_list: IgnoreListConfig::default(),
+            chatter_drops: ChatterDropConfig::default(),
+            obs_preview: ObsPreviewConfig::default(),
+            stream_health: StreamHealthConfig::default(),
+            hype_meter: HypeMeterConfig::default(),
+            bot_voice: BotVoiceConfig::default(),
+            trivia: TriviaConfig::default(),
+            output_filter: OutputFilterConfig::default(),
+            world_react: WorldReactConfig::default(),
+            profiles: ProfilesConfig::default(),
+            happy_hour: HappyHourConfig::default(),
+            command_abuse: CommandAbuseConfig::default(),
+            api_access: ApiAccessConfig::default(),
+            remote_control: RemoteControlConfig::default(),
+            tts_moderation: TtsModerationConfig::default(),
+            command_metrics: CommandMetricsConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            status_page: StatusPageConfig::default(),
+            raid_train: RaidTrainConfig::default(),
+            suggestions: SuggestionsConfig::default(),
+            watch_queue: WatchQueueConfig::default(),
+            bits_vote: BitsVoteConfig::default(),
+            predictions: PredictionsConfig::default(),
+            voice_commands: VoiceCommandsConfig::default(),
+            redemption_announcements: RedemptionAnnouncementConfig::default(),
+            premiere: PremiereConfig::default(),
+            faq: FaqConfig::default(),
+            ticker: TickerConfig::default(),
+            charity: CharityConfig::default(),
+            soundboard: SoundboardConfig::default(),
+            chat_rate: ChatRateConfig::default(),
+            follow_guard: FollowGuardConfig::default(),
+            stream_segments: StreamSegmentsConfig::default(),
+            notification_forwarding: NotificationForwardingConfig::default(),
         };
 
         config.save()?;
@@ -602,11 +2125,57 @@ impl Config {
         Ok(input.trim().to_string())
     }
 
+    pub fn public_web_url(&self) -> String {
+        match &self.web_ui_public_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => format!(
+                "http://{}:{}",
+                self.web_ui_host.as_deref().unwrap_or("localhost"),
+                self.web_ui_port.unwrap_or(3333)
+            ),
+        }
+    }
+
+    // Has to match an OAuth Redirect URL registered for the app in the Twitch developer console
+    pub fn twitch_redirect_uri(&self) -> String {
+        let path = self.twitch_redirect_path.trim_start_matches('/');
+        format!("{}/{}", self.public_web_url(), path)
+    }
+
+    // The config as it should be written out: fields set from the environment go back to what the
+    // file has (or the default), so a later save doesn't persist them
+    fn without_env_overrides(&self) -> Result<Config, Box<dyn std::error::Error + Send + Sync>> {
+        let overrides = env_overrides();
+        if overrides.is_empty() {
+            return Ok(self.clone());
+        }
+
+        let on_disk = fs::read_to_string(paths::resolve(paths::CONFIG_FILE))
+            .ok()
+            .and_then(|contents| toml::from_str::<Config>(&contents).ok())
+            .unwrap_or_default();
+        let mut base = serde_json::to_value(&on_disk)?;
+        let mut tree = serde_json::to_value(self)?;
+        for (name, _) in overrides {
+            let keys = env_keys(&name);
+            // A section the file doesn't have is restored as a whole
+            for depth in (1..=keys.len()).rev() {
+                if let Some(original) = field_mut(&mut base, &keys[..depth]).map(|field| field.clone()) {
+                    if let Some(field) = field_mut(&mut tree, &keys[..depth]) {
+                        *field = original;
+                    }
+                    break;
+                }
+            }
+        }
+        Ok(serde_json::from_value(tree)?)
+    }
+
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let toml = toml::to_string(self)?;
-        let path = std::path::Path::new("mewbot.conf");
-        std::fs::write(path, toml)?;
-        println!("Config saved to: {:?}", path.canonicalize()?);
+        let toml = toml::to_string(&self.without_env_overrides()?)?;
+        let path = paths::resolve(paths::CONFIG_FILE);
+        std::fs::write(&path, toml)?;
+        println!("Config saved to: {:?}", Path::new(&path).canonicalize()?);
         Ok(())
     }
 
@@ -759,7 +2328,7 @@ impl Config {
 
     pub fn save_specific_fields(&self, fields: &[&str]) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut config_to_save = serde_json::Map::new();
-        let full_config = serde_json::to_value(self)?;
+        let full_config = serde_json::to_value(self.without_env_overrides()?)?;
 
         for field in fields {
             if let Some(value) = full_config.get(field) {
@@ -768,7 +2337,7 @@ impl Config {
         }
 
         let config_str = toml::to_string_pretty(&config_to_save)?;
-        std::fs::write(Self::CONFIG_PATH, config_str)?;
+        std::fs::write(paths::resolve(paths::CONFIG_FILE), config_str)?;
         info!("Saved specific fields to config file: {:?}", fields);
         Ok(())
     }
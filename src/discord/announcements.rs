@@ -1,23 +1,27 @@
-use serenity::builder::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateMessage};
+use serenity::builder::{CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateMessage, CreateThread, EditMessage};
 use serenity::model::prelude::*;
 use serenity::http::Http;
 use chrono::{DateTime, Utc};
-use log::info;
+use tracing::info;
 
-pub async fn send_stream_announcement(
-    http: &Http,
-    channel_id: ChannelId,
-    broadcaster_name: &str,
-    started_at: &str,
-    game_name: Option<&str>,
-    title: Option<&str>,
-    thumbnail_url: Option<&str>,
-    profile_image_url: Option<&str>,
-    ai_message: Option<&str>,
-) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    info!("Creating stream announcement for {}", broadcaster_name);
+// Everything needed to (re)build the go-live embed, kept so it can be edited
+// when the title or category changes mid-stream.
+#[derive(Debug, Clone)]
+pub struct StreamAnnouncement {
+    pub broadcaster_name: String,
+    pub started_at: String,
+    pub game_name: Option<String>,
+    pub title: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub profile_image_url: Option<String>,
+    pub ai_message: Option<String>,
+    // e.g. "On a break" or "Live for 2h 10m (1h 55m active)"
+    pub status: Option<String>,
+}
 
-    let started_time = DateTime::parse_from_rfc3339(started_at)
+fn build_stream_embed(announcement: &StreamAnnouncement) -> Result<CreateEmbed, Box<dyn std::error::Error + Send + Sync>> {
+    let broadcaster_name = announcement.broadcaster_name.as_str();
+    let started_time = DateTime::parse_from_rfc3339(&announcement.started_at)
         .map_err(|e| format!("Failed to parse start time: {}", e))?
         .with_timezone(&Utc);
 
@@ -27,35 +31,167 @@ pub async fn send_stream_announcement(
         .url(format!("https://twitch.tv/{}", broadcaster_name))
         .timestamp(started_time);
 
-    if let Some(game) = game_name {
+    if let Some(game) = &announcement.game_name {
         embed = embed.field("Game", game, true);
     }
 
-    if let Some(stream_title) = title {
+    if let Some(stream_title) = &announcement.title {
         embed = embed.field("Title", stream_title, true);
     }
 
-    if let Some(thumbnail) = thumbnail_url {
+    if let Some(status) = &announcement.status {
+        embed = embed.field("Status", status, false);
+    }
+
+    if let Some(thumbnail) = &announcement.thumbnail_url {
         embed = embed.thumbnail(thumbnail);
     }
 
-    if let Some(profile_url) = profile_image_url {
+    if let Some(profile_url) = &announcement.profile_image_url {
         embed = embed.author(CreateEmbedAuthor::new(broadcaster_name)
             .icon_url(profile_url)
             .url(format!("https://twitch.tv/{}", broadcaster_name)));
     }
 
-    if let Some(ai_msg) = ai_message {
+    if let Some(ai_msg) = &announcement.ai_message {
         embed = embed.description(ai_msg);
     }
 
-    embed = embed.footer(CreateEmbedFooter::new("Come join the stream!"));
+    Ok(embed.footer(CreateEmbedFooter::new("Come join the stream!")))
+}
 
-    channel_id.send_message(&http, CreateMessage::default()
-        .content(format!("Hey <@&1147208922265034772>, {} is live!", broadcaster_name))
+pub async fn send_stream_announcement(
+    http: &Http,
+    channel_id: ChannelId,
+    announcement: &StreamAnnouncement,
+) -> Result<MessageId, Box<dyn std::error::Error + Send + Sync>> {
+    info!("Creating stream announcement for {}", announcement.broadcaster_name);
+
+    let embed = build_stream_embed(announcement)?;
+
+    let message = channel_id.send_message(&http, CreateMessage::default()
+        .content(format!("Hey <@&1147208922265034772>, {} is live!", announcement.broadcaster_name))
         .embed(embed)
     ).await?;
 
     info!("Successfully sent stream announcement to Discord");
+    Ok(message.id)
+}
+
+pub async fn edit_stream_announcement(
+    http: &Http,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    announcement: &StreamAnnouncement,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let embed = build_stream_embed(announcement)?;
+    channel_id.edit_message(&http, message_id, EditMessage::new().embed(embed)).await?;
+
+    info!("Updated stream announcement {} in Discord", message_id);
+    Ok(())
+}
+
+// Discord caps thread names at 100 characters
+const MAX_THREAD_NAME: usize = 100;
+
+// A thread under the go-live post for this stream's chat, clips and recap
+pub async fn create_stream_thread(
+    http: &Http,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    announcement: &StreamAnnouncement,
+) -> Result<ChannelId, Box<dyn std::error::Error + Send + Sync>> {
+    let date = DateTime::parse_from_rfc3339(&announcement.started_at)
+        .map(|started| started.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+        .format("%Y-%m-%d");
+    let name = match &announcement.title {
+        Some(title) => format!("{} · {}", date, title),
+        None => format!("{} · {}'s stream", date, announcement.broadcaster_name),
+    };
+    let name: String = name.chars().take(MAX_THREAD_NAME).collect();
+
+    let thread = channel_id.create_thread_from_message(&http, message_id, CreateThread::new(name)
+        .auto_archive_duration(AutoArchiveDuration::OneDay)
+    ).await?;
+
+    info!("Created Discord thread {} for the stream", thread.id);
+    Ok(thread.id)
+}
+
+pub async fn send_stream_recap(
+    http: &Http,
+    thread_id: ChannelId,
+    recap: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let embed = CreateEmbed::default()
+        .color(0x9146FF) // Twitch purple
+        .title("Stream recap")
+        .description(recap)
+        .timestamp(Utc::now());
+
+    thread_id.send_message(&http, CreateMessage::default().embed(embed)).await?;
+
+    info!("Posted the stream recap to Discord");
+    Ok(())
+}
+
+pub async fn send_modlog_entry(
+    http: &Http,
+    channel_id: ChannelId,
+    event_type: &str,
+    user_name: &str,
+    moderator_name: &str,
+    reason: Option<&str>,
+    ends_at: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let color = match event_type {
+        "ban" => 0xE74C3C,
+        "timeout" => 0xF39C12,
+        _ => 0x2ECC71,
+    };
+
+    let mut embed = CreateEmbed::default()
+        .color(color)
+        .title(format!("{} {}", event_type.to_uppercase(), user_name))
+        .field("Moderator", moderator_name, true)
+        .field("Reason", reason.filter(|r| !r.is_empty()).unwrap_or("No reason given"), true)
+        .timestamp(Utc::now());
+
+    if let Some(ends) = ends_at {
+        embed = embed.field("Ends", ends, true);
+    }
+
+    channel_id.send_message(&http, CreateMessage::default().embed(embed)).await?;
+
+    info!("Mirrored {} of {} to Discord modlog", event_type, user_name);
+    Ok(())
+}
+
+pub async fn send_clip_archive_entry(
+    http: &Http,
+    channel_id: ChannelId,
+    clip: &crate::storage::ArchivedClip,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut embed = CreateEmbed::default()
+        .color(0x9146FF) // Twitch purple
+        .title(&clip.title)
+        .url(&clip.url)
+        .field("Channel", &clip.broadcaster_name, true)
+        .field("Clipped by", &clip.creator_name, true)
+        .field("Duration", format!("{:.0}s", clip.duration), true)
+        .footer(CreateEmbedFooter::new(format!("Submitted by {}", clip.submitted_by)))
+        .timestamp(clip.timestamp);
+
+    if let Some(thumbnail) = &clip.thumbnail_url {
+        embed = embed.image(thumbnail);
+    }
+
+    channel_id.send_message(&http, CreateMessage::default()
+        .content(&clip.url)
+        .embed(embed)
+    ).await?;
+
+    info!("Archived clip {} to Discord", clip.clip_id);
     Ok(())
 }
\ No newline at end of file
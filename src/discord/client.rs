@@ -6,14 +6,20 @@ use crate::config::Config;
 use crate::discord::UserLinks;
 use std::sync::Arc;
 use std::time::Duration;
-use log::{info, warn};
+use tracing::{info, warn};
+use serenity::gateway::ShardManager;
 use serenity::http::Http;
 use tokio::sync::{RwLock, Mutex};
 
 use super::events::EventHandler;
 
+// One gateway connection shared by the whole bot. The HTTP client and shard
+// manager are kept outside the mutex so announcements and shutdown don't wait
+// on the running client.
 pub struct DiscordClient {
     client: Arc<Mutex<Option<Client>>>,
+    http: Arc<Http>,
+    shard_manager: Arc<ShardManager>,
 }
 
 impl DiscordClient {
@@ -39,39 +45,32 @@ impl DiscordClient {
             .await?;
 
         Ok(Self {
+            http: client.http.clone(),
+            shard_manager: client.shard_manager.clone(),
             client: Arc::new(Mutex::new(Some(client))),
         })
     }
 
     pub async fn shutdown(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Shutting down DiscordClient...");
-        let mut client_guard = self.client.lock().await;
-        if let Some(client) = client_guard.take() {
-            let shard_manager = client.shard_manager.clone();
-            match tokio::time::timeout(Duration::from_secs(10), shard_manager.shutdown_all()).await {
-                Ok(_) => info!("Discord shards shut down successfully"),
-                Err(_) => warn!("Timed out while shutting down Discord shards"),
-            }
+        match tokio::time::timeout(Duration::from_secs(10), self.shard_manager.shutdown_all()).await {
+            Ok(_) => info!("Discord shards shut down successfully"),
+            Err(_) => warn!("Timed out while shutting down Discord shards"),
         }
         info!("DiscordClient shutdown complete.");
         Ok(())
     }
 
     pub async fn start(&self) -> Result<(), serenity::Error> {
-        let mut client_guard = self.client.lock().await;
-        if let Some(mut client) = client_guard.take() {
-            client.start().await?;
-            *client_guard = Some(client);
-            Ok(())
+        let client = self.client.lock().await.take();
+        if let Some(mut client) = client {
+            client.start().await
         } else {
             Err(serenity::Error::Other("Discord client has already been started"))
         }
     }
 
     pub async fn get_http(&self) -> Arc<Http> {
-        self.client.lock().await
-            .as_ref()
-            .expect("Discord client not initialized")
-            .http.clone()
+        self.http.clone()
     }
 }
\ No newline at end of file
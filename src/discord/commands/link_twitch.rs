@@ -7,7 +7,7 @@ use serenity::builder::CreateInteractionResponseMessage;
 use rand::Rng;
 use crate::discord::UserLinks;
 use std::sync::Arc;
-use log::{error, info};
+use tracing::{error, info};
 
 pub fn register() -> CreateCommand {
     CreateCommand::new("linktwitch")
@@ -5,7 +5,7 @@ use crate::config::Config;
 // use crate::storage::StorageClient;
 use crate::discord::UserLinks;
 use std::sync::Arc;
-use log::{debug, error, info};
+use tracing::{debug, error, info};
 use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
 use tokio::sync::RwLock;
 use crate::discord::commands::{link_twitch, ping};
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+use std::time::Duration;
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tokio::time::timeout;
+use crate::config::{Config, OBSInstanceConfig};
+use crate::obs::{OBSInstance, OBSWebSocketClient};
+use crate::twitch::api::client::OAUTH_SCOPES;
+use crate::twitch::connection_monitor::ConnectionHealth;
+use crate::vrchat::VRChatApi;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+// Where the bot sends OSC; VRChat listens here when OSC is turned on
+const VRCHAT_OSC_PORT: u16 = 9000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    // The integration isn't configured, so there's nothing to check
+    Skipped,
+}
+
+impl CheckStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skipped => "SKIP",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), status, detail: detail.into() }
+    }
+
+    fn from_result(name: &str, result: Result<String, String>) -> Self {
+        match result {
+            Ok(detail) => Self::new(name, CheckStatus::Pass, detail),
+            Err(detail) => Self::new(name, CheckStatus::Fail, detail),
+        }
+    }
+}
+
+// Runs every check against the given config. `twitch_token` overrides the stored access token,
+// so a running bot can pass the one it refreshed.
+pub async fn run(config: &Config, twitch_token: Option<String>) -> Vec<CheckResult> {
+    let client = Client::builder().timeout(CHECK_TIMEOUT).build().unwrap_or_default();
+    let mut results = Vec::new();
+
+    let token = twitch_token.or_else(|| config.twitch_access_token.clone());
+    results.push(match token {
+        Some(token) => CheckResult::from_result("Twitch Helix", check_twitch_scopes(&client, &token).await),
+        None => CheckResult::new("Twitch Helix", CheckStatus::Fail, "not authorized yet, run mewbot --reinit-twitch-token"),
+    });
+
+    let obs = &config.obs_manager;
+    results.push(CheckResult::from_result("OBS", check_obs("Instance1", &obs.instance1).await));
+    if obs.is_dual_pc_setup {
+        results.push(match &obs.instance2 {
+            Some(instance) => CheckResult::from_result("OBS (second PC)", check_obs("Instance2", instance).await),
+            None => CheckResult::new("OBS (second PC)", CheckStatus::Fail, "dual PC setup is on but instance2 isn't configured"),
+        });
+    }
+
+    results.push(match &config.vrchat_auth_cookie {
+        Some(cookie) => CheckResult::from_result("VRChat", check_vrchat(cookie).await),
+        None => CheckResult::new("VRChat", CheckStatus::Skipped, "no auth cookie"),
+    });
+
+    results.push(match &config.discord_token {
+        Some(token) => CheckResult::from_result("Discord", check_discord(&client, token).await),
+        None => CheckResult::new("Discord", CheckStatus::Skipped, "no bot token"),
+    });
+
+    results.push(CheckResult::from_result("VRChat OSC", check_osc_port()));
+
+    let ai_keys = [
+        ("OpenAI", &config.openai_secret, "https://api.openai.com/v1/models"),
+        ("Anthropic", &config.anthropic_secret, "https://api.anthropic.com/v1/models"),
+        ("xAI", &config.xai_secret, "https://api.x.ai/v1/models"),
+    ];
+    for (name, key, url) in ai_keys {
+        results.push(match key.as_deref().filter(|key| !key.is_empty()) {
+            Some(key) => CheckResult::from_result(name, check_ai_key(&client, name, key, url).await),
+            None => CheckResult::new(name, CheckStatus::Skipped, "no API key"),
+        });
+    }
+
+    results
+}
+
+async fn check_twitch_scopes(client: &Client, token: &str) -> Result<String, String> {
+    let response = client.get("https://id.twitch.tv/oauth2/validate")
+        .header("Authorization", format!("OAuth {}", token))
+        .send()
+        .await
+        .map_err(|e| format!("couldn't reach Twitch: {}", e))?;
+    if response.status() == StatusCode::UNAUTHORIZED {
+        return Err("the access token is invalid or expired, run mewbot --reinit-twitch-token".to_string());
+    }
+    let body: Value = response.json().await.map_err(|e| format!("unexpected response: {}", e))?;
+    let granted: Vec<&str> = body["scopes"].as_array()
+        .map(|scopes| scopes.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let missing: Vec<&str> = OAUTH_SCOPES.iter().copied().filter(|scope| !granted.contains(scope)).collect();
+    if !missing.is_empty() {
+        return Err(format!("missing scopes {}, run mewbot --reinit-twitch-token", missing.join(", ")));
+    }
+    Ok(format!("token for {} has all {} scopes", body["login"].as_str().unwrap_or("unknown"), OAUTH_SCOPES.len()))
+}
+
+// Connects and identifies like the bot does, then hangs up
+async fn check_obs(name: &str, instance: &OBSInstanceConfig) -> Result<String, String> {
+    let client = OBSWebSocketClient::new(OBSInstance {
+        name: name.to_string(),
+        address: instance.ip.clone(),
+        port: instance.port,
+        auth_required: instance.auth_required,
+        password: instance.password.clone(),
+        use_ssl: instance.use_ssl,
+    }, Arc::new(ConnectionHealth::default()));
+    let result = timeout(CHECK_TIMEOUT * 2, client.attempt_connect()).await;
+    let _ = client.disconnect().await;
+    match result {
+        Ok(Ok(())) => Ok(format!("handshake with {}:{} succeeded", instance.ip, instance.port)),
+        Ok(Err(e)) => Err(format!("{}:{}: {}", instance.ip, instance.port, e)),
+        Err(_) => Err(format!("{}:{} didn't answer in time", instance.ip, instance.port)),
+    }
+}
+
+async fn check_vrchat(cookie: &str) -> Result<String, String> {
+    let api = VRChatApi::new(Arc::new(RwLock::new(cookie.to_string()))).map_err(|e| e.to_string())?;
+    let user = timeout(CHECK_TIMEOUT, api.current_user()).await
+        .map_err(|_| "VRChat didn't answer in time".to_string())?
+        .map_err(|e| format!("{}, run mewbot --reinit-vrchat-token", e))?;
+    if !user.requires_two_factor_auth.is_empty() {
+        return Err("the session still needs two-factor auth, run mewbot --reinit-vrchat-token".to_string());
+    }
+    Ok(format!("logged in as {}", user.display_name))
+}
+
+// The gateway endpoint needs a valid bot token, so this covers both
+async fn check_discord(client: &Client, token: &str) -> Result<String, String> {
+    let response = client.get("https://discord.com/api/v10/gateway/bot")
+        .header("Authorization", format!("Bot {}", token))
+        .send()
+        .await
+        .map_err(|e| format!("couldn't reach Discord: {}", e))?;
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED {
+        return Err("the bot token was rejected".to_string());
+    }
+    if !status.is_success() {
+        return Err(format!("gateway lookup failed with {}", status));
+    }
+    let body: Value = response.json().await.map_err(|e| format!("unexpected response: {}", e))?;
+    let remaining = body["session_start_limit"]["remaining"].as_u64().unwrap_or(0);
+    if remaining == 0 {
+        return Err("no gateway sessions left today, Discord will refuse to connect".to_string());
+    }
+    Ok(format!("gateway reachable, {} session starts left today", remaining))
+}
+
+// OSC is fire-and-forget UDP, so the best we can do is see whether something holds VRChat's port
+fn check_osc_port() -> Result<String, String> {
+    match std::net::UdpSocket::bind(("0.0.0.0", VRCHAT_OSC_PORT)) {
+        Ok(_) => Err(format!("nothing is listening on port {}, is VRChat running with OSC enabled?", VRCHAT_OSC_PORT)),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Ok(format!("port {} is in use, VRChat looks ready", VRCHAT_OSC_PORT)),
+        Err(e) => Err(format!("couldn't check port {}: {}", VRCHAT_OSC_PORT, e)),
+    }
+}
+
+async fn check_ai_key(client: &Client, name: &str, key: &str, url: &str) -> Result<String, String> {
+    let request = if name == "Anthropic" {
+        client.get(url).header("x-api-key", key).header("anthropic-version", "2023-06-01")
+    } else {
+        client.get(url).bearer_auth(key)
+    };
+    let response = request.send().await.map_err(|e| format!("couldn't reach {}: {}", name, e))?;
+    match response.status() {
+        status if status.is_success() => Ok("API key accepted".to_string()),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err("API key was rejected".to_string()),
+        status => Err(format!("key check failed with {}", status)),
+    }
+}
+
+// One line per check, for the terminal
+pub fn format_report(results: &[CheckResult]) -> String {
+    let width = results.iter().map(|result| result.name.len()).max().unwrap_or(0);
+    results.iter()
+        .map(|result| format!("[{}] {:width$}  {}", result.status.label(), result.name, result.detail, width = width))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Short enough for a chat message: the counts, then what failed and why
+pub fn summarize(results: &[CheckResult]) -> String {
+    let count = |status| results.iter().filter(|result| result.status == status).count();
+    let failures: Vec<String> = results.iter()
+        .filter(|result| result.status == CheckStatus::Fail)
+        .map(|result| format!("{}: {}", result.name, result.detail))
+        .collect();
+    let summary = format!(
+        "{} passed, {} failed, {} skipped",
+        count(CheckStatus::Pass), count(CheckStatus::Fail), count(CheckStatus::Skipped)
+    );
+    if failures.is_empty() {
+        summary
+    } else {
+        format!("{}. {}", summary, failures.join(" | "))
+    }
+}
+
+pub fn all_passed(results: &[CheckResult]) -> bool {
+    results.iter().all(|result| result.status != CheckStatus::Fail)
+}
@@ -0,0 +1,35 @@
+use std::sync::{Arc, RwLock};
+use serde_json::Value;
+use twitch_irc::message::PrivmsgMessage;
+
+#[derive(Debug, Clone)]
+pub enum BotEvent {
+    ChatMessage(PrivmsgMessage),
+    // Raw EventSub notification, e.g. "channel.follow" with its event payload
+    EventSub { event_type: String, payload: Value },
+}
+
+// Hooks run inline on the bot's event paths, so they should return quickly
+// and hand any slow work off to their own task.
+pub type EventHook = Arc<dyn Fn(&BotEvent) + Send + Sync>;
+
+#[derive(Clone, Default)]
+pub struct EventHooks {
+    hooks: Arc<RwLock<Vec<EventHook>>>,
+}
+
+impl EventHooks {
+    pub fn add(&self, hook: EventHook) {
+        self.hooks.write().unwrap().push(hook);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.read().unwrap().is_empty()
+    }
+
+    pub fn emit(&self, event: &BotEvent) {
+        for hook in self.hooks.read().unwrap().iter() {
+            hook(event);
+        }
+    }
+}
@@ -9,20 +9,36 @@ pub mod web_ui;
 mod bot_status;
 pub mod obs;
 pub mod stream_state;
+pub mod backpressure;
+pub mod tasks;
+pub mod hooks;
+pub mod output_filter;
+pub mod scheduler;
+pub mod builder;
+pub mod doctor;
+pub mod paths;
+pub mod chunker;
+pub mod calculator;
+pub mod notifications;
+#[cfg(feature = "bench")]
+pub mod bench;
 
 use bot_status::BotStatus;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use crate::twitch::irc::message_handler::MessageHandler;
+use crate::twitch::irc::triggers::KeywordTriggerManager;
 use crate::config::{Config};
 use crate::vrchat::{VRChatClient, VRChatManager};
 use crate::vrchat::World;
 use crate::ai::AIClient;
 use std::time::Duration;
-use log::{error, info, warn};
+use tracing::{error, info, warn};
 use tokio::task::JoinHandle;
 use crate::discord::UserLinks;
-use crate::osc::{OSCManager};
+use crate::osc::{OSCHeartbeat, OSCManager};
+use crate::notifications::Notifier;
+use crate::output_filter::OutputFilter;
 use crate::storage::StorageClient;
 use crate::web_ui::websocket::{DashboardState};
 use tokio::sync::mpsc;
@@ -30,6 +46,13 @@ use crate::obs::{OBSInstance, OBSManager};
 use crate::stream_state::StreamStateMachine;
 use crate::twitch::TwitchManager;
 use crate::web_ui::websocket::WebSocketMessage;
+use crate::scheduler::{JobSettings, Schedule, Scheduler};
+use crate::tasks::TaskRegistry;
+use crate::twitch::connection_monitor::{AlertTargets, ConnectionHealth};
+
+pub use builder::{MewBot, MewBotBuilder, Subsystems};
+pub use hooks::{BotEvent, EventHook};
+pub use twitch::irc::command_system::{Command, CommandContext, CommandPlugin, CommandRegistry};
 
 pub struct BotClients {
     pub twitch_manager: Arc<TwitchManager>,
@@ -43,6 +66,8 @@ pub struct BotClients {
     pub dashboard_state: Arc<RwLock<DashboardState>>,
     pub websocket_tx: mpsc::UnboundedSender<WebSocketMessage>,
     pub websocket_rx: Option<mpsc::UnboundedReceiver<WebSocketMessage>>,
+    // Background loops of this instance, stopped on shutdown
+    pub tasks: Arc<TaskRegistry>,
 }
 
 impl BotClients {
@@ -92,11 +117,19 @@ impl BotClients {
 }
 
 pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std::error::Error + Send + Sync>> {
+    init_with(config, &Subsystems::default()).await
+}
+
+pub(crate) async fn init_with(config: Arc<RwLock<Config>>, subsystems: &Subsystems) -> Result<BotClients, Box<dyn std::error::Error + Send + Sync>> {
     let bot_status = BotStatus::new();
 
     let (websocket_tx, websocket_rx) = mpsc::unbounded_channel::<WebSocketMessage>();
 
+    let tasks = Arc::new(TaskRegistry::new());
+    let scheduler = Arc::new(Scheduler::load(&paths::resolve(paths::SCHEDULED_JOBS), tasks.clone()));
+
     let config_read = config.read().await;
+    let output_filter = Arc::new(OutputFilter::new(&config_read.output_filter));
     let ai_client = if let Some(openai_secret) = &config_read.openai_secret {
         Some(Arc::new(AIClient::new(
             Some(openai_secret.clone()),
@@ -105,13 +138,13 @@ pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std
             config_read.google_search_api_key.clone(),
             config_read.google_search_cx.clone(),
             config_read.bing_search_api_key.clone(),
-        )))
+        ).with_output_filter(output_filter.clone())))
     } else {
         None
     };
     drop(config_read);
 
-    let vrchat = if config.read().await.is_vrchat_configured() {
+    let vrchat = if subsystems.vrchat && config.read().await.is_vrchat_configured() {
         match VRChatClient::new(config.clone(), websocket_tx.clone()).await {
             Ok(vrchat_client) => {
                 info!("VRChat client initialized successfully.");
@@ -126,7 +159,7 @@ pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std
         None
     };
 
-    let osc_manager = match OSCManager::new("127.0.0.1:9000").await {
+    let osc_manager = match OSCManager::new("127.0.0.1:9000", output_filter.clone()).await {
         Ok(manager) => {
             info!("OSCManager initialized successfully.");
             Arc::new(manager)
@@ -137,10 +170,10 @@ pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std
         }
     };
 
-    let storage = Arc::new(RwLock::new(StorageClient::new("mewbot_data.db")?));
+    let storage = Arc::new(RwLock::new(StorageClient::new(&paths::resolve(paths::DATABASE))?));
     let user_links = Arc::new(UserLinks::new());
 
-    let discord = if config.read().await.is_discord_configured() {
+    let discord = if subsystems.discord && config.read().await.is_discord_configured() {
         Some(Arc::new(discord::DiscordClient::new(
             config.clone(),
             // storage.clone(),
@@ -152,24 +185,34 @@ pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std
 
     let dashboard_state = Arc::new(RwLock::new(DashboardState::new(
         bot_status.clone(),
+        &config.read().await.broadcast.dashboard,
     )));
 
-    let obs_manager = Arc::new(OBSManager::new(websocket_tx.clone()));
+    let notifier = Arc::new(Notifier::new(config.clone()));
+    let connections = Arc::new(ConnectionHealth::new(Some(AlertTargets {
+        config: config.clone(),
+        dashboard_state: dashboard_state.clone(),
+        discord_client: discord.clone(),
+        notifier: notifier.clone(),
+    })));
+    let obs_manager = Arc::new(OBSManager::new(websocket_tx.clone(), connections.clone()));
 
     // Initialize OBS instances from config
     let obs_config = config.read().await.obs_manager.clone();
-    if let Err(e) = obs_manager.add_instance("Instance1".to_string(), OBSInstance {
-        name: "Instance1".to_string(),
-        address: obs_config.instance1.ip.clone(),
-        port: obs_config.instance1.port,
-        auth_required: obs_config.instance1.auth_required,
-        password: obs_config.instance1.password.clone(),
-        use_ssl: obs_config.instance1.use_ssl,
-    }).await {
-        warn!("Failed to add OBS Instance1: {}. Continuing without this instance.", e);
+    if subsystems.obs {
+        if let Err(e) = obs_manager.add_instance("Instance1".to_string(), OBSInstance {
+            name: "Instance1".to_string(),
+            address: obs_config.instance1.ip.clone(),
+            port: obs_config.instance1.port,
+            auth_required: obs_config.instance1.auth_required,
+            password: obs_config.instance1.password.clone(),
+            use_ssl: obs_config.instance1.use_ssl,
+        }).await {
+            warn!("Failed to add OBS Instance1: {}. Continuing without this instance.", e);
+        }
     }
 
-    if obs_config.is_dual_pc_setup {
+    if subsystems.obs && obs_config.is_dual_pc_setup {
         if let Some(instance2) = obs_config.instance2 {
             if let Err(e) = obs_manager.add_instance("Instance2".to_string(), OBSInstance {
                 name: "Instance2".to_string(),
@@ -184,14 +227,6 @@ pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std
         }
     }
 
-    let vrchat_manager = vrchat.as_ref().map(|vrchat_client| {
-        Arc::new(VRChatManager::new(
-            Arc::clone(vrchat_client),
-            dashboard_state.clone(),
-            Some(osc_manager.clone()),  // Wrap in Some()
-        ))
-    });
-
     let config = Arc::new(config.read().await.clone());
     let stream_state_machine = StreamStateMachine::new();
 
@@ -204,14 +239,34 @@ pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std
         dashboard_state.clone(),
         websocket_tx.clone(),
         stream_state_machine.clone(),
+        subsystems.obs.then(|| obs_manager.clone()),
+        discord.clone(),
+        tasks.clone(),
+        scheduler,
+        connections,
+        output_filter,
+        notifier,
     ).await?;
+    twitch_manager.bot_voice.configure(&twitch_manager.config.bot_voice);
+    twitch_manager.tts_queue.configure(&twitch_manager.config.tts_moderation);
+    twitch_manager.profiles.configure(&twitch_manager.config.profiles, &twitch_manager.bot_voice, &twitch_manager.scheduler);
 
     twitch_manager.initialize().await?;
 
+    let vrchat_manager = vrchat.as_ref().map(|vrchat_client| {
+        Arc::new(VRChatManager::new(
+            Arc::clone(vrchat_client),
+            dashboard_state.clone(),
+            Some(osc_manager.clone()),  // Wrap in Some()
+            twitch_manager.world_changes.clone(),
+            twitch_manager.prompts.clone(),
+        ))
+    });
+
     let clients = BotClients {
         twitch_manager: twitch_manager.clone().into(),
         vrchat: vrchat_manager,
-        obs: Some(obs_manager),
+        obs: subsystems.obs.then_some(obs_manager),
         discord,
         ai_client,
         osc_manager,
@@ -220,6 +275,7 @@ pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std
         dashboard_state,
         websocket_tx,
         websocket_rx: Some(websocket_rx),
+        tasks,
     };
 
     clients.bot_status.write().await.set_online(true);
@@ -227,39 +283,149 @@ pub async fn init(config: Arc<RwLock<Config>>) -> Result<BotClients, Box<dyn std
     Ok(clients)
 }
 
-pub async fn run(mut clients: BotClients, config: Arc<RwLock<Config>>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+pub async fn run(clients: BotClients, config: Arc<RwLock<Config>>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    run_with(clients, config, &Subsystems::default()).await
+}
+
+pub(crate) async fn run_with(mut clients: BotClients, config: Arc<RwLock<Config>>, subsystems: &Subsystems) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let mut handles: Vec<JoinHandle<Result<(), Box<dyn std::error::Error + Send + Sync>>>> = vec![];
 
-    let web_ui = Arc::new(web_ui::WebUI::new(
+    let keyword_triggers = Arc::new(KeywordTriggerManager::new(&paths::resolve(paths::KEYWORD_TRIGGERS)));
+
+    // Chat commands and the dashboard always get an OBS manager; without the OBS subsystem it has no instances
+    let obs_manager = match &clients.obs {
+        Some(obs_manager) => obs_manager.clone(),
+        None => Arc::new(OBSManager::new(clients.websocket_tx.clone(), clients.twitch_manager.connections.clone())),
+    };
+
+    if subsystems.web_ui {
+        clients.twitch_manager.add_event_hook(Arc::new({
+            let compat_events = clients.twitch_manager.compat_events.clone();
+            move |event: &hooks::BotEvent| compat_events.forward_event(event)
+        }));
+        clients.twitch_manager.add_event_hook(Arc::new({
+            let ticker = clients.twitch_manager.ticker.clone();
+            move |event: &hooks::BotEvent| ticker.on_event(event)
+        }));
+    }
+
+    let hype_config = config.read().await.hype_meter.clone();
+    if hype_config.enabled {
+        let hype_meter = Arc::new(osc::HypeMeter::new(hype_config, clients.osc_manager.clone()));
+        clients.twitch_manager.add_event_hook(Arc::new({
+            let hype_meter = hype_meter.clone();
+            move |event: &hooks::BotEvent| hype_meter.on_event(event)
+        }));
+        clients.tasks.spawn("hype_meter", async move {
+            hype_meter.run().await;
+        });
+    }
+
+    let web_ui = subsystems.web_ui.then(|| Arc::new(web_ui::WebUI::new(
         config.clone(),
         clients.storage.clone(),
         clients.bot_status.clone(),
         clients.twitch_manager.irc_manager.clone(),
         clients.dashboard_state.clone(),
-        clients.obs.clone().expect("OBS manager should be initialized"),
-        clients.vrchat.clone().expect("VRChat manager should be initialized"),
-    ));
+        obs_manager.clone(),
+        clients.vrchat.clone(),
+        keyword_triggers.clone(),
+        clients.twitch_manager.get_redeem_manager(),
+        clients.twitch_manager.get_osc_configs(),
+        clients.osc_manager.get_vrchat_osc(),
+        clients.ai_client.clone(),
+        clients.twitch_manager.get_api_client(),
+        clients.twitch_manager.clone(),
+    )));
 
     let shutdown_signal = Arc::new(tokio::sync::Notify::new());
 
-    let _web_ui_handle = tokio::spawn({
-        let web_ui = web_ui.clone();
-        let shutdown_signal = shutdown_signal.clone();
-        async move {
-            web_ui.run(async move {
-                shutdown_signal.notified().await;
-            }).await
+    if let Some(web_ui) = &web_ui {
+        let _web_ui_handle = tokio::spawn({
+            let web_ui = web_ui.clone();
+            let shutdown_signal = shutdown_signal.clone();
+            async move {
+                web_ui.run(async move {
+                    shutdown_signal.notified().await;
+                }).await
+            }
+        });
+    }
+
+    if subsystems.redeems {
+        info!("Initializing channel point redeems...");
+        if let Some(redeem_manager) = clients.twitch_manager.redeem_manager.write().await.as_mut() {
+            redeem_manager.initialize_redeems().await?;
+
+            clients.tasks.spawn("redeem_queue_ticker", twitch::redeems::queue::run_queue_ticker(
+                redeem_manager.get_queue(),
+                clients.osc_manager.get_vrchat_osc(),
+                clients.dashboard_state.clone(),
+            ));
         }
-    });
+        clients.tasks.spawn("reward_price_decay", twitch::redeems::pricing::run_decay_ticker(clients.twitch_manager.clone()));
+
+        twitch::happy_hour::resume(clients.twitch_manager.clone()).await;
+        let happy_hour_config = config.read().await.happy_hour.clone();
+        if let Some(expr) = happy_hour_config.schedule.clone() {
+            let twitch_manager = clients.twitch_manager.clone();
+            clients.twitch_manager.scheduler.register(
+                "happy_hour",
+                "Starts a scheduled happy hour",
+                JobSettings {
+                    schedule: Schedule::Cron { expr },
+                    jitter_secs: 0,
+                    enabled: true,
+                    catch_up: false,
+                },
+                move || {
+                    let twitch_manager = twitch_manager.clone();
+                    let config = happy_hour_config.clone();
+                    async move {
+                        let duration = chrono::Duration::seconds(config.scheduled_duration_secs as i64);
+                        let happy_hour = twitch::happy_hour::start(twitch_manager.clone(), &config, duration).await?;
+                        twitch::happy_hour::announce(&twitch_manager, &twitch::happy_hour::describe(&happy_hour)).await;
+                        Ok(())
+                    }
+                },
+            );
+        }
+
+        let poll_interval = config.read().await.reward_announcements.poll_interval_secs.max(30);
+        let redeem_manager = clients.twitch_manager.get_redeem_manager();
+        clients.twitch_manager.scheduler.register(
+            "reward_watcher",
+            "Checks Twitch for rewards created outside the bot",
+            JobSettings::every(poll_interval),
+            move || {
+                let redeem_manager = redeem_manager.clone();
+                async move {
+                    match redeem_manager.read().await.as_ref() {
+                        Some(manager) => manager.update_from_twitch().await.map_err(|e| e.to_string()),
+                        None => Ok(()),
+                    }
+                }
+            },
+        );
 
-    info!("Initializing channel point redeems...");
-    if let Some(redeem_manager) = clients.twitch_manager.redeem_manager.write().await.as_mut() {
-        redeem_manager.initialize_redeems().await?;
+        let twitch_manager = clients.twitch_manager.clone();
+        clients.twitch_manager.scheduler.register(
+            "follower_snapshot",
+            "Records the follower count for growth tracking",
+            JobSettings::every(twitch::follower_growth::SNAPSHOT_INTERVAL_SECS).with_catch_up(),
+            move || {
+                let twitch_manager = twitch_manager.clone();
+                async move {
+                    twitch::follower_growth::snapshot(&twitch_manager).await.map(|_| ())
+                }
+            },
+        );
     }
 
     info!("Setting up Twitch IRC message handling...");
     let _channel = config.read().await.twitch_channel_to_join.clone()
         .ok_or("Twitch channel to join not set")?;
+    twitch::role_grants::resume(clients.twitch_manager.clone()).await;
 
     let world_info = Arc::new(Mutex::new(None::<World>));
     let message_handler = Arc::new(MessageHandler::new(
@@ -268,9 +434,10 @@ pub async fn run(mut clients: BotClients, config: Arc<RwLock<Config>>) -> Result
         clients.storage.clone(),
         clients.websocket_tx.clone(),
         world_info,
-        clients.vrchat.clone().expect("VRChatClient should be initialized"),
+        clients.vrchat.clone(),
         clients.ai_client.clone(),
-        clients.obs.clone().expect("OBS manager should be initialized"), // Add this line
+        obs_manager.clone(),
+        keyword_triggers.clone(),
     ));
 
     // Start only one message handler
@@ -309,46 +476,51 @@ pub async fn run(mut clients: BotClients, config: Arc<RwLock<Config>>) -> Result
         clients.dashboard_state.write().await.update_discord_status(true).await;
     }
 
-    let eventsub_client = clients.twitch_manager.eventsub_client.clone();
-    let eventsub_handle = tokio::spawn({
-        let eventsub_client = eventsub_client.clone();
-        async move {
-            info!("Starting EventSub client");
-            let client = eventsub_client.lock().await;
-            if let Some(client) = client.as_ref() {
-                loop {
-                    match client.connect_and_listen().await {
-                        Ok(_) => {},
-                        Err(e) => {
-                            error!("EventSub client error: {:?}", e);
-                            // Optional: add a delay before attempting to reconnect
-                            tokio::time::sleep(Duration::from_secs(5)).await;
+    if subsystems.eventsub {
+        let eventsub_client = clients.twitch_manager.eventsub_client.clone();
+        let eventsub_handle = tokio::spawn({
+            let eventsub_client = eventsub_client.clone();
+            async move {
+                info!("Starting EventSub client");
+                let client = eventsub_client.lock().await;
+                if let Some(client) = client.as_ref() {
+                    loop {
+                        match client.connect_and_listen().await {
+                            Ok(_) => {},
+                            Err(e) => {
+                                error!("EventSub client error: {:?}", e);
+                                // Optional: add a delay before attempting to reconnect
+                                tokio::time::sleep(Duration::from_secs(5)).await;
+                            }
                         }
                     }
+                } else {
+                    error!("EventSub client is not initialized");
                 }
-            } else {
-                error!("EventSub client is not initialized");
+                Ok(()) as Result<(), Box<dyn std::error::Error + Send + Sync>>
             }
-            Ok(()) as Result<(), Box<dyn std::error::Error + Send + Sync>>
-        }
-    });
-    handles.push(eventsub_handle);
-    info!("EventSub client started.");
-
-    let eventsub_client_clone = clients.twitch_manager.eventsub_client.clone();
-    tokio::spawn(async move {
-        loop {
-            tokio::time::sleep(Duration::from_secs(3600)).await;
-            let client = eventsub_client_clone.lock().await;
-            if let Some(client) = client.as_ref() {
-                if let Err(e) = client.refresh_token_periodically().await {
-                    error!("Failed to refresh token: {:?}", e);
+        });
+        handles.push(eventsub_handle);
+        info!("EventSub client started.");
+
+        let eventsub_client_clone = clients.twitch_manager.eventsub_client.clone();
+        clients.twitch_manager.scheduler.register(
+            "token_refresh",
+            "Refreshes the EventSub access token",
+            JobSettings::every(3600).with_catch_up(),
+            move || {
+                let eventsub_client = eventsub_client_clone.clone();
+                async move {
+                    let client = eventsub_client.lock().await;
+                    match client.as_ref() {
+                        Some(client) => client.refresh_token_periodically().await
+                            .map_err(|e| format!("Failed to refresh token: {:?}", e)),
+                        None => Err("EventSub client is not initialized".to_string()),
+                    }
                 }
-            } else {
-                error!("EventSub client is not initialized");
-            }
-        }
-    });
+            },
+        );
+    }
 
     if let Some(vrchat_client) = &clients.vrchat {
         let current_user_id = vrchat_client.get_current_user_id().await?;
@@ -356,12 +528,14 @@ pub async fn run(mut clients: BotClients, config: Arc<RwLock<Config>>) -> Result
         let vrchat_handle = tokio::spawn({
             let dashboard_state = clients.dashboard_state.clone();
             let vrchat_client = Arc::clone(vrchat_client);
+            let connections = clients.twitch_manager.connections.clone();
             async move {
                 let result = crate::vrchat::websocket::handler(
                     auth_cookie,
                     current_user_id,
                     vrchat_client,
-                    dashboard_state.clone()
+                    dashboard_state.clone(),
+                    connections,
                 ).await;
                 if let Err(e) = &result {
                     error!("VRChat websocket handler error: {:?}", e);
@@ -377,9 +551,9 @@ pub async fn run(mut clients: BotClients, config: Arc<RwLock<Config>>) -> Result
 
     // Start the WebSocket handler
     let ws_handle = tokio::spawn({
-        let obs_manager = clients.obs.clone().expect("OBS manager should be initialized");
+        let obs_manager = obs_manager.clone();
         let twitch_manager = clients.twitch_manager.clone();
-        let vrchat_manager = clients.vrchat.clone().expect("VRChat manager should be initialized");
+        let vrchat_manager = clients.vrchat.clone();
         let websocket_rx = clients.websocket_rx.take();
         async move {
             if let Some(mut rx) = websocket_rx {
@@ -397,6 +571,98 @@ pub async fn run(mut clients: BotClients, config: Arc<RwLock<Config>>) -> Result
     });
     handles.push(ws_handle);
 
+    let osc_heartbeat = Arc::new(OSCHeartbeat::new(
+        clients.osc_manager.clone(),
+        clients.twitch_manager.get_osc_configs(),
+        clients.dashboard_state.clone(),
+        clients.obs.clone(),
+    ));
+    clients.tasks.spawn("osc_heartbeat", {
+        let osc_heartbeat = osc_heartbeat.clone();
+        async move {
+            osc_heartbeat.run().await;
+        }
+    });
+    info!("OSC heartbeat started.");
+
+    if config.read().await.avatar_changes.enabled {
+        let avatar_watcher = crate::vrchat::avatars::AvatarWatcher::new(
+            config.clone(),
+            clients.twitch_manager.clone(),
+            clients.storage.clone(),
+            clients.dashboard_state.clone(),
+            clients.vrchat.as_ref().map(|vrchat_manager| vrchat_manager.api()),
+        );
+        clients.tasks.spawn("avatar_watcher", async move {
+            avatar_watcher.run().await;
+        });
+    }
+
+    let obs_preview = config.read().await.obs_preview.clone();
+    if obs_preview.enabled && web_ui.is_some() {
+        if let Some(obs_manager) = clients.obs.clone() {
+            clients.tasks.spawn("obs_preview", obs::preview::run_preview_loop(
+                obs_manager,
+                clients.dashboard_state.clone(),
+                obs_preview,
+            ));
+        }
+    }
+
+    if let Some(obs_manager) = clients.obs.clone() {
+        let health_monitor = obs::health::StreamHealthMonitor::new(
+            config.clone(),
+            obs_manager,
+            clients.storage.clone(),
+            clients.dashboard_state.clone(),
+            clients.discord.clone(),
+        );
+        clients.tasks.spawn("stream_health", async move {
+            health_monitor.run().await;
+        });
+    }
+
+    if config.read().await.chatter_drops.enabled {
+        clients.tasks.spawn("chatter_drops", twitch::chatter_drops::run(clients.twitch_manager.clone()));
+    }
+
+    if config.read().await.stream_segments.enabled {
+        clients.tasks.spawn("stream_segments", twitch::stream_segments::run(clients.twitch_manager.clone()));
+    }
+
+    let predictions_config = config.read().await.predictions.clone();
+    if predictions_config.enabled {
+        let twitch_manager = clients.twitch_manager.clone();
+        clients.twitch_manager.scheduler.register(
+            "prediction_resolver",
+            "Settles the open prediction when its configured condition is met",
+            JobSettings::every(predictions_config.check_interval_secs.max(10)),
+            move || {
+                let twitch_manager = twitch_manager.clone();
+                let config = predictions_config.clone();
+                async move {
+                    twitch::predictions::check(&twitch_manager, &config).await.map_err(|e| e.to_string())
+                }
+            },
+        );
+    }
+
+    let user_manager = clients.twitch_manager.user_manager.clone();
+    clients.twitch_manager.scheduler.register(
+        "role_refresh",
+        "Re-checks the roles of everyone in the user cache",
+        JobSettings::every(900).with_jitter(60),
+        move || {
+            let user_manager = user_manager.clone();
+            async move {
+                user_manager.refresh_roles().await;
+                Ok(())
+            }
+        },
+    );
+
+    clients.tasks.spawn("scheduler", clients.twitch_manager.scheduler.clone().run());
+
     info!("Bot is now running. Press Ctrl+C to exit.");
 
     let _ctrl_c_signal = shutdown_signal.clone();
@@ -406,14 +672,11 @@ pub async fn run(mut clients: BotClients, config: Arc<RwLock<Config>>) -> Result
 
     let (web_ui_shutdown_tx, web_ui_shutdown_rx) = tokio::sync::oneshot::channel();
 
-    let web_ui_handle = tokio::spawn({
-        let web_ui = web_ui.clone();
-        async move {
-            web_ui.run(async move {
-                web_ui_shutdown_rx.await.ok();
-            }).await
-        }
-    });
+    let web_ui_handle = web_ui.clone().map(|web_ui| tokio::spawn(async move {
+        web_ui.run(async move {
+            web_ui_shutdown_rx.await.ok();
+        }).await
+    }));
 
     tokio::spawn(async move {
         tokio::signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
@@ -440,12 +703,18 @@ pub async fn run(mut clients: BotClients, config: Arc<RwLock<Config>>) -> Result
             let _ = web_ui_shutdown_tx.send(());
 
             // Wait for the Web UI to shut down (with a timeout)
-            match tokio::time::timeout(std::time::Duration::from_secs(10), web_ui_handle).await {
-                Ok(Ok(_)) => info!("Web UI shut down successfully."),
-                Ok(Err(e)) => warn!("Web UI shut down with error: {:?}", e),
-                Err(_) => warn!("Timed out waiting for Web UI to shut down."),
+            if let Some(web_ui_handle) = web_ui_handle {
+                match tokio::time::timeout(std::time::Duration::from_secs(10), web_ui_handle).await {
+                    Ok(Ok(_)) => info!("Web UI shut down successfully."),
+                    Ok(Err(e)) => warn!("Web UI shut down with error: {:?}", e),
+                    Err(_) => warn!("Timed out waiting for Web UI to shut down."),
+                }
             }
 
+            // Stop background loops first so nothing keeps using the clients shut down below
+            clients.tasks.shutdown(Duration::from_secs(5)).await;
+            osc_heartbeat.send_offline().await;
+
             clients.shutdown().await
         }
     };
@@ -1,12 +1,17 @@
-use clap::{Parser, ArgAction};
-use mewbot::{config::Config, init, run};
-use std::sync::Arc;
+use clap::{Parser, Subcommand, ArgAction};
+use mewbot::{config::{Config, TelemetryConfig}, doctor, paths, MewBotBuilder};
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
-use log::{error, info, LevelFilter};
-use fern::colors::{Color, ColoredLevelConfig};
+use tracing::{error, info, Level};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::filter::{filter_fn, Targets};
+use tracing_subscriber::fmt::time::ChronoLocal;
+use tracing_subscriber::prelude::*;
+#[cfg(feature = "otlp")]
+use tracing_subscriber::Layer;
 use chrono::Local;
 use std::{fs, panic};
-use std::path::Path;
+use std::path::PathBuf;
 
 /// MewBot - A Twitch and VRChat bot
 #[derive(Parser, Debug)]
@@ -31,86 +36,174 @@ struct Args {
     /// Reinitialize VRChat token
     #[arg(long, action = ArgAction::SetTrue)]
     reinit_vrchat_token: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
 }
 
-fn setup_logger(log_level: LevelFilter, single_level: bool) -> Result<(), fern::InitError> {
-    // Configure colors for log levels
-    let colors = ColoredLevelConfig::new()
-        .error(Color::Red)
-        .warn(Color::Yellow)
-        .info(Color::Green)
-        .debug(Color::Blue)
-        .trace(Color::Magenta);
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Check every configured integration and print a pass/fail report
+    Doctor,
+    /// Create an admin API token for the dashboard; needed once web_ui_public_url is set
+    AdminToken {
+        /// Shown in the dashboard's list of tokens
+        #[arg(long, default_value = "dashboard")]
+        name: String,
+    },
+    /// Push synthetic chat and redemptions through the bot and report throughput and latency
+    #[cfg(feature = "bench")]
+    Bench {
+        /// Chat messages per second
+        #[arg(long, default_value_t = 20)]
+        messages_per_sec: u32,
+        /// Channel point redemptions per minute
+        #[arg(long, default_value_t = 30)]
+        redemptions_per_min: u32,
+        /// How long to keep the load up
+        #[arg(long, default_value_t = 30)]
+        duration_secs: u64,
+        /// Distinct synthetic chatters
+        #[arg(long, default_value_t = 200)]
+        chatters: u32,
+        /// Share of chat messages that are commands, from 0 to 1
+        #[arg(long, default_value_t = 0.2)]
+        command_share: f64,
+    },
+}
 
+fn setup_logger(log_level: LevelFilter, single_level: bool, telemetry: &TelemetryConfig) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Create logs directory if it doesn't exist
-    let logs_dir = Path::new("logs");
-    fs::create_dir_all(logs_dir)?;
+    let logs_dir = PathBuf::from(paths::resolve(paths::LOG_DIR));
+    fs::create_dir_all(&logs_dir)?;
 
     // Generate a unique log file name based on the current date and time
     let log_file_name = Local::now().format("mewbot_%Y-%m-%d_%H-%M-%S.log").to_string();
-    let log_file_path = logs_dir.join(log_file_name);
-
-    // Define a list of modules to filter out or set to a higher log level
-    let filtered_modules = vec![
-        "tokio_tungstenite",
-        "tungstenite",
-        "hyper_util",
-        "serenity",
-        "tracing::span",
-        "twitch_irc",
-    ];
-
-    // Build the logger
-    let dispatch = fern::Dispatch::new()
-        .format(move |out, message, record| {
-            if !single_level || record.level() == log_level {
-                out.finish(format_args!(
-                    "{}[{}][{}] {}",
-                    Local::now().format("[%Y-%m-%d][%H:%M:%S]"),
-                    record.target(),
-                    colors.color(record.level()),
-                    message
-                ))
-            }
-        })
-        .level(log_level)
-        .level_for("serenity", LevelFilter::Error)
-        .level_for("twitch_irc", LevelFilter::Warn)
-        .level_for("twitch_irc::connection", LevelFilter::Error)
-        .level_for("twitch_irc::client", LevelFilter::Error)
-        .level_for("mewbot::discord::events::handler", LevelFilter::Warn)
-        .level_for("mewbot::twitch::connection_monitor", LevelFilter::Warn)
-        .filter(move |metadata| {
-            // Filter out specified modules
-            !filtered_modules.iter().any(|&module| metadata.target().starts_with(module))
-                && !(metadata.level() <= log::Level::Info && metadata.target().contains("do_heartbeat"))
-        })
-        .chain(std::io::stdout())
-        .chain(fern::log_file(log_file_path)?);
-
-    // Apply the logger configuration
-    dispatch.apply()?;
+    let log_file = fs::File::create(logs_dir.join(log_file_name))?;
+
+    // Noisy dependencies are turned off or down; everything else follows the chosen level
+    let targets = Targets::new()
+        .with_default(log_level)
+        .with_target("tokio_tungstenite", LevelFilter::OFF)
+        .with_target("tungstenite", LevelFilter::OFF)
+        .with_target("hyper_util", LevelFilter::OFF)
+        .with_target("serenity", LevelFilter::OFF)
+        .with_target("twitch_irc", LevelFilter::OFF)
+        .with_target("mewbot::discord::events::handler", LevelFilter::WARN)
+        .with_target("mewbot::twitch::connection_monitor", LevelFilter::WARN);
+
+    let extra_filter = filter_fn(move |metadata| {
+        // Spans always pass so their fields reach the events inside them
+        if metadata.is_span() {
+            return true;
+        }
+        (!single_level || LevelFilter::from_level(*metadata.level()) == log_level)
+            && !(*metadata.level() <= Level::INFO && metadata.target().contains("do_heartbeat"))
+    });
+
+    // Span fields (channel, user, command, request id) are printed in front of each line
+    let timer = ChronoLocal::new("[%Y-%m-%d][%H:%M:%S]".to_string());
+    let console = tracing_subscriber::fmt::layer()
+        .with_timer(timer.clone())
+        .with_writer(std::io::stdout);
+    let file = tracing_subscriber::fmt::layer()
+        .with_timer(timer)
+        .with_ansi(false)
+        .with_writer(Mutex::new(log_file));
+
+    tracing_subscriber::registry()
+        .with(console)
+        .with(file)
+        .with(otlp_layer(telemetry)?)
+        .with(targets)
+        .with(extra_filter)
+        .try_init()?;
 
     Ok(())
 }
 
+// Exports spans to an OpenTelemetry collector when an endpoint is configured
+#[cfg(feature = "otlp")]
+fn otlp_layer<S>(telemetry: &TelemetryConfig) -> Result<Option<impl Layer<S>>, Box<dyn std::error::Error + Send + Sync>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::{runtime, trace::TracerProvider, Resource};
+
+    let endpoint = match &telemetry.otlp_endpoint {
+        Some(endpoint) if !endpoint.trim().is_empty() => endpoint.trim(),
+        _ => return Ok(None),
+    };
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new("service.name", telemetry.service_name.clone())]))
+        .build();
+    let tracer = provider.tracer("mewbot");
+    opentelemetry::global::set_tracer_provider(provider);
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+#[cfg(not(feature = "otlp"))]
+fn otlp_layer(telemetry: &TelemetryConfig) -> Result<Option<tracing_subscriber::layer::Identity>, Box<dyn std::error::Error + Send + Sync>> {
+    if telemetry.otlp_endpoint.as_deref().map_or(false, |e| !e.trim().is_empty()) {
+        eprintln!("An OTLP endpoint is configured but this build doesn't have the otlp feature; spans won't be exported.");
+    }
+    Ok(None)
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let args = Args::parse();
 
     let mut config = Config::new()?;
 
+    // Also runs without the logger, so only the report is printed
+    #[cfg(feature = "bench")]
+    if let Some(Commands::Bench { messages_per_sec, redemptions_per_min, duration_secs, chatters, command_share }) = args.command {
+        let options = mewbot::bench::BenchOptions { messages_per_sec, redemptions_per_min, duration_secs, chatters, command_share };
+        println!("Running for {}s: {} messages/s, {} redemptions/min...", duration_secs, messages_per_sec, redemptions_per_min);
+        let report = mewbot::bench::run(config, options).await?;
+        println!("{}", mewbot::bench::format_report(&report));
+        return Ok(());
+    }
+
+    // Runs before the logger is set up so the report isn't buried in connection logs
+    if let Some(Commands::Doctor) = args.command {
+        let results = doctor::run(&config, None).await;
+        println!("{}", doctor::format_report(&results));
+        println!("\n{}", doctor::summarize(&results));
+        if !doctor::all_passed(&results) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Commands::AdminToken { name }) = &args.command {
+        let storage = mewbot::storage::StorageClient::new(&paths::resolve(paths::DATABASE))?;
+        let token = mewbot::web_ui::create_admin_token(&storage, name)?;
+        println!("{}", token);
+        println!("Open the dashboard once with ?token=<token> after its address; the browser remembers it.");
+        return Ok(());
+    }
+
     // Set log level
     let log_level = if let Some(level) = args.log_level {
         match level.to_lowercase().as_str() {
-            "error" => LevelFilter::Error,
-            "warn" => LevelFilter::Warn,
-            "info" => LevelFilter::Info,
-            "debug" => LevelFilter::Debug,
-            "trace" => LevelFilter::Trace,
+            "error" => LevelFilter::ERROR,
+            "warn" => LevelFilter::WARN,
+            "info" => LevelFilter::INFO,
+            "debug" => LevelFilter::DEBUG,
+            "trace" => LevelFilter::TRACE,
             _ => {
                 eprintln!("Invalid log level. Using default (INFO).");
-                LevelFilter::Info
+                LevelFilter::INFO
             }
         }
     } else {
@@ -118,7 +211,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     };
 
     // Initialize logger
-    setup_logger(log_level, args.single_level)?;
+    setup_logger(log_level, args.single_level, &config.telemetry)?;
 
     // Handle token reinitialization
     if args.reinit_twitch_token {
@@ -144,7 +237,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Single-level logging enabled. Only showing logs at the {} level.", log_level);
     }
 
-    let clients = init(Arc::clone(&config)).await?;
+    let bot = MewBotBuilder::new()
+        .shared_config(Arc::clone(&config))
+        .build()
+        .await?;
 
     // Initialize the RedeemManager with current status
     // clients.twitch_manager.redeem_manager.write().await.initialize_with_current_status().await?;
@@ -153,8 +249,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         error!("A panic occurred: {:?}", panic_info);
     }));
 
-    run(clients, config).await?;
+    bot.run().await?;
 
     info!("MewBot shutting down");
+    #[cfg(feature = "otlp")]
+    opentelemetry::global::shutdown_tracer_provider();
     Ok(())
 }
\ No newline at end of file
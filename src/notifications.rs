@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use reqwest::Client;
+use serde_json::json;
+use tokio::sync::RwLock;
+use tracing::{debug, error};
+use crate::config::{Config, NotificationForwardingConfig, NotifyRoute};
+
+// Forwards the few events worth interrupting the streamer for to desktop notifications and/or a
+// Telegram chat, so they're seen even when chat and the dashboard aren't.
+
+#[derive(Debug, Clone, Copy)]
+pub enum NotifyEvent {
+    BigRaid,
+    SubsystemFailing,
+    RedeemReview,
+}
+
+fn route(config: &NotificationForwardingConfig, event: NotifyEvent) -> NotifyRoute {
+    match event {
+        NotifyEvent::BigRaid => config.raids,
+        NotifyEvent::SubsystemFailing => config.failing_subsystems,
+        NotifyEvent::RedeemReview => config.redeem_reviews,
+    }
+}
+
+// Reads the live config, so forwarding follows changes made on the dashboard
+pub struct Notifier {
+    config: Arc<RwLock<Config>>,
+    http: Client,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new(Arc::new(RwLock::new(Config::default())))
+    }
+}
+
+impl Notifier {
+    pub fn new(config: Arc<RwLock<Config>>) -> Self {
+        Self {
+            config,
+            http: Client::new(),
+        }
+    }
+
+    // Whether the event goes anywhere, for callers that would otherwise skip the work leading up to it
+    pub async fn wants(&self, event: NotifyEvent) -> bool {
+        let config = &self.config.read().await.notification_forwarding;
+        config.enabled && route(config, event) != NotifyRoute::Off
+    }
+
+    // Doesn't wait for delivery; failures are only logged
+    pub fn forward(&self, event: NotifyEvent, title: String, body: String) {
+        let config = self.config.clone();
+        let http = self.http.clone();
+        tokio::spawn(async move {
+            let config = config.read().await.notification_forwarding.clone();
+            if !config.enabled {
+                return;
+            }
+            let route = route(&config, event);
+            if route.desktop() {
+                show_desktop(title.clone(), body.clone()).await;
+            }
+            if route.telegram() {
+                send_telegram(&http, &config, &title, &body).await;
+            }
+        });
+    }
+}
+
+#[cfg(feature = "desktop-notifications")]
+async fn show_desktop(title: String, body: String) {
+    let result = tokio::task::spawn_blocking(move || {
+        notify_rust::Notification::new()
+            .summary(&title)
+            .body(&body)
+            .appname("mewbot")
+            .show()
+            .map(|_| ())
+    }).await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => error!("Failed to show a desktop notification: {}", e),
+        Err(e) => error!("Desktop notification task failed: {}", e),
+    }
+}
+
+#[cfg(not(feature = "desktop-notifications"))]
+async fn show_desktop(title: String, _body: String) {
+    debug!("Not showing desktop notification \"{}\": built without the desktop-notifications feature", title);
+}
+
+async fn send_telegram(http: &Client, config: &NotificationForwardingConfig, title: &str, body: &str) {
+    let (token, chat_id) = match (&config.telegram_bot_token, &config.telegram_chat_id) {
+        (Some(token), Some(chat_id)) => (token, chat_id),
+        _ => {
+            debug!("Not sending \"{}\" to Telegram: no bot token or chat id configured", title);
+            return;
+        }
+    };
+    let result = http.post(format!("https://api.telegram.org/bot{}/sendMessage", token))
+        .json(&json!({
+            "chat_id": chat_id,
+            "text": format!("{}\n{}", title, body),
+        }))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    if let Err(e) = result {
+        // The error's URL would contain the token
+        error!("Failed to send \"{}\" to Telegram: {}", title, e.without_url());
+    }
+}
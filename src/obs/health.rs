@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::Utc;
+use tracing::{error, warn};
+use serde_json::{json, Value};
+use serenity::model::id::UserId;
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Duration, Instant};
+use crate::config::{Config, StreamHealthConfig};
+use crate::discord::DiscordClient;
+use crate::obs::OBSManager;
+use crate::storage::{StorageClient, StreamHealthSample};
+use crate::web_ui::websocket::{DashboardState, WebSocketMessage};
+
+// Cumulative counters from one poll; OBS only reports totals, so samples are the difference between two polls
+struct Counters {
+    dropped_frames: u64,
+    stream_frames: u64,
+    encoder_skipped_frames: u64,
+    encoder_frames: u64,
+    render_skipped_frames: u64,
+    render_frames: u64,
+    bytes: u64,
+    cpu_usage: f64,
+    fps: f64,
+    at: Instant,
+}
+
+impl Counters {
+    fn from_stats(obs_stats: &Value, stream_status: &Value) -> Self {
+        let count = |value: &Value| value.as_u64().unwrap_or(0);
+        Counters {
+            dropped_frames: count(&stream_status["outputSkippedFrames"]),
+            stream_frames: count(&stream_status["outputTotalFrames"]),
+            encoder_skipped_frames: count(&obs_stats["outputSkippedFrames"]),
+            encoder_frames: count(&obs_stats["outputTotalFrames"]),
+            render_skipped_frames: count(&obs_stats["renderSkippedFrames"]),
+            render_frames: count(&obs_stats["renderTotalFrames"]),
+            bytes: count(&stream_status["outputBytes"]),
+            cpu_usage: obs_stats["cpuUsage"].as_f64().unwrap_or(0.0),
+            fps: obs_stats["activeFps"].as_f64().unwrap_or(0.0),
+            at: Instant::now(),
+        }
+    }
+
+    // None when a counter went backwards, which means OBS restarted the output in between
+    fn sample_since(&self, previous: &Counters, instance_name: &str) -> Option<StreamHealthSample> {
+        let elapsed = self.at.duration_since(previous.at).as_secs_f64();
+        if elapsed <= 0.0 {
+            return None;
+        }
+
+        Some(StreamHealthSample {
+            instance_name: instance_name.to_string(),
+            dropped_frames_percent: percent(
+                self.dropped_frames.checked_sub(previous.dropped_frames)?,
+                self.stream_frames.checked_sub(previous.stream_frames)?,
+            ),
+            encoder_lag_percent: percent(
+                self.encoder_skipped_frames.checked_sub(previous.encoder_skipped_frames)?,
+                self.encoder_frames.checked_sub(previous.encoder_frames)?,
+            ),
+            render_lag_percent: percent(
+                self.render_skipped_frames.checked_sub(previous.render_skipped_frames)?,
+                self.render_frames.checked_sub(previous.render_frames)?,
+            ),
+            bitrate_kbps: self.bytes.checked_sub(previous.bytes)? as f64 * 8.0 / 1000.0 / elapsed,
+            cpu_usage: self.cpu_usage,
+            fps: self.fps,
+            timestamp: Utc::now(),
+        })
+    }
+}
+
+// Skipped frames count towards the total in OBS
+fn percent(skipped: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        skipped as f64 / total as f64 * 100.0
+    }
+}
+
+fn problems(config: &StreamHealthConfig, sample: &StreamHealthSample) -> Vec<(&'static str, String)> {
+    let mut problems = Vec::new();
+    if sample.dropped_frames_percent > config.max_dropped_frames_percent {
+        problems.push(("dropped_frames", format!("dropping {:.1}% of frames (network)", sample.dropped_frames_percent)));
+    }
+    if sample.encoder_lag_percent > config.max_encoder_lag_percent {
+        problems.push(("encoder_lag", format!("skipping {:.1}% of frames (encoder overloaded)", sample.encoder_lag_percent)));
+    }
+    if sample.render_lag_percent > config.max_render_lag_percent {
+        problems.push(("render_lag", format!("missing {:.1}% of frames (rendering lag)", sample.render_lag_percent)));
+    }
+    if sample.bitrate_kbps < config.min_bitrate_kbps {
+        problems.push(("bitrate", format!("bitrate down to {:.0} kbps", sample.bitrate_kbps)));
+    }
+    problems
+}
+
+pub struct StreamHealthMonitor {
+    config: Arc<RwLock<Config>>,
+    obs_manager: Arc<OBSManager>,
+    storage: Arc<RwLock<StorageClient>>,
+    dashboard_state: Arc<RwLock<DashboardState>>,
+    discord_client: Option<Arc<DiscordClient>>,
+}
+
+impl StreamHealthMonitor {
+    pub fn new(
+        config: Arc<RwLock<Config>>,
+        obs_manager: Arc<OBSManager>,
+        storage: Arc<RwLock<StorageClient>>,
+        dashboard_state: Arc<RwLock<DashboardState>>,
+        discord_client: Option<Arc<DiscordClient>>,
+    ) -> Self {
+        Self {
+            config,
+            obs_manager,
+            storage,
+            dashboard_state,
+            discord_client,
+        }
+    }
+
+    pub async fn run(&self) {
+        let mut previous: HashMap<String, Counters> = HashMap::new();
+        // (instance, problem) -> when it was last alerted
+        let mut last_alerts: HashMap<(String, &'static str), Instant> = HashMap::new();
+
+        loop {
+            let config = self.config.read().await.stream_health.clone();
+            sleep(Duration::from_secs(config.poll_interval_secs.max(1))).await;
+            if !config.enabled {
+                continue;
+            }
+
+            for (instance_name, obs_stats, stream_status) in self.obs_manager.collect_stats().await {
+                if !stream_status["outputActive"].as_bool().unwrap_or(false) {
+                    previous.remove(&instance_name);
+                    continue;
+                }
+
+                let counters = Counters::from_stats(&obs_stats, &stream_status);
+                let sample = previous.get(&instance_name).and_then(|prev| counters.sample_since(prev, &instance_name));
+                previous.insert(instance_name.clone(), counters);
+                let sample = match sample {
+                    Some(sample) => sample,
+                    None => continue,
+                };
+
+                if let Err(e) = self.storage.read().await.log_stream_health(&sample) {
+                    error!("Failed to log stream health: {:?}", e);
+                }
+                self.publish(&sample).await;
+
+                for (problem, description) in problems(&config, &sample) {
+                    let key = (instance_name.clone(), problem);
+                    let cooldown = Duration::from_secs(config.alert_cooldown_secs);
+                    if last_alerts.get(&key).map_or(false, |last| last.elapsed() < cooldown) {
+                        continue;
+                    }
+                    last_alerts.insert(key, Instant::now());
+                    self.alert(&config, &format!("Stream health ({}): {}", instance_name, description)).await;
+                }
+            }
+        }
+    }
+
+    async fn publish(&self, sample: &StreamHealthSample) {
+        let message = WebSocketMessage {
+            module: "obs".to_string(),
+            action: "health".to_string(),
+            data: json!(sample),
+        };
+        if let Err(e) = self.dashboard_state.read().await.broadcast_message(message).await {
+            error!("Failed to push stream health: {:?}", e);
+        }
+    }
+
+    // Never posts to chat; viewers don't need to hear about dropped frames
+    async fn alert(&self, config: &StreamHealthConfig, message: &str) {
+        warn!("{}", message);
+        self.dashboard_state.write().await.add_notification(message.to_string()).await;
+
+        let user_id = config.discord_user_id.as_ref().and_then(|id| id.parse::<u64>().ok());
+        if let (Some(discord_client), Some(user_id)) = (&self.discord_client, user_id) {
+            let http = discord_client.get_http().await;
+            let result = match UserId::new(user_id).create_dm_channel(&http).await {
+                Ok(channel) => channel.id.say(&http, format!("⚠️ {}", message)).await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                error!("Failed to send stream health DM: {}", e);
+            }
+        }
+    }
+}
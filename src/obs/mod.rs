@@ -1,6 +1,8 @@
 // src/obs/mod.rs
 
+pub(crate) mod health;
 pub(crate) mod models;
+pub(crate) mod preview;
 mod websocket;
 
 pub use models::{
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
-use log::{error, info};
+use tracing::{debug, error, info};
 use serde::{Deserialize, Serialize};
 use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
 use tungstenite::Message;
@@ -9,6 +9,10 @@ use async_trait::async_trait;
 use crate::web_ui::websocket::{WebSocketMessage};
 use serde_json::{json, Value};
 use crate::obs::websocket::ConnectionState;
+use crate::twitch::connection_monitor::ConnectionHealth;
+
+// Browser sources reload when their settings are re-applied, which can take a moment
+const SETTINGS_REFRESH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
 #[async_trait]
 pub trait OBSStateUpdate: Send + Sync {
@@ -45,6 +49,9 @@ pub struct OBSWebSocketClient {
     pub response_channels: Arc<RwLock<HashMap<String, oneshot::Sender<serde_json::Value>>>>,
     pub(crate) connection_task: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
     pub(crate) should_reconnect: Arc<AtomicBool>,
+    // scene name -> source name -> sceneItemId
+    pub(crate) scene_item_ids: Arc<RwLock<HashMap<String, HashMap<String, i64>>>>,
+    pub(crate) connections: Arc<ConnectionHealth>,
 }
 
 
@@ -64,13 +71,15 @@ pub struct OBSInstanceState {
 pub struct OBSManager {
     clients: Arc<RwLock<HashMap<String, OBSWebSocketClient>>>,
     ws_sender: mpsc::UnboundedSender<WebSocketMessage>,
+    connections: Arc<ConnectionHealth>,
 }
 
 impl OBSManager {
-    pub fn new(ws_sender: mpsc::UnboundedSender<WebSocketMessage>) -> Self {
+    pub fn new(ws_sender: mpsc::UnboundedSender<WebSocketMessage>, connections: Arc<ConnectionHealth>) -> Self {
         Self {
             clients: Arc::new(RwLock::new(HashMap::new())),
             ws_sender,
+            connections,
         }
     }
 
@@ -133,7 +142,7 @@ impl OBSManager {
         Ok(())
     }
 
-    async fn toggle_source(&self, instance_name: &str, scene_name: &str, source_name: &str, enabled: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    pub(crate) async fn toggle_source(&self, instance_name: &str, scene_name: &str, source_name: &str, enabled: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut clients = self.clients.write().await;
         if let Some(client) = clients.get_mut(instance_name) {
             client.set_scene_item_enabled(scene_name, source_name, enabled).await?;
@@ -145,6 +154,26 @@ impl OBSManager {
         Ok(())
     }
 
+    pub(crate) async fn toggle_filter(&self, instance_name: &str, source_name: &str, filter_name: &str, enabled: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let clients = self.clients.read().await;
+        if let Some(client) = clients.get(instance_name) {
+            client.set_source_filter_enabled(source_name, filter_name, enabled).await?;
+        } else {
+            return Err(format!("OBS instance not found: {}", instance_name).into());
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn set_input_volume(&self, instance_name: &str, input_name: &str, volume_db: f64) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let clients = self.clients.read().await;
+        if let Some(client) = clients.get(instance_name) {
+            client.set_input_volume(input_name, volume_db).await?;
+        } else {
+            return Err(format!("OBS instance not found: {}", instance_name).into());
+        }
+        Ok(())
+    }
+
     pub(crate) async fn refresh_source(&self, instance_name: &str, source_name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let clients = self.clients.read().await;
 
@@ -156,40 +185,19 @@ impl OBSManager {
             drop(state);
 
             info!("Attempting to refresh source {} on instance {}", source_name, instance_name);
-            let request_id = "refresh_browser_source";
 
             // First try to verify the source exists and is a browser source
-            let verify_payload = json!({
-            "op": 6,
-            "d": {
-                "requestType": "GetInputSettings",
-                "requestId": "verify_source",
-                "requestData": {
-                    "inputName": source_name
-                }
-            }
-        });
-
-            match client.send_request(verify_payload, "verify_source").await {
+            match client.request("GetInputSettings", json!({ "inputName": source_name })).await {
                 Ok(response) => {
                     info!("Source info response: {:?}", response);
 
                     // Now try to refresh using SetInputSettings with the same settings
-                    if let Some(settings) = response["responseData"]["inputSettings"].as_object() {
+                    if let Some(settings) = response["inputSettings"].as_object() {
                         info!("Attempting to refresh by re-applying settings");
-                        let refresh_payload = json!({
-                        "op": 6,
-                        "d": {
-                            "requestType": "SetInputSettings",
-                            "requestId": request_id,
-                            "requestData": {
-                                "inputName": source_name,
-                                "inputSettings": settings
-                            }
-                        }
-                    });
-
-                        client.send_request(refresh_payload, request_id).await?;
+                        client.request_with_timeout("SetInputSettings", json!({
+                            "inputName": source_name,
+                            "inputSettings": settings
+                        }), SETTINGS_REFRESH_TIMEOUT).await?;
                         info!("Settings re-applied to {} on {}", source_name, instance_name);
                     }
                 }
@@ -217,6 +225,63 @@ impl OBSManager {
         instances
     }
 
+    // Raw GetStats and GetStreamStatus responses for each connected instance
+    pub(crate) async fn collect_stats(&self) -> Vec<(String, Value, Value)> {
+        let clients = self.clients.read().await;
+        let mut stats = Vec::new();
+        for (name, client) in clients.iter() {
+            if client.state.read().await.connection_state != ConnectionState::Connected {
+                continue;
+            }
+            let result = match client.get_stats().await {
+                Ok(obs_stats) => client.get_stream_status().await.map(|stream_status| (obs_stats, stream_status)),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok((obs_stats, stream_status)) => stats.push((name.clone(), obs_stats, stream_status)),
+                Err(e) => debug!("Failed to get stats from OBS instance {}: {}", name, e),
+            }
+        }
+        stats
+    }
+
+    // Screenshot of each connected instance's program scene
+    pub(crate) async fn capture_previews(&self, image_width: u32, image_quality: u8) -> Vec<Value> {
+        let clients = self.clients.read().await;
+        let mut previews = Vec::new();
+        for (name, client) in clients.iter() {
+            if client.state.read().await.connection_state != ConnectionState::Connected {
+                continue;
+            }
+            let scene = match client.get_current_scene().await {
+                Ok(scene) => scene,
+                Err(e) => {
+                    debug!("Failed to get program scene for preview on {}: {}", name, e);
+                    continue;
+                }
+            };
+            match client.get_source_screenshot(&scene.name, image_width, image_quality).await {
+                Ok(image) => previews.push(json!({
+                    "instance_name": name,
+                    "scene_name": scene.name,
+                    "image": image,
+                })),
+                Err(e) => debug!("Failed to capture preview of {} on {}: {}", scene.name, name, e),
+            }
+        }
+        previews
+    }
+
+    pub async fn is_connected(&self) -> bool {
+        let clients = self.clients.read().await;
+        for client in clients.values() {
+            if client.state.read().await.connection_state == ConnectionState::Connected {
+                return true;
+            }
+        }
+        false
+    }
+
     async fn send_update(&self, update: Value) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let message = WebSocketMessage {
             module: "obs".to_string(),
@@ -228,7 +293,7 @@ impl OBSManager {
     }
 
     pub async fn add_instance(&self, name: String, instance: OBSInstance) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let client = OBSWebSocketClient::new(instance);
+        let client = OBSWebSocketClient::new(instance, self.connections.clone());
         self.clients.write().await.insert(name.clone(), client.clone());
         if let Err(e) = client.connect().await {
             error!("Failed to start connection manager for OBS instance {}: {}", name, e);
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use tracing::error;
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Duration};
+use crate::config::ObsPreviewConfig;
+use crate::obs::OBSManager;
+use crate::web_ui::websocket::{DashboardState, WebSocketMessage};
+
+// Pushes program scene screenshots to the dashboard for remote monitoring.
+// Screenshots are only taken while a dashboard is connected, since encoding
+// them costs OBS a frame of render time.
+pub async fn run_preview_loop(
+    obs_manager: Arc<OBSManager>,
+    dashboard_state: Arc<RwLock<DashboardState>>,
+    config: ObsPreviewConfig,
+) {
+    let interval = Duration::from_secs(config.interval_secs.max(1));
+
+    loop {
+        sleep(interval).await;
+
+        if dashboard_state.read().await.viewer_count() == 0 {
+            continue;
+        }
+
+        for preview in obs_manager.capture_previews(config.image_width, config.image_quality.min(100)).await {
+            let message = WebSocketMessage {
+                module: "obs".to_string(),
+                action: "preview".to_string(),
+                data: preview,
+            };
+            if let Err(e) = dashboard_state.read().await.broadcast_message(message).await {
+                error!("Failed to push OBS preview: {:?}", e);
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@ use tokio::sync::{mpsc, RwLock, oneshot, Mutex};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use futures_util::{SinkExt, StreamExt};
-use log::{error, info, warn, debug};
+use tracing::{error, info, warn, debug, Instrument};
 use serde::{Deserialize};
 use tokio_tungstenite::{connect_async_with_config, tungstenite::protocol::Message, tungstenite::client::IntoClientRequest, Connector, WebSocketStream, MaybeTlsStream};
 use tokio_tungstenite::tungstenite::http::Uri;
@@ -16,12 +16,16 @@ use base64::{engine::general_purpose, Engine as _};
 use futures_util::stream::SplitSink;
 use native_tls::TlsConnector;
 use tokio::net::TcpStream;
+use uuid::Uuid;
 use crate::obs::models::{OBSInstance, OBSScene, OBSSceneItem};
 use crate::obs::{OBSClientState, OBSInstanceState, OBSWebSocketClient};
+use crate::twitch::connection_monitor::ConnectionHealth;
 
 pub const TIMEOUT_DURATION: Duration = Duration::from_millis(1000);
 pub const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(10);
 const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+// General | Scenes | Filters | SceneItems
+const EVENT_SUBSCRIPTIONS: u32 = 1 | 4 | 32 | 128;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectionState {
@@ -56,7 +60,7 @@ pub struct AuthenticationInfo {
 }
 
 impl OBSWebSocketClient {
-    pub fn new(instance: OBSInstance) -> Self {
+    pub fn new(instance: OBSInstance, connections: Arc<ConnectionHealth>) -> Self {
         Self {
             instance,
             state: Arc::new(RwLock::new(OBSClientState {
@@ -66,6 +70,8 @@ impl OBSWebSocketClient {
             response_channels: Arc::new(RwLock::new(HashMap::new())),
             connection_task: Arc::new(Mutex::new(None)),
             should_reconnect: Arc::new(AtomicBool::new(true)),
+            scene_item_ids: Arc::new(RwLock::new(HashMap::new())),
+            connections,
         }
     }
 
@@ -77,9 +83,10 @@ impl OBSWebSocketClient {
 
         self.should_reconnect.store(true, Ordering::SeqCst);
         let client = self.clone();
+        let span = tracing::info_span!("obs", instance = %self.instance.name);
         *connection_task = Some(tokio::spawn(async move {
             client.connection_manager().await;
-        }));
+        }.instrument(span)));
 
         Ok(())
     }
@@ -109,6 +116,7 @@ impl OBSWebSocketClient {
                         Ok(_) => {
                             info!("Successfully connected to OBS instance: {}", self.instance.address);
                             self.state.write().await.connection_state = ConnectionState::Connected;
+                            self.connections.report_connected(&format!("OBS ({})", self.instance.name));
                             attempt = 0;
                             retry_delay = Duration::from_millis(100);
                         }
@@ -155,6 +163,8 @@ impl OBSWebSocketClient {
     pub fn attempt_connect(&self) -> Pin<Box<dyn Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>> + Send + '_>> {
         Box::pin(async move {
             info!("Starting connection attempt to OBS instance: {}", self.instance.address);
+            // Item ids aren't stable across OBS restarts
+            self.scene_item_ids.write().await.clear();
             let scheme = if self.instance.use_ssl { "wss" } else { "ws" };
             let url = format!("{}://{}:{}", scheme, self.instance.address, self.instance.port);
             let uri: Uri = url.parse()?;
@@ -186,7 +196,7 @@ impl OBSWebSocketClient {
             let client_clone = self.clone();
             tokio::spawn(async move {
                 client_clone.handle_incoming_messages(read).await;
-            });
+            }.instrument(tracing::Span::current()));
 
             info!("WebSocket connection established. Waiting for Hello message...");
             let hello_message = match tokio::time::timeout(Duration::from_secs(10), self.wait_for_hello()).await {
@@ -223,7 +233,7 @@ impl OBSWebSocketClient {
                 "d": {
                     "rpcVersion": 1,
                     "authentication": 0,
-                    "eventSubscriptions": 33
+                    "eventSubscriptions": EVENT_SUBSCRIPTIONS
                 }
             });
             debug!("Identify payload: {:?}", identify_payload);
@@ -291,6 +301,8 @@ impl OBSWebSocketClient {
                 }
             }
         }
+        self.connections.report_disconnected(&format!("OBS ({})", self.instance.name));
+
         // Attempt to reconnect
         info!("Connection closed, attempting to reconnect...");
         if let Err(e) = self.connect().await {
@@ -315,6 +327,9 @@ impl OBSWebSocketClient {
                     warn!("Received Identified message but no receiver was waiting for it");
                 }
             }
+            5 => {
+                self.handle_event(&response.d).await;
+            }
             7 => {
                 debug!("Received request response: {:?}", response.d);
                 if let Some(request_id) = response.d["requestId"].as_str() {
@@ -329,6 +344,31 @@ impl OBSWebSocketClient {
         }
     }
 
+    async fn handle_event(&self, event: &Value) {
+        let event_type = event["eventType"].as_str().unwrap_or("");
+        debug!("[{}] Received event: {}", self.instance.name, event_type);
+        match event_type {
+            "CurrentProgramSceneChanged" | "SceneItemCreated" | "SceneItemRemoved" | "SceneRemoved" => {
+                self.invalidate_scene_items(event["eventData"]["sceneName"].as_str()).await;
+            }
+            "SceneNameChanged" => {
+                self.invalidate_scene_items(event["eventData"]["oldSceneName"].as_str()).await;
+            }
+            "SceneListChanged" => self.invalidate_scene_items(None).await,
+            _ => {}
+        }
+    }
+
+    async fn invalidate_scene_items(&self, scene_name: Option<&str>) {
+        let mut scene_item_ids = self.scene_item_ids.write().await;
+        match scene_name {
+            Some(scene_name) => {
+                scene_item_ids.remove(scene_name);
+            }
+            None => scene_item_ids.clear(),
+        }
+    }
+
     fn spawn_write_loop(
         &self,
         mut write: SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>,
@@ -437,16 +477,7 @@ impl OBSWebSocketClient {
     }
 
     pub async fn get_current_scene(&self) -> Result<OBSScene, Box<dyn std::error::Error + Send + Sync>> {
-        let request_id = "get_current_scene";
-        let payload = json!({
-            "op": 6,
-            "d": {
-                "requestType": "GetCurrentProgramScene",
-                "requestId": request_id
-            }
-        });
-
-        let response = self.send_request(payload, request_id).await?;
+        let response = self.request("GetCurrentProgramScene", Value::Null).await?;
 
         let scene_name = response["currentProgramSceneName"].as_str()
             .ok_or("Invalid response: missing scene name")?;
@@ -458,36 +489,14 @@ impl OBSWebSocketClient {
     }
 
     pub async fn set_current_scene(&self, scene_name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let request_id = "set_current_scene";
-        let payload = json!({
-            "op": 6,
-            "d": {
-                "requestType": "SetCurrentProgramScene",
-                "requestId": request_id,
-                "requestData": {
-                    "sceneName": scene_name
-                }
-            }
-        });
-
-        self.send_request(payload, request_id).await?;
-
+        self.request("SetCurrentProgramScene", json!({ "sceneName": scene_name })).await?;
         Ok(())
     }
 
     async fn get_scene_list(&self) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
-        let request_id = "get_scene_list";
-        let payload = json!({
-            "op": 6,
-            "d": {
-                "requestType": "GetSceneList",
-                "requestId": request_id
-            }
-        });
-
-        let response = self.send_request(payload, request_id).await?;
+        let response = self.request("GetSceneList", Value::Null).await?;
 
-        let scenes = response["responseData"]["scenes"].as_array()
+        let scenes = response["scenes"].as_array()
             .ok_or("Invalid response: missing scenes array")?;
 
         Ok(scenes.iter()
@@ -496,23 +505,16 @@ impl OBSWebSocketClient {
     }
 
     pub async fn get_scene_items(&self, scene_name: &str) -> Result<Vec<OBSSceneItem>, Box<dyn std::error::Error + Send + Sync>> {
-        let request_id = "get_scene_items";
-        let payload = json!({
-            "op": 6,
-            "d": {
-                "requestType": "GetSceneItemList",
-                "requestId": request_id,
-                "requestData": {
-                    "sceneName": scene_name
-                }
-            }
-        });
-
-        let response = self.send_request(payload, request_id).await?;
+        let response = self.request("GetSceneItemList", json!({ "sceneName": scene_name })).await?;
 
         let items = response["sceneItems"].as_array()
             .ok_or("Invalid response: missing sceneItems array")?;
 
+        let item_ids = items.iter()
+            .filter_map(|item| Some((item["sourceName"].as_str()?.to_string(), item["sceneItemId"].as_i64()?)))
+            .collect();
+        self.scene_item_ids.write().await.insert(scene_name.to_string(), item_ids);
+
         Ok(items.iter()
             .filter_map(|item| {
                 let name = item["sourceName"].as_str()?;
@@ -527,44 +529,80 @@ impl OBSWebSocketClient {
             .collect())
     }
 
+    // OBS addresses scene items by numeric id, so look it up by source name,
+    // refreshing the scene's cached ids once if the item isn't known yet
+    async fn resolve_scene_item_id(&self, scene_name: &str, item_name: &str) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(id) = self.scene_item_ids.read().await.get(scene_name).and_then(|items| items.get(item_name)) {
+            return Ok(*id);
+        }
+
+        self.get_scene_items(scene_name).await?;
+        self.scene_item_ids.read().await.get(scene_name)
+            .and_then(|items| items.get(item_name))
+            .copied()
+            .ok_or_else(|| format!("Source {} not found in scene {}", item_name, scene_name).into())
+    }
+
     pub async fn set_scene_item_enabled(&self, scene_name: &str, item_name: &str, enabled: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let request_id = "set_scene_item_enabled";
-        let payload = json!({
-            "op": 6,
-            "d": {
-                "requestType": "SetSceneItemEnabled",
-                "requestId": request_id,
-                "requestData": {
-                    "sceneName": scene_name,
-                    "sceneItemId": item_name,
-                    "sceneItemEnabled": enabled
-                }
-            }
-        });
+        let scene_item_id = self.resolve_scene_item_id(scene_name, item_name).await?;
+        self.request("SetSceneItemEnabled", json!({
+            "sceneName": scene_name,
+            "sceneItemId": scene_item_id,
+            "sceneItemEnabled": enabled
+        })).await?;
+        Ok(())
+    }
 
-        self.send_request(payload, request_id).await?;
+    pub async fn set_source_filter_enabled(&self, source_name: &str, filter_name: &str, enabled: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.request("SetSourceFilterEnabled", json!({
+            "sourceName": source_name,
+            "filterName": filter_name,
+            "filterEnabled": enabled
+        })).await?;
+        Ok(())
+    }
 
+    // OBS takes -100 to +26 dB
+    pub async fn set_input_volume(&self, input_name: &str, volume_db: f64) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.request("SetInputVolume", json!({
+            "inputName": input_name,
+            "inputVolumeDb": volume_db.clamp(-100.0, 26.0)
+        })).await?;
         Ok(())
     }
 
+    // Render, encoder and resource counters since OBS started
+    pub async fn get_stats(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.request("GetStats", Value::Null).await
+    }
+
+    // Stream output counters since the stream started; outputActive is false while offline
+    pub async fn get_stream_status(&self) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.request("GetStreamStatus", Value::Null).await
+    }
+
+    // Returns a data URI ("data:image/jpg;base64,...") of the source's current frame
+    pub async fn get_source_screenshot(&self, source_name: &str, image_width: u32, image_quality: u8) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let response = self.request("GetSourceScreenshot", json!({
+            "sourceName": source_name,
+            "imageFormat": "jpg",
+            "imageWidth": image_width,
+            "imageCompressionQuality": image_quality
+        })).await?;
+
+        response["imageData"].as_str()
+            .map(String::from)
+            .ok_or_else(|| "Invalid response: missing imageData".into())
+    }
+
     pub async fn refresh_browser_source(&self, source_name: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let request_id = "refresh_browser_source";
         debug!("Attempting to refresh browser source: {}", source_name);
 
-        let payload = json!({
-        "op": 6,
-        "d": {
-            "requestType": "TriggerInputPropertiesAction", // Changed from PressInputPropertiesButton
-            "requestId": request_id,
-            "requestData": {
-                "inputName": source_name,
-                "actionName": "refresh" // Changed from propertyName/refreshNoCache
-            }
-        }
-    });
-
-        debug!("Sending refresh payload: {}", payload);
-        match self.send_request(payload, request_id).await {
+        let request_data = json!({
+            "inputName": source_name,
+            "actionName": "refresh"
+        });
+        match self.request("TriggerInputPropertiesAction", request_data).await {
             Ok(response) => {
                 debug!("Received response for refresh: {:?}", response);
                 Ok(())
@@ -576,16 +614,64 @@ impl OBSWebSocketClient {
         }
     }
 
-    pub(crate) async fn send_request(&self, payload: Value, request_id: &str) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+    pub(crate) async fn request(&self, request_type: &str, request_data: Value) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.request_with_timeout(request_type, request_data, TIMEOUT_DURATION).await
+    }
+
+    // Sends a request under a fresh id and returns its responseData, or the
+    // error OBS reported for it
+    #[tracing::instrument(
+        name = "obs_request",
+        level = "debug",
+        skip_all,
+        fields(instance = %self.instance.name, request = request_type, request_id = tracing::field::Empty)
+    )]
+    pub(crate) async fn request_with_timeout(
+        &self,
+        request_type: &str,
+        request_data: Value,
+        request_timeout: Duration,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let request_id = Uuid::new_v4().to_string();
+        tracing::Span::current().record("request_id", request_id.as_str());
+        let mut payload = json!({
+            "op": 6,
+            "d": {
+                "requestType": request_type,
+                "requestId": request_id
+            }
+        });
+        if !request_data.is_null() {
+            payload["d"]["requestData"] = request_data;
+        }
+
         let (tx, rx) = oneshot::channel();
-        self.response_channels.write().await.insert(request_id.to_string(), tx);
+        self.response_channels.write().await.insert(request_id.clone(), tx);
 
-        self.send_message(payload).await?;
+        if let Err(e) = self.send_message(payload).await {
+            self.response_channels.write().await.remove(&request_id);
+            return Err(e);
+        }
 
-        // Wait for the response
-        let response = timeout(TIMEOUT_DURATION, rx).await??;
+        let mut response = match timeout(request_timeout, rx).await {
+            Ok(response) => response?,
+            Err(_) => {
+                self.response_channels.write().await.remove(&request_id);
+                return Err(format!("OBS request {} timed out after {:?}", request_type, request_timeout).into());
+            }
+        };
+
+        let status = &response["requestStatus"];
+        if !status["result"].as_bool().unwrap_or(false) {
+            return Err(format!(
+                "OBS request {} failed with code {}: {}",
+                request_type,
+                status["code"].as_i64().unwrap_or(0),
+                status["comment"].as_str().unwrap_or("no details"),
+            ).into());
+        }
 
-        Ok(response)
+        Ok(response["responseData"].take())
     }
 }
 
@@ -597,6 +683,8 @@ impl Clone for OBSWebSocketClient {
             response_channels: Arc::clone(&self.response_channels),
             connection_task: Arc::clone(&self.connection_task),
             should_reconnect: Arc::clone(&self.should_reconnect),
+            scene_item_ids: Arc::clone(&self.scene_item_ids),
+            connections: Arc::clone(&self.connections),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{Duration, Instant};
+use tracing::{debug, warn};
+use twitch_irc::message::PrivmsgMessage;
+
+use crate::config::{ChatRelayMode, Config};
+use crate::osc::vrchat::VRChatOSC;
+
+pub struct ChatRelay {
+    vrchat_osc: Arc<VRChatOSC>,
+    config: Arc<RwLock<Config>>,
+    last_relay: Mutex<Option<Instant>>,
+}
+
+impl ChatRelay {
+    pub fn new(vrchat_osc: Arc<VRChatOSC>, config: Arc<RwLock<Config>>) -> Self {
+        Self {
+            vrchat_osc,
+            config,
+            last_relay: Mutex::new(None),
+        }
+    }
+
+    pub async fn relay(&self, msg: &PrivmsgMessage, text: &str) {
+        let relay_config = self.config.read().await.chat_relay.clone();
+
+        if !Self::should_relay(relay_config.mode, msg, text) {
+            return;
+        }
+
+        {
+            let mut last_relay = self.last_relay.lock().await;
+            let min_interval = Duration::from_secs(relay_config.min_interval_secs);
+            if let Some(last) = *last_relay {
+                if last.elapsed() < min_interval {
+                    debug!("Skipping chatbox relay for {}: rate limited", msg.sender.name);
+                    return;
+                }
+            }
+            *last_relay = Some(Instant::now());
+        }
+
+        let filtered = Self::filter_words(text, &relay_config.blocked_words);
+        let chatbox_message = format!("{}: {}", msg.sender.name, filtered);
+
+        if let Err(e) = self.vrchat_osc.send_chatbox_message(&chatbox_message, true, relay_config.play_sound).await {
+            warn!("Failed to relay chat message to VRChat chatbox: {:?}", e);
+        }
+    }
+
+    fn should_relay(mode: ChatRelayMode, msg: &PrivmsgMessage, text: &str) -> bool {
+        match mode {
+            ChatRelayMode::Off => false,
+            ChatRelayMode::All => !text.starts_with('!'),
+            ChatRelayMode::Moderators => msg.badges.iter().any(|badge| badge.name == "moderator" || badge.name == "broadcaster"),
+            ChatRelayMode::Mentions => {
+                let channel = msg.channel_login.to_lowercase();
+                text.to_lowercase()
+                    .split_whitespace()
+                    .any(|word| word.trim_start_matches('@').trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_') == channel)
+            }
+        }
+    }
+
+    fn filter_words(text: &str, blocked_words: &[String]) -> String {
+        text.split(' ')
+            .map(|word| {
+                let lowered = word.to_lowercase();
+                if blocked_words.iter().any(|blocked| !blocked.is_empty() && lowered.contains(&blocked.to_lowercase())) {
+                    "*".repeat(word.chars().count())
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::obs::OBSManager;
+use crate::osc::manager::OSCManager;
+use crate::osc::models::{OSCHeartbeatConfig, OSCMessageType, OSCValue};
+use crate::osc::osc_config::OSCConfigurations;
+use crate::web_ui::websocket::DashboardState;
+
+pub struct OSCHeartbeat {
+    osc_manager: Arc<OSCManager>,
+    osc_configs: Arc<RwLock<OSCConfigurations>>,
+    dashboard_state: Arc<RwLock<DashboardState>>,
+    obs_manager: Option<Arc<OBSManager>>,
+}
+
+impl OSCHeartbeat {
+    pub fn new(
+        osc_manager: Arc<OSCManager>,
+        osc_configs: Arc<RwLock<OSCConfigurations>>,
+        dashboard_state: Arc<RwLock<DashboardState>>,
+        obs_manager: Option<Arc<OBSManager>>,
+    ) -> Self {
+        Self {
+            osc_manager,
+            osc_configs,
+            dashboard_state,
+            obs_manager,
+        }
+    }
+
+    pub async fn run(&self) {
+        info!("Starting OSC heartbeat loop");
+        let mut pulse = false;
+
+        loop {
+            // Re-read each tick so config changes apply without restarting the loop
+            let config = self.osc_configs.read().await.heartbeat.clone();
+            let interval = Duration::from_secs(config.interval_secs.max(1));
+
+            if config.enabled {
+                pulse = !pulse;
+                if let Err(e) = self.send_status(&config, pulse).await {
+                    debug!("Failed to send OSC heartbeat: {:?}", e);
+                }
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    async fn send_status(&self, config: &OSCHeartbeatConfig, pulse: bool) -> Result<(), crate::osc::OSCError> {
+        let (bot_online, twitch, vrchat, discord) = {
+            let state = self.dashboard_state.read().await;
+            let bot_online = state.bot_status.read().await.is_online();
+            (bot_online, state.twitch_status, state.vrchat_status, state.discord_status)
+        };
+        let obs = match &self.obs_manager {
+            Some(obs_manager) => obs_manager.is_connected().await,
+            None => false,
+        };
+
+        self.send_bool(&config.heartbeat_endpoint, pulse).await?;
+        self.send_bool(&config.bot_endpoint, bot_online).await?;
+        self.send_bool(&config.twitch_endpoint, twitch).await?;
+        self.send_bool(&config.obs_endpoint, obs).await?;
+        self.send_bool(&config.vrchat_endpoint, vrchat).await?;
+        self.send_bool(&config.discord_endpoint, discord).await?;
+        Ok(())
+    }
+
+    pub async fn send_offline(&self) {
+        let config = self.osc_configs.read().await.heartbeat.clone();
+        if !config.enabled {
+            return;
+        }
+
+        for endpoint in [
+            &config.heartbeat_endpoint,
+            &config.bot_endpoint,
+            &config.twitch_endpoint,
+            &config.obs_endpoint,
+            &config.vrchat_endpoint,
+            &config.discord_endpoint,
+        ] {
+            if let Err(e) = self.send_bool(endpoint, false).await {
+                warn!("Failed to clear OSC status parameter {}: {:?}", endpoint, e);
+            }
+        }
+    }
+
+    async fn send_bool(&self, endpoint: &str, value: bool) -> Result<(), crate::osc::OSCError> {
+        if endpoint.is_empty() {
+            return Ok(());
+        }
+        self.osc_manager.send_osc_message(endpoint, &OSCMessageType::Boolean, &OSCValue::Boolean(value)).await
+    }
+}
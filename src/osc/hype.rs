@@ -0,0 +1,114 @@
+use std::sync::{Arc, Mutex};
+use tracing::{debug, error};
+use serde_json::Value;
+use tokio::time::{sleep, Duration, Instant};
+use crate::config::{HypeDecayCurve, HypeMeterConfig};
+use crate::hooks::BotEvent;
+use crate::osc::{OSCManager, OSCMessageType, OSCValue};
+
+// Below this the meter snaps to zero, otherwise exponential decay never gets there
+const EMPTY_THRESHOLD: f32 = 0.01;
+// Smaller changes aren't worth an OSC message
+const MIN_CHANGE: f32 = 0.005;
+
+// A 0.0-1.0 avatar parameter that jumps up with the size of each cheer, sub or raid
+// and decays back down over time.
+pub struct HypeMeter {
+    config: HypeMeterConfig,
+    osc_manager: Arc<OSCManager>,
+    level: Mutex<f32>,
+}
+
+impl HypeMeter {
+    pub fn new(config: HypeMeterConfig, osc_manager: Arc<OSCManager>) -> Self {
+        Self {
+            config,
+            osc_manager,
+            level: Mutex::new(0.0),
+        }
+    }
+
+    // Event hook; only bumps the level, the decay loop sends it
+    pub fn on_event(&self, event: &BotEvent) {
+        let amount = match event {
+            BotEvent::EventSub { event_type, payload } => self.magnitude(event_type, payload),
+            BotEvent::ChatMessage(_) => return,
+        };
+        if amount <= 0.0 {
+            return;
+        }
+
+        let mut level = self.level.lock().unwrap();
+        *level = (*level + amount).min(1.0);
+        debug!("Hype meter raised by {:.2} to {:.2}", amount, *level);
+    }
+
+    fn magnitude(&self, event_type: &str, payload: &Value) -> f32 {
+        let config = &self.config;
+        match event_type {
+            "channel.cheer" => ratio(payload["bits"].as_u64().unwrap_or(0), config.bits_for_full),
+            // Gifted subs are counted once from the gift event instead of per recipient
+            "channel.subscribe" if payload["is_gift"].as_bool().unwrap_or(false) => 0.0,
+            "channel.subscribe" | "channel.subscription.message" => config.sub_value * tier_multiplier(payload),
+            "channel.subscription.gift" => {
+                config.sub_value * tier_multiplier(payload) * payload["total"].as_u64().unwrap_or(1) as f32
+            }
+            "channel.raid" => ratio(payload["viewers"].as_u64().unwrap_or(0), config.raid_viewers_for_full),
+            _ => 0.0,
+        }
+    }
+
+    fn decay(&self, level: f32, elapsed: f32) -> f32 {
+        let decay_secs = self.config.decay_secs.max(0.1);
+        let decayed = match self.config.decay_curve {
+            HypeDecayCurve::Linear => level - elapsed / decay_secs,
+            HypeDecayCurve::Exponential => level * 0.5f32.powf(elapsed / decay_secs),
+        };
+        if decayed < EMPTY_THRESHOLD {
+            0.0
+        } else {
+            decayed
+        }
+    }
+
+    pub async fn run(&self) {
+        let interval = Duration::from_millis(self.config.update_interval_ms.max(50));
+        let mut last_tick = Instant::now();
+        let mut last_sent = 0.0f32;
+
+        loop {
+            sleep(interval).await;
+            let elapsed = last_tick.elapsed().as_secs_f32();
+            last_tick = Instant::now();
+
+            let current = {
+                let mut level = self.level.lock().unwrap();
+                // Send the peak before it starts decaying so bumps are always visible
+                let current = *level;
+                *level = self.decay(current, elapsed);
+                current
+            };
+
+            let changed = (current - last_sent).abs() >= MIN_CHANGE || (current == 0.0 && last_sent != 0.0);
+            if !changed {
+                continue;
+            }
+            match self.osc_manager.send_osc_message(&self.config.parameter, &OSCMessageType::Float, &OSCValue::Float(current)).await {
+                Ok(()) => last_sent = current,
+                Err(e) => error!("Failed to send hype meter level: {}", e),
+            }
+        }
+    }
+}
+
+fn ratio(amount: u64, full: u64) -> f32 {
+    amount as f32 / full.max(1) as f32
+}
+
+fn tier_multiplier(payload: &Value) -> f32 {
+    match payload["tier"].as_str() {
+        Some("2000") => 2.0,
+        Some("3000") => 5.0,
+        _ => 1.0,
+    }
+}
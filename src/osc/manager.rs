@@ -6,6 +6,7 @@ use crate::osc::client::OSCClient;
 use crate::osc::errors::OSCError;
 use crate::osc::models::{OSCMessageType, OSCValue};
 use crate::osc::vrchat::VRChatOSC;
+use crate::output_filter::OutputFilter;
 
 pub struct OSCManager {
     client: Arc<RwLock<OSCClient>>,
@@ -19,7 +20,7 @@ impl Default for OSCManager {
         let client = Arc::new(RwLock::new(OSCClient::default()));
         Self {
             client: client.clone(),
-            vrchat_osc: Arc::new(VRChatOSC::new(client)),
+            vrchat_osc: Arc::new(VRChatOSC::new(client, Arc::new(OutputFilter::default()))),
             connection_status: Arc::new(RwLock::new(false)),
             last_reconnect_attempt: Arc::new(RwLock::new(Instant::now())),
         }
@@ -27,9 +28,9 @@ impl Default for OSCManager {
 }
 
 impl OSCManager {
-    pub async fn new(target_addr: &str) -> Result<Self, OSCError> {
+    pub async fn new(target_addr: &str, output_filter: Arc<OutputFilter>) -> Result<Self, OSCError> {
         let client = Arc::new(RwLock::new(OSCClient::new(target_addr).await?));
-        let vrchat_osc = Arc::new(VRChatOSC::new(Arc::clone(&client)));
+        let vrchat_osc = Arc::new(VRChatOSC::new(Arc::clone(&client), output_filter));
 
         Ok(Self {
             client,
@@ -4,9 +4,15 @@ mod vrchat;
 pub(crate) mod models;
 pub(crate) mod osc_config;
 mod errors;
+mod heartbeat;
+mod chat_relay;
+mod hype;
 
 pub use manager::OSCManager;
 pub use vrchat::VRChatOSC;
-pub use models::{OSCConfig, OSCMessageType, OSCValue};
+pub use models::{OSCConfig, OSCHeartbeatConfig, OSCMessageType, OSCValue};
 pub use osc_config::OSCConfigurations;
-pub use errors::OSCError;
\ No newline at end of file
+pub use errors::OSCError;
+pub use heartbeat::OSCHeartbeat;
+pub use chat_relay::ChatRelay;
+pub use hype::HypeMeter;
\ No newline at end of file
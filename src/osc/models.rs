@@ -12,6 +12,33 @@ pub struct OSCConfig {
     pub send_chat_message: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OSCHeartbeatConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub heartbeat_endpoint: String,
+    pub bot_endpoint: String,
+    pub twitch_endpoint: String,
+    pub obs_endpoint: String,
+    pub vrchat_endpoint: String,
+    pub discord_endpoint: String,
+}
+
+impl Default for OSCHeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 5,
+            heartbeat_endpoint: "/avatar/parameters/MewbotHeartbeat".to_string(),
+            bot_endpoint: "/avatar/parameters/MewbotOnline".to_string(),
+            twitch_endpoint: "/avatar/parameters/MewbotTwitch".to_string(),
+            obs_endpoint: "/avatar/parameters/MewbotOBS".to_string(),
+            vrchat_endpoint: "/avatar/parameters/MewbotVRChat".to_string(),
+            discord_endpoint: "/avatar/parameters/MewbotDiscord".to_string(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum OSCMessageType {
     Integer,
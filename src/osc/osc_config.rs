@@ -2,11 +2,25 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
-use crate::osc::models::{OSCConfig, OSCMessageType, OSCValue};
+use crate::osc::models::{OSCConfig, OSCHeartbeatConfig, OSCMessageType, OSCValue};
+
+pub fn osc_config_path() -> String {
+    crate::paths::resolve(crate::paths::OSC_CONFIG)
+}
+
+// Characters OSC reserves for address pattern matching
+const RESERVED_ADDRESS_CHARS: &[char] = &[' ', '#', '*', ',', '?', '[', ']', '{', '}'];
 
 #[derive(Serialize, Deserialize)]
 pub struct OSCConfigurations {
     pub configs: HashMap<String, OSCConfig>,
+    #[serde(default)]
+    pub heartbeat: OSCHeartbeatConfig,
+    // Per-avatar overrides for `configs`, keyed by VRChat avatar id
+    #[serde(default)]
+    pub avatar_profiles: HashMap<String, HashMap<String, OSCConfig>>,
+    #[serde(skip)]
+    active_avatar: Option<String>,
 }
 
 impl OSCConfigurations {
@@ -30,12 +44,83 @@ impl OSCConfigurations {
     }
 
     pub fn get_config(&self, key: &str) -> Option<&OSCConfig> {
-        self.configs.get(key)
+        self.active_avatar.as_ref()
+            .and_then(|avatar_id| self.avatar_profiles.get(avatar_id))
+            .and_then(|profile| profile.get(key))
+            .or_else(|| self.configs.get(key))
+    }
+
+    // Returns true if the avatar has its own profile
+    pub fn set_active_avatar(&mut self, avatar_id: &str) -> bool {
+        self.active_avatar = Some(avatar_id.to_string());
+        self.avatar_profiles.contains_key(avatar_id)
     }
 
     pub fn add_config(&mut self, key: &str, config: OSCConfig) {
         self.configs.insert(key.to_string(), config);
     }
+
+    pub fn remove_config(&mut self, key: &str) -> bool {
+        self.configs.remove(key).is_some()
+    }
+
+    // Fails if the old key doesn't exist or the new one is taken
+    pub fn rename_config(&mut self, key: &str, new_key: &str) -> bool {
+        if self.configs.contains_key(new_key) {
+            return false;
+        }
+        match self.configs.remove(key) {
+            Some(config) => {
+                self.configs.insert(new_key.to_string(), config);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn duplicate_config(&mut self, key: &str, new_key: &str) -> bool {
+        if self.configs.contains_key(new_key) {
+            return false;
+        }
+        match self.configs.get(key).cloned() {
+            Some(config) => {
+                self.configs.insert(new_key.to_string(), config);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Checks a config before it's saved or sent, since VRChat silently ignores bad addresses
+pub fn validate_config(config: &OSCConfig) -> Result<(), String> {
+    let endpoint = &config.osc_endpoint;
+    if !endpoint.starts_with('/') || endpoint.len() < 2 {
+        return Err("Address must start with / and name a parameter".to_string());
+    }
+    if endpoint.ends_with('/') || endpoint.contains("//") {
+        return Err("Address has an empty path segment".to_string());
+    }
+    if let Some(c) = endpoint.chars().find(|c| RESERVED_ADDRESS_CHARS.contains(c) || c.is_control()) {
+        return Err(format!("Address can't contain '{}'", c));
+    }
+    if !value_matches_type(&config.osc_type, &config.osc_value) {
+        return Err("Value doesn't match the parameter type".to_string());
+    }
+    if !value_matches_type(&config.osc_type, &config.default_value) {
+        return Err("Default value doesn't match the parameter type".to_string());
+    }
+    Ok(())
+}
+
+fn value_matches_type(osc_type: &OSCMessageType, value: &OSCValue) -> bool {
+    matches!(
+        (osc_type, value),
+        (OSCMessageType::Boolean, OSCValue::Boolean(_))
+            | (OSCMessageType::Integer, OSCValue::Integer(_))
+            | (OSCMessageType::Float, OSCValue::Float(_))
+            | (OSCMessageType::String, OSCValue::String(_))
+    )
 }
 
 impl Default for OSCConfigurations {
@@ -59,6 +144,11 @@ impl Default for OSCConfigurations {
             execution_duration: Some(300), // 5 seconds * 60 frames
             send_chat_message: false,
         });
-        OSCConfigurations { configs }
+        OSCConfigurations {
+            configs,
+            heartbeat: OSCHeartbeatConfig::default(),
+            avatar_profiles: HashMap::new(),
+            active_avatar: None,
+        }
     }
 }
\ No newline at end of file
@@ -3,18 +3,20 @@ use std::time::Duration;
 use crate::osc::client::OSCClient;
 use crate::osc::errors::OSCError;
 use crate::osc::models::{OSCConfig, OSCMessageType, OSCValue};
+use crate::output_filter::OutputFilter;
 
 pub struct VRChatOSC {
     client: Arc<tokio::sync::RwLock<OSCClient>>,
+    output_filter: Arc<OutputFilter>,
 }
 
 impl VRChatOSC {
-    pub fn new(client: Arc<tokio::sync::RwLock<OSCClient>>) -> Self {
-        Self { client }
+    pub fn new(client: Arc<tokio::sync::RwLock<OSCClient>>, output_filter: Arc<OutputFilter>) -> Self {
+        Self { client, output_filter }
     }
 
     pub async fn send_chatbox_message(&self, message: &str, send_immediately: bool, play_sound: bool) -> Result<(), OSCError> {
-        let truncated_message = message.chars().take(144).collect::<String>();
+        let truncated_message = self.output_filter.filter(message).chars().take(144).collect::<String>();
 
         self.client.read().await.send_osc_message(
             "/chatbox/input",
@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+use std::sync::RwLock;
+use tracing::warn;
+use crate::config::OutputFilterConfig;
+
+#[derive(Default)]
+struct FilterState {
+    enabled: bool,
+    deny: Vec<String>,
+    allow: HashSet<String>,
+    ai_moderation: bool,
+}
+
+// Shared by everything that posts bot output, so a config change applies to all of it at once
+#[derive(Default)]
+pub struct OutputFilter {
+    state: RwLock<FilterState>,
+}
+
+impl OutputFilter {
+    pub fn new(config: &OutputFilterConfig) -> Self {
+        let filter = Self::default();
+        filter.configure(config);
+        filter
+    }
+
+    // Loads the word lists; called at startup and when the config changes
+    pub fn configure(&self, config: &OutputFilterConfig) {
+        let mut filter = self.state.write().unwrap();
+        filter.enabled = config.enabled;
+        filter.deny = config.deny_words.iter().map(|w| w.trim().to_lowercase()).filter(|w| !w.is_empty()).collect();
+        filter.allow = config.allow_words.iter().map(|w| w.trim().to_lowercase()).collect();
+        filter.ai_moderation = config.enabled && config.ai_moderation;
+    }
+
+    pub fn ai_moderation_enabled(&self) -> bool {
+        self.state.read().unwrap().ai_moderation
+    }
+
+    // Masks every word containing a denied term, unless the whole word is on the allow list.
+    // Everything the bot sends to chat, the chatbox or TTS goes through here.
+    pub fn filter(&self, text: &str) -> String {
+        mask(&self.state.read().unwrap(), text)
+    }
+}
+
+fn mask(filter: &FilterState, text: &str) -> String {
+    if !filter.enabled || filter.deny.is_empty() {
+        return text.to_string();
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut word = String::new();
+    let mut masked = 0;
+    let mut flush = |word: &mut String, output: &mut String| {
+        if word.is_empty() {
+            return;
+        }
+        let lower = word.to_lowercase();
+        if !filter.allow.contains(&lower) && filter.deny.iter().any(|term| lower.contains(term.as_str())) {
+            output.extend(word.chars().map(|_| '*'));
+            masked += 1;
+        } else {
+            output.push_str(word);
+        }
+        word.clear();
+    };
+
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut output);
+            output.push(c);
+        }
+    }
+    flush(&mut word, &mut output);
+
+    if masked > 0 {
+        warn!("Output filter masked {} word(s) in an outgoing message", masked);
+    }
+    output
+}
+
+pub fn moderation_prompt(text: &str) -> String {
+    format!(
+        "You moderate messages a Twitch bot is about to post. Reply with only SAFE or UNSAFE. \
+         UNSAFE means the message contains slurs, hate speech, harassment, sexual content, threats, \
+         encouragement of self-harm or anything else that breaks Twitch's community guidelines.\n\nMessage: {}",
+        text
+    )
+}
+
+pub fn is_flagged(moderation_response: &str) -> bool {
+    !moderation_response.trim().to_uppercase().starts_with("SAFE")
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+// Every file mewbot reads or writes lives in the working directory unless MEWBOT_DATA_DIR points
+// somewhere else, e.g. a mounted volume. Each file can also be moved on its own with its variable.
+pub const DATA_DIR_VAR: &str = "MEWBOT_DATA_DIR";
+
+// (variable, default file name) for each path; these are never treated as config overrides
+pub const CONFIG_FILE: (&str, &str) = ("MEWBOT_CONFIG_PATH", "mewbot.conf");
+pub const DATABASE: (&str, &str) = ("MEWBOT_DB_PATH", "mewbot_data.db");
+pub const LOG_DIR: (&str, &str) = ("MEWBOT_LOG_DIR", "logs");
+pub const OSC_CONFIG: (&str, &str) = ("MEWBOT_OSC_CONFIG_PATH", "osc_config.json");
+pub const REDEEMS_CONFIG: (&str, &str) = ("MEWBOT_REDEEMS_CONFIG_PATH", "redeems_config.json");
+pub const KEYWORD_TRIGGERS: (&str, &str) = ("MEWBOT_TRIGGERS_PATH", "keyword_triggers.json");
+pub const AI_PROMPTS: (&str, &str) = ("MEWBOT_PROMPTS_PATH", "ai_prompts.json");
+pub const SCHEDULED_JOBS: (&str, &str) = ("MEWBOT_JOBS_PATH", "scheduled_jobs.json");
+pub const HAPPY_HOUR_STATE: (&str, &str) = ("MEWBOT_HAPPY_HOUR_PATH", "happy_hour.json");
+pub const ROLE_GRANTS: (&str, &str) = ("MEWBOT_ROLE_GRANTS_PATH", "role_grants.json");
+pub const FAQ: (&str, &str) = ("MEWBOT_FAQ_PATH", "faq.json");
+pub const SOUNDBOARD: (&str, &str) = ("MEWBOT_SOUNDBOARD_PATH", "soundboard.json");
+pub const COMMAND_CONFIG: (&str, &str) = ("MEWBOT_COMMAND_CONFIG_PATH", "command_config.json");
+pub const ASSETS_DIR: (&str, &str) = ("MEWBOT_ASSETS_DIR", "assets");
+// Audio files the overlay plays for the soundboard
+pub const SOUNDS_DIR: (&str, &str) = ("MEWBOT_SOUNDS_DIR", "sounds");
+
+pub const ALL: &[(&str, &str)] = &[
+    CONFIG_FILE, DATABASE, LOG_DIR, OSC_CONFIG, REDEEMS_CONFIG, KEYWORD_TRIGGERS,
+    AI_PROMPTS, SCHEDULED_JOBS, HAPPY_HOUR_STATE, ROLE_GRANTS, FAQ, SOUNDBOARD, COMMAND_CONFIG, ASSETS_DIR, SOUNDS_DIR,
+];
+
+fn non_empty_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+pub fn data_dir() -> PathBuf {
+    non_empty_var(DATA_DIR_VAR).map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."))
+}
+
+// The file's own variable, or its default name inside the data directory
+pub fn resolve((var, default_name): (&str, &str)) -> String {
+    match non_empty_var(var) {
+        Some(path) => path,
+        None if non_empty_var(DATA_DIR_VAR).is_none() => default_name.to_string(),
+        None => data_dir().join(default_name).to_string_lossy().into_owned(),
+    }
+}
+
+pub fn is_path_var(name: &str) -> bool {
+    name == DATA_DIR_VAR || ALL.iter().any(|(var, _)| *var == name)
+}
@@ -0,0 +1,397 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Datelike, Duration, Local, Timelike, Utc};
+use tracing::{debug, error, info, warn};
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::tasks::TaskRegistry;
+
+
+// Cron schedules are matched against local time, minute by minute, up to a year ahead
+const MAX_CRON_LOOKAHEAD_MINUTES: i64 = 366 * 24 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Schedule {
+    Every { secs: u64 },
+    // Standard five-field cron: minute hour day-of-month month day-of-week
+    Cron { expr: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct JobSettings {
+    pub schedule: Schedule,
+    // Each run is pushed back by a random 0..=jitter_secs so jobs don't all fire together
+    #[serde(default)]
+    pub jitter_secs: u64,
+    pub enabled: bool,
+    // Run once straight away if the bot was down when a run was due
+    #[serde(default)]
+    pub catch_up: bool,
+}
+
+impl JobSettings {
+    pub fn every(secs: u64) -> Self {
+        Self {
+            schedule: Schedule::Every { secs },
+            jitter_secs: 0,
+            enabled: true,
+            catch_up: false,
+        }
+    }
+
+    pub fn with_jitter(mut self, jitter_secs: u64) -> Self {
+        self.jitter_secs = jitter_secs;
+        self
+    }
+
+    pub fn with_catch_up(mut self) -> Self {
+        self.catch_up = true;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct JobInfo {
+    pub name: String,
+    pub description: String,
+    pub settings: JobSettings,
+    pub last_run: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub next_run: Option<DateTime<Utc>>,
+    pub running: bool,
+    // Held back by the active stream profile
+    pub paused: bool,
+}
+
+// What survives a restart; the handler and description come from the code.
+// Settings are only saved once they've been changed from the dashboard.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedJob {
+    #[serde(default)]
+    settings: Option<JobSettings>,
+    last_run: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    next_run: Option<DateTime<Utc>>,
+}
+
+type JobFn = Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send + Sync>;
+
+struct Job {
+    info: JobInfo,
+    customized: bool,
+    handler: JobFn,
+}
+
+struct SchedulerState {
+    path: String,
+    persisted: HashMap<String, PersistedJob>,
+    jobs: HashMap<String, Job>,
+    paused: HashSet<String>,
+}
+
+// Recurring jobs for one bot instance; runs are spawned on its task registry
+pub struct Scheduler {
+    state: Mutex<SchedulerState>,
+    tasks: Arc<TaskRegistry>,
+}
+
+// Records how a job's run ended when its task does, so `running` is cleared even if the handler
+// panics or the task is cancelled at shutdown
+struct RunningGuard {
+    scheduler: Arc<Scheduler>,
+    name: String,
+    // None until the handler returns
+    result: Option<Result<(), String>>,
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        let mut state = self.scheduler.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(job) = state.jobs.get_mut(&self.name) {
+            job.info.running = false;
+            let result = match self.result.take() {
+                Some(result) => Some(result),
+                None if std::thread::panicking() => Some(Err("The job panicked".to_string())),
+                None => None,
+            };
+            if let Some(result) = result {
+                job.info.last_run = Some(Utc::now());
+                job.info.last_error = result.err();
+            }
+        }
+        save(&state);
+    }
+}
+
+fn save(scheduler: &SchedulerState) {
+    let jobs: HashMap<&String, PersistedJob> = scheduler.jobs.iter()
+        .map(|(name, job)| (name, PersistedJob {
+            settings: job.customized.then(|| job.info.settings.clone()),
+            last_run: job.info.last_run,
+            last_error: job.info.last_error.clone(),
+            next_run: job.info.next_run,
+        }))
+        .collect();
+    let result = serde_json::to_string_pretty(&jobs)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(&scheduler.path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Failed to save scheduled jobs to {}: {}", scheduler.path, e);
+    }
+}
+
+// One cron field as the set of values it allows, or None if it's malformed
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|s| *s > 0)?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                None => {
+                    let value = range.parse().ok()?;
+                    (value, if part.contains('/') { max } else { value })
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    Some(values)
+}
+
+struct CronSpec {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days: Vec<u32>,
+    months: Vec<u32>,
+    weekdays: Vec<u32>,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronSpec {
+    fn parse(expr: &str) -> Option<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return None;
+        }
+        Some(Self {
+            minutes: parse_cron_field(fields[0], 0, 59)?,
+            hours: parse_cron_field(fields[1], 0, 23)?,
+            days: parse_cron_field(fields[2], 1, 31)?,
+            months: parse_cron_field(fields[3], 1, 12)?,
+            // 7 is Sunday too
+            weekdays: parse_cron_field(fields[4], 0, 7)?.into_iter().map(|d| d % 7).collect(),
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+
+    fn matches(&self, time: &DateTime<Local>) -> bool {
+        let day = self.days.contains(&time.day());
+        let weekday = self.weekdays.contains(&time.weekday().num_days_from_sunday());
+        // Like cron, a restricted day-of-month and day-of-week match if either does
+        let day_matches = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        };
+        day_matches
+            && self.minutes.contains(&time.minute())
+            && self.hours.contains(&time.hour())
+            && self.months.contains(&time.month())
+    }
+
+    fn next_after(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let from = from.with_timezone(&Local);
+        let start = from.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        (0..MAX_CRON_LOOKAHEAD_MINUTES)
+            .map(|minutes| start + Duration::minutes(minutes))
+            .find(|time| self.matches(time))
+            .map(|time| time.with_timezone(&Utc))
+    }
+}
+
+pub fn validate(settings: &JobSettings) -> Result<(), String> {
+    match &settings.schedule {
+        Schedule::Every { secs } if *secs == 0 => Err("The interval must be at least a second".to_string()),
+        Schedule::Cron { expr } if CronSpec::parse(expr).is_none() => Err(format!("Invalid cron expression: {}", expr)),
+        _ => Ok(()),
+    }
+}
+
+fn next_run(settings: &JobSettings, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let next = match &settings.schedule {
+        Schedule::Every { secs } => from + Duration::seconds((*secs).max(1) as i64),
+        Schedule::Cron { expr } => CronSpec::parse(expr)?.next_after(from)?,
+    };
+    let jitter = match settings.jitter_secs {
+        0 => 0,
+        jitter => rand::thread_rng().gen_range(0..=jitter),
+    };
+    Some(next + Duration::seconds(jitter as i64))
+}
+
+impl Scheduler {
+    pub fn new(path: &str, tasks: Arc<TaskRegistry>) -> Self {
+        Self {
+            state: Mutex::new(SchedulerState {
+                path: path.to_string(),
+                persisted: HashMap::new(),
+                jobs: HashMap::new(),
+                paused: HashSet::new(),
+            }),
+            tasks,
+        }
+    }
+
+    // Reads saved schedules and run times, so it has to happen before any job is registered
+    pub fn load(path: &str, tasks: Arc<TaskRegistry>) -> Self {
+        let persisted = match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                error!("Failed to parse {}, using default job schedules: {}", path, e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        let scheduler = Self::new(path, tasks);
+        scheduler.state.lock().unwrap().persisted = persisted;
+        scheduler
+    }
+
+    // Registers a recurring job. Settings saved from the dashboard win over `defaults`.
+    pub fn register<F, Fut>(&self, name: &str, description: &str, defaults: JobSettings, handler: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        let now = Utc::now();
+        let mut scheduler = self.state.lock().unwrap();
+        let persisted = scheduler.persisted.get(name).cloned();
+        let saved_settings = persisted.as_ref()
+            .and_then(|saved| saved.settings.clone())
+            .filter(|settings| validate(settings).is_ok());
+        let customized = saved_settings.is_some();
+        let settings = saved_settings.unwrap_or(defaults);
+
+        let next = match persisted.as_ref().and_then(|saved| saved.next_run) {
+            Some(due) if due > now => Some(due),
+            Some(due) if settings.catch_up => {
+                info!("Job {} missed its run at {}, catching up", name, due);
+                Some(now)
+            }
+            Some(due) => {
+                debug!("Job {} missed its run at {}, skipping to the next one", name, due);
+                next_run(&settings, now)
+            }
+            None => next_run(&settings, now),
+        };
+
+        let handler: JobFn = Arc::new(move || Box::pin(handler()));
+        scheduler.jobs.insert(name.to_string(), Job {
+            info: JobInfo {
+                name: name.to_string(),
+                description: description.to_string(),
+                settings,
+                last_run: persisted.as_ref().and_then(|saved| saved.last_run),
+                last_error: persisted.and_then(|saved| saved.last_error),
+                next_run: next,
+                running: false,
+                paused: false,
+            },
+            customized,
+            handler,
+        });
+        save(&scheduler);
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> {
+        let scheduler = self.state.lock().unwrap();
+        let mut jobs: Vec<JobInfo> = scheduler.jobs.values()
+            .map(|job| JobInfo {
+                paused: scheduler.paused.contains(&job.info.name),
+                ..job.info.clone()
+            })
+            .collect();
+        jobs.sort_by(|a, b| a.name.cmp(&b.name));
+        jobs
+    }
+
+    pub fn update(&self, name: &str, settings: JobSettings) -> Result<(), String> {
+        validate(&settings)?;
+        let mut scheduler = self.state.lock().unwrap();
+        let job = scheduler.jobs.get_mut(name).ok_or_else(|| format!("Unknown job {}", name))?;
+        job.info.next_run = next_run(&settings, Utc::now());
+        job.info.settings = settings;
+        job.customized = true;
+        info!("Updated schedule for job {}", name);
+        save(&scheduler);
+        Ok(())
+    }
+
+    // Replaces the set of jobs that are skipped when due; names of jobs that aren't registered yet are kept
+    pub fn set_paused(&self, names: &[String]) {
+        self.state.lock().unwrap().paused = names.iter().cloned().collect();
+    }
+
+    // Runs the job straight away, even when it's disabled
+    pub fn run_now(self: &Arc<Self>, name: &str) -> Result<(), String> {
+        let mut scheduler = self.state.lock().unwrap();
+        let job = scheduler.jobs.get_mut(name).ok_or_else(|| format!("Unknown job {}", name))?;
+        if job.info.running {
+            return Err(format!("{} is already running", name));
+        }
+        self.spawn_job(name.to_string(), job);
+        Ok(())
+    }
+
+    fn spawn_job(self: &Arc<Self>, name: String, job: &mut Job) {
+        job.info.running = true;
+        let handler = job.handler.clone();
+        // Made out here so it's dropped even if the task is cancelled before it first runs
+        let mut guard = RunningGuard { scheduler: self.clone(), name: name.clone(), result: None };
+        self.tasks.spawn(&format!("job_{}", name), async move {
+            debug!("Running job {}", guard.name);
+            let result = handler().await;
+            if let Err(e) = &result {
+                warn!("Job {} failed: {}", guard.name, e);
+            }
+            guard.result = Some(result);
+        });
+    }
+
+    // Starts due jobs once a second; a job that is still running when it comes due again is skipped
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            let now = Utc::now();
+            let mut guard = self.state.lock().unwrap();
+            let scheduler = &mut *guard;
+            for (name, job) in scheduler.jobs.iter_mut() {
+                let due = job.info.settings.enabled
+                    && !scheduler.paused.contains(name)
+                    && job.info.next_run.map_or(false, |next| next <= now);
+                if !due {
+                    continue;
+                }
+                job.info.next_run = next_run(&job.info.settings, now);
+                if job.info.running {
+                    warn!("Job {} is still running, skipping this run", name);
+                    continue;
+                }
+                self.spawn_job(name.clone(), job);
+            }
+        }
+    }
+}
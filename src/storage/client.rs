@@ -1,10 +1,10 @@
 use rusqlite::{Connection, Result, params};
-use crate::storage::models::ChatterData;
+use crate::storage::models::{ApiToken, ArchivedClip, ChatLogEntry, ChatterData, CommandUsageStats, CustomCommand, FollowerGrowth, LeaderboardEntry, ModerationEvent, ReachedMilestone, RewardPriceChange, SegmentTotal, ShortLink, StoredEvent, StreamHealthSample, StreamSegment, StreamSuggestion};
 use std::path::Path;
 use std::str::FromStr;
 use chrono::{DateTime, Utc};
 use std::sync::{Arc, Mutex};
-use log::info;
+use tracing::info;
 // use parking_lot::RwLock;
 // use lru_cache::LruCache;
 use crate::twitch::roles::UserRole;
@@ -70,6 +70,328 @@ impl StorageClient {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS moderation_log (
+                id INTEGER PRIMARY KEY,
+                event_type TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                user_name TEXT NOT NULL,
+                moderator_id TEXT NOT NULL,
+                moderator_name TEXT NOT NULL,
+                reason TEXT,
+                ends_at TEXT,
+                appeal_status TEXT,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS clips (
+                clip_id TEXT PRIMARY KEY,
+                url TEXT NOT NULL,
+                title TEXT NOT NULL,
+                broadcaster_name TEXT NOT NULL,
+                creator_name TEXT NOT NULL,
+                submitted_by TEXT NOT NULL,
+                thumbnail_url TEXT,
+                duration REAL NOT NULL,
+                stream_id TEXT,
+                created_at TEXT NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS chat_log (
+                message_id TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                user_name TEXT NOT NULL,
+                message TEXT NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // The chat log is only used for short moderation lookbacks
+        conn.execute(
+            "DELETE FROM chat_log WHERE timestamp < ?1",
+            params![(Utc::now() - chrono::Duration::days(7)).timestamp()],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS chat_activity (
+                id INTEGER PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                user_name TEXT NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_chat_activity_timestamp ON chat_activity (timestamp)",
+            [],
+        )?;
+
+        // Leaderboards look back a month at most
+        conn.execute(
+            "DELETE FROM chat_activity WHERE timestamp < ?1",
+            params![(Utc::now() - chrono::Duration::days(31)).timestamp()],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS redemption_log (
+                id INTEGER PRIMARY KEY,
+                redemption_id TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                user_name TEXT NOT NULL,
+                reward_title TEXT NOT NULL,
+                cost INTEGER NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS channel_stats (
+                key TEXT PRIMARY KEY,
+                value INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS stream_sessions (
+                id INTEGER PRIMARY KEY,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS milestones (
+                kind TEXT NOT NULL,
+                threshold INTEGER NOT NULL,
+                reached_at INTEGER NOT NULL,
+                PRIMARY KEY (kind, threshold)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS avatar_log (
+                id INTEGER PRIMARY KEY,
+                avatar_id TEXT NOT NULL,
+                avatar_name TEXT NOT NULL,
+                stream_session_id INTEGER,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS ignored_users (
+                user_login TEXT PRIMARY KEY,
+                user_id TEXT,
+                reason TEXT NOT NULL,
+                added_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS drop_points (
+                user_id TEXT PRIMARY KEY,
+                user_name TEXT NOT NULL,
+                points INTEGER NOT NULL,
+                drops_won INTEGER NOT NULL,
+                last_won_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS stream_health_log (
+                id INTEGER PRIMARY KEY,
+                stream_session_id INTEGER,
+                instance_name TEXT NOT NULL,
+                dropped_frames_percent REAL NOT NULL,
+                encoder_lag_percent REAL NOT NULL,
+                render_lag_percent REAL NOT NULL,
+                bitrate_kbps REAL NOT NULL,
+                cpu_usage REAL NOT NULL,
+                fps REAL NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS follower_snapshots (
+                id INTEGER PRIMARY KEY,
+                stream_session_id INTEGER,
+                follower_count INTEGER NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS short_links (
+                slug TEXT PRIMARY KEY,
+                url TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                clicks INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS trivia_stats (
+                user_id TEXT PRIMARY KEY,
+                user_login TEXT NOT NULL,
+                correct_answers INTEGER NOT NULL,
+                points INTEGER NOT NULL,
+                last_correct_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS lurk_sessions (
+                id INTEGER PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                user_login TEXT NOT NULL,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS eventsub_log (
+                id INTEGER PRIMARY KEY,
+                event_type TEXT NOT NULL,
+                message TEXT NOT NULL,
+                received_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS command_usage (
+                id INTEGER PRIMARY KEY,
+                command TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                user_name TEXT NOT NULL,
+                outcome TEXT NOT NULL,
+                timestamp INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_command_usage_timestamp ON command_usage (timestamp)",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS api_tokens (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                token_hash TEXT NOT NULL UNIQUE,
+                scopes TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER,
+                revoked INTEGER NOT NULL DEFAULT 0
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS tts_bans (
+                user_login TEXT PRIMARY KEY,
+                banned_by TEXT NOT NULL,
+                added_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS stream_suggestions (
+                id INTEGER PRIMARY KEY,
+                kind TEXT NOT NULL,
+                value TEXT NOT NULL,
+                user_id TEXT NOT NULL,
+                user_name TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'pending',
+                created_at INTEGER NOT NULL,
+                decided_by TEXT,
+                decided_at INTEGER
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS opted_out_users (
+                user_id TEXT PRIMARY KEY,
+                user_login TEXT NOT NULL,
+                added_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS custom_commands (
+                name TEXT PRIMARY KEY,
+                response TEXT NOT NULL,
+                created_by TEXT NOT NULL,
+                uses INTEGER NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS kv_store (
+                namespace TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                updated_at INTEGER NOT NULL,
+                PRIMARY KEY (namespace, key)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS stream_segments (
+                id INTEGER PRIMARY KEY,
+                stream_session_id INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                name TEXT NOT NULL,
+                started_at INTEGER NOT NULL,
+                ended_at INTEGER
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS reward_price_changes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                reward_title TEXT NOT NULL,
+                old_cost INTEGER NOT NULL,
+                new_cost INTEGER NOT NULL,
+                reason TEXT NOT NULL,
+                changed_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         println!("Database schema created or updated successfully");
 
         Ok(StorageClient {
@@ -116,6 +438,9 @@ impl StorageClient {
         let query = "INSERT INTO messages (user_id, message, timestamp) VALUES (?1, ?2, ?3)";
 
         let conn = self.conn.lock().unwrap();
+        if Self::opted_out(&conn, user_id)? {
+            return Ok(());
+        }
 
         // First, ensure the user exists in the chatters table
         self.ensure_chatter_exists(user_id, &conn)?;
@@ -140,6 +465,1149 @@ impl StorageClient {
         Ok(())
     }
 
+    pub fn add_moderation_event(
+        &self,
+        event_type: &str,
+        user_id: &str,
+        user_name: &str,
+        moderator_id: &str,
+        moderator_name: &str,
+        reason: Option<&str>,
+        ends_at: Option<&str>,
+    ) -> Result<i64> {
+        let query = "INSERT INTO moderation_log (event_type, user_id, user_name, moderator_id, moderator_name, reason, ends_at, timestamp)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![event_type, user_id, user_name, moderator_id, moderator_name, reason, ends_at, Utc::now().timestamp()])?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn get_moderation_log(&self, event_type: Option<&str>, user: Option<&str>, limit: usize) -> Result<Vec<ModerationEvent>> {
+        let query = "SELECT id, event_type, user_id, user_name, moderator_id, moderator_name, reason, ends_at, appeal_status, timestamp
+                     FROM moderation_log
+                     WHERE (?1 IS NULL OR event_type = ?1)
+                       AND (?2 IS NULL OR user_name = ?2 COLLATE NOCASE OR moderator_name = ?2 COLLATE NOCASE)
+                     ORDER BY timestamp DESC
+                     LIMIT ?3";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let events = stmt.query_map(params![event_type, user, limit as i64], |row| {
+            Ok(ModerationEvent {
+                id: row.get(0)?,
+                event_type: row.get(1)?,
+                user_id: row.get(2)?,
+                user_name: row.get(3)?,
+                moderator_id: row.get(4)?,
+                moderator_name: row.get(5)?,
+                reason: row.get(6)?,
+                ends_at: row.get(7)?,
+                appeal_status: row.get(8)?,
+                timestamp: DateTime::from_timestamp(row.get::<_, i64>(9)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(events)
+    }
+
+    pub fn set_appeal_status(&self, id: i64, status: Option<&str>) -> Result<bool> {
+        let query = "UPDATE moderation_log SET appeal_status = ?1 WHERE id = ?2";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let updated = stmt.execute(params![status, id])?;
+
+        Ok(updated > 0)
+    }
+
+    pub fn log_chat_message(&self, message_id: &str, user_id: &str, user_name: &str, message: &str) -> Result<()> {
+        let query = "INSERT OR IGNORE INTO chat_log (message_id, user_id, user_name, message, timestamp) VALUES (?1, ?2, ?3, ?4, ?5)";
+
+        let conn = self.conn.lock().unwrap();
+        if Self::opted_out(&conn, user_id)? {
+            return Ok(());
+        }
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![message_id, user_id, user_name, message, Utc::now().timestamp()])?;
+
+        Ok(())
+    }
+
+    pub fn find_recent_chat_messages(&self, phrase: &str, since: DateTime<Utc>) -> Result<Vec<ChatLogEntry>> {
+        let query = "SELECT message_id, user_id, user_name, message, timestamp
+                     FROM chat_log
+                     WHERE timestamp >= ?1 AND instr(lower(message), lower(?2)) > 0
+                     ORDER BY timestamp DESC";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let entries = stmt.query_map(params![since.timestamp(), phrase], |row| {
+            Ok(ChatLogEntry {
+                message_id: row.get(0)?,
+                user_id: row.get(1)?,
+                user_name: row.get(2)?,
+                message: row.get(3)?,
+                timestamp: DateTime::from_timestamp(row.get::<_, i64>(4)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+
+    // Most recent chat line that isn't a command, skipping the given message (usually the caller's own)
+    pub fn get_last_chat_message(&self, exclude_message_id: &str) -> Result<Option<ChatLogEntry>> {
+        let query = "SELECT message_id, user_id, user_name, message, timestamp
+                     FROM chat_log
+                     WHERE message_id != ?1 AND message NOT LIKE '!%'
+                     ORDER BY timestamp DESC, rowid DESC
+                     LIMIT 1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let mut rows = stmt.query_map(params![exclude_message_id], |row| {
+            Ok(ChatLogEntry {
+                message_id: row.get(0)?,
+                user_id: row.get(1)?,
+                user_name: row.get(2)?,
+                message: row.get(3)?,
+                timestamp: DateTime::from_timestamp(row.get::<_, i64>(4)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        })?;
+
+        rows.next().transpose()
+    }
+
+    pub fn record_chat_activity(&self, user_id: &str, user_name: &str) -> Result<()> {
+        let query = "INSERT INTO chat_activity (user_id, user_name, timestamp) VALUES (?1, ?2, ?3)";
+
+        let conn = self.conn.lock().unwrap();
+        if Self::opted_out(&conn, user_id)? {
+            return Ok(());
+        }
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![user_id, user_name, Utc::now().timestamp()])?;
+
+        Ok(())
+    }
+
+    pub fn log_redemption(&self, redemption_id: &str, user_id: &str, user_name: &str, reward_title: &str, cost: u64) -> Result<()> {
+        let query = "INSERT INTO redemption_log (redemption_id, user_id, user_name, reward_title, cost, timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
+
+        let conn = self.conn.lock().unwrap();
+        if Self::opted_out(&conn, user_id)? {
+            return Ok(());
+        }
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![redemption_id, user_id, user_name, reward_title, cost as i64, Utc::now().timestamp()])?;
+
+        Ok(())
+    }
+
+    pub fn get_top_chatters(&self, since: DateTime<Utc>, limit: usize) -> Result<Vec<LeaderboardEntry>> {
+        let query = "SELECT user_id, MAX(user_name), COUNT(*) AS total
+                     FROM chat_activity
+                     WHERE timestamp >= ?1 AND user_id NOT IN (SELECT user_id FROM opted_out_users)
+                     GROUP BY user_id
+                     ORDER BY total DESC
+                     LIMIT ?2";
+        self.query_leaderboard(query, since, limit)
+    }
+
+    pub fn get_top_redeemers(&self, since: DateTime<Utc>, limit: usize) -> Result<Vec<LeaderboardEntry>> {
+        let query = "SELECT user_id, MAX(user_name), COUNT(*) AS total
+                     FROM redemption_log
+                     WHERE timestamp >= ?1 AND user_id NOT IN (SELECT user_id FROM opted_out_users)
+                     GROUP BY user_id
+                     ORDER BY total DESC
+                     LIMIT ?2";
+        self.query_leaderboard(query, since, limit)
+    }
+
+    // Twitch doesn't expose viewers' point balances, so points spent on redeems is the closest stat we have
+    pub fn get_top_point_spenders(&self, since: DateTime<Utc>, limit: usize) -> Result<Vec<LeaderboardEntry>> {
+        let query = "SELECT user_id, MAX(user_name), SUM(cost) AS total
+                     FROM redemption_log
+                     WHERE timestamp >= ?1 AND user_id NOT IN (SELECT user_id FROM opted_out_users)
+                     GROUP BY user_id
+                     ORDER BY total DESC
+                     LIMIT ?2";
+        self.query_leaderboard(query, since, limit)
+    }
+
+    pub fn get_channel_stat(&self, key: &str) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached("SELECT value FROM channel_stats WHERE key = ?1")?;
+        let mut rows = stmt.query(params![key])?;
+        match rows.next()? {
+            Some(row) => row.get(0),
+            None => Ok(0),
+        }
+    }
+
+    pub fn set_channel_stat(&self, key: &str, value: i64) -> Result<()> {
+        let query = "INSERT INTO channel_stats (key, value) VALUES (?1, ?2)
+                     ON CONFLICT(key) DO UPDATE SET value = excluded.value";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![key, value])?;
+
+        Ok(())
+    }
+
+    pub fn increment_channel_stat(&self, key: &str, amount: i64) -> Result<i64> {
+        let query = "INSERT INTO channel_stats (key, value) VALUES (?1, ?2)
+                     ON CONFLICT(key) DO UPDATE SET value = value + excluded.value
+                     RETURNING value";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![key, amount], |row| row.get(0))
+    }
+
+    pub fn start_stream_session(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        // A crash mid-stream leaves a session open; close it where it was last known to be running
+        conn.execute("UPDATE stream_sessions SET ended_at = started_at WHERE ended_at IS NULL", [])?;
+        conn.execute("UPDATE stream_segments SET ended_at = started_at WHERE ended_at IS NULL", [])?;
+        conn.execute(
+            "INSERT INTO stream_sessions (started_at) VALUES (?1)",
+            params![Utc::now().timestamp()],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn end_stream_session(&self) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().timestamp();
+        conn.execute("UPDATE stream_sessions SET ended_at = ?1 WHERE ended_at IS NULL", params![now])?;
+        conn.execute("UPDATE stream_segments SET ended_at = ?1 WHERE ended_at IS NULL", params![now])?;
+
+        Ok(())
+    }
+
+    // Ends the open segment of this kind and starts a new one in the running stream.
+    // Does nothing while there's no stream running.
+    pub fn start_stream_segment(&self, kind: &str, name: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let now = Utc::now().timestamp();
+        conn.execute("UPDATE stream_segments SET ended_at = ?2 WHERE kind = ?1 AND ended_at IS NULL", params![kind, now])?;
+        conn.execute(
+            "INSERT INTO stream_segments (stream_session_id, kind, name, started_at)
+             SELECT id, ?1, ?2, ?3 FROM stream_sessions WHERE ended_at IS NULL ORDER BY id DESC LIMIT 1",
+            params![kind, name, now],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn end_stream_segment(&self, kind: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE stream_segments SET ended_at = ?2 WHERE kind = ?1 AND ended_at IS NULL",
+            params![kind, Utc::now().timestamp()],
+        )?;
+
+        Ok(())
+    }
+
+    // Segments of the latest stream in the order they started, the open one counted up to now
+    pub fn get_latest_stream_segments(&self) -> Result<Vec<StreamSegment>> {
+        let query = "SELECT kind, name, started_at, ended_at, COALESCE(ended_at, ?1) - started_at
+                     FROM stream_segments
+                     WHERE stream_session_id = (SELECT MAX(id) FROM stream_sessions)
+                     ORDER BY started_at, id";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let segments = stmt.query_map(params![Utc::now().timestamp()], |row| {
+            Ok(StreamSegment {
+                kind: row.get(0)?,
+                name: row.get(1)?,
+                started_at: DateTime::from_timestamp(row.get::<_, i64>(2)?, 0).unwrap_or_else(|| Utc::now()),
+                ended_at: row.get::<_, Option<i64>>(3)?.and_then(|ended_at| DateTime::from_timestamp(ended_at, 0)),
+                seconds: row.get(4)?,
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(segments)
+    }
+
+    // Time spent in each game and world across the streams since `since`, most first
+    pub fn get_segment_totals(&self, since: DateTime<Utc>) -> Result<Vec<SegmentTotal>> {
+        let query = "SELECT kind, name, SUM(COALESCE(ended_at, ?1) - started_at) AS seconds, COUNT(DISTINCT stream_session_id)
+                     FROM stream_segments
+                     WHERE started_at >= ?2
+                     GROUP BY kind, name
+                     ORDER BY seconds DESC";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let totals = stmt.query_map(params![Utc::now().timestamp(), since.timestamp()], |row| {
+            Ok(SegmentTotal {
+                kind: row.get(0)?,
+                name: row.get(1)?,
+                seconds: row.get(2)?,
+                streams: row.get(3)?,
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(totals)
+    }
+
+    pub fn add_ignored_user(&self, user_login: &str, user_id: Option<&str>, reason: &str) -> Result<()> {
+        let query = "INSERT OR REPLACE INTO ignored_users (user_login, user_id, reason, added_at) VALUES (?1, ?2, ?3, ?4)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![user_login.to_lowercase(), user_id, reason, Utc::now().timestamp()])?;
+
+        Ok(())
+    }
+
+    // Returns false if the user wasn't on the list
+    pub fn remove_ignored_user(&self, user_login: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute("DELETE FROM ignored_users WHERE user_login = ?1", params![user_login.to_lowercase()])?;
+
+        Ok(removed > 0)
+    }
+
+    // Only undoes entries added automatically for a ban, not ones a mod added by hand
+    pub fn remove_banned_ignored_user(&self, user_id: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM ignored_users WHERE user_id = ?1 AND reason = 'banned'", params![user_id])?;
+
+        Ok(())
+    }
+
+    pub fn is_user_ignored(&self, user_id: &str, user_login: &str) -> Result<bool> {
+        let query = "SELECT EXISTS(SELECT 1 FROM ignored_users WHERE user_id = ?1 OR user_login = ?2)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![user_id, user_login.to_lowercase()], |row| row.get(0))
+    }
+
+    pub fn get_ignored_users(&self) -> Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached("SELECT user_login FROM ignored_users ORDER BY user_login")?;
+        let users = stmt.query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>>>()?;
+
+        Ok(users)
+    }
+
+    // Returns false if they had already opted out. Their stored chat, redeem and lurk history is deleted.
+    pub fn add_opt_out(&self, user_id: &str, user_login: &str) -> Result<bool> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let added = tx.execute(
+            "INSERT OR IGNORE INTO opted_out_users (user_id, user_login, added_at) VALUES (?1, ?2, ?3)",
+            params![user_id, user_login.to_lowercase(), Utc::now().timestamp()],
+        )?;
+        for table in ["messages", "chat_log", "chat_activity", "redemption_log", "lurk_sessions"] {
+            tx.execute(&format!("DELETE FROM {} WHERE user_id = ?1", table), params![user_id])?;
+        }
+        tx.commit()?;
+
+        Ok(added > 0)
+    }
+
+    // Returns false if they hadn't opted out
+    pub fn remove_opt_out(&self, user_id: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute("DELETE FROM opted_out_users WHERE user_id = ?1", params![user_id])?;
+
+        Ok(removed > 0)
+    }
+
+    pub fn is_opted_out(&self, user_id: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        Self::opted_out(&conn, user_id)
+    }
+
+    // For writes that already hold the connection lock
+    fn opted_out(conn: &Connection, user_id: &str) -> Result<bool> {
+        let mut stmt = conn.prepare_cached("SELECT EXISTS(SELECT 1 FROM opted_out_users WHERE user_id = ?1)")?;
+        stmt.query_row(params![user_id], |row| row.get(0))
+    }
+
+    // Returns the winner's new point total
+    pub fn award_drop_points(&self, user_id: &str, user_name: &str, points: u64) -> Result<i64> {
+        let query = "INSERT INTO drop_points (user_id, user_name, points, drops_won, last_won_at) VALUES (?1, ?2, ?3, 1, ?4)
+                     ON CONFLICT(user_id) DO UPDATE SET
+                         user_name = excluded.user_name,
+                         points = points + excluded.points,
+                         drops_won = drops_won + 1,
+                         last_won_at = excluded.last_won_at
+                     RETURNING points";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![user_id, user_name, points as i64, Utc::now().timestamp()], |row| row.get(0))
+    }
+
+    // Adds or takes away drop points without counting a drop. Returns the new total, or None when
+    // they don't have enough to take
+    pub fn adjust_drop_points(&self, user_id: &str, delta: i64) -> Result<Option<i64>> {
+        let query = "UPDATE drop_points SET points = points + ?2 WHERE user_id = ?1 AND points + ?2 >= 0 RETURNING points";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        match stmt.query_row(params![user_id, delta], |row| row.get(0)) {
+            Ok(points) => Ok(Some(points)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Ties the change to the stream that's running, if any
+    pub fn log_avatar_change(&self, avatar_id: &str, avatar_name: &str) -> Result<()> {
+        let query = "INSERT INTO avatar_log (avatar_id, avatar_name, stream_session_id, timestamp)
+                     VALUES (?1, ?2, (SELECT MAX(id) FROM stream_sessions WHERE ended_at IS NULL), ?3)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![avatar_id, avatar_name, Utc::now().timestamp()])?;
+
+        Ok(())
+    }
+
+    // Lifetime seconds streamed, counting the current stream up to now
+    pub fn get_total_stream_seconds(&self) -> Result<i64> {
+        let query = "SELECT COALESCE(SUM(COALESCE(ended_at, ?1) - started_at), 0) FROM stream_sessions";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![Utc::now().timestamp()], |row| row.get(0))
+    }
+
+    // Returns false if this milestone was already celebrated
+    pub fn mark_milestone_reached(&self, kind: &str, threshold: u64) -> Result<bool> {
+        let query = "INSERT OR IGNORE INTO milestones (kind, threshold, reached_at) VALUES (?1, ?2, ?3)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let inserted = stmt.execute(params![kind, threshold as i64, Utc::now().timestamp()])?;
+
+        Ok(inserted > 0)
+    }
+
+    pub fn get_reached_milestones(&self) -> Result<Vec<ReachedMilestone>> {
+        let query = "SELECT kind, threshold, reached_at FROM milestones ORDER BY reached_at DESC";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let milestones = stmt.query_map([], |row| {
+            Ok(ReachedMilestone {
+                kind: row.get(0)?,
+                threshold: row.get::<_, i64>(1)? as u64,
+                reached_at: DateTime::from_timestamp(row.get::<_, i64>(2)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(milestones)
+    }
+
+    pub fn log_stream_health(&self, sample: &StreamHealthSample) -> Result<()> {
+        let query = "INSERT INTO stream_health_log (stream_session_id, instance_name, dropped_frames_percent, encoder_lag_percent,
+                         render_lag_percent, bitrate_kbps, cpu_usage, fps, timestamp)
+                     VALUES ((SELECT MAX(id) FROM stream_sessions WHERE ended_at IS NULL), ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![
+            sample.instance_name,
+            sample.dropped_frames_percent,
+            sample.encoder_lag_percent,
+            sample.render_lag_percent,
+            sample.bitrate_kbps,
+            sample.cpu_usage,
+            sample.fps,
+            sample.timestamp.timestamp(),
+        ])?;
+
+        Ok(())
+    }
+
+    // Samples from the most recent stream session, oldest first
+    pub fn get_last_session_health(&self) -> Result<Vec<StreamHealthSample>> {
+        let query = "SELECT instance_name, dropped_frames_percent, encoder_lag_percent, render_lag_percent,
+                            bitrate_kbps, cpu_usage, fps, timestamp
+                     FROM stream_health_log
+                     WHERE stream_session_id = (SELECT MAX(id) FROM stream_sessions)
+                     ORDER BY timestamp";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let samples = stmt.query_map([], |row| {
+            Ok(StreamHealthSample {
+                instance_name: row.get(0)?,
+                dropped_frames_percent: row.get(1)?,
+                encoder_lag_percent: row.get(2)?,
+                render_lag_percent: row.get(3)?,
+                bitrate_kbps: row.get(4)?,
+                cpu_usage: row.get(5)?,
+                fps: row.get(6)?,
+                timestamp: DateTime::from_timestamp(row.get::<_, i64>(7)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(samples)
+    }
+
+    pub fn add_follower_snapshot(&self, follower_count: u32) -> Result<()> {
+        let query = "INSERT INTO follower_snapshots (stream_session_id, follower_count, timestamp)
+                     VALUES ((SELECT MAX(id) FROM stream_sessions WHERE ended_at IS NULL), ?1, ?2)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![follower_count, Utc::now().timestamp()])?;
+
+        Ok(())
+    }
+
+    // The follower count as of `at`: the last snapshot before it, or the first one after it when
+    // tracking started later than that. None until there's a snapshot at all.
+    pub fn get_follower_count_at(&self, at: DateTime<Utc>) -> Result<Option<i64>> {
+        let query = "SELECT COALESCE(
+                         (SELECT follower_count FROM follower_snapshots WHERE timestamp <= ?1 ORDER BY timestamp DESC LIMIT 1),
+                         (SELECT follower_count FROM follower_snapshots WHERE timestamp > ?1 ORDER BY timestamp ASC LIMIT 1)
+                     )";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![at.timestamp()], |row| row.get(0))
+    }
+
+    // Followers at the start and end of the latest `limit` stream sessions, oldest first. The start
+    // is the snapshot taken when the stream went live, or the last one before it.
+    pub fn get_follower_growth_by_session(&self, limit: usize) -> Result<Vec<FollowerGrowth>> {
+        let query = "SELECT id, started_at, ended_at, followers_at_start, followers_at_end FROM (
+                         SELECT s.id, s.started_at, s.ended_at,
+                                COALESCE(
+                                    (SELECT follower_count FROM follower_snapshots WHERE stream_session_id = s.id ORDER BY timestamp ASC LIMIT 1),
+                                    (SELECT follower_count FROM follower_snapshots WHERE timestamp <= s.started_at ORDER BY timestamp DESC LIMIT 1)
+                                ) AS followers_at_start,
+                                (SELECT follower_count FROM follower_snapshots
+                                 WHERE timestamp <= COALESCE(s.ended_at, ?1) ORDER BY timestamp DESC LIMIT 1) AS followers_at_end
+                         FROM stream_sessions s
+                         ORDER BY s.id DESC
+                         LIMIT ?2
+                     )
+                     WHERE followers_at_start IS NOT NULL AND followers_at_end IS NOT NULL
+                     ORDER BY id";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let growth = stmt.query_map(params![Utc::now().timestamp(), limit as i64], |row| {
+            let followers_at_start: i64 = row.get(3)?;
+            let followers_at_end: i64 = row.get(4)?;
+            Ok(FollowerGrowth {
+                stream_session_id: row.get(0)?,
+                started_at: DateTime::from_timestamp(row.get::<_, i64>(1)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+                ended_at: row.get::<_, Option<i64>>(2)?.and_then(|ended_at| DateTime::from_timestamp(ended_at, 0)),
+                followers_at_start,
+                followers_at_end,
+                gained: followers_at_end - followers_at_start,
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(growth)
+    }
+
+    // Returns the user's correct answers and points after this one
+    pub fn record_trivia_win(&self, user_id: &str, user_login: &str, points: u64) -> Result<(i64, i64)> {
+        let query = "INSERT INTO trivia_stats (user_id, user_login, correct_answers, points, last_correct_at) VALUES (?1, ?2, 1, ?3, ?4)
+                     ON CONFLICT(user_id) DO UPDATE SET
+                         user_login = excluded.user_login,
+                         correct_answers = correct_answers + 1,
+                         points = points + excluded.points,
+                         last_correct_at = excluded.last_correct_at
+                     RETURNING correct_answers, points";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![user_id, user_login, points as i64, Utc::now().timestamp()], |row| Ok((row.get(0)?, row.get(1)?)))
+    }
+
+    // Correct answers, points and rank by points, or None if they've never answered
+    pub fn get_trivia_stats(&self, user_login: &str) -> Result<Option<(i64, i64, i64)>> {
+        let query = "SELECT correct_answers, points, (SELECT COUNT(*) FROM trivia_stats AS other WHERE other.points > trivia_stats.points) + 1
+                     FROM trivia_stats WHERE user_login = ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        match stmt.query_row(params![user_login.to_lowercase()], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))) {
+            Ok(stats) => Ok(Some(stats)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Returns false if the slug is already taken
+    pub fn add_short_link(&self, slug: &str, url: &str, created_by: &str) -> Result<bool> {
+        let query = "INSERT OR IGNORE INTO short_links (slug, url, created_by, clicks, created_at) VALUES (?1, ?2, ?3, 0, ?4)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let inserted = stmt.execute(params![slug, url, created_by, Utc::now().timestamp()])?;
+
+        Ok(inserted > 0)
+    }
+
+    pub fn remove_short_link(&self, slug: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute("DELETE FROM short_links WHERE slug = ?1", params![slug])?;
+
+        Ok(removed > 0)
+    }
+
+    // Returns false if the name is already taken
+    pub fn add_custom_command(&self, name: &str, response: &str, created_by: &str) -> Result<bool> {
+        let query = "INSERT OR IGNORE INTO custom_commands (name, response, created_by, uses, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, 0, ?4, ?4)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let inserted = stmt.execute(params![name, response, created_by, Utc::now().timestamp()])?;
+
+        Ok(inserted > 0)
+    }
+
+    // Returns false if there's no such command
+    pub fn edit_custom_command(&self, name: &str, response: &str) -> Result<bool> {
+        let query = "UPDATE custom_commands SET response = ?2, updated_at = ?3 WHERE name = ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let updated = stmt.execute(params![name, response, Utc::now().timestamp()])?;
+
+        Ok(updated > 0)
+    }
+
+    pub fn remove_custom_command(&self, name: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute("DELETE FROM custom_commands WHERE name = ?1", params![name])?;
+
+        Ok(removed > 0)
+    }
+
+    pub fn get_custom_command(&self, name: &str) -> Result<Option<CustomCommand>> {
+        let query = "SELECT name, response, created_by, uses, created_at, updated_at FROM custom_commands WHERE name = ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        match stmt.query_row(params![name], Self::custom_command_from_row) {
+            Ok(command) => Ok(Some(command)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get_custom_commands(&self) -> Result<Vec<CustomCommand>> {
+        let query = "SELECT name, response, created_by, uses, created_at, updated_at FROM custom_commands ORDER BY name";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let commands = stmt.query_map([], Self::custom_command_from_row)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(commands)
+    }
+
+    // Counts a use and returns the new total, for $(count)
+    pub fn count_custom_command_use(&self, name: &str) -> Result<i64> {
+        let query = "UPDATE custom_commands SET uses = uses + 1 WHERE name = ?1 RETURNING uses";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![name], |row| row.get(0))
+    }
+
+    pub fn log_reward_price_change(&self, reward_title: &str, old_cost: u32, new_cost: u32, reason: &str) -> Result<()> {
+        let query = "INSERT INTO reward_price_changes (reward_title, old_cost, new_cost, reason, changed_at) VALUES (?1, ?2, ?3, ?4, ?5)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![reward_title, old_cost, new_cost, reason, Utc::now().timestamp()])?;
+
+        Ok(())
+    }
+
+    // Newest first
+    pub fn get_reward_price_changes(&self, limit: usize) -> Result<Vec<RewardPriceChange>> {
+        let query = "SELECT reward_title, old_cost, new_cost, reason, changed_at FROM reward_price_changes ORDER BY id DESC LIMIT ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let changes = stmt.query_map(params![limit as i64], |row| {
+            Ok(RewardPriceChange {
+                reward_title: row.get(0)?,
+                old_cost: row.get(1)?,
+                new_cost: row.get(2)?,
+                reason: row.get(3)?,
+                changed_at: DateTime::from_timestamp(row.get::<_, i64>(4)?, 0).unwrap_or_else(|| Utc::now()),
+            })
+        })?
+        .collect::<Result<Vec<_>>>()?;
+
+        Ok(changes)
+    }
+
+    fn custom_command_from_row(row: &rusqlite::Row) -> Result<CustomCommand> {
+        Ok(CustomCommand {
+            name: row.get(0)?,
+            response: row.get(1)?,
+            created_by: row.get(2)?,
+            uses: row.get(3)?,
+            created_at: DateTime::from_timestamp(row.get::<_, i64>(4)?, 0).unwrap_or_else(|| Utc::now()),
+            updated_at: DateTime::from_timestamp(row.get::<_, i64>(5)?, 0).unwrap_or_else(|| Utc::now()),
+        })
+    }
+
+    // Counts the click and returns where to send the visitor
+    pub fn resolve_short_link(&self, slug: &str) -> Result<Option<String>> {
+        let query = "UPDATE short_links SET clicks = clicks + 1 WHERE slug = ?1 RETURNING url";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        match stmt.query_row(params![slug], |row| row.get(0)) {
+            Ok(url) => Ok(Some(url)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Returns false if the user is already lurking. Opted out users can lurk, it just isn't timed.
+    pub fn start_lurk(&self, user_id: &str, user_login: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        if Self::opted_out(&conn, user_id)? {
+            return Ok(true);
+        }
+        let lurking: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM lurk_sessions WHERE user_id = ?1 AND ended_at IS NULL)",
+            params![user_id],
+            |row| row.get(0),
+        )?;
+        if lurking {
+            return Ok(false);
+        }
+        conn.execute(
+            "INSERT INTO lurk_sessions (user_id, user_login, started_at) VALUES (?1, ?2, ?3)",
+            params![user_id, user_login, Utc::now().timestamp()],
+        )?;
+
+        Ok(true)
+    }
+
+    // Seconds the lurk lasted, or None if they weren't lurking
+    pub fn end_lurk(&self, user_id: &str) -> Result<Option<i64>> {
+        let query = "UPDATE lurk_sessions SET ended_at = ?2 WHERE user_id = ?1 AND ended_at IS NULL RETURNING ended_at - started_at";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        match stmt.query_row(params![user_id, Utc::now().timestamp()], |row| row.get(0)) {
+            Ok(seconds) => Ok(Some(seconds)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Closes every open lurk, for when the stream ends
+    pub fn end_all_lurks(&self) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE lurk_sessions SET ended_at = ?1 WHERE ended_at IS NULL",
+            params![Utc::now().timestamp()],
+        )
+    }
+
+    // Lurk count and total seconds, counting a lurk that's still going up to now
+    pub fn get_lurk_stats(&self, user_login: &str) -> Result<(i64, i64)> {
+        let query = "SELECT COUNT(*), COALESCE(SUM(COALESCE(ended_at, ?2) - started_at), 0) FROM lurk_sessions WHERE user_login = ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![user_login.to_lowercase(), Utc::now().timestamp()], |row| Ok((row.get(0)?, row.get(1)?)))
+    }
+
+    // Lurkers and lurks since the given time, for the stream recap
+    pub fn count_lurks_since(&self, since: DateTime<Utc>) -> Result<(i64, i64)> {
+        let query = "SELECT COUNT(DISTINCT user_id), COUNT(*) FROM lurk_sessions WHERE started_at >= ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.query_row(params![since.timestamp()], |row| Ok((row.get(0)?, row.get(1)?)))
+    }
+
+    // When the current (or most recent) stream started
+    pub fn get_stream_session_start(&self) -> Result<Option<DateTime<Utc>>> {
+        let query = "SELECT started_at FROM stream_sessions ORDER BY id DESC LIMIT 1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        match stmt.query_row([], |row| row.get::<_, i64>(0)) {
+            Ok(started_at) => Ok(DateTime::from_timestamp(started_at, 0)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Keeps the raw notification for the event inspector; only the newest `keep` are kept
+    pub fn log_eventsub_event(&self, event_type: &str, message: &str, keep: usize) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO eventsub_log (event_type, message, received_at) VALUES (?1, ?2, ?3)",
+            params![event_type, message, Utc::now().timestamp()],
+        )?;
+        conn.execute(
+            "DELETE FROM eventsub_log WHERE id <= (SELECT MAX(id) FROM eventsub_log) - ?1",
+            params![keep as i64],
+        )?;
+        Ok(())
+    }
+
+    // Newest first, optionally only one event type
+    pub fn get_eventsub_events(&self, event_type: Option<&str>, limit: usize) -> Result<Vec<StoredEvent>> {
+        let query = "SELECT id, event_type, message, received_at FROM eventsub_log
+                     WHERE ?1 IS NULL OR event_type = ?1 ORDER BY id DESC LIMIT ?2";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let events = stmt.query_map(params![event_type, limit as i64], Self::stored_event)?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(events)
+    }
+
+    pub fn get_eventsub_event(&self, id: i64) -> Result<Option<StoredEvent>> {
+        let query = "SELECT id, event_type, message, received_at FROM eventsub_log WHERE id = ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        match stmt.query_row(params![id], Self::stored_event) {
+            Ok(event) => Ok(Some(event)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn stored_event(row: &rusqlite::Row) -> Result<StoredEvent> {
+        Ok(StoredEvent {
+            id: row.get(0)?,
+            event_type: row.get(1)?,
+            message: row.get(2)?,
+            received_at: DateTime::from_timestamp(row.get::<_, i64>(3)?, 0)
+                .unwrap_or_else(|| Utc::now()),
+        })
+    }
+
+    // outcome is "ok", "error" or "denied"
+    pub fn log_command_usage(&self, command: &str, user_id: &str, user_name: &str, outcome: &str) -> Result<()> {
+        let query = "INSERT INTO command_usage (command, user_id, user_name, outcome, timestamp) VALUES (?1, ?2, ?3, ?4, ?5)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![command, user_id, user_name, outcome, Utc::now().timestamp()])?;
+        Ok(())
+    }
+
+    pub fn get_command_usage(&self, since: DateTime<Utc>) -> Result<Vec<CommandUsageStats>> {
+        let query = "SELECT command, COUNT(*),
+                            SUM(CASE WHEN outcome = 'error' THEN 1 ELSE 0 END),
+                            SUM(CASE WHEN outcome = 'denied' THEN 1 ELSE 0 END),
+                            COUNT(DISTINCT user_id)
+                     FROM command_usage
+                     WHERE timestamp >= ?1
+                     GROUP BY command
+                     ORDER BY COUNT(*) DESC";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let stats = stmt.query_map(params![since.timestamp()], |row| {
+            Ok(CommandUsageStats {
+                command: row.get(0)?,
+                uses: row.get(1)?,
+                errors: row.get(2)?,
+                denied: row.get(3)?,
+                unique_users: row.get(4)?,
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(stats)
+    }
+
+    pub fn get_top_command_users(&self, since: DateTime<Utc>, limit: usize) -> Result<Vec<LeaderboardEntry>> {
+        let query = "SELECT user_id, MAX(user_name), COUNT(*) AS total
+                     FROM command_usage
+                     WHERE timestamp >= ?1 AND user_id NOT IN (SELECT user_id FROM opted_out_users)
+                     GROUP BY user_id
+                     ORDER BY total DESC
+                     LIMIT ?2";
+        self.query_leaderboard(query, since, limit)
+    }
+
+    // Only the hash of the token is stored; scopes are comma separated
+    pub fn create_api_token(&self, name: &str, token_hash: &str, scopes: &str) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO api_tokens (name, token_hash, scopes, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![name, token_hash, scopes, Utc::now().timestamp()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    // Scopes of the token if it exists and isn't revoked; marks it as used
+    pub fn use_api_token(&self, token_hash: &str) -> Result<Option<String>> {
+        let query = "UPDATE api_tokens SET last_used_at = ?2 WHERE token_hash = ?1 AND revoked = 0 RETURNING scopes";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        match stmt.query_row(params![token_hash, Utc::now().timestamp()], |row| row.get(0)) {
+            Ok(scopes) => Ok(Some(scopes)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get_api_tokens(&self) -> Result<Vec<ApiToken>> {
+        let query = "SELECT id, name, scopes, created_at, last_used_at, revoked FROM api_tokens ORDER BY revoked, created_at DESC";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let tokens = stmt.query_map([], |row| {
+            Ok(ApiToken {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                scopes: row.get::<_, String>(2)?.split(',').filter(|s| !s.is_empty()).map(str::to_string).collect(),
+                created_at: DateTime::from_timestamp(row.get::<_, i64>(3)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+                last_used_at: row.get::<_, Option<i64>>(4)?.and_then(|t| DateTime::from_timestamp(t, 0)),
+                revoked: row.get(5)?,
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(tokens)
+    }
+
+    // Returns false if there's no active token with that id
+    pub fn revoke_api_token(&self, id: i64) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let revoked = conn.execute("UPDATE api_tokens SET revoked = 1 WHERE id = ?1 AND revoked = 0", params![id])?;
+        Ok(revoked > 0)
+    }
+
+    pub fn add_tts_ban(&self, user_login: &str, banned_by: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO tts_bans (user_login, banned_by, added_at) VALUES (?1, ?2, ?3)",
+            params![user_login.to_lowercase(), banned_by, Utc::now().timestamp()],
+        )?;
+
+        Ok(())
+    }
+
+    // Returns false if the user wasn't banned
+    pub fn remove_tts_ban(&self, user_login: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let removed = conn.execute("DELETE FROM tts_bans WHERE user_login = ?1", params![user_login.to_lowercase()])?;
+
+        Ok(removed > 0)
+    }
+
+    pub fn is_tts_banned(&self, user_login: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached("SELECT EXISTS(SELECT 1 FROM tts_bans WHERE user_login = ?1)")?;
+        stmt.query_row(params![user_login.to_lowercase()], |row| row.get(0))
+    }
+
+    pub fn get_tts_bans(&self) -> Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached("SELECT user_login FROM tts_bans ORDER BY user_login")?;
+        let users = stmt.query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>>>()?;
+
+        Ok(users)
+    }
+
+    pub fn add_stream_suggestion(&self, kind: &str, value: &str, user_id: &str, user_name: &str) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO stream_suggestions (kind, value, user_id, user_name, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![kind, value, user_id, user_name, Utc::now().timestamp()],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    pub fn count_pending_stream_suggestions(&self, user_id: &str) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached("SELECT COUNT(*) FROM stream_suggestions WHERE user_id = ?1 AND status = 'pending'")?;
+        stmt.query_row(params![user_id], |row| row.get(0))
+    }
+
+    // Oldest first, like the queue they are
+    pub fn get_pending_stream_suggestions(&self) -> Result<Vec<StreamSuggestion>> {
+        let query = "SELECT id, kind, value, user_name, created_at FROM stream_suggestions WHERE status = 'pending' ORDER BY id";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let suggestions = stmt.query_map([], |row| {
+            Ok(StreamSuggestion {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                value: row.get(2)?,
+                user_name: row.get(3)?,
+                created_at: DateTime::from_timestamp(row.get::<_, i64>(4)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(suggestions)
+    }
+
+    // Moves a pending suggestion to "approved" or "rejected"; None if it wasn't pending
+    pub fn resolve_stream_suggestion(&self, id: i64, status: &str, decided_by: &str) -> Result<Option<StreamSuggestion>> {
+        let query = "UPDATE stream_suggestions SET status = ?2, decided_by = ?3, decided_at = ?4
+                     WHERE id = ?1 AND status = 'pending'
+                     RETURNING id, kind, value, user_name, created_at";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let result = stmt.query_row(params![id, status, decided_by, Utc::now().timestamp()], |row| {
+            Ok(StreamSuggestion {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                value: row.get(2)?,
+                user_name: row.get(3)?,
+                created_at: DateTime::from_timestamp(row.get::<_, i64>(4)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        });
+        match result {
+            Ok(suggestion) => Ok(Some(suggestion)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get_short_links(&self) -> Result<Vec<ShortLink>> {
+        let query = "SELECT slug, url, created_by, clicks, created_at FROM short_links ORDER BY clicks DESC, slug";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let links = stmt.query_map([], |row| {
+            Ok(ShortLink {
+                slug: row.get(0)?,
+                url: row.get(1)?,
+                created_by: row.get(2)?,
+                clicks: row.get(3)?,
+                created_at: DateTime::from_timestamp(row.get::<_, i64>(4)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(links)
+    }
+
+    fn query_leaderboard(&self, query: &str, since: DateTime<Utc>, limit: usize) -> Result<Vec<LeaderboardEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let entries = stmt.query_map(params![since.timestamp(), limit as i64], |row| {
+            Ok(LeaderboardEntry {
+                user_id: row.get(0)?,
+                user_name: row.get(1)?,
+                value: row.get(2)?,
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+
+    pub fn has_clip(&self, clip_id: &str) -> Result<bool> {
+        let query = "SELECT 1 FROM clips WHERE clip_id = ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.exists([clip_id])
+    }
+
+    // Returns false if the clip was already archived
+    pub fn add_clip(&self, clip: &ArchivedClip) -> Result<bool> {
+        let query = "INSERT OR IGNORE INTO clips (clip_id, url, title, broadcaster_name, creator_name, submitted_by, thumbnail_url, duration, stream_id, created_at, timestamp)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let inserted = stmt.execute(params![
+            clip.clip_id,
+            clip.url,
+            clip.title,
+            clip.broadcaster_name,
+            clip.creator_name,
+            clip.submitted_by,
+            clip.thumbnail_url,
+            clip.duration,
+            clip.stream_id,
+            clip.created_at,
+            clip.timestamp.timestamp(),
+        ])?;
+
+        Ok(inserted > 0)
+    }
+
+    pub fn get_clips(&self, limit: usize) -> Result<Vec<ArchivedClip>> {
+        let query = "SELECT clip_id, url, title, broadcaster_name, creator_name, submitted_by, thumbnail_url, duration, stream_id, created_at, timestamp
+                     FROM clips
+                     ORDER BY timestamp DESC
+                     LIMIT ?1";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let clips = stmt.query_map(params![limit as i64], |row| {
+            Ok(ArchivedClip {
+                clip_id: row.get(0)?,
+                url: row.get(1)?,
+                title: row.get(2)?,
+                broadcaster_name: row.get(3)?,
+                creator_name: row.get(4)?,
+                submitted_by: row.get(5)?,
+                thumbnail_url: row.get(6)?,
+                duration: row.get(7)?,
+                stream_id: row.get(8)?,
+                created_at: row.get(9)?,
+                timestamp: DateTime::from_timestamp(row.get::<_, i64>(10)?, 0)
+                    .unwrap_or_else(|| Utc::now()),
+            })
+        })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(clips)
+    }
+
     fn ensure_chatter_exists(&self, user_id: &str, conn: &Connection) -> Result<()> {
         let query = "INSERT OR IGNORE INTO chatters (user_id, username, is_streamer, chatter_type, sentiment, last_seen)
                      VALUES (?1, ?1, 0, 'new', 0.0, ?2)";
@@ -0,0 +1,224 @@
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde_json::Value;
+use tracing::warn;
+use crate::storage::{KvEntry, StorageClient};
+
+// Small state only: counters, flags, per-user preferences
+const MAX_NAME_LEN: usize = 64;
+const MAX_VALUE_BYTES: usize = 4096;
+
+type KvResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+// Namespaced key-value state for scripts, chain steps and plugins, so they can keep
+// what they need without a table of their own. Namespaces and keys are letters, digits, _ - and .
+pub trait KeyValueStore {
+    fn kv_get(&self, namespace: &str, key: &str) -> KvResult<Option<Value>>;
+    fn kv_set(&self, namespace: &str, key: &str, value: &Value) -> KvResult<()>;
+    // Whether there was anything to delete
+    fn kv_delete(&self, namespace: &str, key: &str) -> KvResult<bool>;
+    fn kv_list(&self, namespace: &str) -> KvResult<Vec<KvEntry>>;
+    // Each namespace with how many keys it holds
+    fn kv_namespaces(&self) -> KvResult<Vec<(String, i64)>>;
+    // Adds `by` to an integer value, counting a missing key as 0, and returns the new value
+    fn kv_increment(&self, namespace: &str, key: &str, by: i64) -> KvResult<i64>;
+
+    fn namespace(&self, name: &str) -> Namespace<'_, Self> where Self: Sized {
+        Namespace { store: self, name: name.to_string() }
+    }
+}
+
+// A KeyValueStore scoped to one namespace, what a plugin would be handed
+pub struct Namespace<'a, S: KeyValueStore> {
+    store: &'a S,
+    name: String,
+}
+
+impl<S: KeyValueStore> Namespace<'_, S> {
+    pub fn get(&self, key: &str) -> KvResult<Option<Value>> {
+        self.store.kv_get(&self.name, key)
+    }
+
+    pub fn set(&self, key: &str, value: &Value) -> KvResult<()> {
+        self.store.kv_set(&self.name, key, value)
+    }
+
+    pub fn delete(&self, key: &str) -> KvResult<bool> {
+        self.store.kv_delete(&self.name, key)
+    }
+
+    pub fn list(&self) -> KvResult<Vec<KvEntry>> {
+        self.store.kv_list(&self.name)
+    }
+
+    pub fn increment(&self, key: &str, by: i64) -> KvResult<i64> {
+        self.store.kv_increment(&self.name, key, by)
+    }
+}
+
+fn check_name(kind: &str, name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(format!("A {} has to be 1 to {} characters", kind, MAX_NAME_LEN));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')) {
+        return Err(format!("{} isn't a valid {}; use letters, digits, _ - and .", name, kind));
+    }
+    Ok(())
+}
+
+fn check_names(namespace: &str, key: &str) -> Result<(), String> {
+    check_name("namespace", namespace)?;
+    check_name("key", key)
+}
+
+fn to_entry(namespace: String, key: String, value: String, updated_at: i64) -> KvEntry {
+    KvEntry {
+        namespace,
+        key,
+        // Only ever written by kv_set, so anything unparsable was edited by hand; show it as a string
+        value: serde_json::from_str(&value).unwrap_or(Value::String(value)),
+        updated_at: DateTime::from_timestamp(updated_at, 0).unwrap_or_else(|| Utc::now()),
+    }
+}
+
+impl KeyValueStore for StorageClient {
+    fn kv_get(&self, namespace: &str, key: &str) -> KvResult<Option<Value>> {
+        check_names(namespace, key)?;
+        let query = "SELECT value FROM kv_store WHERE namespace = ?1 AND key = ?2";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let value: Option<String> = stmt.query_row(params![namespace, key], |row| row.get(0)).optional()?;
+        Ok(value.map(|value| serde_json::from_str(&value).unwrap_or(Value::String(value))))
+    }
+
+    fn kv_set(&self, namespace: &str, key: &str, value: &Value) -> KvResult<()> {
+        check_names(namespace, key)?;
+        let value = serde_json::to_string(value)?;
+        if value.len() > MAX_VALUE_BYTES {
+            return Err(format!("Values are limited to {} bytes", MAX_VALUE_BYTES).into());
+        }
+        let query = "INSERT INTO kv_store (namespace, key, value, updated_at) VALUES (?1, ?2, ?3, ?4)
+                     ON CONFLICT(namespace, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        stmt.execute(params![namespace, key, value, Utc::now().timestamp()])?;
+        Ok(())
+    }
+
+    fn kv_delete(&self, namespace: &str, key: &str) -> KvResult<bool> {
+        check_names(namespace, key)?;
+        let query = "DELETE FROM kv_store WHERE namespace = ?1 AND key = ?2";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        Ok(stmt.execute(params![namespace, key])? > 0)
+    }
+
+    fn kv_list(&self, namespace: &str) -> KvResult<Vec<KvEntry>> {
+        check_name("namespace", namespace)?;
+        let query = "SELECT namespace, key, value, updated_at FROM kv_store WHERE namespace = ?1 ORDER BY key";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let entries = stmt.query_map(params![namespace], |row| {
+            Ok(to_entry(row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(entries)
+    }
+
+    fn kv_namespaces(&self) -> KvResult<Vec<(String, i64)>> {
+        let query = "SELECT namespace, COUNT(*) FROM kv_store GROUP BY namespace ORDER BY namespace";
+
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare_cached(query)?;
+        let namespaces = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(namespaces)
+    }
+
+    fn kv_increment(&self, namespace: &str, key: &str, by: i64) -> KvResult<i64> {
+        check_names(namespace, key)?;
+
+        // Read and write under the one connection lock, so two increments can't both read the old value
+        let conn = self.conn.lock().unwrap();
+        let current: Option<String> = conn
+            .prepare_cached("SELECT value FROM kv_store WHERE namespace = ?1 AND key = ?2")?
+            .query_row(params![namespace, key], |row| row.get(0))
+            .optional()?;
+        let current = match current {
+            Some(value) => match serde_json::from_str::<Value>(&value).ok().and_then(|value| value.as_i64()) {
+                Some(number) => number,
+                None => return Err(format!("{}:{} isn't a whole number", namespace, key).into()),
+            },
+            None => 0,
+        };
+        let updated = current.checked_add(by).ok_or("The counter would overflow")?;
+
+        conn.prepare_cached(
+            "INSERT INTO kv_store (namespace, key, value, updated_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(namespace, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        )?
+            .execute(params![namespace, key, updated.to_string(), Utc::now().timestamp()])?;
+        Ok(updated)
+    }
+}
+
+// Fills in the key-value placeholders of a chat template (keyword trigger responses, chain
+// chat steps), so they can keep state between runs:
+//   {kv:ns:key}         the value, or nothing if it isn't set
+//   {kv+:ns:key}        adds 1 and shows the new value; {kv-:ns:key} takes 1 away
+//   {kv=:ns:key:value}  sets the value (a number if it parses as one) and shows nothing
+// Run it before anything viewers typed is substituted in, or they could write to the store.
+pub fn expand<S: KeyValueStore>(store: &S, template: &str) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{kv") {
+        output.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        match placeholder.find('}') {
+            Some(end) => {
+                output.push_str(&expand_one(store, &placeholder[1..end]).unwrap_or_else(|| placeholder[..=end].to_string()));
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                output.push_str(placeholder);
+                rest = "";
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+// None leaves the text alone, for braces that only look like a placeholder
+fn expand_one<S: KeyValueStore>(store: &S, placeholder: &str) -> Option<String> {
+    let mut parts = placeholder.splitn(4, ':');
+    let op = parts.next()?;
+    let namespace = parts.next()?;
+    let key = parts.next()?;
+    let value = parts.next();
+
+    let result = match (op, value) {
+        ("kv", None) => store.kv_get(namespace, key).map(|value| match value {
+            Some(Value::String(text)) => text,
+            Some(value) => value.to_string(),
+            None => String::new(),
+        }),
+        ("kv+", None) => store.kv_increment(namespace, key, 1).map(|count| count.to_string()),
+        ("kv-", None) => store.kv_increment(namespace, key, -1).map(|count| count.to_string()),
+        ("kv=", Some(value)) => {
+            let value = value.parse::<i64>().map(Value::from).unwrap_or_else(|_| Value::String(value.to_string()));
+            store.kv_set(namespace, key, &value).map(|_| String::new())
+        }
+        _ => return None,
+    };
+    Some(result.unwrap_or_else(|e| {
+        warn!("Key-value placeholder {{{}}} failed: {}", placeholder, e);
+        String::new()
+    }))
+}
@@ -1,5 +1,7 @@
 mod models;
 mod client;
+mod kv;
 
-pub use models::ChatterData;
-pub use client::StorageClient;
\ No newline at end of file
+pub use models::{ApiToken, ArchivedClip, ChatLogEntry, ChatterData, CommandUsageStats, CustomCommand, FollowerGrowth, KvEntry, LeaderboardEntry, ModerationEvent, ReachedMilestone, RewardPriceChange, SegmentTotal, ShortLink, StoredEvent, StreamHealthSample, StreamSegment, StreamSuggestion};
+pub use client::StorageClient;
+pub use kv::{expand as expand_kv_placeholders, KeyValueStore, Namespace};
\ No newline at end of file
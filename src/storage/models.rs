@@ -1,4 +1,6 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use crate::twitch::roles::UserRole;
 
 #[derive(Debug, Clone, Default)]
@@ -34,4 +36,180 @@ impl ChatterData {
             role: UserRole::Viewer,
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ModerationEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub user_id: String,
+    pub user_name: String,
+    pub moderator_id: String,
+    pub moderator_name: String,
+    pub reason: Option<String>,
+    pub ends_at: Option<String>,
+    pub appeal_status: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ArchivedClip {
+    pub clip_id: String,
+    pub url: String,
+    pub title: String,
+    pub broadcaster_name: String,
+    pub creator_name: String,
+    pub submitted_by: String,
+    pub thumbnail_url: Option<String>,
+    pub duration: f64,
+    pub stream_id: Option<String>,
+    pub created_at: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatLogEntry {
+    pub message_id: String,
+    pub user_id: String,
+    pub user_name: String,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LeaderboardEntry {
+    pub user_id: String,
+    pub user_name: String,
+    pub value: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReachedMilestone {
+    pub kind: String,
+    pub threshold: u64,
+    pub reached_at: DateTime<Utc>,
+}
+
+// Follower counts around one stream session; `gained` can be negative
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FollowerGrowth {
+    pub stream_session_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub followers_at_start: i64,
+    pub followers_at_end: i64,
+    pub gained: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StreamHealthSample {
+    pub instance_name: String,
+    pub dropped_frames_percent: f64,
+    pub encoder_lag_percent: f64,
+    pub render_lag_percent: f64,
+    pub bitrate_kbps: f64,
+    pub cpu_usage: f64,
+    pub fps: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+// A response command added with !addcom or from the dashboard
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CustomCommand {
+    // With its !, e.g. "!hello"
+    pub name: String,
+    pub response: String,
+    pub created_by: String,
+    pub uses: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// A stretch of a stream spent in one game or VRChat world
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StreamSegment {
+    // "game" or "world"
+    pub kind: String,
+    pub name: String,
+    pub started_at: DateTime<Utc>,
+    // None while it's still going
+    pub ended_at: Option<DateTime<Utc>>,
+    pub seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SegmentTotal {
+    pub kind: String,
+    pub name: String,
+    pub seconds: i64,
+    // How many streams it showed up in
+    pub streams: i64,
+}
+
+// One entry in the audit trail of dynamically priced rewards
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RewardPriceChange {
+    pub reward_title: String,
+    pub old_cost: u32,
+    pub new_cost: u32,
+    // "redeemed" or "decay"
+    pub reason: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ShortLink {
+    pub slug: String,
+    pub url: String,
+    pub created_by: String,
+    pub clicks: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+// One value in the key-value store; values are any JSON
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KvEntry {
+    pub namespace: String,
+    pub key: String,
+    pub value: serde_json::Value,
+    pub updated_at: DateTime<Utc>,
+}
+
+// A raw EventSub notification as it came off the websocket
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StoredEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub message: String,
+    pub received_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CommandUsageStats {
+    pub command: String,
+    pub uses: i64,
+    pub errors: i64,
+    pub denied: i64,
+    pub unique_users: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ApiToken {
+    pub id: i64,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked: bool,
+}
+
+// A viewer's title or tags suggestion waiting in the dashboard queue
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StreamSuggestion {
+    pub id: i64,
+    // "title" or "tags"; tags are comma separated
+    pub kind: String,
+    pub value: String,
+    pub user_name: String,
+    pub created_at: DateTime<Utc>,
+}
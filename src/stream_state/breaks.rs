@@ -0,0 +1,94 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+// Going offline for less than this and coming back counts as a blip in the same stream
+const OFFLINE_BLIP_GRACE_SECS: i64 = 10 * 60;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UptimeSummary {
+    pub started_at: DateTime<Utc>,
+    pub raw_seconds: i64,
+    pub active_seconds: i64,
+    pub breaks: u32,
+    pub on_break: bool,
+    pub break_reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct CurrentBreak {
+    started_at: DateTime<Utc>,
+    reason: Option<String>,
+}
+
+// Wall-clock bookkeeping for the current stream: when it started and how much
+// of it was spent on breaks, whether planned (!brb) or the stream dropping.
+#[derive(Debug, Default)]
+pub(crate) struct SessionTimes {
+    started_at: Option<DateTime<Utc>>,
+    break_seconds: i64,
+    breaks: u32,
+    current_break: Option<CurrentBreak>,
+    offline_since: Option<DateTime<Utc>>,
+}
+
+impl SessionTimes {
+    pub(crate) fn went_live(&mut self, now: DateTime<Utc>) {
+        match self.offline_since.take() {
+            Some(offline_since) if self.started_at.is_some() && now - offline_since < Duration::seconds(OFFLINE_BLIP_GRACE_SECS) => {
+                self.break_seconds += (now - offline_since).num_seconds();
+                self.breaks += 1;
+            }
+            _ => {
+                *self = Self {
+                    started_at: Some(now),
+                    ..Self::default()
+                };
+            }
+        }
+    }
+
+    pub(crate) fn went_offline(&mut self, now: DateTime<Utc>) {
+        self.end_break(now);
+        self.offline_since = Some(now);
+    }
+
+    // Returns false if the stream isn't running or a break is already in progress
+    pub(crate) fn start_break(&mut self, now: DateTime<Utc>, reason: Option<String>) -> bool {
+        if self.started_at.is_none() || self.offline_since.is_some() || self.current_break.is_some() {
+            return false;
+        }
+        self.current_break = Some(CurrentBreak { started_at: now, reason });
+        true
+    }
+
+    pub(crate) fn end_break(&mut self, now: DateTime<Utc>) -> Option<Duration> {
+        let current_break = self.current_break.take()?;
+        let length = now - current_break.started_at;
+        self.break_seconds += length.num_seconds();
+        self.breaks += 1;
+        Some(length)
+    }
+
+    pub(crate) fn summary(&self, now: DateTime<Utc>) -> Option<UptimeSummary> {
+        let started_at = self.started_at?;
+        if let Some(offline_since) = self.offline_since {
+            if now - offline_since >= Duration::seconds(OFFLINE_BLIP_GRACE_SECS) {
+                return None;
+            }
+        }
+
+        let ongoing_break = self.current_break.as_ref().map(|b| b.started_at)
+            .or(self.offline_since)
+            .map_or(0, |since| (now - since).num_seconds());
+        let raw_seconds = (now - started_at).num_seconds();
+
+        Some(UptimeSummary {
+            started_at,
+            raw_seconds,
+            active_seconds: (raw_seconds - self.break_seconds - ongoing_break).max(0),
+            breaks: self.breaks,
+            on_break: self.current_break.is_some() || self.offline_since.is_some(),
+            break_reason: self.current_break.as_ref().and_then(|b| b.reason.clone()),
+        })
+    }
+}
@@ -1,7 +1,9 @@
 use tokio::sync::{broadcast, RwLock};
 use std::sync::Arc;
-use log::{info, warn};
+use chrono::{Duration, Utc};
+use tracing::{info, warn};
 use serde::{Serialize, Deserialize};
+use crate::stream_state::breaks::{SessionTimes, UptimeSummary};
 use crate::stream_state::errors::StateTransitionError;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -15,6 +17,7 @@ pub enum StreamState {
 pub struct StreamStateMachine {
     current_state: RwLock<StreamState>,
     transition_channel: broadcast::Sender<StreamState>,
+    session: RwLock<SessionTimes>,
 }
 
 impl StreamStateMachine {
@@ -23,6 +26,7 @@ impl StreamStateMachine {
         Arc::new(Self {
             current_state: RwLock::new(StreamState::Offline),
             transition_channel,
+            session: RwLock::new(SessionTimes::default()),
         })
     }
 
@@ -44,6 +48,12 @@ impl StreamStateMachine {
             *state = new_state.clone();
             drop(state); // Release the write lock before sending the broadcast
 
+            match (&current_state, &new_state) {
+                (StreamState::GoingLive, StreamState::Live(_)) => self.session.write().await.went_live(Utc::now()),
+                (_, StreamState::Offline) => self.session.write().await.went_offline(Utc::now()),
+                _ => {}
+            }
+
             if let Err(e) = self.transition_channel.send(new_state.clone()) {
                 warn!("Failed to broadcast state transition: {:?}", e);
                 return Err(StateTransitionError::BroadcastError);
@@ -146,4 +156,16 @@ impl StreamStateMachine {
             },
         }
     }
-}
\ No newline at end of file
+
+    pub async fn start_break(&self, reason: Option<String>) -> bool {
+        self.session.write().await.start_break(Utc::now(), reason)
+    }
+
+    pub async fn end_break(&self) -> Option<Duration> {
+        self.session.write().await.end_break(Utc::now())
+    }
+
+    pub async fn uptime_summary(&self) -> Option<UptimeSummary> {
+        self.session.read().await.summary(Utc::now())
+    }
+}
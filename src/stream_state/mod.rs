@@ -1,5 +1,7 @@
 mod errors;
 mod manager;
+mod breaks;
 
 pub use errors::StateTransitionError;
-pub use manager::{StreamState, StreamStateMachine};
\ No newline at end of file
+pub use manager::{StreamState, StreamStateMachine};
+pub use breaks::UptimeSummary;
\ No newline at end of file
@@ -0,0 +1,127 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+// Registry of long-running background tasks, so graceful shutdown can stop
+// every loop instead of leaving them running after the clients shut down.
+// Each bot instance owns one (BotClients::tasks, shared with TwitchManager), so a
+// second instance in the same process starts with a registry that isn't cancelled.
+pub struct TaskRegistry {
+    token: CancellationToken,
+    tasks: Mutex<Vec<(String, JoinHandle<()>)>>,
+}
+
+impl Default for TaskRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self {
+            token: CancellationToken::new(),
+            tasks: Mutex::new(Vec::new()),
+        }
+    }
+
+    // The returned handle stops just this task, for loops that can be turned off while running
+    pub fn spawn<F>(&self, name: &str, future: F) -> AbortHandle
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let token = self.token.clone();
+        let task_name = name.to_string();
+        let handle = tokio::spawn(async move {
+            tokio::select! {
+                _ = token.cancelled() => debug!("Background task '{}' cancelled", task_name),
+                _ = future => debug!("Background task '{}' finished", task_name),
+            }
+        });
+
+        let abort_handle = handle.abort_handle();
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.retain(|(_, handle)| !handle.is_finished());
+        tasks.push((name.to_string(), handle));
+        abort_handle
+    }
+
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    pub fn running(&self) -> usize {
+        self.tasks.lock().unwrap().iter().filter(|(_, handle)| !handle.is_finished()).count()
+    }
+
+    // Cancels every registered task and waits for them, aborting any that don't stop in time
+    pub async fn shutdown(&self, timeout: Duration) {
+        self.token.cancel();
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        info!("Stopping {} background tasks...", tasks.len());
+
+        for (name, handle) in tasks {
+            let abort_handle = handle.abort_handle();
+            match tokio::time::timeout(timeout, handle).await {
+                Ok(_) => debug!("Background task '{}' stopped", name),
+                Err(_) => {
+                    warn!("Background task '{}' did not stop in time, aborting", name);
+                    abort_handle.abort();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn shutdown_stops_every_task() {
+        let registry = TaskRegistry::new();
+        for name in ["first", "second", "third"] {
+            registry.spawn(name, async {
+                loop {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            });
+        }
+        assert_eq!(registry.running(), 3);
+
+        registry.shutdown(Duration::from_secs(1)).await;
+        assert_eq!(registry.running(), 0);
+        assert!(registry.token().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn registries_are_independent() {
+        let first = TaskRegistry::new();
+        first.spawn("loop", std::future::pending());
+        first.shutdown(Duration::from_secs(1)).await;
+
+        // A registry for the next instance isn't affected by the last one shutting down
+        let second = TaskRegistry::new();
+        second.spawn("loop", std::future::pending());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!second.token().is_cancelled());
+        assert_eq!(second.running(), 1);
+        second.shutdown(Duration::from_secs(1)).await;
+    }
+
+    #[tokio::test]
+    async fn aborted_tasks_stop_counting() {
+        let registry = TaskRegistry::new();
+        let handle = registry.spawn("loop", std::future::pending());
+        registry.spawn("other", std::future::pending());
+        handle.abort();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(registry.running(), 1);
+
+        registry.shutdown(Duration::from_secs(1)).await;
+        assert_eq!(registry.running(), 0);
+    }
+}
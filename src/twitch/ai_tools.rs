@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::{json, Value};
+use crate::ai::{ToolDefinition, ToolExecutor};
+use crate::twitch::irc::commands::followers::format_duration as format_follow_age;
+use crate::twitch::stream_breaks::format_duration;
+use crate::twitch::utils::get_stream_uptime;
+use crate::twitch::TwitchManager;
+
+// Read-only stream facts the AI redeems may look up instead of guessing
+pub struct StreamTools {
+    twitch_manager: Arc<TwitchManager>,
+}
+
+impl StreamTools {
+    pub fn new(twitch_manager: Arc<TwitchManager>) -> Self {
+        Self { twitch_manager }
+    }
+
+    async fn uptime(&self) -> Result<String, String> {
+        let channel = self.twitch_manager.config.twitch_channel_to_join.clone()
+            .ok_or("No channel configured")?;
+        let uptime = get_stream_uptime(&channel, self.twitch_manager.get_api_client())
+            .await
+            .map_err(|e| format!("Couldn't get the uptime: {}", e))?;
+        Ok(match uptime {
+            Some(duration) => format!("The stream has been live for {}", format_duration(duration.num_seconds())),
+            None => "The stream is offline".to_string(),
+        })
+    }
+
+    async fn follow_age(&self, username: &str) -> Result<String, String> {
+        let api_client = self.twitch_manager.get_api_client();
+        let broadcaster_id = api_client.get_broadcaster_id().await.map_err(|e| e.to_string())?;
+        let user_info = api_client.get_user_info(username).await.map_err(|e| e.to_string())?;
+        let user_id = user_info["data"][0]["id"].as_str()
+            .ok_or_else(|| format!("There is no Twitch user called {}", username))?;
+        let (followers, _) = api_client.get_follower_info(&broadcaster_id, Some(user_id))
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(match followers.first() {
+            Some(follower) => format!(
+                "{} has been following for {} (since {})",
+                username,
+                format_follow_age(follower.followed_at, Utc::now()),
+                follower.followed_at.format("%Y-%m-%d")
+            ),
+            None => format!("{} doesn't follow the channel", username),
+        })
+    }
+}
+
+#[async_trait]
+impl ToolExecutor for StreamTools {
+    fn definitions(&self) -> Vec<ToolDefinition> {
+        vec![
+            ToolDefinition {
+                name: "get_current_world".to_string(),
+                description: "The VRChat world the streamer is in right now".to_string(),
+                input_schema: json!({"type": "object", "properties": {}}),
+            },
+            ToolDefinition {
+                name: "get_current_game".to_string(),
+                description: "The game or category the stream is set to".to_string(),
+                input_schema: json!({"type": "object", "properties": {}}),
+            },
+            ToolDefinition {
+                name: "get_uptime".to_string(),
+                description: "How long the stream has been live, or whether it is offline".to_string(),
+                input_schema: json!({"type": "object", "properties": {}}),
+            },
+            ToolDefinition {
+                name: "get_follow_age".to_string(),
+                description: "How long a Twitch user has followed the channel".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "username": {"type": "string", "description": "Twitch login name, without the @"}
+                    },
+                    "required": ["username"]
+                }),
+            },
+        ]
+    }
+
+    async fn call(&self, name: &str, input: &Value) -> Result<String, String> {
+        match name {
+            "get_current_world" => Ok(self.twitch_manager.prompts.current_world()
+                .unwrap_or_else(|| "The streamer isn't in a VRChat world right now".to_string())),
+            "get_current_game" => Ok(self.twitch_manager.get_current_game().await
+                .unwrap_or_else(|| "The stream has no game set".to_string())),
+            "get_uptime" => self.uptime().await,
+            "get_follow_age" => {
+                let username = input["username"].as_str().unwrap_or_default().trim_start_matches('@').trim();
+                if username.is_empty() {
+                    return Err("A username is required".to_string());
+                }
+                self.follow_age(username).await
+            }
+            _ => Err(format!("Unknown tool {}", name)),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+use serde_json::json;
+use serenity::model::id::ChannelId;
+use tokio::time::{sleep, Duration};
+use crate::config::{AlertAction, AlertEventType, AlertRoute};
+use crate::obs::OBSManager;
+use crate::twitch::models::OBSSourceAction;
+use crate::twitch::tts::{self, TtsRequest};
+use crate::twitch::TwitchManager;
+use crate::web_ui::websocket::WebSocketMessage;
+
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    pub event_type: AlertEventType,
+    pub user_name: String,
+    // Raid viewers, bits, gifted subs, resub months or donation amount depending on the event
+    pub amount: u64,
+    pub tier: Option<String>,
+    pub message: Option<String>,
+}
+
+impl AlertEvent {
+    pub fn new(event_type: AlertEventType, user_name: &str, amount: u64) -> Self {
+        Self {
+            event_type,
+            user_name: user_name.to_string(),
+            amount,
+            tier: None,
+            message: None,
+        }
+    }
+
+    pub fn with_tier(mut self, tier: &str) -> Self {
+        self.tier = Some(tier.to_string());
+        self
+    }
+
+    pub fn with_message(mut self, message: &str) -> Self {
+        if !message.is_empty() {
+            self.message = Some(message.to_string());
+        }
+        self
+    }
+
+    pub fn render(&self, template: &str) -> String {
+        let tier = match self.tier.as_deref() {
+            Some("1000") => "Tier 1",
+            Some("2000") => "Tier 2",
+            Some("3000") => "Tier 3",
+            Some(other) => other,
+            None => "",
+        };
+        template
+            .replace("{user}", &self.user_name)
+            .replace("{amount}", &self.amount.to_string())
+            .replace("{tier}", tier)
+            .replace("{message}", self.message.as_deref().unwrap_or(""))
+    }
+}
+
+fn route_matches(route: &AlertRoute, event: &AlertEvent) -> bool {
+    if route.event != event.event_type {
+        return false;
+    }
+    if let Some(tier) = &route.tier {
+        if event.tier.as_ref() != Some(tier) {
+            return false;
+        }
+    }
+    route.min_amount.map_or(true, |min| event.amount >= min)
+        && route.max_amount.map_or(true, |max| event.amount <= max)
+}
+
+pub fn matching_route<'a>(routes: &'a [AlertRoute], event: &AlertEvent) -> Option<&'a AlertRoute> {
+    routes.iter().find(|route| route_matches(route, event))
+}
+
+// Runs the first configured route matching the event. Returns false when no
+// route matches, so the caller falls back to its built-in alert.
+pub async fn dispatch(twitch_manager: &Arc<TwitchManager>, channel: &str, event: &AlertEvent) -> bool {
+    let route = match matching_route(&twitch_manager.config.alert_routing.routes, event) {
+        Some(route) => route,
+        None => return false,
+    };
+
+    debug!("Routing {:?} alert for {} through {} actions", event.event_type, event.user_name, route.actions.len());
+    for action in &route.actions {
+        if let Err(e) = run_action(twitch_manager, channel, event, action).await {
+            error!("Alert action {:?} failed for {:?} event: {}", action, event.event_type, e);
+        }
+    }
+
+    info!("Processed routed {:?} alert for {}", event.event_type, event.user_name);
+    true
+}
+
+async fn run_action(
+    twitch_manager: &Arc<TwitchManager>,
+    channel: &str,
+    event: &AlertEvent,
+    action: &AlertAction,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match action {
+        AlertAction::Chat { template } => {
+            twitch_manager.send_message_as_bot(channel, &event.render(template)).await?;
+        }
+        AlertAction::Discord { channel_id, template } => {
+            let discord_client = twitch_manager.discord_client.as_ref().ok_or("Discord is not configured")?;
+            let channel_id = ChannelId::new(channel_id.parse::<u64>()?);
+            let http = discord_client.get_http().await;
+            channel_id.say(&http, event.render(template)).await?;
+        }
+        AlertAction::Osc(osc_config) => {
+            let osc_manager = twitch_manager.get_osc_manager();
+            osc_manager.send_osc_message(&osc_config.osc_endpoint, &osc_config.osc_type, &osc_config.osc_value).await?;
+            if let Some(frames) = osc_config.execution_duration {
+                let osc_config = osc_config.clone();
+                tokio::spawn(async move {
+                    sleep(Duration::from_secs_f32(frames as f32 / 60.0)).await;
+                    if let Err(e) = osc_manager.send_osc_message(&osc_config.osc_endpoint, &osc_config.osc_type, &osc_config.default_value).await {
+                        error!("Failed to reset OSC value for alert: {}", e);
+                    }
+                });
+            }
+        }
+        AlertAction::ObsSource(obs_action) => {
+            let obs_manager = twitch_manager.get_obs_manager().ok_or("OBS is not available")?;
+            set_obs_source(&obs_manager, obs_action, true).await?;
+            let obs_action = obs_action.clone();
+            tokio::spawn(async move {
+                sleep(Duration::from_secs(obs_action.duration_secs)).await;
+                if let Err(e) = set_obs_source(&obs_manager, &obs_action, false).await {
+                    error!("Failed to revert OBS source {} after alert: {:?}", obs_action.source_name, e);
+                }
+            });
+        }
+        AlertAction::Tts { template } => {
+            let request = TtsRequest {
+                user_name: event.user_name.clone(),
+                text: event.render(template),
+                voice: None,
+                source: json!(event.event_type).as_str().unwrap_or("alert").to_string(),
+                redemption: None,
+                extra: json!({
+                    "event": event.event_type,
+                    "amount": event.amount,
+                    "tier": event.tier,
+                }),
+            };
+            tts::submit(twitch_manager, request).await?;
+        }
+        AlertAction::Overlay { template } => {
+            send_overlay_message(twitch_manager, "overlay", event, event.render(template)).await;
+        }
+    }
+
+    Ok(())
+}
+
+async fn set_obs_source(
+    obs_manager: &OBSManager,
+    action: &OBSSourceAction,
+    enabled: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match &action.filter_name {
+        Some(filter) => obs_manager.toggle_filter(&action.instance_name, &action.source_name, filter, enabled).await,
+        None => obs_manager.toggle_source(&action.instance_name, &action.scene_name, &action.source_name, enabled).await,
+    }
+}
+
+async fn send_overlay_message(twitch_manager: &Arc<TwitchManager>, action: &str, event: &AlertEvent, text: String) {
+    let message = WebSocketMessage {
+        module: "alerts".to_string(),
+        action: action.to_string(),
+        data: json!({
+            "event": event.event_type,
+            "user": event.user_name,
+            "amount": event.amount,
+            "tier": event.tier,
+            "text": twitch_manager.irc_manager.get_output_filter().filter(&text),
+        }),
+    };
+
+    let dashboard_state = twitch_manager.irc_manager.get_dashboard_state();
+    let state = dashboard_state.read().await;
+    match state.broadcast_message(message).await {
+        Ok(0) => warn!("No overlay clients connected for {} alert", action),
+        Ok(_) => {}
+        Err(e) => error!("Failed to send {} alert to overlay: {}", action, e),
+    }
+}
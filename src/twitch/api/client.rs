@@ -1,14 +1,13 @@
 use crate::config::Config;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::{Mutex};
 use chrono::{DateTime, Utc, Duration};
-use warp::Filter;
-use tokio::time::{timeout, sleep, Duration as TokioDuration};
-use std::convert::Infallible;
+use tokio::time::{timeout, Duration as TokioDuration};
 use std::sync::atomic::{AtomicBool, Ordering};
-use log::{debug, error, info, warn};
+use tracing::{debug, error, info, warn};
 use serde_json::Value;
 use thiserror::Error;
 use std::error::Error as StdError;
@@ -17,6 +16,18 @@ use crate::twitch::api::requests::channel::Clip;
 use crate::twitch::api::requests::followers;
 use crate::twitch::api::requests::followers::FollowerInfo;
 use crate::twitch::models::channel_points;
+use crate::twitch::api::oauth::PendingAuthorizations;
+
+// Everything the broadcaster token is asked for; `mewbot doctor` checks the token still has all of them
+pub const OAUTH_SCOPES: &[&str] = &[
+    "chat:read", "chat:edit", "channel:read:subscriptions", "moderator:read:followers",
+    "moderator:manage:shoutouts", "channel:manage:redemptions", "channel:manage:ads", "channel:read:ads",
+    "channel:manage:vips", "moderation:read", "moderator:manage:announcements", "bits:read",
+    "channel:moderate", "channel:manage:broadcast", "user:edit:broadcast", "channel:edit:commercial",
+    "clips:edit", "moderator:manage:chat_messages", "moderator:manage:banned_users", "channel:manage:raids",
+    "channel:manage:predictions", "channel:manage:moderators", "channel:read:charity",
+    "moderator:manage:chat_settings",
+];
 
 #[derive(Error, Debug)]
 pub enum TwitchAPIError {
@@ -55,6 +66,7 @@ pub struct TwitchAPIClient {
     token: Arc<Mutex<Option<TwitchToken>>>,
     pub(crate) client: Client,
     initialized: Arc<AtomicBool>,
+    authorizations: Arc<PendingAuthorizations>,
 }
 
 impl Default for TwitchAPIClient {
@@ -64,6 +76,7 @@ impl Default for TwitchAPIClient {
             token: Arc::new(Mutex::new(None)),
             client: Client::new(),
             initialized: Arc::new(AtomicBool::new(false)),
+            authorizations: Arc::new(PendingAuthorizations::new()),
         }
     }
 }
@@ -76,6 +89,7 @@ impl TwitchAPIClient {
             token: Arc::new(Mutex::new(None)),
             client,
             initialized: Arc::new(AtomicBool::new(false)),
+            authorizations: Arc::new(PendingAuthorizations::new()),
         };
 
         // Initialize the token if it exists in the config
@@ -84,6 +98,7 @@ impl TwitchAPIClient {
         Ok(api_client)
     }
 
+    #[tracing::instrument(name = "twitch_api", level = "debug", skip_all, fields(method = %method, endpoint = endpoint))]
     async fn authenticated_request<T: serde::de::DeserializeOwned>(
         &self,
         method: reqwest::Method,
@@ -154,6 +169,17 @@ impl TwitchAPIClient {
         ).await
     }
 
+    // One request for up to 100 channels; only the live ones come back
+    pub async fn get_streams_by_logins(&self, logins: &[String]) -> Result<Value, TwitchAPIError> {
+        let query: Vec<(&str, &str)> = logins.iter().map(|login| ("user_login", login.as_str())).collect();
+        self.authenticated_request(
+            reqwest::Method::GET,
+            "streams",
+            Some(&query),
+            None,
+        ).await
+    }
+
     pub async fn get_channel_information(&self, broadcaster_id: &str) -> Result<Value, TwitchAPIError> {
         self.authenticated_request(
             reqwest::Method::GET,
@@ -186,6 +212,127 @@ impl TwitchAPIClient {
         Ok(clips)
     }
 
+    pub async fn get_clip(&self, clip_id: &str) -> Result<Value, TwitchAPIError> {
+        self.authenticated_request(
+            reqwest::Method::GET,
+            "clips",
+            Some(&[("id", clip_id)]),
+            None,
+        ).await
+    }
+
+    pub async fn create_clip(&self, broadcaster_id: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let response: Value = self.authenticated_request(
+            reqwest::Method::POST,
+            "clips",
+            Some(&[("broadcaster_id", broadcaster_id)]),
+            None,
+        ).await?;
+
+        let clip_id = response["data"][0]["id"].as_str()
+            .ok_or("No clip id in create clip response")?
+            .to_string();
+
+        Ok(clip_id)
+    }
+
+    pub async fn delete_chat_message(&self, broadcaster_id: &str, message_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let token = self.get_token().await?;
+        let client_id = self.get_client_id().await?;
+
+        // Messages are deleted as the broadcaster, so it doubles as the moderator
+        let response = self.client
+            .delete("https://api.twitch.tv/helix/moderation/chat")
+            .header("Client-ID", client_id)
+            .header("Authorization", format!("Bearer {}", token))
+            .query(&[
+                ("broadcaster_id", broadcaster_id),
+                ("moderator_id", broadcaster_id),
+                ("message_id", message_id),
+            ])
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Failed to delete message {}: {} - {}", message_id, status, error_text).into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn add_channel_vip(&self, broadcaster_id: &str, user_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.change_channel_role(reqwest::Method::POST, "channels/vips", broadcaster_id, user_id).await
+    }
+
+    pub async fn remove_channel_vip(&self, broadcaster_id: &str, user_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.change_channel_role(reqwest::Method::DELETE, "channels/vips", broadcaster_id, user_id).await
+    }
+
+    pub async fn add_channel_moderator(&self, broadcaster_id: &str, user_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.change_channel_role(reqwest::Method::POST, "moderation/moderators", broadcaster_id, user_id).await
+    }
+
+    pub async fn remove_channel_moderator(&self, broadcaster_id: &str, user_id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.change_channel_role(reqwest::Method::DELETE, "moderation/moderators", broadcaster_id, user_id).await
+    }
+
+    // VIP and moderator changes answer 204 with no body, so this can't go through authenticated_request
+    async fn change_channel_role(
+        &self,
+        method: reqwest::Method,
+        endpoint: &str,
+        broadcaster_id: &str,
+        user_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let token = self.get_token().await?;
+        let client_id = self.get_client_id().await?;
+
+        let response = self.client
+            .request(method.clone(), format!("https://api.twitch.tv/helix/{}", endpoint))
+            .header("Client-ID", client_id)
+            .header("Authorization", format!("Bearer {}", token))
+            .query(&[("broadcaster_id", broadcaster_id), ("user_id", user_id)])
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("{} {} for user {} failed: {} - {}", method, endpoint, user_id, status, error_text).into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn timeout_user(
+        &self,
+        broadcaster_id: &str,
+        user_id: &str,
+        duration_secs: u32,
+        reason: &str,
+    ) -> Result<(), TwitchAPIError> {
+        info!("Timing out user {} for {}s", user_id, duration_secs);
+        self.authenticated_request::<serde_json::Value>(
+            reqwest::Method::POST,
+            "moderation/bans",
+            Some(&[
+                ("broadcaster_id", broadcaster_id),
+                ("moderator_id", broadcaster_id),
+            ]),
+            Some(serde_json::json!({
+                "data": {
+                    "user_id": user_id,
+                    "duration": duration_secs,
+                    "reason": reason,
+                }
+            })),
+        ).await?;
+
+        Ok(())
+    }
+
     pub async fn get_recent_vods(&self, user_id: &str, limit: u32) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
         let response: serde_json::Value = self.authenticated_request(
             reqwest::Method::GET,
@@ -229,6 +376,58 @@ impl TwitchAPIClient {
         Ok(())
     }
 
+    // Followers-only state as (enabled, minutes someone has to have followed for)
+    pub async fn get_follower_mode(&self, broadcaster_id: &str) -> Result<(bool, u64), TwitchAPIError> {
+        let response: serde_json::Value = self.authenticated_request(
+            reqwest::Method::GET,
+            "chat/settings",
+            Some(&[("broadcaster_id", broadcaster_id)]),
+            None,
+        ).await?;
+
+        let settings = &response["data"][0];
+        Ok((
+            settings["follower_mode"].as_bool().unwrap_or(false),
+            settings["follower_mode_duration"].as_u64().unwrap_or(0),
+        ))
+    }
+
+    pub async fn set_follower_mode(&self, broadcaster_id: &str, enabled: bool, duration_minutes: u64) -> Result<(), TwitchAPIError> {
+        info!("Setting followers-only chat to {} ({} minutes)", enabled, duration_minutes);
+        let body = if enabled {
+            serde_json::json!({ "follower_mode": true, "follower_mode_duration": duration_minutes })
+        } else {
+            serde_json::json!({ "follower_mode": false })
+        };
+        self.authenticated_request::<serde_json::Value>(
+            reqwest::Method::PATCH,
+            "chat/settings",
+            Some(&[
+                ("broadcaster_id", broadcaster_id),
+                ("moderator_id", broadcaster_id),
+            ]),
+            Some(body),
+        ).await?;
+
+        Ok(())
+    }
+
+    // Twitch runs its usual countdown before the raid goes through
+    pub async fn start_raid(&self, from_broadcaster_id: &str, to_broadcaster_id: &str) -> Result<(), TwitchAPIError> {
+        info!("Starting raid from {} to {}", from_broadcaster_id, to_broadcaster_id);
+        self.authenticated_request::<serde_json::Value>(
+            reqwest::Method::POST,
+            "raids",
+            Some(&[
+                ("from_broadcaster_id", from_broadcaster_id),
+                ("to_broadcaster_id", to_broadcaster_id),
+            ]),
+            None,
+        ).await?;
+
+        Ok(())
+    }
+
 
     pub async fn authenticate(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         if self.config.twitch_access_token.is_some() && self.config.twitch_refresh_token.is_some() {
@@ -264,45 +463,44 @@ impl TwitchAPIClient {
         self.initialized.load(Ordering::SeqCst)
     }
 
-    async fn start_auth_flow(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let auth_code = Arc::new(Mutex::new(None));
-        let auth_code_clone = auth_code.clone();
-
-        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
-        let tx = Arc::new(Mutex::new(Some(tx)));
-
-        let routes = warp::get()
-            .and(warp::path("callback"))
-            .and(warp::query::<std::collections::HashMap<String, String>>())
-            .and(warp::any().map(move || (auth_code_clone.clone(), tx.clone())))
-            .and_then(|p: std::collections::HashMap<String, String>,
-                       (auth_code, tx): (Arc<Mutex<Option<String>>>, Arc<Mutex<Option<tokio::sync::oneshot::Sender<()>>>>)| async move {
-                if let Some(code) = p.get("code") {
-                    *auth_code.lock().await = Some(code.to_string());
-                    if let Some(tx) = tx.lock().await.take() {
-                        let _ = tx.send(());
-                    }
-                    Ok::<_, Infallible>("Authorization successful! You can close this window now.".to_string())
-                } else if let Some(error) = p.get("error") {
-                    Ok::<_, Infallible>(format!("Authorization failed: {}. Please try again.", error))
-                } else {
-                    Ok::<_, Infallible>("Authorization failed. Please try again.".to_string())
-                }
-            });
-
-        warn!("Starting local server on http://localhost:3000");
-        let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(([127, 0, 0, 1], 3000), async {
-            rx.await.ok();
-        });
+    fn authorize_url(&self, state: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let client_id = self.config.twitch_client_id.as_ref().ok_or("Twitch client ID not set")?;
+        let url = reqwest::Url::parse_with_params("https://id.twitch.tv/oauth2/authorize", &[
+            ("client_id", client_id.as_str()),
+            ("redirect_uri", self.config.twitch_redirect_uri().as_str()),
+            ("response_type", "code"),
+            ("scope", OAUTH_SCOPES.join(" ").as_str()),
+            ("state", state),
+        ])?;
+        Ok(url.to_string())
+    }
 
-        warn!("Local server running on {}", addr);
+    async fn wait_for_code(receiver: tokio::sync::oneshot::Receiver<Result<String, String>>) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        match timeout(TokioDuration::from_secs(300), receiver).await {
+            Ok(Ok(Ok(code))) => Ok(code),
+            Ok(Ok(Err(e))) => Err(format!("Twitch authorization failed: {}", e).into()),
+            Ok(Err(_)) => Err("The authorization was abandoned".into()),
+            Err(_) => Err("Timeout waiting for authorization code".into()),
+        }
+    }
 
-        let server_handle = tokio::spawn(server);
+    async fn start_auth_flow(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (state, receiver) = self.authorizations.begin();
+        let auth_url = self.authorize_url(&state)?;
 
-        let auth_url = format!(
-            "https://id.twitch.tv/oauth2/authorize?client_id={}&redirect_uri=http://localhost:3000/callback&response_type=code&scope=chat:read chat:edit channel:read:subscriptions moderator:read:followers moderator:manage:shoutouts channel:read:subscriptions channel:manage:redemptions channel:manage:ads channel:read:ads channel:manage:vips moderation:read moderator:manage:announcements bits:read channel:manage:broadcast user:edit:broadcast channel:edit:commercial",
-            self.config.twitch_client_id.as_ref().ok_or("Twitch client ID not set")?
-        );
+        // On the first start the web UI isn't up yet, so answer the redirect on its port until Twitch calls back
+        let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let server_handle = if self.authorizations.web_ui_listening() {
+            None
+        } else {
+            let port = self.config.web_ui_port.unwrap_or(3333);
+            let routes = crate::web_ui::oauth::oauth_callback_route(&self.config.twitch_redirect_path, self.authorizations.clone());
+            let (addr, server) = warp::serve(routes).try_bind_with_graceful_shutdown(([127, 0, 0, 1], port), async {
+                shutdown_rx.await.ok();
+            })?;
+            warn!("Waiting for the Twitch redirect on {}", addr);
+            Some(tokio::spawn(server))
+        };
 
         println!("Please open the following URL in your browser to authorize the application:");
         println!("{}", auth_url);
@@ -311,30 +509,49 @@ impl TwitchAPIClient {
             println!("Failed to open the browser automatically. Please open the URL manually.");
         }
 
-        let code = match timeout(TokioDuration::from_secs(300), async {
-            loop {
-                if let Some(code) = auth_code.lock().await.take() {
-                    return code;
-                }
-                sleep(TokioDuration::from_millis(100)).await;
-            }
-        }).await {
-            Ok(code) => code,
-            Err(_) => return Err("Timeout waiting for authorization code".into()),
-        };
+        let code = Self::wait_for_code(receiver).await;
+        if let Some(server_handle) = server_handle {
+            let _ = shutdown_tx.send(());
+            // Ensure the server is fully shut down before the web UI wants the port
+            server_handle.await?;
+        }
+        let code = code?;
 
         println!("Received authorization code. Exchanging for token...");
 
         self.exchange_code(code).await?;
 
-        // Ensure the server is fully shut down
-        server_handle.await?;
-
         println!("Authorization flow completed successfully.");
 
         Ok(())
     }
 
+    // Shared with the web UI, which serves the redirect once it's up
+    pub fn authorizations(&self) -> Arc<PendingAuthorizations> {
+        self.authorizations.clone()
+    }
+
+    // Authorizes again while the bot runs, e.g. after adding scopes; returns the URL for the
+    // broadcaster to open. The new tokens are swapped in once Twitch redirects back.
+    pub fn reauthorize(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let (state, receiver) = self.authorizations.begin();
+        let auth_url = self.authorize_url(&state)?;
+
+        let api_client = self.clone();
+        tokio::spawn(async move {
+            let result = match Self::wait_for_code(receiver).await {
+                Ok(code) => api_client.exchange_code(code).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => info!("Twitch re-authorization completed"),
+                Err(e) => error!("Twitch re-authorization failed: {}", e),
+            }
+        });
+
+        Ok(auth_url)
+    }
+
     async fn exchange_code(&self, code: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let client_id = self.config.twitch_client_id.as_ref().ok_or("Twitch client ID not set")?;
         let client_secret = self.config.twitch_client_secret.as_ref().ok_or("Twitch client secret not set")?;
@@ -347,7 +564,7 @@ impl TwitchAPIClient {
                 ("client_secret", client_secret),
                 ("code", &code),
                 ("grant_type", &"authorization_code".to_string()),
-                ("redirect_uri", &"http://localhost:3000/callback".to_string()),
+                ("redirect_uri", &self.config.twitch_redirect_uri()),
             ])
             .send()
             .await?;
@@ -743,6 +960,34 @@ impl TwitchAPIClient {
             Err(format!("Failed to update custom reward. Status: {}, Body: {}", status, error_body).into())
         }
     }
+
+    // Changes only the cost, leaving everything else about the reward as it is
+    pub async fn update_custom_reward_cost(&self, reward_id: &str, cost: u32) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let token = self.get_token().await?;
+        let client_id = self.get_client_id().await?;
+        let broadcaster_id = self.get_broadcaster_id().await?;
+
+        let url = format!(
+            "https://api.twitch.tv/helix/channel_points/custom_rewards?broadcaster_id={}&id={}",
+            broadcaster_id, reward_id
+        );
+
+        let response = self.client
+            .patch(&url)
+            .header("Client-ID", client_id)
+            .header("Authorization", format!("Bearer {}", token))
+            .json(&serde_json::json!({ "cost": cost }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let error_body = response.text().await?;
+            Err(format!("Failed to update reward cost. Status: {}, Body: {}", status, error_body).into())
+        }
+    }
 }
 
 impl TwitchAPIClient {
@@ -756,6 +1001,26 @@ impl TwitchAPIClient {
         Ok(!data["data"].as_array().unwrap_or(&vec![]).is_empty())
     }
 
+    // The user id of every moderator, following the pages
+    pub async fn get_moderator_ids(&self, broadcaster_id: &str) -> Result<HashSet<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut ids = HashSet::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut url = format!("https://api.twitch.tv/helix/moderation/moderators?broadcaster_id={}&first=100", broadcaster_id);
+            if let Some(cursor) = &cursor {
+                url.push_str(&format!("&after={}", cursor));
+            }
+            let response = self.send_authenticated_request(&url).await?;
+            let data: serde_json::Value = response.json().await?;
+            ids.extend(data["data"].as_array().into_iter().flatten().filter_map(|moderator| moderator["user_id"].as_str().map(str::to_string)));
+            cursor = data["pagination"]["cursor"].as_str().map(str::to_string);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(ids)
+    }
+
     pub async fn check_user_vip(&self, broadcaster_id: &str, user_id: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
         let url = format!(
             "https://api.twitch.tv/helix/channels/vips?broadcaster_id={}&user_id={}",
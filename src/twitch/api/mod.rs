@@ -1,4 +1,5 @@
 pub mod client;
+pub mod oauth;
 pub mod requests;
 
-pub use client::TwitchAPIClient;
\ No newline at end of file
+pub use client::TwitchAPIClient;
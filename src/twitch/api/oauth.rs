@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+
+// Authorizations waiting for Twitch to send the broadcaster back, keyed by the `state` sent along
+// with them. The web UI serves the redirect and hands the code (or Twitch's error) to the waiting
+// flow. Owned by TwitchAPIClient, which starts the flows.
+#[derive(Default)]
+pub struct PendingAuthorizations {
+    pending: Mutex<HashMap<String, oneshot::Sender<Result<String, String>>>>,
+    // Until the web UI is up (the first start, before there are tokens) the auth flow serves the
+    // redirect itself on the web UI's port
+    web_ui_listening: AtomicBool,
+}
+
+impl PendingAuthorizations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_web_ui_listening(&self, listening: bool) {
+        self.web_ui_listening.store(listening, Ordering::SeqCst);
+    }
+
+    pub fn web_ui_listening(&self) -> bool {
+        self.web_ui_listening.load(Ordering::SeqCst)
+    }
+
+    // Returns the state to put in the authorize URL and where its code will arrive
+    pub fn begin(&self) -> (String, oneshot::Receiver<Result<String, String>>) {
+        let state = uuid::Uuid::new_v4().simple().to_string();
+        let (tx, rx) = oneshot::channel();
+        let mut pending = self.pending.lock().unwrap();
+        // Flows that timed out dropped their receiver
+        pending.retain(|_, waiting| !waiting.is_closed());
+        pending.insert(state.clone(), tx);
+        (state, rx)
+    }
+
+    // False when nothing is waiting for this state, e.g. an old or forged redirect
+    pub fn complete(&self, state: &str, result: Result<String, String>) -> bool {
+        match self.pending.lock().unwrap().remove(state) {
+            Some(waiting) => waiting.send(result).is_ok(),
+            None => false,
+        }
+    }
+}
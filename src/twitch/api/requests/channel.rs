@@ -1,4 +1,4 @@
-use log::{info, warn, error};
+use tracing::{info, warn, error};
 use crate::twitch::api::TwitchAPIClient;
 use serde_json::Value;
 use crate::twitch::api::client::TwitchAPIError;
@@ -126,6 +126,36 @@ pub async fn update_channel_category(
     Ok(())
 }
 
+// Replaces every tag on the channel
+pub async fn update_channel_tags(
+    api_client: &TwitchAPIClient,
+    broadcaster_id: &str,
+    tags: &[String],
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let token = api_client.get_token().await?;
+    let client_id = api_client.get_client_id().await?;
+
+    let response = api_client.client
+        .patch("https://api.twitch.tv/helix/channels")
+        .header("Client-ID", client_id)
+        .header("Authorization", format!("Bearer {}", token))
+        .query(&[("broadcaster_id", broadcaster_id)])
+        .json(&serde_json::json!({
+            "tags": tags
+        }))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await?;
+        return Err(format!("Failed to update channel tags. Status: {}, Error: {}",
+                           status, error_text).into());
+    }
+
+    Ok(())
+}
+
 pub async fn update_content_classification_labels(
     api_client: &TwitchAPIClient,
     broadcaster_id: &str,
@@ -191,10 +221,41 @@ pub async fn start_commercial(
     Ok(())
 }
 
-pub async fn search_category(
+// Only works while the channel is live
+pub async fn create_stream_marker(
+    api_client: &TwitchAPIClient,
+    broadcaster_id: &str,
+    description: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let token = api_client.get_token().await?;
+    let client_id = api_client.get_client_id().await?;
+
+    let response = api_client.client
+        .post("https://api.twitch.tv/helix/streams/markers")
+        .header("Client-ID", client_id)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "user_id": broadcaster_id,
+            "description": description
+        }))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await?;
+        return Err(format!("Failed to create stream marker. Status: {}, Error: {}",
+                           status, error_text).into());
+    }
+
+    Ok(())
+}
+
+// Returns (id, name) for every category Twitch considers relevant, best match first
+pub async fn search_categories(
     api_client: &TwitchAPIClient,
     query: &str,
-) -> Result<Option<(String, String)>, Box<dyn std::error::Error + Send + Sync>> {
+) -> Result<Vec<(String, String)>, Box<dyn std::error::Error + Send + Sync>> {
     let token = api_client.get_token().await?;
     let client_id = api_client.get_client_id().await?;
 
@@ -202,7 +263,7 @@ pub async fn search_category(
         .get("https://api.twitch.tv/helix/search/categories")
         .header("Client-ID", client_id)
         .header("Authorization", format!("Bearer {}", token))
-        .query(&[("query", query)])
+        .query(&[("query", query), ("first", "20")])
         .send()
         .await?;
 
@@ -215,16 +276,14 @@ pub async fn search_category(
 
     let body: serde_json::Value = response.json().await?;
 
-    // Get the first (best) match
-    if let Some(categories) = body["data"].as_array() {
-        if let Some(first_match) = categories.first() {
-            let game_id = first_match["id"].as_str()
-                .ok_or("Game ID not found in response")?;
-            let game_name = first_match["name"].as_str()
-                .ok_or("Game name not found in response")?;
-            return Ok(Some((game_id.to_string(), game_name.to_string())));
-        }
-    }
+    let categories = body["data"].as_array()
+        .map(|categories| categories.iter()
+            .filter_map(|category| Some((
+                category["id"].as_str()?.to_string(),
+                category["name"].as_str()?.to_string(),
+            )))
+            .collect())
+        .unwrap_or_default();
 
-    Ok(None)
+    Ok(categories)
 }
\ No newline at end of file
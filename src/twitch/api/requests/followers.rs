@@ -3,7 +3,7 @@
 use crate::twitch::api::TwitchAPIClient;
 use serde_json::Value;
 use chrono::{DateTime, Utc};
-use log::error;
+use tracing::error;
 
 pub async fn get_follower_count(client: &TwitchAPIClient, broadcaster_id: &str) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
     let token = client.get_token().await?;
@@ -3,6 +3,7 @@ pub mod shoutout;
 pub mod channel_points;
 pub mod announcement;
 pub mod followers;
+pub mod predictions;
 
 pub use announcement::send_announcement;
 pub use channel::{get_channel_game};
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use crate::twitch::api::TwitchAPIClient;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PredictionOutcome {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub users: u64,
+    #[serde(default)]
+    pub channel_points: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Prediction {
+    pub id: String,
+    pub title: String,
+    pub outcomes: Vec<PredictionOutcome>,
+    // ACTIVE, LOCKED, RESOLVED or CANCELED
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// The prediction that's still open or locked, if any; Helix lists the newest first
+pub async fn get_open_prediction(
+    api_client: &TwitchAPIClient,
+    broadcaster_id: &str,
+) -> Result<Option<Prediction>, Box<dyn std::error::Error + Send + Sync>> {
+    let token = api_client.get_token().await?;
+    let client_id = api_client.get_client_id().await?;
+
+    let response = api_client.client
+        .get("https://api.twitch.tv/helix/predictions")
+        .header("Client-ID", client_id)
+        .header("Authorization", format!("Bearer {}", token))
+        .query(&[("broadcaster_id", broadcaster_id), ("first", "5")])
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await?;
+        return Err(format!("Failed to get predictions. Status: {}, Error: {}",
+                           status, error_text).into());
+    }
+
+    let body: serde_json::Value = response.json().await?;
+    let predictions: Vec<Prediction> = serde_json::from_value(body["data"].clone())?;
+    Ok(predictions.into_iter().find(|p| p.status == "ACTIVE" || p.status == "LOCKED"))
+}
+
+// Pays out the prediction to everyone who picked the winning outcome
+pub async fn resolve_prediction(
+    api_client: &TwitchAPIClient,
+    broadcaster_id: &str,
+    prediction_id: &str,
+    winning_outcome_id: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let token = api_client.get_token().await?;
+    let client_id = api_client.get_client_id().await?;
+
+    let response = api_client.client
+        .patch("https://api.twitch.tv/helix/predictions")
+        .header("Client-ID", client_id)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({
+            "broadcaster_id": broadcaster_id,
+            "id": prediction_id,
+            "status": "RESOLVED",
+            "winning_outcome_id": winning_outcome_id
+        }))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await?;
+        return Err(format!("Failed to resolve prediction. Status: {}, Error: {}",
+                           status, error_text).into());
+    }
+
+    Ok(())
+}
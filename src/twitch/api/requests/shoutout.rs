@@ -1,4 +1,4 @@
-use log::{info, error};
+use tracing::{info, error};
 use std::sync::Arc;
 use crate::twitch::api::client::TwitchAPIError;
 use crate::twitch::TwitchManager;
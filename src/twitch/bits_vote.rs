@@ -0,0 +1,236 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use tracing::{error, info, warn};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::json;
+use tokio::time::{sleep, Duration};
+use crate::config::BitsVoteConfig;
+use crate::osc::{OSCMessageType, OSCValue};
+use crate::twitch::TwitchManager;
+use crate::web_ui::websocket::WebSocketMessage;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BitsVoteOption {
+    pub name: String,
+    pub bits: u64,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BitsVoteSummary {
+    pub id: u64,
+    pub question: String,
+    pub options: Vec<BitsVoteOption>,
+    pub total_bits: u64,
+    pub ends_at: DateTime<Utc>,
+    pub closed: bool,
+}
+
+impl BitsVoteSummary {
+    // Option number (from 1) with the most bits; None for a tie or no bits at all
+    pub fn winner(&self) -> Option<usize> {
+        let top = self.options.iter().map(|option| option.bits).max().unwrap_or(0);
+        let mut leaders = self.options.iter().enumerate().filter(|(_, option)| option.bits == top);
+        match (leaders.next(), leaders.next()) {
+            (Some((index, _)), None) if top > 0 => Some(index + 1),
+            _ => None,
+        }
+    }
+}
+
+// Like a quick poll, but cheers are the votes and every bit counts
+struct BitsVote {
+    id: u64,
+    question: String,
+    options: Vec<String>,
+    bits: Vec<u64>,
+    ends_at: DateTime<Utc>,
+    osc_parameter: String,
+}
+
+impl BitsVote {
+    fn summary(&self, closed: bool) -> BitsVoteSummary {
+        BitsVoteSummary {
+            id: self.id,
+            question: self.question.clone(),
+            options: self.options.iter().zip(&self.bits)
+                .map(|(name, bits)| BitsVoteOption { name: name.clone(), bits: *bits })
+                .collect(),
+            total_bits: self.bits.iter().sum(),
+            ends_at: self.ends_at,
+            closed,
+        }
+    }
+
+    // The option named earliest in the cheer text, by number or by name; cheermotes like "cheer100" are skipped
+    fn choice_index(&self, message: &str) -> Option<usize> {
+        let words: Vec<String> = message.split_whitespace()
+            .filter(|word| !is_cheermote(word))
+            .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        let mut best: Option<(usize, usize)> = None;
+        for (index, option) in self.options.iter().enumerate() {
+            let option_words: Vec<String> = option.split_whitespace().map(str::to_lowercase).collect();
+            let number = (index + 1).to_string();
+            let position = words.iter().position(|word| *word == number).into_iter()
+                .chain(words.windows(option_words.len().max(1)).position(|window| window == option_words.as_slice()))
+                .min();
+            if let Some(position) = position {
+                if best.map_or(true, |(best_position, _)| position < best_position) {
+                    best = Some((position, index));
+                }
+            }
+        }
+        best.map(|(_, index)| index)
+    }
+}
+
+fn is_cheermote(word: &str) -> bool {
+    let letters = word.trim_end_matches(|c: char| c.is_ascii_digit());
+    letters.len() < word.len() && !letters.is_empty() && letters.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+// The running vote, owned by TwitchManager and shared with the overlay routes
+pub struct BitsVotes {
+    vote: Mutex<Option<BitsVote>>,
+    // Kept so the overlay can keep showing the winner after the vote closes
+    last_result: Mutex<Option<BitsVoteSummary>>,
+    next_id: AtomicU64,
+}
+
+impl BitsVotes {
+    pub fn new() -> Self {
+        Self {
+            vote: Mutex::new(None),
+            last_result: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn current(&self) -> Option<BitsVoteSummary> {
+        self.vote.lock().unwrap().as_ref().map(|vote| vote.summary(false))
+    }
+
+    // The running vote, or else the result of the last one
+    pub fn latest(&self) -> Option<BitsVoteSummary> {
+        self.current().or_else(|| self.last_result.lock().unwrap().clone())
+    }
+
+    // Counts a cheer towards the option it names; None when there's no vote or no option was named
+    pub fn cheer(&self, message: &str, bits: u64) -> Option<BitsVoteSummary> {
+        let mut vote = self.vote.lock().unwrap();
+        let vote = vote.as_mut()?;
+        let index = vote.choice_index(message)?;
+        vote.bits[index] += bits;
+        Some(vote.summary(false))
+    }
+
+    // Closes the running vote early; the caller finishes it
+    pub fn end(&self) -> Option<(BitsVoteSummary, String)> {
+        self.vote.lock().unwrap().take().map(|vote| (vote.summary(true), vote.osc_parameter))
+    }
+}
+
+// Returns None if a vote is already running
+pub fn start(
+    twitch_manager: Arc<TwitchManager>,
+    config: &BitsVoteConfig,
+    question: String,
+    options: Vec<String>,
+    duration_secs: u64,
+) -> Option<BitsVoteSummary> {
+    let summary = {
+        let mut vote = twitch_manager.bits_votes.vote.lock().unwrap();
+        if vote.is_some() {
+            return None;
+        }
+        let new_vote = BitsVote {
+            id: twitch_manager.bits_votes.next_id.fetch_add(1, Ordering::Relaxed),
+            question,
+            bits: vec![0; options.len()],
+            options,
+            ends_at: Utc::now() + chrono::Duration::seconds(duration_secs as i64),
+            osc_parameter: config.osc_parameter.clone(),
+        };
+        let summary = new_vote.summary(false);
+        *vote = Some(new_vote);
+        summary
+    };
+    info!("Bits vote #{} started: {}", summary.id, summary.question);
+
+    let id = summary.id;
+    let tasks = twitch_manager.tasks.clone();
+    tasks.spawn("bits_vote", async move {
+        sleep(Duration::from_secs(duration_secs)).await;
+        // The vote may have been ended by hand and another one started in the meantime
+        let ended = {
+            let mut vote = twitch_manager.bits_votes.vote.lock().unwrap();
+            match vote.as_ref() {
+                Some(running) if running.id == id => vote.take(),
+                _ => None,
+            }
+        };
+        if let Some(vote) = ended {
+            finish(&twitch_manager, &vote.summary(true), &vote.osc_parameter).await;
+        }
+    });
+
+    Some(summary)
+}
+
+pub fn describe_options(summary: &BitsVoteSummary) -> String {
+    summary.options.iter().enumerate()
+        .map(|(i, option)| format!("{}) {}", i + 1, option.name))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn describe_result(summary: &BitsVoteSummary) -> String {
+    match summary.winner() {
+        Some(number) => {
+            let winner = &summary.options[number - 1];
+            format!(
+                "Bits vote closed: {} - {} wins with {} bits of {}!",
+                summary.question, winner.name, winner.bits, summary.total_bits
+            )
+        }
+        None if summary.total_bits == 0 => format!("Bits vote closed: {} - nobody cheered!", summary.question),
+        None => format!("Bits vote closed: {} - it's a tie!", summary.question),
+    }
+}
+
+// Pushes live tallies to the dashboard and any overlay listening for the "bitsvote" module
+pub async fn publish(twitch_manager: &TwitchManager, summary: &BitsVoteSummary) {
+    let message = WebSocketMessage {
+        module: "bitsvote".to_string(),
+        action: if summary.closed { "end" } else { "update" }.to_string(),
+        data: json!({ "vote": summary, "winner": summary.winner() }),
+    };
+    let dashboard_state = twitch_manager.irc_manager.get_dashboard_state();
+    if let Err(e) = dashboard_state.read().await.broadcast_message(message).await {
+        warn!("Failed to send bits vote tallies to the dashboard: {}", e);
+    }
+}
+
+// Announces the winner, sets the avatar parameter and publishes the final tallies
+pub async fn finish(twitch_manager: &TwitchManager, summary: &BitsVoteSummary, osc_parameter: &str) {
+    info!("Bits vote #{} closed with {} bits", summary.id, summary.total_bits);
+    *twitch_manager.bits_votes.last_result.lock().unwrap() = Some(summary.clone());
+    publish(twitch_manager, summary).await;
+
+    let winner = summary.winner().unwrap_or(0) as i32;
+    if let Err(e) = twitch_manager.get_osc_manager()
+        .send_osc_message(osc_parameter, &OSCMessageType::Integer, &OSCValue::Integer(winner)).await
+    {
+        error!("Failed to send bits vote result over OSC: {}", e);
+    }
+
+    if let Some(channel) = &twitch_manager.config.twitch_channel_to_join {
+        if let Err(e) = twitch_manager.send_message_as_bot(channel, &describe_result(summary)).await {
+            error!("Failed to announce bits vote result: {}", e);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use serenity::model::id::{ChannelId, MessageId};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{Duration, Instant};
+use crate::discord::announcements::StreamAnnouncement;
+
+// How long a change made through !title/!game waits for its channel.update echo
+const EXPECTED_CHANGE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelField {
+    Title,
+    Category,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChannelChanges {
+    pub title: Option<String>,
+    pub category: Option<String>,
+}
+
+// Tracks the last known title/category so channel.update can tell what actually
+// changed, and remembers mewbot's own edits so they aren't announced twice.
+#[derive(Debug, Default)]
+pub struct ChannelUpdates {
+    title: RwLock<Option<String>>,
+    category: RwLock<Option<String>>,
+    expected: Mutex<Vec<(ChannelField, String, Instant)>>,
+    go_live_announcement: RwLock<Option<(ChannelId, MessageId, StreamAnnouncement)>>,
+    // The Discord thread under the go-live post, where this stream's clips and recap go
+    stream_thread: RwLock<Option<ChannelId>>,
+}
+
+impl ChannelUpdates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Called by commands right after they change the channel through the API
+    pub async fn expect_change(&self, field: ChannelField, value: &str) {
+        let mut expected = self.expected.lock().await;
+        expected.retain(|(_, _, at)| at.elapsed() < EXPECTED_CHANGE_TTL);
+        expected.push((field, value.to_lowercase(), Instant::now()));
+    }
+
+    // Consumes a pending self-made change matching this value, if there is one
+    pub async fn take_expected(&self, field: ChannelField, value: &str) -> bool {
+        let value = value.to_lowercase();
+        let mut expected = self.expected.lock().await;
+        expected.retain(|(_, _, at)| at.elapsed() < EXPECTED_CHANGE_TTL);
+        match expected.iter().position(|(f, v, _)| *f == field && *v == value) {
+            Some(index) => {
+                expected.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Stores the new title/category and returns the ones that differ from before.
+    // With nothing known yet (e.g. right after startup) both count as changed.
+    pub async fn apply(&self, title: &str, category: &str) -> ChannelChanges {
+        let mut changes = ChannelChanges::default();
+
+        let mut current_title = self.title.write().await;
+        if current_title.as_deref() != Some(title) {
+            *current_title = Some(title.to_string());
+            changes.title = Some(title.to_string());
+        }
+
+        let mut current_category = self.category.write().await;
+        if current_category.as_deref() != Some(category) {
+            *current_category = Some(category.to_string());
+            changes.category = Some(category.to_string());
+        }
+
+        changes
+    }
+
+    pub async fn set_go_live_announcement(&self, channel_id: ChannelId, message_id: MessageId, announcement: StreamAnnouncement) {
+        *self.go_live_announcement.write().await = Some((channel_id, message_id, announcement));
+    }
+
+    pub async fn clear_go_live_announcement(&self) {
+        *self.go_live_announcement.write().await = None;
+    }
+
+    pub async fn set_stream_thread(&self, thread_id: ChannelId) {
+        *self.stream_thread.write().await = Some(thread_id);
+    }
+
+    pub async fn stream_thread(&self) -> Option<ChannelId> {
+        *self.stream_thread.read().await
+    }
+
+    pub async fn clear_stream_thread(&self) {
+        *self.stream_thread.write().await = None;
+    }
+
+    // Updates the remembered announcement and hands it back so the Discord message can be edited
+    pub async fn update_go_live_announcement(&self, title: &str, category: &str) -> Option<(ChannelId, MessageId, StreamAnnouncement)> {
+        let mut stored = self.go_live_announcement.write().await;
+        let (channel_id, message_id, announcement) = stored.as_mut()?;
+        announcement.title = Some(title.to_string());
+        announcement.game_name = Some(category.to_string());
+        Some((*channel_id, *message_id, announcement.clone()))
+    }
+
+    pub async fn set_go_live_status(&self, status: Option<String>) -> Option<(ChannelId, MessageId, StreamAnnouncement)> {
+        let mut stored = self.go_live_announcement.write().await;
+        let (channel_id, message_id, announcement) = stored.as_mut()?;
+        announcement.status = status;
+        Some((*channel_id, *message_id, announcement.clone()))
+    }
+}
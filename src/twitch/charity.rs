@@ -0,0 +1,128 @@
+use std::sync::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::warn;
+use crate::twitch::TwitchManager;
+use crate::web_ui::websocket::WebSocketMessage;
+
+// The channel's charity campaign as of the last EventSub notification
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CharityCampaign {
+    pub id: String,
+    pub charity_name: String,
+    pub charity_logo: String,
+    pub currency: String,
+    pub current: f64,
+    pub target: f64,
+    // False once the campaign has stopped; the final total stays up until the next one
+    pub active: bool,
+}
+
+impl CharityCampaign {
+    pub fn percent(&self) -> u64 {
+        if self.target <= 0.0 {
+            return 0;
+        }
+        (self.current * 100.0 / self.target).floor() as u64
+    }
+}
+
+struct CharityState {
+    campaign: Option<CharityCampaign>,
+    // Milestone percents already called out for this campaign
+    announced: Vec<u64>,
+}
+
+// The running (or last) campaign, owned by TwitchManager and shared with the overlay routes
+pub struct Charity {
+    state: Mutex<CharityState>,
+}
+
+impl Charity {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(CharityState {
+                campaign: None,
+                announced: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn current(&self) -> Option<CharityCampaign> {
+        self.state.lock().unwrap().campaign.clone()
+    }
+
+    pub fn start(&self, payload: &Value) -> CharityCampaign {
+        let campaign = campaign_from(payload, true);
+        let mut state = self.state.lock().unwrap();
+        state.campaign = Some(campaign.clone());
+        state.announced.clear();
+        campaign
+    }
+
+    // Updates the totals; also returns the highest milestone this update crossed, if any
+    pub fn progress(&self, payload: &Value, milestone_percents: &[u64]) -> (CharityCampaign, Option<u64>) {
+        let campaign = campaign_from(payload, true);
+        let mut state = self.state.lock().unwrap();
+        // Started before the bot was running, or a different campaign than the one we knew about
+        if state.campaign.as_ref().map_or(true, |known| known.id != campaign.id) {
+            state.announced.clear();
+        }
+        let percent = campaign.percent();
+        let crossed = milestone_percents.iter()
+            .copied()
+            .filter(|milestone| percent >= *milestone && !state.announced.contains(milestone))
+            .collect::<Vec<_>>();
+        state.announced.extend(&crossed);
+        state.campaign = Some(campaign.clone());
+        (campaign, crossed.into_iter().max())
+    }
+
+    pub fn stop(&self, payload: &Value) -> CharityCampaign {
+        let campaign = campaign_from(payload, false);
+        self.state.lock().unwrap().campaign = Some(campaign.clone());
+        campaign
+    }
+}
+
+// Twitch sends amounts as {"value": 1050, "decimal_places": 2, "currency": "USD"}
+pub fn amount(value: &Value) -> f64 {
+    let minor = value["value"].as_i64().unwrap_or(0) as f64;
+    let decimal_places = value["decimal_places"].as_u64().unwrap_or(0) as i32;
+    minor / 10f64.powi(decimal_places)
+}
+
+pub fn format_amount(amount: f64, currency: &str) -> String {
+    match currency {
+        "USD" | "CAD" | "AUD" => format!("${:.2}", amount),
+        "EUR" => format!("€{:.2}", amount),
+        "GBP" => format!("£{:.2}", amount),
+        _ => format!("{:.2} {}", amount, currency),
+    }
+}
+
+fn campaign_from(payload: &Value, active: bool) -> CharityCampaign {
+    CharityCampaign {
+        id: payload["id"].as_str().unwrap_or_default().to_string(),
+        charity_name: payload["charity_name"].as_str().unwrap_or("charity").to_string(),
+        charity_logo: payload["charity_logo"].as_str().unwrap_or_default().to_string(),
+        currency: payload["target_amount"]["currency"].as_str().unwrap_or("USD").to_string(),
+        current: amount(&payload["current_amount"]),
+        target: amount(&payload["target_amount"]),
+        active,
+    }
+}
+
+// Pushes the totals to the dashboard and any overlay listening for the "charity" module
+pub async fn publish(twitch_manager: &TwitchManager, campaign: &CharityCampaign) {
+    let message = WebSocketMessage {
+        module: "charity".to_string(),
+        action: "update".to_string(),
+        data: json!({ "campaign": campaign }),
+    };
+    let dashboard_state = twitch_manager.irc_manager.get_dashboard_state();
+    if let Err(e) = dashboard_state.read().await.broadcast_message(message).await {
+        warn!("Failed to send charity progress to the dashboard: {}", e);
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{error, info};
+use rand::distributions::{Distribution, WeightedIndex};
+use tokio::time::{sleep, Duration};
+use twitch_irc::message::PrivmsgMessage;
+use crate::config::ChatterDropConfig;
+use crate::osc::{OSCMessageType, OSCValue};
+use crate::twitch::roles::{is_subscriber, is_vip, UserRole};
+use crate::twitch::TwitchManager;
+
+#[derive(Debug, Clone)]
+struct WindowChatter {
+    user_name: String,
+    role: UserRole,
+}
+
+#[derive(Debug, Default)]
+pub struct ChatterDrops {
+    // Everyone who chatted since the last drop, keyed by user id
+    window: Mutex<HashMap<String, WindowChatter>>,
+}
+
+impl ChatterDrops {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// Called for every chat message that made it past the ignore list
+pub fn record(twitch_manager: &TwitchManager, msg: &PrivmsgMessage) {
+    if !twitch_manager.config.chatter_drops.enabled {
+        return;
+    }
+
+    let is_bot = twitch_manager.config.twitch_bot_username.as_ref()
+        .map_or(false, |bot| bot.eq_ignore_ascii_case(&msg.sender.login));
+    if is_bot || msg.badges.iter().any(|b| b.name == "broadcaster") {
+        return;
+    }
+
+    let role = if msg.badges.iter().any(|b| b.name == "moderator") {
+        UserRole::Moderator
+    } else if is_vip(&msg.badges) {
+        UserRole::VIP
+    } else if is_subscriber(&msg.badges) {
+        UserRole::Subscriber
+    } else {
+        UserRole::Viewer
+    };
+
+    twitch_manager.chatter_drops.window.lock().unwrap().insert(msg.sender.id.clone(), WindowChatter {
+        user_name: msg.sender.name.clone(),
+        role,
+    });
+}
+
+fn weight(config: &ChatterDropConfig, role: &UserRole) -> f64 {
+    match role {
+        UserRole::Moderator | UserRole::Broadcaster => config.moderator_weight,
+        UserRole::VIP => config.vip_weight,
+        UserRole::Subscriber => config.subscriber_weight,
+        UserRole::Viewer => 1.0,
+    }
+}
+
+fn pick_winner(config: &ChatterDropConfig, chatters: &[(String, WindowChatter)]) -> Option<usize> {
+    let weights: Vec<f64> = chatters.iter()
+        .map(|(_, chatter)| weight(config, &chatter.role).max(0.0))
+        .collect();
+    // Errors when every weight is zero, which just means nobody is eligible
+    let distribution = WeightedIndex::new(&weights).ok()?;
+    Some(distribution.sample(&mut rand::thread_rng()))
+}
+
+pub async fn run(twitch_manager: Arc<TwitchManager>) {
+    let config = twitch_manager.config.chatter_drops.clone();
+    let interval = Duration::from_secs(config.interval_minutes.max(1) * 60);
+    let channel = match twitch_manager.config.twitch_channel_to_join.clone() {
+        Some(channel) => channel,
+        None => {
+            error!("Chatter drops need twitch_channel_to_join to be set");
+            return;
+        }
+    };
+
+    loop {
+        sleep(interval).await;
+
+        let chatters: Vec<(String, WindowChatter)> = twitch_manager.chatter_drops.window.lock().unwrap().drain().collect();
+        if chatters.is_empty() || !twitch_manager.is_stream_live().await {
+            continue;
+        }
+
+        let (user_id, winner) = match pick_winner(&config, &chatters) {
+            Some(index) => chatters[index].clone(),
+            None => continue,
+        };
+        drop_to(&twitch_manager, &config, &channel, &user_id, &winner.user_name).await;
+    }
+}
+
+async fn drop_to(twitch_manager: &Arc<TwitchManager>, config: &ChatterDropConfig, channel: &str, user_id: &str, user_name: &str) {
+    info!("Chatter drop won by {}", user_name);
+
+    let mut total = config.points as i64;
+    if config.points > 0 {
+        if let Some(storage) = twitch_manager.get_storage() {
+            match storage.read().await.award_drop_points(user_id, user_name, config.points) {
+                Ok(new_total) => total = new_total,
+                Err(e) => error!("Failed to award drop points to {}: {:?}", user_name, e),
+            }
+        }
+    }
+
+    let message = config.message
+        .replace("{user}", user_name)
+        .replace("{points}", &config.points.to_string())
+        .replace("{total}", &total.to_string());
+    if let Err(e) = twitch_manager.send_message_as_bot(channel, &message).await {
+        error!("Failed to announce chatter drop: {:?}", e);
+    }
+
+    if let Some(parameter) = config.celebration_parameter.clone() {
+        let osc_manager = twitch_manager.get_osc_manager();
+        tokio::spawn(async move {
+            if let Err(e) = osc_manager.send_osc_message(&parameter, &OSCMessageType::Boolean, &OSCValue::Boolean(true)).await {
+                error!("Failed to trigger chatter drop celebration: {}", e);
+                return;
+            }
+            sleep(Duration::from_secs(5)).await;
+            if let Err(e) = osc_manager.send_osc_message(&parameter, &OSCMessageType::Boolean, &OSCValue::Boolean(false)).await {
+                error!("Failed to reset chatter drop celebration: {}", e);
+            }
+        });
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use chrono::Utc;
+use tracing::{debug, error, info};
+use regex::Regex;
+use serenity::model::id::ChannelId;
+use crate::storage::ArchivedClip;
+use crate::twitch::TwitchManager;
+
+lazy_static::lazy_static! {
+    static ref CLIP_URL_REGEX: Regex = Regex::new(
+        r"(?:https?://)?(?:clips\.twitch\.tv/(?:embed\?clip=)?|(?:www\.|m\.)?twitch\.tv/[A-Za-z0-9_]+/clip/)([A-Za-z0-9_-]+)"
+    ).unwrap();
+}
+
+pub fn extract_clip_ids(message: &str) -> Vec<String> {
+    let mut ids: Vec<String> = CLIP_URL_REGEX.captures_iter(message)
+        .map(|cap| cap[1].to_string())
+        .collect();
+    ids.dedup();
+    ids
+}
+
+// Resolves a clip through Helix and stores it, mirroring it to the Discord clips channel.
+// Returns None if the clip was already archived.
+pub async fn archive_clip(
+    twitch_manager: &Arc<TwitchManager>,
+    clip_id: &str,
+    submitted_by: &str,
+) -> Result<Option<ArchivedClip>, Box<dyn std::error::Error + Send + Sync>> {
+    let storage = twitch_manager.get_storage().ok_or("Storage not available for clip archive")?;
+
+    if storage.read().await.has_clip(clip_id)? {
+        debug!("Clip {} is already archived", clip_id);
+        return Ok(None);
+    }
+
+    let api_client = twitch_manager.get_api_client();
+    let response = api_client.get_clip(clip_id).await?;
+    let data = response["data"].get(0).ok_or_else(|| format!("Clip {} not found", clip_id))?;
+
+    let broadcaster_id = data["broadcaster_id"].as_str().unwrap_or("");
+    let live_stream_id = current_stream_id(twitch_manager, broadcaster_id).await;
+    let from_current_stream = live_stream_id.is_some();
+    let stream_id = live_stream_id
+        .or_else(|| data["video_id"].as_str().filter(|v| !v.is_empty()).map(String::from));
+
+    let clip = ArchivedClip {
+        clip_id: clip_id.to_string(),
+        url: data["url"].as_str().unwrap_or("").to_string(),
+        title: data["title"].as_str().unwrap_or("Untitled clip").to_string(),
+        broadcaster_name: data["broadcaster_name"].as_str().unwrap_or("Unknown").to_string(),
+        creator_name: data["creator_name"].as_str().unwrap_or("Unknown").to_string(),
+        submitted_by: submitted_by.to_string(),
+        thumbnail_url: data["thumbnail_url"].as_str().map(String::from),
+        duration: data["duration"].as_f64().unwrap_or(0.0),
+        stream_id,
+        created_at: data["created_at"].as_str().unwrap_or("").to_string(),
+        timestamp: Utc::now(),
+    };
+
+    if !storage.read().await.add_clip(&clip)? {
+        return Ok(None);
+    }
+    info!("Archived clip {} ({}) submitted by {}", clip.clip_id, clip.title, submitted_by);
+
+    if let Some(discord_client) = &twitch_manager.discord_client {
+        // Clips from the current stream go in its thread, anything else in the clips channel
+        let stream_thread = if from_current_stream {
+            twitch_manager.channel_updates.stream_thread().await
+        } else {
+            None
+        };
+        let channel_id = stream_thread.or_else(|| twitch_manager.config.discord_clips_channel_id.as_ref()
+            .and_then(|id| id.parse::<u64>().ok())
+            .map(ChannelId::new));
+        if let Some(channel_id) = channel_id {
+            let http = discord_client.get_http().await;
+            if let Err(e) = crate::discord::announcements::send_clip_archive_entry(&http, channel_id, &clip).await {
+                error!("Failed to archive clip to Discord: {}", e);
+            }
+        }
+    }
+
+    Ok(Some(clip))
+}
+
+// Clips of our own channel taken while live are grouped under the current stream
+async fn current_stream_id(twitch_manager: &Arc<TwitchManager>, broadcaster_id: &str) -> Option<String> {
+    if !twitch_manager.is_stream_live().await {
+        return None;
+    }
+
+    let api_client = twitch_manager.get_api_client();
+    let own_id = api_client.get_broadcaster_id().await.ok()?;
+    if own_id != broadcaster_id {
+        return None;
+    }
+
+    let stream_info = api_client.get_stream_info(&own_id).await.ok()?;
+    stream_info["data"][0]["id"].as_str().map(String::from)
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+use crate::twitch::roles::UserRole;
+
+// Per-command overrides set from the dashboard; anything left unset keeps the command's own default.
+// Enforced by the command registry, so plugin commands can be configured the same way.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CommandConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // Shared by everyone in chat; mods and the broadcaster skip it
+    #[serde(default)]
+    pub cooldown_secs: Option<u64>,
+    // Lowest role that can use the command
+    #[serde(default)]
+    pub required_role: Option<UserRole>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for CommandConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cooldown_secs: None,
+            required_role: None,
+        }
+    }
+}
+
+impl CommandConfig {
+    fn is_default(&self) -> bool {
+        self.enabled && self.cooldown_secs.is_none() && self.required_role.is_none()
+    }
+}
+
+struct CommandConfigStore {
+    path: String,
+    // Command name with its !, e.g. "!xmas"
+    commands: HashMap<String, CommandConfig>,
+}
+
+// The overrides from the dashboard, owned by TwitchManager
+pub struct CommandOverrides {
+    store: Mutex<CommandConfigStore>,
+}
+
+fn save(store: &CommandConfigStore) {
+    let result = serde_json::to_string_pretty(&store.commands)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&store.path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Failed to update {}: {}", store.path, e);
+    }
+}
+
+impl CommandOverrides {
+    pub fn new(path: &str) -> Self {
+        Self {
+            store: Mutex::new(CommandConfigStore {
+                path: path.to_string(),
+                commands: HashMap::new(),
+            }),
+        }
+    }
+
+    pub fn load(path: &str) -> Self {
+        let commands = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                error!("Failed to parse {}, using every command's defaults: {}", path, e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        let overrides = Self::new(path);
+        overrides.store.lock().unwrap().commands = commands;
+        overrides
+    }
+
+    pub fn get(&self, name: &str) -> CommandConfig {
+        self.store.lock().unwrap().commands.get(&name.to_lowercase()).cloned().unwrap_or_default()
+    }
+
+    pub fn all(&self) -> HashMap<String, CommandConfig> {
+        self.store.lock().unwrap().commands.clone()
+    }
+
+    // Setting a command back to all defaults drops it from the file
+    pub fn set(&self, name: &str, config: CommandConfig) {
+        let name = name.to_lowercase();
+        let mut store = self.store.lock().unwrap();
+        if config.is_default() {
+            store.commands.remove(&name);
+        } else {
+            store.commands.insert(name, config);
+        }
+        save(&store);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+// Upper bounds of the latency buckets in milliseconds; anything slower lands in the last bucket
+const BUCKETS_MS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    // One more than BUCKETS_MS for the overflow bucket
+    counts: [u64; BUCKETS_MS.len() + 1],
+    total: u64,
+    sum_ms: u64,
+    max_ms: u64,
+    slow: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, ms: u64, slow: bool) {
+        let bucket = BUCKETS_MS.iter().position(|&bound| ms <= bound).unwrap_or(BUCKETS_MS.len());
+        self.counts[bucket] += 1;
+        self.total += 1;
+        self.sum_ms += ms;
+        self.max_ms = self.max_ms.max(ms);
+        if slow {
+            self.slow += 1;
+        }
+    }
+
+    // Upper bound of the bucket holding the given quantile
+    fn quantile(&self, q: f64) -> Option<u64> {
+        if self.total == 0 {
+            return None;
+        }
+        let target = ((self.total as f64) * q).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= target {
+                return Some(BUCKETS_MS.get(i).copied().unwrap_or(self.max_ms));
+            }
+        }
+        Some(self.max_ms)
+    }
+
+    fn summary(&self, name: &str) -> LatencySummary {
+        LatencySummary {
+            name: name.to_string(),
+            count: self.total,
+            slow: self.slow,
+            avg_ms: if self.total == 0 { 0 } else { self.sum_ms / self.total },
+            p50_ms: self.quantile(0.5),
+            p95_ms: self.quantile(0.95),
+            p99_ms: self.quantile(0.99),
+            max_ms: self.max_ms,
+            buckets: BUCKETS_MS.iter()
+                .map(|bound| Some(*bound))
+                .chain(std::iter::once(None))
+                .zip(self.counts.iter())
+                .map(|(le_ms, count)| LatencyBucket { le_ms, count: *count })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LatencyBucket {
+    // None for the overflow bucket
+    pub le_ms: Option<u64>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LatencySummary {
+    pub name: String,
+    pub count: u64,
+    // Runs over the configured slow threshold
+    pub slow: u64,
+    pub avg_ms: u64,
+    // Percentiles are bucket upper bounds, so they're approximate
+    pub p50_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+    pub p99_ms: Option<u64>,
+    pub max_ms: u64,
+    pub buckets: Vec<LatencyBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CommandMetrics {
+    // Every chat message through the message handler, commands included
+    pub messages: LatencySummary,
+    // Slowest first by p95
+    pub commands: Vec<LatencySummary>,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    messages: Histogram,
+    commands: HashMap<String, Histogram>,
+}
+
+// Latency histograms since startup, owned by TwitchManager
+#[derive(Default)]
+pub struct CommandLatency {
+    state: Mutex<MetricsState>,
+}
+
+fn millis(elapsed: Duration) -> u64 {
+    elapsed.as_millis().min(u64::MAX as u128) as u64
+}
+
+impl CommandLatency {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_command(&self, command: &str, elapsed: Duration, slow: bool) {
+        self.state.lock().unwrap().commands.entry(command.to_string()).or_default().observe(millis(elapsed), slow);
+    }
+
+    pub fn record_message(&self, elapsed: Duration, slow: bool) {
+        self.state.lock().unwrap().messages.observe(millis(elapsed), slow);
+    }
+
+    pub fn snapshot(&self) -> CommandMetrics {
+        let state = self.state.lock().unwrap();
+        let mut commands: Vec<LatencySummary> = state.commands.iter()
+            .map(|(name, histogram)| histogram.summary(name))
+            .collect();
+        commands.sort_by(|a, b| b.p95_ms.cmp(&a.p95_ms).then(b.max_ms.cmp(&a.max_ms)));
+        CommandMetrics {
+            messages: state.messages.summary("messages"),
+            commands,
+        }
+    }
+}
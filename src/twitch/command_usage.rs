@@ -0,0 +1,131 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+use schemars::JsonSchema;
+use serde::Serialize;
+use crate::twitch::irc::command_system::CommandContext;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Ok,
+    Error,
+    // The user didn't have the role or tier for the command, or it was cooling down
+    Denied,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Ok => "ok",
+            Outcome::Error => "error",
+            Outcome::Denied => "denied",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SoftBan {
+    pub user_id: String,
+    pub user_name: String,
+    pub remaining_secs: u64,
+}
+
+#[derive(Default)]
+struct AbuseState {
+    // Recent failed or denied commands per user id
+    failures: HashMap<String, VecDeque<Instant>>,
+    // user id -> (user name, banned until)
+    soft_bans: HashMap<String, (String, Instant)>,
+}
+
+// Failed commands and soft-bans, owned by TwitchManager
+#[derive(Default)]
+pub struct CommandAbuse {
+    state: Mutex<AbuseState>,
+}
+
+impl CommandAbuse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Time left on the user's soft-ban, if they have one
+    pub fn soft_ban_remaining(&self, user_id: &str) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+        let until = state.soft_bans.get(user_id)?.1;
+        match until.checked_duration_since(Instant::now()) {
+            Some(remaining) => Some(remaining),
+            None => {
+                state.soft_bans.remove(user_id);
+                None
+            }
+        }
+    }
+
+    pub fn soft_bans(&self) -> Vec<SoftBan> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        state.soft_bans.retain(|_, (_, until)| *until > now);
+        state.soft_bans.iter()
+            .map(|(user_id, (user_name, until))| SoftBan {
+                user_id: user_id.clone(),
+                user_name: user_name.clone(),
+                remaining_secs: until.duration_since(now).as_secs(),
+            })
+            .collect()
+    }
+
+    pub fn lift_soft_ban(&self, user_id: &str) -> bool {
+        let mut state = self.state.lock().unwrap();
+        state.failures.remove(user_id);
+        state.soft_bans.remove(user_id).is_some()
+    }
+
+    // Returns true when this failure pushed the user over the limit
+    fn record_failure(&self, user_id: &str, user_name: &str, max_failures: usize, window: Duration, ban: Duration) -> bool {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let failures = state.failures.entry(user_id.to_string()).or_default();
+        failures.push_back(now);
+        while failures.front().map_or(false, |t| now.duration_since(*t) > window) {
+            failures.pop_front();
+        }
+        if failures.len() < max_failures.max(1) {
+            return false;
+        }
+
+        state.failures.remove(user_id);
+        state.soft_bans.insert(user_id.to_string(), (user_name.to_string(), now + ban));
+        true
+    }
+}
+
+// Logs the invocation for the usage report and soft-bans users who keep failing commands
+pub async fn record(ctx: &CommandContext, command: &str, outcome: Outcome, exempt: bool) {
+    let sender = &ctx.msg.sender;
+    if let Err(e) = ctx.storage.read().await.log_command_usage(command, &sender.id, &sender.login, outcome.as_str()) {
+        error!("Failed to log usage of {}: {:?}", command, e);
+    }
+    if outcome == Outcome::Ok || exempt {
+        return;
+    }
+
+    let (enabled, max_failures, window_secs, soft_ban_secs) = {
+        let config = ctx.config.read().await;
+        let abuse = &config.command_abuse;
+        (abuse.enabled, abuse.max_failures, abuse.window_secs, abuse.soft_ban_secs)
+    };
+    if !enabled || !ctx.twitch_manager.command_abuse.record_failure(&sender.id, &sender.login, max_failures, Duration::from_secs(window_secs), Duration::from_secs(soft_ban_secs)) {
+        return;
+    }
+
+    info!("Soft-banned {} from commands for {}s after {} failed commands", sender.login, soft_ban_secs, max_failures);
+    let message = format!("@{}, slow down! Commands are off for you for {} minutes.", sender.name, (soft_ban_secs + 59) / 60);
+    if let Err(e) = ctx.bot_client.send_message(&ctx.channel, &message).await {
+        error!("Failed to announce command soft-ban: {}", e);
+    }
+    ctx.twitch_manager.irc_manager.get_dashboard_state().write().await
+        .add_notification(format!("{} was soft-banned from commands for spamming failing commands", sender.login))
+        .await;
+}
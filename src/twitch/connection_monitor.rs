@@ -1,7 +1,18 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
 use chrono::{DateTime, Utc, Duration};
-use log::{info, warn};
+use tracing::{error, info, warn};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serenity::model::id::UserId;
+use tokio::sync::RwLock;
+use crate::config::Config;
+use crate::discord::DiscordClient;
+use crate::notifications::{Notifier, NotifyEvent};
+use crate::web_ui::websocket::DashboardState;
 
 pub struct ConnectionMonitor {
+    name: String,
     last_connected: Option<DateTime<Utc>>,
     last_message_received: Option<DateTime<Utc>>,
     pub(crate) disconnection_count: u32,
@@ -16,8 +27,9 @@ pub enum ConnectionState {
 }
 
 impl ConnectionMonitor {
-    pub fn new() -> Self {
+    pub fn new(name: &str) -> Self {
         Self {
+            name: name.to_string(),
             last_connected: None,
             last_message_received: None,
             disconnection_count: 0,
@@ -33,8 +45,9 @@ impl ConnectionMonitor {
         }
         self.last_connected = Some(now);
         self.connection_state = ConnectionState::Connected;
-        info!("Twitch IRC connected. Total uptime: {}, Disconnection count: {}",
-              format_duration(self.total_uptime), self.disconnection_count);
+        report_connected(&self.name);
+        info!("{} connected. Total uptime: {}, Disconnection count: {}",
+              self.name, format_duration(self.total_uptime), self.disconnection_count);
     }
 
     pub fn on_disconnect(&mut self) {
@@ -43,8 +56,9 @@ impl ConnectionMonitor {
             self.total_uptime = self.total_uptime + (Utc::now() - last);
         }
         self.connection_state = ConnectionState::Disconnected;
-        warn!("Twitch IRC disconnected. Total uptime: {}, Disconnection count: {}",
-              format_duration(self.total_uptime), self.disconnection_count);
+        report_disconnected(&self.name);
+        warn!("{} disconnected. Total uptime: {}, Disconnection count: {}",
+              self.name, format_duration(self.total_uptime), self.disconnection_count);
     }
 
     pub fn on_message_received(&mut self) {
@@ -53,7 +67,7 @@ impl ConnectionMonitor {
 
     pub fn start_reconnecting(&mut self) {
         self.connection_state = ConnectionState::Reconnecting;
-        warn!("Starting reconnection process for Twitch IRC");
+        warn!("Starting reconnection process for {}", self.name);
     }
 
     pub fn is_connection_stale(&self, timeout: Duration) -> bool {
@@ -64,10 +78,184 @@ impl ConnectionMonitor {
     }
 }
 
+// Shared view over every connection the bot keeps open (IRC, EventSub, OBS,
+// VRChat), keyed by a display name such as "OBS (Main)". Only real
+// transitions count, so repeated connect/disconnect reports are harmless.
+#[derive(Default)]
+struct ConnectionHistory {
+    tracking_since: Option<DateTime<Utc>>,
+    connected_since: Option<DateTime<Utc>>,
+    uptime: Duration,
+    disconnects: u32,
+    recent_disconnects: VecDeque<DateTime<Utc>>,
+    last_alert: Option<DateTime<Utc>>,
+}
+
+impl ConnectionHistory {
+    fn uptime_at(&self, now: DateTime<Utc>) -> Duration {
+        self.uptime + self.connected_since.map_or(Duration::zero(), |since| now - since)
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        while self.recent_disconnects.front().map_or(false, |&t| now - t > Duration::hours(1)) {
+            self.recent_disconnects.pop_front();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ConnectionStats {
+    pub name: String,
+    pub connected: bool,
+    // Percentage of the time since the connection was first seen
+    pub availability: f64,
+    pub uptime_secs: i64,
+    pub downtime_secs: i64,
+    pub disconnects: u32,
+    pub disconnects_last_hour: usize,
+}
+
+#[derive(Clone)]
+pub struct AlertTargets {
+    pub config: Arc<RwLock<Config>>,
+    pub dashboard_state: Arc<RwLock<DashboardState>>,
+    pub discord_client: Option<Arc<DiscordClient>>,
+    pub notifier: Arc<Notifier>,
+}
+
+// Owned by TwitchManager and handed to the OBS and VRChat sides. Without alert targets flapping is
+// only logged.
+#[derive(Default)]
+pub struct ConnectionHealth {
+    connections: Mutex<HashMap<String, ConnectionHistory>>,
+    alert_targets: Option<AlertTargets>,
+}
+
+impl ConnectionHealth {
+    pub fn new(alert_targets: Option<AlertTargets>) -> Self {
+        Self {
+            connections: Mutex::new(HashMap::new()),
+            alert_targets,
+        }
+    }
+
+    pub fn report_connected(&self, name: &str) {
+        let now = Utc::now();
+        let mut connections = self.connections.lock().unwrap();
+        let history = connections.entry(name.to_string()).or_default();
+        history.tracking_since.get_or_insert(now);
+        if history.connected_since.is_none() {
+            history.connected_since = Some(now);
+        }
+    }
+
+    pub fn report_disconnected(self: &Arc<Self>, name: &str) {
+        let now = Utc::now();
+        let flaps = {
+            let mut connections = self.connections.lock().unwrap();
+            let history = connections.entry(name.to_string()).or_default();
+            history.tracking_since.get_or_insert(now);
+            let since = match history.connected_since.take() {
+                Some(since) => since,
+                None => return,
+            };
+            history.uptime = history.uptime + (now - since);
+            history.disconnects += 1;
+            history.recent_disconnects.push_back(now);
+            history.prune(now);
+            history.recent_disconnects.len()
+        };
+
+        if self.alert_targets.is_some() {
+            let health = self.clone();
+            let name = name.to_string();
+            tokio::spawn(async move { health.alert_if_flapping(name, flaps).await });
+        }
+    }
+
+    pub fn connection_stats(&self) -> Vec<ConnectionStats> {
+        let now = Utc::now();
+        let mut connections = self.connections.lock().unwrap();
+        let mut stats: Vec<ConnectionStats> = connections.iter_mut()
+            .map(|(name, history)| {
+                history.prune(now);
+                let tracked = history.tracking_since.map_or(Duration::zero(), |since| now - since);
+                let uptime = history.uptime_at(now);
+                let availability = if tracked.num_milliseconds() > 0 {
+                    uptime.num_milliseconds() as f64 / tracked.num_milliseconds() as f64 * 100.0
+                } else {
+                    100.0
+                };
+                ConnectionStats {
+                    name: name.clone(),
+                    connected: history.connected_since.is_some(),
+                    availability: availability.min(100.0),
+                    uptime_secs: uptime.num_seconds(),
+                    downtime_secs: (tracked - uptime).num_seconds().max(0),
+                    disconnects: history.disconnects,
+                    disconnects_last_hour: history.recent_disconnects.len(),
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| a.name.cmp(&b.name));
+        stats
+    }
+
+    async fn alert_if_flapping(&self, name: String, flaps: usize) {
+        let targets = match &self.alert_targets {
+            Some(targets) => targets,
+            None => return,
+        };
+        let alerts = targets.config.read().await.connection_alerts.clone();
+        // Forwarded notifications use the same threshold, even with the other alerts turned off
+        let forwarding = targets.notifier.wants(NotifyEvent::SubsystemFailing).await;
+        if (!alerts.enabled && !forwarding) || flaps < alerts.flaps_per_hour as usize {
+            return;
+        }
+
+        // One alert per connection per hour is plenty
+        {
+            let now = Utc::now();
+            let mut connections = self.connections.lock().unwrap();
+            let history = connections.entry(name.clone()).or_default();
+            if history.last_alert.map_or(false, |last| now - last < Duration::hours(1)) {
+                return;
+            }
+            history.last_alert = Some(now);
+        }
+
+        let message = format!("{} has disconnected {} times in the last hour", name, flaps);
+        warn!("Connection flapping: {}", message);
+
+        if forwarding {
+            targets.notifier.forward(NotifyEvent::SubsystemFailing, format!("{} is failing", name), message.clone());
+        }
+        if !alerts.enabled {
+            return;
+        }
+
+        if alerts.dashboard {
+            targets.dashboard_state.write().await.add_notification(message.clone()).await;
+        }
+
+        let user_id = alerts.discord_user_id.as_ref().and_then(|id| id.parse::<u64>().ok());
+        if let (Some(discord_client), Some(user_id)) = (&targets.discord_client, user_id) {
+            let http = discord_client.get_http().await;
+            let result = match UserId::new(user_id).create_dm_channel(&http).await {
+                Ok(channel) => channel.id.say(&http, format!("⚠️ {}", message)).await.map(|_| ()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                error!("Failed to send connection alert DM: {}", e);
+            }
+        }
+    }
+}
+
 fn format_duration(duration: Duration) -> String {
     let seconds = duration.num_seconds();
     let hours = seconds / 3600;
     let minutes = (seconds % 3600) / 60;
     let seconds = seconds % 60;
     format!("{}h {}m {}s", hours, minutes, seconds)
-}
\ No newline at end of file
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use crate::twitch::api::TwitchAPIClient;
+
+// The settings form only has room for this many
+pub const MAX_COSTREAMS: usize = 4;
+
+// Helix is asked again at most this often, however many dashboards and !team calls there are
+const CACHE_FOR: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CoStream {
+    pub login: String,
+    pub display_name: String,
+    pub live: bool,
+    pub title: Option<String>,
+    pub game: Option<String>,
+    pub viewers: Option<u64>,
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+// Recent statuses, owned by TwitchManager and shared with the dashboard routes
+#[derive(Debug, Default)]
+pub struct CoStreamCache {
+    // Keyed by the list of logins, so the co-streams panel and the raid train don't evict each other
+    cache: Mutex<HashMap<Vec<String>, (Instant, Vec<CoStream>)>>,
+}
+
+// Entries in additional_streams can be a login or a pasted channel URL
+pub fn normalize_login(entry: &str) -> Option<String> {
+    let entry = entry.trim().trim_end_matches('/');
+    let login = entry.rsplit('/').next().unwrap_or(entry).trim_start_matches('@').to_lowercase();
+    let valid = !login.is_empty() && login.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some(login)
+}
+
+// The configured co-streamers, cleaned up and without blanks or duplicates
+pub fn logins(additional_streams: &[String]) -> Vec<String> {
+    let mut logins: Vec<String> = Vec::new();
+    for login in additional_streams.iter().filter_map(|entry| normalize_login(entry)) {
+        if !logins.contains(&login) {
+            logins.push(login);
+        }
+    }
+    logins
+}
+
+impl CoStreamCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Drops the cached statuses, e.g. after the list of channels changed
+    pub fn invalidate(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    // Live status of each channel in the list, in the given order
+    pub async fn statuses(&self, api_client: &TwitchAPIClient, additional_streams: &[String]) -> Result<Vec<CoStream>, Box<dyn std::error::Error + Send + Sync>> {
+        let logins = logins(additional_streams);
+        if let Some((fetched_at, streams)) = self.cache.lock().unwrap().get(&logins) {
+            if fetched_at.elapsed() < CACHE_FOR {
+                return Ok(streams.clone());
+            }
+        }
+        if logins.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = api_client.get_streams_by_logins(&logins).await?;
+        let live = response["data"].as_array().cloned().unwrap_or_default();
+        let streams: Vec<CoStream> = logins.iter()
+            .map(|login| match live.iter().find(|s| s["user_login"].as_str().map_or(false, |l| l.eq_ignore_ascii_case(login))) {
+                Some(stream) => CoStream {
+                    login: login.clone(),
+                    display_name: stream["user_name"].as_str().unwrap_or(login).to_string(),
+                    live: true,
+                    title: stream["title"].as_str().map(str::to_string),
+                    game: stream["game_name"].as_str().filter(|g| !g.is_empty()).map(str::to_string),
+                    viewers: stream["viewer_count"].as_u64(),
+                    started_at: stream["started_at"].as_str().and_then(|s| s.parse().ok()),
+                },
+                None => CoStream {
+                    login: login.clone(),
+                    display_name: login.clone(),
+                    live: false,
+                    title: None,
+                    game: None,
+                    viewers: None,
+                    started_at: None,
+                },
+            })
+            .collect();
+
+        self.cache.lock().unwrap().insert(logins, (Instant::now(), streams.clone()));
+        Ok(streams)
+    }
+}
@@ -0,0 +1,92 @@
+use tracing::error;
+use crate::twitch::irc::command_system::{CommandContext, CommandRegistry};
+use crate::twitch::stream_breaks::format_duration;
+use crate::twitch::utils::get_stream_uptime;
+
+const MAX_NAME_LENGTH: usize = 32;
+const MAX_RESPONSE_LENGTH: usize = 400;
+// Unless the dashboard sets one, so a popular command can't flood chat
+pub const DEFAULT_COOLDOWN_SECS: u64 = 5;
+
+// "hello" or "!Hello" -> "!hello"
+pub fn normalize_name(name: &str) -> String {
+    format!("!{}", name.trim().trim_start_matches('!').to_lowercase())
+}
+
+// Checks a new command's name; Err is the reason to give back
+pub fn check_name(commands: &CommandRegistry, name: &str) -> Result<(), String> {
+    let bare = name.trim_start_matches('!');
+    if bare.is_empty() || bare.len() > MAX_NAME_LENGTH || !bare.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(format!("Command names can only use letters, numbers, - and _ (up to {} characters).", MAX_NAME_LENGTH));
+    }
+    if commands.describe().iter().any(|command| command.name.eq_ignore_ascii_case(name)) {
+        return Err(format!("{} is a built-in command.", name));
+    }
+    Ok(())
+}
+
+pub fn check_response(response: &str) -> Result<(), String> {
+    let response = response.trim();
+    if response.is_empty() {
+        return Err("The response can't be empty.".to_string());
+    }
+    if response.chars().count() > MAX_RESPONSE_LENGTH {
+        return Err(format!("Responses are limited to {} characters.", MAX_RESPONSE_LENGTH));
+    }
+    // Viewers would be running chat commands like /ban through the bot
+    if response.starts_with('/') || response.starts_with('.') {
+        return Err("Responses can't start with / or .".to_string());
+    }
+    Ok(())
+}
+
+// Fills in the variables:
+//   $(user)    whoever used the command        $(touser)  the first argument, or the user
+//   $(args)    everything after the command    $(count)   how many times it's been used
+//   $(uptime)  how long the stream's been live $(game), $(title)  the channel's category and title
+//   $(channel) the channel name
+pub async fn render(ctx: &CommandContext, response: &str, args: &[String], count: i64) -> String {
+    let touser = args.first().map(|arg| arg.trim_start_matches('@').to_string()).unwrap_or_else(|| ctx.msg.sender.name.clone());
+    let mut text = response
+        .replace("$(user)", &ctx.msg.sender.name)
+        .replace("$(touser)", &touser)
+        .replace("$(args)", &args.join(" "))
+        .replace("$(count)", &count.to_string())
+        .replace("$(channel)", &ctx.channel);
+
+    if text.contains("$(uptime)") {
+        let uptime = match get_stream_uptime(&ctx.channel, ctx.twitch_manager.get_api_client()).await {
+            Ok(Some(duration)) => format_duration(duration.num_seconds()),
+            Ok(None) => "offline".to_string(),
+            Err(e) => {
+                error!("Failed to get the uptime for a custom command: {:?}", e);
+                "unknown".to_string()
+            }
+        };
+        text = text.replace("$(uptime)", &uptime);
+    }
+    if text.contains("$(game)") || text.contains("$(title)") {
+        let (game, title) = match channel_info(ctx).await {
+            Ok(info) => info,
+            Err(e) => {
+                error!("Failed to get channel info for a custom command: {:?}", e);
+                ("unknown".to_string(), "unknown".to_string())
+            }
+        };
+        text = text.replace("$(game)", &game).replace("$(title)", &title);
+    }
+
+    // $(args) and $(touser) come from chat, so check the start again
+    text.trim_start_matches(|c: char| c == '/' || c == '.' || c.is_whitespace()).to_string()
+}
+
+async fn channel_info(ctx: &CommandContext) -> Result<(String, String), Box<dyn std::error::Error + Send + Sync>> {
+    let api_client = ctx.twitch_manager.get_api_client();
+    let broadcaster_id = api_client.get_broadcaster_id().await?;
+    let info = api_client.get_channel_information(&broadcaster_id).await?;
+    let channel = &info["data"][0];
+    Ok((
+        channel["game_name"].as_str().unwrap_or_default().to_string(),
+        channel["title"].as_str().unwrap_or_default().to_string(),
+    ))
+}
@@ -15,12 +15,14 @@ use futures_util::SinkExt;
 use serde::ser::StdError;
 use std::fmt;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use log::{debug, error, info, trace, warn};
+use tracing::{debug, error, info, trace, warn, Instrument};
 use tungstenite::protocol::CloseFrame;
 use crate::osc::models::OSCConfig;
-use crate::osc::osc_config::OSCConfigurations;
+use crate::osc::osc_config::{osc_config_path, OSCConfigurations};
 
 type BoxedError = Box<dyn StdError + Send + Sync>;
+
+const EVENTSUB_CONNECTION: &str = "Twitch EventSub";
 type WebSocketTx = SplitSink<WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>, Message>;
 type WebSocketRx = SplitStream<WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>>;
 
@@ -112,9 +114,11 @@ impl TwitchEventSubClient {
                 Ok(()) => {
                     self.reconnect_attempts.store(0, Ordering::SeqCst);
                     self.consecutive_keepalive_failures.store(0, Ordering::SeqCst);
+                    self.twitch_manager.connections.report_connected(EVENTSUB_CONNECTION);
                     if let Err(e) = self.listen_for_messages().await {
                         error!("Error in message handling: {:?}", e);
                     }
+                    self.twitch_manager.connections.report_disconnected(EVENTSUB_CONNECTION);
                     warn!("Connection lost. Attempting to reconnect...");
                 }
                 Err(e) => {
@@ -306,7 +310,14 @@ impl TwitchEventSubClient {
         info!("Received notification: {:?}", response);
 
         let message = serde_json::to_string(response)?;
-        if let Err(e) = handlers::handle_message(&message, &self.twitch_manager).await {
+        // The message id doubles as the request id when following one notification through the logs
+        let span = tracing::info_span!(
+            "eventsub",
+            event = response["metadata"]["subscription_type"].as_str().unwrap_or(""),
+            request_id = response["metadata"]["message_id"].as_str().unwrap_or(""),
+            user = response["payload"]["event"]["user_login"].as_str().unwrap_or(""),
+        );
+        if let Err(e) = handlers::handle_message(&message, &self.twitch_manager).instrument(span).await {
             error!("Error handling EventSub message: {:?}", e);
         }
 
@@ -384,6 +395,12 @@ impl TwitchEventSubClient {
         })),
             ("channel.cheer", "1", json!({
                 "broadcaster_user_id": channel_id
+        })),
+            ("channel.ban", "1", json!({
+            "broadcaster_user_id": channel_id
+        })),
+            ("channel.unban", "1", json!({
+            "broadcaster_user_id": channel_id
         })),
             ("channel.channel_points_custom_reward_redemption.add", "1", json!({
             "broadcaster_user_id": channel_id
@@ -394,6 +411,20 @@ impl TwitchEventSubClient {
             ("channel.ad_break.begin", "1", json!({ // New subscription
             "broadcaster_user_id": channel_id
         })),
+            ("channel.charity_campaign.donate", "1", json!({
+            "broadcaster_user_id": channel_id
+        })),
+            ("channel.charity_campaign.start", "1", json!({
+            "broadcaster_user_id": channel_id
+        })),
+            ("channel.charity_campaign.progress", "1", json!({
+            "broadcaster_user_id": channel_id
+        })),
+            ("channel.charity_campaign.stop", "1", json!({
+            "broadcaster_user_id": channel_id
+        })),
+            // drop.entitlement.grant isn't here: Twitch only delivers it over webhooks, to the
+            // organization that owns the game, so a channel bot can't subscribe to it
         ];
 
         for (subscription_type, version, condition) in subscriptions {
@@ -436,6 +467,8 @@ impl TwitchEventSubClient {
     pub async fn add_osc_config(&self, event_type: String, config: OSCConfig) {
         let mut configs = self.osc_configs.write().await;
         configs.add_config(&event_type, config);
-        configs.save("osc_config.json").unwrap_or_else(|e| eprintln!("Failed to save OSC configs: {}", e));
+        if let Err(e) = configs.save(&osc_config_path()) {
+            error!("Failed to save OSC configs: {}", e);
+        }
     }
 }
\ No newline at end of file
@@ -1,7 +1,11 @@
 use serde_json::Value;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use tracing::{debug, error};
+use tokio::time::{sleep, Duration, Instant};
 use crate::twitch::TwitchManager;
 use crate::ai::AIClient;
+use crate::osc::{OSCMessageType, OSCValue};
 
 pub async fn handle_ad_break_begin(
     event: &Value,
@@ -9,8 +13,13 @@ pub async fn handle_ad_break_begin(
     twitch_manager: &Arc<TwitchManager>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     if let Some(payload) = event.get("payload").and_then(|p| p.get("event")) {
-        let duration_seconds = payload["duration_seconds"].as_str().unwrap_or("0").parse::<u32>().unwrap_or(0);
-        let is_automatic = payload["is_automatic"].as_str().unwrap_or("false") == "true";
+        let duration_seconds = payload["duration_seconds"].as_u64()
+            .or_else(|| payload["duration_seconds"].as_str().and_then(|d| d.parse().ok()))
+            .unwrap_or(0) as u32;
+        let is_automatic = payload["is_automatic"].as_bool()
+            .unwrap_or_else(|| payload["is_automatic"].as_str() == Some("true"));
+
+        start_ad_countdown(twitch_manager.clone(), duration_seconds);
 
         // Generate a friendly AI message
         let ai_client = AIClient::new(None, None, None, None, None, None); // You might want to pass actual API keys here
@@ -27,8 +36,62 @@ pub async fn handle_ad_break_begin(
             duration_seconds, ai_message
         );
 
-        twitch_manager.send_message_as_bot(channel, &message).await?;
+        twitch_manager.send_long_message_as_bot(channel, &message).await?;
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// Keeps in-VR streamers informed: holds the AdBreak avatar parameter on and
+// counts down in the chatbox until the ads are over.
+fn start_ad_countdown(twitch_manager: Arc<TwitchManager>, duration_seconds: u32) {
+    if duration_seconds == 0 {
+        return;
+    }
+
+    // Bumped on every ad break so an older countdown stops once a newer one starts
+    let generation = twitch_manager.ad_break_generation.fetch_add(1, Ordering::SeqCst) + 1;
+    let current_generation = twitch_manager.ad_break_generation.clone();
+    let config = twitch_manager.config.ad_break.clone();
+    let osc_manager = twitch_manager.get_osc_manager();
+    let vrchat_osc = osc_manager.get_vrchat_osc();
+
+    twitch_manager.tasks.spawn("ad_break_countdown", async move {
+        if let Err(e) = osc_manager.send_osc_message(&config.avatar_parameter, &OSCMessageType::Boolean, &OSCValue::Boolean(true)).await {
+            error!("Failed to set ad break avatar parameter: {}", e);
+        }
+
+        let ends_at = Instant::now() + Duration::from_secs(duration_seconds as u64);
+        let interval = Duration::from_secs(config.update_interval_secs.max(1));
+
+        loop {
+            if current_generation.load(Ordering::SeqCst) != generation {
+                debug!("Ad break countdown superseded by a newer ad break");
+                return;
+            }
+
+            let remaining = ends_at.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+
+            if config.chatbox_countdown {
+                let message = format!("Ads: {}s remaining", remaining.as_secs_f32().ceil() as u64);
+                if let Err(e) = vrchat_osc.send_chatbox_message(&message, true, false).await {
+                    error!("Failed to update ad break chatbox countdown: {}", e);
+                }
+            }
+
+            sleep(interval.min(remaining)).await;
+        }
+
+        if let Err(e) = osc_manager.send_osc_message(&config.avatar_parameter, &OSCMessageType::Boolean, &OSCValue::Boolean(false)).await {
+            error!("Failed to reset ad break avatar parameter: {}", e);
+        }
+        if config.chatbox_countdown {
+            if let Err(e) = vrchat_osc.send_chatbox_message("Ads are over, we're back!", true, false).await {
+                error!("Failed to send ad break end chatbox message: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,106 @@
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{error, info};
+use serenity::model::id::ChannelId;
+use crate::twitch::TwitchManager;
+
+pub async fn handle_ban(
+    event: &Value,
+    _channel: &str,
+    twitch_manager: &Arc<TwitchManager>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(payload) = event.get("payload").and_then(|p| p.get("event")) {
+        let is_permanent = payload["is_permanent"].as_bool().unwrap_or(true);
+        let event_type = if is_permanent { "ban" } else { "timeout" };
+        let reason = payload["reason"].as_str();
+        let ends_at = payload["ends_at"].as_str();
+
+        info!("{} {} by {}: {:?}",
+            event_type,
+            payload["user_name"].as_str().unwrap_or("Unknown"),
+            payload["moderator_user_name"].as_str().unwrap_or("Unknown"),
+            reason
+        );
+
+        record(payload, event_type, reason, ends_at, twitch_manager).await;
+
+        if is_permanent && twitch_manager.config.ignore_list.ignore_banned {
+            if let (Some(storage), Some(user_login)) = (twitch_manager.get_storage(), payload["user_login"].as_str()) {
+                if let Err(e) = storage.read().await.add_ignored_user(user_login, payload["user_id"].as_str(), "banned") {
+                    error!("Failed to add banned user to the ignore list: {:?}", e);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn handle_unban(
+    event: &Value,
+    _channel: &str,
+    twitch_manager: &Arc<TwitchManager>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(payload) = event.get("payload").and_then(|p| p.get("event")) {
+        info!("unban {} by {}",
+            payload["user_name"].as_str().unwrap_or("Unknown"),
+            payload["moderator_user_name"].as_str().unwrap_or("Unknown")
+        );
+
+        record(payload, "unban", None, None, twitch_manager).await;
+
+        if let Some(storage) = twitch_manager.get_storage() {
+            if let Err(e) = storage.read().await.remove_banned_ignored_user(payload["user_id"].as_str().unwrap_or("")) {
+                error!("Failed to remove unbanned user from the ignore list: {:?}", e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn record(
+    payload: &Value,
+    event_type: &str,
+    reason: Option<&str>,
+    ends_at: Option<&str>,
+    twitch_manager: &Arc<TwitchManager>,
+) {
+    let user_id = payload["user_id"].as_str().unwrap_or("");
+    let user_name = payload["user_name"].as_str().unwrap_or("Unknown");
+    let moderator_id = payload["moderator_user_id"].as_str().unwrap_or("");
+    let moderator_name = payload["moderator_user_name"].as_str().unwrap_or("Unknown");
+
+    if let Some(storage) = twitch_manager.get_storage() {
+        if let Err(e) = storage.read().await.add_moderation_event(
+            event_type,
+            user_id,
+            user_name,
+            moderator_id,
+            moderator_name,
+            reason,
+            ends_at,
+        ) {
+            error!("Failed to store moderation event: {:?}", e);
+        }
+    }
+
+    if let Some(discord_client) = &twitch_manager.discord_client {
+        if let Some(modlog_channel_id) = &twitch_manager.config.discord_modlog_channel_id {
+            if let Ok(channel_id) = modlog_channel_id.parse::<u64>() {
+                let http = discord_client.get_http().await;
+                if let Err(e) = crate::discord::announcements::send_modlog_entry(
+                    &http,
+                    ChannelId::new(channel_id),
+                    event_type,
+                    user_name,
+                    moderator_name,
+                    reason,
+                    ends_at,
+                ).await {
+                    error!("Failed to mirror moderation event to Discord: {}", e);
+                }
+            }
+        }
+    }
+}
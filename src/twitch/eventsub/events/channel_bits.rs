@@ -1,8 +1,11 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::{error, info, debug, warn};
+use tracing::{error, info, debug, warn};
 use crate::twitch::TwitchManager;
+use crate::config::AlertEventType;
+use crate::twitch::alerts::{self, AlertEvent};
 use crate::osc::models::{OSCConfig, OSCMessageType, OSCValue};
+use crate::twitch::bits_vote;
 
 pub async fn handle(
     event: &Value,
@@ -23,6 +26,16 @@ pub async fn handle(
 
         debug!("Received bits event: {} bits from {}", bits_used, user_name);
 
+        let cheer_message = payload["message"].get("text").and_then(Value::as_str).unwrap_or("");
+        if let Some(summary) = twitch_manager.bits_votes.cheer(cheer_message, bits_used) {
+            bits_vote::publish(twitch_manager, &summary).await;
+        }
+
+        let alert = AlertEvent::new(AlertEventType::Cheer, user_name, bits_used).with_message(cheer_message);
+        if alerts::dispatch(twitch_manager, channel, &alert).await {
+            return Ok(());
+        }
+
         // Determine which OSC action to use based on the number of bits
         let osc_value = match bits_used {
             1..=99 => 14,
@@ -64,7 +77,7 @@ pub async fn handle(
         }
 
         // Send chat message
-        let message = format!("Thank you {} for the {} bits!", user_name, bits_used);
+        let message = twitch_manager.bot_voice.render("bits", &[("user", user_name), ("amount", bits_used.to_string().as_str())]);
         if let Err(e) = twitch_manager.send_message_as_bot(channel, &message).await {
             error!("Failed to send thank you message to chat: {}", e);
         } else {
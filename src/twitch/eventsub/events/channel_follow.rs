@@ -1,8 +1,11 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::{info, error, warn};
+use tracing::{info, error, warn};
 use crate::twitch::api::TwitchAPIClient;
 use crate::twitch::TwitchManager;
+use crate::config::AlertEventType;
+use crate::twitch::alerts::{self, AlertEvent};
+use crate::twitch::milestones;
 
 async fn get_follower_stream_info(api_client: &TwitchAPIClient, user_id: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     let stream_info = api_client.get_stream_info(user_id).await?;
@@ -43,6 +46,23 @@ pub async fn handle(
         if let Some(user_name) = payload["user_name"].as_str() {
             if let Some(user_id) = payload["user_id"].as_str() {
                 info!("New follower: {}", user_name);
+                milestones::record_follow(twitch_manager, channel);
+
+                // Raid followers get one aggregate welcome when raid mode ends
+                let raid_mode = twitch_manager.get_raid_mode();
+                if raid_mode.is_active().await {
+                    raid_mode.record_suppressed_follow();
+                    return Ok(());
+                }
+
+                // A burst of follows outside a raid looks like follow-bots
+                if twitch_manager.get_follow_guard().on_follow(twitch_manager, channel).await {
+                    return Ok(());
+                }
+
+                if alerts::dispatch(twitch_manager, channel, &AlertEvent::new(AlertEventType::Follow, user_name, 1)).await {
+                    return Ok(());
+                }
 
                 let api_client = twitch_manager.get_api_client();
                 let follower_info = get_follower_stream_info(&api_client, user_id).await?;
@@ -64,18 +84,18 @@ pub async fn handle(
                         Ok(ai_response) => ai_response,
                         Err(e) => {
                             error!("Failed to generate AI response: {:?}", e);
-                            format!("Welcome to the community, {}! Thanks for following!", user_name)
+                            twitch_manager.bot_voice.render("follow", &[("user", user_name)])
                         }
                     }
                 } else {
                     warn!("AI client not available. Using default welcome message.");
-                    format!("Welcome to the community, {}! Thanks for following!", user_name)
+                    twitch_manager.bot_voice.render("follow", &[("user", user_name)])
                 };
 
                 // Append the follower count tag
                 welcome_message.push_str(&format!(" cat-like-entity#{}", follower_count));
 
-                twitch_manager.send_message_as_bot(channel, &welcome_message).await?;
+                twitch_manager.send_long_message_as_bot(channel, &welcome_message).await?;
             }
         }
     }
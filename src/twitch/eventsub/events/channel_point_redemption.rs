@@ -1,8 +1,12 @@
+use crate::notifications::NotifyEvent;
 use crate::twitch::manager::TwitchManager;
 use crate::twitch::models::{Redemption, RedemptionStatus};
+use crate::twitch::ignore_list;
+use crate::twitch::redeems::announcements::Outcome;
+use crate::twitch::redeems::pricing;
 use serde_json::Value;
 use std::sync::Arc;
-use log::{debug, error};
+use tracing::{debug, error};
 
 
 
@@ -15,17 +19,29 @@ pub async fn handle_new_redemption(
 
     debug!("Processing new redemption: {:?}", redemption);
 
+    if let Some(storage) = twitch_manager.get_storage() {
+        let storage = storage.read().await;
+        let user_login = event["user_login"].as_str().unwrap_or(&redemption.user_name);
+        // Ignored users can still redeem, they just don't show up on the leaderboards
+        if !ignore_list::is_ignored(&twitch_manager.config.ignore_list, &storage, &redemption.user_id, user_login) {
+            let cost = event["reward"]["cost"].as_u64().unwrap_or(0);
+            if let Err(e) = storage.log_redemption(&redemption.id, &redemption.user_id, &redemption.user_name, &redemption.reward_title, cost) {
+                error!("Failed to log redemption: {:?}", e);
+            }
+        }
+    }
+
     let redeem_manager = twitch_manager.get_redeem_manager();
     let redeem_manager = redeem_manager.read().await;
 
     if let Some(redeem_manager) = redeem_manager.as_ref() {
         let result = redeem_manager.handle_redemption(&redemption).await;
+        pricing::on_redemption(twitch_manager, redeem_manager, &redemption.reward_title).await;
+        let mut auto_completed = false;
 
         if result.success {
             debug!("Redemption handled successfully: {:?}", result);
-            if let Some(ref message) = result.message {
-                twitch_manager.send_message_as_bot(channel, &message).await?;
-            }
+            redeem_manager.announce(channel, &redemption, Outcome::Success, result.message.as_deref(), None).await;
 
             // Check if the redeem should be auto-completed
             let redeems = redeem_manager.registry.get_all().await;
@@ -37,10 +53,29 @@ pub async fn handle_new_redemption(
                         &redemption.reward_id,
                         &redemption.id,
                     ).await?;
+                    auto_completed = true;
                 }
             }
         } else {
             error!("Failed to handle redemption: {:?}", result);
+            redeem_manager.announce(channel, &redemption, Outcome::Failure, result.message.as_deref(), None).await;
+        }
+
+        // Anything left unfulfilled sits in the streamer's queue until it is resolved
+        if !auto_completed {
+            let queue = redeem_manager.get_queue();
+            let number = queue.push(redemption.clone()).await;
+            let position = queue.len().await;
+            redeem_manager.announce(channel, &redemption, Outcome::Queued, None, Some((number, position))).await;
+
+            let review_rewards = &twitch_manager.config.notification_forwarding.review_rewards;
+            if review_rewards.is_empty() || review_rewards.iter().any(|title| title.eq_ignore_ascii_case(&redemption.reward_title)) {
+                let body = match &redemption.user_input {
+                    Some(input) if !input.is_empty() => format!("{} redeemed it: {}", redemption.user_name, input),
+                    _ => format!("{} redeemed it", redemption.user_name),
+                };
+                twitch_manager.notifier.forward(NotifyEvent::RedeemReview, format!("{} needs review (#{})", redemption.reward_title, number), body);
+            }
         }
     } else {
         return Err("RedeemManager is not initialized".into());
@@ -51,6 +86,7 @@ pub async fn handle_new_redemption(
 
 pub async fn handle_redemption_update(
     event: &Value,
+    twitch_manager: &Arc<TwitchManager>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     debug!("Received channel point redemption update: {:?}", event);
 
@@ -63,18 +99,26 @@ pub async fn handle_redemption_update(
         },
         RedemptionStatus::Fulfilled => {
             debug!("Redemption has been fulfilled: {:?}", redemption);
+            remove_from_queue(twitch_manager, &redemption.id).await;
         },
         RedemptionStatus::Canceled => {
             debug!("Redemption canceled: {:?}", redemption);
-            // You might want to handle cancellation in your RedeemManager
-            // twitch_manager.redeem_manager.write().await.cancel_redemption(&redemption.id).await?;
+            remove_from_queue(twitch_manager, &redemption.id).await;
         },
     }
 
     Ok(())
 }
 
-fn parse_redemption(event: &Value) -> Redemption {
+async fn remove_from_queue(twitch_manager: &Arc<TwitchManager>, redemption_id: &str) {
+    let redeem_manager = twitch_manager.get_redeem_manager();
+    let redeem_manager = redeem_manager.read().await;
+    if let Some(redeem_manager) = redeem_manager.as_ref() {
+        redeem_manager.get_queue().remove(redemption_id).await;
+    }
+}
+
+pub(crate) fn parse_redemption(event: &Value) -> Redemption {
     Redemption {
         id: event["id"].as_str().unwrap_or("").to_string(),
         broadcaster_id: event["broadcaster_user_id"].as_str().unwrap_or("").to_string(),
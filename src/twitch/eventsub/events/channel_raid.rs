@@ -1,8 +1,12 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::{debug};
+use std::time::Duration;
+use tracing::{debug};
 use crate::twitch::api::requests::get_channel_game;
 use crate::twitch::TwitchManager;
+use crate::config::AlertEventType;
+use crate::twitch::alerts::{self, AlertEvent};
+use crate::notifications::NotifyEvent;
 
 pub async fn handle(
     event: &Value,
@@ -16,6 +20,27 @@ pub async fn handle(
         let api_client = twitch_manager.get_api_client();
         debug!("Raid received from: {} with {} viewers", from_broadcaster_user_name, viewers);
 
+        let raid_config = &twitch_manager.config.raid_mode;
+        if raid_config.enabled && viewers >= raid_config.min_viewers {
+            twitch_manager.get_raid_mode().activate(
+                twitch_manager.clone(),
+                channel.to_string(),
+                Duration::from_secs(raid_config.duration_secs),
+            ).await;
+        }
+
+        if viewers >= twitch_manager.config.notification_forwarding.raid_min_viewers {
+            twitch_manager.notifier.forward(
+                NotifyEvent::BigRaid,
+                format!("Raid from {}", from_broadcaster_user_name),
+                format!("{} is raiding with {} viewers", from_broadcaster_user_name, viewers),
+            );
+        }
+
+        if alerts::dispatch(twitch_manager, channel, &AlertEvent::new(AlertEventType::Raid, from_broadcaster_user_name, viewers)).await {
+            return Ok(());
+        }
+
         // Get the game they were playing
         let game = get_channel_game(from_broadcaster_user_id, &api_client).await?;
 
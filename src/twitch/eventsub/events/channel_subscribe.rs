@@ -1,7 +1,10 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::{error, debug, info};
+use tracing::{error, debug, info};
 use crate::twitch::TwitchManager;
+use crate::config::AlertEventType;
+use crate::twitch::alerts::{self, AlertEvent};
+use crate::twitch::milestones;
 use crate::osc::models::OSCConfig;
 use crate::osc::models::{OSCMessageType, OSCValue};
 
@@ -24,6 +27,13 @@ pub async fn handle(
 
         debug!("Received subscribe event: {} - {} (Gift: {})", user_name, tier_name, is_gift);
 
+        milestones::record_subs(twitch_manager, channel, 1).await;
+
+        let alert = AlertEvent::new(AlertEventType::Subscribe, user_name, 1).with_tier(tier);
+        if alerts::dispatch(twitch_manager, channel, &alert).await {
+            return Ok(());
+        }
+
         // Create OSC config for new subscribers
         let osc_config = OSCConfig {
             uses_osc: true,
@@ -53,9 +63,9 @@ pub async fn handle(
         }
 
         let message = if is_gift {
-            format!("{} received a gifted {} subscription! Thank you to the generous gifter!", user_name, tier_name)
+            twitch_manager.bot_voice.render("gift_received", &[("user", user_name), ("tier", tier_name)])
         } else {
-            format!("Thank you {} for subscribing with a {} subscription!", user_name, tier_name)
+            twitch_manager.bot_voice.render("subscribe", &[("user", user_name), ("tier", tier_name)])
         };
 
         if let Err(e) = twitch_manager.send_message_as_bot(channel, &message).await {
@@ -1,7 +1,9 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::{error, info, debug};
+use tracing::{error, info, debug};
 use crate::twitch::TwitchManager;
+use crate::config::AlertEventType;
+use crate::twitch::alerts::{self, AlertEvent};
 use crate::osc::models::{OSCConfig, OSCMessageType, OSCValue};
 
 pub async fn handle(
@@ -24,6 +26,11 @@ pub async fn handle(
 
         debug!("Received gift sub event: {} gifted {} {} subs (Total: {})", user_name, total, tier_name, cumulative_total);
 
+        let alert = AlertEvent::new(AlertEventType::GiftSub, user_name, total).with_tier(tier);
+        if alerts::dispatch(twitch_manager, channel, &alert).await {
+            return Ok(());
+        }
+
         // Create OSC config for gift subs
         let osc_config = OSCConfig {
             uses_osc: true,
@@ -52,10 +59,12 @@ pub async fn handle(
             Err(e) => error!("Failed to send OSC message for gift sub event: {}", e),
         }
 
-        let message = format!(
-            "WOW! {} just gifted {} {} subscriptions! They've gifted a total of {} subs in the channel!",
-            user_name, total, tier_name, cumulative_total
-        );
+        let message = twitch_manager.bot_voice.render("gift_subs", &[
+            ("user", user_name),
+            ("amount", total.to_string().as_str()),
+            ("tier", tier_name),
+            ("total", cumulative_total.to_string().as_str()),
+        ]);
 
         if let Err(e) = twitch_manager.send_message_as_bot(channel, &message).await {
             error!("Failed to send thank you message to chat: {}", e);
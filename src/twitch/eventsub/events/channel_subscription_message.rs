@@ -1,7 +1,10 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::{error, info, debug};
+use tracing::{error, info, debug};
 use crate::twitch::TwitchManager;
+use crate::config::AlertEventType;
+use crate::twitch::alerts::{self, AlertEvent};
+use crate::twitch::milestones;
 use crate::osc::models::{OSCConfig, OSCMessageType, OSCValue};
 
 pub async fn handle(
@@ -19,6 +22,16 @@ pub async fn handle(
 
         debug!("Received resub event: {} ({} months), message: '{}'", user_name, cumulative_months, message);
 
+        milestones::record_subs(twitch_manager, channel, 1).await;
+
+        let tier = payload["tier"].as_str().unwrap_or("1000");
+        let alert = AlertEvent::new(AlertEventType::Resub, user_name, cumulative_months)
+            .with_tier(tier)
+            .with_message(message);
+        if alerts::dispatch(twitch_manager, channel, &alert).await {
+            return Ok(());
+        }
+
         // Create OSC config for resubscribers
         let osc_config = OSCConfig {
             uses_osc: true,
@@ -48,9 +61,9 @@ pub async fn handle(
         }
 
         let response = if !message.is_empty() {
-            format!("Thank you {} for {} months of support! They said: {}", user_name, cumulative_months, message)
+            twitch_manager.bot_voice.render("resub_message", &[("user", user_name), ("months", cumulative_months.to_string().as_str()), ("message", message)])
         } else {
-            format!("Thank you {} for {} months of support!", user_name, cumulative_months)
+            twitch_manager.bot_voice.render("resub", &[("user", user_name), ("months", cumulative_months.to_string().as_str())])
         };
 
         if let Err(e) = twitch_manager.send_message_as_bot(channel, &response).await {
@@ -1,7 +1,8 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::info;
+use tracing::{error, info};
 use crate::twitch::TwitchManager;
+use crate::twitch::channel_updates::ChannelField;
 
 pub async fn handle(
     event: &Value,
@@ -12,13 +13,47 @@ pub async fn handle(
         if let (Some(title), Some(category_name)) = (payload["title"].as_str(), payload["category_name"].as_str()) {
             info!("Channel update event: Title: '{}', Category: '{}'", title, category_name);
 
-            let response = format!("Channel updated! Category: {} Title: {}", category_name, title);
+            let channel_updates = &twitch_manager.channel_updates;
+            let changes = channel_updates.apply(title, category_name).await;
 
-            twitch_manager.send_message_as_bot(channel, response.as_str()).await?;
+            // !game and !title already confirm their change in chat
+            if let Some(category) = &changes.category {
+                if !channel_updates.take_expected(ChannelField::Category, category).await {
+                    twitch_manager.send_message_as_bot(channel, &twitch_manager.bot_voice.render("now_playing", &[("category", category.as_str())])).await?;
+                }
+            }
+            if let Some(new_title) = &changes.title {
+                if !channel_updates.take_expected(ChannelField::Title, new_title).await {
+                    twitch_manager.send_message_as_bot(channel, &twitch_manager.bot_voice.render("new_title", &[("title", new_title.as_str())])).await?;
+                }
+            }
 
-            twitch_manager.handle_stream_update(category_name.to_string()).await?;
+            if changes.title.is_some() || changes.category.is_some() {
+                update_discord_announcement(twitch_manager, title, category_name).await;
+            }
+
+            if changes.category.is_some() {
+                twitch_manager.handle_stream_update(category_name.to_string()).await?;
+            }
         }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+async fn update_discord_announcement(twitch_manager: &Arc<TwitchManager>, title: &str, category_name: &str) {
+    let discord_client = match &twitch_manager.discord_client {
+        Some(client) => client,
+        None => return,
+    };
+
+    if let Some((channel_id, message_id, announcement)) = twitch_manager.channel_updates
+        .update_go_live_announcement(title, category_name)
+        .await
+    {
+        let http = discord_client.get_http().await;
+        if let Err(e) = crate::discord::announcements::edit_stream_announcement(&http, channel_id, message_id, &announcement).await {
+            error!("Failed to update Discord go-live announcement: {}", e);
+        }
+    }
+}
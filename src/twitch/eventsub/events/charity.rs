@@ -0,0 +1,93 @@
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{error, info};
+use crate::config::AlertEventType;
+use crate::twitch::alerts::{self, AlertEvent};
+use crate::twitch::charity::{self, CharityCampaign};
+use crate::twitch::TwitchManager;
+
+pub async fn handle_donation(
+    event: &Value,
+    channel: &str,
+    twitch_manager: &Arc<TwitchManager>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let payload = &event["payload"]["event"];
+    let user_name = payload["user_name"].as_str().unwrap_or("Anonymous");
+    let charity_name = payload["charity_name"].as_str().unwrap_or("charity");
+    let donated = charity::amount(&payload["amount"]);
+    let currency = payload["amount"]["currency"].as_str().unwrap_or("USD");
+    let formatted = charity::format_amount(donated, currency);
+    info!("{} donated {} to {}", user_name, formatted, charity_name);
+
+    // Progress arrives as its own notification, so totals are left to handle_progress
+    let alert = AlertEvent::new(AlertEventType::CharityDonation, user_name, donated.floor() as u64).with_message(charity_name);
+    if alerts::dispatch(twitch_manager, channel, &alert).await {
+        return Ok(());
+    }
+
+    if twitch_manager.config.charity.announce_donations {
+        let message = twitch_manager.bot_voice.render("charity_donation", &[("user", user_name), ("amount", formatted.as_str()), ("charity", charity_name)]);
+        twitch_manager.send_message_as_bot(channel, &message).await?;
+    }
+    Ok(())
+}
+
+pub async fn handle_start(
+    event: &Value,
+    channel: &str,
+    twitch_manager: &Arc<TwitchManager>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let campaign = twitch_manager.charity.start(&event["payload"]["event"]);
+    info!("Charity campaign for {} started", campaign.charity_name);
+    charity::publish(twitch_manager, &campaign).await;
+
+    if twitch_manager.config.charity.announce_campaign {
+        announce(twitch_manager, channel, "charity_start", &campaign, None).await;
+    }
+    Ok(())
+}
+
+pub async fn handle_progress(
+    event: &Value,
+    channel: &str,
+    twitch_manager: &Arc<TwitchManager>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let milestones = &twitch_manager.config.charity.milestone_percents;
+    let (campaign, milestone) = twitch_manager.charity.progress(&event["payload"]["event"], milestones);
+    charity::publish(twitch_manager, &campaign).await;
+
+    if let Some(percent) = milestone {
+        announce(twitch_manager, channel, "charity_milestone", &campaign, Some(percent)).await;
+    }
+    Ok(())
+}
+
+pub async fn handle_stop(
+    event: &Value,
+    channel: &str,
+    twitch_manager: &Arc<TwitchManager>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let campaign = twitch_manager.charity.stop(&event["payload"]["event"]);
+    info!("Charity campaign for {} ended at {}/{}", campaign.charity_name, campaign.current, campaign.target);
+    charity::publish(twitch_manager, &campaign).await;
+
+    if twitch_manager.config.charity.announce_campaign {
+        announce(twitch_manager, channel, "charity_end", &campaign, None).await;
+    }
+    Ok(())
+}
+
+async fn announce(twitch_manager: &TwitchManager, channel: &str, key: &str, campaign: &CharityCampaign, percent: Option<u64>) {
+    let current = charity::format_amount(campaign.current, &campaign.currency);
+    let target = charity::format_amount(campaign.target, &campaign.currency);
+    let percent = percent.unwrap_or_else(|| campaign.percent()).to_string();
+    let message = twitch_manager.bot_voice.render(key, &[
+        ("charity", campaign.charity_name.as_str()),
+        ("current", current.as_str()),
+        ("target", target.as_str()),
+        ("percent", percent.as_str()),
+    ]);
+    if let Err(e) = twitch_manager.send_message_as_bot(channel, &message).await {
+        error!("Failed to announce the charity campaign: {}", e);
+    }
+}
@@ -10,4 +10,6 @@ pub mod channel_subscription_gift;
 pub mod channel_subscription_end;
 pub mod channel_point_redemption;
 pub(crate) mod ads;
-pub mod channel_bits;
\ No newline at end of file
+pub mod channel_bits;
+pub mod channel_ban;
+pub mod charity;
\ No newline at end of file
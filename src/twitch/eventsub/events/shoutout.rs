@@ -11,7 +11,7 @@ pub async fn handle_shoutout_create(
         let to_broadcaster_user_name = payload["to_broadcaster_user_name"].as_str().unwrap_or("Unknown");
         let to_broadcaster_user_id = payload["to_broadcaster_user_id"].as_str().unwrap_or("Unknown");
 
-        let message = format!("Hey, you should go check out {}! Click the heart at the top of the chatbox ~ it's easy! luv luv", to_broadcaster_user_name);
+        let message = twitch_manager.bot_voice.render("shoutout_sent", &[("user", to_broadcaster_user_name)]);
         twitch_manager.send_message_as_bot(channel, message.as_str()).await?;
 
         // Handle the successful shoutout in TwitchManager
@@ -30,7 +30,7 @@ pub async fn handle_shoutout_receive(
         let from_broadcaster_user_name = payload["from_broadcaster_user_name"].as_str().unwrap_or("Unknown");
         let viewer_count = payload["viewer_count"].as_u64().unwrap_or(0);
 
-        let message = format!("We just received a shoutout from {} with {} viewers! Thank you for the support!", from_broadcaster_user_name, viewer_count);
+        let message = twitch_manager.bot_voice.render("shoutout_received", &[("user", from_broadcaster_user_name), ("viewers", viewer_count.to_string().as_str())]);
         twitch_manager.send_message_as_bot(channel, message.as_str()).await?;
     }
 
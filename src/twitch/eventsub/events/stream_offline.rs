@@ -1,7 +1,9 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::{info};
+use tracing::{error, info};
 use crate::twitch::manager::TwitchManager;
+use crate::twitch::milestones::{self, MilestoneKind};
+use crate::twitch::{follower_growth, lurk, stream_segments};
 
 pub async fn handle(
     event: &Value,
@@ -11,13 +13,43 @@ pub async fn handle(
     if let Some(payload) = event.get("payload").and_then(|p| p.get("event")) {
         let broadcaster_user_name = payload["broadcaster_user_name"].as_str().unwrap_or("Unknown");
 
-        let message = format!("{} ended stream!  mao  Stay amazing and cute! mao", broadcaster_user_name);
+        let message = twitch_manager.bot_voice.render("stream_offline", &[("user", broadcaster_user_name)]);
         twitch_manager.send_message_as_bot(channel, message.as_str()).await?;
 
         let message = format!("bark bark bark bark bark bark bark bark bark");
         twitch_manager.send_message_as_bot(channel, message.as_str()).await?;
 
         twitch_manager.stream_state_machine.set_stream_offline().await?;
+        twitch_manager.channel_updates.clear_go_live_announcement().await;
+
+        if let Some(storage) = twitch_manager.get_storage() {
+            // Before the session closes, so it counts toward this stream
+            if let Err(e) = follower_growth::snapshot(twitch_manager).await {
+                error!("Failed to snapshot followers at stream end: {}", e);
+            }
+            let recap = {
+                let storage = storage.read().await;
+                let parts: Vec<String> = [stream_segments::recap(twitch_manager, &storage), lurk::end_of_stream_recap(&storage)]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                (!parts.is_empty()).then(|| parts.join(" "))
+            };
+            if let Some(recap) = recap {
+                twitch_manager.send_long_message_as_bot(channel, &recap).await?;
+                if let (Some(discord_client), Some(thread_id)) = (&twitch_manager.discord_client, twitch_manager.channel_updates.stream_thread().await) {
+                    let http = discord_client.get_http().await;
+                    if let Err(e) = crate::discord::announcements::send_stream_recap(&http, thread_id, &recap).await {
+                        error!("Failed to post the recap to Discord: {}", e);
+                    }
+                }
+            }
+            if let Err(e) = storage.read().await.end_stream_session() {
+                error!("Failed to record stream session end: {:?}", e);
+            }
+        }
+        twitch_manager.channel_updates.clear_stream_thread().await;
+        milestones::check(twitch_manager, channel, MilestoneKind::HoursStreamed).await;
 
         info!("Stream is now offline");
     }
@@ -1,8 +1,10 @@
 use serde_json::Value;
 use std::sync::Arc;
-use log::{info, error};
+use tracing::{info, error};
 use crate::twitch::TwitchManager;
 use serenity::model::id::ChannelId;
+use crate::discord::announcements::StreamAnnouncement;
+use crate::twitch::{follower_growth, stream_segments};
 
 async fn generate_stream_description(
     ai_client: &Arc<crate::ai::AIClient>,
@@ -35,9 +37,8 @@ pub async fn handle(
             url.replace("{width}", "1920").replace("{height}", "1080")
         });
 
-        let message = format!("{} has gone live! Stream started at {}. Come join the fun!",
-                              broadcaster_user_name, started_at);
-        twitch_manager.send_message_as_bot(channel, &message).await?;
+        let message = twitch_manager.bot_voice.render("stream_online", &[("user", broadcaster_user_name), ("time", started_at)]);
+        twitch_manager.send_long_message_as_bot(channel, &message).await?;
 
         // Send Discord announcement if configured
         if let Some(discord_client) = &twitch_manager.discord_client {
@@ -69,24 +70,49 @@ pub async fn handle(
                         None
                     };
 
-                    match crate::discord::announcements::send_stream_announcement(
-                        &http,
-                        ChannelId::new(channel_id),
-                        broadcaster_user_name,
-                        started_at,
-                        game_name,
-                        title,
-                        thumbnail_url.as_deref(),
-                        profile_image_url.as_deref(),
-                        ai_message.as_deref(),
-                    ).await {
-                        Ok(_) => info!("Discord announcement sent successfully"),
+                    let announcement = StreamAnnouncement {
+                        broadcaster_name: broadcaster_user_name.to_string(),
+                        started_at: started_at.to_string(),
+                        game_name: game_name.map(String::from),
+                        title: title.map(String::from),
+                        thumbnail_url,
+                        profile_image_url,
+                        ai_message,
+                        status: None,
+                    };
+
+                    let channel_id = ChannelId::new(channel_id);
+                    match crate::discord::announcements::send_stream_announcement(&http, channel_id, &announcement).await {
+                        Ok(message_id) => {
+                            info!("Discord announcement sent successfully");
+                            match crate::discord::announcements::create_stream_thread(&http, channel_id, message_id, &announcement).await {
+                                Ok(thread_id) => twitch_manager.channel_updates.set_stream_thread(thread_id).await,
+                                Err(e) => error!("Failed to create the Discord thread for this stream: {}", e),
+                            }
+                            twitch_manager.channel_updates
+                                .set_go_live_announcement(channel_id, message_id, announcement)
+                                .await;
+                        }
                         Err(e) => error!("Failed to send Discord announcement: {}", e),
                     }
                 }
             }
         }
 
+        if let (Some(t), Some(g)) = (title, game_name) {
+            twitch_manager.channel_updates.apply(t, g).await;
+        }
+
+        if let Some(storage) = twitch_manager.get_storage() {
+            if let Err(e) = storage.read().await.start_stream_session() {
+                error!("Failed to record stream session start: {:?}", e);
+            }
+        }
+        stream_segments::stream_started(twitch_manager, game_name.unwrap_or("")).await;
+        if let Err(e) = follower_growth::snapshot(twitch_manager).await {
+            error!("Failed to snapshot followers at stream start: {}", e);
+        }
+
         let game_name = game_name.unwrap_or("").to_string();
         twitch_manager.stream_state_machine.set_stream_live(game_name).await?;
 
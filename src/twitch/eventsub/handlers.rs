@@ -1,12 +1,41 @@
 use serde_json::Value;
 use super::events;
 use super::events::{channel_follow, channel_raid, channel_update, stream_online, stream_offline, channel_subscribe, channel_subscription_message, channel_point_redemption};
-use super::events::{channel_bits, channel_subscription_gift, channel_subscription_end};
+use super::events::{channel_bits, channel_subscription_gift, channel_subscription_end, channel_ban, charity};
 use super::events::ads; // New import
 use std::sync::Arc;
-use log::{debug, error};
+use tracing::{debug, error};
+use crate::hooks::BotEvent;
 use crate::twitch::manager::TwitchManager;
 
+// Notifications kept for the dashboard event inspector
+const EVENT_LOG_KEEP: usize = 500;
+
+// Every subscription type handle_message has a handler for
+pub const HANDLED_EVENTS: &[&str] = &[
+    "channel.update",
+    "channel.follow",
+    "channel.raid",
+    "channel.shoutout.create",
+    "channel.shoutout.receive",
+    "stream.online",
+    "stream.offline",
+    "channel.subscribe",
+    "channel.subscription.message",
+    "channel.subscription.gift",
+    "channel.subscription.end",
+    "channel.cheer",
+    "channel.ban",
+    "channel.unban",
+    "channel.channel_points_custom_reward_redemption.add",
+    "channel.channel_points_custom_reward_redemption.update",
+    "channel.ad_break.begin",
+    "channel.charity_campaign.donate",
+    "channel.charity_campaign.start",
+    "channel.charity_campaign.progress",
+    "channel.charity_campaign.stop",
+];
+
 pub async fn handle_message(
     message: &str,
     twitch_manager: &Arc<TwitchManager>,
@@ -18,6 +47,20 @@ pub async fn handle_message(
         .ok_or("Twitch channel not set")?;
 
     if let Some(event_type) = parsed["metadata"]["subscription_type"].as_str() {
+        if let Some(storage) = twitch_manager.get_storage() {
+            if let Err(e) = storage.read().await.log_eventsub_event(event_type, message, EVENT_LOG_KEEP) {
+                error!("Failed to store {} notification: {:?}", event_type, e);
+            }
+        }
+
+        let event_hooks = twitch_manager.get_event_hooks();
+        if !event_hooks.is_empty() {
+            event_hooks.emit(&BotEvent::EventSub {
+                event_type: event_type.to_string(),
+                payload: parsed["payload"]["event"].clone(),
+            });
+        }
+
         match event_type {
             "channel.update" => channel_update::handle(&parsed, channel, twitch_manager).await?,
             "channel.follow" => channel_follow::handle(&parsed, channel, twitch_manager).await?,
@@ -31,15 +74,21 @@ pub async fn handle_message(
             "channel.subscription.gift" => channel_subscription_gift::handle(&parsed, channel, twitch_manager).await?,
             "channel.subscription.end" => channel_subscription_end::handle(&parsed, channel, twitch_manager).await?,
             "channel.cheer" => channel_bits::handle(&parsed, channel, twitch_manager).await?,
+            "channel.ban" => channel_ban::handle_ban(&parsed, channel, twitch_manager).await?,
+            "channel.unban" => channel_ban::handle_unban(&parsed, channel, twitch_manager).await?,
             "channel.channel_points_custom_reward_redemption.add" => {
                 debug!("Received new channel point redemption: {:?}", parsed["payload"]["event"]);
                 channel_point_redemption::handle_new_redemption(&parsed["payload"]["event"], twitch_manager, channel).await?;
             },
             "channel.channel_points_custom_reward_redemption.update" => {
                 debug!("Received channel point redemption update: {:?}", parsed["payload"]["event"]);
-                channel_point_redemption::handle_redemption_update(&parsed["payload"]["event"]).await?;
+                channel_point_redemption::handle_redemption_update(&parsed["payload"]["event"], twitch_manager).await?;
             },
             "channel.ad_break.begin" => ads::handle_ad_break_begin(&parsed, channel, twitch_manager).await?, // New handler
+            "channel.charity_campaign.donate" => charity::handle_donation(&parsed, channel, twitch_manager).await?,
+            "channel.charity_campaign.start" => charity::handle_start(&parsed, channel, twitch_manager).await?,
+            "channel.charity_campaign.progress" => charity::handle_progress(&parsed, channel, twitch_manager).await?,
+            "channel.charity_campaign.stop" => charity::handle_stop(&parsed, channel, twitch_manager).await?,
             _ => error!("Unhandled event type: {}", event_type),
         }
     }
@@ -0,0 +1,193 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use crate::config::{AlertAction, AlertEventType, Config};
+use crate::twitch::alerts::{self, AlertEvent};
+use crate::twitch::eventsub::events::channel_point_redemption::parse_redemption;
+use crate::twitch::eventsub::handlers::HANDLED_EVENTS;
+use crate::twitch::charity;
+use crate::twitch::profiles::StreamProfiles;
+use crate::twitch::redeems::RedeemManager;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TraceStep {
+    pub stage: String,
+    pub detail: String,
+}
+
+fn step(stage: &str, detail: impl Into<String>) -> TraceStep {
+    TraceStep {
+        stage: stage.to_string(),
+        detail: detail.into(),
+    }
+}
+
+// Walks a stored notification through the same decisions handlers::handle_message makes,
+// without sending, toggling or saving anything. Runtime state like raid mode isn't known
+// here, so anything depending on it is reported as a condition.
+pub async fn dry_run(config: &Config, redeem_manager: Option<&RedeemManager>, profiles: &StreamProfiles, message: &str) -> Result<Vec<TraceStep>, String> {
+    let parsed: Value = serde_json::from_str(message).map_err(|e| format!("Not valid JSON: {}", e))?;
+    let event_type = parsed["metadata"]["subscription_type"].as_str()
+        .ok_or("Not an EventSub notification")?;
+    let payload = &parsed["payload"]["event"];
+
+    let mut trace = vec![step("received", event_type)];
+    if config.twitch_channel_to_join.is_none() {
+        trace.push(step("stopped", "No Twitch channel is configured, so nothing would run"));
+        return Ok(trace);
+    }
+    trace.push(step("hooks", "Forwarded to any registered event hooks"));
+
+    if !HANDLED_EVENTS.contains(&event_type) {
+        trace.push(step("stopped", format!("No handler for {}", event_type)));
+        return Ok(trace);
+    }
+    trace.push(step("handler", format!("{} handler", event_type)));
+
+    let tier = payload["tier"].as_str().unwrap_or("1000");
+    match event_type {
+        "channel.follow" => {
+            let user_name = payload["user_name"].as_str().unwrap_or("Unknown");
+            trace.push(step("milestones", "Counted towards the follower milestone"));
+            trace.push(step("raid mode", "If raid mode is active the welcome is held for the raid summary and nothing below runs"));
+            let guard = &config.follow_guard;
+            if guard.enabled && guard.follow_threshold > 0 {
+                trace.push(step("follow guard", format!(
+                    "{} follows within {}s turn on follow-bot protection{}",
+                    guard.follow_threshold, guard.window_secs,
+                    if guard.pause_follow_alerts { ", which skips the alert below" } else { "" }
+                )));
+            }
+            trace_alert(&mut trace, config, &AlertEvent::new(AlertEventType::Follow, user_name, 1), "AI welcome message with the follower count");
+        }
+        "channel.raid" => {
+            let user_name = payload["from_broadcaster_user_name"].as_str().unwrap_or("Unknown");
+            let viewers = payload["viewers"].as_u64().unwrap_or(0);
+            let raid_mode = &config.raid_mode;
+            if raid_mode.enabled && viewers >= raid_mode.min_viewers {
+                trace.push(step("raid mode", format!("{} viewers turns on raid mode for {}s", viewers, raid_mode.duration_secs)));
+            } else {
+                trace.push(step("raid mode", format!("{} viewers doesn't turn on raid mode", viewers)));
+            }
+            trace_alert(&mut trace, config, &AlertEvent::new(AlertEventType::Raid, user_name, viewers), "Built-in raid thank-you and shoutout");
+        }
+        "channel.subscribe" => {
+            let user_name = payload["user_name"].as_str().unwrap_or("Unknown");
+            trace.push(step("milestones", "Counted towards the sub milestone"));
+            let alert = AlertEvent::new(AlertEventType::Subscribe, user_name, 1).with_tier(tier);
+            trace_alert(&mut trace, config, &alert, "Built-in sub OSC effect and chat message");
+        }
+        "channel.subscription.message" => {
+            let user_name = payload["user_name"].as_str().unwrap_or("Unknown");
+            let months = payload["cumulative_months"].as_u64().unwrap_or(0);
+            let message = payload["message"].get("text").and_then(Value::as_str).unwrap_or("");
+            let alert = AlertEvent::new(AlertEventType::Resub, user_name, months).with_tier(tier).with_message(message);
+            trace_alert(&mut trace, config, &alert, "Built-in resub chat message");
+        }
+        "channel.subscription.gift" => {
+            let user_name = payload["user_name"].as_str().unwrap_or("Anonymous");
+            let total = payload["total"].as_u64().unwrap_or(0);
+            let alert = AlertEvent::new(AlertEventType::GiftSub, user_name, total).with_tier(tier);
+            trace_alert(&mut trace, config, &alert, "Built-in gift sub chat message");
+        }
+        "channel.cheer" => {
+            let user_name = payload["user_name"].as_str().unwrap_or("Anonymous");
+            let bits = match payload["bits"].as_u64() {
+                Some(bits) => bits,
+                None => {
+                    trace.push(step("stopped", "The payload has no bits amount"));
+                    return Ok(trace);
+                }
+            };
+            let message = payload["message"].get("text").and_then(Value::as_str).unwrap_or("");
+            let alert = AlertEvent::new(AlertEventType::Cheer, user_name, bits).with_message(message);
+            trace_alert(&mut trace, config, &alert, "Built-in bits OSC effect and chat message");
+        }
+        "channel.charity_campaign.donate" => {
+            let user_name = payload["user_name"].as_str().unwrap_or("Anonymous");
+            let charity_name = payload["charity_name"].as_str().unwrap_or("charity");
+            let donated = charity::amount(&payload["amount"]);
+            let alert = AlertEvent::new(AlertEventType::CharityDonation, user_name, donated.floor() as u64).with_message(charity_name);
+            let fallback = if config.charity.announce_donations { "Built-in donation thank-you in chat" } else { "Nothing, donation thank-yous are off" };
+            trace_alert(&mut trace, config, &alert, fallback);
+        }
+        "channel.charity_campaign.progress" => {
+            let raised = charity::amount(&payload["current_amount"]);
+            let target = charity::amount(&payload["target_amount"]);
+            trace.push(step("charity", format!("Updates the dashboard and overlay to {:.2} of {:.2}; milestones {:?} are called out once each", raised, target, config.charity.milestone_percents)));
+        }
+        "channel.channel_points_custom_reward_redemption.add" => {
+            trace_redemption(&mut trace, redeem_manager, payload).await;
+        }
+        "channel.update" => {
+            let category = payload["category_name"].as_str().unwrap_or("");
+            trace.push(step("channel", format!("Title \"{}\", category {}", payload["title"].as_str().unwrap_or(""), category)));
+            trace_profile(&mut trace, profiles, category);
+        }
+        "stream.online" => {
+            trace.push(step("stream", "Go-live announcements with a Discord thread for the stream, stream session start and reward sync"));
+            if let Some(category) = payload["category_name"].as_str() {
+                trace_profile(&mut trace, profiles, category);
+            }
+        }
+        "stream.offline" => {
+            trace.push(step("stream", "Ends the stream session, closes open lurks and posts the recap in chat and the stream's Discord thread"));
+        }
+        _ => {}
+    }
+
+    Ok(trace)
+}
+
+fn trace_alert(trace: &mut Vec<TraceStep>, config: &Config, event: &AlertEvent, fallback: &str) {
+    let route = match alerts::matching_route(&config.alert_routing.routes, event) {
+        Some(route) => route,
+        None => {
+            trace.push(step("alert", format!("No alert route matches, so the built-in alert runs: {}", fallback)));
+            return;
+        }
+    };
+
+    trace.push(step("alert", format!("Matched the {:?} route with {} actions", route.event, route.actions.len())));
+    for action in &route.actions {
+        let detail = match action {
+            AlertAction::Chat { template } => format!("Chat: {}", event.render(template)),
+            AlertAction::Discord { channel_id, template } => format!("Discord #{}: {}", channel_id, event.render(template)),
+            AlertAction::Osc(osc_config) => format!("OSC {} = {:?}", osc_config.osc_endpoint, osc_config.osc_value),
+            AlertAction::ObsSource(obs_action) => format!("OBS source {} for {}s", obs_action.source_name, obs_action.duration_secs),
+            AlertAction::Tts { template } => format!("TTS: {}", event.render(template)),
+            AlertAction::Overlay { template } => format!("Overlay: {}", event.render(template)),
+        };
+        trace.push(step("action", detail));
+    }
+}
+
+async fn trace_redemption(trace: &mut Vec<TraceStep>, redeem_manager: Option<&RedeemManager>, payload: &Value) {
+    let redemption = parse_redemption(payload);
+    trace.push(step("redemption", format!("{} redeemed {}", redemption.user_name, redemption.reward_title)));
+
+    let redeem_manager = match redeem_manager {
+        Some(redeem_manager) => redeem_manager,
+        None => {
+            trace.push(step("stopped", "Redeems are not initialized"));
+            return;
+        }
+    };
+    match redeem_manager.describe_route(&redemption.reward_title).await {
+        Some(route) => trace.push(step("redeem", format!("Runs the {}", route))),
+        None => {
+            trace.push(step("stopped", format!("No handler for the reward {}", redemption.reward_title)));
+            return;
+        }
+    }
+    if redeem_manager.registry.get(&redemption.reward_title).await.map_or(false, |r| r.auto_complete) {
+        trace.push(step("redeem", "Auto-completed on Twitch if it succeeds"));
+    }
+}
+
+fn trace_profile(trace: &mut Vec<TraceStep>, profiles: &StreamProfiles, category: &str) {
+    match profiles.auto_switch_target(category) {
+        Some(name) => trace.push(step("profile", format!("Switches to the {} stream profile", name))),
+        None => trace.push(step("profile", "No stream profile switch")),
+    }
+}
@@ -1,5 +1,6 @@
 mod client;
 mod handlers;
+pub mod inspector;
 pub mod events;
 
 pub use client::TwitchEventSubClient;
\ No newline at end of file
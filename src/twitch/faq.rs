@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::{debug, error, info};
+use serde::{Deserialize, Serialize};
+use twitch_irc::message::PrivmsgMessage;
+use crate::ai::prompts::Prompts;
+use crate::ai::AIClient;
+use crate::config::FaqConfig;
+use crate::twitch::{opt_out, TwitchManager};
+
+const QUESTION_WORDS: &[&str] = &[
+    "what", "which", "who", "where", "when", "why", "how", "is", "are", "do", "does", "did", "can", "could", "will", "whats",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaqEntry {
+    pub id: u64,
+    // Every word has to appear in the message, in any order: "what headset"
+    pub question: String,
+    pub answer: String,
+    #[serde(default)]
+    pub cooldown_secs: Option<u64>,
+    #[serde(default)]
+    pub times_answered: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FaqFile {
+    entries: Vec<FaqEntry>,
+}
+
+struct FaqStore {
+    path: String,
+    file: FaqFile,
+    // Entry id -> when it was last answered
+    last_answered: HashMap<u64, Instant>,
+    last_ai_lookup: Option<Instant>,
+}
+
+// The FAQ entries and when they were last answered, owned by TwitchManager
+pub struct Faq {
+    store: Mutex<FaqStore>,
+}
+
+fn save(store: &FaqStore) {
+    let result = serde_json::to_string_pretty(&store.file)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&store.path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Failed to update {}: {}", store.path, e);
+    }
+}
+
+impl Faq {
+    pub fn new(path: &str) -> Self {
+        Self {
+            store: Mutex::new(FaqStore {
+                path: path.to_string(),
+                file: FaqFile::default(),
+                last_answered: HashMap::new(),
+                last_ai_lookup: None,
+            }),
+        }
+    }
+
+    pub fn load(path: &str) -> Self {
+        let file = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                error!("Failed to parse {}, starting with an empty FAQ: {}", path, e);
+                FaqFile::default()
+            }),
+            Err(_) => FaqFile::default(),
+        };
+        let faq = Self::new(path);
+        faq.store.lock().unwrap().file = file;
+        faq
+    }
+
+    pub fn list(&self) -> Vec<FaqEntry> {
+        self.store.lock().unwrap().file.entries.clone()
+    }
+
+    // Adding a question that's already there replaces its answer
+    pub fn add(&self, question: &str, answer: &str) -> FaqEntry {
+        let mut store = self.store.lock().unwrap();
+        let question = question.trim().to_string();
+        let entry = match store.file.entries.iter_mut().find(|entry| entry.question.eq_ignore_ascii_case(&question)) {
+            Some(existing) => {
+                existing.answer = answer.trim().to_string();
+                existing.clone()
+            }
+            None => {
+                let id = store.file.entries.iter().map(|entry| entry.id).max().unwrap_or(0) + 1;
+                let entry = FaqEntry {
+                    id,
+                    question,
+                    answer: answer.trim().to_string(),
+                    cooldown_secs: None,
+                    times_answered: 0,
+                };
+                store.file.entries.push(entry.clone());
+                entry
+            }
+        };
+        save(&store);
+        entry
+    }
+
+    // By number or by the question itself
+    pub fn remove(&self, key: &str) -> Option<FaqEntry> {
+        let mut store = self.store.lock().unwrap();
+        let key = key.trim().trim_start_matches('#');
+        let index = store.file.entries.iter().position(|entry| {
+            key.parse::<u64>().map_or(false, |id| entry.id == id) || entry.question.eq_ignore_ascii_case(key)
+        })?;
+        let removed = store.file.entries.remove(index);
+        store.last_answered.remove(&removed.id);
+        save(&store);
+        Some(removed)
+    }
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+fn looks_like_question(text: &str) -> bool {
+    text.contains('?') || words(text).first().map_or(false, |first| QUESTION_WORDS.contains(&first.as_str()))
+}
+
+fn matches(entry: &FaqEntry, message_words: &[String]) -> bool {
+    let pattern = words(&entry.question);
+    !pattern.is_empty() && pattern.iter().all(|word| message_words.contains(word))
+}
+
+// Marks the entry as answered unless it's still on cooldown; returns the answer to send
+fn take_answer(store: &mut FaqStore, id: u64, default_cooldown: u64) -> Option<String> {
+    let entry = store.file.entries.iter_mut().find(|entry| entry.id == id)?;
+    let cooldown = entry.cooldown_secs.unwrap_or(default_cooldown);
+    if let Some(last) = store.last_answered.get(&id) {
+        if last.elapsed().as_secs() < cooldown {
+            debug!("FAQ #{} is on cooldown", id);
+            return None;
+        }
+    }
+    entry.times_answered += 1;
+    let answer = entry.answer.clone();
+    store.last_answered.insert(id, Instant::now());
+    save(store);
+    Some(answer)
+}
+
+// Called for every chat message that isn't a command
+pub async fn check(twitch_manager: &TwitchManager, ai_client: &Option<Arc<AIClient>>, config: &FaqConfig, msg: &PrivmsgMessage, text: &str) {
+    if !config.enabled || (config.questions_only && !looks_like_question(text)) {
+        return;
+    }
+
+    // Only real questions are worth an AI lookup, and opted-out users' messages never go to
+    // the AI; they still get the exact matches
+    let may_ask_ai = config.ai_fallback && looks_like_question(text) && match twitch_manager.get_storage() {
+        Some(storage) => !opt_out::is_opted_out(&*storage.read().await, &msg.sender.id),
+        None => false,
+    };
+
+    let message_words = words(text);
+    let (matched, ask_ai) = {
+        let mut store = twitch_manager.faq.store.lock().unwrap();
+        if store.file.entries.is_empty() {
+            return;
+        }
+        let matched = store.file.entries.iter().find(|entry| matches(entry, &message_words)).map(|entry| entry.id);
+        let ask_ai = matched.is_none()
+            && may_ask_ai
+            && store.last_ai_lookup.map_or(true, |last| last.elapsed().as_secs() >= config.ai_cooldown_secs);
+        if ask_ai {
+            store.last_ai_lookup = Some(Instant::now());
+        }
+        (matched, ask_ai)
+    };
+
+    let id = match (matched, ai_client) {
+        (Some(id), _) => id,
+        (None, Some(ai_client)) if ask_ai => match pick_with_ai(ai_client, &twitch_manager.prompts, twitch_manager.faq.list(), &msg.sender.name, text).await {
+            Some(id) => id,
+            None => return,
+        },
+        _ => return,
+    };
+
+    let answer = take_answer(&mut twitch_manager.faq.store.lock().unwrap(), id, config.cooldown_secs);
+    if let Some(answer) = answer {
+        info!("Answering FAQ #{} for {}", id, msg.sender.login);
+        let reply = format!("@{} {}", msg.sender.name, answer);
+        if let Err(e) = twitch_manager.send_message_as_bot(&msg.channel_login, &reply).await {
+            error!("Failed to send FAQ answer: {:?}", e);
+        }
+    }
+}
+
+// The AI only chooses an entry; the answer sent is always the one mods wrote
+async fn pick_with_ai(ai_client: &AIClient, prompts: &Prompts, entries: Vec<FaqEntry>, username: &str, question: &str) -> Option<u64> {
+    let faq = entries.iter()
+        .enumerate()
+        .map(|(i, entry)| format!("{}. {} -> {}", i + 1, entry.question, entry.answer))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let prompt = prompts.render("chat.faq", &[("faq", &faq), ("question", question), ("username", username)]);
+
+    let reply = match ai_client.generate_response_without_history(&prompt).await {
+        Ok(reply) => reply,
+        Err(e) => {
+            error!("FAQ lookup failed: {:?}", e);
+            return None;
+        }
+    };
+    let number: usize = reply.trim().trim_end_matches('.').parse().ok()?;
+    let entry = entries.get(number.checked_sub(1)?)?;
+    debug!("AI matched \"{}\" to FAQ #{}", question, entry.id);
+    Some(entry.id)
+}
@@ -0,0 +1,184 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use serenity::model::id::ChannelId;
+use tracing::{error, info, warn};
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Duration, Instant};
+use crate::config::FollowGuardConfig;
+use crate::twitch::TwitchManager;
+
+// Protective mode for follow-bot and join-bot attacks: sudden bursts of follows or chat joins,
+// outside of a raid, pause follow alerts, can turn on followers-only chat and tell mods in Discord.
+#[derive(Debug, Default)]
+pub struct FollowGuard {
+    follows: Mutex<VecDeque<Instant>>,
+    joins: Mutex<VecDeque<Instant>>,
+    active_until: RwLock<Option<Instant>>,
+    suppressed_follows: AtomicU32,
+}
+
+// Counts the event and returns how many fell inside the window
+fn count_recent(events: &Mutex<VecDeque<Instant>>, window: Duration) -> usize {
+    let mut events = events.lock().unwrap();
+    let now = Instant::now();
+    events.push_back(now);
+    while events.front().map_or(false, |event| now.duration_since(*event) > window) {
+        events.pop_front();
+    }
+    events.len()
+}
+
+impl FollowGuard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn is_active(&self) -> bool {
+        matches!(*self.active_until.read().await, Some(until) if Instant::now() < until)
+    }
+
+    // True when the follow should go without an alert
+    pub async fn on_follow(self: &Arc<Self>, twitch_manager: &Arc<TwitchManager>, channel: &str) -> bool {
+        let config = &twitch_manager.config.follow_guard;
+        if !config.enabled {
+            return false;
+        }
+        let follows = count_recent(&self.follows, Duration::from_secs(config.window_secs));
+        if config.follow_threshold > 0 && follows >= config.follow_threshold as usize {
+            let reason = format!("{} follows in {}s", follows, config.window_secs);
+            self.activate(twitch_manager.clone(), channel.to_string(), reason).await;
+        }
+
+        if config.pause_follow_alerts && self.is_active().await {
+            self.suppressed_follows.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+
+    pub async fn on_join(self: &Arc<Self>, twitch_manager: &Arc<TwitchManager>, channel: &str) {
+        let config = &twitch_manager.config.follow_guard;
+        if !config.enabled || config.join_threshold == 0 {
+            return;
+        }
+        // Raids bring a crowd in all at once; raid mode handles those
+        if twitch_manager.get_raid_mode().is_active().await {
+            return;
+        }
+        let joins = count_recent(&self.joins, Duration::from_secs(config.window_secs));
+        if joins >= config.join_threshold as usize {
+            let reason = format!("{} chat joins in {}s", joins, config.window_secs);
+            self.activate(twitch_manager.clone(), channel.to_string(), reason).await;
+        }
+    }
+
+    // Starts (or extends) protection, undoing its actions once it runs out
+    async fn activate(self: &Arc<Self>, twitch_manager: Arc<TwitchManager>, channel: String, reason: String) {
+        let config = twitch_manager.config.follow_guard.clone();
+        let until = Instant::now() + Duration::from_secs(config.duration_mins * 60);
+        let already_active = {
+            let mut active_until = self.active_until.write().await;
+            let already_active = matches!(*active_until, Some(current) if Instant::now() < current);
+            *active_until = Some(until);
+            already_active
+        };
+        if already_active {
+            return;
+        }
+        warn!("Possible follow-bot attack ({}), protection on for {} minutes", reason, config.duration_mins);
+
+        // Left alone afterwards if followers-only chat was already on
+        let mut restore_follower_mode = false;
+        if config.followers_only {
+            match enable_followers_only(&twitch_manager, &config).await {
+                Ok(changed) => restore_follower_mode = changed,
+                Err(e) => error!("Failed to turn on followers-only chat: {}", e),
+            }
+        }
+
+        let mut actions = Vec::new();
+        if config.pause_follow_alerts {
+            actions.push("follow alerts paused".to_string());
+        }
+        if restore_follower_mode {
+            actions.push(format!("followers-only chat ({} minutes) on", config.followers_only_minutes));
+        }
+        let actions = if actions.is_empty() { String::new() } else { format!(" {}.", actions.join(", ")) };
+        notify_mods(&twitch_manager, &config, &format!(
+            "Possible follow-bot attack in {}: {}. Protection is on for {} minutes.{}",
+            channel, reason, config.duration_mins, actions
+        )).await;
+
+        let guard = self.clone();
+        tokio::spawn(async move {
+            // Keep waiting while further bursts extend the window
+            loop {
+                let until = match *guard.active_until.read().await {
+                    Some(until) => until,
+                    None => break,
+                };
+                if Instant::now() >= until {
+                    break;
+                }
+                sleep(until - Instant::now()).await;
+            }
+
+            *guard.active_until.write().await = None;
+            let follows = guard.suppressed_follows.swap(0, Ordering::Relaxed);
+            info!("Follow-bot protection ended, {} follow alerts were skipped", follows);
+
+            if restore_follower_mode {
+                let result = match twitch_manager.get_api_client().get_broadcaster_id().await {
+                    Ok(broadcaster_id) => twitch_manager.get_api_client().set_follower_mode(&broadcaster_id, false, 0).await
+                        .map_err(|e| e.to_string()),
+                    Err(e) => Err(e.to_string()),
+                };
+                if let Err(e) = result {
+                    error!("Failed to turn followers-only chat back off: {}", e);
+                }
+            }
+            notify_mods(&twitch_manager, &config, &format!(
+                "Follow-bot protection in {} has ended; {} follows came in without alerts.",
+                channel, follows
+            )).await;
+        });
+    }
+}
+
+// True if followers-only chat was off and is now on
+async fn enable_followers_only(twitch_manager: &TwitchManager, config: &FollowGuardConfig) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    let api_client = twitch_manager.get_api_client();
+    let broadcaster_id = api_client.get_broadcaster_id().await?;
+    let (enabled, _) = api_client.get_follower_mode(&broadcaster_id).await?;
+    if enabled {
+        return Ok(false);
+    }
+    api_client.set_follower_mode(&broadcaster_id, true, config.followers_only_minutes).await?;
+    Ok(true)
+}
+
+async fn notify_mods(twitch_manager: &TwitchManager, config: &FollowGuardConfig, message: &str) {
+    let channel_id = match &config.discord_channel_id {
+        Some(channel_id) => channel_id,
+        None => return,
+    };
+    let discord_client = match &twitch_manager.discord_client {
+        Some(discord_client) => discord_client,
+        None => {
+            warn!("Follow-bot protection has a Discord channel set, but Discord is not configured");
+            return;
+        }
+    };
+    let channel_id = match channel_id.parse::<u64>() {
+        Ok(channel_id) => ChannelId::new(channel_id),
+        Err(e) => {
+            error!("Invalid follow_guard Discord channel ID {}: {}", channel_id, e);
+            return;
+        }
+    };
+    let http = discord_client.get_http().await;
+    if let Err(e) = channel_id.say(&http, message).await {
+        error!("Failed to notify mods on Discord: {}", e);
+    }
+}
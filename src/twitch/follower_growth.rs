@@ -0,0 +1,39 @@
+use chrono::{Duration, Utc};
+use tracing::error;
+use crate::twitch::TwitchManager;
+
+// Follower counts are snapshotted every hour and whenever the stream starts or ends, so
+// !followers can say how many came in today and this week and the dashboard can chart growth
+// per stream.
+pub const SNAPSHOT_INTERVAL_SECS: u64 = 3600;
+
+pub async fn snapshot(twitch_manager: &TwitchManager) -> Result<u32, String> {
+    let api_client = twitch_manager.get_api_client();
+    let broadcaster_id = api_client.get_broadcaster_id().await.map_err(|e| e.to_string())?;
+    let follower_count = api_client.get_follower_count(&broadcaster_id).await.map_err(|e| e.to_string())?;
+    if let Some(storage) = twitch_manager.get_storage() {
+        storage.read().await.add_follower_snapshot(follower_count).map_err(|e| e.to_string())?;
+    }
+    Ok(follower_count)
+}
+
+fn signed(delta: i64) -> String {
+    if delta >= 0 { format!("+{}", delta) } else { delta.to_string() }
+}
+
+// "+12 today, +48 this week", or None before there are any snapshots to compare with
+pub async fn describe_deltas(twitch_manager: &TwitchManager, follower_count: u32) -> Option<String> {
+    let storage = twitch_manager.get_storage()?;
+    let storage = storage.read().await;
+    let now = Utc::now();
+    let since = |days| match storage.get_follower_count_at(now - Duration::days(days)) {
+        Ok(count) => count.map(|count| follower_count as i64 - count),
+        Err(e) => {
+            error!("Failed to read follower snapshots: {:?}", e);
+            None
+        }
+    };
+    let today = since(1)?;
+    let week = since(7)?;
+    Some(format!("{} today, {} this week", signed(today), signed(week)))
+}
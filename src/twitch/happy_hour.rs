@@ -0,0 +1,217 @@
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use tracing::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use crate::config::HappyHourConfig;
+use crate::twitch::TwitchManager;
+
+// Written while a happy hour is running so the original costs survive a restart
+fn state_path() -> String {
+    crate::paths::resolve(crate::paths::HAPPY_HOUR_STATE)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscountedReward {
+    pub id: String,
+    pub title: String,
+    pub original_cost: u32,
+    pub cost: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HappyHour {
+    pub ends_at: DateTime<Utc>,
+    pub rewards: Vec<DiscountedReward>,
+}
+
+// The running happy hour, owned by TwitchManager
+#[derive(Debug, Default)]
+pub struct HappyHours {
+    active: Mutex<Option<HappyHour>>,
+}
+
+impl HappyHours {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// "30m", "1h", "1h30m", "90s"; a bare number is minutes
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim().to_lowercase();
+    if let Ok(minutes) = text.parse::<i64>() {
+        return (minutes > 0).then(|| Duration::minutes(minutes));
+    }
+
+    let mut total = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let value: i64 = number.parse().ok()?;
+        number.clear();
+        total += match c {
+            'h' => value * 3600,
+            'm' => value * 60,
+            's' => value,
+            _ => return None,
+        };
+    }
+    (number.is_empty() && total > 0).then(|| Duration::seconds(total))
+}
+
+pub fn current(twitch_manager: &TwitchManager) -> Option<HappyHour> {
+    twitch_manager.happy_hours.active.lock().unwrap().clone()
+}
+
+fn save(happy_hour: Option<&HappyHour>) {
+    let result = match happy_hour {
+        Some(happy_hour) => serde_json::to_string_pretty(happy_hour)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(state_path(), json).map_err(|e| e.to_string())),
+        None => match std::fs::remove_file(state_path()) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        },
+    };
+    if let Err(e) = result {
+        error!("Failed to update {}: {}", state_path(), e);
+    }
+}
+
+// Sets the cost on Twitch, and in the registry so reward syncs don't undo it
+async fn set_cost(twitch_manager: &TwitchManager, reward: &DiscountedReward, cost: u32) -> bool {
+    if let Err(e) = twitch_manager.api_client.update_custom_reward_cost(&reward.id, cost).await {
+        error!("Failed to set the cost of {} to {}: {:?}", reward.title, cost, e);
+        return false;
+    }
+    if let Some(redeem_manager) = twitch_manager.redeem_manager.read().await.as_ref() {
+        if let Some(mut info) = redeem_manager.registry.get(&reward.title).await {
+            info.cost = cost;
+            redeem_manager.registry.add_or_update(reward.title.clone(), info).await;
+        }
+    }
+    true
+}
+
+fn schedule_end(twitch_manager: Arc<TwitchManager>, ends_at: DateTime<Utc>) {
+    let tasks = twitch_manager.tasks.clone();
+    tasks.spawn("happy_hour_end", async move {
+        let wait = (ends_at - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+        // It may have been ended early and another one started since
+        let still_running = twitch_manager.happy_hours.active.lock().unwrap().as_ref().map_or(false, |active| active.ends_at == ends_at);
+        if still_running {
+            if end(&twitch_manager).await.is_some() {
+                announce(&twitch_manager, "Happy hour is over, rewards are back to their usual prices!").await;
+            }
+        }
+    });
+}
+
+pub async fn start(twitch_manager: Arc<TwitchManager>, config: &HappyHourConfig, duration: Duration) -> Result<HappyHour, String> {
+    if twitch_manager.happy_hours.active.lock().unwrap().is_some() {
+        return Err("A happy hour is already running".to_string());
+    }
+
+    let candidates: Vec<DiscountedReward> = {
+        let redeem_manager = twitch_manager.redeem_manager.read().await;
+        let redeem_manager = redeem_manager.as_ref().ok_or("Redeems aren't set up")?;
+        let mut candidates = Vec::new();
+        for title in &config.rewards {
+            match redeem_manager.registry.get(title).await {
+                Some(info) if info.id.is_some() => candidates.push(DiscountedReward {
+                    id: info.id.unwrap_or_default(),
+                    title: info.title,
+                    original_cost: info.cost,
+                    cost: (info.cost as u64 * (100 - config.discount_percent.min(99)) as u64 / 100).max(1) as u32,
+                }),
+                _ => warn!("Happy hour reward {} isn't on Twitch right now", title),
+            }
+        }
+        candidates
+    };
+    if candidates.is_empty() {
+        return Err("None of the happy hour rewards are on Twitch right now".to_string());
+    }
+
+    let mut rewards = Vec::new();
+    for reward in candidates {
+        if set_cost(&twitch_manager, &reward, reward.cost).await {
+            rewards.push(reward);
+        }
+    }
+    if rewards.is_empty() {
+        return Err("Couldn't change any reward costs".to_string());
+    }
+
+    let happy_hour = HappyHour {
+        ends_at: Utc::now() + duration,
+        rewards,
+    };
+    save(Some(&happy_hour));
+    *twitch_manager.happy_hours.active.lock().unwrap() = Some(happy_hour.clone());
+    info!("Happy hour started until {} for {} rewards", happy_hour.ends_at, happy_hour.rewards.len());
+    schedule_end(twitch_manager, happy_hour.ends_at);
+    Ok(happy_hour)
+}
+
+// Puts every discounted reward back to its original cost
+pub async fn end(twitch_manager: &TwitchManager) -> Option<HappyHour> {
+    let happy_hour = twitch_manager.happy_hours.active.lock().unwrap().take()?;
+    for reward in &happy_hour.rewards {
+        set_cost(twitch_manager, reward, reward.original_cost).await;
+    }
+    save(None);
+    info!("Happy hour ended, restored {} rewards", happy_hour.rewards.len());
+    Some(happy_hour)
+}
+
+// On startup: restores costs from a happy hour that ended while the bot was down,
+// or re-applies the discount (the reward sync resets it) and waits out the rest
+pub async fn resume(twitch_manager: Arc<TwitchManager>) {
+    let happy_hour: HappyHour = match std::fs::read_to_string(state_path()) {
+        Ok(contents) => match serde_json::from_str(&contents) {
+            Ok(happy_hour) => happy_hour,
+            Err(e) => {
+                error!("Ignoring unreadable {}: {}", state_path(), e);
+                return;
+            }
+        },
+        Err(_) => return,
+    };
+
+    let ends_at = happy_hour.ends_at;
+    *twitch_manager.happy_hours.active.lock().unwrap() = Some(happy_hour.clone());
+    if ends_at <= Utc::now() {
+        info!("Happy hour ended while the bot was offline, restoring reward costs");
+        end(&twitch_manager).await;
+        return;
+    }
+
+    info!("Resuming happy hour until {}", ends_at);
+    for reward in &happy_hour.rewards {
+        set_cost(&twitch_manager, reward, reward.cost).await;
+    }
+    schedule_end(twitch_manager, ends_at);
+}
+
+pub fn describe(happy_hour: &HappyHour) -> String {
+    let rewards: Vec<String> = happy_hour.rewards.iter()
+        .map(|reward| format!("{} {} -> {}", reward.title, reward.original_cost, reward.cost))
+        .collect();
+    let minutes = (happy_hour.ends_at - Utc::now()).num_minutes().max(1);
+    format!("HAPPY HOUR for the next {} minutes! {}", minutes, rewards.join(" | "))
+}
+
+pub async fn announce(twitch_manager: &TwitchManager, message: &str) {
+    let channel = match &twitch_manager.config.twitch_channel_to_join {
+        Some(channel) => channel,
+        None => return,
+    };
+    if let Err(e) = twitch_manager.send_message_as_bot(channel, message).await {
+        error!("Failed to send happy hour message: {}", e);
+    }
+}
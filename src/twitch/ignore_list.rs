@@ -0,0 +1,18 @@
+use tracing::error;
+use crate::config::IgnoreListConfig;
+use crate::storage::StorageClient;
+
+// Checks the configured bot accounts first, then users added by !ignore or banned
+pub fn is_ignored(config: &IgnoreListConfig, storage: &StorageClient, user_id: &str, user_login: &str) -> bool {
+    if config.users.iter().any(|user| user.eq_ignore_ascii_case(user_login)) {
+        return true;
+    }
+
+    match storage.is_user_ignored(user_id, user_login) {
+        Ok(ignored) => ignored,
+        Err(e) => {
+            error!("Failed to check ignore list for {}: {:?}", user_login, e);
+            false
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use super::client::{TwitchIRCManager, TwitchIRCClientType};
 use std::sync::Arc;
-use log::{ error, warn};
-use tokio::sync::broadcast;
+use tracing::{ error, warn};
+use crate::backpressure::MonitoredReceiver;
 use twitch_irc::message::ServerMessage;
 
 pub struct TwitchBotClient {
@@ -41,7 +41,7 @@ impl TwitchBotClient {
         }
     }
 
-    pub fn subscribe(&self) -> broadcast::Receiver<ServerMessage> {
+    pub fn subscribe(&self) -> MonitoredReceiver<ServerMessage> {
         self.manager.subscribe()
     }
 
@@ -2,8 +2,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use chrono::{Duration};
-use log::{debug, error, info, warn};
-use tokio::sync::{RwLock, mpsc, broadcast, Mutex};
+use tracing::{debug, error, info, warn};
+use tokio::sync::{RwLock, mpsc, Mutex};
 use tokio::time::sleep;
 use twitch_irc::login::StaticLoginCredentials;
 use twitch_irc::TwitchIRCClient as TwitchIRC;
@@ -13,6 +13,11 @@ use twitch_irc::message::{ServerMessage, ClearChatAction};
 use crate::web_ui::websocket::{DashboardState, WebSocketMessage};
 use crate::config::{Config, SocialLinks};
 use crate::twitch::connection_monitor::ConnectionMonitor;
+use crate::backpressure::{BroadcastChannelConfig, MonitoredReceiver, MonitoredSender};
+use crate::output_filter::OutputFilter;
+use crate::twitch::irc::dedup::Deduplicator;
+use crate::twitch::irc::pacing::ChatPacing;
+use crate::tasks::TaskRegistry;
 pub type TwitchIRCClientType = TwitchIRC<SecureTCPTransport, StaticLoginCredentials>;
 
 
@@ -31,17 +36,19 @@ pub struct IRCClient {
     reconnect_attempts: Arc<AtomicU32>,
     channels: Arc<RwLock<Vec<String>>>,
     circuit_breaker: Arc<AtomicU32>,
+    output_filter: Arc<OutputFilter>,
 }
 
 impl IRCClient {
-    pub fn new(client: Arc<TwitchIRCClientType>, channels: Vec<String>) -> Self {
+    pub fn new(client: Arc<TwitchIRCClientType>, channels: Vec<String>, username: &str, output_filter: Arc<OutputFilter>) -> Self {
         Self {
             client,
-            monitor: Arc::new(Mutex::new(ConnectionMonitor::new())),
+            monitor: Arc::new(Mutex::new(ConnectionMonitor::new(&format!("Twitch IRC ({})", username)))),
             state: Arc::new(RwLock::new(ConnectionState::Disconnected)),
             reconnect_attempts: Arc::new(AtomicU32::new(0)),
             channels: Arc::new(RwLock::new(channels)),
             circuit_breaker: Arc::new(AtomicU32::new(0)),
+            output_filter,
         }
     }
 
@@ -179,7 +186,7 @@ impl IRCClient {
 
     pub async fn send_message(&self, channel: &str, message: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Sending message to channel {}: {}", channel, message);
-        match self.client.say(channel.to_string(), message.to_string()).await {
+        match self.client.say(channel.to_string(), self.output_filter.filter(message)).await {
             Ok(_) => {
                 debug!("Successfully sent message to channel {}", channel);
                 Ok(())
@@ -203,24 +210,35 @@ impl IRCClient {
 
 pub struct TwitchIRCManager {
     clients: RwLock<HashMap<String, IRCClient>>,
-    message_sender: broadcast::Sender<ServerMessage>,
+    message_sender: MonitoredSender<ServerMessage>,
     websocket_sender: mpsc::UnboundedSender<WebSocketMessage>,
     social_links: Arc<RwLock<SocialLinks>>,
     dashboard_state: Arc<RwLock<DashboardState>>,
     config: Arc<Config>,
+    tasks: Arc<TaskRegistry>,
+    pacing: Arc<ChatPacing>,
+    dedup: Deduplicator,
+    output_filter: Arc<OutputFilter>,
+    #[cfg(feature = "bench")]
+    captured: Option<Arc<std::sync::atomic::AtomicU64>>,
 }
 
 impl Default for TwitchIRCManager {
     fn default() -> Self {
-        let (tx, _) = broadcast::channel(1000);
         let (websocket_tx, _) = mpsc::unbounded_channel();
         Self {
             clients: RwLock::new(HashMap::new()),
-            message_sender: tx,
+            message_sender: MonitoredSender::new("irc", &BroadcastChannelConfig::with_capacity(1000)),
             websocket_sender: websocket_tx,
             social_links: Arc::new(RwLock::new(SocialLinks::default())),
             dashboard_state: Arc::new(RwLock::new(DashboardState::default())),
             config: Arc::new(Config::default()),
+            tasks: Arc::new(TaskRegistry::new()),
+            pacing: Arc::new(ChatPacing::new()),
+            dedup: Deduplicator::new(),
+            output_filter: Arc::new(OutputFilter::default()),
+            #[cfg(feature = "bench")]
+            captured: None,
         }
     }
 }
@@ -231,8 +249,10 @@ impl TwitchIRCManager {
         social_links: Arc<RwLock<SocialLinks>>,
         dashboard_state: Arc<RwLock<DashboardState>>,
         config: Arc<Config>,
+        tasks: Arc<TaskRegistry>,
+        output_filter: Arc<OutputFilter>,
     ) -> Self {
-        let (message_sender, _) = broadcast::channel(1000);
+        let message_sender = MonitoredSender::new("irc", &config.broadcast.irc);
         TwitchIRCManager {
             clients: RwLock::new(HashMap::new()),
             message_sender,
@@ -240,9 +260,22 @@ impl TwitchIRCManager {
             social_links,
             dashboard_state,
             config,
+            tasks,
+            pacing: Arc::new(ChatPacing::new()),
+            dedup: Deduplicator::new(),
+            output_filter,
+            #[cfg(feature = "bench")]
+            captured: None,
         }
     }
 
+    // For bench runs: outgoing messages are counted here and dropped instead of sent
+    #[cfg(feature = "bench")]
+    pub(crate) fn capture_output(mut self, counter: Arc<std::sync::atomic::AtomicU64>) -> Self {
+        self.captured = Some(counter);
+        self
+    }
+
     pub async fn shutdown(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Shutting down TwitchIRCManager...");
         let clients = self.clients.read().await;
@@ -275,7 +308,7 @@ impl TwitchIRCManager {
         info!("Twitch IRC client created successfully for user: {}", username);
 
         let client = Arc::new(client);
-        let irc_client = IRCClient::new(client.clone(), channels.clone());
+        let irc_client = IRCClient::new(client.clone(), channels.clone(), &username, self.output_filter.clone());
 
         self.clients.write().await.insert(username.clone(), irc_client.clone());
         if handle_messages && username == *self.config.twitch_bot_username.as_ref().unwrap() {
@@ -285,7 +318,8 @@ impl TwitchIRCManager {
             let irc_client_clone = irc_client.clone();
             let dashboard_state = self.dashboard_state.clone();
             let bot_username = self.config.twitch_bot_username.as_ref().unwrap().clone();
-            tokio::spawn(async move {
+            let pacing = self.pacing.clone();
+            self.tasks.spawn(&format!("irc_messages_{}", username), async move {
                 Self::handle_client_messages(
                     username_clone,
                     incoming_messages,
@@ -294,6 +328,7 @@ impl TwitchIRCManager {
                     irc_client_clone,
                     dashboard_state,
                     bot_username,
+                    pacing,
                 ).await;
             });
         }
@@ -307,11 +342,12 @@ impl TwitchIRCManager {
     pub async fn handle_client_messages(
         username: String,
         mut incoming_messages: mpsc::UnboundedReceiver<ServerMessage>,
-        message_sender: broadcast::Sender<ServerMessage>,
+        message_sender: MonitoredSender<ServerMessage>,
         websocket_sender: mpsc::UnboundedSender<WebSocketMessage>,
         irc_client: IRCClient,
         dashboard_state: Arc<RwLock<DashboardState>>,
         bot_username: String,
+        pacing: Arc<ChatPacing>,
     ) {
         while let Some(message) = incoming_messages.recv().await {
             debug!("Received message in handle_client_messages for user {}: {:?}", username, message);
@@ -335,9 +371,7 @@ impl TwitchIRCManager {
                         }
 
                         // Broadcast the message only for the bot client
-                        if let Err(e) = message_sender.send(message.clone()) {
-                            debug!("Failed to broadcast message: {:?}", e);
-                        }
+                        message_sender.send(message.clone()).await;
                     }
                 },
                 ServerMessage::Whisper(msg) => {
@@ -356,9 +390,7 @@ impl TwitchIRCManager {
                         }
 
                         // Broadcast the whisper only for the bot client
-                        if let Err(e) = message_sender.send(message.clone()) {
-                            error!("Failed to broadcast whisper: {:?}", e);
-                        }
+                        message_sender.send(message.clone()).await;
                     }
                 },
                 ServerMessage::Reconnect(_) => {
@@ -385,6 +417,9 @@ impl TwitchIRCManager {
                         if let Err(e) = websocket_sender.send(websocket_message) {
                             error!("Failed to send join message to WebSocket: {:?}", e);
                         }
+
+                        // For the follow guard's join-burst check
+                        message_sender.send(message.clone()).await;
                     }
                 },
                 ServerMessage::Part(msg) => {
@@ -402,6 +437,10 @@ impl TwitchIRCManager {
                         }
                     }
                 },
+                ServerMessage::UserState(msg) => {
+                    let privileged = msg.badges.iter().any(|badge| badge.name == "moderator" || badge.name == "broadcaster");
+                    pacing.update_channel_status(&username, &msg.channel_login, privileged);
+                },
                 _ => {
                     // Handle other message types if needed
                 }
@@ -570,15 +609,34 @@ impl TwitchIRCManager {
     }
 
     pub async fn send_message(&self, username: &str, channel: &str, message: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        #[cfg(feature = "bench")]
+        if let Some(captured) = &self.captured {
+            captured.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
         if let Some(client) = self.get_client(username).await {
-            client.say(channel.to_string(), message.to_string()).await?;
+            self.pacing.wait_turn(&self.config.chat_rate, username, channel).await;
+            let text = self.dedup.deduplicate(username, channel, &self.output_filter.filter(message));
+            client.say(channel.to_string(), text).await?;
             Ok(())
         } else {
             Err("Client not found".into())
         }
     }
 
-    pub fn subscribe(&self) -> broadcast::Receiver<ServerMessage> {
+    pub fn get_dashboard_state(&self) -> Arc<RwLock<DashboardState>> {
+        self.dashboard_state.clone()
+    }
+
+    pub fn get_output_filter(&self) -> Arc<OutputFilter> {
+        self.output_filter.clone()
+    }
+
+    pub fn channel_metrics(&self) -> (String, serde_json::Value) {
+        self.message_sender.metrics_snapshot()
+    }
+
+    pub fn subscribe(&self) -> MonitoredReceiver<ServerMessage> {
         self.message_sender.subscribe()
     }
 
@@ -1,17 +1,22 @@
 use std::collections::HashMap;
 use std::sync::Arc;
-use log::{debug};
-use tokio::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+use tokio::sync::{Mutex, OnceCell, RwLock, Semaphore};
 use twitch_irc::message::PrivmsgMessage;
 
 use crate::ai::AIClient;
-use crate::config::Config;
+use crate::config::{CommandTier, Config};
 use crate::discord::UserLinks;
-use crate::storage::StorageClient;
+use crate::storage::{CustomCommand, StorageClient};
 use crate::twitch::irc::TwitchBotClient;
+use crate::twitch::command_config::CommandConfig;
+use crate::twitch::command_usage::{self, Outcome};
+use crate::twitch::custom_commands;
+use crate::twitch::opt_out;
 use crate::twitch::manager::TwitchManager;
 use crate::twitch::redeems::RedeemManager;
-use crate::twitch::roles::{get_user_role, UserRole};
+use crate::twitch::roles::{get_user_role, is_subscriber, is_vip, UserRole};
 use crate::vrchat::VRChatManager;
 use crate::vrchat::models::World;
 use crate::obs::OBSManager;
@@ -26,7 +31,7 @@ pub struct CommandContext {
     pub storage: Arc<RwLock<StorageClient>>,
     pub user_links: Arc<UserLinks>,
     pub config: Arc<RwLock<Config>>,
-    pub vrchat_manager: Arc<VRChatManager>,
+    pub vrchat_manager: Option<Arc<VRChatManager>>,
     pub ai_client: Option<Arc<AIClient>>,
     pub is_stream_online: bool,
     pub obs_manager: Arc<OBSManager>,
@@ -38,16 +43,94 @@ pub trait Command: Send + Sync {
     fn description(&self) -> &'static str;
     async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
     fn required_role(&self) -> UserRole;
+    fn required_tier(&self) -> Option<CommandTier> {
+        None
+    }
+    // Heavy commands (AI calls, multiple API requests) share a limited number of slots
+    fn is_heavy(&self) -> bool {
+        false
+    }
+    // Commands that send the caller's own words to an AI, refused for viewers who ran !optout
+    fn uses_ai(&self) -> bool {
+        false
+    }
+    // What the command would do with these arguments, for "--simulate". Must not change anything;
+    // None when the command has no dry run.
+    async fn simulate(&self, _ctx: &CommandContext, _args: Vec<String>) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(None)
+    }
+    // Shared by everyone in chat; mods and the broadcaster skip it
+    fn cooldown_secs(&self) -> u64 {
+        0
+    }
+    // Arguments as shown by "--help", e.g. "<name> [amount]"
+    fn usage(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+// Added after a command's arguments by a mod, e.g. "!nuke spam 5 600 --simulate"
+const SIMULATE_FLAG: &str = "--simulate";
+// "!sound --help" replies with the description and usage instead of running the command
+const HELP_FLAG: &str = "--help";
+
+// A set of chat commands added without touching commands/mod.rs, e.g. by a crate embedding the bot
+// (see MewBotBuilder::plugin). Roles, tiers, cooldowns, --help and --simulate are handled by the
+// registry exactly as for built-in commands; built-in names can't be taken over.
+pub trait CommandPlugin: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn commands(&self) -> Vec<Arc<dyn Command>>;
+}
+
+struct LoadedPlugin {
+    name: &'static str,
+    commands: HashMap<String, Arc<dyn Command>>,
+}
+
+fn describe_command(command: &dyn Command, plugin: Option<&str>) -> CommandInfo {
+    CommandInfo {
+        name: command.name().to_string(),
+        description: command.description().to_string(),
+        required_role: command.required_role(),
+        tier: command.required_tier(),
+        cooldown_secs: command.cooldown_secs(),
+        usage: command.usage().map(str::to_string),
+        plugin: plugin.map(str::to_string),
+    }
+}
+
+// Static description of a registered command, used for the public commands page
+#[derive(Debug, Clone)]
+pub struct CommandInfo {
+    pub name: String,
+    pub description: String,
+    pub required_role: UserRole,
+    pub tier: Option<CommandTier>,
+    pub cooldown_secs: u64,
+    pub usage: Option<String>,
+    // The plugin that added it, None for built-in commands
+    pub plugin: Option<String>,
 }
 
 pub struct CommandRegistry {
     commands: HashMap<String, Box<dyn Command>>,
+    // Can change while the bot runs, unlike the built-in commands
+    plugins: std::sync::RwLock<Vec<LoadedPlugin>>,
+    // Name -> response for commands added with !addcom, so chat doesn't hit the database for every "!"
+    custom: std::sync::RwLock<HashMap<String, String>>,
+    heavy_permits: OnceCell<Semaphore>,
+    // Command name -> when it last ran for someone who's held to its cooldown
+    last_run: std::sync::Mutex<HashMap<String, Instant>>,
 }
 
 impl CommandRegistry {
     pub fn new() -> Self {
         Self {
             commands: HashMap::new(),
+            plugins: std::sync::RwLock::new(Vec::new()),
+            custom: std::sync::RwLock::new(HashMap::new()),
+            heavy_permits: OnceCell::new(),
+            last_run: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
@@ -55,25 +138,311 @@ impl CommandRegistry {
         self.commands.insert(command.name().to_string(), command);
     }
 
+    // Replaces a plugin of the same name, so a plugin can be reloaded
+    pub fn register_plugin(&self, plugin: &dyn CommandPlugin) -> Result<(), String> {
+        let mut plugins = self.plugins.write().unwrap();
+        let mut commands = HashMap::new();
+        for command in plugin.commands() {
+            let name = command.name().to_lowercase();
+            if !name.starts_with('!') || name.len() < 2 || name.contains(char::is_whitespace) {
+                return Err(format!("{} isn't a valid command name; it needs a ! and no spaces", name));
+            }
+            if self.commands.contains_key(&name) {
+                return Err(format!("{} is a built-in command", name));
+            }
+            if let Some(other) = plugins.iter().find(|other| other.name != plugin.name() && other.commands.contains_key(&name)) {
+                return Err(format!("{} is already registered by plugin {}", name, other.name));
+            }
+            commands.insert(name, command);
+        }
+
+        info!("Registered command plugin {} with {} commands", plugin.name(), commands.len());
+        plugins.retain(|other| other.name != plugin.name());
+        plugins.push(LoadedPlugin { name: plugin.name(), commands });
+        Ok(())
+    }
+
+    pub fn unregister_plugin(&self, name: &str) -> bool {
+        let mut plugins = self.plugins.write().unwrap();
+        let before = plugins.len();
+        plugins.retain(|plugin| plugin.name != name);
+        plugins.len() < before
+    }
+
+    // Custom commands are kept in memory; whatever adds, edits or removes one in storage updates them here too
+    pub fn load_custom(&self, commands: Vec<CustomCommand>) {
+        *self.custom.write().unwrap() = commands.into_iter().map(|command| (command.name, command.response)).collect();
+    }
+
+    pub fn set_custom(&self, name: &str, response: &str) {
+        self.custom.write().unwrap().insert(name.to_string(), response.to_string());
+    }
+
+    pub fn remove_custom(&self, name: &str) {
+        self.custom.write().unwrap().remove(name);
+    }
+
+    fn plugin_command(&self, name: &str) -> Option<Arc<dyn Command>> {
+        let name = name.to_lowercase();
+        self.plugins.read().unwrap().iter().find_map(|plugin| plugin.commands.get(&name).cloned())
+    }
+
+    // Sorted by name, plugin commands included; tier overrides from the config are applied by the caller
+    pub fn describe(&self) -> Vec<CommandInfo> {
+        let mut commands: Vec<CommandInfo> = self.commands.values()
+            .map(|command| describe_command(command.as_ref(), None))
+            .collect();
+        for plugin in self.plugins.read().unwrap().iter() {
+            commands.extend(plugin.commands.values().map(|command| describe_command(command.as_ref(), Some(plugin.name))));
+        }
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        commands
+    }
+
+    #[tracing::instrument(name = "command", skip_all, fields(command = name, user = %ctx.msg.sender.login, channel = %ctx.channel))]
     pub async fn execute(&self, name: &str, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        if let Some(command) = self.commands.get(name) {
-            debug!("Executing command '{}' for user '{}'", name, ctx.msg.sender.name);
+        let from_plugin;
+        let command: &dyn Command = match self.commands.get(name) {
+            Some(command) => command.as_ref(),
+            None => match self.plugin_command(name) {
+                Some(command) => {
+                    from_plugin = command;
+                    from_plugin.as_ref()
+                }
+                None => return self.run_custom(name, ctx, args).await,
+            },
+        };
+        let settings = ctx.twitch_manager.command_overrides.get(command.name());
+        if !settings.enabled {
+            debug!("Command '{}' is turned off", name);
+            return Ok(());
+        }
+        if let Some(remaining) = ctx.twitch_manager.command_abuse.soft_ban_remaining(&ctx.msg.sender.id) {
+            debug!("Ignoring '{}' from soft-banned user '{}' ({}s left)", name, ctx.msg.sender.name, remaining.as_secs());
+            return Ok(());
+        }
+
+        if args.last().map_or(false, |arg| arg.eq_ignore_ascii_case(SIMULATE_FLAG)) {
             let user_role = get_user_role(&ctx.msg.sender.id, &ctx.twitch_manager, Some(&ctx.msg.badges)).await?;
+            // Viewers don't get dry runs; for them it's just another argument
+            if user_role >= UserRole::Moderator {
+                let mut args = args;
+                args.pop();
+                return self.simulate(name, command, &settings, ctx, args, &user_role).await;
+            }
+        }
+
+        if command.uses_ai() && opt_out::is_opted_out(&*ctx.storage.read().await, &ctx.msg.sender.id) {
+            let response = format!("@{}, you've opted out of AI features, use !optin to turn them back on.", ctx.msg.sender.name);
+            ctx.bot_client.send_message(&ctx.channel, &response).await?;
+            return Ok(());
+        }
 
-            debug!("User role: {:?}, Required role: {:?}", user_role, command.required_role());
+        debug!("Executing command '{}' for user '{}'", name, ctx.msg.sender.name);
+        let started = Instant::now();
+        let arg_count = args.len();
+        let user_role = get_user_role(&ctx.msg.sender.id, &ctx.twitch_manager, Some(&ctx.msg.badges)).await?;
+        let result = self.run(name, command, &settings, ctx, args, &user_role).await;
 
-            if user_role >= command.required_role() {
-                debug!("User has sufficient role. Executing command.");
-                command.execute(ctx, args).await
+        let outcome = match &result {
+            Ok(true) => Outcome::Ok,
+            Ok(false) => Outcome::Denied,
+            Err(_) => Outcome::Error,
+        };
+        let elapsed = started.elapsed();
+        let threshold = Duration::from_millis(ctx.config.read().await.command_metrics.slow_command_ms);
+        let slow = elapsed > threshold;
+        ctx.twitch_manager.command_latency.record_command(name, elapsed, slow);
+        if slow {
+            warn!(
+                elapsed_ms = elapsed.as_millis() as u64,
+                args = arg_count,
+                role = ?user_role,
+                heavy = command.is_heavy(),
+                outcome = outcome.as_str(),
+                "Slow command '{}' from {} in {} took {:?} (threshold {:?})",
+                name, ctx.msg.sender.login, ctx.channel, elapsed, threshold
+            );
+        }
+        command_usage::record(ctx, name, outcome, user_role >= UserRole::Moderator).await;
+        result.map(|_| ())
+    }
+
+    // Commands added with !addcom, looked up last so they can never shadow a built-in or plugin command.
+    // Entries in command_config.json apply to them the same way as to built-ins.
+    async fn run_custom(&self, name: &str, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // Every chat message comes through here; custom commands always start with !
+        if !name.starts_with('!') {
+            return Ok(());
+        }
+        let name = name.to_lowercase();
+        let response = self.custom.read().unwrap().get(&name).cloned();
+        let response = match response {
+            Some(response) => response,
+            None => {
+                debug!("Command '{}' not found", name);
+                return Ok(());
+            }
+        };
+        let settings = ctx.twitch_manager.command_overrides.get(&name);
+        if !settings.enabled || ctx.twitch_manager.command_abuse.soft_ban_remaining(&ctx.msg.sender.id).is_some() {
+            return Ok(());
+        }
+
+        let user_role = get_user_role(&ctx.msg.sender.id, &ctx.twitch_manager, Some(&ctx.msg.badges)).await?;
+        let required_role = settings.required_role.clone().unwrap_or(UserRole::Viewer);
+        let cooldown_secs = settings.cooldown_secs.unwrap_or(custom_commands::DEFAULT_COOLDOWN_SECS);
+        let allowed = user_role >= required_role
+            && (user_role >= UserRole::Moderator || self.start_cooldown(&name, cooldown_secs));
+        if allowed {
+            let count = ctx.storage.read().await.count_custom_command_use(&name)?;
+            let response = custom_commands::render(ctx, &response, &args, count).await;
+            ctx.bot_client.send_message(&ctx.channel, &response).await?;
+        } else {
+            debug!("Custom command '{}' denied or cooling down for {}", name, ctx.msg.sender.login);
+        }
+        let outcome = if allowed { Outcome::Ok } else { Outcome::Denied };
+        command_usage::record(ctx, &name, outcome, user_role >= UserRole::Moderator).await;
+        Ok(())
+    }
+
+    // Ok(false) when the user was denied the command
+    async fn run(&self, name: &str, command: &dyn Command, settings: &CommandConfig, ctx: &CommandContext, args: Vec<String>, user_role: &UserRole) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let required_role = required_role(command, settings);
+        debug!("User role: {:?}, Required role: {:?}", user_role, required_role);
+
+        if *user_role >= required_role {
+            if let Some(denial) = self.check_tier(name, command, ctx, user_role).await {
+                debug!("User is not in the required tier. Sending denial message.");
+                ctx.bot_client.send_message(&ctx.channel, &denial).await?;
+                return Ok(false);
+            }
+            let cooldown_secs = settings.cooldown_secs.unwrap_or_else(|| command.cooldown_secs());
+            if *user_role < UserRole::Moderator && !self.start_cooldown(name, cooldown_secs) {
+                debug!("Command '{}' is on cooldown", name);
+                return Ok(false);
+            }
+            // Only after the role check and cooldown, so viewers can't use it to spam chat
+            if args.len() == 1 && args[0].eq_ignore_ascii_case(HELP_FLAG) {
+                let help = match command.usage() {
+                    Some(usage) => format!("{} {}: {}", name, usage, command.description()),
+                    None => format!("{}: {}", name, command.description()),
+                };
+                ctx.bot_client.send_message(&ctx.channel, &help).await?;
+                return Ok(true);
+            }
+            debug!("User has sufficient role. Executing command.");
+            if command.is_heavy() {
+                let permits = self.heavy_permits.get_or_init(|| async {
+                    Semaphore::new(ctx.config.read().await.message_workers.max_heavy_commands.max(1))
+                }).await;
+                let _permit = permits.acquire().await?;
+                command.execute(ctx, args).await?;
             } else {
-                debug!("User does not have sufficient role. Sending error message.");
-                let response = format!("@{}, this command is only available to {:?}s and above.", ctx.msg.sender.name, command.required_role());
-                ctx.bot_client.send_message(&ctx.channel, &response).await?;
-                Ok(())
+                command.execute(ctx, args).await?;
             }
+            Ok(true)
         } else {
-            debug!("Command '{}' not found", name);
-            Ok(())
+            debug!("User does not have sufficient role. Sending error message.");
+            let response = if required_role == UserRole::Subscriber {
+                format_denial(&ctx.config.read().await.command_tiers.subscriber_denial_message, name, ctx)
+            } else {
+                format!("@{}, this command is only available to {:?}s and above.", ctx.msg.sender.name, required_role)
+            };
+            ctx.bot_client.send_message(&ctx.channel, &response).await?;
+            Ok(false)
+        }
+    }
+
+    // Reports what the command would do instead of running it
+    async fn simulate(&self, name: &str, command: &dyn Command, settings: &CommandConfig, ctx: &CommandContext, args: Vec<String>, user_role: &UserRole) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let required_role = required_role(command, settings);
+        let report = if *user_role < required_role {
+            format!("{} needs {:?} or above, so it would be refused.", name, required_role)
+        } else {
+            match command.simulate(ctx, args).await {
+                Ok(Some(report)) => report,
+                Ok(None) => format!("{} has no dry run, so nothing was done.", name),
+                Err(e) => format!("{} would fail: {}", name, e),
+            }
+        };
+        info!("{} simulated {}: {}", ctx.msg.sender.login, name, report);
+        ctx.bot_client.send_message(&ctx.channel, &format!("[simulate] {}", report)).await?;
+        Ok(())
+    }
+
+    // False while the command is still cooling down; otherwise starts the next cooldown
+    fn start_cooldown(&self, name: &str, cooldown_secs: u64) -> bool {
+        if cooldown_secs == 0 {
+            return true;
+        }
+        let mut last_run = self.last_run.lock().unwrap();
+        if let Some(last) = last_run.get(name) {
+            if last.elapsed() < Duration::from_secs(cooldown_secs) {
+                return false;
+            }
+        }
+        last_run.insert(name.to_string(), Instant::now());
+        true
+    }
+
+    // Returns the denial message if the user doesn't meet the command's sub/VIP tier
+    async fn check_tier(&self, name: &str, command: &dyn Command, ctx: &CommandContext, user_role: &UserRole) -> Option<String> {
+        let config = ctx.config.read().await;
+        let tier = config.command_tiers.tiers.get(name).copied().or_else(|| command.required_tier())?;
+
+        // Mods and the broadcaster are never gated by tiers
+        if *user_role >= UserRole::Moderator {
+            return None;
+        }
+
+        let (allowed, template) = match tier {
+            CommandTier::Subscriber => (is_subscriber(&ctx.msg.badges), &config.command_tiers.subscriber_denial_message),
+            CommandTier::Vip => (is_vip(&ctx.msg.badges), &config.command_tiers.vip_denial_message),
+        };
+        if allowed {
+            return None;
+        }
+
+        Some(format_denial(template, name, ctx))
+    }
+}
+
+// The dashboard's override, or the command's own
+fn required_role(command: &dyn Command, settings: &CommandConfig) -> UserRole {
+    settings.required_role.clone().unwrap_or_else(|| command.required_role())
+}
+
+fn format_denial(template: &str, name: &str, ctx: &CommandContext) -> String {
+    template
+        .replace("{user}", &ctx.msg.sender.name)
+        .replace("{command}", name)
+        .replace("{sub_link}", &format!("https://www.twitch.tv/subs/{}", ctx.channel))
+}
+
+// Splits arguments on spaces, keeping "quoted phrases" together
+pub fn split_quoted(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in text.chars() {
+        match c {
+            '"' => {
+                if quoted && !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                quoted = !quoted;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
         }
     }
-}
\ No newline at end of file
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
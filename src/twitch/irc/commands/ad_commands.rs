@@ -95,7 +95,7 @@ impl Command for StartAdCommand {
         let bot_client = ctx.bot_client.clone();
         let channel = ctx.channel.clone();
         let ad_manager_clone = ad_manager.clone();
-        tokio::spawn(async move {
+        ctx.twitch_manager.tasks.spawn("ad_loop", async move {
             loop {
                 sleep(std::time::Duration::from_secs((interval_minutes * 60) as u64)).await;
                 let ad_manager_lock = ad_manager_clone.read().await;
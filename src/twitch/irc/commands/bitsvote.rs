@@ -0,0 +1,55 @@
+use crate::twitch::bits_vote;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::quick_poll::{self, MAX_OPTIONS};
+use crate::twitch::roles::UserRole;
+
+pub struct BitsVoteCommand;
+
+#[async_trait::async_trait]
+impl Command for BitsVoteCommand {
+    fn name(&self) -> &'static str {
+        "!bitsvote"
+    }
+
+    fn description(&self) -> &'static str {
+        "Starts a vote decided by cheers: !bitsvote \"Question?\" option1 option2 ..., !bitsvote end to close it early"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match args.first().map(|a| a.to_lowercase()).as_deref() {
+            Some("end") => match ctx.twitch_manager.bits_votes.end() {
+                Some((summary, osc_parameter)) => {
+                    bits_vote::finish(&ctx.twitch_manager, &summary, &osc_parameter).await;
+                    return Ok(());
+                }
+                None => "There's no bits vote running.".to_string(),
+            },
+            _ => match quick_poll::parse_args(&args) {
+                Some((question, options)) => {
+                    let config = ctx.config.read().await.bits_vote.clone();
+                    let duration = config.default_duration_secs;
+                    match bits_vote::start(ctx.twitch_manager.clone(), &config, question, options, duration) {
+                        Some(summary) => {
+                            bits_vote::publish(&ctx.twitch_manager, &summary).await;
+                            format!(
+                                "Bits vote: {} Cheer with the option's name or number, e.g. cheer100 1: {} (closes in {}s)",
+                                summary.question,
+                                bits_vote::describe_options(&summary),
+                                duration,
+                            )
+                        }
+                        None => "A bits vote is already running, use !bitsvote end first.".to_string(),
+                    }
+                }
+                None => format!("Usage: !bitsvote \"Question?\" option1 option2 ... (2 to {} options)", MAX_OPTIONS),
+            },
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
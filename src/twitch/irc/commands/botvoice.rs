@@ -0,0 +1,44 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct BotVoiceCommand;
+
+#[async_trait::async_trait]
+impl Command for BotVoiceCommand {
+    fn name(&self) -> &'static str {
+        "!botvoice"
+    }
+
+    fn description(&self) -> &'static str {
+        "Switches the bot's reply style: !botvoice <name>, or !botvoice to list voices"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let voice = &ctx.twitch_manager.bot_voice;
+        let requested = match args.first() {
+            Some(name) => name,
+            None => {
+                let message = format!("Current voice: {}. Available: {}", voice.active(), voice.available().join(", "));
+                ctx.bot_client.send_message(&ctx.channel, &message).await?;
+                return Ok(());
+            }
+        };
+
+        let message = match voice.set_active(requested) {
+            Some(name) => {
+                let mut config = ctx.config.write().await;
+                config.bot_voice.active = name.clone();
+                config.save()?;
+                voice.render("voice_changed", &[("voice", name.as_str())])
+            }
+            None => format!("Unknown voice '{}'. Available: {}", requested, voice.available().join(", ")),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
@@ -0,0 +1,49 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+use crate::twitch::stream_breaks::{format_duration, publish_uptime};
+
+pub struct BrbCommand;
+
+#[async_trait::async_trait]
+impl Command for BrbCommand {
+    fn name(&self) -> &'static str {
+        "!brb"
+    }
+
+    fn description(&self) -> &'static str {
+        "Marks a planned break so it doesn't count towards active uptime: !brb start [reason], !brb stop"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let state_machine = &ctx.twitch_manager.stream_state_machine;
+
+        let message = match args.first().map(|a| a.to_lowercase()).as_deref() {
+            Some("start") => {
+                let reason = if args.len() > 1 { Some(args[1..].join(" ")) } else { None };
+                if state_machine.start_break(reason.clone()).await {
+                    match reason {
+                        Some(reason) => format!("Taking a short break ({}), be right back!", reason),
+                        None => "Taking a short break, be right back!".to_string(),
+                    }
+                } else if !ctx.twitch_manager.is_stream_live().await {
+                    "The stream isn't live right now.".to_string()
+                } else {
+                    "Already on a break! Use !brb stop when you're back.".to_string()
+                }
+            }
+            Some("stop") => match state_machine.end_break().await {
+                Some(length) => format!("Welcome back! That break lasted {}.", format_duration(length.num_seconds())),
+                None => "There's no break in progress.".to_string(),
+            },
+            _ => "Usage: !brb start [reason] | !brb stop".to_string(),
+        };
+
+        publish_uptime(&ctx.twitch_manager).await;
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
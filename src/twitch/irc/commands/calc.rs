@@ -1,8 +1,37 @@
+use crate::calculator;
 use crate::twitch::irc::command_system::{Command, CommandContext};
 use crate::twitch::roles::UserRole;
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Per user, shared by !calc and !roll so neither can be used to flood chat
+const RATE_LIMIT: Duration = Duration::from_secs(3);
+
+#[derive(Default)]
+pub struct CalcRateLimit {
+    last_use: Mutex<HashMap<String, Instant>>,
+}
+
+impl CalcRateLimit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn rate_limited(&self, user_id: &str) -> bool {
+        let mut last_use = self.last_use.lock().unwrap();
+        let now = Instant::now();
+        last_use.retain(|_, used| now.duration_since(*used) < RATE_LIMIT);
+        if last_use.contains_key(user_id) {
+            return true;
+        }
+        last_use.insert(user_id.to_string(), now);
+        false
+    }
+}
 
 pub struct CalcCommand;
+pub struct RollCommand;
 
 #[async_trait::async_trait]
 impl Command for CalcCommand {
@@ -11,33 +40,24 @@ impl Command for CalcCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Calculates a mathematical expression"
+        "Calculates an expression, with percentages and unit conversions: !calc 20% of 150, !calc 5mi in km"
     }
 
     async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         if args.is_empty() {
-            ctx.bot_client.send_message(&ctx.channel, "Usage: !calc <expression>").await?;
+            ctx.bot_client.send_message(&ctx.channel, "Usage: !calc <expression>, e.g. !calc (3+4)*2, !calc 80 + 10%, !calc 100f in c").await?;
+            return Ok(());
+        }
+        if ctx.twitch_manager.calc_rate_limit.rate_limited(&ctx.msg.sender.id) {
             return Ok(());
         }
-
-        let mut constants = HashMap::new();
-        constants.insert("pi".to_string(), std::f64::consts::PI);
-        constants.insert("e".to_string(), std::f64::consts::E);
-        constants.insert("phi".to_string(), (1.0 + 5.0_f64.sqrt()) / 2.0);
-        constants.insert("tau".to_string(), std::f64::consts::TAU);
 
         let expression = args.join(" ");
-
-        match tokenize(&expression, &constants).and_then(|tokens| evaluate(&tokens, &constants)) {
-            Ok(result) => {
-                let response = format!("Result: {}", result);
-                ctx.bot_client.send_message(&ctx.channel, &response).await?;
-            },
-            Err(e) => {
-                let error_message = format!("Error: {}", e);
-                ctx.bot_client.send_message(&ctx.channel, &error_message).await?;
-            }
-        }
+        let response = match calculator::calculate(&expression) {
+            Ok(result) => format!("@{} {}", ctx.msg.sender.name, result),
+            Err(e) => format!("@{} Error: {}", ctx.msg.sender.name, e),
+        };
+        ctx.bot_client.send_message(&ctx.channel, &response).await?;
 
         Ok(())
     }
@@ -47,143 +67,33 @@ impl Command for CalcCommand {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum Token {
-    Number(f64),
-    Constant(String),
-    Plus,
-    Minus,
-    Multiply,
-    Divide,
-    Power,
-    LeftParen,
-    RightParen,
-}
+#[async_trait::async_trait]
+impl Command for RollCommand {
+    fn name(&self) -> &'static str {
+        "!roll"
+    }
 
-fn tokenize(input: &str, constants: &HashMap<String, f64>) -> Result<Vec<Token>, String> {
-    let mut tokens = Vec::new();
-    let mut chars = input.chars().peekable();
-
-    while let Some(ch) = chars.next() {
-        match ch {
-            '0'..='9' | '.' => {
-                let mut num = String::new();
-                num.push(ch);
-                while let Some(&next_ch) = chars.peek() {
-                    if next_ch.is_digit(10) || next_ch == '.' {
-                        num.push(chars.next().unwrap());
-                    } else {
-                        break;
-                    }
-                }
-                tokens.push(Token::Number(num.parse().map_err(|e| format!("Invalid number: {}", e))?));
-            },
-            'a'..='z' | 'A'..='Z' | 'π' | 'φ' | 'τ' => {
-                let mut name = String::new();
-                name.push(ch);
-                while let Some(&next_ch) = chars.peek() {
-                    if next_ch.is_alphanumeric() || next_ch == 'π' || next_ch == 'φ' || next_ch == 'τ' {
-                        name.push(chars.next().unwrap());
-                    } else {
-                        break;
-                    }
-                }
-                let constant_name = match name.as_str() {
-                    "π" => "pi",
-                    "φ" => "phi",
-                    "τ" => "tau",
-                    _ => &name,
-                };
-                if constants.contains_key(constant_name) {
-                    tokens.push(Token::Constant(constant_name.to_string()));
-                } else {
-                    return Err(format!("Unknown constant: {}", name));
-                }
-            },
-            '+' => tokens.push(Token::Plus),
-            '-' => tokens.push(Token::Minus),
-            '*' => tokens.push(Token::Multiply),
-            '/' => tokens.push(Token::Divide),
-            '^' => tokens.push(Token::Power),
-            '(' => tokens.push(Token::LeftParen),
-            ')' => tokens.push(Token::RightParen),
-            ' ' => {},
-            _ => return Err(format!("Invalid character: {}", ch)),
-        }
+    fn description(&self) -> &'static str {
+        "Rolls dice: !roll 3d6+2, !roll d20 (the default)"
     }
-    Ok(tokens)
-}
 
-fn evaluate(tokens: &[Token], constants: &HashMap<String, f64>) -> Result<f64, String> {
-    let mut output = Vec::new();
-    let mut operators = Vec::new();
-
-    for token in tokens.iter() {
-        match token {
-            Token::Number(n) => output.push(*n),
-            Token::Constant(name) => {
-                if let Some(&value) = constants.get(name) {
-                    output.push(value);
-                } else {
-                    return Err(format!("Unknown constant: {}", name));
-                }
-            },
-            Token::Plus | Token::Minus => {
-                while let Some(op) = operators.last() {
-                    if *op != Token::LeftParen {
-                        apply_operator(&mut output, operators.pop().unwrap())?;
-                    } else {
-                        break;
-                    }
-                }
-                operators.push(token.clone());
-            },
-            Token::Multiply | Token::Divide => {
-                while let Some(op) = operators.last() {
-                    if matches!(op, Token::Multiply | Token::Divide | Token::Power) {
-                        apply_operator(&mut output, operators.pop().unwrap())?;
-                    } else {
-                        break;
-                    }
-                }
-                operators.push(token.clone());
-            },
-            Token::Power => operators.push(token.clone()),
-            Token::LeftParen => operators.push(token.clone()),
-            Token::RightParen => {
-                while let Some(op) = operators.pop() {
-                    if op == Token::LeftParen {
-                        break;
-                    }
-                    apply_operator(&mut output, op)?;
-                }
-            },
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if ctx.twitch_manager.calc_rate_limit.rate_limited(&ctx.msg.sender.id) {
+            return Ok(());
         }
-    }
 
-    while let Some(op) = operators.pop() {
-        apply_operator(&mut output, op)?;
+        let spec = args.join("");
+        let result = calculator::roll(&spec, &mut rand::thread_rng());
+        let response = match result {
+            Ok(result) => format!("@{} {}", ctx.msg.sender.name, calculator::describe_roll(&spec, &result)),
+            Err(e) => format!("@{} {}", ctx.msg.sender.name, e),
+        };
+        ctx.bot_client.send_message(&ctx.channel, &response).await?;
+
+        Ok(())
     }
 
-    output.pop().ok_or_else(|| "Invalid expression".to_string())
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
 }
-
-fn apply_operator(output: &mut Vec<f64>, op: Token) -> Result<(), String> {
-    let b = output.pop().ok_or("Invalid expression: not enough operands")?;
-    let a = output.pop().ok_or("Invalid expression: not enough operands")?;
-    let result = match op {
-        Token::Plus => a + b,
-        Token::Minus => a - b,
-        Token::Multiply => a * b,
-        Token::Divide => {
-            if b == 0.0 {
-                return Err("Division by zero".to_string());
-            }
-            a / b
-        },
-        Token::Power => a.powf(b),
-        _ => return Err("Invalid operator".to_string()),
-    };
-    output.push(result);
-    Ok(())
-}
\ No newline at end of file
@@ -1,9 +1,12 @@
+use std::collections::HashMap;
 use std::error::Error;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use rand::Rng;
 use crate::twitch::irc::command_system::{Command, CommandContext};
 use crate::twitch::roles::UserRole;
 use crate::twitch::api::requests::channel;
+use crate::twitch::channel_updates::ChannelField;
 
 // Add these imports:
 use crate::twitch::api::TwitchAPIClient;
@@ -17,7 +20,125 @@ pub struct RefreshAdsCommand;
 pub struct AdNomsterCommand;
 
 lazy_static::lazy_static! {
-    static ref AD_NOMSTER_TASK: Mutex<Option<tokio::task::JoinHandle<()>>> = Mutex::new(None);
+    static ref AD_NOMSTER_TASK: Mutex<Option<tokio::task::AbortHandle>> = Mutex::new(None);
+}
+
+const CATEGORY_CHOICE_TTL: Duration = Duration::from_secs(60);
+const MAX_CATEGORY_CHOICES: usize = 3;
+// Similarity a search result needs to be picked without asking
+const CATEGORY_MATCH_THRESHOLD: f64 = 0.9;
+const CATEGORY_MATCH_MARGIN: f64 = 0.05;
+
+struct PendingCategoryChoice {
+    choices: Vec<(String, String)>,
+    created_at: Instant,
+}
+
+enum CategoryMatch {
+    Found(String, String),
+    Ambiguous(Vec<(String, String)>),
+    NotFound,
+}
+
+fn normalize_category(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn category_similarity(query: &str, name: &str) -> f64 {
+    let similarity = strsim::jaro_winkler(query, name);
+    // "minecraft" should still strongly prefer "Minecraft" over "Minecraft Dungeons"
+    if name.starts_with(query) {
+        similarity.max(0.85)
+    } else {
+        similarity
+    }
+}
+
+fn match_category(query: &str, candidates: Vec<(String, String)>) -> CategoryMatch {
+    let query = normalize_category(query);
+
+    if let Some((id, name)) = candidates.iter().find(|(_, name)| normalize_category(name) == query) {
+        return CategoryMatch::Found(id.clone(), name.clone());
+    }
+
+    let mut scored: Vec<(f64, (String, String))> = candidates.into_iter()
+        .map(|(id, name)| (category_similarity(&query, &normalize_category(&name)), (id, name)))
+        .collect();
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    match scored.as_slice() {
+        [] => CategoryMatch::NotFound,
+        [(_, (id, name))] => CategoryMatch::Found(id.clone(), name.clone()),
+        [(best, (id, name)), (second, _), ..]
+            if *best >= CATEGORY_MATCH_THRESHOLD && best - second >= CATEGORY_MATCH_MARGIN =>
+        {
+            CategoryMatch::Found(id.clone(), name.clone())
+        }
+        _ => CategoryMatch::Ambiguous(scored.into_iter()
+            .take(MAX_CATEGORY_CHOICES)
+            .map(|(_, category)| category)
+            .collect()),
+    }
+}
+
+// Categories offered by an ambiguous !game, waiting for the user to pick one
+#[derive(Default)]
+pub struct CategoryChoices {
+    // Keyed by user ID, so two mods answering prompts don't pick each other's choices
+    pending: Mutex<HashMap<String, PendingCategoryChoice>>,
+}
+
+impl CategoryChoices {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn offer(&self, user_id: &str, choices: Vec<(String, String)>) {
+        self.pending.lock().await.insert(user_id.to_string(), PendingCategoryChoice {
+            choices,
+            created_at: Instant::now(),
+        });
+    }
+
+    async fn take(&self, user_id: &str, choice: usize) -> Option<(String, String)> {
+        let mut pending = self.pending.lock().await;
+        pending.retain(|_, p| p.created_at.elapsed() < CATEGORY_CHOICE_TTL);
+        let category = pending.get(user_id)?.choices.get(choice.checked_sub(1)?)?.clone();
+        pending.remove(user_id);
+        Some(category)
+    }
+
+    // Same lookup as take, but leaves the prompt open
+    async fn peek(&self, user_id: &str, choice: usize) -> Option<(String, String)> {
+        let pending = self.pending.lock().await;
+        pending.get(user_id)
+            .filter(|p| p.created_at.elapsed() < CATEGORY_CHOICE_TTL)?
+            .choices.get(choice.checked_sub(1)?)
+            .cloned()
+    }
+}
+
+async fn current_channel_field(ctx: &CommandContext, field: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let api_client = ctx.twitch_manager.get_api_client();
+    let broadcaster_id = api_client.get_broadcaster_id().await?;
+    let info = api_client.get_channel_information(&broadcaster_id).await?;
+    Ok(info["data"][0][field].as_str().unwrap_or_default().to_string())
+}
+
+async fn set_category(ctx: &CommandContext, game_id: &str, name: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let api_client = ctx.twitch_manager.get_api_client();
+    let broadcaster_id = api_client.get_broadcaster_id().await?;
+
+    channel::update_channel_category(&api_client, &broadcaster_id, game_id).await?;
+    ctx.twitch_manager.channel_updates.expect_change(ChannelField::Category, name).await;
+    ctx.bot_client.send_message(&ctx.channel, &format!("Stream category updated to: {}", name)).await?;
+    Ok(())
 }
 
 #[async_trait::async_trait]
@@ -66,11 +187,20 @@ impl Command for TitleCommand {
         let broadcaster_id = api_client.get_broadcaster_id().await?;
 
         channel::update_channel_title(&api_client, &broadcaster_id, &new_title).await?;
+        ctx.twitch_manager.channel_updates.expect_change(ChannelField::Title, &new_title).await;
 
         ctx.bot_client.send_message(&ctx.channel, &format!("Stream title updated to: {}", new_title)).await?;
         Ok(())
     }
 
+    async fn simulate(&self, ctx: &CommandContext, args: Vec<String>) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        if args.is_empty() {
+            return Ok(Some("Usage: !title <new title>".to_string()));
+        }
+        let current = current_channel_field(ctx, "title").await?;
+        Ok(Some(format!("!title would change the title from \"{}\" to \"{}\"", current, args.join(" "))))
+    }
+
     fn required_role(&self) -> UserRole {
         UserRole::Moderator
     }
@@ -92,20 +222,33 @@ impl Command for GameCommand {
             return Ok(());
         }
 
+        // "!game 2" answers an earlier "did you mean" prompt
+        if args.len() == 1 {
+            if let Ok(choice) = args[0].parse::<usize>() {
+                if let Some((game_id, name)) = ctx.twitch_manager.category_choices.take(&ctx.msg.sender.id, choice).await {
+                    return set_category(ctx, &game_id, &name).await;
+                }
+            }
+        }
+
         let game_name = args.join(" ");
         let api_client = ctx.twitch_manager.get_api_client();
-        let broadcaster_id = api_client.get_broadcaster_id().await?;
-
-        // First search for the game
-        match channel::search_category(&api_client, &game_name).await? {
-            Some((game_id, exact_name)) => {
-                // Update the category with the found game ID
-                channel::update_channel_category(&api_client, &broadcaster_id, &game_id).await?;
-                ctx.bot_client.send_message(&ctx.channel,
-                                            &format!("Stream category updated to: {}", exact_name)
-                ).await?;
-            },
-            None => {
+        let candidates = channel::search_categories(&api_client, &game_name).await?;
+
+        match match_category(&game_name, candidates) {
+            CategoryMatch::Found(game_id, name) => set_category(ctx, &game_id, &name).await?,
+            CategoryMatch::Ambiguous(choices) => {
+                let options: Vec<String> = choices.iter()
+                    .enumerate()
+                    .map(|(i, (_, name))| format!("{}) {}", i + 1, name))
+                    .collect();
+                ctx.bot_client.send_message(&ctx.channel, &format!(
+                    "@{}, did you mean: {}? Reply with !game <number>",
+                    ctx.msg.sender.name, options.join(" ")
+                )).await?;
+                ctx.twitch_manager.category_choices.offer(&ctx.msg.sender.id, choices).await;
+            }
+            CategoryMatch::NotFound => {
                 ctx.bot_client.send_message(&ctx.channel,
                                             &format!("Could not find a category matching '{}'", game_name)
                 ).await?;
@@ -115,6 +258,33 @@ impl Command for GameCommand {
         Ok(())
     }
 
+    async fn simulate(&self, ctx: &CommandContext, args: Vec<String>) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        if args.is_empty() {
+            return Ok(Some("Usage: !game <category name>".to_string()));
+        }
+        let current = current_channel_field(ctx, "game_name").await?;
+
+        if args.len() == 1 {
+            if let Ok(choice) = args[0].parse::<usize>() {
+                if let Some((_, name)) = ctx.twitch_manager.category_choices.peek(&ctx.msg.sender.id, choice).await {
+                    return Ok(Some(format!("!game would change the category from {} to {}", current, name)));
+                }
+            }
+        }
+
+        let game_name = args.join(" ");
+        let api_client = ctx.twitch_manager.get_api_client();
+        let candidates = channel::search_categories(&api_client, &game_name).await?;
+        Ok(Some(match match_category(&game_name, candidates) {
+            CategoryMatch::Found(_, name) => format!("!game would change the category from {} to {}", current, name),
+            CategoryMatch::Ambiguous(choices) => {
+                let names: Vec<&str> = choices.iter().map(|(_, name)| name.as_str()).collect();
+                format!("!game would ask which category you meant: {}", names.join(", "))
+            }
+            CategoryMatch::NotFound => format!("!game would find no category matching '{}'", game_name),
+        }))
+    }
+
     fn required_role(&self) -> UserRole {
         UserRole::Moderator
     }
@@ -178,6 +348,10 @@ impl Command for RunAdCommand {
         Ok(())
     }
 
+    async fn simulate(&self, _ctx: &CommandContext, _args: Vec<String>) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Some("!runad would start a 180 second commercial".to_string()))
+    }
+
     fn required_role(&self) -> UserRole {
         UserRole::Moderator
     }
@@ -212,7 +386,7 @@ impl Command for AdNomsterCommand {
                     let channel = ctx.channel.clone();
                     let twitch_manager = ctx.twitch_manager.clone();
 
-                    let handle = tokio::spawn(async move {
+                    let handle = ctx.twitch_manager.tasks.spawn("ad_nomster", async move {
                         let api_client = twitch_manager.get_api_client();
                         loop {
                             if let Err(e) = refresh_ads(&api_client, &bot_client, &channel).await {
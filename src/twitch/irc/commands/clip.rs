@@ -0,0 +1,56 @@
+use tracing::error;
+use tokio::time::{sleep, Duration};
+use crate::twitch::clips::archive_clip;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct ClipCommand;
+
+#[async_trait::async_trait]
+impl Command for ClipCommand {
+    fn name(&self) -> &'static str {
+        "!clip"
+    }
+
+    fn description(&self) -> &'static str {
+        "Creates a clip of the live stream and archives it"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if !ctx.is_stream_online {
+            ctx.bot_client.send_message(&ctx.channel, "Can't clip while the stream is offline.").await?;
+            return Ok(());
+        }
+
+        let api_client = ctx.twitch_manager.get_api_client();
+        let broadcaster_id = api_client.get_broadcaster_id().await?;
+
+        let clip_id = match api_client.create_clip(&broadcaster_id).await {
+            Ok(id) => id,
+            Err(e) => {
+                error!("Failed to create clip: {:?}", e);
+                ctx.bot_client.send_message(&ctx.channel, "Sorry, I couldn't create a clip right now.").await?;
+                return Ok(());
+            }
+        };
+
+        let clip_url = format!("https://clips.twitch.tv/{}", clip_id);
+        ctx.bot_client.send_message(&ctx.channel, &format!("Clip created: {}", clip_url)).await?;
+
+        // Twitch needs a few seconds to finish processing before the clip shows up in Helix
+        let twitch_manager = ctx.twitch_manager.clone();
+        let submitted_by = ctx.msg.sender.name.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_secs(15)).await;
+            if let Err(e) = archive_clip(&twitch_manager, &clip_id, &submitted_by).await {
+                error!("Failed to archive clip {}: {:?}", clip_id, e);
+            }
+        });
+
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Subscriber
+    }
+}
@@ -0,0 +1,25 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct CommandsCommand;
+
+#[async_trait::async_trait]
+impl Command for CommandsCommand {
+    fn name(&self) -> &'static str {
+        "!commands"
+    }
+
+    fn description(&self) -> &'static str {
+        "Links to the list of all chat commands"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/commands", ctx.config.read().await.public_web_url());
+        ctx.bot_client.send_message(&ctx.channel, &format!("All commands: {}", url)).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
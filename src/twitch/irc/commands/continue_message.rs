@@ -1,4 +1,5 @@
 use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::chunker::split_response;
 use crate::twitch::roles::UserRole;
 
 pub struct ContinueCommand;
@@ -16,12 +17,12 @@ impl Command for ContinueCommand {
     async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         if let Some(ai_client) = &ctx.ai_client {
             if let Some(remainder) = ai_client.get_remainder(&ctx.msg.sender.id).await {
-                if remainder.len() > 500 {
-                    let message = format!("{}...", &remainder[..497]);
-                    ai_client.store_remainder(ctx.msg.sender.id.clone(), remainder[497..].to_string()).await;
-                    ctx.bot_client.send_message(&ctx.channel, &format!("{} (Use !continue to see more)", message)).await?;
-                } else {
-                    ctx.bot_client.send_message(&ctx.channel, &remainder).await?;
+                match split_response(remainder) {
+                    (message, Some(rest)) => {
+                        ai_client.store_remainder(ctx.msg.sender.id.clone(), rest).await;
+                        ctx.bot_client.send_message(&ctx.channel, &format!("{} (Use !continue to see more)", message)).await?;
+                    }
+                    (message, None) => ctx.bot_client.send_message(&ctx.channel, &message).await?,
                 }
             } else {
                 ctx.bot_client.send_message(&ctx.channel, "No continuation available.").await?;
@@ -0,0 +1,140 @@
+use tracing::error;
+use crate::twitch::custom_commands::{check_name, check_response, normalize_name};
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+// (name, response) from "!addcom !hello Hi $(user)"
+fn split_args(args: &[String]) -> Option<(String, String)> {
+    let name = normalize_name(args.first()?);
+    let response = args[1..].join(" ");
+    Some((name, response))
+}
+
+pub struct AddComCommand;
+
+#[async_trait::async_trait]
+impl Command for AddComCommand {
+    fn name(&self) -> &'static str {
+        "!addcom"
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds a response command; responses can use $(user), $(touser), $(args), $(count), $(uptime), $(game) and $(title)"
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<!name> <response>")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match split_args(&args) {
+            Some((name, response)) => match check_name(&ctx.twitch_manager.commands, &name).and_then(|_| check_response(&response)) {
+                Ok(()) => match ctx.storage.read().await.add_custom_command(&name, response.trim(), &ctx.msg.sender.login) {
+                    Ok(true) => {
+                        ctx.twitch_manager.commands.set_custom(&name, response.trim());
+                        format!("Added {}.", name)
+                    }
+                    Ok(false) => format!("{} already exists, use !editcom to change it.", name),
+                    Err(e) => {
+                        error!("Failed to add custom command {}: {:?}", name, e);
+                        format!("Couldn't add {}, please try again.", name)
+                    }
+                },
+                Err(reason) => reason,
+            },
+            None => "Usage: !addcom <!name> <response>".to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
+
+pub struct EditComCommand;
+
+#[async_trait::async_trait]
+impl Command for EditComCommand {
+    fn name(&self) -> &'static str {
+        "!editcom"
+    }
+
+    fn description(&self) -> &'static str {
+        "Changes the response of a command added with !addcom"
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<!name> <response>")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match split_args(&args) {
+            Some((name, response)) => match check_response(&response) {
+                Ok(()) => match ctx.storage.read().await.edit_custom_command(&name, response.trim()) {
+                    Ok(true) => {
+                        ctx.twitch_manager.commands.set_custom(&name, response.trim());
+                        format!("Updated {}.", name)
+                    }
+                    Ok(false) => format!("There's no {} command, use !addcom to add it.", name),
+                    Err(e) => {
+                        error!("Failed to edit custom command {}: {:?}", name, e);
+                        format!("Couldn't update {}, please try again.", name)
+                    }
+                },
+                Err(reason) => reason,
+            },
+            None => "Usage: !editcom <!name> <response>".to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
+
+pub struct DelComCommand;
+
+#[async_trait::async_trait]
+impl Command for DelComCommand {
+    fn name(&self) -> &'static str {
+        "!delcom"
+    }
+
+    fn description(&self) -> &'static str {
+        "Removes a command added with !addcom"
+    }
+
+    fn usage(&self) -> Option<&'static str> {
+        Some("<!name>")
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match args.first().map(|name| normalize_name(name)) {
+            Some(name) => match ctx.storage.read().await.remove_custom_command(&name) {
+                Ok(true) => {
+                    ctx.twitch_manager.commands.remove_custom(&name);
+                    format!("Removed {}.", name)
+                }
+                Ok(false) => format!("There's no {} command.", name),
+                Err(e) => {
+                    error!("Failed to remove custom command {}: {:?}", name, e);
+                    format!("Couldn't remove {}, please try again.", name)
+                }
+            },
+            None => "Usage: !delcom <!name>".to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
@@ -0,0 +1,39 @@
+use tracing::info;
+use crate::ai::translate::truncate_chars;
+use crate::doctor;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+// Twitch drops anything longer
+const MAX_REPLY_CHARS: usize = 500;
+
+pub struct DiagCommand;
+
+#[async_trait::async_trait]
+impl Command for DiagCommand {
+    fn name(&self) -> &'static str {
+        "!diag"
+    }
+
+    fn description(&self) -> &'static str {
+        "Checks Twitch, OBS, VRChat, Discord, OSC and the AI keys and reports what's broken"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        ctx.bot_client.send_message(&ctx.channel, "Running diagnostics, this takes a few seconds...").await?;
+
+        // A copy, so a slow check doesn't hold up config changes
+        let config = ctx.config.read().await.clone();
+        let twitch_token = ctx.twitch_manager.get_api_client().get_token().await.ok();
+        let results = doctor::run(&config, twitch_token).await;
+        info!("Diagnostics:\n{}", doctor::format_report(&results));
+
+        let message = truncate_chars(&format!("Diagnostics: {}", doctor::summarize(&results)), MAX_REPLY_CHARS);
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Broadcaster
+    }
+}
@@ -1,6 +1,7 @@
 use crate::twitch::irc::command_system::{Command, CommandContext};
 use crate::twitch::roles::UserRole;
 use crate::twitch::api::requests::announcement::send_announcement;
+use crate::ai::prompts::Prompts;
 use crate::ai::AIClient;
 use std::sync::Arc;
 
@@ -21,7 +22,7 @@ impl Command for DiscordCommand {
         let discord_link = irc_manager.get_discord_link().await;
 
         // Generate a custom greeting using AI
-        let discord_message = generate_discord_message(&ctx.ai_client, &discord_link).await;
+        let discord_message = generate_discord_message(&ctx.ai_client, &ctx.twitch_manager.prompts, &discord_link).await;
 
         let api_client = ctx.twitch_manager.get_api_client();
         // Send an announcement
@@ -35,19 +36,15 @@ impl Command for DiscordCommand {
     fn required_role(&self) -> UserRole {
         UserRole::Viewer
     }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
 }
 
-async fn generate_discord_message(ai_client: &Option<Arc<AIClient>>, discord_link: &str) -> String {
+async fn generate_discord_message(ai_client: &Option<Arc<AIClient>>, prompts: &Prompts, discord_link: &str) -> String {
     if let Some(ai) = ai_client {
-        let prompt = format!(
-            "Generate a friendly and inviting message to encourage Twitch viewers to join our Discord community. \
-            Feel free to mention: VR, technology, cute & funny anime, catgirls, foxgirls, \
-            catboys, foxboys, 3D art or living in the matrix. \
-            Don't use the word viewers.  If anything, say chatters or everyone. Good vibes. Good vibes. \
-            The message should be brief (1-2 sentences) and include the following Discord link: {}. \
-            Make sure the tone is casual and welcoming.",
-            discord_link
-        );
+        let prompt = prompts.render("command.discord", &[("link", discord_link)]);
 
         match ai.generate_response_without_history(&prompt).await {
             Ok(response) => {
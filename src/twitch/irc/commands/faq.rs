@@ -0,0 +1,54 @@
+use crate::twitch::irc::command_system::{split_quoted, Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct FaqCommand;
+
+#[async_trait::async_trait]
+impl Command for FaqCommand {
+    fn name(&self) -> &'static str {
+        "!faq"
+    }
+
+    fn description(&self) -> &'static str {
+        "Manages the questions the bot answers on its own: !faq add \"what headset\" \"Quest 3 + PC link\", !faq remove <number>, !faq list"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let usage = "Usage: !faq add \"question words\" \"answer\", !faq remove <number>, !faq list";
+        let message = match args.first().map(|arg| arg.to_lowercase()).as_deref() {
+            Some("add") => {
+                let parts = split_quoted(&args[1..].join(" "));
+                match parts.as_slice() {
+                    [question, answer @ ..] if !answer.is_empty() => {
+                        let entry = ctx.twitch_manager.faq.add(question, &answer.join(" "));
+                        format!("FAQ #{} saved: \"{}\" → {}", entry.id, entry.question, entry.answer)
+                    }
+                    _ => usage.to_string(),
+                }
+            }
+            Some("remove") | Some("delete") if args.len() > 1 => match ctx.twitch_manager.faq.remove(&args[1..].join(" ")) {
+                Some(entry) => format!("Removed FAQ #{}: \"{}\"", entry.id, entry.question),
+                None => "There's no FAQ entry like that.".to_string(),
+            },
+            Some("list") => {
+                let entries = ctx.twitch_manager.faq.list();
+                if entries.is_empty() {
+                    "The FAQ is empty.".to_string()
+                } else {
+                    entries.iter()
+                        .map(|entry| format!("#{} {}", entry.id, entry.question))
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                }
+            }
+            _ => usage.to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
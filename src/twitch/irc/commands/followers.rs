@@ -1,6 +1,7 @@
+use crate::twitch::follower_growth;
 use crate::twitch::irc::command_system::{Command, CommandContext};
 use crate::twitch::roles::UserRole;
-use log::error;
+use tracing::error;
 use chrono::{DateTime, Utc};
 
 pub struct FollowersCommand;
@@ -13,7 +14,7 @@ impl Command for FollowersCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Shows the current number of followers for the channel"
+        "Shows the current number of followers for the channel and how many came in today and this week"
     }
 
     async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -23,7 +24,10 @@ impl Command for FollowersCommand {
             Ok(broadcaster_id) => {
                 match twitch_api_client.get_follower_count(&broadcaster_id).await {
                     Ok(follower_count) => {
-                        let response = format!("@{}, the channel currently has {} followers!", ctx.msg.sender.name, follower_count);
+                        let response = match follower_growth::describe_deltas(&ctx.twitch_manager, follower_count).await {
+                            Some(deltas) => format!("@{}, the channel currently has {} followers! ({})", ctx.msg.sender.name, follower_count, deltas),
+                            None => format!("@{}, the channel currently has {} followers!", ctx.msg.sender.name, follower_count),
+                        };
                         ctx.bot_client.send_message(&ctx.channel, &response).await?;
                     },
                     Err(e) => {
@@ -111,7 +115,7 @@ impl Command for FollowAgeCommand {
     }
 }
 
-fn format_duration(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
+pub(crate) fn format_duration(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
     let duration = end.signed_duration_since(start);
 
     let seconds = duration.num_seconds();
@@ -21,7 +21,7 @@ impl Command for IsItFridayCommand {
         let is_friday = Utc::now().weekday().num_days_from_monday() == 4;
         let friday_message = generate_friday_message(&ctx.ai_client, is_friday).await;
 
-        ctx.twitch_manager.send_message_as_bot(&ctx.channel, &friday_message).await?;
+        ctx.twitch_manager.send_long_message_as_bot(&ctx.channel, &friday_message).await?;
 
         Ok(())
     }
@@ -29,6 +29,10 @@ impl Command for IsItFridayCommand {
     fn required_role(&self) -> UserRole {
         UserRole::Viewer
     }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
 }
 
 #[async_trait::async_trait]
@@ -45,7 +49,7 @@ impl Command for XmasCommand {
         let days_until_christmas = calculate_days_until_christmas();
         let xmas_message = generate_xmas_message(&ctx.ai_client, days_until_christmas).await;
 
-        ctx.twitch_manager.send_message_as_bot(&ctx.channel, &xmas_message).await?;
+        ctx.twitch_manager.send_long_message_as_bot(&ctx.channel, &xmas_message).await?;
 
         Ok(())
     }
@@ -53,6 +57,10 @@ impl Command for XmasCommand {
     fn required_role(&self) -> UserRole {
         UserRole::Viewer
     }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
 }
 
 async fn generate_friday_message(ai_client: &Option<Arc<AIClient>>, is_friday: bool) -> String {
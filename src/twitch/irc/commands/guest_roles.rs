@@ -0,0 +1,87 @@
+use chrono::Duration;
+use crate::twitch::happy_hour::parse_duration;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::role_grants::{self, GrantedRole};
+use crate::twitch::roles::UserRole;
+
+pub struct GuestVipCommand;
+pub struct GuestModCommand;
+
+#[async_trait::async_trait]
+impl Command for GuestVipCommand {
+    fn name(&self) -> &'static str {
+        "!guestvip"
+    }
+
+    fn description(&self) -> &'static str {
+        "VIPs someone for a while: !guestvip <user> [2h], !guestvip end <user> to take it back early"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = run(ctx, &args, GrantedRole::Vip, "!guestvip").await;
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for GuestModCommand {
+    fn name(&self) -> &'static str {
+        "!guestmod"
+    }
+
+    fn description(&self) -> &'static str {
+        "Mods someone for a while: !guestmod <user> [2h], !guestmod end <user> to take it back early"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = run(ctx, &args, GrantedRole::Moderator, "!guestmod").await;
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Broadcaster
+    }
+}
+
+// Without a duration the grant lasts the evening
+const DEFAULT_HOURS: i64 = 4;
+
+async fn run(ctx: &CommandContext, args: &[String], role: GrantedRole, command: &str) -> String {
+    match (args.first().map(|a| a.to_lowercase()).as_deref(), args.get(1)) {
+        (None, _) => {
+            let grants: Vec<String> = ctx.twitch_manager.role_grants.list().iter()
+                .filter(|grant| grant.role == role)
+                .map(role_grants::describe)
+                .collect();
+            if grants.is_empty() {
+                format!("No guest {}s right now. Usage: {} <user> [duration like 2h]", role, command)
+            } else {
+                format!("Guest {}s: {}", role, grants.join(", "))
+            }
+        }
+        (Some("end") | Some("remove"), Some(user)) => match role_grants::revoke(&ctx.twitch_manager, user, role).await {
+            Ok(Some(grant)) => format!("{} is no longer a guest {}.", grant.login, role),
+            Ok(None) => format!("{} isn't a guest {}.", user.trim_start_matches('@'), role),
+            Err(e) => format!("Couldn't take {} back: {}", role, e),
+        },
+        (Some(user), duration) => {
+            let duration = match duration {
+                Some(text) => match parse_duration(text) {
+                    Some(duration) => duration,
+                    None => return format!("Usage: {} <user> [duration like 2h], or {} end <user>", command, command),
+                },
+                None => Duration::hours(DEFAULT_HOURS),
+            };
+            match role_grants::grant(ctx.twitch_manager.clone(), user, role, duration).await {
+                Ok(grant) => format!("Guest {} granted: {}", role, role_grants::describe(&grant)),
+                Err(e) => format!("Couldn't make {} a guest {}: {}", user.trim_start_matches('@'), role, e),
+            }
+        }
+    }
+}
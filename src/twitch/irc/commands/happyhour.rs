@@ -0,0 +1,51 @@
+use chrono::Duration;
+use crate::config::HappyHourConfig;
+use crate::twitch::happy_hour;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct HappyHourCommand;
+
+#[async_trait::async_trait]
+impl Command for HappyHourCommand {
+    fn name(&self) -> &'static str {
+        "!happyhour"
+    }
+
+    fn description(&self) -> &'static str {
+        "Discounts the happy hour rewards for a while: !happyhour [30m], !happyhour end to stop early"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let config = ctx.config.read().await.happy_hour.clone();
+
+        let message = match args.first().map(|a| a.to_lowercase()).as_deref() {
+            Some("end") | Some("stop") => match happy_hour::end(&ctx.twitch_manager).await {
+                Some(_) => "Happy hour is over, rewards are back to their usual prices!".to_string(),
+                None => "There's no happy hour running.".to_string(),
+            },
+            Some(text) => match happy_hour::parse_duration(text) {
+                Some(duration) => start(ctx, &config, duration).await,
+                None => "Usage: !happyhour [duration like 30m or 1h], or !happyhour end".to_string(),
+            },
+            None => match happy_hour::current(&ctx.twitch_manager) {
+                Some(active) => happy_hour::describe(&active),
+                None => start(ctx, &config, Duration::seconds(config.scheduled_duration_secs as i64)).await,
+            },
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
+
+async fn start(ctx: &CommandContext, config: &HappyHourConfig, duration: Duration) -> String {
+    match happy_hour::start(ctx.twitch_manager.clone(), config, duration).await {
+        Ok(active) => happy_hour::describe(&active),
+        Err(e) => format!("Couldn't start happy hour: {}", e),
+    }
+}
@@ -0,0 +1,67 @@
+use tracing::error;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct IgnoreCommand;
+
+#[async_trait::async_trait]
+impl Command for IgnoreCommand {
+    fn name(&self) -> &'static str {
+        "!ignore"
+    }
+
+    fn description(&self) -> &'static str {
+        "Manages users the bot ignores: !ignore add <user> [reason], !ignore remove <user>, !ignore list"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let action = args.first().map(|a| a.to_lowercase());
+        let user = args.get(1).map(|u| u.trim_start_matches('@').to_lowercase());
+
+        let message = match (action.as_deref(), user) {
+            (Some("add"), Some(user)) => {
+                let reason = if args.len() > 2 { args[2..].join(" ") } else { format!("added by {}", ctx.msg.sender.login) };
+                match ctx.storage.read().await.add_ignored_user(&user, None, &reason) {
+                    Ok(()) => format!("Now ignoring {}.", user),
+                    Err(e) => {
+                        error!("Failed to ignore {}: {:?}", user, e);
+                        format!("Couldn't ignore {}, please try again.", user)
+                    }
+                }
+            }
+            (Some("remove"), Some(user)) => {
+                match ctx.storage.read().await.remove_ignored_user(&user) {
+                    Ok(true) => format!("No longer ignoring {}.", user),
+                    Ok(false) => {
+                        let configured = ctx.config.read().await.ignore_list.users.iter().any(|u| u.eq_ignore_ascii_case(&user));
+                        if configured {
+                            format!("{} is on the ignore list in the config file, remove them there.", user)
+                        } else {
+                            format!("{} isn't being ignored.", user)
+                        }
+                    }
+                    Err(e) => {
+                        error!("Failed to unignore {}: {:?}", user, e);
+                        format!("Couldn't unignore {}, please try again.", user)
+                    }
+                }
+            }
+            (Some("list"), _) => {
+                let users = ctx.storage.read().await.get_ignored_users()?;
+                if users.is_empty() {
+                    "Nobody has been ignored with !ignore.".to_string()
+                } else {
+                    format!("Ignored users: {}", users.join(", "))
+                }
+            }
+            _ => "Usage: !ignore add <user> [reason] | !ignore remove <user> | !ignore list".to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
@@ -0,0 +1,83 @@
+use tracing::error;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+const MAX_SLUG_LENGTH: usize = 32;
+
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= MAX_SLUG_LENGTH
+        && slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+pub struct LinkCommand;
+
+#[async_trait::async_trait]
+impl Command for LinkCommand {
+    fn name(&self) -> &'static str {
+        "!link"
+    }
+
+    fn description(&self) -> &'static str {
+        "Manages short links served at /l/<name>: !link add <name> <url>, !link remove <name>, !link list, !link <name>"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let base_url = ctx.config.read().await.public_web_url();
+        let action = args.first().map(|a| a.to_lowercase());
+        let slug = args.get(1).map(|s| s.to_lowercase());
+
+        let message = match (action.as_deref(), slug) {
+            (Some("add"), Some(slug)) => match args.get(2) {
+                Some(url) if url.starts_with("https://") || url.starts_with("http://") => {
+                    if !is_valid_slug(&slug) {
+                        format!("Link names can only use letters, numbers, - and _ (up to {} characters).", MAX_SLUG_LENGTH)
+                    } else {
+                        match ctx.storage.read().await.add_short_link(&slug, url, &ctx.msg.sender.login) {
+                            Ok(true) => format!("Short link ready: {}/l/{}", base_url, slug),
+                            Ok(false) => format!("{} is already taken, remove it first.", slug),
+                            Err(e) => {
+                                error!("Failed to add short link {}: {:?}", slug, e);
+                                format!("Couldn't add {}, please try again.", slug)
+                            }
+                        }
+                    }
+                }
+                _ => "Usage: !link add <name> <http(s) url>".to_string(),
+            },
+            (Some("remove"), Some(slug)) => match ctx.storage.read().await.remove_short_link(&slug) {
+                Ok(true) => format!("Removed the {} link.", slug),
+                Ok(false) => format!("There's no {} link.", slug),
+                Err(e) => {
+                    error!("Failed to remove short link {}: {:?}", slug, e);
+                    format!("Couldn't remove {}, please try again.", slug)
+                }
+            },
+            (Some("list"), _) => {
+                let links = ctx.storage.read().await.get_short_links()?;
+                if links.is_empty() {
+                    "No short links yet, add one with !link add <name> <url>.".to_string()
+                } else {
+                    let names: Vec<String> = links.iter().map(|link| link.slug.clone()).collect();
+                    format!("Short links: {}", names.join(", "))
+                }
+            }
+            (Some(slug), None) if !matches!(slug, "add" | "remove") => {
+                let exists = ctx.storage.read().await.get_short_links()?.iter().any(|link| link.slug == slug);
+                if exists {
+                    format!("{}/l/{}", base_url, slug)
+                } else {
+                    format!("There's no {} link.", slug)
+                }
+            }
+            _ => "Usage: !link add <name> <url> | !link remove <name> | !link list | !link <name>".to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
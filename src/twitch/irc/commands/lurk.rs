@@ -0,0 +1,89 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::lurk;
+use crate::twitch::roles::UserRole;
+use crate::twitch::stream_breaks::format_duration;
+
+pub struct LurkCommand;
+pub struct UnlurkCommand;
+pub struct LurkStatsCommand;
+
+#[async_trait::async_trait]
+impl Command for LurkCommand {
+    fn name(&self) -> &'static str {
+        "!lurk"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lets chat know you're lurking; !unlurk when you're back"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let started = ctx.storage.read().await.start_lurk(&ctx.msg.sender.id, &ctx.msg.sender.login)?;
+        let message = if started {
+            lurk::lurk_message(&ctx.msg.sender.name)
+        } else {
+            format!("@{}, you're already lurking!", ctx.msg.sender.name)
+        };
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for UnlurkCommand {
+    fn name(&self) -> &'static str {
+        "!unlurk"
+    }
+
+    fn description(&self) -> &'static str {
+        "Ends your lurk and shows how long it lasted"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match ctx.storage.read().await.end_lurk(&ctx.msg.sender.id)? {
+            Some(seconds) => lurk::unlurk_message(&ctx.msg.sender.name, seconds),
+            None => format!("@{}, you weren't lurking, but welcome back anyway!", ctx.msg.sender.name),
+        };
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for LurkStatsCommand {
+    fn name(&self) -> &'static str {
+        "!lurkstats"
+    }
+
+    fn description(&self) -> &'static str {
+        "Shows how often and how long someone has lurked: !lurkstats [user]"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let user = args.first()
+            .map(|u| u.trim_start_matches('@').to_lowercase())
+            .unwrap_or_else(|| ctx.msg.sender.login.clone());
+
+        let message = match ctx.storage.read().await.get_lurk_stats(&user)? {
+            (0, _) => format!("{} has never lurked. Suspicious.", user),
+            (lurks, seconds) => format!(
+                "{} has lurked {} {} for a total of {}.",
+                user, lurks, if lurks == 1 { "time" } else { "times" }, format_duration(seconds)
+            ),
+        };
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
@@ -12,12 +12,41 @@ pub(crate) mod calc;
 pub(crate) mod fun_commands;
 pub(crate) mod ad_commands;
 pub(crate) mod reset_drop_game;
+pub(crate) mod clip;
+pub(crate) mod moderation;
+pub(crate) mod translate;
+pub(crate) mod commands_list;
+pub(crate) mod test_redeem;
+pub(crate) mod vrchat_group;
+pub(crate) mod ignore;
+pub(crate) mod brb;
+pub(crate) mod botvoice;
+pub(crate) mod quickpoll;
+pub(crate) mod vote;
+pub(crate) mod link;
+pub(crate) mod trivia;
+pub(crate) mod react;
+pub(crate) mod profile;
+pub(crate) mod lurk;
+pub(crate) mod happyhour;
+pub(crate) mod tts;
+pub(crate) mod team;
+pub(crate) mod raid_train;
+pub(crate) mod suggest;
+pub(crate) mod watch;
+pub(crate) mod bitsvote;
+pub(crate) mod diag;
+pub(crate) mod guest_roles;
+pub(crate) mod opt_out;
+pub(crate) mod faq;
+pub(crate) mod sound;
+pub(crate) mod custom_commands;
 mod channel_management;
 mod continue_message;
 
 pub use continue_message::ContinueCommand;
 pub use ping::PingCommand;
-pub use calc::CalcCommand;
+pub use calc::{CalcCommand, RollCommand};
 pub use discord::DiscordCommand;
 pub use followers::{FollowersCommand, FollowAgeCommand};
 pub use fun_commands::{IsItFridayCommand, XmasCommand};
@@ -27,4 +56,33 @@ pub use verify::VerifyCommand;
 pub use vrc::VRCCommand;
 pub use world::WorldCommand;
 pub use reset_drop_game::ResetDropGameCommand;
-pub use channel_management::{TitleCommand, GameCommand, ContentCommand, RunAdCommand, RefreshAdsCommand, AdNomsterCommand};
+pub use clip::ClipCommand;
+pub use moderation::{DeleteCommand, NukeCommand};
+pub use translate::TranslateCommand;
+pub use commands_list::CommandsCommand;
+pub use test_redeem::TestRedeemCommand;
+pub use vrchat_group::{GroupInfoCommand, GroupInstanceCommand};
+pub use ignore::IgnoreCommand;
+pub use brb::BrbCommand;
+pub use botvoice::BotVoiceCommand;
+pub use quickpoll::QuickPollCommand;
+pub use vote::VoteCommand;
+pub use link::LinkCommand;
+pub use trivia::{TriviaCommand, TriviaStatsCommand};
+pub use react::ReactCommand;
+pub use profile::ProfileCommand;
+pub use lurk::{LurkCommand, UnlurkCommand, LurkStatsCommand};
+pub use happyhour::HappyHourCommand;
+pub use tts::{SkipTtsCommand, TtsBanCommand, TtsUnbanCommand};
+pub use team::TeamCommand;
+pub use raid_train::RaidTrainCommand;
+pub use suggest::SuggestCommand;
+pub use watch::WatchCommand;
+pub use bitsvote::BitsVoteCommand;
+pub use diag::DiagCommand;
+pub use guest_roles::{GuestVipCommand, GuestModCommand};
+pub use opt_out::{OptOutCommand, OptInCommand};
+pub use faq::FaqCommand;
+pub use sound::SoundCommand;
+pub use custom_commands::{AddComCommand, EditComCommand, DelComCommand};
+pub use channel_management::{TitleCommand, GameCommand, ContentCommand, RunAdCommand, RefreshAdsCommand, AdNomsterCommand, CategoryChoices};
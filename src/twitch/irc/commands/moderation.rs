@@ -0,0 +1,280 @@
+use std::collections::HashSet;
+use chrono::{DateTime, Duration, Utc};
+use tracing::{error, info};
+use crate::storage::ChatLogEntry;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::recent_chat::RECENT_CHAT_MINUTES;
+use crate::twitch::roles::UserRole;
+
+pub struct DeleteCommand;
+pub struct NukeCommand;
+
+const MAX_NUKE_LOOKBACK_MINUTES: i64 = RECENT_CHAT_MINUTES;
+
+pub fn reply_parent_id(ctx: &CommandContext) -> Option<String> {
+    ctx.msg.source.tags.0.get("reply-parent-msg-id").cloned().flatten()
+}
+
+const NUKE_USAGE: &str = "Usage: !nuke <phrase> <minutes> [timeout seconds]";
+
+// !nuke <phrase> <minutes> [timeout seconds] -> (phrase, lookback minutes, timeout)
+fn parse_nuke_args(mut args: Vec<String>) -> Option<(String, i64, Option<u32>)> {
+    let mut numbers = Vec::new();
+    while numbers.len() < 2 && args.len() > 1 {
+        match args.last().and_then(|a| a.parse::<u32>().ok()) {
+            Some(n) => {
+                numbers.insert(0, n);
+                args.pop();
+            }
+            None => break,
+        }
+    }
+
+    let lookback_minutes = match numbers.first() {
+        Some(&minutes) if minutes > 0 => (minutes as i64).min(MAX_NUKE_LOOKBACK_MINUTES),
+        _ => return None,
+    };
+    let timeout_secs = numbers.get(1).copied().filter(|&t| t > 0);
+    let phrase = args.join(" ").trim_matches('"').to_string();
+    if phrase.is_empty() {
+        return None;
+    }
+    Some((phrase, lookback_minutes, timeout_secs))
+}
+
+// The in-memory buffer has everyone, viewers who opted out of chat storage included, but only
+// since the bot started. The stored chat log fills in messages from before a restart.
+async fn find_messages(ctx: &CommandContext, phrase: &str, since: DateTime<Utc>) -> Vec<ChatLogEntry> {
+    let recent = ctx.twitch_manager.recent_chat.find(phrase, since);
+    let stored = match ctx.storage.read().await.find_recent_chat_messages(phrase, since) {
+        Ok(stored) => stored,
+        Err(e) => {
+            error!("Failed to search the chat log for nuke: {:?}", e);
+            Vec::new()
+        }
+    };
+    merge_messages(recent, stored)
+}
+
+// Newest first, each message once
+fn merge_messages(mut recent: Vec<ChatLogEntry>, stored: Vec<ChatLogEntry>) -> Vec<ChatLogEntry> {
+    let seen: HashSet<String> = recent.iter().map(|entry| entry.message_id.clone()).collect();
+    recent.extend(stored.into_iter().filter(|entry| !seen.contains(&entry.message_id)));
+    recent.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    recent
+}
+
+// The caller, the broadcaster, the bot and every moderator among the senders. If the moderator
+// list can't be fetched, every sender is left alone.
+async fn protected_users(ctx: &CommandContext, api_client: &TwitchAPIClient, broadcaster_id: &str, entries: &[ChatLogEntry]) -> HashSet<String> {
+    let mut protected = HashSet::from([ctx.msg.sender.id.clone(), broadcaster_id.to_string()]);
+    match api_client.get_bot_id().await {
+        Ok(bot_id) => {
+            protected.insert(bot_id);
+        }
+        Err(e) => error!("Failed to look up the bot's id for nuke: {:?}", e),
+    }
+
+    match api_client.get_moderator_ids(broadcaster_id).await {
+        Ok(moderators) => protected.extend(moderators),
+        Err(e) => {
+            error!("Failed to fetch the moderator list, skipping every sender: {:?}", e);
+            protected.extend(entries.iter().map(|entry| entry.user_id.clone()));
+        }
+    }
+    protected
+}
+
+fn nuke_targets<'a>(entries: &'a [ChatLogEntry], protected: &HashSet<String>) -> Vec<&'a ChatLogEntry> {
+    entries.iter().filter(|entry| !protected.contains(&entry.user_id)).collect()
+}
+
+#[async_trait::async_trait]
+impl Command for DeleteCommand {
+    fn name(&self) -> &'static str {
+        "!delete"
+    }
+
+    fn description(&self) -> &'static str {
+        "Deletes a chat message (reply to it, or pass its message id)"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message_id = match reply_parent_id(ctx).or_else(|| args.first().cloned()) {
+            Some(id) => id,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, "Usage: reply to a message with !delete, or !delete <msg-id>").await?;
+                return Ok(());
+            }
+        };
+
+        let api_client = ctx.twitch_manager.get_api_client();
+        let broadcaster_id = api_client.get_broadcaster_id().await?;
+
+        if let Err(e) = api_client.delete_chat_message(&broadcaster_id, &message_id).await {
+            error!("Failed to delete message {}: {:?}", message_id, e);
+            ctx.bot_client.send_message(&ctx.channel, "Sorry, I couldn't delete that message.").await?;
+        }
+
+        Ok(())
+    }
+
+    async fn simulate(&self, ctx: &CommandContext, args: Vec<String>) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let message_id = match reply_parent_id(ctx).or_else(|| args.first().cloned()) {
+            Some(id) => id,
+            None => return Ok(Some("!delete needs a reply or a message id".to_string())),
+        };
+        let reply_text = ctx.msg.source.tags.0.get("reply-parent-msg-body").cloned().flatten();
+        Ok(Some(match reply_text {
+            Some(text) => format!("!delete would remove message {}: \"{}\"", message_id, text),
+            None => format!("!delete would remove message {}", message_id),
+        }))
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for NukeCommand {
+    fn name(&self) -> &'static str {
+        "!nuke"
+    }
+
+    fn description(&self) -> &'static str {
+        "Deletes recent messages containing a phrase and optionally times out the senders"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let (phrase, lookback_minutes, timeout_secs) = match parse_nuke_args(args) {
+            Some(parsed) => parsed,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, NUKE_USAGE).await?;
+                return Ok(());
+            }
+        };
+
+        let since = Utc::now() - Duration::minutes(lookback_minutes);
+        let entries = find_messages(ctx, &phrase, since).await;
+
+        let api_client = ctx.twitch_manager.get_api_client();
+        let broadcaster_id = api_client.get_broadcaster_id().await?;
+
+        let protected = protected_users(ctx, &api_client, &broadcaster_id, &entries).await;
+
+        let mut deleted = 0;
+        let mut senders = HashSet::new();
+        for entry in nuke_targets(&entries, &protected) {
+            match api_client.delete_chat_message(&broadcaster_id, &entry.message_id).await {
+                Ok(_) => deleted += 1,
+                Err(e) => error!("Failed to delete message {} during nuke: {:?}", entry.message_id, e),
+            }
+            senders.insert(entry.user_id.clone());
+        }
+
+        let mut timed_out = 0;
+        if let Some(duration) = timeout_secs {
+            let reason = format!("Nuked for \"{}\"", phrase);
+            for user_id in &senders {
+                match api_client.timeout_user(&broadcaster_id, user_id, duration, &reason).await {
+                    Ok(_) => timed_out += 1,
+                    Err(e) => error!("Failed to time out {} during nuke: {:?}", user_id, e),
+                }
+            }
+        }
+
+        info!("{} nuked \"{}\": {} messages deleted, {} users timed out", ctx.msg.sender.name, phrase, deleted, timed_out);
+        let response = match timeout_secs {
+            Some(duration) => format!("Nuked \"{}\": deleted {} messages and timed out {} users for {}s.", phrase, deleted, timed_out, duration),
+            None => format!("Nuked \"{}\": deleted {} messages.", phrase, deleted),
+        };
+        ctx.bot_client.send_message(&ctx.channel, &response).await?;
+
+        Ok(())
+    }
+
+    async fn simulate(&self, ctx: &CommandContext, args: Vec<String>) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let (phrase, lookback_minutes, timeout_secs) = match parse_nuke_args(args) {
+            Some(parsed) => parsed,
+            None => return Ok(Some(NUKE_USAGE.to_string())),
+        };
+        let since = Utc::now() - Duration::minutes(lookback_minutes);
+        let entries = find_messages(ctx, &phrase, since).await;
+        let api_client = ctx.twitch_manager.get_api_client();
+        let broadcaster_id = api_client.get_broadcaster_id().await?;
+        let protected = protected_users(ctx, &api_client, &broadcaster_id, &entries).await;
+        let targets = nuke_targets(&entries, &protected);
+        let mut senders: Vec<&str> = targets.iter().map(|entry| entry.user_name.as_str()).collect();
+        senders.sort_unstable();
+        senders.dedup();
+
+        let mut report = format!(
+            "!nuke would delete {} messages containing \"{}\" from the last {} minutes",
+            targets.len(), phrase, lookback_minutes
+        );
+        if let Some(duration) = timeout_secs {
+            report.push_str(&format!(" and time out {} users for {}s", senders.len(), duration));
+        }
+        if !senders.is_empty() {
+            report.push_str(&format!(": {}", senders.join(", ")));
+        }
+        Ok(Some(report))
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message_id: &str, user_id: &str) -> ChatLogEntry {
+        ChatLogEntry {
+            message_id: message_id.to_string(),
+            user_id: user_id.to_string(),
+            user_name: format!("user{}", user_id),
+            message: "buy followers".to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn nuke_skips_protected_users() {
+        let entries = vec![
+            entry("1", "spammer"),
+            entry("2", "caller"),
+            entry("3", "broadcaster"),
+            entry("4", "bot"),
+            entry("5", "other_mod"),
+            entry("6", "spammer"),
+        ];
+        let protected: HashSet<String> = ["caller", "broadcaster", "bot", "other_mod"].iter().map(|id| id.to_string()).collect();
+
+        let targets: Vec<&str> = nuke_targets(&entries, &protected).iter().map(|entry| entry.message_id.as_str()).collect();
+        assert_eq!(targets, vec!["1", "6"]);
+    }
+
+    #[test]
+    fn merges_stored_messages_without_duplicates() {
+        let mut older = entry("1", "spammer");
+        older.timestamp = Utc::now() - Duration::minutes(30);
+        let recent = vec![entry("2", "spammer"), entry("3", "opted_out")];
+        let stored = vec![entry("2", "spammer"), older];
+
+        let merged: Vec<String> = merge_messages(recent, stored).into_iter().map(|entry| entry.message_id).collect();
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged.last().map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn parses_nuke_arguments() {
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(parse_nuke_args(args("buy followers 5 600")), Some(("buy followers".to_string(), 5, Some(600))));
+        assert_eq!(parse_nuke_args(args("spam 120")), Some(("spam".to_string(), MAX_NUKE_LOOKBACK_MINUTES, None)));
+        assert_eq!(parse_nuke_args(args("5")), None);
+    }
+}
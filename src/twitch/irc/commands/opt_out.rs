@@ -0,0 +1,59 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct OptOutCommand;
+pub struct OptInCommand;
+
+#[async_trait::async_trait]
+impl Command for OptOutCommand {
+    fn name(&self) -> &'static str {
+        "!optout"
+    }
+
+    fn description(&self) -> &'static str {
+        "Stops the bot storing your chat, timing your lurks, ranking you on leaderboards and sending your messages to AI; !optin to undo"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let added = ctx.storage.read().await.add_opt_out(&ctx.msg.sender.id, &ctx.msg.sender.login)?;
+        let message = if added {
+            format!(
+                "@{}, you're opted out. Your stored chat history is deleted and nothing new is kept, you're off the leaderboards and AI features won't see your messages. !optin to undo.",
+                ctx.msg.sender.name
+            )
+        } else {
+            format!("@{}, you're already opted out. !optin to undo.", ctx.msg.sender.name)
+        };
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for OptInCommand {
+    fn name(&self) -> &'static str {
+        "!optin"
+    }
+
+    fn description(&self) -> &'static str {
+        "Undoes !optout"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = if ctx.storage.read().await.remove_opt_out(&ctx.msg.sender.id)? {
+            format!("@{}, welcome back, you're opted in again.", ctx.msg.sender.name)
+        } else {
+            format!("@{}, you weren't opted out.", ctx.msg.sender.name)
+        };
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
@@ -14,7 +14,7 @@ impl Command for PingCommand {
     }
 
     async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        ctx.bot_client.send_message(&ctx.channel, "Pong!").await?;
+        ctx.bot_client.send_message(&ctx.channel, &ctx.twitch_manager.bot_voice.render("ping", &[])).await?;
         Ok(())
     }
 
@@ -0,0 +1,48 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::profiles;
+use crate::twitch::roles::UserRole;
+
+pub struct ProfileCommand;
+
+#[async_trait::async_trait]
+impl Command for ProfileCommand {
+    fn name(&self) -> &'static str {
+        "!profile"
+    }
+
+    fn description(&self) -> &'static str {
+        "Switches the stream profile: !profile <name>, or !profile to list profiles"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if args.is_empty() {
+            let names = ctx.twitch_manager.profiles.names();
+            let message = if names.is_empty() {
+                "No stream profiles are set up.".to_string()
+            } else {
+                let active = ctx.twitch_manager.profiles.active().map_or_else(|| "none".to_string(), |profile| profile.name);
+                format!("Current profile: {}. Available: {}", active, names.join(", "))
+            };
+            ctx.bot_client.send_message(&ctx.channel, &message).await?;
+            return Ok(());
+        }
+
+        let requested = args.join(" ");
+        let message = match profiles::apply(&ctx.twitch_manager, &requested).await {
+            Some(profile) => {
+                let mut config = ctx.config.write().await;
+                config.profiles.active = Some(profile.name.clone());
+                config.save()?;
+                format!("Switched to the {} profile.", profile.name)
+            }
+            None => format!("Unknown profile '{}'. Available: {}", requested, ctx.twitch_manager.profiles.names().join(", ")),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
@@ -0,0 +1,50 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::quick_poll::{self, MAX_OPTIONS};
+use crate::twitch::roles::UserRole;
+
+pub struct QuickPollCommand;
+
+#[async_trait::async_trait]
+impl Command for QuickPollCommand {
+    fn name(&self) -> &'static str {
+        "!quickpoll"
+    }
+
+    fn description(&self) -> &'static str {
+        "Starts a chat poll: !quickpoll \"Question?\" option1 option2 ..., !quickpoll end to close it early"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match args.first().map(|a| a.to_lowercase()).as_deref() {
+            Some("end") => match quick_poll::end(&ctx.twitch_manager) {
+                Some(summary) => {
+                    quick_poll::finish(&ctx.twitch_manager, &summary).await;
+                    return Ok(());
+                }
+                None => "There's no poll running.".to_string(),
+            },
+            _ => match quick_poll::parse_args(&args) {
+                Some((question, options)) => match quick_poll::start(ctx.twitch_manager.clone(), question, options) {
+                    Some(summary) => {
+                        quick_poll::publish(&ctx.twitch_manager, &summary).await;
+                        format!(
+                            "Poll: {} Vote with !vote <number>: {} (closes in {}s)",
+                            summary.question,
+                            quick_poll::describe_options(&summary),
+                            quick_poll::DEFAULT_DURATION_SECS,
+                        )
+                    }
+                    None => "A poll is already running, use !quickpoll end first.".to_string(),
+                },
+                None => format!("Usage: !quickpoll \"Question?\" option1 option2 ... (2 to {} options)", MAX_OPTIONS),
+            },
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
@@ -0,0 +1,81 @@
+use tracing::error;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::raid_train;
+use crate::twitch::roles::UserRole;
+
+pub struct RaidTrainCommand;
+
+#[async_trait::async_trait]
+impl Command for RaidTrainCommand {
+    fn name(&self) -> &'static str {
+        "!raidtrain"
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds the next live channel on the raid train: !raidtrain [go|cancel]"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let config = ctx.config.read().await.raid_train.clone();
+        if !config.enabled {
+            ctx.bot_client.send_message(&ctx.channel, "The raid train isn't enabled.").await?;
+            return Ok(());
+        }
+
+        let message = match args.first().map(|arg| arg.to_lowercase()).as_deref() {
+            // The countdown task replies once it acts on these
+            Some("go") => match ctx.twitch_manager.raid_train.confirm() {
+                Some(_) => None,
+                None => Some("There's no raid to confirm.".to_string()),
+            },
+            Some("cancel") => match ctx.twitch_manager.raid_train.cancel() {
+                Some(_) => None,
+                None => Some("There's no raid to cancel.".to_string()),
+            },
+            _ => match raid_train::propose(ctx.twitch_manager.clone(), config, ctx.channel.clone()).await {
+                Ok(message) => Some(message),
+                Err(e) => {
+                    error!("Failed to check the raid train: {:?}", e);
+                    Some("Couldn't check who's live, please try again.".to_string())
+                }
+            },
+        };
+
+        if let Some(message) = message {
+            ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        }
+        Ok(())
+    }
+
+    async fn simulate(&self, ctx: &CommandContext, args: Vec<String>) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let config = ctx.config.read().await.raid_train.clone();
+        if !config.enabled {
+            return Ok(Some("!raidtrain would do nothing, the raid train isn't enabled".to_string()));
+        }
+
+        let pending = ctx.twitch_manager.raid_train.pending();
+        let report = match (args.first().map(|arg| arg.to_lowercase()).as_deref(), pending) {
+            (Some("go"), Some(pending)) => format!("!raidtrain go would raid {} now", pending.target.display_name),
+            (Some("cancel"), Some(pending)) => format!("!raidtrain cancel would call off the raid to {}", pending.target.display_name),
+            (Some("go") | Some("cancel"), None) => "!raidtrain would do nothing, there's no raid pending".to_string(),
+            (_, Some(pending)) => format!("!raidtrain would do nothing, it's already headed to {}", pending.target.display_name),
+            (_, None) => {
+                let train = raid_train::statuses(&ctx.twitch_manager.costreams, &ctx.twitch_manager.get_api_client(), &config).await?;
+                match raid_train::next_stop(&ctx.channel, &train) {
+                    Some(target) if config.auto_raid => format!(
+                        "!raidtrain would raid {} automatically in {}s unless cancelled", target.display_name, config.confirm_secs
+                    ),
+                    Some(target) => format!(
+                        "!raidtrain would suggest {} and wait {}s for !raidtrain go", target.display_name, config.confirm_secs
+                    ),
+                    None => "!raidtrain would find nobody else on the train live".to_string(),
+                }
+            }
+        };
+        Ok(Some(report))
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
@@ -0,0 +1,48 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct ReactCommand;
+
+#[async_trait::async_trait]
+impl Command for ReactCommand {
+    fn name(&self) -> &'static str {
+        "!react"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reviews held AI world reactions: !react list, !react approve [id], !react deny [id]"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let id = args.get(1).and_then(|id| id.trim_start_matches('#').parse::<u64>().ok());
+
+        let message = match args.first().map(|a| a.to_lowercase()).as_deref() {
+            Some("approve") => match ctx.twitch_manager.world_reactions.take(id) {
+                Some(reaction) => format!("@{} {}", reaction.user_name, reaction.text),
+                None => "No reaction is waiting for approval.".to_string(),
+            },
+            Some("deny") => match ctx.twitch_manager.world_reactions.take(id) {
+                Some(reaction) => format!("Dropped reaction #{} for {}.", reaction.id, reaction.user_name),
+                None => "No reaction is waiting for approval.".to_string(),
+            },
+            _ => {
+                let pending = ctx.twitch_manager.world_reactions.pending();
+                if pending.is_empty() {
+                    "No reactions waiting for approval.".to_string()
+                } else {
+                    let list: Vec<String> = pending.iter()
+                        .map(|reaction| format!("#{} {}", reaction.id, reaction.user_name))
+                        .collect();
+                    format!("Waiting for approval: {} (the texts are in the bot log)", list.join(", "))
+                }
+            }
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Broadcaster
+    }
+}
@@ -4,9 +4,9 @@ use crate::ai::AIClient;
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use chrono::{DateTime, Utc, Duration};
-use log::{error};
+use tracing::{error};
 use crate::twitch::TwitchManager;
-use crate::twitch::models::shoutout::{GLOBAL_COOLDOWN_SECONDS};
+use crate::twitch::models::shoutout::{GLOBAL_COOLDOWN_SECONDS, USER_COOLDOWN_SECONDS};
 
 
 
@@ -48,6 +48,19 @@ impl ShoutoutCooldown {
         self.per_user.insert(user_id.to_string(), now);
     }
 
+    // Seconds left on the global and per-user cooldowns, 0 when clear
+    pub fn remaining_cooldowns(&self, user_id: &str) -> (i64, i64) {
+        let now = Utc::now();
+        let global = GLOBAL_COOLDOWN_SECONDS as i64 - now.signed_duration_since(self.last_global_shoutout).num_seconds();
+        let user = self.per_user.get(user_id)
+            .map_or(0, |last| USER_COOLDOWN_SECONDS as i64 - now.signed_duration_since(*last).num_seconds());
+        (global.max(0), user.max(0))
+    }
+
+    pub fn queue_len(&self) -> usize {
+        self.queue.len()
+    }
+
     pub fn enqueue(&mut self, user_id: String, username: String) {
         self.queue.push_back(ShoutoutQueueItem {
             user_id,
@@ -85,6 +98,10 @@ impl Command for ShoutoutCommand {
         UserRole::Subscriber
     }
 
+    fn is_heavy(&self) -> bool {
+        true
+    }
+
     async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         if args.is_empty() {
             ctx.bot_client.send_message(&ctx.channel, "Usage: !so <username>").await?;
@@ -111,7 +128,7 @@ impl Command for ShoutoutCommand {
         let shoutout_message = generate_shoutout_message(&ctx.twitch_manager, &ctx.ai_client, &target_username).await?;
 
         // Send the shoutout message
-        ctx.bot_client.send_message(&ctx.channel, &shoutout_message).await?;
+        ctx.twitch_manager.send_long_message_as_bot(&ctx.channel, &shoutout_message).await?;
 
         // Queue the API shoutout
         let api_client = ctx.twitch_manager.get_api_client();
@@ -130,6 +147,39 @@ impl Command for ShoutoutCommand {
 
         Ok(())
     }
+
+    async fn simulate(&self, ctx: &CommandContext, args: Vec<String>) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let target_username = match args.first() {
+            Some(arg) => arg.trim_start_matches('@').to_lowercase(),
+            None => return Ok(Some("Usage: !so <username>".to_string())),
+        };
+        if target_username == ctx.msg.sender.name.to_lowercase() || target_username == ctx.channel.to_lowercase() {
+            return Ok(Some(format!("!so would refuse to shout out {}", target_username)));
+        }
+
+        let user_info = ctx.twitch_manager.get_api_client().get_user_info(&target_username).await?;
+        let user = match user_info["data"].as_array().and_then(|arr| arr.first()) {
+            Some(user) => user,
+            None => return Ok(Some(format!("!so would fail, there's no Twitch user named {}", target_username))),
+        };
+        let user_id = user["id"].as_str().unwrap_or("");
+        let display_name = user["display_name"].as_str().unwrap_or(&target_username);
+
+        let (global, per_user, queued) = {
+            let cooldowns = ctx.twitch_manager.shoutout_cooldowns.lock().await;
+            let (global, per_user) = cooldowns.remaining_cooldowns(user_id);
+            (global, per_user, cooldowns.queue_len())
+        };
+        let mut report = format!(
+            "!so would post a shoutout message for {} and queue a Twitch shoutout behind {} others (global cooldown {}s left",
+            display_name, queued, global
+        );
+        if per_user > 0 {
+            report.push_str(&format!(", {} was shouted out recently, {}s left", display_name, per_user));
+        }
+        report.push(')');
+        Ok(Some(report))
+    }
 }
 
 async fn generate_shoutout_message(
@@ -0,0 +1,94 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+use crate::twitch::soundboard::Players;
+
+pub struct SoundCommand;
+
+#[async_trait::async_trait]
+impl Command for SoundCommand {
+    fn name(&self) -> &'static str {
+        "!sound"
+    }
+
+    fn description(&self) -> &'static str {
+        "Plays a soundboard sound, paid for with drop points: !sound <name>, or !sound on its own for the list"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let config = ctx.config.read().await.soundboard.clone();
+        if !config.enabled {
+            ctx.bot_client.send_message(&ctx.channel, "The soundboard is turned off.").await?;
+            return Ok(());
+        }
+
+        let name = match args.first() {
+            Some(name) => name.to_lowercase(),
+            None => {
+                let sounds: Vec<String> = ctx.twitch_manager.soundboard.list().into_iter()
+                    .filter(|status| status.sound.enabled)
+                    .map(|status| match status.sound.cost {
+                        0 => status.sound.name,
+                        cost => format!("{} ({})", status.sound.name, cost),
+                    })
+                    .collect();
+                let message = if sounds.is_empty() {
+                    "There are no sounds yet.".to_string()
+                } else {
+                    format!("Sounds (drop point cost): {}", sounds.join(", "))
+                };
+                ctx.twitch_manager.send_long_message_as_bot(&ctx.channel, &message).await?;
+                return Ok(());
+            }
+        };
+
+        let sound = match ctx.twitch_manager.soundboard.get(&name) {
+            Some(sound) => sound,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, &format!("@{}, there's no sound called {}.", ctx.msg.sender.name, name)).await?;
+                return Ok(());
+            }
+        };
+
+        // Taken up front so two quick plays can't both spend the same points
+        let is_broadcaster = ctx.msg.badges.iter().any(|badge| badge.name == "broadcaster");
+        let cost = if is_broadcaster { 0 } else { sound.cost as i64 };
+        let balance = if cost > 0 {
+            match ctx.storage.read().await.adjust_drop_points(&ctx.msg.sender.id, -cost)? {
+                Some(balance) => Some(balance),
+                None => {
+                    ctx.bot_client.send_message(&ctx.channel, &format!(
+                        "@{}, {} costs {} drop points and you don't have enough.", ctx.msg.sender.name, sound.name, cost
+                    )).await?;
+                    return Ok(());
+                }
+            }
+        } else {
+            None
+        };
+
+        let players = Players {
+            obs_manager: ctx.twitch_manager.get_obs_manager(),
+            dashboard_state: ctx.twitch_manager.irc_manager.get_dashboard_state(),
+        };
+        match ctx.twitch_manager.soundboard.play(&players, &config, &sound.name, false).await {
+            Ok(_) => {
+                if let Some(balance) = balance {
+                    ctx.bot_client.send_message(&ctx.channel, &format!(
+                        "@{} played {} for {} drop points, {} left.", ctx.msg.sender.name, sound.name, cost, balance
+                    )).await?;
+                }
+            }
+            Err(reason) => {
+                if cost > 0 {
+                    ctx.storage.read().await.adjust_drop_points(&ctx.msg.sender.id, cost)?;
+                }
+                ctx.bot_client.send_message(&ctx.channel, &format!("@{}, {}.", ctx.msg.sender.name, reason)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
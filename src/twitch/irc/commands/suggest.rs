@@ -0,0 +1,41 @@
+use tracing::info;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+use crate::twitch::suggestions::{self, SuggestionKind};
+
+pub struct SuggestCommand;
+
+#[async_trait::async_trait]
+impl Command for SuggestCommand {
+    fn name(&self) -> &'static str {
+        "!suggest"
+    }
+
+    fn description(&self) -> &'static str {
+        "Suggests a stream title or tags for the streamer to approve: !suggest title <title> or !suggest tags <tag> <tag>"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let user = &ctx.msg.sender.name;
+        let message = match args.split_first().and_then(|(kind, rest)| SuggestionKind::parse(kind).map(|kind| (kind, rest.join(" ")))) {
+            Some((kind, input)) => {
+                let config = ctx.config.read().await.suggestions.clone();
+                match suggestions::submit(&*ctx.storage.read().await, &config, kind, &input, &ctx.msg.sender.id, user) {
+                    Ok(_) => format!("@{} thanks, your {} suggestion is waiting for approval!", user, kind.as_str()),
+                    Err(reason) => {
+                        info!("Rejected {} suggestion from {}: {}", kind.as_str(), user, reason);
+                        format!("@{} your suggestion wasn't queued: {}.", user, reason)
+                    }
+                }
+            }
+            None => "Usage: !suggest title <title> or !suggest tags <tag> <tag>".to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
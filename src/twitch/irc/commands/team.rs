@@ -0,0 +1,53 @@
+use tracing::error;
+use crate::twitch::costreams;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct TeamCommand;
+
+#[async_trait::async_trait]
+impl Command for TeamCommand {
+    fn name(&self) -> &'static str {
+        "!team"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lists the co-streamers who are live right now"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let additional_streams = ctx.config.read().await.additional_streams.clone();
+        if costreams::logins(&additional_streams).is_empty() {
+            ctx.bot_client.send_message(&ctx.channel, "There are no co-streamers set up.").await?;
+            return Ok(());
+        }
+
+        let message = match ctx.twitch_manager.costreams.statuses(&ctx.twitch_manager.get_api_client(), &additional_streams).await {
+            Ok(streams) => {
+                let live: Vec<String> = streams.iter()
+                    .filter(|stream| stream.live)
+                    .map(|stream| match &stream.game {
+                        Some(game) => format!("{} ({}) twitch.tv/{}", stream.display_name, game, stream.login),
+                        None => format!("{} twitch.tv/{}", stream.display_name, stream.login),
+                    })
+                    .collect();
+                if live.is_empty() {
+                    "None of the co-streamers are live right now.".to_string()
+                } else {
+                    format!("Live with us: {}", live.join(" | "))
+                }
+            }
+            Err(e) => {
+                error!("Failed to check co-streamers: {:?}", e);
+                "Couldn't check who's live, please try again.".to_string()
+            }
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
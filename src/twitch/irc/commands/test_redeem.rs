@@ -0,0 +1,57 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct TestRedeemCommand;
+
+#[async_trait::async_trait]
+impl Command for TestRedeemCommand {
+    fn name(&self) -> &'static str {
+        "!testredeem"
+    }
+
+    fn description(&self) -> &'static str {
+        "Dry-runs a channel point reward without touching Twitch: !testredeem <reward> [| input]"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let redeem_manager = ctx.redeem_manager.read().await;
+        let redeem_manager = match redeem_manager.as_ref() {
+            Some(redeem_manager) => redeem_manager,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, "Redeems are not initialized").await?;
+                return Ok(());
+            }
+        };
+
+        if args.is_empty() {
+            let rewards = redeem_manager.testable_rewards().await;
+            ctx.bot_client.send_message(&ctx.channel, &format!(
+                "Usage: !testredeem <reward> [| input]. Rewards: {}", rewards.join(", ")
+            )).await?;
+            return Ok(());
+        }
+
+        let joined = args.join(" ");
+        let (reward_title, user_input) = match joined.split_once('|') {
+            Some((title, input)) => (title.trim().to_string(), Some(input.trim().to_string()).filter(|i| !i.is_empty())),
+            None => (joined.trim().to_string(), None),
+        };
+
+        let result = redeem_manager.test_redemption(&reward_title, &ctx.msg.sender.name, user_input).await;
+        let outcome = if result.success { "succeeded" } else { "failed" };
+        let reply = match result.message {
+            Some(message) => format!("[test] {} {}: {}", reward_title, outcome, message),
+            None => format!("[test] {} {}", reward_title, outcome),
+        };
+        ctx.bot_client.send_message(&ctx.channel, &reply).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Broadcaster
+    }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,75 @@
+use tracing::error;
+use crate::ai::translate;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+
+pub struct TranslateCommand;
+
+#[async_trait::async_trait]
+impl Command for TranslateCommand {
+    fn name(&self) -> &'static str {
+        "!translate"
+    }
+
+    fn description(&self) -> &'static str {
+        "Translates a message (or the last chat line) into another language: !translate [language] [message]"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let ai_client = match &ctx.ai_client {
+            Some(ai_client) => ai_client,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, "Translation isn't available right now.").await?;
+                return Ok(());
+            }
+        };
+
+        let translation_config = ctx.config.read().await.translation.clone();
+        let target_language = args.first().cloned().unwrap_or_else(|| translation_config.default_language.clone());
+
+        let text = if args.len() > 1 {
+            args[1..].join(" ")
+        } else {
+            let last_message = ctx.storage.read().await.get_last_chat_message(&ctx.msg.message_id)?;
+            match last_message {
+                Some(entry) => entry.message,
+                None => {
+                    ctx.bot_client.send_message(&ctx.channel, "There's no recent chat message to translate.").await?;
+                    return Ok(());
+                }
+            }
+        };
+
+        let translation = match translate::translate(ai_client, &text, &target_language, translation_config.max_input_chars).await {
+            Ok(translation) => translation,
+            Err(e) => {
+                error!("Translation failed: {}", e);
+                ctx.bot_client.send_message(&ctx.channel, &format!("Sorry, I couldn't translate that: {}", e)).await?;
+                return Ok(());
+            }
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &translation.chat_message()).await?;
+
+        if translation_config.send_to_chatbox {
+            let vrchat_osc = ctx.twitch_manager.get_osc_manager().get_vrchat_osc();
+            if let Err(e) = vrchat_osc.send_chatbox_message(&translation.chatbox_message(), true, false).await {
+                error!("Failed to send translation to VRChat chatbox: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
+
+    fn uses_ai(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,92 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+use crate::twitch::trivia;
+
+const FALLBACK_TOPIC: &str = "video games";
+
+pub struct TriviaCommand;
+
+impl TriviaCommand {
+    // The VRChat world when we're in VRChat, otherwise the stream's category
+    async fn topic(ctx: &CommandContext) -> String {
+        let game = ctx.twitch_manager.get_current_game().await;
+        if game.as_deref().map_or(false, |g| g.eq_ignore_ascii_case("VRChat")) {
+            if let Some(world) = ctx.world_info.lock().await.as_ref() {
+                return format!("the VRChat world {}", world.name);
+            }
+        }
+        game.unwrap_or_else(|| FALLBACK_TOPIC.to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for TriviaCommand {
+    fn name(&self) -> &'static str {
+        "!trivia"
+    }
+
+    fn description(&self) -> &'static str {
+        "Asks chat a trivia question about the current game or world; first correct answer in chat wins points"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if let Some(question) = trivia::current_question(&ctx.twitch_manager) {
+            ctx.bot_client.send_message(&ctx.channel, &format!("There's already a question open: {}", question)).await?;
+            return Ok(());
+        }
+
+        let config = ctx.config.read().await.trivia.clone();
+        let topic = Self::topic(ctx).await;
+        let question = match trivia::pick_question(&ctx.twitch_manager, ctx.ai_client.as_deref(), &config, &topic).await {
+            Some(question) => question,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, "I couldn't come up with a trivia question right now, try again in a bit.").await?;
+                return Ok(());
+            }
+        };
+
+        let message = format!("Trivia time! {} (answer in chat within {}s)", question.question, config.answer_secs);
+        if trivia::start(ctx.twitch_manager.clone(), &config, question) {
+            ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        }
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
+}
+
+pub struct TriviaStatsCommand;
+
+#[async_trait::async_trait]
+impl Command for TriviaStatsCommand {
+    fn name(&self) -> &'static str {
+        "!triviastats"
+    }
+
+    fn description(&self) -> &'static str {
+        "Shows trivia points and correct answers: !triviastats [user]"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let user = args.first()
+            .map(|u| u.trim_start_matches('@').to_lowercase())
+            .unwrap_or_else(|| ctx.msg.sender.login.clone());
+
+        let message = match ctx.storage.read().await.get_trivia_stats(&user)? {
+            Some((correct, points, rank)) => format!("{} has {} trivia points from {} correct answers (#{}).", user, points, correct, rank),
+            None => format!("{} hasn't answered any trivia yet.", user),
+        };
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
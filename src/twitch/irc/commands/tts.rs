@@ -0,0 +1,102 @@
+use tracing::error;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+use crate::twitch::tts;
+
+pub struct SkipTtsCommand;
+
+#[async_trait::async_trait]
+impl Command for SkipTtsCommand {
+    fn name(&self) -> &'static str {
+        "!skiptts"
+    }
+
+    fn description(&self) -> &'static str {
+        "Stops the TTS message that's currently being read out"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match tts::skip(&ctx.twitch_manager.irc_manager.get_dashboard_state()).await {
+            Ok(0) => "No TTS overlay is connected.".to_string(),
+            Ok(_) => "Skipped the current TTS.".to_string(),
+            Err(e) => {
+                error!("Failed to skip TTS: {}", e);
+                "Couldn't skip the TTS, please try again.".to_string()
+            }
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
+
+pub struct TtsBanCommand;
+
+#[async_trait::async_trait]
+impl Command for TtsBanCommand {
+    fn name(&self) -> &'static str {
+        "!ttsban"
+    }
+
+    fn description(&self) -> &'static str {
+        "Stops a user's redeems and alerts from being read out: !ttsban <user>"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match args.first().map(|u| u.trim_start_matches('@').to_lowercase()) {
+            Some(user) => match ctx.storage.read().await.add_tts_ban(&user, &ctx.msg.sender.login) {
+                Ok(()) => format!("{} can no longer use TTS.", user),
+                Err(e) => {
+                    error!("Failed to ban {} from TTS: {:?}", user, e);
+                    format!("Couldn't ban {} from TTS, please try again.", user)
+                }
+            },
+            None => "Usage: !ttsban <user>".to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
+
+pub struct TtsUnbanCommand;
+
+#[async_trait::async_trait]
+impl Command for TtsUnbanCommand {
+    fn name(&self) -> &'static str {
+        "!ttsunban"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lets a user use TTS again: !ttsunban <user>"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = match args.first().map(|u| u.trim_start_matches('@').to_lowercase()) {
+            Some(user) => match ctx.storage.read().await.remove_tts_ban(&user) {
+                Ok(true) => format!("{} can use TTS again.", user),
+                Ok(false) => format!("{} isn't banned from TTS.", user),
+                Err(e) => {
+                    error!("Failed to unban {} from TTS: {:?}", user, e);
+                    format!("Couldn't unban {} from TTS, please try again.", user)
+                }
+            },
+            None => "Usage: !ttsunban <user>".to_string(),
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Moderator
+    }
+}
@@ -1,7 +1,8 @@
 use crate::twitch::irc::command_system::{Command, CommandContext};
 use crate::twitch::roles::UserRole;
+use crate::twitch::stream_breaks::format_duration;
 use crate::twitch::utils::get_stream_uptime;
-use log::{error, warn};
+use tracing::{error, warn};
 
 pub struct UptimeCommand;
 
@@ -12,31 +13,52 @@ impl Command for UptimeCommand {
     }
 
     fn description(&self) -> &'static str {
-        "Shows how long the stream has been live"
+        "Shows how long the stream has been live, and how much of that wasn't on a break"
     }
 
     async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         warn!("Starting handle_uptime for channel: {}", ctx.channel);
 
-        match get_stream_uptime(&ctx.channel, ctx.twitch_manager.get_api_client()).await {
-            Ok(uptime) => {
-                let response = match uptime {
-                    Some(duration) => format!(
-                        "Stream has been live for {} hours, {} minutes, and {} seconds",
-                        duration.num_hours(),
-                        duration.num_minutes() % 60,
-                        duration.num_seconds() % 60,
-                    ),
-                    None => "Stream is currently offline.".to_string(),
+        let uptime = get_stream_uptime(&ctx.channel, ctx.twitch_manager.get_api_client()).await;
+        let summary = ctx.twitch_manager.stream_state_machine.uptime_summary().await;
+
+        let response = match (uptime, summary) {
+            (uptime, Some(summary)) if summary.breaks > 0 || summary.on_break => {
+                // Twitch restarts its clock after a drop, and the bot's own clock starts
+                // late if it was restarted mid-stream, so trust whichever is longer
+                let api_seconds = match uptime {
+                    Ok(Some(duration)) => duration.num_seconds(),
+                    _ => 0,
                 };
-                ctx.bot_client.send_message(&ctx.channel, &response).await?;
-            },
-            Err(e) => {
+                let raw_seconds = summary.raw_seconds.max(api_seconds);
+                let break_seconds = summary.raw_seconds - summary.active_seconds;
+                let break_count = summary.breaks + summary.on_break as u32;
+                let mut response = format!(
+                    "Stream has been live for {} ({} active, {} {})",
+                    format_duration(raw_seconds),
+                    format_duration((raw_seconds - break_seconds).max(0)),
+                    break_count,
+                    if break_count == 1 { "break" } else { "breaks" },
+                );
+                if summary.on_break {
+                    response.push_str(" - on a break right now, be right back!");
+                }
+                response
+            }
+            (Ok(Some(duration)), _) => format!(
+                "Stream has been live for {} hours, {} minutes, and {} seconds",
+                duration.num_hours(),
+                duration.num_minutes() % 60,
+                duration.num_seconds() % 60,
+            ),
+            (Ok(None), _) => "Stream is currently offline.".to_string(),
+            (Err(e), _) => {
                 error!("Error getting stream uptime: {:?}", e);
-                let error_response = "Sorry, I couldn't retrieve the stream uptime. Please try again later.".to_string();
-                ctx.bot_client.send_message(&ctx.channel, &error_response).await?;
+                "Sorry, I couldn't retrieve the stream uptime. Please try again later.".to_string()
             }
-        }
+        };
+        ctx.bot_client.send_message(&ctx.channel, &response).await?;
+
         warn!("Completed handle_uptime");
         Ok(())
     }
@@ -44,4 +66,4 @@ impl Command for UptimeCommand {
     fn required_role(&self) -> UserRole {
         UserRole::Viewer
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,6 @@
 use crate::twitch::irc::command_system::{Command, CommandContext};
 use crate::twitch::roles::UserRole;
-use log::{info, warn};
+use tracing::{info, warn};
 
 pub struct VerifyCommand;
 
@@ -0,0 +1,37 @@
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::quick_poll;
+use crate::twitch::roles::UserRole;
+
+pub struct VoteCommand;
+
+#[async_trait::async_trait]
+impl Command for VoteCommand {
+    fn name(&self) -> &'static str {
+        "!vote"
+    }
+
+    fn description(&self) -> &'static str {
+        "Votes in the current chat poll: !vote <number or option>"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let current = match quick_poll::current(&ctx.twitch_manager) {
+            Some(current) => current,
+            None => return Ok(()),
+        };
+
+        // Accepted votes stay quiet so a busy poll doesn't flood chat
+        match quick_poll::vote(&ctx.twitch_manager, &ctx.msg.sender.id, &args.join(" ")) {
+            Some(summary) => quick_poll::publish(&ctx.twitch_manager, &summary).await,
+            None => {
+                let message = format!("@{} pick one of: {}", ctx.msg.sender.name, quick_poll::describe_options(&current));
+                ctx.bot_client.send_message(&ctx.channel, &message).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
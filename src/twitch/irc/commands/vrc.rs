@@ -1,6 +1,7 @@
 use crate::twitch::irc::command_system::{Command, CommandContext};
 use crate::twitch::roles::UserRole;
 use crate::twitch::api::requests::announcement::send_announcement;
+use crate::ai::prompts::Prompts;
 use crate::ai::AIClient;
 use std::sync::Arc;
 
@@ -21,7 +22,7 @@ impl Command for VRCCommand {
         let vrc_link = irc_manager.get_vrchat_group_link().await;
 
         // Generate a custom greeting using AI
-        let vrc_message = generate_vrc_message(&ctx.ai_client, &vrc_link).await;
+        let vrc_message = generate_vrc_message(&ctx.ai_client, &ctx.twitch_manager.prompts, &vrc_link).await;
 
         let api_client = ctx.twitch_manager.get_api_client();
         // Send an announcement
@@ -35,19 +36,15 @@ impl Command for VRCCommand {
     fn required_role(&self) -> UserRole {
         UserRole::Viewer
     }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
 }
 
-async fn generate_vrc_message(ai_client: &Option<Arc<AIClient>>, vrc_link: &str) -> String {
+async fn generate_vrc_message(ai_client: &Option<Arc<AIClient>>, prompts: &Prompts, vrc_link: &str) -> String {
     if let Some(ai) = ai_client {
-        let prompt = format!(
-            "Generate a friendly and inviting message to encourage Twitch viewers to join our VRChat community group. \
-            Feel free to mention: VR, technology, cute & funny anime, catgirls, foxgirls, \
-            catboys, foxboys, 3D art or living in the matrix. \
-            Don't use the word viewers.  If anything, say chatters or everyone. Good vibes. Good vibes. \
-            The message should be brief (1-2 sentences) and include the following VRChat group link: {}. \
-            Make sure the tone is casual and welcoming.",
-            vrc_link
-        );
+        let prompt = prompts.render("command.vrc", &[("link", vrc_link)]);
 
         match ai.generate_response_without_history(&prompt).await {
             Ok(response) => {
@@ -0,0 +1,136 @@
+use tracing::error;
+use serenity::model::id::ChannelId;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+use crate::vrchat::groups::{create_group_instance, launch_url, resolve_group};
+use crate::vrchat::GroupAccessType;
+
+pub struct GroupInfoCommand;
+pub struct GroupInstanceCommand;
+
+#[async_trait::async_trait]
+impl Command for GroupInfoCommand {
+    fn name(&self) -> &'static str {
+        "!groupinfo"
+    }
+
+    fn description(&self) -> &'static str {
+        "Shows the channel's VRChat group"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, _args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let group_link = match ctx.config.read().await.social_links.vrchat_group.clone() {
+            Some(link) => link,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, "No VRChat group is set up for this channel.").await?;
+                return Ok(());
+            }
+        };
+
+        // Without a VRChat session the link is still worth sharing
+        let vrchat_manager = match &ctx.vrchat_manager {
+            Some(vrchat_manager) => vrchat_manager,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, &format!("Join our VRChat group: {}", group_link)).await?;
+                return Ok(());
+            }
+        };
+
+        let message = match resolve_group(&vrchat_manager.api(), &group_link).await {
+            Ok(group) => format!("VRChat group: {} ({} members) - {}", group.name, group.member_count, group.url()),
+            Err(e) => {
+                error!("Failed to look up VRChat group: {}", e);
+                format!("Join our VRChat group: {}", group_link)
+            }
+        };
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
+
+#[async_trait::async_trait]
+impl Command for GroupInstanceCommand {
+    fn name(&self) -> &'static str {
+        "!groupinstance"
+    }
+
+    fn description(&self) -> &'static str {
+        "Opens a VRChat group instance of the current world and shares it on Discord: !groupinstance [public|plus|members]"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let access = match args.first().map(|a| a.to_lowercase()).as_deref() {
+            None | Some("public") => GroupAccessType::Public,
+            Some("plus") => GroupAccessType::Plus,
+            Some("members") => GroupAccessType::Members,
+            Some(_) => {
+                ctx.bot_client.send_message(&ctx.channel, "Usage: !groupinstance [public|plus|members]").await?;
+                return Ok(());
+            }
+        };
+
+        let (group_link, discord_channel) = {
+            let config = ctx.config.read().await;
+            (config.social_links.vrchat_group.clone(), config.discord_announcement_channel_id.clone())
+        };
+        let group_link = match group_link {
+            Some(link) => link,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, "Set a VRChat group link in the config first.").await?;
+                return Ok(());
+            }
+        };
+
+        let vrchat_manager = match &ctx.vrchat_manager {
+            Some(vrchat_manager) => vrchat_manager,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, "VRChat isn't connected right now.").await?;
+                return Ok(());
+            }
+        };
+
+        let world = match vrchat_manager.get_current_world().await {
+            Ok(world) => world,
+            Err(_) => {
+                ctx.bot_client.send_message(&ctx.channel, "Couldn't tell which world we're in, so no instance was opened.").await?;
+                return Ok(());
+            }
+        };
+
+        let api = vrchat_manager.api();
+        let group = resolve_group(&api, &group_link).await?;
+        let instance = create_group_instance(&api, &group, &world.id, access).await?;
+        let url = launch_url(&instance);
+
+        ctx.bot_client.send_message(&ctx.channel, &format!(
+            "Opened a {} instance of {} for {}!", group.name, world.name, match access {
+                GroupAccessType::Public => "everyone",
+                GroupAccessType::Plus => "group members and their friends",
+                GroupAccessType::Members => "group members",
+            }
+        )).await?;
+
+        let channel_id = discord_channel.and_then(|id| id.parse::<u64>().ok());
+        if let (Some(discord_client), Some(channel_id)) = (&ctx.twitch_manager.discord_client, channel_id) {
+            let http = discord_client.get_http().await;
+            let message = format!("🐾 **{}** group instance is open in **{}**! Join here: {}", group.name, world.name, url);
+            if let Err(e) = ChannelId::new(channel_id).say(&http, message).await {
+                error!("Failed to share group instance on Discord: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Broadcaster
+    }
+
+    fn is_heavy(&self) -> bool {
+        true
+    }
+}
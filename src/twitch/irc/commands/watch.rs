@@ -0,0 +1,50 @@
+use tracing::info;
+use crate::twitch::irc::command_system::{Command, CommandContext};
+use crate::twitch::roles::UserRole;
+use crate::twitch::watch_queue;
+
+pub struct WatchCommand;
+
+#[async_trait::async_trait]
+impl Command for WatchCommand {
+    fn name(&self) -> &'static str {
+        "!watch"
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds a YouTube video to the movie night queue, or shows what's on: !watch [youtube link]"
+    }
+
+    async fn execute(&self, ctx: &CommandContext, args: Vec<String>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let user = &ctx.msg.sender.name;
+        let message = match args.first() {
+            Some(link) => {
+                let config = ctx.config.read().await.watch_queue.clone();
+                match ctx.twitch_manager.watch_requests.request(&config, link, &ctx.msg.sender.id, user).await {
+                    Ok((item, position)) => format!("@{} added {} at #{} in the queue!", user, watch_queue::describe(&item), position),
+                    Err(reason) => {
+                        info!("Rejected watch request from {}: {}", user, reason);
+                        format!("@{} couldn't add that: {}.", user, reason)
+                    }
+                }
+            }
+            None => {
+                let queue = ctx.twitch_manager.watch_requests.snapshot();
+                match queue.current {
+                    Some(current) => format!(
+                        "Now watching {} from {}. {} more in the queue.",
+                        watch_queue::describe(&current), current.requested_by, queue.queue.len()
+                    ),
+                    None => format!("Nothing playing yet. {} in the queue, add one with !watch <youtube link>", queue.queue.len()),
+                }
+            }
+        };
+
+        ctx.bot_client.send_message(&ctx.channel, &message).await?;
+        Ok(())
+    }
+
+    fn required_role(&self) -> UserRole {
+        UserRole::Viewer
+    }
+}
@@ -1,12 +1,11 @@
 use crate::twitch::irc::command_system::{Command, CommandContext};
 use crate::twitch::roles::UserRole;
-use log::{error, info};
+use tracing::{error, info};
 use tokio::time::Duration;
 use tokio::sync::Mutex;
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
-
-const MAX_IRC_MESSAGE_LENGTH: usize = 500;
+use crate::chunker;
 
 pub struct WorldCommand;
 
@@ -35,13 +34,21 @@ impl Command for WorldCommand {
         }
         cooldowns.insert(ctx.channel.clone(), now);
 
-        if !ctx.vrchat_manager.is_online().await {
+        let vrchat_manager = match &ctx.vrchat_manager {
+            Some(vrchat_manager) => vrchat_manager,
+            None => {
+                ctx.bot_client.send_message(&ctx.channel, "VRChat isn't connected right now.").await?;
+                return Ok(());
+            }
+        };
+
+        if !vrchat_manager.is_online().await {
             info!("VRChat status is offline");
             ctx.bot_client.send_message(&ctx.channel, "The VRChat status is currently offline.").await?;
             return Ok(());
         }
 
-        match ctx.vrchat_manager.get_current_world().await {
+        match vrchat_manager.get_current_world().await {
             Ok(world) => {
                 info!("Successfully fetched current world data");
 
@@ -60,15 +67,15 @@ impl Command for WorldCommand {
                 );
 
                 // Split messages if they're too long
-                let first_messages = split_message(&first_message);
-                let second_messages = split_message(&second_message);
+                let first_messages = chunker::chat_chunks(&first_message);
+                let second_messages = chunker::chat_chunks(&second_message);
 
                 // Send all parts with proper delays and error handling
                 for msg in first_messages {
                     match ctx.bot_client.send_message(&ctx.channel, &msg).await {
                         Ok(_) => {
                             // Significant delay between messages to avoid rate limiting
-                            tokio::time::sleep(Duration::from_millis(500)).await;
+                            tokio::time::sleep(chunker::CHUNK_DELAY).await;
                         },
                         Err(e) => {
                             error!("Failed to send first part of world info: {:?}", e);
@@ -88,7 +95,7 @@ impl Command for WorldCommand {
                 for msg in second_messages {
                     match ctx.bot_client.send_message(&ctx.channel, &msg).await {
                         Ok(_) => {
-                            tokio::time::sleep(Duration::from_millis(500)).await;
+                            tokio::time::sleep(chunker::CHUNK_DELAY).await;
                         },
                         Err(e) => {
                             error!("Failed to send second part of world info: {:?}", e);
@@ -118,42 +125,3 @@ impl Command for WorldCommand {
         UserRole::Subscriber
     }
 }
-
-fn split_message(message: &str) -> Vec<String> {
-    if message.len() <= MAX_IRC_MESSAGE_LENGTH {
-        return vec![message.to_string()];
-    }
-
-    let mut messages = Vec::new();
-    let mut current_message = String::new();
-    let words = message.split_whitespace();
-
-    for word in words {
-        if current_message.len() + word.len() + 1 > MAX_IRC_MESSAGE_LENGTH {
-            if !current_message.is_empty() {
-                messages.push(current_message.trim().to_string());
-                current_message = String::new();
-            }
-            // If a single word is too long, split it
-            if word.len() > MAX_IRC_MESSAGE_LENGTH {
-                for chunk in word.as_bytes().chunks(MAX_IRC_MESSAGE_LENGTH - 3) {
-                    let chunk_str = String::from_utf8_lossy(chunk);
-                    messages.push(format!("{}...", chunk_str));
-                }
-            } else {
-                current_message = word.to_string();
-            }
-        } else {
-            if !current_message.is_empty() {
-                current_message.push(' ');
-            }
-            current_message.push_str(word);
-        }
-    }
-
-    if !current_message.is_empty() {
-        messages.push(current_message.trim().to_string());
-    }
-
-    messages
-}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::chunker::TWITCH_MAX_CHARS;
+
+// Twitch silently drops a message that's identical to the sender's previous one within this window
+const DUPLICATE_WINDOW: Duration = Duration::from_secs(30);
+// A space and a tag character: invisible in chat, but enough for Twitch to see a different message
+const INVISIBLE_SUFFIX: &str = " \u{E0000}";
+
+struct LastMessage {
+    text: String,
+    sent_at: Instant,
+}
+
+#[derive(Default)]
+pub struct Deduplicator {
+    // Keyed by (sender, channel), since Twitch compares each account's messages per channel
+    last_sent: Mutex<HashMap<(String, String), LastMessage>>,
+}
+
+impl Deduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The text to actually send. A repeat of the last message gets the invisible suffix, and the one after
+    // that goes out plain again, so timers and repeated command replies always get through.
+    pub fn deduplicate(&self, sender: &str, channel: &str, message: &str) -> String {
+        let key = (sender.to_lowercase(), channel.trim_start_matches('#').to_lowercase());
+        let mut last_sent = self.last_sent.lock().unwrap();
+        let text = match last_sent.get(&key) {
+            Some(last) if last.sent_at.elapsed() < DUPLICATE_WINDOW && last.text == message => with_suffix(message),
+            _ => message.to_string(),
+        };
+        last_sent.insert(key, LastMessage { text: text.clone(), sent_at: Instant::now() });
+        text
+    }
+}
+
+// A message already at the length limit gives up its last characters to make room
+fn with_suffix(message: &str) -> String {
+    let room = TWITCH_MAX_CHARS - INVISIBLE_SUFFIX.chars().count();
+    let base: String = message.chars().take(room).collect();
+    format!("{}{}", base.trim_end(), INVISIBLE_SUFFIX)
+}
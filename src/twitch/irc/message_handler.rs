@@ -2,19 +2,29 @@
 
 use super::command_system::{CommandContext, CommandRegistry};
 use crate::config::Config;
-use crate::storage::StorageClient;
+use crate::storage::{expand_kv_placeholders, StorageClient};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
-use log::{debug, error};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, warn, Instrument};
 use tokio::sync::{mpsc, Mutex, RwLock};
 use twitch_irc::message::ServerMessage;
 use crate::ai::AIClient;
+use crate::hooks::BotEvent;
 use crate::obs::OBSManager;
+use crate::osc::ChatRelay;
+use crate::twitch::clips::{archive_clip, extract_clip_ids};
+use crate::twitch::{chatter_drops, faq, ignore_list, opt_out, trivia};
+use crate::twitch::irc::triggers::{KeywordTriggerManager, TriggerAction};
+use crate::twitch::roles::get_user_role;
 use crate::vrchat::{VRChatManager, World};
 use crate::web_ui::websocket::WebSocketMessage;
 use crate::twitch::manager::TwitchManager;
 use crate::twitch::irc::commands::{
     PingCommand,
     CalcCommand,
+    RollCommand,
     DiscordCommand,
     IsItFridayCommand,
     XmasCommand,
@@ -32,19 +42,129 @@ use crate::twitch::irc::commands::{
     RefreshAdsCommand,
     AdNomsterCommand,
     ContinueCommand,
+    ClipCommand,
+    DeleteCommand, NukeCommand,
+    TranslateCommand,
+    CommandsCommand,
+    TestRedeemCommand,
+    GroupInfoCommand,
+    GroupInstanceCommand,
+    IgnoreCommand,
+    BrbCommand,
+    BotVoiceCommand,
+    QuickPollCommand,
+    VoteCommand,
+    LinkCommand,
+    TriviaCommand,
+    TriviaStatsCommand,
+    ReactCommand,
+    ProfileCommand,
+    LurkCommand,
+    UnlurkCommand,
+    LurkStatsCommand,
+    HappyHourCommand,
+    SkipTtsCommand,
+    TtsBanCommand,
+    TtsUnbanCommand,
+    TeamCommand,
+    RaidTrainCommand,
+    SuggestCommand,
+    WatchCommand,
+    BitsVoteCommand,
+    DiagCommand,
+    GuestVipCommand,
+    GuestModCommand,
+    OptOutCommand,
+    OptInCommand,
+    FaqCommand,
+    SoundCommand,
+    AddComCommand,
+    EditComCommand,
+    DelComCommand,
 };
 
 
+// Every built-in chat command; TwitchManager keeps the one registry that chat, plugins and the web UI share.
+pub(crate) fn builtin_commands() -> CommandRegistry {
+    let mut command_registry = CommandRegistry::new();
+
+    // Register commands here
+    command_registry.register(Box::new(PingCommand));
+    command_registry.register(Box::new(CalcCommand));
+    command_registry.register(Box::new(RollCommand));
+    command_registry.register(Box::new(DiscordCommand));
+    command_registry.register(Box::new(FollowersCommand));
+    command_registry.register(Box::new(FollowAgeCommand));
+    command_registry.register(Box::new(IsItFridayCommand));
+    command_registry.register(Box::new(XmasCommand));
+    command_registry.register(Box::new(ShoutoutCommand));
+    command_registry.register(Box::new(UptimeCommand));
+    command_registry.register(Box::new(VerifyCommand));
+    command_registry.register(Box::new(VRCCommand));
+    command_registry.register(Box::new(WorldCommand));
+    command_registry.register(Box::new(ResetDropGameCommand));
+    command_registry.register(Box::new(TitleCommand));
+    command_registry.register(Box::new(GameCommand));
+    command_registry.register(Box::new(ContentCommand));
+    command_registry.register(Box::new(RunAdCommand));
+    command_registry.register(Box::new(RefreshAdsCommand));
+    command_registry.register(Box::new(AdNomsterCommand));
+    command_registry.register(Box::new(ContinueCommand));
+    command_registry.register(Box::new(ClipCommand));
+    command_registry.register(Box::new(DeleteCommand));
+    command_registry.register(Box::new(NukeCommand));
+    command_registry.register(Box::new(TranslateCommand));
+    command_registry.register(Box::new(CommandsCommand));
+    command_registry.register(Box::new(TestRedeemCommand));
+    command_registry.register(Box::new(GroupInfoCommand));
+    command_registry.register(Box::new(GroupInstanceCommand));
+    command_registry.register(Box::new(IgnoreCommand));
+    command_registry.register(Box::new(BrbCommand));
+    command_registry.register(Box::new(BotVoiceCommand));
+    command_registry.register(Box::new(QuickPollCommand));
+    command_registry.register(Box::new(VoteCommand));
+    command_registry.register(Box::new(LinkCommand));
+    command_registry.register(Box::new(TriviaCommand));
+    command_registry.register(Box::new(TriviaStatsCommand));
+    command_registry.register(Box::new(ReactCommand));
+    command_registry.register(Box::new(ProfileCommand));
+    command_registry.register(Box::new(LurkCommand));
+    command_registry.register(Box::new(UnlurkCommand));
+    command_registry.register(Box::new(LurkStatsCommand));
+    command_registry.register(Box::new(HappyHourCommand));
+    command_registry.register(Box::new(SkipTtsCommand));
+    command_registry.register(Box::new(TtsBanCommand));
+    command_registry.register(Box::new(TtsUnbanCommand));
+    command_registry.register(Box::new(TeamCommand));
+    command_registry.register(Box::new(RaidTrainCommand));
+    command_registry.register(Box::new(SuggestCommand));
+    command_registry.register(Box::new(WatchCommand));
+    command_registry.register(Box::new(BitsVoteCommand));
+    command_registry.register(Box::new(DiagCommand));
+    command_registry.register(Box::new(GuestVipCommand));
+    command_registry.register(Box::new(GuestModCommand));
+    command_registry.register(Box::new(OptOutCommand));
+    command_registry.register(Box::new(OptInCommand));
+    command_registry.register(Box::new(FaqCommand));
+    command_registry.register(Box::new(SoundCommand));
+    command_registry.register(Box::new(AddComCommand));
+    command_registry.register(Box::new(EditComCommand));
+    command_registry.register(Box::new(DelComCommand));
+
+    command_registry
+}
+
 pub struct MessageHandler {
     config: Arc<RwLock<Config>>,
     pub(crate) twitch_manager: Arc<TwitchManager>,
     storage: Arc<RwLock<StorageClient>>,
     websocket_sender: mpsc::UnboundedSender<WebSocketMessage>,
     world_info: Arc<Mutex<Option<World>>>,
-    vrchat_manager: Arc<VRChatManager>,
+    vrchat_manager: Option<Arc<VRChatManager>>,
     ai_client: Option<Arc<AIClient>>,
-    command_registry: CommandRegistry,
     obs_manager: Arc<OBSManager>,
+    chat_relay: ChatRelay,
+    keyword_triggers: Arc<KeywordTriggerManager>,
 }
 
 impl MessageHandler {
@@ -54,33 +174,15 @@ impl MessageHandler {
         storage: Arc<RwLock<StorageClient>>,
         websocket_sender: mpsc::UnboundedSender<WebSocketMessage>,
         world_info: Arc<Mutex<Option<World>>>,
-        vrchat_manager: Arc<VRChatManager>,
+        vrchat_manager: Option<Arc<VRChatManager>>,
         ai_client: Option<Arc<AIClient>>,
         obs_manager: Arc<OBSManager>,
+        keyword_triggers: Arc<KeywordTriggerManager>,
     ) -> Self {
-        let mut command_registry = CommandRegistry::new();
-
-        // Register commands here
-        command_registry.register(Box::new(PingCommand));
-        command_registry.register(Box::new(CalcCommand));
-        command_registry.register(Box::new(DiscordCommand));
-        command_registry.register(Box::new(FollowersCommand));
-        command_registry.register(Box::new(FollowAgeCommand));
-        command_registry.register(Box::new(IsItFridayCommand));
-        command_registry.register(Box::new(XmasCommand));
-        command_registry.register(Box::new(ShoutoutCommand));
-        command_registry.register(Box::new(UptimeCommand));
-        command_registry.register(Box::new(VerifyCommand));
-        command_registry.register(Box::new(VRCCommand));
-        command_registry.register(Box::new(WorldCommand));
-        command_registry.register(Box::new(ResetDropGameCommand));
-        command_registry.register(Box::new(TitleCommand));
-        command_registry.register(Box::new(GameCommand));
-        command_registry.register(Box::new(ContentCommand));
-        command_registry.register(Box::new(RunAdCommand));
-        command_registry.register(Box::new(RefreshAdsCommand));
-        command_registry.register(Box::new(AdNomsterCommand));
-        command_registry.register(Box::new(ContinueCommand));
+        let chat_relay = ChatRelay::new(
+            twitch_manager.get_osc_manager().get_vrchat_osc(),
+            config.clone(),
+        );
 
         MessageHandler {
             config,
@@ -90,27 +192,108 @@ impl MessageHandler {
             world_info,
             vrchat_manager,
             ai_client,
-            command_registry,
             obs_manager,
+            chat_relay,
+            keyword_triggers,
         }
     }
 
-    pub async fn handle_messages(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    pub async fn handle_messages(self: Arc<Self>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut receiver = self.twitch_manager.irc_manager.subscribe();
+        let (worker_count, queue_size) = {
+            let config = self.config.read().await;
+            (config.message_workers.workers.max(1), config.message_workers.queue_size.max(1))
+        };
+
+        // Each worker processes its shard in order; a user always maps to the same worker
+        let mut workers = Vec::with_capacity(worker_count);
+        for _ in 0..worker_count {
+            let (tx, mut rx) = mpsc::channel::<ServerMessage>(queue_size);
+            let handler = self.clone();
+            self.twitch_manager.tasks.spawn("message_worker", async move {
+                while let Some(message) = rx.recv().await {
+                    handler.handle_timed(message).await;
+                }
+            });
+            workers.push(tx);
+        }
 
-        while let Ok(message) = receiver.recv().await {
-            if let Err(e) = self.handle_message(message).await {
-                error!("Error handling message: {:?}", e);
+        while let Some(message) = receiver.recv().await {
+            let shard = Self::shard_for(&message, worker_count);
+            // A full shard holds up reading until it catches up; no message is ever dropped
+            if workers[shard].send(message).await.is_err() {
+                error!("Message worker {} has stopped", shard);
             }
         }
 
         Ok(())
     }
 
+    // Handles one message inside a tracing span and records how long it took
+    pub(crate) async fn handle_timed(&self, message: ServerMessage) {
+        let (user, channel) = match &message {
+            ServerMessage::Privmsg(msg) => (msg.sender.login.clone(), msg.channel_login.clone()),
+            _ => (String::new(), String::new()),
+        };
+        let span = tracing::info_span!("chat_message", user = %user, channel = %channel);
+        let started = Instant::now();
+        let is_chat = matches!(message, ServerMessage::Privmsg(_));
+
+        if let Err(e) = self.handle_message(message).instrument(span).await {
+            error!("Error handling message: {:?}", e);
+        }
+
+        // Only chat messages do real work; the rest return straight away and would drown out the numbers
+        if is_chat {
+            let elapsed = started.elapsed();
+            let threshold = Duration::from_millis(self.config.read().await.command_metrics.slow_message_ms);
+            let slow = elapsed > threshold;
+            self.twitch_manager.command_latency.record_message(elapsed, slow);
+            if slow {
+                warn!(
+                    elapsed_ms = elapsed.as_millis() as u64,
+                    "Slow chat message from {} in {} took {:?} (threshold {:?})",
+                    user, channel, elapsed, threshold
+                );
+            }
+        }
+    }
+
+    fn shard_for(message: &ServerMessage, worker_count: usize) -> usize {
+        let user_id = match message {
+            ServerMessage::Privmsg(msg) => &msg.sender.id,
+            ServerMessage::Whisper(msg) => &msg.sender.id,
+            _ => return 0,
+        };
+        let mut hasher = DefaultHasher::new();
+        user_id.hash(&mut hasher);
+        (hasher.finish() as usize) % worker_count
+    }
+
     pub(crate) async fn handle_message(&self, message: ServerMessage) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         debug!("Processing message in handle_message: {:?}", message);
 
+        if let ServerMessage::Join(msg) = &message {
+            let bot_username = self.config.read().await.twitch_bot_username.clone();
+            if !bot_username.map_or(false, |bot| bot.eq_ignore_ascii_case(&msg.user_login)) {
+                self.twitch_manager.get_follow_guard().on_join(&self.twitch_manager, &msg.channel_login).await;
+            }
+            return Ok(());
+        }
+
         if let ServerMessage::Privmsg(msg) = message {
+            let ignore_list = self.config.read().await.ignore_list.clone();
+            if ignore_list::is_ignored(&ignore_list, &*self.storage.read().await, &msg.sender.id, &msg.sender.login) {
+                debug!("Ignoring message from {}", msg.sender.login);
+                return Ok(());
+            }
+            let opted_out = opt_out::is_opted_out(&*self.storage.read().await, &msg.sender.id);
+
+            let event_hooks = self.twitch_manager.get_event_hooks();
+            if !event_hooks.is_empty() {
+                event_hooks.emit(&BotEvent::ChatMessage(msg.clone()));
+            }
+
             let cleaned_message = msg.message_text
                 .chars()
                 .filter(|&c| !c.is_control() && !c.is_whitespace() || c.is_ascii_whitespace())
@@ -132,7 +315,39 @@ impl MessageHandler {
                 error!("Failed to send message to WebSocket: {:?}", e);
             }
 
+            if !self.twitch_manager.get_raid_mode().is_active().await {
+                self.chat_relay.relay(&msg, &cleaned_message).await;
+            }
+
+            for clip_id in extract_clip_ids(&cleaned_message) {
+                let twitch_manager = self.twitch_manager.clone();
+                let submitted_by = msg.sender.name.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = archive_clip(&twitch_manager, &clip_id, &submitted_by).await {
+                        error!("Failed to archive clip {}: {:?}", clip_id, e);
+                    }
+                });
+            }
+
+            // Kept for everyone, opted out or not, so moderation can still reach their messages
+            self.twitch_manager.recent_chat.record(&msg.message_id, &msg.sender.id, &msg.sender.login, &cleaned_message);
+            if !opted_out {
+                let storage = self.storage.read().await;
+                if let Err(e) = storage.log_chat_message(&msg.message_id, &msg.sender.id, &msg.sender.login, &cleaned_message) {
+                    error!("Failed to log chat message: {:?}", e);
+                }
+                if let Err(e) = storage.record_chat_activity(&msg.sender.id, &msg.sender.name) {
+                    error!("Failed to record chat activity: {:?}", e);
+                }
+            }
+            chatter_drops::record(&self.twitch_manager, &msg);
+            trivia::check_answer(&self.twitch_manager, &msg, &cleaned_message).await;
+
             let mut parts = cleaned_message.split_whitespace();
+            // Replies are prefixed with the parent's @mention, so skip it to find the command
+            if msg.source.tags.0.contains_key("reply-parent-msg-id") && cleaned_message.starts_with('@') {
+                parts.next();
+            }
             let command = parts.next();
             let args: Vec<String> = parts.map(String::from).collect();
 
@@ -153,9 +368,56 @@ impl MessageHandler {
                     obs_manager: self.obs_manager.clone(),
                 };
 
-                self.command_registry.execute(cmd, &ctx, args).await?;
+                // A failed command shouldn't keep the message from reaching keyword triggers and the FAQ
+                if let Err(e) = self.twitch_manager.commands.execute(cmd, &ctx, args).await {
+                    error!("Error running {}: {:?}", cmd, e);
+                }
+
+                // Don't let the bot trigger itself
+                let bot_username = self.config.read().await.twitch_bot_username.clone();
+                let from_bot = bot_username.map_or(false, |bot| bot.eq_ignore_ascii_case(&msg.sender.login));
+                if !cmd.starts_with('!') && !from_bot {
+                    self.handle_keyword_triggers(&ctx, &cleaned_message).await?;
+                    let faq_config = self.config.read().await.faq.clone();
+                    faq::check(&self.twitch_manager, &self.ai_client, &faq_config, &msg, &cleaned_message).await;
+                }
             }
         }
         Ok(())
     }
+
+    async fn handle_keyword_triggers(&self, ctx: &CommandContext, message: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let user_role = get_user_role(&ctx.msg.sender.id, &ctx.twitch_manager, Some(&ctx.msg.badges)).await?;
+        let fired = self.keyword_triggers.check(message, &ctx.msg.sender.name, &user_role).await;
+
+        for trigger in fired {
+            match trigger.action {
+                TriggerAction::Respond | TriggerAction::Counter => {
+                    if let Some(response) = trigger.response {
+                        let response = expand_kv_placeholders(&*ctx.storage.read().await, &response);
+                        ctx.bot_client.send_message(&ctx.channel, &response).await?;
+                    }
+                }
+                TriggerAction::WorldInfo => {
+                    let vrchat_manager = match &ctx.vrchat_manager {
+                        Some(vrchat_manager) if vrchat_manager.is_online().await => vrchat_manager,
+                        _ => continue,
+                    };
+                    match vrchat_manager.get_current_world().await {
+                        Ok(world) => {
+                            let prefix = trigger.response.map(|r| format!("{} ", r)).unwrap_or_default();
+                            let response = format!(
+                                "{}Current world: {} by {} | https://vrchat.com/home/world/{}",
+                                prefix, world.name, world.author_name, world.id
+                            );
+                            ctx.bot_client.send_message(&ctx.channel, &response).await?;
+                        }
+                        Err(e) => debug!("Keyword trigger could not fetch current world: {:?}", e),
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -4,8 +4,12 @@ pub mod broadcaster_client;
 pub mod message_handler;
 pub mod command_system;
 pub mod commands;
+pub mod triggers;
+pub mod dedup;
+pub mod pacing;
 
 pub use client::TwitchIRCManager;
 pub use bot_client::TwitchBotClient;
 pub use broadcaster_client::TwitchBroadcasterClient;
 pub use message_handler::MessageHandler;
+pub use triggers::KeywordTriggerManager;
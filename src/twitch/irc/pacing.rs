@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use tracing::{debug, info};
+use crate::config::ChatRateConfig;
+
+// Twitch counts an account's messages over a rolling 30 seconds, across every channel
+const WINDOW: Duration = Duration::from_secs(30);
+const NORMAL_LIMIT: usize = 20;
+// When the account is the broadcaster or a moderator of the channel it's sending to
+const MODERATOR_LIMIT: usize = 100;
+const VERIFIED_LIMIT: usize = 7500;
+// Without mod, Twitch also drops a second message to the same channel within a second
+const NORMAL_CHANNEL_GAP: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct SenderState {
+    sent: VecDeque<Instant>,
+    last_per_channel: HashMap<String, Instant>,
+    // Channels where the account has mod or is the broadcaster, from USERSTATE
+    privileged: HashSet<String>,
+}
+
+// What each account has sent recently, so its chat stays under Twitch's limits
+#[derive(Default)]
+pub struct ChatPacing {
+    senders: Mutex<HashMap<String, SenderState>>,
+}
+
+fn channel_key(channel: &str) -> String {
+    channel.trim_start_matches('#').to_lowercase()
+}
+
+impl ChatPacing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // From the USERSTATE Twitch sends on joining and after each message, so losing mod is picked up too
+    pub fn update_channel_status(&self, sender: &str, channel: &str, privileged: bool) {
+        let channel = channel_key(channel);
+        let mut senders = self.senders.lock().unwrap();
+        let state = senders.entry(sender.to_lowercase()).or_default();
+        let changed = if privileged {
+            state.privileged.insert(channel.clone())
+        } else {
+            state.privileged.remove(&channel)
+        };
+        if changed {
+            info!("{} is {} in {}, pacing chat to match", sender, if privileged { "a moderator" } else { "not a moderator" }, channel);
+        }
+    }
+
+    // Waits until sending won't go over Twitch's limits, then counts the message as sent.
+    // A burst (a chunked reply, several timers at once) is spread out instead of silently dropped.
+    pub async fn wait_turn(&self, config: &ChatRateConfig, sender: &str, channel: &str) {
+        let sender = sender.to_lowercase();
+        let channel = channel_key(channel);
+        loop {
+            let wait = {
+                let mut senders = self.senders.lock().unwrap();
+                let state = senders.entry(sender.clone()).or_default();
+                let now = Instant::now();
+                while state.sent.front().map_or(false, |sent| now.duration_since(*sent) >= WINDOW) {
+                    state.sent.pop_front();
+                }
+
+                // The broadcaster account never sees its own USERSTATE here, but always has the higher limit at home
+                let privileged = sender == channel || state.privileged.contains(&channel);
+                let mut wait = Duration::ZERO;
+                if state.sent.len() >= limit(config, privileged) {
+                    if let Some(oldest) = state.sent.front() {
+                        wait = WINDOW - now.duration_since(*oldest);
+                    }
+                }
+                if !privileged && !config.verified_bot {
+                    if let Some(last) = state.last_per_channel.get(&channel) {
+                        wait = wait.max(NORMAL_CHANNEL_GAP.saturating_sub(now.duration_since(*last)));
+                    }
+                }
+
+                if wait.is_zero() {
+                    state.sent.push_back(now);
+                    state.last_per_channel.insert(channel.clone(), now);
+                    return;
+                }
+                wait
+            };
+            debug!("Holding a message to {} for {:?} to stay under the chat rate limit", channel, wait);
+            sleep(wait).await;
+        }
+    }
+}
+
+// Messages this sender can send to the channel per window, at the configured headroom
+fn limit(config: &ChatRateConfig, privileged: bool) -> usize {
+    let limit = if config.verified_bot {
+        VERIFIED_LIMIT
+    } else if privileged {
+        MODERATOR_LIMIT
+    } else {
+        NORMAL_LIMIT
+    };
+    (limit * (100 - config.headroom_percent.min(90) as usize) / 100).max(1)
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, error, warn};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{Duration, Instant};
+use crate::twitch::roles::UserRole;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerAction {
+    Respond,
+    WorldInfo,
+    Counter,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct KeywordTrigger {
+    pub name: String,
+    pub pattern: String,
+    #[serde(default)]
+    pub is_regex: bool,
+    pub action: TriggerAction,
+    // Supports {user} and {count} placeholders
+    #[serde(default)]
+    pub response: Option<String>,
+    #[serde(default)]
+    pub cooldown_secs: u64,
+    #[serde(default)]
+    pub required_role: UserRole,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub count: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TriggerConfigurations {
+    pub triggers: Vec<KeywordTrigger>,
+}
+
+impl TriggerConfigurations {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let path = path.as_ref();
+        if path.exists() {
+            let content = fs::read_to_string(path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FiredTrigger {
+    pub action: TriggerAction,
+    pub response: Option<String>,
+}
+
+pub struct KeywordTriggerManager {
+    path: PathBuf,
+    configs: RwLock<TriggerConfigurations>,
+    compiled: RwLock<HashMap<String, Regex>>,
+    last_fired: Mutex<HashMap<String, Instant>>,
+}
+
+impl KeywordTriggerManager {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let configs = TriggerConfigurations::load(&path).unwrap_or_else(|e| {
+            error!("Failed to load keyword triggers from {:?}: {}", path, e);
+            TriggerConfigurations::default()
+        });
+        let compiled = Self::compile(&configs.triggers);
+
+        Self {
+            path,
+            configs: RwLock::new(configs),
+            compiled: RwLock::new(compiled),
+            last_fired: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn compile(triggers: &[KeywordTrigger]) -> HashMap<String, Regex> {
+        let mut compiled = HashMap::new();
+        for trigger in triggers {
+            let pattern = if trigger.is_regex {
+                format!("(?i){}", trigger.pattern)
+            } else {
+                format!(r"(?i)\b{}\b", regex::escape(&trigger.pattern))
+            };
+            match Regex::new(&pattern) {
+                Ok(re) => {
+                    compiled.insert(trigger.name.clone(), re);
+                }
+                Err(e) => warn!("Invalid pattern for keyword trigger '{}': {}", trigger.name, e),
+            }
+        }
+        compiled
+    }
+
+    pub async fn get_all(&self) -> Vec<KeywordTrigger> {
+        self.configs.read().await.triggers.clone()
+    }
+
+    pub async fn upsert(&self, trigger: KeywordTrigger) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if trigger.is_regex {
+            Regex::new(&trigger.pattern)?;
+        }
+
+        let mut configs = self.configs.write().await;
+        match configs.triggers.iter_mut().find(|t| t.name == trigger.name) {
+            Some(existing) => *existing = trigger,
+            None => configs.triggers.push(trigger),
+        }
+        *self.compiled.write().await = Self::compile(&configs.triggers);
+        configs.save(&self.path)
+    }
+
+    pub async fn remove(&self, name: &str) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let mut configs = self.configs.write().await;
+        let before = configs.triggers.len();
+        configs.triggers.retain(|t| t.name != name);
+        if configs.triggers.len() == before {
+            return Ok(false);
+        }
+        *self.compiled.write().await = Self::compile(&configs.triggers);
+        configs.save(&self.path)?;
+        Ok(true)
+    }
+
+    pub async fn check(&self, message: &str, user_name: &str, user_role: &UserRole) -> Vec<FiredTrigger> {
+        let compiled = self.compiled.read().await;
+        let mut configs = self.configs.write().await;
+        let mut last_fired = self.last_fired.lock().await;
+        let mut fired = Vec::new();
+        let mut counters_changed = false;
+
+        for trigger in configs.triggers.iter_mut().filter(|t| t.enabled) {
+            let matches = compiled.get(&trigger.name).map_or(false, |re| re.is_match(message));
+            if !matches || *user_role < trigger.required_role {
+                continue;
+            }
+
+            // Counters always count; the cooldown only limits how often they announce
+            if trigger.action == TriggerAction::Counter {
+                trigger.count += 1;
+                counters_changed = true;
+            }
+
+            if let Some(last) = last_fired.get(&trigger.name) {
+                if last.elapsed() < Duration::from_secs(trigger.cooldown_secs) {
+                    debug!("Keyword trigger '{}' is on cooldown", trigger.name);
+                    continue;
+                }
+            }
+            last_fired.insert(trigger.name.clone(), Instant::now());
+
+            let response = trigger.response.as_ref().map(|r| {
+                r.replace("{user}", user_name)
+                    .replace("{count}", &trigger.count.to_string())
+            });
+            fired.push(FiredTrigger {
+                action: trigger.action.clone(),
+                response,
+            });
+        }
+
+        if counters_changed {
+            if let Err(e) = configs.save(&self.path) {
+                error!("Failed to save keyword trigger counters: {}", e);
+            }
+        }
+
+        fired
+    }
+}
@@ -0,0 +1,59 @@
+use tracing::error;
+use rand::seq::SliceRandom;
+use crate::storage::StorageClient;
+use crate::twitch::stream_breaks::format_duration;
+
+// {user} is the lurker, {duration} how long they were gone
+const LURK_RESPONSES: &[&str] = &[
+    "{user} is now lurking. We'll keep the stream warm for you!",
+    "{user} has disappeared into the shadows... but we know you're there ♡",
+    "{user} is going lurky mode. Enjoy, and don't forget to hydrate!",
+    "A wild {user} used LURK! It's super effective.",
+    "{user} is lurking. The cats will keep an eye on you.",
+    "{user} is off to lurk. Thanks for keeping the stream on in the background!",
+];
+
+const UNLURK_RESPONSES: &[&str] = &[
+    "Welcome back {user}! You lurked for {duration}.",
+    "{user} has emerged from a {duration} lurk!",
+    "{user} is back after {duration} in the shadows. Did we miss anything? Oh wait, you did.",
+    "The lurker returns! {user} was gone for {duration}.",
+    "{user} unlurked after {duration}. Hiii ♡",
+];
+
+fn pick(templates: &[&str], user: &str, duration: &str) -> String {
+    templates.choose(&mut rand::thread_rng())
+        .unwrap_or(&"{user}")
+        .replace("{user}", user)
+        .replace("{duration}", duration)
+}
+
+pub fn lurk_message(user: &str) -> String {
+    pick(LURK_RESPONSES, user, "")
+}
+
+pub fn unlurk_message(user: &str, seconds: i64) -> String {
+    pick(UNLURK_RESPONSES, user, &format_duration(seconds))
+}
+
+// Closes any lurks still open and describes this stream's lurkers, if there were any
+pub fn end_of_stream_recap(storage: &StorageClient) -> Option<String> {
+    if let Err(e) = storage.end_all_lurks() {
+        error!("Failed to close open lurks: {:?}", e);
+    }
+    let since = storage.get_stream_session_start().ok().flatten()?;
+    match storage.count_lurks_since(since) {
+        Ok((0, _)) => None,
+        Ok((lurkers, lurks)) => Some(format!(
+            "Stream recap: {} {} kept us company with {} {} ♡",
+            lurkers,
+            if lurkers == 1 { "lurker" } else { "lurkers" },
+            lurks,
+            if lurks == 1 { "lurk" } else { "lurks" },
+        )),
+        Err(e) => {
+            error!("Failed to count lurks for the recap: {:?}", e);
+            None
+        }
+    }
+}
@@ -4,29 +4,75 @@ use std::error::Error;
 use std::error::Error as StdError;
 use std::fmt;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
 use std::time::{Duration};
 use chrono::{DateTime, Utc};
-use log::{debug, error, info};
+use tracing::{debug, error, info};
 use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::time::{sleep, timeout};
 use crate::ai::AIClient;
+use crate::chunker;
 use crate::config::Config;
 use crate::discord::{DiscordClient, UserLinks};
-use crate::osc::osc_config::OSCConfigurations;
+use crate::hooks::{EventHook, EventHooks};
+use crate::obs::OBSManager;
+use crate::osc::osc_config::{osc_config_path, OSCConfigurations};
 use crate::osc::{OSCManager};
 use crate::storage::{ChatterData, StorageClient};
 use crate::twitch::{TwitchAPIClient, TwitchIRCManager};
 use crate::twitch::eventsub::TwitchEventSubClient;
 use crate::twitch::irc::{MessageHandler, TwitchBotClient, TwitchBroadcasterClient};
 use crate::twitch::redeems::RedeemManager;
+use crate::twitch::raid_mode::RaidMode;
+use crate::twitch::follow_guard::FollowGuard;
+use crate::twitch::channel_updates::ChannelUpdates;
+use crate::twitch::recent_chat::RecentChat;
 use crate::twitch::roles::UserRole;
+use crate::twitch::{profiles, stream_segments};
 use crate::web_ui::websocket::{DashboardState, WebSocketMessage};
 use crate::twitch::irc::commands::ad_commands::AdManager;
 use crate::twitch::irc::commands::shoutout::ShoutoutCooldown;
 
 use std::fmt::Debug;
-use crate::discord;
 use crate::stream_state::{StateTransitionError, StreamState, StreamStateMachine};
+use crate::tasks::TaskRegistry;
+use crate::twitch::chatter_drops::ChatterDrops;
+use crate::twitch::quick_poll::QuickPolls;
+use crate::twitch::bits_vote::BitsVotes;
+use crate::twitch::trivia::Trivia;
+use crate::twitch::happy_hour::HappyHours;
+use crate::twitch::redeems::pricing::RewardPrices;
+use crate::twitch::role_grants::RoleGrants;
+use crate::twitch::charity::Charity;
+use crate::twitch::raid_train::RaidTrain;
+use crate::twitch::costreams::CoStreamCache;
+use crate::twitch::premiere::Premieres;
+use crate::twitch::watch_queue::WatchRequests;
+use crate::twitch::world_react::WorldReactions;
+use crate::twitch::stream_segments::WorldChanges;
+use crate::twitch::command_metrics::CommandLatency;
+use crate::twitch::command_usage::CommandAbuse;
+use crate::twitch::command_config::CommandOverrides;
+use crate::twitch::profiles::StreamProfiles;
+use crate::twitch::voice::BotVoice;
+use crate::twitch::tts::TtsQueue;
+use crate::twitch::faq::Faq;
+use crate::twitch::soundboard::Soundboard;
+use crate::twitch::connection_monitor::ConnectionHealth;
+use crate::twitch::irc::commands::calc::CalcRateLimit;
+use crate::twitch::irc::commands::CategoryChoices;
+use crate::notifications::Notifier;
+use crate::output_filter::OutputFilter;
+use crate::ai::prompts::Prompts;
+use crate::web_ui::ticker::Ticker;
+use crate::web_ui::compat::CompatEvents;
+use crate::web_ui::voice_commands::PendingVoiceCommand;
+use crate::web_ui::remote::RemotePairing;
+use crate::web_ui::status_page::StatusCache;
+use crate::twitch::irc::command_system::CommandRegistry;
+use crate::twitch::irc::message_handler::builtin_commands;
+use crate::scheduler::Scheduler;
+use crate::paths;
 use crate::twitch::api::client::TwitchAPIError;
 
 #[derive(Clone, Debug)]
@@ -225,16 +271,6 @@ impl UserManager {
         }
     }
 
-    pub async fn start_role_refresh_task(&self) {
-        let user_manager = self.clone();
-        tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(Duration::from_secs(900)).await; // Run every 15 minutes
-                user_manager.refresh_roles().await;
-            }
-        });
-    }
-
     // Add this method to update streamer data
     pub async fn update_streamer_data(&self, user_id: &str, streamer_data: StreamerData) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut cache = self.user_cache.write().await;
@@ -280,6 +316,49 @@ pub struct TwitchManager {
     pub ad_manager: Arc<RwLock<AdManager>>,
     pub stream_state_machine: Arc<StreamStateMachine>,
     pub discord_client: Option<Arc<DiscordClient>>,
+    pub storage: Option<Arc<RwLock<StorageClient>>>,
+    pub raid_mode: Arc<RaidMode>,
+    pub follow_guard: Arc<FollowGuard>,
+    pub obs_manager: Option<Arc<OBSManager>>,
+    pub(crate) event_hooks: EventHooks,
+    pub channel_updates: Arc<ChannelUpdates>,
+    pub recent_chat: Arc<RecentChat>,
+    pub chatter_drops: Arc<ChatterDrops>,
+    pub quick_polls: Arc<QuickPolls>,
+    pub bits_votes: Arc<BitsVotes>,
+    pub trivia: Arc<Trivia>,
+    pub happy_hours: Arc<HappyHours>,
+    pub reward_prices: Arc<RewardPrices>,
+    pub role_grants: Arc<RoleGrants>,
+    pub charity: Arc<Charity>,
+    pub raid_train: Arc<RaidTrain>,
+    pub costreams: Arc<CoStreamCache>,
+    pub premieres: Arc<Premieres>,
+    pub watch_requests: Arc<WatchRequests>,
+    pub world_reactions: Arc<WorldReactions>,
+    pub world_changes: Arc<WorldChanges>,
+    pub command_latency: Arc<CommandLatency>,
+    pub command_abuse: Arc<CommandAbuse>,
+    pub command_overrides: Arc<CommandOverrides>,
+    pub profiles: Arc<StreamProfiles>,
+    pub bot_voice: Arc<BotVoice>,
+    pub tts_queue: Arc<TtsQueue>,
+    pub faq: Arc<Faq>,
+    pub soundboard: Arc<Soundboard>,
+    pub connections: Arc<ConnectionHealth>,
+    pub notifier: Arc<Notifier>,
+    pub calc_rate_limit: Arc<CalcRateLimit>,
+    pub category_choices: Arc<CategoryChoices>,
+    pub prompts: Arc<Prompts>,
+    pub ticker: Arc<Ticker>,
+    pub compat_events: Arc<CompatEvents>,
+    pub pending_voice_command: Arc<PendingVoiceCommand>,
+    pub remote_pairing: Arc<RemotePairing>,
+    pub status_cache: Arc<StatusCache>,
+    pub ad_break_generation: Arc<AtomicU64>,
+    pub commands: Arc<CommandRegistry>,
+    pub tasks: Arc<TaskRegistry>,
+    pub scheduler: Arc<Scheduler>,
 }
 
 
@@ -287,6 +366,7 @@ impl Default for TwitchManager {
     fn default() -> Self {
         let (shoutout_sender, shoutout_receiver) = mpsc::channel(100);
         let shoutout_receiver = Arc::new(Mutex::new(shoutout_receiver));
+        let tasks = Arc::new(TaskRegistry::new());
 
         Self {
             config: Arc::new(Config::default()),
@@ -308,6 +388,49 @@ impl Default for TwitchManager {
             ad_manager: Arc::new(RwLock::new(AdManager::default())),
             stream_state_machine: StreamStateMachine::new(),
             discord_client: None,
+            storage: None,
+            raid_mode: Arc::new(RaidMode::new()),
+            follow_guard: Arc::new(FollowGuard::new()),
+            obs_manager: None,
+            event_hooks: EventHooks::default(),
+            channel_updates: Arc::new(ChannelUpdates::new()),
+            recent_chat: Arc::new(RecentChat::new()),
+            chatter_drops: Arc::new(ChatterDrops::new()),
+            quick_polls: Arc::new(QuickPolls::new()),
+            bits_votes: Arc::new(BitsVotes::new()),
+            trivia: Arc::new(Trivia::new()),
+            happy_hours: Arc::new(HappyHours::new()),
+            reward_prices: Arc::new(RewardPrices::new()),
+            role_grants: Arc::new(RoleGrants::new()),
+            charity: Arc::new(Charity::new()),
+            raid_train: Arc::new(RaidTrain::new()),
+            costreams: Arc::new(CoStreamCache::new()),
+            premieres: Arc::new(Premieres::new()),
+            watch_requests: Arc::new(WatchRequests::new()),
+            world_reactions: Arc::new(WorldReactions::new()),
+            world_changes: Arc::new(WorldChanges::new()),
+            command_latency: Arc::new(CommandLatency::new()),
+            command_abuse: Arc::new(CommandAbuse::new()),
+            command_overrides: Arc::new(CommandOverrides::new(&paths::resolve(paths::COMMAND_CONFIG))),
+            profiles: Arc::new(StreamProfiles::new()),
+            bot_voice: Arc::new(BotVoice::new()),
+            tts_queue: Arc::new(TtsQueue::new()),
+            faq: Arc::new(Faq::new(&paths::resolve(paths::FAQ))),
+            soundboard: Arc::new(Soundboard::new(&paths::resolve(paths::SOUNDBOARD))),
+            connections: Arc::new(ConnectionHealth::default()),
+            notifier: Arc::new(Notifier::default()),
+            calc_rate_limit: Arc::new(CalcRateLimit::new()),
+            category_choices: Arc::new(CategoryChoices::new()),
+            prompts: Arc::new(Prompts::new(&paths::resolve(paths::AI_PROMPTS))),
+            ticker: Arc::new(Ticker::new()),
+            compat_events: Arc::new(CompatEvents::new()),
+            pending_voice_command: Arc::new(PendingVoiceCommand::new()),
+            remote_pairing: Arc::new(RemotePairing::new()),
+            status_cache: Arc::new(StatusCache::new()),
+            ad_break_generation: Arc::new(AtomicU64::new(0)),
+            commands: Arc::new(builtin_commands()),
+            scheduler: Arc::new(Scheduler::new(&paths::resolve(paths::SCHEDULED_JOBS), tasks.clone())),
+            tasks,
         }
     }
 }
@@ -331,6 +454,47 @@ impl fmt::Debug for TwitchManager {
             .field("shoutout_cooldowns", &"<ShoutoutCooldown>")
             .field("shoutout_sender", &"<mpsc::Sender>")
             .field("ad_manager", &"<AdManager>")
+            .field("storage", &self.storage.as_ref().map(|_| "<StorageClient>"))
+            .field("raid_mode", &self.raid_mode)
+            .field("follow_guard", &self.follow_guard)
+            .field("obs_manager", &self.obs_manager.as_ref().map(|_| "<OBSManager>"))
+            .field("event_hooks", &"<EventHooks>")
+            .field("channel_updates", &self.channel_updates)
+            .field("recent_chat", &"<RecentChat>")
+            .field("chatter_drops", &"<ChatterDrops>")
+            .field("quick_polls", &"<QuickPolls>")
+            .field("bits_votes", &"<BitsVotes>")
+            .field("trivia", &"<Trivia>")
+            .field("happy_hours", &"<HappyHours>")
+            .field("reward_prices", &"<RewardPrices>")
+            .field("role_grants", &"<RoleGrants>")
+            .field("charity", &"<Charity>")
+            .field("raid_train", &"<RaidTrain>")
+            .field("costreams", &"<CoStreamCache>")
+            .field("premieres", &"<Premieres>")
+            .field("watch_requests", &"<WatchRequests>")
+            .field("world_reactions", &"<WorldReactions>")
+            .field("world_changes", &"<WorldChanges>")
+            .field("command_latency", &"<CommandLatency>")
+            .field("command_abuse", &"<CommandAbuse>")
+            .field("command_overrides", &"<CommandOverrides>")
+            .field("profiles", &"<StreamProfiles>")
+            .field("bot_voice", &"<BotVoice>")
+            .field("tts_queue", &"<TtsQueue>")
+            .field("faq", &"<Faq>")
+            .field("soundboard", &"<Soundboard>")
+            .field("connections", &"<ConnectionHealth>")
+            .field("notifier", &"<Notifier>")
+            .field("calc_rate_limit", &"<CalcRateLimit>")
+            .field("category_choices", &"<CategoryChoices>")
+            .field("prompts", &"<Prompts>")
+            .field("ticker", &"<Ticker>")
+            .field("compat_events", &"<CompatEvents>")
+            .field("pending_voice_command", &"<PendingVoiceCommand>")
+            .field("remote_pairing", &"<RemotePairing>")
+            .field("status_cache", &"<StatusCache>")
+            .field("ad_break_generation", &"<AtomicU64>")
+            .field("commands", &"<CommandRegistry>")
             .finish()
     }
 }
@@ -338,28 +502,40 @@ impl fmt::Debug for TwitchManager {
 impl TwitchManager {
     pub async fn new(
         config: Arc<Config>,
-        _storage: Arc<RwLock<StorageClient>>,
+        storage: Arc<RwLock<StorageClient>>,
         ai_client: Option<Arc<AIClient>>,
         osc_manager: Arc<OSCManager>,
         user_links: Arc<UserLinks>,
         dashboard_state: Arc<RwLock<DashboardState>>,
         websocket_tx: mpsc::UnboundedSender<WebSocketMessage>,
         stream_state_machine: Arc<StreamStateMachine>,
+        obs_manager: Option<Arc<OBSManager>>,
+        discord_client: Option<Arc<DiscordClient>>,
+        tasks: Arc<TaskRegistry>,
+        scheduler: Arc<Scheduler>,
+        connections: Arc<ConnectionHealth>,
+        output_filter: Arc<OutputFilter>,
+        notifier: Arc<Notifier>,
     ) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let api_client = Arc::new(TwitchAPIClient::new(config.clone()).await?);
         api_client.authenticate().await?;
 
+        let commands = builtin_commands();
+        commands.load_custom(storage.read().await.get_custom_commands()?);
+
         let social_links = config.social_links.clone();
         let irc_manager = Arc::new(TwitchIRCManager::new(
             websocket_tx.clone(),
             Arc::new(RwLock::new(social_links)),
             dashboard_state.clone(),
             config.clone(),
+            tasks.clone(),
+            output_filter,
         ));
 
         let (bot_client, broadcaster_client) = Self::initialize_irc_clients(&config, &irc_manager).await?;
 
-        let osc_configs = Arc::new(RwLock::new(OSCConfigurations::load("osc_config.json").unwrap_or_default()));
+        let osc_configs = Arc::new(RwLock::new(OSCConfigurations::load(&osc_config_path()).unwrap_or_default()));
 
         let user_manager = UserManager::new(api_client.clone());
 
@@ -367,15 +543,6 @@ impl TwitchManager {
         let (shoutout_sender, shoutout_receiver) = mpsc::channel(100);
         let shoutout_receiver = Arc::new(Mutex::new(shoutout_receiver));
 
-        let discord_client = if config.is_discord_configured() {
-            Some(Arc::new(discord::DiscordClient::new(
-                Arc::new(RwLock::new(config.as_ref().clone())),  // Fixed config wrapping
-                user_links.clone()
-            ).await?))
-        } else {
-            None
-        };
-
         let twitch_manager = Arc::new(Self {
             config: config.clone(),
             api_client: api_client.clone(),
@@ -396,6 +563,49 @@ impl TwitchManager {
             ad_manager: Arc::new(RwLock::new(AdManager::new())),
             stream_state_machine,
             discord_client,
+            storage: Some(storage),
+            raid_mode: Arc::new(RaidMode::new()),
+            follow_guard: Arc::new(FollowGuard::new()),
+            obs_manager,
+            event_hooks: EventHooks::default(),
+            channel_updates: Arc::new(ChannelUpdates::new()),
+            recent_chat: Arc::new(RecentChat::new()),
+            chatter_drops: Arc::new(ChatterDrops::new()),
+            quick_polls: Arc::new(QuickPolls::new()),
+            bits_votes: Arc::new(BitsVotes::new()),
+            trivia: Arc::new(Trivia::new()),
+            happy_hours: Arc::new(HappyHours::new()),
+            reward_prices: Arc::new(RewardPrices::new()),
+            role_grants: Arc::new(RoleGrants::new()),
+            charity: Arc::new(Charity::new()),
+            raid_train: Arc::new(RaidTrain::new()),
+            costreams: Arc::new(CoStreamCache::new()),
+            premieres: Arc::new(Premieres::new()),
+            watch_requests: Arc::new(WatchRequests::new()),
+            world_reactions: Arc::new(WorldReactions::new()),
+            world_changes: Arc::new(WorldChanges::new()),
+            command_latency: Arc::new(CommandLatency::new()),
+            command_abuse: Arc::new(CommandAbuse::new()),
+            command_overrides: Arc::new(CommandOverrides::load(&paths::resolve(paths::COMMAND_CONFIG))),
+            profiles: Arc::new(StreamProfiles::new()),
+            bot_voice: Arc::new(BotVoice::new()),
+            tts_queue: Arc::new(TtsQueue::new()),
+            faq: Arc::new(Faq::load(&paths::resolve(paths::FAQ))),
+            soundboard: Arc::new(Soundboard::load(&paths::resolve(paths::SOUNDBOARD))),
+            connections,
+            notifier,
+            calc_rate_limit: Arc::new(CalcRateLimit::new()),
+            category_choices: Arc::new(CategoryChoices::new()),
+            prompts: Arc::new(Prompts::load(&paths::resolve(paths::AI_PROMPTS))),
+            ticker: Arc::new(Ticker::new()),
+            compat_events: Arc::new(CompatEvents::new()),
+            pending_voice_command: Arc::new(PendingVoiceCommand::new()),
+            remote_pairing: Arc::new(RemotePairing::new()),
+            status_cache: Arc::new(StatusCache::new()),
+            ad_break_generation: Arc::new(AtomicU64::new(0)),
+            commands: Arc::new(commands),
+            tasks,
+            scheduler,
         });
 
         twitch_manager.start_shoutout_processing();
@@ -416,6 +626,21 @@ impl TwitchManager {
         Ok((*twitch_manager).clone())
     }
 
+    // Nothing logs in or connects, and the IRC manager has no clients. For the load test harness.
+    #[cfg(feature = "bench")]
+    pub(crate) fn offline(config: Arc<Config>, storage: Arc<RwLock<StorageClient>>, irc_manager: Arc<TwitchIRCManager>, tasks: Arc<TaskRegistry>) -> Self {
+        let bot_username = config.twitch_bot_username.clone().unwrap_or_else(|| "mewbot".to_string());
+        Self {
+            bot_client: Arc::new(TwitchBotClient::new(bot_username, irc_manager.clone())),
+            config,
+            irc_manager,
+            storage: Some(storage),
+            scheduler: Arc::new(Scheduler::new(&paths::resolve(paths::SCHEDULED_JOBS), tasks.clone())),
+            tasks,
+            ..Self::default()
+        }
+    }
+
     pub async fn initialize(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
         Self::initialize_redeem_manager(self).await?;
 
@@ -461,9 +686,8 @@ impl TwitchManager {
     }
 
     async fn initialize_eventsub_client(&self) -> Result<TwitchEventSubClient, Box<dyn std::error::Error + Send + Sync>> {
-        let osc_configs = Arc::new(RwLock::new(OSCConfigurations::load("osc_config.json").unwrap_or_default()));
-
-        Ok(TwitchEventSubClient::new(Arc::new(self.clone()), osc_configs))
+        // Shares the manager's configs so edits from the dashboard apply to events right away
+        Ok(TwitchEventSubClient::new(Arc::new(self.clone()), self.osc_configs.clone()))
     }
 
     pub async fn shutdown(&self) -> Result<(), Box<dyn StdError + Send + Sync>> {
@@ -518,11 +742,9 @@ impl TwitchManager {
     pub async fn start_message_handler(&self, message_handler: Arc<MessageHandler>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut receiver = self.irc_manager.subscribe();
 
-        tokio::spawn(async move {
-            while let Ok(message) = receiver.recv().await {
-                if let Err(e) = message_handler.handle_message(message).await {
-                    error!("Error handling message: {:?}", e);
-                }
+        self.tasks.spawn("twitch_message_handler", async move {
+            while let Some(message) = receiver.recv().await {
+                message_handler.handle_timed(message).await;
             }
         });
 
@@ -533,7 +755,7 @@ impl TwitchManager {
         let mut receiver = self.stream_state_machine.subscribe();
         let twitch_manager = self.clone();
 
-        tokio::spawn(async move {
+        self.tasks.spawn("stream_state_listener", async move {
             while let Ok(new_state) = receiver.recv().await {
                 match new_state {
                     StreamState::Offline => {
@@ -570,6 +792,7 @@ impl TwitchManager {
     }
 
     pub async fn handle_stream_online(&self, game: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        profiles::auto_switch(self, &game).await;
         if let Some(redeem_manager) = self.redeem_manager.write().await.as_mut() {
             redeem_manager.handle_stream_online(game.clone()).await?;
         }
@@ -587,11 +810,13 @@ impl TwitchManager {
 
     pub async fn handle_stream_update(&self, game_name: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Handling stream update. New game: {}", game_name);
+        profiles::auto_switch(self, &game_name).await;
 
         let current_state = self.stream_state_machine.get_current_state().await;
         match current_state {
             StreamState::Live(_) => {
                 self.stream_state_machine.update_game(game_name.clone()).await?;
+                stream_segments::game_changed(self, &game_name).await;
             },
             StreamState::Offline | StreamState::GoingOffline => {
                 info!("Stream is not live. Storing game update for later: {}", game_name);
@@ -616,6 +841,18 @@ impl TwitchManager {
         self.osc_manager.clone()
     }
 
+    pub fn get_obs_manager(&self) -> Option<Arc<OBSManager>> {
+        self.obs_manager.clone()
+    }
+
+    pub fn add_event_hook(&self, hook: EventHook) {
+        self.event_hooks.add(hook);
+    }
+
+    pub(crate) fn get_event_hooks(&self) -> EventHooks {
+        self.event_hooks.clone()
+    }
+
     pub fn get_osc_configs(&self) -> Arc<RwLock<OSCConfigurations>> {
         self.osc_configs.clone()
     }
@@ -649,6 +886,18 @@ impl TwitchManager {
         self.shoutout_cooldowns.clone()
     }
 
+    pub fn get_storage(&self) -> Option<Arc<RwLock<StorageClient>>> {
+        self.storage.clone()
+    }
+
+    pub fn get_raid_mode(&self) -> Arc<RaidMode> {
+        self.raid_mode.clone()
+    }
+
+    pub fn get_follow_guard(&self) -> Arc<FollowGuard> {
+        self.follow_guard.clone()
+    }
+
     pub fn get_user_links(&self) -> Arc<UserLinks> {
         self.user_links.clone()
     }
@@ -657,6 +906,17 @@ impl TwitchManager {
         self.bot_client.send_message(channel, message).await
     }
 
+    // For AI answers and recaps that can run past Twitch's limit; every part after the first is numbered
+    pub async fn send_long_message_as_bot(&self, channel: &str, message: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        for (i, part) in chunker::chat_chunks(message).iter().enumerate() {
+            if i > 0 {
+                sleep(chunker::CHUNK_DELAY).await;
+            }
+            self.send_message_as_bot(channel, part).await?;
+        }
+        Ok(())
+    }
+
     pub async fn send_message_as_broadcaster(&self, channel: &str, message: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         if let Some(broadcaster_client) = &self.broadcaster_client {
             broadcaster_client.send_message(channel, message).await
@@ -746,7 +1006,7 @@ impl TwitchManager {
 
     pub fn start_shoutout_processing(&self) {
         let twitch_manager = self.clone();
-        tokio::spawn(async move {
+        self.tasks.spawn("shoutout_processing", async move {
             twitch_manager.process_shoutout_queue().await;
         });
     }
@@ -829,27 +1089,6 @@ impl TwitchManager {
         Ok(())
     }
 
-    pub async fn start_role_refresh_task(&self) {
-        let user_manager = self.user_manager.clone();
-
-        tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(Duration::from_secs(900)).await; // Run every 15 minutes
-
-                let users_to_refresh = {
-                    let cache = user_manager.user_cache.read().await;
-                    cache.keys().cloned().collect::<Vec<String>>()
-                };
-
-                for user_id in users_to_refresh {
-                    if let Err(e) = user_manager.get_user(&user_id).await {
-                        error!("Error refreshing role for user {}: {:?}", user_id, e);
-                    }
-                }
-            }
-        });
-    }
-
     pub async fn preload_broadcaster_data(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let broadcaster_id = self.api_client.get_broadcaster_id().await?;
         debug!("Preloading broadcaster data for ID: {}", broadcaster_id);
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+use tracing::{error, info};
+use serde_json::{json, Value};
+use serenity::model::id::ChannelId;
+use tokio::time::{sleep, Duration};
+use crate::config::MilestonesConfig;
+use crate::osc::{OSCMessageType, OSCValue};
+use crate::storage::StorageClient;
+use crate::twitch::TwitchManager;
+
+const FOLLOWERS_STAT: &str = "followers";
+const TOTAL_SUBS_STAT: &str = "total_subs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MilestoneKind {
+    Followers,
+    Subs,
+    HoursStreamed,
+}
+
+impl MilestoneKind {
+    pub const ALL: [MilestoneKind; 3] = [MilestoneKind::Followers, MilestoneKind::Subs, MilestoneKind::HoursStreamed];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            MilestoneKind::Followers => "followers",
+            MilestoneKind::Subs => "subs",
+            MilestoneKind::HoursStreamed => "hours_streamed",
+        }
+    }
+
+    fn describe(&self, threshold: u64) -> String {
+        match self {
+            MilestoneKind::Followers => format!("{} followers", threshold),
+            MilestoneKind::Subs => format!("{} total subs", threshold),
+            MilestoneKind::HoursStreamed => format!("{} hours streamed", threshold),
+        }
+    }
+
+    fn thresholds<'a>(&self, config: &'a MilestonesConfig) -> &'a [u64] {
+        match self {
+            MilestoneKind::Followers => &config.follower_thresholds,
+            MilestoneKind::Subs => &config.sub_thresholds,
+            MilestoneKind::HoursStreamed => &config.hours_streamed_thresholds,
+        }
+    }
+
+    fn current(&self, storage: &StorageClient) -> rusqlite::Result<u64> {
+        let value = match self {
+            MilestoneKind::Followers => storage.get_channel_stat(FOLLOWERS_STAT)?,
+            MilestoneKind::Subs => storage.get_channel_stat(TOTAL_SUBS_STAT)?,
+            MilestoneKind::HoursStreamed => storage.get_total_stream_seconds()? / 3600,
+        };
+        Ok(value.max(0) as u64)
+    }
+}
+
+// Called after every follow; follower totals come from the API since unfollows aren't evented
+pub fn record_follow(twitch_manager: &Arc<TwitchManager>, channel: &str) {
+    if !twitch_manager.config.milestones.enabled {
+        return;
+    }
+
+    let twitch_manager = twitch_manager.clone();
+    let channel = channel.to_string();
+    tokio::spawn(async move {
+        let api_client = twitch_manager.get_api_client();
+        let follower_count = match api_client.get_broadcaster_id().await {
+            Ok(broadcaster_id) => api_client.get_follower_count(&broadcaster_id).await,
+            Err(e) => Err(e),
+        };
+        match follower_count {
+            Ok(count) => {
+                if let Some(storage) = twitch_manager.get_storage() {
+                    if let Err(e) = storage.read().await.set_channel_stat(FOLLOWERS_STAT, count as i64) {
+                        error!("Failed to store follower count: {:?}", e);
+                    }
+                }
+                check(&twitch_manager, &channel, MilestoneKind::Followers).await;
+            }
+            Err(e) => error!("Failed to fetch follower count for milestones: {}", e),
+        }
+    });
+}
+
+pub async fn record_subs(twitch_manager: &Arc<TwitchManager>, channel: &str, count: u64) {
+    if !twitch_manager.config.milestones.enabled {
+        return;
+    }
+
+    if let Some(storage) = twitch_manager.get_storage() {
+        if let Err(e) = storage.read().await.increment_channel_stat(TOTAL_SUBS_STAT, count as i64) {
+            error!("Failed to update total subs: {:?}", e);
+            return;
+        }
+    }
+    check(twitch_manager, channel, MilestoneKind::Subs).await;
+}
+
+// Marks every newly crossed threshold as reached but only celebrates the highest,
+// so turning the feature on for an established channel doesn't fire a burst of them.
+pub async fn check(twitch_manager: &Arc<TwitchManager>, channel: &str, kind: MilestoneKind) {
+    let config = &twitch_manager.config.milestones;
+    if !config.enabled {
+        return;
+    }
+    let storage = match twitch_manager.get_storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+
+    let reached = {
+        let storage = storage.read().await;
+        let current = match kind.current(&storage) {
+            Ok(current) => current,
+            Err(e) => {
+                error!("Failed to read {} milestone progress: {:?}", kind.key(), e);
+                return;
+            }
+        };
+
+        let mut reached = None;
+        for &threshold in kind.thresholds(config).iter().filter(|&&t| t <= current) {
+            match storage.mark_milestone_reached(kind.key(), threshold) {
+                Ok(true) => reached = reached.max(Some(threshold)),
+                Ok(false) => {}
+                Err(e) => error!("Failed to record {} milestone: {:?}", kind.key(), e),
+            }
+        }
+        reached
+    };
+
+    if let Some(threshold) = reached {
+        celebrate(twitch_manager, channel, kind, threshold).await;
+    }
+}
+
+async fn celebrate(twitch_manager: &Arc<TwitchManager>, channel: &str, kind: MilestoneKind, threshold: u64) {
+    let config = &twitch_manager.config.milestones;
+    let description = kind.describe(threshold);
+    info!("Milestone reached: {}", description);
+
+    let message = format!("🎉 Milestone reached: {}! Thank you all so much! 🎉", description);
+    if let Err(e) = twitch_manager.send_message_as_bot(channel, &message).await {
+        error!("Failed to announce milestone in chat: {:?}", e);
+    }
+
+    let osc_manager = twitch_manager.get_osc_manager();
+    let parameter = config.confetti_parameter.clone();
+    tokio::spawn(async move {
+        if let Err(e) = osc_manager.send_osc_message(&parameter, &OSCMessageType::Boolean, &OSCValue::Boolean(true)).await {
+            error!("Failed to trigger milestone confetti: {}", e);
+            return;
+        }
+        sleep(Duration::from_secs(5)).await;
+        if let Err(e) = osc_manager.send_osc_message(&parameter, &OSCMessageType::Boolean, &OSCValue::Boolean(false)).await {
+            error!("Failed to reset milestone confetti: {}", e);
+        }
+    });
+
+    let discord_channel = config.discord_channel_id.as_ref()
+        .or(twitch_manager.config.discord_announcement_channel_id.as_ref())
+        .and_then(|id| id.parse::<u64>().ok());
+    if let (Some(discord_client), Some(channel_id)) = (&twitch_manager.discord_client, discord_channel) {
+        let http = discord_client.get_http().await;
+        if let Err(e) = ChannelId::new(channel_id).say(&http, format!("🎉 The channel just hit **{}**!", description)).await {
+            error!("Failed to post milestone to Discord: {}", e);
+        }
+    }
+}
+
+// Dashboard view: current value and the next threshold for each kind, plus reached history
+pub fn progress(storage: &StorageClient, config: &MilestonesConfig) -> rusqlite::Result<Value> {
+    let mut kinds = Vec::new();
+    for kind in MilestoneKind::ALL {
+        let current = kind.current(storage)?;
+        let next = kind.thresholds(config).iter().copied().filter(|&t| t > current).min();
+        let previous = kind.thresholds(config).iter().copied().filter(|&t| t <= current).max();
+        kinds.push(json!({
+            "kind": kind.key(),
+            "current": current,
+            "previous": previous,
+            "next": next,
+        }));
+    }
+
+    Ok(json!({
+        "enabled": config.enabled,
+        "progress": kinds,
+        "reached": storage.get_reached_milestones()?,
+    }))
+}
@@ -8,7 +8,44 @@ pub mod roles;
 pub mod connection_monitor;
 pub mod manager;
 pub mod models;
+pub mod clips;
+pub mod raid_mode;
+pub mod follow_guard;
+pub mod command_config;
+pub mod custom_commands;
+pub mod alerts;
+pub mod channel_updates;
+pub mod recent_chat;
+pub mod milestones;
+pub mod ignore_list;
+pub mod stream_breaks;
+pub mod stream_segments;
+pub mod chatter_drops;
+pub mod voice;
+pub mod quick_poll;
+pub mod trivia;
+pub mod faq;
+pub mod ai_tools;
+pub mod world_react;
+pub mod profiles;
+pub mod lurk;
+pub mod happy_hour;
+pub mod command_usage;
+pub mod command_metrics;
+pub mod costreams;
+pub mod raid_train;
+pub mod premiere;
+pub mod suggestions;
+pub mod watch_queue;
+pub mod bits_vote;
+pub mod charity;
+pub mod soundboard;
+pub mod predictions;
+pub mod tts;
+pub mod role_grants;
+pub mod opt_out;
+pub mod follower_growth;
 
 pub use api::TwitchAPIClient;
 pub use irc::client::TwitchIRCManager;
-pub use manager::TwitchManager;
\ No newline at end of file
+pub use manager::TwitchManager;
@@ -3,6 +3,7 @@ use std::error::Error;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::Path;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use async_trait::async_trait;
 use crate::osc::models::{OSCConfig};
@@ -72,6 +73,11 @@ pub struct RedeemInfo {
     pub use_osc: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub osc_config: Option<OSCConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obs_action: Option<OBSSourceAction>,
+    // When set, these run instead of the reward's handler, OSC config or OBS action
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<ChainStep>,
     pub enabled_games: Vec<String>,
     pub disabled_games: Vec<String>,
     pub enabled_offline: bool,
@@ -79,6 +85,102 @@ pub struct RedeemInfo {
     pub is_conflicting: bool,
     pub user_input_required: bool,
     pub auto_complete: bool,
+    #[serde(default, skip_serializing_if = "RedeemAnnouncements::is_empty")]
+    pub announcements: RedeemAnnouncements,
+    // What happens to its unfulfilled redemptions when the stream ends
+    #[serde(default)]
+    pub offline_queue: OfflineQueuePolicy,
+    // Raises the cost with each redemption and lets it fall back over time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dynamic_pricing: Option<DynamicPricing>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct DynamicPricing {
+    // Added to the cost after each redemption
+    pub step: u32,
+    // Taken off again every decay_interval_secs without a redemption
+    #[serde(default = "default_decay_step")]
+    pub decay_step: u32,
+    #[serde(default = "default_decay_interval_secs")]
+    pub decay_interval_secs: u64,
+    // Defaults to the reward's cost in redeems_config.json
+    #[serde(default)]
+    pub floor: Option<u32>,
+    pub ceiling: u32,
+}
+
+fn default_decay_step() -> u32 {
+    50
+}
+
+fn default_decay_interval_secs() -> u64 {
+    300
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OfflineQueuePolicy {
+    // Give the points back and tell the viewer in chat
+    #[default]
+    Refund,
+    // Keep them queued for next stream
+    Hold,
+}
+
+// Chat messages about how a redemption went. Unset ones use redemption_announcements from the
+// config, and an empty one turns that message off for the reward.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct RedeemAnnouncements {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
+    // Posted when the redemption lands in the queue for the streamer to fulfill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queued: Option<String>,
+    // Posted when it's refunded because the stream ended before it was fulfilled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refunded: Option<String>,
+}
+
+impl RedeemAnnouncements {
+    pub fn is_empty(&self) -> bool {
+        self.success.is_none() && self.failure.is_none() && self.queued.is_none() && self.refunded.is_none()
+    }
+}
+
+// Shows an OBS source (or enables one of its filters) for a while, then reverts it
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OBSSourceAction {
+    pub instance_name: String,
+    pub scene_name: String,
+    pub source_name: String,
+    #[serde(default)]
+    pub filter_name: Option<String>,
+    pub duration_secs: u64,
+}
+
+// One step of a reward's action chain. Steps run in order and the chain stops at the first failure.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChainStep {
+    // Wait this long before running the step
+    #[serde(default)]
+    pub delay_ms: u64,
+    #[serde(flatten)]
+    pub action: ChainAction,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChainAction {
+    // Sends the OSC config with this key from osc_config.json
+    Osc { config: String },
+    // {user} and {input} are filled in from the redemption
+    Chat { message: String },
+    Obs(OBSSourceAction),
+    // Plays a soundboard sound; its cooldowns apply, its drop point cost doesn't
+    Sound { name: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -192,6 +294,7 @@ pub struct RedeemSettings {
     pub limit_per_user: Option<u32>,
     pub use_osc: bool,
     pub osc_config: Option<OSCConfig>,
+    pub obs_action: Option<OBSSourceAction>,
     pub enabled_games: Vec<String>,
     pub disabled_games: Vec<String>,
     pub enabled_offline: bool,
@@ -204,6 +307,10 @@ pub struct RedeemSettings {
 pub trait RedeemHandler: Send + Sync + Any {
     async fn handle(&self, redemption: &Redemption) -> RedemptionResult;
     fn as_any(&self) -> &dyn Any;
+    // Handlers that send the viewer's input to an AI are skipped for viewers who ran !optout
+    fn uses_ai(&self) -> bool {
+        false
+    }
 }
 
 impl RedeemSettings {
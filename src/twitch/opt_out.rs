@@ -0,0 +1,17 @@
+use tracing::error;
+use crate::storage::StorageClient;
+
+// Viewers who ran !optout: their chat isn't stored, their lurks aren't timed, they're left off the
+// leaderboards and their words are never sent to an AI. The storage layer enforces the same rules,
+// so nothing slips through from a code path that forgets to check. Moderation still sees their
+// last hour of chat through the in-memory RecentChat buffer.
+pub fn is_opted_out(storage: &StorageClient, user_id: &str) -> bool {
+    match storage.is_opted_out(user_id) {
+        Ok(opted_out) => opted_out,
+        Err(e) => {
+            error!("Failed to check opt out for {}: {:?}", user_id, e);
+            // Failing closed keeps a broken database from collecting data it shouldn't
+            true
+        }
+    }
+}
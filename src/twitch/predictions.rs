@@ -0,0 +1,79 @@
+use serde_json::Value;
+use tracing::{info, warn};
+use crate::config::{PredictionMetric, PredictionResolver, PredictionsConfig};
+use crate::twitch::api::requests::predictions::{self, Prediction};
+use crate::twitch::TwitchManager;
+
+// The first resolver whose title matches, so more specific ones go first in the config
+fn find_resolver<'a>(config: &'a PredictionsConfig, prediction: &Prediction) -> Option<&'a PredictionResolver> {
+    let title = prediction.title.to_lowercase();
+    config.resolvers.iter()
+        .find(|resolver| !resolver.title_contains.is_empty() && title.contains(&resolver.title_contains.to_lowercase()))
+}
+
+async fn metric_value(twitch_manager: &TwitchManager, metric: PredictionMetric, broadcaster_id: &str, stream: &Value) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(match metric {
+        PredictionMetric::Viewers => stream["viewer_count"].as_u64().unwrap_or(0),
+        PredictionMetric::Followers => twitch_manager.get_api_client().get_follower_count(broadcaster_id).await? as u64,
+        PredictionMetric::CoinPot => match twitch_manager.get_redeem_manager().read().await.as_ref() {
+            Some(redeem_manager) => redeem_manager.coin_game_price().await as u64,
+            None => 0,
+        },
+    })
+}
+
+fn describe_metric(metric: PredictionMetric) -> &'static str {
+    match metric {
+        PredictionMetric::Viewers => "viewers",
+        PredictionMetric::Followers => "followers",
+        PredictionMetric::CoinPot => "the coin game pot",
+    }
+}
+
+// Settles the open prediction once its resolver's condition is met, or when the stream ends if it says so
+pub async fn check(twitch_manager: &TwitchManager, config: &PredictionsConfig) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if config.resolvers.is_empty() {
+        return Ok(());
+    }
+    let api_client = twitch_manager.get_api_client();
+    let broadcaster_id = api_client.get_broadcaster_id().await?;
+    let prediction = match predictions::get_open_prediction(&api_client, &broadcaster_id).await? {
+        Some(prediction) => prediction,
+        None => return Ok(()),
+    };
+    let resolver = match find_resolver(config, &prediction) {
+        Some(resolver) => resolver,
+        None => return Ok(()),
+    };
+
+    let stream = api_client.get_stream_info(&broadcaster_id).await?;
+    let (outcome, reason) = match stream["data"].get(0) {
+        Some(stream) => {
+            let value = metric_value(twitch_manager, resolver.metric, &broadcaster_id, stream).await?;
+            if value < resolver.threshold {
+                return Ok(());
+            }
+            (resolver.outcome_if_met, format!("{} reached {}", describe_metric(resolver.metric), value))
+        }
+        None => match resolver.outcome_if_offline {
+            Some(outcome) => (outcome, "the stream ended first".to_string()),
+            None => return Ok(()),
+        },
+    };
+
+    let winner = match outcome.checked_sub(1).and_then(|index| prediction.outcomes.get(index)) {
+        Some(winner) => winner,
+        None => {
+            warn!("Prediction \"{}\" has no outcome #{}, leaving it for a mod", prediction.title, outcome);
+            return Ok(());
+        }
+    };
+    predictions::resolve_prediction(&api_client, &broadcaster_id, &prediction.id, &winner.id).await?;
+    info!("Resolved prediction \"{}\" as \"{}\": {}", prediction.title, winner.title, reason);
+
+    if let Some(channel) = &twitch_manager.config.twitch_channel_to_join {
+        let message = format!("🔮 Prediction settled: {} - \"{}\" wins, {}!", prediction.title, winner.title, reason);
+        twitch_manager.send_message_as_bot(channel, &message).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,243 @@
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::json;
+use serenity::builder::EditMessage;
+use serenity::model::channel::Message;
+use serenity::model::id::ChannelId;
+use tokio::sync::{Notify, RwLock};
+use tokio::time::sleep;
+use tracing::{error, info};
+use crate::config::{Config, PremiereConfig};
+use crate::twitch::api::requests::channel;
+use crate::twitch::channel_updates::ChannelField;
+use crate::twitch::TwitchManager;
+use crate::web_ui::websocket::WebSocketMessage;
+
+// Seconds before zero at which the countdown is repeated everywhere
+const MARKS: &[u64] = &[600, 300, 120, 60, 30, 10, 5, 4, 3, 2, 1];
+
+// A countdown started from the dashboard
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Premiere {
+    pub id: u64,
+    pub starts_at: DateTime<Utc>,
+    pub content_scene: String,
+    pub title: String,
+}
+
+struct PremiereState {
+    current: Option<Premiere>,
+    next_id: u64,
+}
+
+// The countdown in progress, owned by TwitchManager
+pub struct Premieres {
+    state: Mutex<PremiereState>,
+    // Wakes the countdown task when it's cancelled
+    cancelled: Notify,
+}
+
+impl Premieres {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(PremiereState {
+                current: None,
+                next_id: 1,
+            }),
+            cancelled: Notify::new(),
+        }
+    }
+
+    pub fn current(&self) -> Option<Premiere> {
+        self.state.lock().unwrap().current.clone()
+    }
+
+    pub fn cancel(&self) -> Option<Premiere> {
+        let cancelled = self.state.lock().unwrap().current.take();
+        if cancelled.is_some() {
+            self.cancelled.notify_one();
+        }
+        cancelled
+    }
+
+    fn still_current(&self, id: u64) -> bool {
+        self.state.lock().unwrap().current.as_ref().map(|p| p.id) == Some(id)
+    }
+}
+
+// Starts the countdown in chat, Discord and the chatbox; at zero OBS cuts to the content scene
+// and the title changes
+pub async fn start(config: &RwLock<Config>, twitch_manager: Arc<TwitchManager>) -> Result<Premiere, String> {
+    let config = config.read().await.premiere.clone();
+    if config.countdown_secs == 0 {
+        return Err("The premiere countdown is set to 0 seconds".to_string());
+    }
+
+    let premiere = {
+        let mut state = twitch_manager.premieres.state.lock().unwrap();
+        if state.current.is_some() {
+            return Err("A premiere is already counting down".to_string());
+        }
+        let premiere = Premiere {
+            id: state.next_id,
+            starts_at: Utc::now() + chrono::Duration::seconds(config.countdown_secs as i64),
+            content_scene: config.content_scene.clone(),
+            title: config.title.clone(),
+        };
+        state.next_id += 1;
+        state.current = Some(premiere.clone());
+        premiere
+    };
+    info!("Premiere starting in {}s", config.countdown_secs);
+
+    let countdown = premiere.clone();
+    let tasks = twitch_manager.tasks.clone();
+    tasks.spawn("premiere_countdown", async move {
+        run_countdown(twitch_manager, config, countdown).await;
+    });
+    Ok(premiere)
+}
+
+fn describe(secs: u64) -> String {
+    match secs {
+        0..=5 => format!("{}...", secs),
+        6..=59 => format!("{} seconds", secs),
+        60 => "1 minute".to_string(),
+        _ if secs % 60 == 0 => format!("{} minutes", secs / 60),
+        _ => format!("{}:{:02}", secs / 60, secs % 60),
+    }
+}
+
+async fn run_countdown(twitch_manager: Arc<TwitchManager>, config: PremiereConfig, premiere: Premiere) {
+    let premieres = &twitch_manager.premieres;
+    let mut outputs = Outputs::new(&twitch_manager, &config);
+    outputs.post(&format!("🎬 Premiere starts in {}!", describe(config.countdown_secs))).await;
+
+    let marks = MARKS.iter().copied().filter(|&mark| mark < config.countdown_secs).chain(std::iter::once(0));
+    for mark in marks {
+        let wait = (premiere.starts_at - chrono::Duration::seconds(mark as i64) - Utc::now()).to_std().unwrap_or_default();
+        let countdown = sleep(wait);
+        tokio::pin!(countdown);
+        let cancelled = loop {
+            tokio::select! {
+                _ = &mut countdown => break false,
+                // A leftover wakeup from an earlier premiere leaves this one current
+                _ = premieres.cancelled.notified() => {
+                    if !premieres.still_current(premiere.id) {
+                        break true;
+                    }
+                }
+            }
+        };
+        if cancelled || !premieres.still_current(premiere.id) {
+            info!("Premiere cancelled");
+            outputs.post("Premiere cancelled.").await;
+            return;
+        }
+        if mark > 0 {
+            outputs.post(&format!("🎬 Premiere in {}", describe(mark))).await;
+        }
+    }
+
+    {
+        let mut state = premieres.state.lock().unwrap();
+        if state.current.as_ref().map(|p| p.id) == Some(premiere.id) {
+            state.current = None;
+        }
+    }
+    go_live(&twitch_manager, &config).await;
+    outputs.post("🎬 The premiere is starting now!").await;
+}
+
+async fn go_live(twitch_manager: &TwitchManager, config: &PremiereConfig) {
+    if !config.content_scene.is_empty() {
+        match &twitch_manager.obs_manager {
+            Some(obs_manager) => {
+                let message = WebSocketMessage {
+                    module: "obs".to_string(),
+                    action: "change_scene".to_string(),
+                    data: json!({ "instance_name": config.obs_instance, "scene_name": config.content_scene }),
+                };
+                if let Err(e) = obs_manager.handle_message(message).await {
+                    error!("Failed to switch OBS to {} for the premiere: {:?}", config.content_scene, e);
+                }
+            }
+            None => error!("Can't switch to {} for the premiere, OBS isn't running", config.content_scene),
+        }
+    }
+
+    if !config.title.is_empty() {
+        let api_client = twitch_manager.get_api_client();
+        let result = match api_client.get_broadcaster_id().await {
+            Ok(broadcaster_id) => channel::update_channel_title(&api_client, &broadcaster_id, &config.title).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => twitch_manager.channel_updates.expect_change(ChannelField::Title, &config.title).await,
+            Err(e) => error!("Failed to set the premiere title: {:?}", e),
+        }
+    }
+}
+
+// Twitch chat gets a line per mark; Discord gets one message that's edited as it counts down
+struct Outputs<'a> {
+    twitch_manager: &'a TwitchManager,
+    channel: String,
+    send_to_chatbox: bool,
+    discord_channel: Option<u64>,
+    discord_message: Option<Message>,
+}
+
+impl<'a> Outputs<'a> {
+    fn new(twitch_manager: &'a TwitchManager, config: &PremiereConfig) -> Self {
+        let discord_channel = config.discord_channel_id.as_ref()
+            .or(twitch_manager.config.discord_announcement_channel_id.as_ref())
+            .and_then(|id| id.parse::<u64>().ok())
+            .filter(|_| config.send_to_discord);
+        Outputs {
+            twitch_manager,
+            channel: twitch_manager.config.twitch_channel_to_join.clone().unwrap_or_default(),
+            send_to_chatbox: config.send_to_chatbox,
+            discord_channel,
+            discord_message: None,
+        }
+    }
+
+    async fn post(&mut self, text: &str) {
+        let twitch_manager = self.twitch_manager;
+        let channel = self.channel.clone();
+        let send_to_chatbox = self.send_to_chatbox;
+        let chat = twitch_manager.send_message_as_bot(&channel, text);
+        let chatbox = async {
+            if send_to_chatbox {
+                let vrchat_osc = twitch_manager.get_osc_manager().get_vrchat_osc();
+                if let Err(e) = vrchat_osc.send_chatbox_message(text, true, false).await {
+                    error!("Failed to show the premiere countdown in the chatbox: {:?}", e);
+                }
+            }
+        };
+        let (chat, _, _) = tokio::join!(chat, chatbox, self.post_to_discord(text));
+        if let Err(e) = chat {
+            error!("Failed to send the premiere countdown to chat: {:?}", e);
+        }
+    }
+
+    async fn post_to_discord(&mut self, text: &str) {
+        let (discord_client, channel_id) = match (&self.twitch_manager.discord_client, self.discord_channel) {
+            (Some(discord_client), Some(channel_id)) => (discord_client, channel_id),
+            _ => return,
+        };
+        let http = discord_client.get_http().await;
+        let result = match &mut self.discord_message {
+            Some(message) => message.edit(&http, EditMessage::new().content(text)).await,
+            None => ChannelId::new(channel_id).say(&http, text).await.map(|message| {
+                self.discord_message = Some(message);
+            }),
+        };
+        if let Err(e) = result {
+            error!("Failed to post the premiere countdown to Discord: {}", e);
+        }
+    }
+}
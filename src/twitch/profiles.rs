@@ -0,0 +1,126 @@
+use std::sync::RwLock;
+use tracing::{error, info, warn};
+use crate::config::{ProfilesConfig, StreamProfile};
+use crate::scheduler::Scheduler;
+use crate::twitch::voice::BotVoice;
+use crate::twitch::TwitchManager;
+
+#[derive(Default)]
+struct ProfileState {
+    auto_switch: bool,
+    profiles: Vec<StreamProfile>,
+    active: Option<StreamProfile>,
+    // The configured voice, used by profiles that don't pick one
+    base_voice: String,
+}
+
+// The configured profiles and which one is active, owned by TwitchManager
+#[derive(Default)]
+pub struct StreamProfiles {
+    state: RwLock<ProfileState>,
+}
+
+impl StreamProfiles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Call after BotVoice::configure so the configured voice can be restored when switching away
+    pub fn configure(&self, config: &ProfilesConfig, voice: &BotVoice, scheduler: &Scheduler) {
+        {
+            let mut state = self.state.write().unwrap();
+            state.auto_switch = config.auto_switch;
+            state.profiles = config.profiles.clone();
+            state.active = None;
+            state.base_voice = voice.active();
+        }
+        match &config.active {
+            Some(name) => {
+                if self.activate(name, voice, scheduler).is_none() {
+                    warn!("Active profile {} isn't defined anymore", name);
+                    scheduler.set_paused(&[]);
+                }
+            }
+            None => scheduler.set_paused(&[]),
+        }
+    }
+
+    pub fn active(&self) -> Option<StreamProfile> {
+        self.state.read().unwrap().active.clone()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.state.read().unwrap().profiles.iter().map(|profile| profile.name.clone()).collect()
+    }
+
+    // Whether the active profile lets this reward be shown
+    pub fn allows_redeem(&self, title: &str) -> bool {
+        match &self.state.read().unwrap().active {
+            Some(profile) if !profile.redeems.is_empty() => profile.redeems.iter().any(|r| r.eq_ignore_ascii_case(title)),
+            _ => true,
+        }
+    }
+
+    // The parts of a profile that don't need the Twitch side: voice and paused jobs
+    fn activate(&self, name: &str, voice: &BotVoice, scheduler: &Scheduler) -> Option<StreamProfile> {
+        let (profile, base_voice) = {
+            let mut state = self.state.write().unwrap();
+            let profile = state.profiles.iter().find(|p| p.name.eq_ignore_ascii_case(name))?.clone();
+            state.active = Some(profile.clone());
+            (profile, state.base_voice.clone())
+        };
+
+        let voice_name = profile.bot_voice.as_deref().unwrap_or(&base_voice);
+        if voice.set_active(voice_name).is_none() {
+            warn!("Profile {} uses unknown bot voice {}", profile.name, voice_name);
+        }
+        scheduler.set_paused(&profile.paused_jobs);
+        Some(profile)
+    }
+
+    // The first profile listing the category, if auto switching is on and it isn't active already
+    pub fn auto_switch_target(&self, game: &str) -> Option<String> {
+        let state = self.state.read().unwrap();
+        if !state.auto_switch {
+            return None;
+        }
+        let matching = state.profiles.iter()
+            .find(|profile| profile.games.iter().any(|g| g.eq_ignore_ascii_case(game)))?;
+        match &state.active {
+            Some(active) if matching.name == active.name => None,
+            _ => Some(matching.name.clone()),
+        }
+    }
+}
+
+// Switches to the named profile and re-syncs everything it touches
+pub async fn apply(twitch_manager: &TwitchManager, name: &str) -> Option<StreamProfile> {
+    let profile = twitch_manager.profiles.activate(name, &twitch_manager.bot_voice, &twitch_manager.scheduler)?;
+    info!("Switched to stream profile {}", profile.name);
+
+    if let Some(avatar_id) = &profile.osc_avatar {
+        if !twitch_manager.get_osc_configs().write().await.set_active_avatar(avatar_id) {
+            warn!("Profile {} uses avatar {} which has no OSC profile", profile.name, avatar_id);
+        }
+    }
+
+    if let Some(redeem_manager) = twitch_manager.redeem_manager.read().await.as_ref() {
+        if let Err(e) = redeem_manager.sync_configured_rewards().await {
+            error!("Failed to sync rewards for profile {}: {:?}", profile.name, e);
+        }
+    }
+
+    let mut notification = format!("Stream profile switched to {}", profile.name);
+    if let Some(collection) = &profile.obs_scene_collection {
+        notification.push_str(&format!(" - switch OBS to the \"{}\" scene collection", collection));
+    }
+    twitch_manager.irc_manager.get_dashboard_state().write().await.add_notification(notification).await;
+
+    Some(profile)
+}
+
+pub async fn auto_switch(twitch_manager: &TwitchManager, game: &str) {
+    if let Some(name) = twitch_manager.profiles.auto_switch_target(game) {
+        apply(twitch_manager, &name).await;
+    }
+}
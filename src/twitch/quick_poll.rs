@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{error, info, warn};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::json;
+use tokio::time::{sleep, Duration};
+use crate::twitch::irc::command_system::split_quoted;
+use crate::twitch::TwitchManager;
+use crate::web_ui::websocket::WebSocketMessage;
+
+pub const DEFAULT_DURATION_SECS: u64 = 120;
+pub const MAX_OPTIONS: usize = 9;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PollOptionResult {
+    pub name: String,
+    pub votes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PollSummary {
+    pub id: u64,
+    pub question: String,
+    pub options: Vec<PollOptionResult>,
+    pub total_votes: usize,
+    pub closed: bool,
+}
+
+// Chat-only poll for channels without Twitch poll access; nothing is persisted
+struct QuickPoll {
+    id: u64,
+    question: String,
+    options: Vec<String>,
+    // user id -> option index, so changing your vote replaces the old one
+    votes: HashMap<String, usize>,
+}
+
+impl QuickPoll {
+    fn summary(&self, closed: bool) -> PollSummary {
+        let mut counts = vec![0; self.options.len()];
+        for &choice in self.votes.values() {
+            counts[choice] += 1;
+        }
+        PollSummary {
+            id: self.id,
+            question: self.question.clone(),
+            options: self.options.iter().zip(counts)
+                .map(|(name, votes)| PollOptionResult { name: name.clone(), votes })
+                .collect(),
+            total_votes: self.votes.len(),
+            closed,
+        }
+    }
+
+    // Accepts the option number as shown in chat or the option itself
+    fn choice_index(&self, choice: &str) -> Option<usize> {
+        match choice.parse::<usize>() {
+            Ok(number) if (1..=self.options.len()).contains(&number) => Some(number - 1),
+            Ok(_) => None,
+            Err(_) => self.options.iter().position(|option| option.eq_ignore_ascii_case(choice)),
+        }
+    }
+}
+
+// The running poll, owned by TwitchManager
+pub struct QuickPolls {
+    poll: Mutex<Option<QuickPoll>>,
+    next_id: AtomicU64,
+}
+
+impl QuickPolls {
+    pub fn new() -> Self {
+        Self {
+            poll: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+// Splits `"Dinner tonight?" pizza "fried rice"` into the question and its options
+pub fn parse_args(args: &[String]) -> Option<(String, Vec<String>)> {
+    let mut tokens = split_quoted(&args.join(" ")).into_iter();
+    let question = tokens.next()?;
+    let options: Vec<String> = tokens.collect();
+    if options.len() < 2 || options.len() > MAX_OPTIONS {
+        return None;
+    }
+    Some((question, options))
+}
+
+pub fn current(twitch_manager: &TwitchManager) -> Option<PollSummary> {
+    twitch_manager.quick_polls.poll.lock().unwrap().as_ref().map(|poll| poll.summary(false))
+}
+
+// Returns None if a poll is already running
+pub fn start(twitch_manager: Arc<TwitchManager>, question: String, options: Vec<String>) -> Option<PollSummary> {
+    let summary = {
+        let mut poll = twitch_manager.quick_polls.poll.lock().unwrap();
+        if poll.is_some() {
+            return None;
+        }
+        let new_poll = QuickPoll {
+            id: twitch_manager.quick_polls.next_id.fetch_add(1, Ordering::Relaxed),
+            question,
+            options,
+            votes: HashMap::new(),
+        };
+        let summary = new_poll.summary(false);
+        *poll = Some(new_poll);
+        summary
+    };
+    info!("Quick poll #{} started: {}", summary.id, summary.question);
+
+    let id = summary.id;
+    let tasks = twitch_manager.tasks.clone();
+    tasks.spawn("quick_poll", async move {
+        sleep(Duration::from_secs(DEFAULT_DURATION_SECS)).await;
+        // The poll may have been ended by hand and another one started in the meantime
+        let summary = {
+            let mut poll = twitch_manager.quick_polls.poll.lock().unwrap();
+            match poll.as_ref() {
+                Some(running) if running.id == id => poll.take().map(|poll| poll.summary(true)),
+                _ => None,
+            }
+        };
+        if let Some(summary) = summary {
+            finish(&twitch_manager, &summary).await;
+        }
+    });
+
+    Some(summary)
+}
+
+// None when there's no poll or the choice doesn't match an option
+pub fn vote(twitch_manager: &TwitchManager, user_id: &str, choice: &str) -> Option<PollSummary> {
+    let mut poll = twitch_manager.quick_polls.poll.lock().unwrap();
+    let poll = poll.as_mut()?;
+    let index = poll.choice_index(choice.trim())?;
+    poll.votes.insert(user_id.to_string(), index);
+    Some(poll.summary(false))
+}
+
+pub fn end(twitch_manager: &TwitchManager) -> Option<PollSummary> {
+    twitch_manager.quick_polls.poll.lock().unwrap().take().map(|poll| poll.summary(true))
+}
+
+pub fn describe_options(summary: &PollSummary) -> String {
+    summary.options.iter().enumerate()
+        .map(|(i, option)| format!("{}) {}", i + 1, option.name))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn describe_result(summary: &PollSummary) -> String {
+    let top = summary.options.iter().map(|option| option.votes).max().unwrap_or(0);
+    if top == 0 {
+        return format!("Poll closed: {} - nobody voted!", summary.question);
+    }
+
+    let winners: Vec<&str> = summary.options.iter()
+        .filter(|option| option.votes == top)
+        .map(|option| option.name.as_str())
+        .collect();
+    let percent = top * 100 / summary.total_votes;
+    if winners.len() == 1 {
+        format!("Poll closed: {} - {} wins with {} votes ({}%)!", summary.question, winners[0], top, percent)
+    } else {
+        format!("Poll closed: {} - it's a tie between {} with {} votes each!", summary.question, winners.join(" and "), top)
+    }
+}
+
+// Pushes live results to the dashboard and any overlay listening for the "poll" module
+pub async fn publish(twitch_manager: &TwitchManager, summary: &PollSummary) {
+    let message = WebSocketMessage {
+        module: "poll".to_string(),
+        action: if summary.closed { "end" } else { "update" }.to_string(),
+        data: json!(summary),
+    };
+    let dashboard_state = twitch_manager.irc_manager.get_dashboard_state();
+    if let Err(e) = dashboard_state.read().await.broadcast_message(message).await {
+        warn!("Failed to send poll results to the dashboard: {}", e);
+    }
+}
+
+// Announces the winner in chat and publishes the final results
+pub async fn finish(twitch_manager: &TwitchManager, summary: &PollSummary) {
+    info!("Quick poll #{} closed with {} votes", summary.id, summary.total_votes);
+    publish(twitch_manager, summary).await;
+
+    let channel = match &twitch_manager.config.twitch_channel_to_join {
+        Some(channel) => channel,
+        None => return,
+    };
+    if let Err(e) = twitch_manager.send_message_as_bot(channel, &describe_result(summary)).await {
+        error!("Failed to announce poll result: {}", e);
+    }
+}
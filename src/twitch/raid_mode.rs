@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use tracing::{error, info};
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Duration, Instant};
+use crate::twitch::TwitchManager;
+
+// While active, per-user greetings and relays are suppressed so a big raid
+// doesn't flood chat, the chatbox and the API with one event per raider.
+#[derive(Debug, Default)]
+pub struct RaidMode {
+    active_until: RwLock<Option<Instant>>,
+    suppressed_follows: AtomicU32,
+}
+
+impl RaidMode {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn is_active(&self) -> bool {
+        matches!(*self.active_until.read().await, Some(until) if Instant::now() < until)
+    }
+
+    pub fn record_suppressed_follow(&self) {
+        self.suppressed_follows.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Starts (or extends) raid mode and posts a single aggregate follow thank-you when it ends
+    pub async fn activate(self: &Arc<Self>, twitch_manager: Arc<TwitchManager>, channel: String, duration: Duration) {
+        let until = Instant::now() + duration;
+        let already_active = {
+            let mut active_until = self.active_until.write().await;
+            let already_active = matches!(*active_until, Some(current) if Instant::now() < current);
+            *active_until = Some(until);
+            already_active
+        };
+        info!("Raid mode active for {}s", duration.as_secs());
+
+        if already_active {
+            return;
+        }
+
+        let raid_mode = self.clone();
+        tokio::spawn(async move {
+            // Keep waiting while further raids extend the window
+            loop {
+                let until = match *raid_mode.active_until.read().await {
+                    Some(until) => until,
+                    None => break,
+                };
+                if Instant::now() >= until {
+                    break;
+                }
+                sleep(until - Instant::now()).await;
+            }
+
+            *raid_mode.active_until.write().await = None;
+            let follows = raid_mode.suppressed_follows.swap(0, Ordering::Relaxed);
+            info!("Raid mode ended, {} follow greetings were suppressed", follows);
+
+            if follows > 0 {
+                let message = format!(
+                    "Welcome to the {} new {} who joined during the raid! Thanks for following!",
+                    follows,
+                    if follows == 1 { "follower" } else { "followers" }
+                );
+                if let Err(e) = twitch_manager.send_message_as_bot(&channel, &message).await {
+                    error!("Failed to send raid follow summary: {:?}", e);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,227 @@
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serenity::model::id::ChannelId;
+use tokio::sync::Notify;
+use tokio::time::{sleep, Duration};
+use tracing::{error, info};
+use crate::config::RaidTrainConfig;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::costreams::{self, CoStream, CoStreamCache};
+use crate::twitch::TwitchManager;
+
+// A raid the train suggested at the end of stream, waiting on a mod or its countdown
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PendingRaid {
+    pub id: u64,
+    pub target: CoStream,
+    // Goes through at expires_at unless cancelled; otherwise the suggestion lapses then
+    pub automatic: bool,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Decision {
+    Go,
+    Cancel,
+}
+
+struct TrainState {
+    pending: Option<PendingRaid>,
+    decision: Option<Decision>,
+    next_id: u64,
+}
+
+// The raid waiting on a decision, owned by TwitchManager and shared with the dashboard routes
+pub struct RaidTrain {
+    state: Mutex<TrainState>,
+    // Wakes the countdown task when a mod confirms or cancels
+    decided: Notify,
+}
+
+impl RaidTrain {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(TrainState {
+                pending: None,
+                decision: None,
+                next_id: 1,
+            }),
+            decided: Notify::new(),
+        }
+    }
+
+    pub fn pending(&self) -> Option<PendingRaid> {
+        self.state.lock().unwrap().pending.clone()
+    }
+
+    fn decide(&self, decision: Decision) -> Option<PendingRaid> {
+        let mut state = self.state.lock().unwrap();
+        let pending = state.pending.clone()?;
+        state.decision = Some(decision);
+        self.decided.notify_one();
+        Some(pending)
+    }
+
+    // Raid the pending target now
+    pub fn confirm(&self) -> Option<PendingRaid> {
+        self.decide(Decision::Go)
+    }
+
+    pub fn cancel(&self) -> Option<PendingRaid> {
+        self.decide(Decision::Cancel)
+    }
+}
+
+// Live status of everyone on the train, in raid order
+pub async fn statuses(costreams: &CoStreamCache, api_client: &TwitchAPIClient, config: &RaidTrainConfig) -> Result<Vec<CoStream>, Box<dyn std::error::Error + Send + Sync>> {
+    costreams.statuses(api_client, &config.channels).await
+}
+
+// The first live channel after ours, wrapping around; from the top if we aren't on the list
+pub fn next_stop<'a>(own_login: &str, train: &'a [CoStream]) -> Option<&'a CoStream> {
+    let start = train.iter().position(|stream| stream.login.eq_ignore_ascii_case(own_login)).map_or(0, |i| i + 1);
+    (0..train.len())
+        .map(|offset| &train[(start + offset) % train.len()])
+        .find(|stream| stream.live && !stream.login.eq_ignore_ascii_case(own_login))
+}
+
+// Finds the next live stop and starts the countdown, or waits for a mod to confirm. Returns the chat reply.
+pub async fn propose(
+    twitch_manager: Arc<TwitchManager>,
+    config: RaidTrainConfig,
+    channel: String,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(pending) = twitch_manager.raid_train.pending() {
+        return Ok(format!("The raid train is already headed to {}.", pending.target.display_name));
+    }
+    if costreams::logins(&config.channels).is_empty() {
+        return Ok("There's no raid train set up.".to_string());
+    }
+
+    let train = statuses(&twitch_manager.costreams, &twitch_manager.get_api_client(), &config).await?;
+    let own_login = twitch_manager.config.twitch_channel_to_join.clone().unwrap_or_default();
+    let target = match next_stop(&own_login, &train) {
+        Some(target) => target.clone(),
+        None => return Ok("Nobody else on the raid train is live right now.".to_string()),
+    };
+
+    let pending = {
+        let mut state = twitch_manager.raid_train.state.lock().unwrap();
+        let pending = PendingRaid {
+            id: state.next_id,
+            target: target.clone(),
+            automatic: config.auto_raid,
+            expires_at: Utc::now() + chrono::Duration::seconds(config.confirm_secs as i64),
+        };
+        state.next_id += 1;
+        state.pending = Some(pending.clone());
+        state.decision = None;
+        pending
+    };
+    info!("Raid train suggests {} (automatic: {})", target.login, pending.automatic);
+
+    let id = pending.id;
+    let tasks = twitch_manager.tasks.clone();
+    tasks.spawn("raid_train_decision", async move {
+        wait_for_decision(twitch_manager, config, channel, train, id).await;
+    });
+
+    let game = target.game.as_ref().map(|game| format!(" ({})", game)).unwrap_or_default();
+    Ok(if pending.automatic {
+        format!(
+            "🚂 Next stop on the raid train: {}{}! Raiding in {}s, mods can stop it with !raidtrain cancel",
+            target.display_name, game, config.confirm_secs
+        )
+    } else {
+        format!(
+            "🚂 Next stop on the raid train: {}{}. Mods: !raidtrain go to raid, !raidtrain cancel to stay",
+            target.display_name, game
+        )
+    })
+}
+
+async fn wait_for_decision(
+    twitch_manager: Arc<TwitchManager>,
+    config: RaidTrainConfig,
+    channel: String,
+    train: Vec<CoStream>,
+    id: u64,
+) {
+    let countdown = sleep(Duration::from_secs(config.confirm_secs));
+    tokio::pin!(countdown);
+    let timed_out = loop {
+        tokio::select! {
+            _ = &mut countdown => break true,
+            _ = twitch_manager.raid_train.decided.notified() => {
+                // A leftover wakeup from an earlier suggestion has no decision attached
+                if twitch_manager.raid_train.state.lock().unwrap().decision.is_some() {
+                    break false;
+                }
+            }
+        }
+    };
+
+    let (pending, decision) = {
+        let mut state = twitch_manager.raid_train.state.lock().unwrap();
+        if state.pending.as_ref().map(|p| p.id) != Some(id) {
+            return;
+        }
+        (state.pending.take().unwrap(), state.decision.take())
+    };
+
+    let message = match decision {
+        Some(Decision::Go) => raid(&twitch_manager, &config, &train, &pending.target).await,
+        None if timed_out && pending.automatic => raid(&twitch_manager, &config, &train, &pending.target).await,
+        Some(Decision::Cancel) => "Raid train cancelled, we're staying put.".to_string(),
+        None => format!("The raid suggestion for {} expired.", pending.target.display_name),
+    };
+    if let Err(e) = twitch_manager.send_message_as_bot(&channel, &message).await {
+        error!("Failed to send raid train message: {:?}", e);
+    }
+}
+
+async fn raid(twitch_manager: &TwitchManager, config: &RaidTrainConfig, train: &[CoStream], target: &CoStream) -> String {
+    if let Err(e) = start_raid(&twitch_manager.get_api_client(), &target.login).await {
+        error!("Failed to raid {}: {:?}", target.login, e);
+        return format!("Couldn't start the raid to {}, please raid manually.", target.display_name);
+    }
+    info!("Raid train raiding {}", target.login);
+    announce(twitch_manager, config, train, target).await;
+    format!("🚂 All aboard! Raiding twitch.tv/{}", target.login)
+}
+
+async fn start_raid(api_client: &TwitchAPIClient, login: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let broadcaster_id = api_client.get_broadcaster_id().await?;
+    let user = api_client.get_user_info(login).await?;
+    let target_id = user["data"][0]["id"].as_str().ok_or("Raid target not found")?;
+    api_client.start_raid(&broadcaster_id, target_id).await?;
+    Ok(())
+}
+
+// Posts the hop and the whole chain to Discord, with the live stops marked
+async fn announce(twitch_manager: &TwitchManager, config: &RaidTrainConfig, train: &[CoStream], target: &CoStream) {
+    let discord_channel = config.discord_channel_id.as_ref()
+        .or(twitch_manager.config.discord_announcement_channel_id.as_ref())
+        .and_then(|id| id.parse::<u64>().ok());
+    if let (Some(discord_client), Some(channel_id)) = (&twitch_manager.discord_client, discord_channel) {
+        let own_login = twitch_manager.config.twitch_channel_to_join.clone().unwrap_or_default();
+        let chain = train.iter()
+            .map(|stream| {
+                let name = if stream.login == target.login { format!("**{}**", stream.display_name) } else { stream.display_name.clone() };
+                if stream.live { format!("🔴 {}", name) } else { name }
+            })
+            .collect::<Vec<_>>()
+            .join(" → ");
+        let message = format!(
+            "🚂 {} is raiding **{}**! Hop on: https://twitch.tv/{}\nRaid train: {}",
+            own_login, target.display_name, target.login, chain
+        );
+
+        let http = discord_client.get_http().await;
+        if let Err(e) = ChannelId::new(channel_id).say(&http, message).await {
+            error!("Failed to post raid train to Discord: {}", e);
+        }
+    }
+}
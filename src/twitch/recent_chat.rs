@@ -0,0 +1,95 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use chrono::{DateTime, Duration, Utc};
+use crate::storage::ChatLogEntry;
+
+// How far back moderation tools like !nuke can reach
+pub const RECENT_CHAT_MINUTES: i64 = 60;
+// Caps memory use during a busy hour; the oldest messages go first
+const MAX_RECENT_MESSAGES: usize = 20_000;
+
+// The last hour of chat, kept in memory only and never written to disk. Moderation looks here as
+// well as in the chat log, so it works the same for viewers who opted out of chat storage.
+#[derive(Debug, Default)]
+pub struct RecentChat {
+    messages: Mutex<VecDeque<ChatLogEntry>>,
+}
+
+impl RecentChat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, message_id: &str, user_id: &str, user_name: &str, message: &str) {
+        self.push(ChatLogEntry {
+            message_id: message_id.to_string(),
+            user_id: user_id.to_string(),
+            user_name: user_name.to_string(),
+            message: message.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    fn push(&self, entry: ChatLogEntry) {
+        let mut messages = self.messages.lock().unwrap();
+        messages.push_back(entry);
+        let cutoff = Utc::now() - Duration::minutes(RECENT_CHAT_MINUTES);
+        while messages.front().map_or(false, |oldest| oldest.timestamp < cutoff) {
+            messages.pop_front();
+        }
+        while messages.len() > MAX_RECENT_MESSAGES {
+            messages.pop_front();
+        }
+    }
+
+    // Messages since the given time containing the phrase (case-insensitive), newest first
+    pub fn find(&self, phrase: &str, since: DateTime<Utc>) -> Vec<ChatLogEntry> {
+        let phrase = phrase.to_lowercase();
+        self.messages.lock().unwrap()
+            .iter()
+            .rev()
+            .take_while(|entry| entry.timestamp >= since)
+            .filter(|entry| entry.message.to_lowercase().contains(&phrase))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(message_id: &str, message: &str, minutes_ago: i64) -> ChatLogEntry {
+        ChatLogEntry {
+            message_id: message_id.to_string(),
+            user_id: "1".to_string(),
+            user_name: "viewer".to_string(),
+            message: message.to_string(),
+            timestamp: Utc::now() - Duration::minutes(minutes_ago),
+        }
+    }
+
+    #[test]
+    fn finds_recent_matches_newest_first() {
+        let recent = RecentChat::new();
+        recent.push(entry("1", "BUY FOLLOWERS now", 30));
+        recent.push(entry("2", "hello", 20));
+        recent.push(entry("3", "cheap followers here", 10));
+        recent.record("4", "2", "other", "buy followers");
+
+        let since = Utc::now() - Duration::minutes(15);
+        let found: Vec<String> = recent.find("Followers", since).into_iter().map(|entry| entry.message_id).collect();
+        assert_eq!(found, vec!["4", "3"]);
+    }
+
+    #[test]
+    fn drops_messages_older_than_the_window() {
+        let recent = RecentChat::new();
+        recent.push(entry("1", "spam", RECENT_CHAT_MINUTES + 5));
+        recent.push(entry("2", "spam", 1));
+
+        let since = Utc::now() - Duration::minutes(RECENT_CHAT_MINUTES * 2);
+        let found: Vec<String> = recent.find("spam", since).into_iter().map(|entry| entry.message_id).collect();
+        assert_eq!(found, vec!["2"]);
+    }
+}
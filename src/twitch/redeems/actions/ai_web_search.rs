@@ -1,10 +1,10 @@
 use std::any::Any;
 use async_trait::async_trait;
 use std::sync::Arc;
-use log::{error, info};
+use tracing::{error, info};
 use crate::ai::AIClient;
 use crate::twitch::models::{Redemption, RedemptionResult, RedeemHandler};
-use super::utils::split_response;
+use crate::chunker::split_response;
 
 pub struct AIWebSearchAction {
     ai_client: Arc<AIClient>,
@@ -61,4 +61,8 @@ impl RedeemHandler for AIWebSearchAction {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn uses_ai(&self) -> bool {
+        true
+    }
 }
\ No newline at end of file
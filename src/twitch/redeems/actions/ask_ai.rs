@@ -1,30 +1,36 @@
 use std::any::Any;
 use async_trait::async_trait;
 use std::sync::Arc;
-use log::{error, info};
+use tracing::{error, info};
 use crate::ai::AIClient;
+use crate::twitch::ai_tools::StreamTools;
+use crate::twitch::TwitchManager;
 use crate::twitch::models::{Redemption, RedemptionResult, RedeemHandler};
-use super::utils::split_response;
+use crate::chunker::split_response;
+
+async fn prompt_for(twitch_manager: &TwitchManager, key: &str, redemption: &Redemption) -> String {
+    let game = twitch_manager.get_current_game().await;
+    let mut vars = vec![("username", redemption.user_name.as_str())];
+    if let Some(game) = &game {
+        vars.push(("game", game.as_str()));
+    }
+    twitch_manager.prompts.render(key, &vars)
+}
 
 pub struct AskAIAction {
     ai_client: Arc<AIClient>,
+    twitch_manager: Arc<TwitchManager>,
 }
 
 impl AskAIAction {
-    pub fn new(ai_client: Arc<AIClient>) -> Self {
-        Self { ai_client }
+    pub fn new(ai_client: Arc<AIClient>, twitch_manager: Arc<TwitchManager>) -> Self {
+        Self { ai_client, twitch_manager }
     }
 }
 
 #[async_trait]
 impl RedeemHandler for AskAIAction {
     async fn handle(&self, redemption: &Redemption) -> RedemptionResult {
-        let base_prompt = "You are an entertaining chatbot. Your owner is the cute and funny catgirl named kittyn. \
-                           Answer questions humorously and in a playful manner. \
-                           We live on Twitch in a cozy but high-tech corner of the metaverse. \
-                           Be friendly and love the chat who asks you these questions. \
-                           Treat chat like they were your own children.";
-
         let user_input = redemption.user_input.as_deref().unwrap_or("").trim();
 
         if user_input.is_empty() {
@@ -34,9 +40,11 @@ impl RedeemHandler for AskAIAction {
             };
         }
 
+        let base_prompt = prompt_for(&self.twitch_manager, "redeem.ask_ai", redemption).await;
         let full_prompt = format!("{}User's question: {}", base_prompt, user_input);
+        let tools = StreamTools::new(self.twitch_manager.clone());
 
-        match self.ai_client.generate_response_without_history(&full_prompt).await {
+        match self.ai_client.generate_response_with_tools(&full_prompt, &tools).await {
             Ok(response) => {
                 let (first_part, remainder) = split_response(response);
 
@@ -64,24 +72,26 @@ impl RedeemHandler for AskAIAction {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn uses_ai(&self) -> bool {
+        true
+    }
 }
 
 pub struct SeriousAIAction {
     ai_client: Arc<AIClient>,
+    twitch_manager: Arc<TwitchManager>,
 }
 
 impl SeriousAIAction {
-    pub fn new(ai_client: Arc<AIClient>) -> Self {
-        Self { ai_client }
+    pub fn new(ai_client: Arc<AIClient>, twitch_manager: Arc<TwitchManager>) -> Self {
+        Self { ai_client, twitch_manager }
     }
 }
 
 #[async_trait]
 impl RedeemHandler for SeriousAIAction {
     async fn handle(&self, redemption: &Redemption) -> RedemptionResult {
-        let base_prompt = "You are a knowledgeable AI assistant. Provide a concise, accurate, and informative answer \
-                           to the following question. Your response should be clear and fit within a single chat message. ";
-
         let user_input = redemption.user_input.as_deref().unwrap_or("").trim();
 
         if user_input.is_empty() {
@@ -91,9 +101,11 @@ impl RedeemHandler for SeriousAIAction {
             };
         }
 
+        let base_prompt = prompt_for(&self.twitch_manager, "redeem.serious_ai", redemption).await;
         let full_prompt = format!("{}User's question: {}", base_prompt, user_input);
+        let tools = StreamTools::new(self.twitch_manager.clone());
 
-        match self.ai_client.generate_response_without_history(&full_prompt).await {
+        match self.ai_client.generate_response_with_tools(&full_prompt, &tools).await {
             Ok(response) => {
                 let (first_part, remainder) = split_response(response);
 
@@ -121,15 +133,20 @@ impl RedeemHandler for SeriousAIAction {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn uses_ai(&self) -> bool {
+        true
+    }
 }
 
 pub struct GrokAIAction {
     ai_client: Arc<AIClient>,
+    twitch_manager: Arc<TwitchManager>,
 }
 
 impl GrokAIAction {
-    pub fn new(ai_client: Arc<AIClient>) -> Self {
-        Self { ai_client }
+    pub fn new(ai_client: Arc<AIClient>, twitch_manager: Arc<TwitchManager>) -> Self {
+        Self { ai_client, twitch_manager }
     }
 }
 
@@ -147,7 +164,8 @@ impl RedeemHandler for GrokAIAction {
         info!("Processing Grok AI request from {}: {}", redemption.user_name, user_input);
 
         // Only try the XAI provider for Grok
-        match self.ai_client.generate_grok_response(user_input).await {
+        let system_prompt = prompt_for(&self.twitch_manager, "redeem.grok", redemption).await;
+        match self.ai_client.generate_grok_response(user_input, &system_prompt).await {
             Ok(response) => {
                 let (first_part, remainder) = split_response(response);
 
@@ -179,4 +197,8 @@ impl RedeemHandler for GrokAIAction {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn uses_ai(&self) -> bool {
+        true
+    }
 }
\ No newline at end of file
@@ -0,0 +1,122 @@
+use std::any::Any;
+use std::sync::Arc;
+use async_trait::async_trait;
+use tracing::{error, info};
+use tokio::time::{sleep, Duration};
+use crate::storage::expand_kv_placeholders;
+use crate::twitch::models::{ChainAction, ChainStep, RedeemHandler, Redemption, RedemptionResult};
+use crate::twitch::redeems::actions::OBSSourceRedeem;
+use crate::twitch::redeems::registry::RedeemRegistry;
+use crate::twitch::soundboard::Players;
+use crate::twitch::TwitchManager;
+
+// Runs the action chain configured on a reward in redeems_config.json, e.g.
+// an OSC toggle, then a chat message, then an OBS source or a sound, each after its own delay.
+pub struct ChainRedeem {
+    twitch_manager: Arc<TwitchManager>,
+    registry: Arc<RedeemRegistry>,
+}
+
+impl ChainRedeem {
+    pub fn new(twitch_manager: Arc<TwitchManager>, registry: Arc<RedeemRegistry>) -> Self {
+        Self { twitch_manager, registry }
+    }
+
+    async fn run_step(twitch_manager: &TwitchManager, step: &ChainStep, redemption: &Redemption) -> Result<(), String> {
+        match &step.action {
+            ChainAction::Osc { config } => {
+                let config = twitch_manager.get_osc_configs().read().await.get_config(config).cloned()
+                    .ok_or_else(|| format!("OSC config {} not found", config))?;
+                let osc_manager = twitch_manager.get_osc_manager();
+                osc_manager.send_osc_message(&config.osc_endpoint, &config.osc_type, &config.osc_value).await
+                    .map_err(|e| format!("Failed to send OSC message: {}", e))?;
+                if let Some(frames) = config.execution_duration {
+                    twitch_manager.tasks.spawn("chain_osc_reset", async move {
+                        sleep(Duration::from_secs_f32(frames as f32 / 60.0)).await;
+                        if let Err(e) = osc_manager.send_osc_message(&config.osc_endpoint, &config.osc_type, &config.default_value).await {
+                            error!("Failed to reset OSC value for {}: {}", config.osc_endpoint, e);
+                        }
+                    });
+                }
+                Ok(())
+            }
+            ChainAction::Chat { message } => {
+                let channel = twitch_manager.config.twitch_channel_to_join.clone()
+                    .ok_or_else(|| "No Twitch channel configured".to_string())?;
+                let mut message = message.replace("{user}", &redemption.user_name);
+                // Before {input}, so the viewer's text can't carry placeholders of its own
+                if let Some(storage) = twitch_manager.get_storage() {
+                    message = expand_kv_placeholders(&*storage.read().await, &message);
+                }
+                let message = message.replace("{input}", redemption.user_input.as_deref().unwrap_or(""));
+                twitch_manager.send_message_as_bot(&channel, &message).await.map_err(|e| e.to_string())
+            }
+            ChainAction::Obs(action) => {
+                let obs_manager = match twitch_manager.get_obs_manager() {
+                    Some(obs_manager) if obs_manager.is_connected().await => obs_manager,
+                    _ => return Err("OBS is not connected".to_string()),
+                };
+                OBSSourceRedeem::apply(&obs_manager, action, true).await.map_err(|e| e.to_string())?;
+                let action = action.clone();
+                twitch_manager.tasks.spawn("chain_obs_revert", async move {
+                    sleep(Duration::from_secs(action.duration_secs)).await;
+                    if let Err(e) = OBSSourceRedeem::apply(&obs_manager, &action, false).await {
+                        error!("Failed to revert OBS source {}: {:?}", action.source_name, e);
+                    }
+                });
+                Ok(())
+            }
+            ChainAction::Sound { name } => {
+                let players = Players {
+                    obs_manager: twitch_manager.get_obs_manager(),
+                    dashboard_state: twitch_manager.irc_manager.get_dashboard_state(),
+                };
+                let config = twitch_manager.config.soundboard.clone();
+                if !config.enabled {
+                    return Err("The soundboard is turned off".to_string());
+                }
+                twitch_manager.soundboard.play(&players, &config, name, false).await.map(|_| ())
+            }
+        }
+    }
+
+    // Stops at the first failing step; the steps that already ran aren't undone
+    async fn run_chain(twitch_manager: Arc<TwitchManager>, steps: Vec<ChainStep>, redemption: Redemption) {
+        for (i, step) in steps.iter().enumerate() {
+            if step.delay_ms > 0 {
+                sleep(Duration::from_millis(step.delay_ms)).await;
+            }
+            if let Err(e) = Self::run_step(&twitch_manager, step, &redemption).await {
+                let message = format!("{} chain stopped at step {} of {}: {}", redemption.reward_title, i + 1, steps.len(), e);
+                error!("{}", message);
+                twitch_manager.irc_manager.get_dashboard_state().write().await.add_notification(message).await;
+                return;
+            }
+        }
+        info!("Finished {} step chain for {} ({})", steps.len(), redemption.reward_title, redemption.user_name);
+    }
+}
+
+#[async_trait]
+impl RedeemHandler for ChainRedeem {
+    async fn handle(&self, redemption: &Redemption) -> RedemptionResult {
+        let steps = self.registry.get(&redemption.reward_title).await.map(|r| r.actions).unwrap_or_default();
+        if steps.is_empty() {
+            return RedemptionResult {
+                success: false,
+                message: Some(format!("No action chain configured for {}", redemption.reward_title)),
+            };
+        }
+
+        self.twitch_manager.tasks.spawn("redeem_chain", Self::run_chain(self.twitch_manager.clone(), steps, redemption.clone()));
+
+        RedemptionResult {
+            success: true,
+            message: None,
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -2,8 +2,13 @@ mod coin_game;
 mod ask_ai;
 mod vrc_osc;
 mod ai_web_search;
-mod utils;
 mod vrc_toggle_redeems;
+mod obs_source;
+mod translate;
+mod world_react;
+mod chain;
+mod tts;
+mod suggestion;
 
 pub use vrc_toggle_redeems::VRCToggleRedeems;
 pub use ai_web_search::AIWebSearchAction;
@@ -11,4 +16,10 @@ pub use coin_game::CoinGameAction;
 pub use ask_ai::AskAIAction;
 pub use ask_ai::SeriousAIAction;
 pub use ask_ai::GrokAIAction;
-pub use vrc_osc::VRCOscRedeems;
\ No newline at end of file
+pub use vrc_osc::VRCOscRedeems;
+pub use obs_source::OBSSourceRedeem;
+pub use translate::TranslateAction;
+pub use world_react::WorldReactAction;
+pub use chain::ChainRedeem;
+pub use tts::TtsRedeem;
+pub use suggestion::SuggestionRedeem;
\ No newline at end of file
@@ -0,0 +1,98 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use tracing::{error, info, warn};
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Duration};
+use crate::obs::OBSManager;
+use crate::twitch::models::{OBSSourceAction, RedeemHandler, Redemption, RedemptionResult};
+use crate::twitch::redeems::registry::RedeemRegistry;
+
+// Toggles an OBS source or filter for the duration configured on the reward.
+// Redeems targeting the same source are played one after another so an
+// earlier revert can't cut a later redeem short.
+pub struct OBSSourceRedeem {
+    obs_manager: Option<Arc<OBSManager>>,
+    registry: Arc<RedeemRegistry>,
+    source_locks: Arc<Mutex<HashMap<String, Arc<Mutex<()>>>>>,
+}
+
+impl OBSSourceRedeem {
+    pub fn new(obs_manager: Option<Arc<OBSManager>>, registry: Arc<RedeemRegistry>) -> Self {
+        Self {
+            obs_manager,
+            registry,
+            source_locks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    async fn source_lock(&self, action: &OBSSourceAction) -> Arc<Mutex<()>> {
+        let key = match &action.filter_name {
+            Some(filter) => format!("{}/{}/{}", action.instance_name, action.source_name, filter),
+            None => format!("{}/{}/{}", action.instance_name, action.scene_name, action.source_name),
+        };
+        self.source_locks.lock().await
+            .entry(key)
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone()
+    }
+
+    pub(crate) async fn apply(obs_manager: &OBSManager, action: &OBSSourceAction, enabled: bool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        match &action.filter_name {
+            Some(filter) => obs_manager.toggle_filter(&action.instance_name, &action.source_name, filter, enabled).await,
+            None => obs_manager.toggle_source(&action.instance_name, &action.scene_name, &action.source_name, enabled).await,
+        }
+    }
+}
+
+#[async_trait]
+impl RedeemHandler for OBSSourceRedeem {
+    async fn handle(&self, redemption: &Redemption) -> RedemptionResult {
+        let action = match self.registry.get(&redemption.reward_title).await.and_then(|r| r.obs_action) {
+            Some(action) => action,
+            None => return RedemptionResult {
+                success: false,
+                message: Some(format!("No OBS action configured for {}", redemption.reward_title)),
+            },
+        };
+
+        let obs_manager = match &self.obs_manager {
+            Some(obs_manager) if obs_manager.is_connected().await => obs_manager.clone(),
+            _ => {
+                warn!("OBS is not connected, can't run OBS action for {}", redemption.reward_title);
+                return RedemptionResult {
+                    success: false,
+                    message: Some("OBS is not connected right now, sorry!".to_string()),
+                };
+            }
+        };
+
+        let lock = self.source_lock(&action).await;
+        let user_name = redemption.user_name.clone();
+        tokio::spawn(async move {
+            let _guard = lock.lock_owned().await;
+
+            if let Err(e) = Self::apply(&obs_manager, &action, true).await {
+                error!("Failed to enable OBS source {} for {}: {:?}", action.source_name, user_name, e);
+                return;
+            }
+            info!("Enabled OBS source {} for {} ({}s)", action.source_name, user_name, action.duration_secs);
+
+            sleep(Duration::from_secs(action.duration_secs)).await;
+
+            if let Err(e) = Self::apply(&obs_manager, &action, false).await {
+                error!("Failed to revert OBS source {}: {:?}", action.source_name, e);
+            }
+        });
+
+        RedemptionResult {
+            success: true,
+            message: None,
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,71 @@
+use std::any::Any;
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::{error, info};
+use crate::twitch::models::{Redemption, RedemptionResult, RedeemHandler};
+use crate::twitch::suggestions::{self, SuggestionKind};
+use crate::twitch::TwitchManager;
+
+// Queues the viewer's title or tags for approval on the dashboard; refunds anything that can't be queued
+pub struct SuggestionRedeem {
+    twitch_manager: Arc<TwitchManager>,
+    kind: SuggestionKind,
+}
+
+impl SuggestionRedeem {
+    pub fn new(twitch_manager: Arc<TwitchManager>, kind: SuggestionKind) -> Self {
+        Self { twitch_manager, kind }
+    }
+}
+
+#[async_trait]
+impl RedeemHandler for SuggestionRedeem {
+    async fn handle(&self, redemption: &Redemption) -> RedemptionResult {
+        let input = redemption.user_input.as_deref().unwrap_or("");
+        let result = match self.twitch_manager.get_storage() {
+            Some(storage) => suggestions::submit(
+                &*storage.read().await,
+                &self.twitch_manager.config.suggestions,
+                self.kind,
+                input,
+                &redemption.user_id,
+                &redemption.user_name,
+            ),
+            None => Err("the bot has no database".to_string()),
+        };
+
+        let message = match &result {
+            Ok(_) => format!("@{} thanks, your {} suggestion is waiting for approval!", redemption.user_name, self.kind.as_str()),
+            Err(reason) => {
+                info!("Rejected {} suggestion from {}: {}", self.kind.as_str(), redemption.user_name, reason);
+                let refunded = match self.twitch_manager.get_api_client()
+                    .refund_channel_points(&redemption.reward_id, &redemption.id).await
+                {
+                    Ok(()) => true,
+                    Err(e) => {
+                        error!("Failed to refund suggestion from {}: {:?}", redemption.user_name, e);
+                        false
+                    }
+                };
+                format!(
+                    "@{} your suggestion wasn't queued: {}.{}",
+                    redemption.user_name, reason, if refunded { " Your points were refunded." } else { "" }
+                )
+            }
+        };
+        if let Some(channel) = &self.twitch_manager.config.twitch_channel_to_join {
+            if let Err(e) = self.twitch_manager.send_message_as_bot(channel, &message).await {
+                error!("Failed to reply to {}'s suggestion: {}", redemption.user_name, e);
+            }
+        }
+
+        RedemptionResult {
+            success: result.is_ok(),
+            message: result.err(),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
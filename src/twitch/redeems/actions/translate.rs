@@ -0,0 +1,73 @@
+use std::any::Any;
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::error;
+use crate::ai::{translate, AIClient};
+use crate::twitch::models::{Redemption, RedemptionResult, RedeemHandler};
+use crate::twitch::TwitchManager;
+
+pub struct TranslateAction {
+    ai_client: Arc<AIClient>,
+    twitch_manager: Arc<TwitchManager>,
+}
+
+impl TranslateAction {
+    pub fn new(ai_client: Arc<AIClient>, twitch_manager: Arc<TwitchManager>) -> Self {
+        Self { ai_client, twitch_manager }
+    }
+}
+
+// Accepts "<message>" for the default language or "<language>: <message>"
+fn parse_input<'a>(input: &'a str, default_language: &'a str) -> (&'a str, &'a str) {
+    match input.split_once(':') {
+        Some((language, message)) if !language.trim().is_empty() && !language.trim().contains(' ') => {
+            (language.trim(), message.trim())
+        }
+        _ => (default_language, input),
+    }
+}
+
+#[async_trait]
+impl RedeemHandler for TranslateAction {
+    async fn handle(&self, redemption: &Redemption) -> RedemptionResult {
+        let user_input = redemption.user_input.as_deref().unwrap_or("").trim();
+
+        if user_input.is_empty() {
+            return RedemptionResult {
+                success: false,
+                message: Some("Please provide a message to translate, optionally as <language>: <message>.".to_string()),
+            };
+        }
+
+        let config = &self.twitch_manager.config.translation;
+        let (target_language, text) = parse_input(user_input, &config.default_language);
+
+        match translate::translate(&self.ai_client, text, target_language, config.max_input_chars).await {
+            Ok(translation) => {
+                if config.send_to_chatbox {
+                    let vrchat_osc = self.twitch_manager.get_osc_manager().get_vrchat_osc();
+                    if let Err(e) = vrchat_osc.send_chatbox_message(&translation.chatbox_message(), true, false).await {
+                        error!("Failed to send translation to VRChat chatbox: {}", e);
+                    }
+                }
+
+                RedemptionResult {
+                    success: true,
+                    message: Some(translation.chat_message()),
+                }
+            }
+            Err(e) => RedemptionResult {
+                success: false,
+                message: Some(format!("Failed to translate message: {}", e)),
+            },
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn uses_ai(&self) -> bool {
+        true
+    }
+}
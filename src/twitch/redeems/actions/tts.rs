@@ -0,0 +1,71 @@
+use std::any::Any;
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::{error, info};
+use serde_json::json;
+use crate::twitch::models::{Redemption, RedemptionResult, RedeemHandler};
+use crate::twitch::tts::{self, TtsRequest};
+use crate::twitch::TwitchManager;
+
+// Reads the viewer's message out through the TTS overlay, after moderation.
+// Accepts "<message>" or "<voice>: <message>".
+pub struct TtsRedeem {
+    twitch_manager: Arc<TwitchManager>,
+}
+
+impl TtsRedeem {
+    pub fn new(twitch_manager: Arc<TwitchManager>) -> Self {
+        Self { twitch_manager }
+    }
+}
+
+#[async_trait]
+impl RedeemHandler for TtsRedeem {
+    async fn handle(&self, redemption: &Redemption) -> RedemptionResult {
+        let (voice, text) = tts::parse_voice(redemption.user_input.as_deref().unwrap_or(""));
+        let request = TtsRequest {
+            user_name: redemption.user_name.clone(),
+            text: text.to_string(),
+            voice: voice.map(str::to_string),
+            source: "redeem".to_string(),
+            redemption: Some((redemption.reward_id.clone(), redemption.id.clone())),
+            extra: json!({ "event": "redeem" }),
+        };
+
+        match tts::submit(&self.twitch_manager, request).await {
+            Ok(()) => RedemptionResult {
+                success: true,
+                message: None,
+            },
+            Err(reason) => {
+                info!("Rejected TTS redeem from {}: {}", redemption.user_name, reason);
+                let refunded = self.twitch_manager.tts_queue.refunds_rejected() && match self.twitch_manager.get_api_client()
+                    .refund_channel_points(&redemption.reward_id, &redemption.id).await
+                {
+                    Ok(()) => true,
+                    Err(e) => {
+                        error!("Failed to refund TTS redeem from {}: {:?}", redemption.user_name, e);
+                        false
+                    }
+                };
+                let message = format!(
+                    "@{} your TTS wasn't played: {}.{}",
+                    redemption.user_name, reason, if refunded { " Your points were refunded." } else { "" }
+                );
+                if let Some(channel) = &self.twitch_manager.config.twitch_channel_to_join {
+                    if let Err(e) = self.twitch_manager.send_message_as_bot(channel, &message).await {
+                        error!("Failed to tell {} their TTS was rejected: {}", redemption.user_name, e);
+                    }
+                }
+                RedemptionResult {
+                    success: false,
+                    message: Some(reason),
+                }
+            }
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -1,7 +1,7 @@
 use std::any::Any;
 use async_trait::async_trait;
 use std::sync::Arc;
-use log::{debug, error, warn};
+use tracing::{debug, error, warn};
 use crate::twitch::models::{Redemption, RedemptionResult, RedeemHandler};
 use crate::twitch::TwitchManager;
 
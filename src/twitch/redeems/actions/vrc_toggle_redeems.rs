@@ -1,7 +1,7 @@
 use std::any::Any;
 use async_trait::async_trait;
 use std::sync::Arc;
-use log::{error, info, debug};
+use tracing::{error, info, debug};
 use tokio::time::Duration;
 use crate::twitch::models::{Redemption, RedemptionResult, RedeemHandler};
 use crate::osc::models::{OSCConfig, OSCMessageType, OSCValue};
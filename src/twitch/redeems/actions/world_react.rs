@@ -0,0 +1,68 @@
+use std::any::Any;
+use std::sync::Arc;
+use async_trait::async_trait;
+use tracing::{error, info};
+use crate::ai::AIClient;
+use crate::twitch::models::{RedeemHandler, Redemption, RedemptionResult};
+use crate::twitch::world_react;
+use crate::twitch::TwitchManager;
+
+// "AI reacts to the world": a vision model comments on a screenshot of the stream
+pub struct WorldReactAction {
+    ai_client: Arc<AIClient>,
+    twitch_manager: Arc<TwitchManager>,
+}
+
+impl WorldReactAction {
+    pub fn new(ai_client: Arc<AIClient>, twitch_manager: Arc<TwitchManager>) -> Self {
+        Self { ai_client, twitch_manager }
+    }
+}
+
+#[async_trait]
+impl RedeemHandler for WorldReactAction {
+    async fn handle(&self, redemption: &Redemption) -> RedemptionResult {
+        let config = &self.twitch_manager.config.world_react;
+
+        if let Err(wait) = self.twitch_manager.world_reactions.claim_cooldown(config, &redemption.user_id) {
+            return RedemptionResult {
+                success: false,
+                message: Some(format!("@{}, the AI needs a {}s break before it can react again!", redemption.user_name, wait)),
+            };
+        }
+
+        let request = redemption.user_input.as_deref().unwrap_or("").trim();
+        let reaction = match world_react::react(&self.twitch_manager, &self.ai_client, config, &redemption.user_name, request).await {
+            Ok(reaction) => reaction,
+            Err(e) => {
+                error!("World reaction for {} failed: {}", redemption.user_name, e);
+                return RedemptionResult {
+                    success: false,
+                    message: Some(format!("The AI couldn't see the stream right now: {}", e)),
+                };
+            }
+        };
+
+        if config.require_approval {
+            let id = self.twitch_manager.world_reactions.hold(&redemption.user_name, reaction);
+            return RedemptionResult {
+                success: true,
+                message: Some(format!("@{}, the AI has thoughts (#{}), waiting for the streamer to approve them", redemption.user_name, id)),
+            };
+        }
+
+        info!("World reaction for {}: {}", redemption.user_name, reaction);
+        RedemptionResult {
+            success: true,
+            message: Some(format!("@{} {}", redemption.user_name, reaction)),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn uses_ai(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,44 @@
+use crate::config::RedemptionAnnouncementConfig;
+use crate::twitch::models::{RedeemAnnouncements, Redemption};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Success,
+    Failure,
+    // Waiting in the redeem queue for the streamer
+    Queued,
+    // Still queued when the stream ended, so the points went back
+    Refunded,
+}
+
+// The reward's own template, or else the config default
+fn template<'a>(reward: &'a RedeemAnnouncements, defaults: &'a RedemptionAnnouncementConfig, outcome: Outcome) -> &'a str {
+    let (own, default) = match outcome {
+        Outcome::Success => (&reward.success, &defaults.success),
+        Outcome::Failure => (&reward.failure, &defaults.failure),
+        Outcome::Queued => (&reward.queued, &defaults.queued),
+        Outcome::Refunded => (&reward.refunded, &defaults.refunded),
+    };
+    own.as_deref().unwrap_or(default)
+}
+
+// None when there's nothing to say, e.g. a "{message}" template and a handler that didn't return one.
+// `queued` is the queue number and the place in line.
+pub fn render(
+    reward: &RedeemAnnouncements,
+    defaults: &RedemptionAnnouncementConfig,
+    outcome: Outcome,
+    redemption: &Redemption,
+    message: Option<&str>,
+    queued: Option<(u32, usize)>,
+) -> Option<String> {
+    let text = template(reward, defaults, outcome)
+        .replace("{user}", &redemption.user_name)
+        .replace("{reward}", &redemption.reward_title)
+        .replace("{input}", redemption.user_input.as_deref().unwrap_or(""))
+        .replace("{message}", message.unwrap_or(""))
+        .replace("{number}", &queued.map(|(number, _)| number.to_string()).unwrap_or_default())
+        .replace("{position}", &queued.map(|(_, position)| position.to_string()).unwrap_or_default());
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
@@ -1,16 +1,25 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::sync::Arc;
-use log::info;
+use tracing::{error, info, warn};
+use serenity::model::id::ChannelId;
 use tokio::sync::RwLock;
 use crate::twitch::{TwitchAPIClient, TwitchManager};
 use crate::ai::AIClient;
 use crate::osc::OSCConfigurations;
-use crate::twitch::models::{CoinGameState, RedeemConfigurations, RedeemHandler, RedeemInfo, Redemption, RedemptionResult};
+use crate::twitch::models::{ChainStep, CoinGameState, DynamicPricing, OfflineQueuePolicy, RedeemAnnouncements, RedeemConfigurations, RedeemHandler, RedeemInfo, Redemption, RedemptionResult, RedemptionStatus};
 use crate::twitch::redeems::{AskAIAction, SeriousAIAction, CoinGameAction, VRCOscRedeems};
-use crate::twitch::redeems::actions::{AIWebSearchAction, GrokAIAction, VRCToggleRedeems};
+use crate::twitch::redeems::actions::{AIWebSearchAction, ChainRedeem, GrokAIAction, OBSSourceRedeem, SuggestionRedeem, TranslateAction, TtsRedeem, VRCToggleRedeems, WorldReactAction};
 use crate::twitch::redeems::registry::RedeemRegistry;
 use crate::twitch::redeems::sync_manager::RedeemSyncManager;
+use crate::twitch::redeems::queue::RedemptionQueue;
+use crate::twitch::redeems::announcements::{self, Outcome};
+use crate::twitch::opt_out;
+use crate::twitch::suggestions::SuggestionKind;
+
+fn redeems_config_path() -> String {
+    crate::paths::resolve(crate::paths::REDEEMS_CONFIG)
+}
 
 pub struct RedeemManager {
     twitch_manager: Arc<TwitchManager>,
@@ -20,6 +29,11 @@ pub struct RedeemManager {
     handlers: HashMap<String, Box<dyn RedeemHandler + Send + Sync>>,
     coin_game_state: Arc<RwLock<CoinGameState>>,
     osc_configs: Arc<RwLock<OSCConfigurations>>,  // Add this line
+    queue: Arc<RedemptionQueue>,
+    obs_source_redeem: Arc<OBSSourceRedeem>,
+    chain_redeem: Arc<ChainRedeem>,
+    // Every reward ID seen on Twitch, so rewards made from the Twitch dashboard can be spotted
+    known_reward_ids: Arc<RwLock<HashSet<String>>>,
 }
 
 impl Clone for RedeemManager {
@@ -33,6 +47,10 @@ impl Clone for RedeemManager {
             handlers: HashMap::new(), // Start with an empty HashMap
             coin_game_state: self.coin_game_state.clone(),
             osc_configs: self.osc_configs.clone(),
+            queue: self.queue.clone(),
+            obs_source_redeem: self.obs_source_redeem.clone(),
+            chain_redeem: self.chain_redeem.clone(),
+            known_reward_ids: self.known_reward_ids.clone(),
         }
     }
 }
@@ -46,6 +64,8 @@ impl RedeemManager {
         let coin_game_state = Arc::new(RwLock::new(CoinGameState::new(20)));
         let vrc_osc_redeems = VRCOscRedeems::new(twitch_manager.clone());
         let osc_configs = twitch_manager.get_osc_configs();
+        let obs_source_redeem = Arc::new(OBSSourceRedeem::new(twitch_manager.get_obs_manager(), registry.clone()));
+        let chain_redeem = Arc::new(ChainRedeem::new(twitch_manager.clone(), registry.clone()));
 
         let mut redeem_manager = Self {
             twitch_manager: twitch_manager.clone(),
@@ -55,6 +75,10 @@ impl RedeemManager {
             handlers: HashMap::new(),
             coin_game_state: coin_game_state.clone(),
             osc_configs,
+            queue: Arc::new(RedemptionQueue::new()),
+            obs_source_redeem,
+            chain_redeem,
+            known_reward_ids: Arc::new(RwLock::new(HashSet::new())),
         };
 
         let redeem_manager_arc = Arc::new(redeem_manager.clone());
@@ -66,11 +90,11 @@ impl RedeemManager {
         );
         handlers.insert(
             "mao mao".to_string(),
-            Box::new(AskAIAction::new(ai_client.clone())) as Box<dyn RedeemHandler + Send + Sync>
+            Box::new(AskAIAction::new(ai_client.clone(), twitch_manager.clone())) as Box<dyn RedeemHandler + Send + Sync>
         );
         handlers.insert(
             "get ai answer".to_string(),
-            Box::new(SeriousAIAction::new(ai_client.clone())) as Box<dyn RedeemHandler + Send + Sync>
+            Box::new(SeriousAIAction::new(ai_client.clone(), twitch_manager.clone())) as Box<dyn RedeemHandler + Send + Sync>
         );
         handlers.insert(
             "toss pillo".to_string(),
@@ -98,7 +122,27 @@ impl RedeemManager {
         );
         handlers.insert(
             "ask grok".to_string(),
-            Box::new(GrokAIAction::new(ai_client.clone())) as Box<dyn RedeemHandler + Send + Sync>
+            Box::new(GrokAIAction::new(ai_client.clone(), twitch_manager.clone())) as Box<dyn RedeemHandler + Send + Sync>
+        );
+        handlers.insert(
+            "translate".to_string(),
+            Box::new(TranslateAction::new(ai_client.clone(), twitch_manager.clone())) as Box<dyn RedeemHandler + Send + Sync>
+        );
+        handlers.insert(
+            "AI reacts to the world".to_string(),
+            Box::new(WorldReactAction::new(ai_client.clone(), twitch_manager.clone())) as Box<dyn RedeemHandler + Send + Sync>
+        );
+        handlers.insert(
+            "tts".to_string(),
+            Box::new(TtsRedeem::new(twitch_manager.clone())) as Box<dyn RedeemHandler + Send + Sync>
+        );
+        handlers.insert(
+            "suggest a title".to_string(),
+            Box::new(SuggestionRedeem::new(twitch_manager.clone(), SuggestionKind::Title)) as Box<dyn RedeemHandler + Send + Sync>
+        );
+        handlers.insert(
+            "suggest tags".to_string(),
+            Box::new(SuggestionRedeem::new(twitch_manager.clone(), SuggestionKind::Tags)) as Box<dyn RedeemHandler + Send + Sync>
         );
         handlers.insert(
             "evil kittyn".to_string(),
@@ -130,10 +174,11 @@ impl RedeemManager {
     pub async fn initialize_redeems(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let initial_configs = self.load_initial_configs().await?;
         let twitch_redeems = self.sync_manager.fetch_all_redeems().await?;
+        self.known_reward_ids.write().await.extend(twitch_redeems.iter().filter_map(|r| r.id.clone()));
 
         for mut config in initial_configs {
             if !config.enabled_games.is_empty() && !config.disabled_games.is_empty() {
-                log::warn!("Conflicting configuration for redeem '{}': both enabled_games and disabled_games are populated. Disabling this redeem.", config.title);
+                tracing::warn!("Conflicting configuration for redeem '{}': both enabled_games and disabled_games are populated. Disabling this redeem.", config.title);
                 config.is_conflicting = true;
                 config.is_enabled = false;
             }
@@ -169,8 +214,7 @@ impl RedeemManager {
     }
 
     async fn load_initial_configs(&self) -> Result<Vec<RedeemInfo>, Box<dyn Error + Send + Sync>> {
-        let config_path = "redeems_config.json";  // You might want to make this configurable
-        let configs = RedeemConfigurations::load(config_path)?;
+        let configs = RedeemConfigurations::load(&redeems_config_path())?;
         Ok(configs.redeems)
     }
 
@@ -187,12 +231,17 @@ impl RedeemManager {
             limit_per_user: local.limit_per_user,
             use_osc: local.use_osc,
             osc_config: local.osc_config,
+            obs_action: local.obs_action,
+            actions: local.actions,
             enabled_games: local.enabled_games,
             disabled_games: local.disabled_games,
             enabled_offline: local.enabled_offline,
             is_conflicting: local.is_conflicting,
             user_input_required: local.user_input_required,
             auto_complete: local.auto_complete,
+            announcements: local.announcements,
+            offline_queue: local.offline_queue,
+            dynamic_pricing: local.dynamic_pricing,
         }
     }
 
@@ -202,7 +251,7 @@ impl RedeemManager {
         let is_live = self.twitch_manager.is_stream_live().await;
 
         for redeem in all_redeems {
-            let should_be_active = redeem.is_active(current_game.as_deref(), is_live);
+            let should_be_active = redeem.is_active(current_game.as_deref(), is_live) && self.twitch_manager.profiles.allows_redeem(&redeem.title);
 
             if should_be_active {
                 if let Some(_id) = redeem.id.as_ref() {
@@ -211,6 +260,7 @@ impl RedeemManager {
                 } else {
                     // Create new redeem
                     let new_id = self.sync_manager.create_redeem(&redeem).await?;
+                    self.known_reward_ids.write().await.insert(new_id.clone());
                     let mut updated_redeem = redeem.clone();
                     updated_redeem.id = Some(new_id);
                     self.registry.add_or_update(redeem.title.clone(), updated_redeem).await;
@@ -227,9 +277,80 @@ impl RedeemManager {
         Ok(())
     }
 
+    // Looks for rewards created outside the bot since the last check and lets the broadcaster know
+    pub async fn update_from_twitch(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let twitch_redeems = self.sync_manager.fetch_all_redeems().await?;
+
+        let new_rewards: Vec<RedeemInfo> = {
+            let mut known = self.known_reward_ids.write().await;
+            twitch_redeems.into_iter()
+                .filter(|r| r.id.as_ref().map_or(false, |id| known.insert(id.clone())))
+                .collect()
+        };
+
+        for reward in new_rewards {
+            info!("Discovered new channel point reward: {} ({} points)", reward.title, reward.cost);
+            self.announce_new_reward(&reward).await;
+        }
+
+        Ok(())
+    }
+
+    async fn announce_new_reward(&self, reward: &RedeemInfo) {
+        let config = &self.twitch_manager.config;
+        let handled = self.handlers.contains_key(&reward.title) || self.registry.get(&reward.title).await.is_some();
+
+        let notification = if handled {
+            format!("New reward \"{}\" ({} points) was created on Twitch", reward.title, reward.cost)
+        } else {
+            format!("New reward \"{}\" ({} points) was created on Twitch and has no bot handler", reward.title, reward.cost)
+        };
+        self.twitch_manager.irc_manager.get_dashboard_state().write().await.add_notification(notification).await;
+
+        if !config.reward_announcements.enabled {
+            return;
+        }
+
+        let mut message = format!("New channel point reward: {} for {} points!", reward.title, reward.cost);
+        if !reward.prompt.is_empty() {
+            message.push_str(&format!(" {}", reward.prompt));
+        }
+
+        if let Some(channel) = &config.twitch_channel_to_join {
+            if let Err(e) = self.twitch_manager.send_message_as_bot(channel, &message).await {
+                error!("Failed to announce new reward in chat: {:?}", e);
+            }
+        }
+
+        let discord_channel = config.reward_announcements.discord_channel_id.as_ref()
+            .or(config.discord_announcement_channel_id.as_ref())
+            .and_then(|id| id.parse::<u64>().ok());
+        if let (Some(discord_client), Some(channel_id)) = (&self.twitch_manager.discord_client, discord_channel) {
+            let http = discord_client.get_http().await;
+            if let Err(e) = ChannelId::new(channel_id).say(&http, format!("✨ {}", message)).await {
+                error!("Failed to announce new reward on Discord: {}", e);
+            }
+        }
+    }
+
+    #[tracing::instrument(name = "redemption", skip_all, fields(reward = %redemption.reward_title, user = %redemption.user_name, request_id = %redemption.id))]
     pub async fn handle_redemption(&self, redemption: &Redemption) -> RedemptionResult {
-        if let Some(handler) = self.handlers.get(&redemption.reward_title) {
+        let configured = self.registry.get(&redemption.reward_title).await;
+        if configured.as_ref().map_or(false, |r| !r.actions.is_empty()) {
+            // An action chain replaces whatever the reward would otherwise do
+            self.chain_redeem.handle(redemption).await
+        } else if let Some(handler) = self.handlers.get(&redemption.reward_title) {
+            if handler.uses_ai() && self.is_opted_out(&redemption.user_id).await {
+                // Left unfulfilled so it lands in the queue for the streamer to refund
+                return RedemptionResult {
+                    success: false,
+                    message: Some(format!("@{}, you've opted out of AI features, use !optin to turn them back on.", redemption.user_name)),
+                };
+            }
             handler.handle(redemption).await
+        } else if configured.map_or(false, |r| r.obs_action.is_some()) {
+            // Rewards with an OBS action in redeems_config.json don't need a dedicated handler
+            self.obs_source_redeem.handle(redemption).await
         } else {
             RedemptionResult {
                 success: false,
@@ -238,6 +359,158 @@ impl RedeemManager {
         }
     }
 
+    async fn is_opted_out(&self, user_id: &str) -> bool {
+        match self.twitch_manager.get_storage() {
+            Some(storage) => opt_out::is_opted_out(&*storage.read().await, user_id),
+            None => false,
+        }
+    }
+
+    // Configured rewards and their action chains, for the dashboard chain editor
+    pub async fn action_chains(&self) -> Vec<(String, Vec<ChainStep>)> {
+        let mut chains: Vec<(String, Vec<ChainStep>)> = self.registry.get_all().await.into_iter()
+            .map(|redeem| (redeem.title, redeem.actions))
+            .collect();
+        chains.sort_by_key(|(title, _)| title.to_lowercase());
+        chains
+    }
+
+    // Replaces a reward's action chain in redeems_config.json and in the running registry.
+    // An empty chain turns the reward back into a normal one.
+    pub async fn save_action_chain(&self, title: &str, actions: Vec<ChainStep>) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut redeem = self.registry.get(title).await
+            .ok_or_else(|| format!("No configured reward named '{}'", title))?;
+
+        let mut configs = RedeemConfigurations::load(&redeems_config_path())?;
+        let stored = configs.redeems.iter_mut().find(|r| r.title == title)
+            .ok_or_else(|| format!("{} isn't in {}", title, redeems_config_path()))?;
+        stored.actions = actions.clone();
+        configs.save(&redeems_config_path())?;
+
+        info!("Saved {} step action chain for {}", actions.len(), title);
+        redeem.actions = actions;
+        self.registry.add_or_update(title.to_string(), redeem).await;
+        Ok(())
+    }
+
+    // Configured rewards and their announcement templates, for the dashboard
+    pub async fn announcement_templates(&self) -> Vec<(String, RedeemAnnouncements)> {
+        let mut templates: Vec<(String, RedeemAnnouncements)> = self.registry.get_all().await.into_iter()
+            .map(|redeem| (redeem.title, redeem.announcements))
+            .collect();
+        templates.sort_by_key(|(title, _)| title.to_lowercase());
+        templates
+    }
+
+    // Dynamically priced rewards with their current cost, for the dashboard
+    pub async fn dynamic_prices(&self) -> Vec<(String, u32, DynamicPricing)> {
+        let mut prices: Vec<(String, u32, DynamicPricing)> = self.registry.get_all().await.into_iter()
+            .filter_map(|redeem| redeem.dynamic_pricing.map(|pricing| (redeem.title, redeem.cost, pricing)))
+            .collect();
+        prices.sort_by_key(|(title, _, _)| title.to_lowercase());
+        prices
+    }
+
+    // Saves a reward's announcement templates to redeems_config.json and the running registry
+    pub async fn save_announcements(&self, title: &str, announcements: RedeemAnnouncements) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut redeem = self.registry.get(title).await
+            .ok_or_else(|| format!("No configured reward named '{}'", title))?;
+
+        let mut configs = RedeemConfigurations::load(&redeems_config_path())?;
+        let stored = configs.redeems.iter_mut().find(|r| r.title == title)
+            .ok_or_else(|| format!("{} isn't in {}", title, redeems_config_path()))?;
+        stored.announcements = announcements.clone();
+        configs.save(&redeems_config_path())?;
+
+        info!("Saved announcement templates for {}", title);
+        redeem.announcements = announcements;
+        self.registry.add_or_update(title.to_string(), redeem).await;
+        Ok(())
+    }
+
+    // Posts how a redemption went, using the reward's templates or the config defaults.
+    // Every outcome of a real redemption goes through here so they read the same.
+    pub async fn announce(
+        &self,
+        channel: &str,
+        redemption: &Redemption,
+        outcome: Outcome,
+        message: Option<&str>,
+        queued: Option<(u32, usize)>,
+    ) {
+        let defaults = &self.twitch_manager.config.redemption_announcements;
+        if !defaults.enabled {
+            return;
+        }
+        let reward = self.registry.get(&redemption.reward_title).await.map(|r| r.announcements).unwrap_or_default();
+        if let Some(text) = announcements::render(&reward, defaults, outcome, redemption, message, queued) {
+            if let Err(e) = self.twitch_manager.send_message_as_bot(channel, &text).await {
+                error!("Failed to announce {:?} redemption of {}: {:?}", outcome, redemption.reward_title, e);
+            }
+        }
+    }
+
+    // Which path handle_redemption would take for the reward, for the event inspector
+    pub async fn describe_route(&self, reward_title: &str) -> Option<String> {
+        let configured = self.registry.get(reward_title).await;
+        match &configured {
+            Some(redeem) if !redeem.actions.is_empty() => Some(format!("action chain with {} steps", redeem.actions.len())),
+            _ if self.handlers.contains_key(reward_title) => Some("dedicated handler".to_string()),
+            Some(redeem) if redeem.obs_action.is_some() => Some("OBS source action".to_string()),
+            _ => None,
+        }
+    }
+
+    // Every reward title that can be dry-run with test_redemption
+    pub async fn testable_rewards(&self) -> Vec<String> {
+        let mut titles: Vec<String> = self.handlers.keys()
+            .filter(|title| title.as_str() != "Coin Game")
+            .cloned()
+            .collect();
+        for redeem in self.registry.get_all().await {
+            if (redeem.obs_action.is_some() || !redeem.actions.is_empty()) && !titles.contains(&redeem.title) {
+                titles.push(redeem.title);
+            }
+        }
+        titles.sort_by_key(|title| title.to_lowercase());
+        titles
+    }
+
+    // Runs a reward's handler with a made-up redemption. Nothing is logged, queued,
+    // completed or refunded, so OSC sequences and AI prompts can be checked offline.
+    pub async fn test_redemption(&self, reward_title: &str, user_name: &str, user_input: Option<String>) -> RedemptionResult {
+        let title = match self.testable_rewards().await.into_iter().find(|t| t.eq_ignore_ascii_case(reward_title)) {
+            Some(title) => title,
+            None if reward_title.eq_ignore_ascii_case("Coin Game") => {
+                return RedemptionResult {
+                    success: false,
+                    message: Some("The coin game changes the reward on Twitch and can't be tested".to_string()),
+                };
+            }
+            None => {
+                return RedemptionResult {
+                    success: false,
+                    message: Some(format!("No testable reward named '{}'", reward_title)),
+                };
+            }
+        };
+
+        let reward_id = self.registry.get(&title).await.and_then(|r| r.id).unwrap_or_default();
+        let redemption = Redemption {
+            id: format!("test-{}", uuid::Uuid::new_v4()),
+            broadcaster_id: self.twitch_manager.config.twitch_user_id.clone().unwrap_or_default(),
+            user_id: String::new(),
+            user_name: user_name.to_string(),
+            reward_id,
+            reward_title: title,
+            user_input,
+            status: RedemptionStatus::Unfulfilled,
+        };
+
+        info!("Test redemption of '{}' by {}", redemption.reward_title, user_name);
+        self.handle_redemption(&redemption).await
+    }
+
     pub async fn handle_stream_online(&self, game: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Handling stream online event. Game: {}", game);
 
@@ -254,6 +527,12 @@ impl RedeemManager {
         Ok(())
     }
 
+    // What the coin game reward costs right now; 0 while the game isn't running
+    pub async fn coin_game_price(&self) -> u32 {
+        let coin_game_state = self.coin_game_state.read().await;
+        if coin_game_state.is_active { coin_game_state.current_price } else { 0 }
+    }
+
     pub async fn handle_stream_offline(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Handling stream offline event");
 
@@ -270,10 +549,45 @@ impl RedeemManager {
         // Existing code to disable Coin Game
         let mut coin_game_state = self.coin_game_state.write().await;
         coin_game_state.is_active = false;
+        drop(coin_game_state);
+
+        self.flush_queue().await;
 
         Ok(())
     }
 
+    // Refunds whatever is still queued at the end of stream, unless the reward holds it for next time
+    async fn flush_queue(&self) {
+        let entries = self.queue.get_all().await;
+        if entries.is_empty() {
+            return;
+        }
+        let channel = self.twitch_manager.config.twitch_channel_to_join.clone().unwrap_or_default();
+        let mut refunded = 0;
+        let mut held = 0;
+
+        for entry in entries {
+            let redemption = &entry.redemption;
+            let policy = self.registry.get(&redemption.reward_title).await.map(|r| r.offline_queue).unwrap_or_default();
+            if policy == OfflineQueuePolicy::Hold {
+                held += 1;
+                continue;
+            }
+
+            let result = self.twitch_manager.api_client.refund_channel_points(&redemption.reward_id, &redemption.id).await;
+            // Rewards this app didn't create can't be refunded from here, so those stay queued
+            if let Err(e) = result {
+                warn!("Couldn't refund queued {} from {}, leaving it queued: {:?}", redemption.reward_title, redemption.user_name, e);
+                held += 1;
+                continue;
+            }
+            self.queue.remove(&redemption.id).await;
+            self.announce(&channel, redemption, Outcome::Refunded, None, None).await;
+            refunded += 1;
+        }
+        info!("Stream ended with queued redemptions: refunded {}, kept {}", refunded, held);
+    }
+
     pub async fn handle_stream_update(&self, game_name: String) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Handling stream update event. New game: {}", game_name);
 
@@ -290,4 +604,8 @@ impl RedeemManager {
     pub fn get_ai_client(&self) -> Arc<AIClient> {
         self.ai_client.clone()
     }
-}
\ No newline at end of file
+
+    pub fn get_queue(&self) -> Arc<RedemptionQueue> {
+        self.queue.clone()
+    }
+}
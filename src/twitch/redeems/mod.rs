@@ -1,7 +1,11 @@
-mod manager;
+pub(crate) mod manager;
 mod actions;
 mod registry;
 mod sync_manager;
+pub(crate) mod queue;
+pub(crate) mod announcements;
+pub(crate) mod pricing;
 
 pub use manager::RedeemManager;
+pub use queue::{QueuedRedemption, RedemptionQueue};
 pub use self::actions::{AskAIAction, CoinGameAction, SeriousAIAction, VRCOscRedeems};
\ No newline at end of file
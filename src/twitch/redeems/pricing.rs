@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+use crate::twitch::happy_hour;
+use crate::twitch::models::DynamicPricing;
+use crate::twitch::redeems::RedeemManager;
+use crate::twitch::TwitchManager;
+
+const DECAY_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// A dynamically priced reward that's currently above its floor
+struct PriceState {
+    floor: u32,
+    cost: u32,
+    last_change: Instant,
+}
+
+// Current prices of the rewards above their floor, by title; owned by TwitchManager
+#[derive(Default)]
+pub struct RewardPrices {
+    prices: Mutex<HashMap<String, PriceState>>,
+}
+
+impl RewardPrices {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// Happy hour owns a reward's cost until it ends
+fn in_happy_hour(twitch_manager: &TwitchManager, title: &str) -> bool {
+    happy_hour::current(twitch_manager).map_or(false, |happy_hour| happy_hour.rewards.iter().any(|reward| reward.title == title))
+}
+
+// Sets the cost on Twitch and in the registry so reward syncs keep it, and adds it to the audit trail
+async fn set_cost(twitch_manager: &TwitchManager, redeem_manager: &RedeemManager, title: &str, reward_id: &str, old_cost: u32, cost: u32, reason: &str) {
+    if let Err(e) = redeem_manager.get_api_client().update_custom_reward_cost(reward_id, cost).await {
+        error!("Failed to set the cost of {} to {}: {:?}", title, cost, e);
+        return;
+    }
+    if let Some(mut info) = redeem_manager.registry.get(title).await {
+        info.cost = cost;
+        redeem_manager.registry.add_or_update(title.to_string(), info).await;
+    }
+    if let Some(storage) = twitch_manager.get_storage() {
+        if let Err(e) = storage.read().await.log_reward_price_change(title, old_cost, cost, reason) {
+            error!("Failed to log the price change of {}: {:?}", title, e);
+        }
+    }
+    info!("{} now costs {} (was {}, {})", title, cost, old_cost, reason);
+}
+
+// Raises the reward's cost by its step, up to the ceiling
+pub async fn on_redemption(twitch_manager: &TwitchManager, redeem_manager: &RedeemManager, title: &str) {
+    let info = match redeem_manager.registry.get(title).await {
+        Some(info) => info,
+        None => return,
+    };
+    let (pricing, reward_id) = match (&info.dynamic_pricing, &info.id) {
+        (Some(pricing), Some(reward_id)) => (pricing, reward_id),
+        _ => return,
+    };
+    if in_happy_hour(twitch_manager, title) {
+        return;
+    }
+
+    let (old_cost, cost) = {
+        let mut prices = twitch_manager.reward_prices.prices.lock().unwrap();
+        let state = prices.entry(title.to_string()).or_insert_with(|| {
+            let floor = pricing.floor.unwrap_or(info.cost);
+            PriceState { floor, cost: info.cost.max(floor), last_change: Instant::now() }
+        });
+        let old_cost = state.cost;
+        state.cost = state.cost.saturating_add(pricing.step).min(pricing.ceiling.max(state.floor));
+        state.last_change = Instant::now();
+        (old_cost, state.cost)
+    };
+    if cost != old_cost {
+        set_cost(twitch_manager, redeem_manager, title, reward_id, old_cost, cost, "redeemed").await;
+    }
+}
+
+// The next lower cost if the reward has gone a full interval without a redemption.
+// Rewards that are back at their floor stop being tracked.
+fn decay(reward_prices: &RewardPrices, title: &str, pricing: &DynamicPricing) -> Option<(u32, u32)> {
+    let mut prices = reward_prices.prices.lock().unwrap();
+    let state = prices.get_mut(title)?;
+    if state.last_change.elapsed() < Duration::from_secs(pricing.decay_interval_secs) {
+        return None;
+    }
+    let old_cost = state.cost;
+    state.cost = state.cost.saturating_sub(pricing.decay_step.max(1)).max(state.floor);
+    state.last_change = Instant::now();
+    let cost = state.cost;
+    if cost == state.floor {
+        prices.remove(title);
+    }
+    Some((old_cost, cost))
+}
+
+pub async fn run_decay_ticker(twitch_manager: Arc<TwitchManager>) {
+    info!("Starting reward price decay ticker");
+    let mut interval = tokio::time::interval(DECAY_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+        let redeem_manager = twitch_manager.redeem_manager.read().await;
+        let redeem_manager = match redeem_manager.as_ref() {
+            Some(redeem_manager) => redeem_manager,
+            None => continue,
+        };
+
+        for reward in redeem_manager.registry.get_all().await {
+            let (pricing, reward_id) = match (&reward.dynamic_pricing, &reward.id) {
+                (Some(pricing), Some(reward_id)) => (pricing, reward_id),
+                _ => continue,
+            };
+            if in_happy_hour(&twitch_manager, &reward.title) {
+                continue;
+            }
+            if let Some((old_cost, cost)) = decay(&twitch_manager.reward_prices, &reward.title, pricing) {
+                if cost != old_cost {
+                    set_cost(&twitch_manager, redeem_manager, &reward.title, reward_id, old_cost, cost, "decay").await;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use tracing::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::{Notify, RwLock};
+use tokio::time::Duration;
+use crate::osc::VRChatOSC;
+use crate::twitch::models::Redemption;
+use crate::web_ui::websocket::{DashboardState, WebSocketMessage};
+
+const TICKER_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedRedemption {
+    pub number: u32,
+    pub redemption: Redemption,
+}
+
+impl QueuedRedemption {
+    pub fn display(&self) -> String {
+        format!("Next up: #{} {} from {}", self.number, self.redemption.reward_title, self.redemption.user_name)
+    }
+}
+
+pub struct RedemptionQueue {
+    entries: RwLock<VecDeque<QueuedRedemption>>,
+    next_number: AtomicU32,
+    // Wakes the ticker so the dashboard sees changes straight away
+    changed: Notify,
+}
+
+impl Default for RedemptionQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RedemptionQueue {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(VecDeque::new()),
+            next_number: AtomicU32::new(1),
+            changed: Notify::new(),
+        }
+    }
+
+    pub async fn push(&self, redemption: Redemption) -> u32 {
+        let number = self.next_number.fetch_add(1, Ordering::SeqCst);
+        debug!("Queueing redemption #{}: {} from {}", number, redemption.reward_title, redemption.user_name);
+        self.entries.write().await.push_back(QueuedRedemption { number, redemption });
+        self.changed.notify_one();
+        number
+    }
+
+    pub async fn remove(&self, redemption_id: &str) -> bool {
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|entry| entry.redemption.id != redemption_id);
+        let removed = before != entries.len();
+        if removed {
+            self.changed.notify_one();
+        }
+        removed
+    }
+
+    // Moves an entry to `position` (0 is next up), clamped to the end of the queue.
+    // Returns false if the redemption isn't queued.
+    pub async fn move_to(&self, redemption_id: &str, position: usize) -> bool {
+        let mut entries = self.entries.write().await;
+        let entry = match entries.iter().position(|entry| entry.redemption.id == redemption_id) {
+            Some(index) => entries.remove(index),
+            None => None,
+        };
+        match entry {
+            Some(entry) => {
+                let position = position.min(entries.len());
+                entries.insert(position, entry);
+                self.changed.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // To the front, ahead of everything else
+    pub async fn bump(&self, redemption_id: &str) -> bool {
+        self.move_to(redemption_id, 0).await
+    }
+
+    // To the back; it stays unresolved on Twitch, just out of the way
+    pub async fn skip(&self, redemption_id: &str) -> bool {
+        self.move_to(redemption_id, usize::MAX).await
+    }
+
+    pub async fn head(&self) -> Option<QueuedRedemption> {
+        self.entries.read().await.front().cloned()
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+
+    pub async fn get_all(&self) -> Vec<QueuedRedemption> {
+        self.entries.read().await.iter().cloned().collect()
+    }
+}
+
+pub async fn run_queue_ticker(
+    queue: Arc<RedemptionQueue>,
+    vrchat_osc: Arc<VRChatOSC>,
+    dashboard_state: Arc<RwLock<DashboardState>>,
+) {
+    info!("Starting redeem queue ticker");
+    let mut interval = tokio::time::interval(TICKER_INTERVAL);
+    let mut was_showing = false;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = queue.changed.notified() => {
+                publish(&queue, &dashboard_state).await;
+            }
+        }
+
+        let head = queue.head().await;
+        let pending = queue.len().await;
+
+        match head {
+            Some(entry) => {
+                let text = entry.display();
+                if let Err(e) = vrchat_osc.send_chatbox_message(&text, true, false).await {
+                    warn!("Failed to send redeem queue ticker to chatbox: {:?}", e);
+                }
+                dashboard_state.write().await.update_redeem_queue(Some(text), pending).await;
+                was_showing = true;
+            }
+            None if was_showing => {
+                if let Err(e) = vrchat_osc.send_chatbox_message("", true, false).await {
+                    warn!("Failed to clear redeem queue ticker from chatbox: {:?}", e);
+                }
+                dashboard_state.write().await.update_redeem_queue(None, 0).await;
+                was_showing = false;
+            }
+            None => {}
+        }
+    }
+}
+
+// The whole queue in order, for the dashboard's reorderable list
+async fn publish(queue: &RedemptionQueue, dashboard_state: &Arc<RwLock<DashboardState>>) {
+    let message = WebSocketMessage {
+        module: "redeem_queue".to_string(),
+        action: "update".to_string(),
+        data: json!({ "entries": queue.get_all().await }),
+    };
+    if let Err(e) = dashboard_state.read().await.broadcast_message(message).await {
+        warn!("Failed to send the redeem queue to the dashboard: {}", e);
+    }
+}
@@ -18,7 +18,6 @@ impl RedeemRegistry {
         redeems.insert(title, info);
     }
 
-    #[allow(dead_code)]
     pub async fn get(&self, title: &str) -> Option<RedeemInfo> {
         let redeems = self.redeems.read().await;
         redeems.get(title).cloned()
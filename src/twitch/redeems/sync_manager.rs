@@ -1,6 +1,6 @@
 use crate::twitch::TwitchAPIClient;
 use std::sync::Arc;
-use crate::twitch::models::RedeemInfo;
+use crate::twitch::models::{OfflineQueuePolicy, RedeemAnnouncements, RedeemInfo};
 
 pub struct RedeemSyncManager {
     api_client: Arc<TwitchAPIClient>,
@@ -27,12 +27,17 @@ impl RedeemSyncManager {
                 limit_per_user: reward.max_per_user_per_stream.map(|mpups| mpups.max_per_user_per_stream),
                 use_osc: false, // This information isn't available from the API, so we'll need to merge with local config
                 osc_config: None,
+                obs_action: None,
+                actions: Vec::new(),
                 enabled_games: Vec::new(),
                 disabled_games: Vec::new(),
                 enabled_offline: false,
                 is_conflicting: false,
                 user_input_required: reward.is_user_input_required,
                 auto_complete: false,
+                announcements: RedeemAnnouncements::default(),
+                offline_queue: OfflineQueuePolicy::default(),
+                dynamic_pricing: None,
             })
             .collect())
     }
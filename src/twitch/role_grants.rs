@@ -0,0 +1,183 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Duration, Utc};
+use tracing::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use crate::twitch::TwitchManager;
+
+// Every grant that hasn't been taken back yet, so revokes still happen after a restart
+fn state_path() -> String {
+    crate::paths::resolve(crate::paths::ROLE_GRANTS)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GrantedRole {
+    Vip,
+    Moderator,
+}
+
+impl fmt::Display for GrantedRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GrantedRole::Vip => write!(f, "VIP"),
+            GrantedRole::Moderator => write!(f, "mod"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleGrant {
+    pub user_id: String,
+    pub login: String,
+    pub role: GrantedRole,
+    pub expires_at: DateTime<Utc>,
+}
+
+// The timed grants still running, owned by TwitchManager
+#[derive(Debug, Default)]
+pub struct RoleGrants {
+    grants: Mutex<Vec<RoleGrant>>,
+}
+
+impl RoleGrants {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn list(&self) -> Vec<RoleGrant> {
+        self.grants.lock().unwrap().clone()
+    }
+
+    fn find(&self, login: &str, role: GrantedRole) -> Option<RoleGrant> {
+        self.grants.lock().unwrap().iter()
+            .find(|grant| grant.role == role && grant.login.eq_ignore_ascii_case(login))
+            .cloned()
+    }
+
+    fn forget(&self, user_id: &str, role: GrantedRole) {
+        let mut grants = self.grants.lock().unwrap();
+        grants.retain(|grant| !(grant.user_id == user_id && grant.role == role));
+        save(&grants);
+    }
+}
+
+fn save(grants: &[RoleGrant]) {
+    let result = serde_json::to_string_pretty(grants)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(state_path(), json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Failed to update {}: {}", state_path(), e);
+    }
+}
+
+fn schedule_revoke(twitch_manager: Arc<TwitchManager>, grant: RoleGrant) {
+    let tasks = twitch_manager.tasks.clone();
+    tasks.spawn(&format!("role_revoke_{}_{}", grant.login, grant.role), async move {
+        let wait = (grant.expires_at - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+        // It may have been extended or revoked by hand since
+        let still_due = twitch_manager.role_grants.find(&grant.login, grant.role).map_or(false, |current| current.expires_at == grant.expires_at);
+        if still_due {
+            if let Err(e) = revoke(&twitch_manager, &grant.login, grant.role).await {
+                error!("Failed to take {} back from {}: {}", grant.role, grant.login, e);
+            }
+        }
+    });
+}
+
+// Grants the role until `duration` from now. Running it again for someone who already has a
+// timed grant moves the expiry; people who have the role for good are left alone, so the
+// timer never takes away a role the bot didn't give.
+pub async fn grant(twitch_manager: Arc<TwitchManager>, login: &str, role: GrantedRole, duration: Duration) -> Result<RoleGrant, String> {
+    let login = login.trim_start_matches('@').to_lowercase();
+    let api_client = &twitch_manager.api_client;
+    let broadcaster_id = api_client.get_broadcaster_id().await.map_err(|e| e.to_string())?;
+    let user = api_client.get_user_info(&login).await.map_err(|e| e.to_string())?;
+    let user_id = user["data"][0]["id"].as_str().ok_or_else(|| format!("There's no Twitch user called {}", login))?.to_string();
+
+    let existing = twitch_manager.role_grants.find(&login, role);
+    if existing.is_none() {
+        let has_role = match role {
+            GrantedRole::Vip => api_client.check_user_vip(&broadcaster_id, &user_id).await,
+            GrantedRole::Moderator => api_client.check_user_mod(&broadcaster_id, &user_id).await,
+        };
+        if has_role.map_err(|e| e.to_string())? {
+            return Err(format!("{} is already a {}", login, role));
+        }
+        let result = match role {
+            GrantedRole::Vip => api_client.add_channel_vip(&broadcaster_id, &user_id).await,
+            GrantedRole::Moderator => api_client.add_channel_moderator(&broadcaster_id, &user_id).await,
+        };
+        result.map_err(|e| e.to_string())?;
+    }
+
+    let grant = RoleGrant {
+        user_id,
+        login,
+        role,
+        expires_at: Utc::now() + duration,
+    };
+    {
+        let mut grants = twitch_manager.role_grants.grants.lock().unwrap();
+        grants.retain(|other| !(other.user_id == grant.user_id && other.role == role));
+        grants.push(grant.clone());
+        save(&grants);
+    }
+    info!("Granted {} to {} until {}", role, grant.login, grant.expires_at);
+    schedule_revoke(twitch_manager, grant.clone());
+    Ok(grant)
+}
+
+// Takes a timed role back now; Ok(None) when the bot never granted it
+pub async fn revoke(twitch_manager: &TwitchManager, login: &str, role: GrantedRole) -> Result<Option<RoleGrant>, String> {
+    let grant = match twitch_manager.role_grants.find(login.trim_start_matches('@'), role) {
+        Some(grant) => grant,
+        None => return Ok(None),
+    };
+    let api_client = &twitch_manager.api_client;
+    let broadcaster_id = api_client.get_broadcaster_id().await.map_err(|e| e.to_string())?;
+    let result = match role {
+        GrantedRole::Vip => api_client.remove_channel_vip(&broadcaster_id, &grant.user_id).await,
+        GrantedRole::Moderator => api_client.remove_channel_moderator(&broadcaster_id, &grant.user_id).await,
+    };
+    // Someone may have removed the role by hand already; the grant is done either way
+    if let Err(e) = result {
+        warn!("Removing {} from {} failed, forgetting the grant anyway: {}", role, grant.login, e);
+    }
+    twitch_manager.role_grants.forget(&grant.user_id, role);
+    info!("Took {} back from {}", role, grant.login);
+    Ok(Some(grant))
+}
+
+// On startup: revokes grants that ran out while the bot was down and waits out the rest
+pub async fn resume(twitch_manager: Arc<TwitchManager>) {
+    let grants: Vec<RoleGrant> = match std::fs::read_to_string(state_path()) {
+        Ok(contents) => match serde_json::from_str(&contents) {
+            Ok(grants) => grants,
+            Err(e) => {
+                error!("Ignoring unreadable {}: {}", state_path(), e);
+                return;
+            }
+        },
+        Err(_) => return,
+    };
+    if grants.is_empty() {
+        return;
+    }
+
+    info!("Resuming {} timed role grants", grants.len());
+    *twitch_manager.role_grants.grants.lock().unwrap() = grants.clone();
+    for grant in grants {
+        schedule_revoke(twitch_manager.clone(), grant);
+    }
+}
+
+pub fn describe(grant: &RoleGrant) -> String {
+    let minutes = (grant.expires_at - Utc::now()).num_minutes().max(1);
+    if minutes >= 60 {
+        format!("{} ({}h{}m left)", grant.login, minutes / 60, minutes % 60)
+    } else {
+        format!("{} ({}m left)", grant.login, minutes)
+    }
+}
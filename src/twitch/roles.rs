@@ -1,11 +1,13 @@
 use std::fmt;
 use std::str::FromStr;
 use std::cmp::Ordering;
-use log::{debug, error};
+use tracing::{debug, error};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use twitch_irc::message::Badge;
 use crate::twitch::manager::TwitchManager;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum UserRole {
     Viewer,
     Subscriber,
@@ -75,6 +77,15 @@ impl FromStr for UserRole {
     }
 }
 
+// Badge-based checks for the sub and VIP command tiers, which don't follow the role hierarchy
+pub fn is_subscriber(badges: &[Badge]) -> bool {
+    badges.iter().any(|b| b.name == "subscriber" || b.name == "founder")
+}
+
+pub fn is_vip(badges: &[Badge]) -> bool {
+    badges.iter().any(|b| b.name == "vip")
+}
+
 pub async fn get_user_role(
     user_id: &str,
     twitch_manager: &TwitchManager,
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Duration};
+use tracing::{error, info, warn};
+use crate::config::SoundboardConfig;
+use crate::obs::OBSManager;
+use crate::twitch::models::OBSSourceAction;
+use crate::twitch::redeems::actions::OBSSourceRedeem;
+use crate::web_ui::websocket::{DashboardState, WebSocketMessage};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SoundPlayback {
+    // A media source shown for duration_secs, then hidden again for the next play
+    Obs(OBSSourceAction),
+    // A file in the sounds folder, played by browser sources with /soundboard/overlay open
+    Overlay { file: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Sound {
+    // What chat types after !sound; matched case-insensitively
+    pub name: String,
+    pub playback: SoundPlayback,
+    // Drop points !sound takes; redeems are paid for with channel points instead
+    #[serde(default)]
+    pub cost: u64,
+    #[serde(default)]
+    pub cooldown_secs: u64,
+    // Measured RMS loudness of the clip in dBFS, used to bring it to the configured target
+    #[serde(default)]
+    pub loudness_db: Option<f64>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub times_played: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Sound {
+    // Gain that brings the clip to the target loudness; unmeasured clips play as they are
+    pub fn gain_db(&self, config: &SoundboardConfig) -> f64 {
+        self.loudness_db.map_or(0.0, |loudness| config.target_loudness_db - loudness)
+    }
+}
+
+// A sound as the dashboard shows it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SoundStatus {
+    #[serde(flatten)]
+    pub sound: Sound,
+    pub cooldown_remaining_secs: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SoundboardFile {
+    sounds: Vec<Sound>,
+}
+
+struct SoundboardStore {
+    path: String,
+    file: SoundboardFile,
+    // Lowercased sound name -> when it last played
+    last_played: HashMap<String, Instant>,
+    last_any: Option<Instant>,
+}
+
+// The sounds and their cooldowns, owned by TwitchManager
+pub struct Soundboard {
+    store: Mutex<SoundboardStore>,
+}
+
+fn save(store: &SoundboardStore) {
+    let result = serde_json::to_string_pretty(&store.file)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&store.path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!("Failed to update {}: {}", store.path, e);
+    }
+}
+
+fn remaining(last: Option<&Instant>, cooldown_secs: u64) -> u64 {
+    last.map_or(0, |last| cooldown_secs.saturating_sub(last.elapsed().as_secs()))
+}
+
+// Where sounds are played: OBS for media sources, the dashboard websocket for the overlay
+#[derive(Clone)]
+pub struct Players {
+    pub obs_manager: Option<Arc<OBSManager>>,
+    pub dashboard_state: Arc<RwLock<DashboardState>>,
+}
+
+impl Soundboard {
+    pub fn new(path: &str) -> Self {
+        Self {
+            store: Mutex::new(SoundboardStore {
+                path: path.to_string(),
+                file: SoundboardFile::default(),
+                last_played: HashMap::new(),
+                last_any: None,
+            }),
+        }
+    }
+
+    pub fn load(path: &str) -> Self {
+        let file = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                error!("Failed to parse {}, starting with an empty soundboard: {}", path, e);
+                SoundboardFile::default()
+            }),
+            Err(_) => SoundboardFile::default(),
+        };
+        let soundboard = Self::new(path);
+        soundboard.store.lock().unwrap().file = file;
+        soundboard
+    }
+
+    pub fn list(&self) -> Vec<SoundStatus> {
+        let store = self.store.lock().unwrap();
+        store.file.sounds.iter()
+            .map(|sound| SoundStatus {
+                cooldown_remaining_secs: remaining(store.last_played.get(&sound.name.to_lowercase()), sound.cooldown_secs),
+                sound: sound.clone(),
+            })
+            .collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<Sound> {
+        self.store.lock().unwrap().file.sounds.iter().find(|sound| sound.name.eq_ignore_ascii_case(name)).cloned()
+    }
+
+    // Adds the sound, or replaces the one with the same name (keeping its play count)
+    pub fn upsert(&self, mut sound: Sound) -> Result<(), String> {
+        sound.name = sound.name.trim().to_lowercase();
+        if sound.name.is_empty() || sound.name.contains(char::is_whitespace) {
+            return Err("Sound names have to be a single word".to_string());
+        }
+        if let SoundPlayback::Overlay { file } = &sound.playback {
+            // Served straight out of the sounds folder, so no paths
+            if file.is_empty() || file.contains(|c: char| c == '/' || c == '\\') || file.contains("..") {
+                return Err("Overlay sounds need a plain file name from the sounds folder".to_string());
+            }
+        }
+
+        let mut store = self.store.lock().unwrap();
+        match store.file.sounds.iter_mut().find(|existing| existing.name == sound.name) {
+            Some(existing) => {
+                sound.times_played = existing.times_played;
+                *existing = sound;
+            }
+            None => store.file.sounds.push(sound),
+        }
+        save(&store);
+        Ok(())
+    }
+
+    pub fn remove(&self, name: &str) -> Option<Sound> {
+        let mut store = self.store.lock().unwrap();
+        let index = store.file.sounds.iter().position(|sound| sound.name.eq_ignore_ascii_case(name))?;
+        let removed = store.file.sounds.remove(index);
+        store.last_played.remove(&removed.name);
+        save(&store);
+        Some(removed)
+    }
+
+    // Plays the sound unless it's disabled or on cooldown; Err is the reason it didn't play.
+    // With `ignore_cooldowns`, for the dashboard's test button, cooldowns aren't checked or started.
+    pub async fn play(&self, players: &Players, config: &SoundboardConfig, name: &str, ignore_cooldowns: bool) -> Result<Sound, String> {
+        let sound = {
+            let mut store = self.store.lock().unwrap();
+            let sound = store.file.sounds.iter().find(|sound| sound.name.eq_ignore_ascii_case(name)).cloned()
+                .ok_or_else(|| format!("There's no sound called {}", name))?;
+            if !sound.enabled {
+                return Err(format!("{} is turned off", sound.name));
+            }
+            if !ignore_cooldowns {
+                let wait = remaining(store.last_played.get(&sound.name), sound.cooldown_secs)
+                    .max(remaining(store.last_any.as_ref(), config.global_cooldown_secs));
+                if wait > 0 {
+                    return Err(format!("{} can play again in {}s", sound.name, wait));
+                }
+                store.last_played.insert(sound.name.clone(), Instant::now());
+                store.last_any = Some(Instant::now());
+            }
+            sound
+        };
+
+        if let Err(e) = output(players, config, &sound).await {
+            // Didn't play, so it shouldn't hold up the next one
+            if !ignore_cooldowns {
+                let mut store = self.store.lock().unwrap();
+                store.last_played.remove(&sound.name);
+                store.last_any = None;
+            }
+            return Err(e);
+        }
+
+        {
+            let mut store = self.store.lock().unwrap();
+            if let Some(stored) = store.file.sounds.iter_mut().find(|stored| stored.name == sound.name) {
+                stored.times_played += 1;
+            }
+            save(&store);
+        }
+        info!("Played sound {}", sound.name);
+        Ok(sound)
+    }
+}
+
+async fn output(players: &Players, config: &SoundboardConfig, sound: &Sound) -> Result<(), String> {
+    let gain_db = sound.gain_db(config);
+    match &sound.playback {
+        SoundPlayback::Obs(action) => {
+            let obs_manager = match &players.obs_manager {
+                Some(obs_manager) if obs_manager.is_connected().await => obs_manager.clone(),
+                _ => return Err("OBS is not connected".to_string()),
+            };
+            if sound.loudness_db.is_some() {
+                if let Err(e) = obs_manager.set_input_volume(&action.instance_name, &action.source_name, gain_db).await {
+                    warn!("Failed to set the volume of {}: {}", action.source_name, e);
+                }
+            }
+            OBSSourceRedeem::apply(&obs_manager, action, true).await.map_err(|e| e.to_string())?;
+            let action = action.clone();
+            tokio::spawn(async move {
+                sleep(Duration::from_secs(action.duration_secs)).await;
+                if let Err(e) = OBSSourceRedeem::apply(&obs_manager, &action, false).await {
+                    error!("Failed to hide sound source {}: {:?}", action.source_name, e);
+                }
+            });
+            Ok(())
+        }
+        SoundPlayback::Overlay { file } => {
+            // Browsers can't play louder than the file, so quiet clips top out at full volume
+            let volume = 10f64.powf(gain_db / 20.0).min(1.0);
+            let message = WebSocketMessage {
+                module: "soundboard".to_string(),
+                action: "play".to_string(),
+                data: json!({ "name": sound.name, "url": format!("/sounds/{}", file), "volume": volume }),
+            };
+            match players.dashboard_state.read().await.broadcast_message(message).await {
+                Ok(0) => Err("No soundboard overlay is open".to_string()),
+                Ok(_) => Ok(()),
+                Err(e) => Err(format!("Failed to reach the overlay: {}", e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use tracing::{error, warn};
+use serde_json::json;
+use crate::stream_state::UptimeSummary;
+use crate::twitch::TwitchManager;
+use crate::web_ui::websocket::WebSocketMessage;
+
+pub fn format_duration(seconds: i64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+pub fn describe(summary: &UptimeSummary) -> String {
+    if summary.on_break {
+        match &summary.break_reason {
+            Some(reason) => format!("On a break ({})", reason),
+            None => "On a break".to_string(),
+        }
+    } else {
+        format!(
+            "Live for {} ({} active)",
+            format_duration(summary.raw_seconds),
+            format_duration(summary.active_seconds),
+        )
+    }
+}
+
+// Pushes the uptime to overlays and the go-live embed whenever a break starts or ends
+pub async fn publish_uptime(twitch_manager: &Arc<TwitchManager>) {
+    let summary = twitch_manager.stream_state_machine.uptime_summary().await;
+
+    let message = WebSocketMessage {
+        module: "stream".to_string(),
+        action: "uptime".to_string(),
+        data: json!(summary),
+    };
+    let dashboard_state = twitch_manager.irc_manager.get_dashboard_state();
+    if let Err(e) = dashboard_state.read().await.broadcast_message(message).await {
+        warn!("Failed to send uptime to overlays: {}", e);
+    }
+
+    let discord_client = match &twitch_manager.discord_client {
+        Some(client) => client,
+        None => return,
+    };
+    if let Some((channel_id, message_id, announcement)) = twitch_manager.channel_updates
+        .set_go_live_status(summary.as_ref().map(describe))
+        .await
+    {
+        let http = discord_client.get_http().await;
+        if let Err(e) = crate::discord::announcements::edit_stream_announcement(&http, channel_id, message_id, &announcement).await {
+            error!("Failed to update Discord go-live announcement: {}", e);
+        }
+    }
+}
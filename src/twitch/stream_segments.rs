@@ -0,0 +1,189 @@
+use std::sync::Arc;
+use tokio::sync::watch;
+use tracing::{error, info};
+use crate::storage::{StorageClient, StreamSegment};
+use crate::twitch::api::requests::channel;
+use crate::twitch::stream_breaks::format_duration;
+use crate::twitch::TwitchManager;
+
+// Each stream is split into segments whenever the category changes and, while in VRChat, whenever
+// the world changes. They're used for the end-of-stream recap, VOD markers and the dashboard stats.
+
+const GAME: &str = "game";
+const WORLD: &str = "world";
+const VRCHAT: &str = "VRChat";
+// Twitch's limit on marker descriptions
+const MAX_MARKER_LENGTH: usize = 140;
+const MAX_RECAP_SEGMENTS: usize = 6;
+
+// The VRChat world the broadcaster is in, from the VRChat websocket. VRChatManager gets a handle
+// to TwitchManager's so the VRChat side doesn't need to know about streams.
+pub struct WorldChanges {
+    sender: watch::Sender<Option<String>>,
+}
+
+impl Default for WorldChanges {
+    fn default() -> Self {
+        Self { sender: watch::channel(None).0 }
+    }
+}
+
+impl WorldChanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn world_changed(&self, world: &str) {
+        self.sender.send_if_modified(|current| {
+            if current.as_deref() == Some(world) {
+                return false;
+            }
+            *current = Some(world.to_string());
+            true
+        });
+    }
+
+    fn current(&self) -> Option<String> {
+        self.sender.borrow().clone()
+    }
+}
+
+fn is_vrchat(game: &str) -> bool {
+    game.eq_ignore_ascii_case(VRCHAT)
+}
+
+fn start(storage: &StorageClient, kind: &str, name: &str) {
+    if let Err(e) = storage.start_stream_segment(kind, name) {
+        error!("Failed to start the {} segment for {}: {:?}", kind, name, e);
+    }
+}
+
+async fn mark(twitch_manager: &TwitchManager, description: &str) {
+    if !twitch_manager.config.stream_segments.create_markers {
+        return;
+    }
+    let description: String = description.chars().take(MAX_MARKER_LENGTH).collect();
+    let api_client = twitch_manager.get_api_client();
+    let result = match api_client.get_broadcaster_id().await {
+        Ok(broadcaster_id) => channel::create_stream_marker(&api_client, &broadcaster_id, &description).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        error!("Failed to create a stream marker for {}: {:?}", description, e);
+    }
+}
+
+// When the stream goes live, after its session has been started
+pub async fn stream_started(twitch_manager: &TwitchManager, game: &str) {
+    if !twitch_manager.config.stream_segments.enabled {
+        return;
+    }
+    let storage = match twitch_manager.get_storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+    let storage = storage.read().await;
+    if !game.is_empty() {
+        start(&storage, GAME, game);
+    }
+    if is_vrchat(game) {
+        if let Some(world) = twitch_manager.world_changes.current() {
+            start(&storage, WORLD, &world);
+        }
+    }
+}
+
+pub async fn game_changed(twitch_manager: &TwitchManager, game: &str) {
+    if !twitch_manager.config.stream_segments.enabled || !twitch_manager.is_stream_live().await {
+        return;
+    }
+    let storage = match twitch_manager.get_storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+    {
+        let storage = storage.read().await;
+        start(&storage, GAME, game);
+        // Worlds only count while the stream is in VRChat
+        let world = twitch_manager.world_changes.current().filter(|_| is_vrchat(game));
+        match world {
+            Some(world) => start(&storage, WORLD, &world),
+            None => {
+                if let Err(e) = storage.end_stream_segment(WORLD) {
+                    error!("Failed to end the world segment: {:?}", e);
+                }
+            }
+        }
+    }
+    info!("New stream segment: {}", game);
+    mark(twitch_manager, &format!("Game: {}", game)).await;
+}
+
+// Follows world changes for as long as the bot runs
+pub async fn run(twitch_manager: Arc<TwitchManager>) {
+    let mut world_changes = twitch_manager.world_changes.sender.subscribe();
+    while world_changes.changed().await.is_ok() {
+        let world = match world_changes.borrow_and_update().clone() {
+            Some(world) => world,
+            None => continue,
+        };
+        if !twitch_manager.config.stream_segments.enabled || !twitch_manager.is_stream_live().await {
+            continue;
+        }
+        let in_vrchat = twitch_manager.get_current_game().await.map_or(false, |game| is_vrchat(&game));
+        if !in_vrchat {
+            continue;
+        }
+        if let Some(storage) = twitch_manager.get_storage() {
+            start(&*storage.read().await, WORLD, &world);
+        }
+        info!("New stream segment: {}", world);
+        mark(&twitch_manager, &format!("World: {}", world)).await;
+    }
+}
+
+// "45m in Murder 4, 1h 0m in The Black Cat". VRChat's time is broken down by world, so it isn't
+// listed itself unless no worlds were recorded.
+pub fn describe(segments: &[StreamSegment]) -> Option<String> {
+    let has_worlds = segments.iter().any(|segment| segment.kind == WORLD);
+    let mut totals: Vec<(&str, i64)> = Vec::new();
+    for segment in segments {
+        if segment.kind == GAME && has_worlds && is_vrchat(&segment.name) {
+            continue;
+        }
+        match totals.iter_mut().find(|(name, _)| *name == segment.name) {
+            Some((_, seconds)) => *seconds += segment.seconds,
+            None => totals.push((&segment.name, segment.seconds)),
+        }
+    }
+    // Anything under a minute was most likely a misclick
+    totals.retain(|(_, seconds)| *seconds >= 60);
+    if totals.is_empty() {
+        return None;
+    }
+
+    let more = totals.len().saturating_sub(MAX_RECAP_SEGMENTS);
+    let mut parts: Vec<String> = totals.iter()
+        .take(MAX_RECAP_SEGMENTS)
+        .map(|(name, seconds)| format!("{} in {}", format_duration(*seconds), name))
+        .collect();
+    if more > 0 {
+        parts.push(format!("{} more", more));
+    }
+    Some(parts.join(", "))
+}
+
+// For the end-of-stream recap
+pub fn recap(twitch_manager: &TwitchManager, storage: &StorageClient) -> Option<String> {
+    let config = &twitch_manager.config.stream_segments;
+    if !config.enabled || !config.in_recap {
+        return None;
+    }
+    match storage.get_latest_stream_segments() {
+        Ok(segments) => describe(&segments).map(|segments| format!("This stream: {}.", segments)),
+        Err(e) => {
+            error!("Failed to load stream segments for the recap: {:?}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::config::SuggestionsConfig;
+use crate::storage::{StorageClient, StreamSuggestion};
+use crate::twitch::api::requests::channel;
+use crate::twitch::api::TwitchAPIClient;
+
+// Twitch's own limits, checked up front so a bad suggestion never reaches the queue
+const MAX_TITLE_CHARS: usize = 140;
+const MAX_TAGS: usize = 10;
+const MAX_TAG_CHARS: usize = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SuggestionKind {
+    Title,
+    Tags,
+}
+
+impl SuggestionKind {
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind.to_lowercase().as_str() {
+            "title" => Some(Self::Title),
+            "tags" | "tag" => Some(Self::Tags),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::Tags => "tags",
+        }
+    }
+}
+
+// The value as it's stored and applied: a trimmed title, or comma separated tags
+pub fn normalize(kind: SuggestionKind, input: &str) -> Result<String, String> {
+    match kind {
+        SuggestionKind::Title => {
+            let title = input.split_whitespace().collect::<Vec<_>>().join(" ");
+            if title.is_empty() {
+                Err("the title is empty".to_string())
+            } else if title.chars().count() > MAX_TITLE_CHARS {
+                Err(format!("titles can be at most {} characters", MAX_TITLE_CHARS))
+            } else {
+                Ok(title)
+            }
+        }
+        SuggestionKind::Tags => {
+            let mut tags: Vec<String> = Vec::new();
+            for tag in input.split(|c: char| c == ',' || c.is_whitespace()).map(|t| t.trim_start_matches('#')).filter(|t| !t.is_empty()) {
+                if tag.chars().count() > MAX_TAG_CHARS || !tag.chars().all(char::is_alphanumeric) {
+                    return Err(format!("tags are letters and numbers only, up to {} characters", MAX_TAG_CHARS));
+                }
+                if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                    tags.push(tag.to_string());
+                }
+            }
+            if tags.is_empty() {
+                Err("there are no tags".to_string())
+            } else if tags.len() > MAX_TAGS {
+                Err(format!("a channel can have at most {} tags", MAX_TAGS))
+            } else {
+                Ok(tags.join(","))
+            }
+        }
+    }
+}
+
+// Queues a suggestion; the error is a reason fit for chat
+pub fn submit(
+    storage: &StorageClient,
+    config: &SuggestionsConfig,
+    kind: SuggestionKind,
+    input: &str,
+    user_id: &str,
+    user_name: &str,
+) -> Result<i64, String> {
+    if !config.enabled {
+        return Err("suggestions are turned off".to_string());
+    }
+    let value = normalize(kind, input)?;
+    let pending = storage.count_pending_stream_suggestions(user_id).map_err(|e| e.to_string())?;
+    if pending >= config.max_pending_per_user {
+        return Err(format!("you already have {} suggestions waiting", pending));
+    }
+    storage.add_stream_suggestion(kind.as_str(), &value, user_id, user_name).map_err(|e| e.to_string())
+}
+
+// Changes the channel through Helix
+pub async fn apply(api_client: &TwitchAPIClient, suggestion: &StreamSuggestion) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let broadcaster_id = api_client.get_broadcaster_id().await?;
+    match SuggestionKind::parse(&suggestion.kind) {
+        Some(SuggestionKind::Title) => channel::update_channel_title(api_client, &broadcaster_id, &suggestion.value).await,
+        Some(SuggestionKind::Tags) => {
+            let tags: Vec<String> = suggestion.value.split(',').map(str::to_string).collect();
+            channel::update_channel_tags(api_client, &broadcaster_id, &tags).await
+        }
+        None => Err(format!("Unknown suggestion kind {}", suggestion.kind).into()),
+    }
+}
+
+// Chat message crediting the viewer once their suggestion is live
+pub fn announcement(suggestion: &StreamSuggestion) -> String {
+    match SuggestionKind::parse(&suggestion.kind) {
+        Some(SuggestionKind::Tags) => format!(
+            "🏷️ New tags: {}, suggested by @{}",
+            suggestion.value.replace(',', ", "), suggestion.user_name
+        ),
+        _ => format!("📝 New title: \"{}\", suggested by @{}", suggestion.value, suggestion.user_name),
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{error, info, warn};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use tokio::time::{sleep, Duration};
+use twitch_irc::message::PrivmsgMessage;
+use crate::ai::trivia::{self, TriviaQuestion};
+use crate::ai::AIClient;
+use crate::config::TriviaConfig;
+use crate::twitch::TwitchManager;
+
+// Questions that passed the fact check, kept on disk so they can be reused
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct QuestionCache {
+    questions: Vec<TriviaQuestion>,
+}
+
+impl QuestionCache {
+    fn load(path: &str) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable trivia cache {}: {}", path, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self, path: &str) {
+        let result = serde_json::to_string_pretty(self)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            error!("Failed to save trivia cache {}: {}", path, e);
+        }
+    }
+}
+
+struct Round {
+    id: u64,
+    question: TriviaQuestion,
+    points: u64,
+    answer_similarity: f64,
+}
+
+// The open round, owned by TwitchManager
+pub struct Trivia {
+    round: Mutex<Option<Round>>,
+    // Questions asked since the bot started, so the cache doesn't repeat itself
+    asked: Mutex<HashSet<String>>,
+    next_id: AtomicU64,
+}
+
+impl Trivia {
+    pub fn new() -> Self {
+        Self {
+            round: Mutex::new(None),
+            asked: Mutex::new(HashSet::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+pub fn current_question(twitch_manager: &TwitchManager) -> Option<String> {
+    twitch_manager.trivia.round.lock().unwrap().as_ref().map(|round| round.question.question.clone())
+}
+
+// A fresh AI question if one passes the fact check, otherwise an unasked one from the cache
+pub async fn pick_question(twitch_manager: &TwitchManager, ai_client: Option<&AIClient>, config: &TriviaConfig, topic: &str) -> Option<TriviaQuestion> {
+    let mut cache = QuestionCache::load(&config.question_cache_path);
+
+    if let Some(ai_client) = ai_client {
+        let avoid: Vec<String> = cache.questions.iter()
+            .filter(|q| q.topic.eq_ignore_ascii_case(topic))
+            .map(|q| q.question.clone())
+            .collect();
+        match trivia::generate_question(ai_client, topic, &avoid).await {
+            Ok(question) => {
+                cache.questions.push(question.clone());
+                cache.save(&config.question_cache_path);
+                return Some(question);
+            }
+            Err(e) => warn!("Falling back to cached trivia: {}", e),
+        }
+    }
+
+    let asked = twitch_manager.trivia.asked.lock().unwrap();
+    let unasked: Vec<&TriviaQuestion> = cache.questions.iter().filter(|q| !asked.contains(&q.question)).collect();
+    let on_topic: Vec<&TriviaQuestion> = unasked.iter().copied().filter(|q| q.topic.eq_ignore_ascii_case(topic)).collect();
+    let pool = if on_topic.is_empty() { unasked } else { on_topic };
+    pool.choose(&mut rand::thread_rng()).map(|q| (*q).clone())
+}
+
+// Returns false if a round is already open
+pub fn start(twitch_manager: Arc<TwitchManager>, config: &TriviaConfig, question: TriviaQuestion) -> bool {
+    let id = twitch_manager.trivia.next_id.fetch_add(1, Ordering::Relaxed);
+    {
+        let mut round = twitch_manager.trivia.round.lock().unwrap();
+        if round.is_some() {
+            return false;
+        }
+        twitch_manager.trivia.asked.lock().unwrap().insert(question.question.clone());
+        info!("Trivia round #{} started: {} ({})", id, question.question, question.answer);
+        *round = Some(Round {
+            id,
+            question,
+            points: config.points,
+            answer_similarity: config.answer_similarity,
+        });
+    }
+
+    let answer_secs = config.answer_secs;
+    let tasks = twitch_manager.tasks.clone();
+    tasks.spawn("trivia_round", async move {
+        sleep(Duration::from_secs(answer_secs)).await;
+        let expired = {
+            let mut round = twitch_manager.trivia.round.lock().unwrap();
+            match round.as_ref() {
+                Some(open) if open.id == id => round.take(),
+                _ => None,
+            }
+        };
+        if let Some(round) = expired {
+            announce(&twitch_manager, &format!("Time's up! The answer was: {}", round.question.answer)).await;
+        }
+    });
+
+    true
+}
+
+// Called for every chat message; the first close-enough answer wins the round
+pub async fn check_answer(twitch_manager: &TwitchManager, msg: &PrivmsgMessage, text: &str) {
+    let won = {
+        let mut round = twitch_manager.trivia.round.lock().unwrap();
+        match round.as_ref() {
+            Some(open) if trivia::is_correct(text, &open.question.answer, open.answer_similarity) => round.take(),
+            _ => None,
+        }
+    };
+    let round = match won {
+        Some(round) => round,
+        None => return,
+    };
+
+    let storage = match twitch_manager.get_storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+    let result = storage.read().await.record_trivia_win(&msg.sender.id, &msg.sender.login, round.points);
+    let message = match result {
+        Ok((correct, points)) => format!(
+            "{} got it! The answer was {}. +{} points ({} points, {} correct so far)",
+            msg.sender.name, round.question.answer, round.points, points, correct
+        ),
+        Err(e) => {
+            error!("Failed to record trivia win for {}: {:?}", msg.sender.login, e);
+            format!("{} got it! The answer was {}.", msg.sender.name, round.question.answer)
+        }
+    };
+    announce(twitch_manager, &message).await;
+}
+
+async fn announce(twitch_manager: &TwitchManager, message: &str) {
+    let channel = match &twitch_manager.config.twitch_channel_to_join {
+        Some(channel) => channel,
+        None => return,
+    };
+    if let Err(e) = twitch_manager.send_message_as_bot(channel, message).await {
+        error!("Failed to send trivia message: {}", e);
+    }
+}
@@ -0,0 +1,243 @@
+use std::sync::{Arc, Mutex};
+use chrono::{DateTime, Utc};
+use tracing::{error, info, warn};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::sync::RwLock;
+use tokio::time::{sleep, Duration};
+use crate::config::TtsModerationConfig;
+use crate::output_filter::OutputFilter;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::TwitchManager;
+use crate::web_ui::websocket::{DashboardState, WebSocketMessage};
+
+// A TTS message waiting on the dashboard for its hold to run out or a mod to approve it
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PendingTts {
+    pub id: u64,
+    pub user_name: String,
+    pub text: String,
+    pub voice: Option<String>,
+    // "redeem" or the alert event that queued it
+    pub source: String,
+    pub queued_at: DateTime<Utc>,
+    // (reward id, redemption id) for redeems, so a rejection can refund the points
+    #[serde(skip)]
+    redemption: Option<(String, String)>,
+    // Sent to the overlay along with the text, like the alert amount and tier
+    #[serde(skip)]
+    extra: Value,
+}
+
+pub struct TtsRequest {
+    pub user_name: String,
+    pub text: String,
+    pub voice: Option<String>,
+    pub source: String,
+    pub redemption: Option<(String, String)>,
+    pub extra: Value,
+}
+
+struct TtsState {
+    config: TtsModerationConfig,
+    pending: Vec<PendingTts>,
+    next_id: u64,
+}
+
+// The moderation settings and held messages, owned by TwitchManager
+pub struct TtsQueue {
+    state: Mutex<TtsState>,
+}
+
+impl Default for TtsQueue {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(TtsState {
+                config: TtsModerationConfig::default(),
+                pending: Vec::new(),
+                next_id: 1,
+            }),
+        }
+    }
+}
+
+impl TtsQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Called at startup and when the config changes
+    pub fn configure(&self, config: &TtsModerationConfig) {
+        self.state.lock().unwrap().config = config.clone();
+    }
+
+    pub fn refunds_rejected(&self) -> bool {
+        self.state.lock().unwrap().config.refund_rejected
+    }
+
+    pub fn pending(&self) -> Vec<PendingTts> {
+        self.state.lock().unwrap().pending.clone()
+    }
+
+    fn take(&self, id: u64) -> Option<PendingTts> {
+        let mut state = self.state.lock().unwrap();
+        let index = state.pending.iter().position(|item| item.id == id)?;
+        Some(state.pending.remove(index))
+    }
+
+    // Plays a held message now; false if it isn't pending anymore
+    pub async fn approve(&self, dashboard_state: &Arc<RwLock<DashboardState>>, output_filter: &OutputFilter, id: u64) -> bool {
+        match self.take(id) {
+            Some(item) => {
+                play(dashboard_state, output_filter, &item).await;
+                self.publish(dashboard_state).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Drops a held message and refunds it if it came from a redeem
+    pub async fn reject(&self, dashboard_state: &Arc<RwLock<DashboardState>>, api_client: &TwitchAPIClient, id: u64) -> Option<PendingTts> {
+        let item = self.take(id)?;
+        info!("Rejected TTS #{} from {}", item.id, item.user_name);
+        if let Some((reward_id, redemption_id)) = &item.redemption {
+            if self.refunds_rejected() {
+                if let Err(e) = api_client.refund_channel_points(reward_id, redemption_id).await {
+                    error!("Failed to refund rejected TTS redeem from {}: {:?}", item.user_name, e);
+                }
+            }
+        }
+        self.publish(dashboard_state).await;
+        Some(item)
+    }
+
+    // Keeps the dashboard's pending list live
+    async fn publish(&self, dashboard_state: &Arc<RwLock<DashboardState>>) {
+        let message = WebSocketMessage {
+            module: "tts".to_string(),
+            action: "queue".to_string(),
+            data: json!({ "pending": self.pending() }),
+        };
+        if let Err(e) = dashboard_state.read().await.broadcast_message(message).await {
+            warn!("Failed to send the TTS queue to the dashboard: {}", e);
+        }
+    }
+}
+
+// Splits "<voice>: <message>"; anything that doesn't look like a voice name stays part of the message
+pub fn parse_voice(input: &str) -> (Option<&str>, &str) {
+    match input.split_once(':') {
+        Some((voice, message)) if !voice.trim().is_empty() && !voice.trim().contains(' ') => (Some(voice.trim()), message.trim()),
+        _ => (None, input.trim()),
+    }
+}
+
+fn blocked_reason(config: &TtsModerationConfig, text: &str, voice: Option<&str>) -> Option<String> {
+    if text.trim().is_empty() {
+        return Some("There's nothing to read out".to_string());
+    }
+    if text.chars().count() > config.max_chars {
+        return Some(format!("TTS is limited to {} characters", config.max_chars));
+    }
+    let lower = text.to_lowercase();
+    if config.blocked_words.iter().map(|w| w.trim().to_lowercase()).any(|w| !w.is_empty() && lower.contains(&w)) {
+        return Some("The message contains a blocked word".to_string());
+    }
+    match voice {
+        Some(voice) if config.blocked_voices.iter().any(|v| v.eq_ignore_ascii_case(voice)) => {
+            Some(format!("The {} voice is blocked", voice))
+        }
+        _ => None,
+    }
+}
+
+// Queues the message for the dashboard, or plays it straight away when there's no hold.
+// Banned users are turned away even with moderation off. Err is the reason it was rejected.
+pub async fn submit(twitch_manager: &TwitchManager, request: TtsRequest) -> Result<(), String> {
+    if let Some(storage) = twitch_manager.get_storage() {
+        match storage.read().await.is_tts_banned(&request.user_name) {
+            Ok(true) => return Err(format!("{} is banned from TTS", request.user_name)),
+            Ok(false) => {}
+            Err(e) => error!("Failed to check TTS ban for {}: {:?}", request.user_name, e),
+        }
+    }
+
+    let queue = twitch_manager.tts_queue.clone();
+    let (item, config) = {
+        let mut state = queue.state.lock().unwrap();
+        if state.config.enabled {
+            if let Some(reason) = blocked_reason(&state.config, &request.text, request.voice.as_deref()) {
+                return Err(reason);
+            }
+        }
+        let item = PendingTts {
+            id: state.next_id,
+            user_name: request.user_name,
+            text: request.text,
+            voice: request.voice,
+            source: request.source,
+            queued_at: Utc::now(),
+            redemption: request.redemption,
+            extra: request.extra,
+        };
+        state.next_id += 1;
+        (item, state.config.clone())
+    };
+
+    let dashboard_state = twitch_manager.irc_manager.get_dashboard_state();
+    let output_filter = twitch_manager.irc_manager.get_output_filter();
+    if !config.enabled || (config.hold_secs == 0 && !config.require_approval) {
+        play(&dashboard_state, &output_filter, &item).await;
+        return Ok(());
+    }
+
+    info!("Holding TTS #{} from {} for moderation", item.id, item.user_name);
+    let id = item.id;
+    queue.state.lock().unwrap().pending.push(item);
+    queue.publish(&dashboard_state).await;
+
+    if !config.require_approval {
+        tokio::spawn(async move {
+            sleep(Duration::from_secs(config.hold_secs)).await;
+            // Gone already if a mod approved or rejected it in the meantime
+            if let Some(item) = queue.take(id) {
+                play(&dashboard_state, &output_filter, &item).await;
+                queue.publish(&dashboard_state).await;
+            }
+        });
+    }
+    Ok(())
+}
+
+// Tells overlays to stop whatever they're reading out. Returns how many were listening.
+pub async fn skip(dashboard_state: &Arc<RwLock<DashboardState>>) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+    let message = WebSocketMessage {
+        module: "alerts".to_string(),
+        action: "skip_tts".to_string(),
+        data: json!({}),
+    };
+    dashboard_state.read().await.broadcast_message(message).await
+}
+
+async fn play(dashboard_state: &Arc<RwLock<DashboardState>>, output_filter: &OutputFilter, item: &PendingTts) {
+    let mut data = match &item.extra {
+        Value::Object(extra) => extra.clone(),
+        _ => serde_json::Map::new(),
+    };
+    data.insert("user".to_string(), json!(item.user_name));
+    data.insert("text".to_string(), json!(output_filter.filter(&item.text)));
+    data.insert("voice".to_string(), json!(item.voice));
+    let message = WebSocketMessage {
+        module: "alerts".to_string(),
+        action: "tts".to_string(),
+        data: Value::Object(data),
+    };
+
+    match dashboard_state.read().await.broadcast_message(message).await {
+        Ok(0) => warn!("No overlay clients connected for TTS from {}", item.user_name),
+        Ok(_) => {}
+        Err(e) => error!("Failed to send TTS to overlay: {}", e),
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use chrono::{DateTime, Utc, Duration};
-use log::{debug, error};
+use tracing::{debug, error};
 use crate::twitch::TwitchAPIClient;
 use serde_json::Value;
 
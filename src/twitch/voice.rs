@@ -0,0 +1,178 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::RwLock;
+use crate::config::BotVoiceConfig;
+
+// Template packs for the bot's canned replies, using the same {placeholder} names
+// as the default pack. Packs from the config override these key by key, and any
+// key a pack doesn't define falls back to "default".
+const DEFAULT_PACK: &[(&str, &str)] = &[
+    ("follow", "Welcome to the community, {user}! Thanks for following!"),
+    ("bits", "Thank you {user} for the {amount} bits!"),
+    ("subscribe", "Thank you {user} for subscribing with a {tier} subscription!"),
+    ("gift_received", "{user} received a gifted {tier} subscription! Thank you to the generous gifter!"),
+    ("gift_subs", "WOW! {user} just gifted {amount} {tier} subscriptions! They've gifted a total of {total} subs in the channel!"),
+    ("resub", "Thank you {user} for {months} months of support!"),
+    ("resub_message", "Thank you {user} for {months} months of support! They said: {message}"),
+    ("shoutout_sent", "Hey, you should go check out {user}! Click the heart at the top of the chatbox ~ it's easy! luv luv"),
+    ("shoutout_received", "We just received a shoutout from {user} with {viewers} viewers! Thank you for the support!"),
+    ("stream_online", "{user} has gone live! Stream started at {time}. Come join the fun!"),
+    ("stream_offline", "{user} ended stream!  mao  Stay amazing and cute! mao"),
+    ("now_playing", "Now playing: {category}"),
+    ("new_title", "New stream title: {title}"),
+    ("ping", "Pong!"),
+    ("voice_changed", "Bot voice set to {voice}."),
+    ("charity_donation", "Thank you {user} for donating {amount} to {charity}!"),
+    ("charity_start", "We're raising money for {charity}! The goal is {target}, donate with the button under the stream."),
+    ("charity_milestone", "The {charity} fundraiser is {percent}% of the way there: {current} of {target}!"),
+    ("charity_end", "The {charity} fundraiser has ended with {current} raised. Thank you all!"),
+];
+
+const FORMAL_PACK: &[(&str, &str)] = &[
+    ("follow", "Welcome, {user}. Thank you for following the channel."),
+    ("bits", "Thank you, {user}, for your contribution of {amount} bits."),
+    ("subscribe", "Thank you, {user}, for your {tier} subscription."),
+    ("gift_received", "{user} has received a gifted {tier} subscription. Our thanks to the gifter."),
+    ("gift_subs", "{user} has gifted {amount} {tier} subscriptions, bringing their total to {total}. Thank you."),
+    ("resub", "Thank you, {user}, for {months} months of continued support."),
+    ("resub_message", "Thank you, {user}, for {months} months of continued support. They wrote: {message}"),
+    ("shoutout_sent", "We recommend visiting {user}'s channel."),
+    ("shoutout_received", "Thank you, {user}, for the shoutout to your {viewers} viewers."),
+    ("stream_online", "{user} is now live as of {time}. Welcome."),
+    ("stream_offline", "{user} has ended the stream. Thank you all for attending."),
+    ("now_playing", "Current category: {category}"),
+    ("new_title", "Stream title updated: {title}"),
+    ("ping", "Online and responsive."),
+    ("voice_changed", "Voice changed to {voice}."),
+    ("charity_donation", "Thank you, {user}, for your donation of {amount} to {charity}."),
+    ("charity_start", "This stream is raising funds for {charity}, with a goal of {target}."),
+    ("charity_milestone", "The fundraiser for {charity} has reached {percent}% of its goal: {current} of {target}."),
+    ("charity_end", "The fundraiser for {charity} has concluded, having raised {current}. Thank you."),
+];
+
+const CUTE_PACK: &[(&str, &str)] = &[
+    ("follow", "Nyaa~ welcome {user}! Thank you for the follow ♡"),
+    ("bits", "Waaah {user} threw {amount} bits! Thank you so much ♡(˃͈ દ ˂͈ ༶ )"),
+    ("subscribe", "{user} subbed with {tier}!! Welcome to the family ♡"),
+    ("gift_received", "{user} got a {tier} gift sub! Yay~ thank you generous gifter ♡"),
+    ("gift_subs", "OMG {user} gifted {amount} {tier} subs!! That's {total} total! Sugoi~ ♡"),
+    ("resub", "{user} has been here {months} months!! Thank you ♡"),
+    ("resub_message", "{user} has been here {months} months!! They said: {message} ♡"),
+    ("shoutout_sent", "Go give {user} some love~ click the heart at the top of chat! ♡"),
+    ("shoutout_received", "Kyaa~ {user} shouted us out to {viewers} viewers! Thank you ♡"),
+    ("stream_online", "{user} is live!! Started at {time}, come hang out~ ♡"),
+    ("stream_offline", "{user} ended stream~ otsukare! See you next time ♡"),
+    ("now_playing", "Now playing: {category} ♡"),
+    ("new_title", "New title~ {title} ♡"),
+    ("ping", "Pong~ ♡"),
+    ("voice_changed", "Voice set to {voice}~ ♡"),
+    ("charity_donation", "{user} donated {amount} to {charity}!! So kind ♡"),
+    ("charity_start", "We're fundraising for {charity}~ help us reach {target}! ♡"),
+    ("charity_milestone", "{charity} fundraiser is {percent}% there~ {current} of {target}! ♡"),
+    ("charity_end", "The {charity} fundraiser is over~ we raised {current}! Thank you ♡"),
+];
+
+const SARCASTIC_PACK: &[(&str, &str)] = &[
+    ("follow", "Oh look, {user} pressed a button. Welcome, I guess."),
+    ("bits", "{user} parted with {amount} bits. Bold financial decision. Thanks."),
+    ("subscribe", "{user} is paying for {tier} now. No refunds. Thanks!"),
+    ("gift_received", "{user} got a free {tier} sub. Someone here has money to burn."),
+    ("gift_subs", "{user} just gifted {amount} {tier} subs. {total} total. Are you okay? Thank you though."),
+    ("resub", "{user} has been here {months} months. Still not bored, apparently."),
+    ("resub_message", "{user}, {months} months in, has this to say: {message}"),
+    ("shoutout_sent", "Go watch {user}. They're probably better at this anyway."),
+    ("shoutout_received", "{user} told {viewers} people about us. Brave."),
+    ("stream_online", "{user} went live at {time}. Try to contain your excitement."),
+    ("stream_offline", "{user} ended stream. You can go outside now."),
+    ("now_playing", "Now playing: {category}. Sure, why not."),
+    ("new_title", "New title: {title}. Riveting."),
+    ("ping", "Yes, I'm still here. Pong."),
+    ("voice_changed", "Fine. I'm {voice} now."),
+    ("charity_donation", "{user} gave {amount} to {charity}. An actual good decision. Thanks."),
+    ("charity_start", "We're raising money for {charity}. Goal: {target}. Yes, you can help."),
+    ("charity_milestone", "{charity} fundraiser: {current} of {target}, {percent}%. Not bad."),
+    ("charity_end", "{charity} fundraiser over. {current} raised. You did good, chat."),
+];
+
+const BUILTIN_PACKS: &[(&str, &[(&str, &str)])] = &[
+    ("default", DEFAULT_PACK),
+    ("formal", FORMAL_PACK),
+    ("cute", CUTE_PACK),
+    ("sarcastic", SARCASTIC_PACK),
+];
+
+#[derive(Default)]
+struct VoiceState {
+    active: String,
+    custom_packs: HashMap<String, HashMap<String, String>>,
+}
+
+// The active voice and the packs from the config, owned by TwitchManager
+pub struct BotVoice {
+    state: RwLock<VoiceState>,
+}
+
+impl Default for BotVoice {
+    fn default() -> Self {
+        Self {
+            state: RwLock::new(VoiceState {
+                active: "default".to_string(),
+                custom_packs: HashMap::new(),
+            }),
+        }
+    }
+}
+
+fn builtin_template(pack: &str, key: &str) -> Option<&'static str> {
+    BUILTIN_PACKS.iter()
+        .find(|(name, _)| *name == pack)
+        .and_then(|(_, templates)| templates.iter().find(|(k, _)| *k == key))
+        .map(|(_, template)| *template)
+}
+
+impl BotVoice {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Loads the configured voice and custom packs; called at startup and when the config changes
+    pub fn configure(&self, config: &BotVoiceConfig) {
+        let mut voice = self.state.write().unwrap();
+        voice.custom_packs = config.packs.clone();
+        voice.active = config.active.clone();
+    }
+
+    pub fn active(&self) -> String {
+        self.state.read().unwrap().active.clone()
+    }
+
+    pub fn available(&self) -> Vec<String> {
+        let voice = self.state.read().unwrap();
+        BUILTIN_PACKS.iter()
+            .map(|(name, _)| name.to_string())
+            .chain(voice.custom_packs.keys().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    // Returns the pack's name as stored, or None if there's no such pack
+    pub fn set_active(&self, name: &str) -> Option<String> {
+        let name = self.available().into_iter().find(|voice| voice.eq_ignore_ascii_case(name))?;
+        self.state.write().unwrap().active = name.clone();
+        Some(name)
+    }
+
+    pub fn render(&self, key: &str, vars: &[(&str, &str)]) -> String {
+        let template = {
+            let voice = self.state.read().unwrap();
+            let custom = |pack: &str| voice.custom_packs.get(pack).and_then(|templates| templates.get(key)).cloned();
+            custom(&voice.active)
+                .or_else(|| builtin_template(&voice.active, key).map(String::from))
+                .or_else(|| custom("default"))
+                .or_else(|| builtin_template("default", key).map(String::from))
+                .unwrap_or_else(|| key.to_string())
+        };
+
+        vars.iter().fold(template, |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::{error, warn};
+use url::Url;
+use crate::ai::translate::{truncate_chars, CHATBOX_MAX_CHARS};
+use crate::config::WatchQueueConfig;
+use crate::osc::VRChatOSC;
+
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WatchItem {
+    pub id: u64,
+    pub video_id: String,
+    // Short link that VRChat video players accept
+    pub url: String,
+    pub title: Option<String>,
+    pub requested_by: String,
+    pub requested_at: DateTime<Utc>,
+    // For the per-user limit, which shouldn't reset on a name change
+    #[serde(skip)]
+    requested_by_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WatchQueue {
+    pub current: Option<WatchItem>,
+    pub queue: Vec<WatchItem>,
+}
+
+struct QueueState {
+    current: Option<WatchItem>,
+    queue: Vec<WatchItem>,
+    // Videos already watched this session, so the same one isn't requested twice in a night
+    played: Vec<String>,
+    // Keyed by user id
+    last_request: HashMap<String, Instant>,
+    next_id: u64,
+}
+
+// The queue itself, owned by TwitchManager and shared with the dashboard routes
+pub struct WatchRequests {
+    state: Mutex<QueueState>,
+}
+
+// youtube.com/watch?v=, youtu.be/, /shorts/, /embed/ and /live/ links; None for anything else
+pub fn video_id(link: &str) -> Option<String> {
+    let link = link.trim();
+    let url = Url::parse(link).or_else(|_| Url::parse(&format!("https://{}", link))).ok()?;
+    let host = url.host_str()?.trim_start_matches("www.").trim_start_matches("m.").trim_start_matches("music.");
+    let id = match host {
+        "youtu.be" => url.path_segments()?.next().map(str::to_string),
+        "youtube.com" => {
+            let mut segments = url.path_segments()?;
+            match segments.next() {
+                Some("watch") => url.query_pairs().find(|(key, _)| key == "v").map(|(_, v)| v.into_owned()),
+                Some("shorts") | Some("embed") | Some("live") => segments.next().map(str::to_string),
+                _ => None,
+            }
+        }
+        _ => None,
+    }?;
+    let valid = id.len() == 11 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(id)
+}
+
+// Title from YouTube's oEmbed endpoint, which also tells us the video exists and is public.
+// If YouTube can't be reached the link is let through without a title.
+async fn lookup_title(video_id: &str) -> Result<Option<String>, String> {
+    let response = reqwest::Client::new()
+        .get("https://www.youtube.com/oembed")
+        .query(&[("url", format!("https://www.youtube.com/watch?v={}", video_id)), ("format", "json".to_string())])
+        .timeout(LOOKUP_TIMEOUT)
+        .send()
+        .await;
+
+    match response {
+        Ok(response) if response.status().is_success() => {
+            let body: Value = response.json().await.unwrap_or_default();
+            Ok(body["title"].as_str().map(str::to_string))
+        }
+        Ok(response) if response.status().is_client_error() => Err("that video is private or doesn't exist".to_string()),
+        Ok(response) => {
+            warn!("YouTube lookup for {} returned {}", video_id, response.status());
+            Ok(None)
+        }
+        Err(e) => {
+            warn!("Couldn't look up YouTube video {}: {}", video_id, e);
+            Ok(None)
+        }
+    }
+}
+
+// Why a request can't be queued right now, if anything
+fn check(state: &QueueState, config: &WatchQueueConfig, video_id: &str, user_id: &str) -> Result<(), String> {
+    let queued = state.current.iter().chain(state.queue.iter()).any(|item| item.video_id == video_id);
+    if queued {
+        return Err("that video is already in the queue".to_string());
+    }
+    if state.played.iter().any(|played| played == video_id) {
+        return Err("we already watched that one".to_string());
+    }
+    if state.queue.len() >= config.max_queue_length {
+        return Err("the queue is full".to_string());
+    }
+    let waiting = state.queue.iter().filter(|item| item.requested_by_id == user_id).count();
+    if waiting >= config.max_per_user {
+        return Err(format!("you already have {} videos waiting", waiting));
+    }
+    if let Some(last) = state.last_request.get(user_id) {
+        let cooldown = Duration::from_secs(config.user_cooldown_secs);
+        if last.elapsed() < cooldown {
+            let remaining = (cooldown - last.elapsed()).as_secs() + 1;
+            return Err(format!("you can add another video in {}s", remaining));
+        }
+    }
+    Ok(())
+}
+
+impl WatchRequests {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                current: None,
+                queue: Vec::new(),
+                played: Vec::new(),
+                last_request: HashMap::new(),
+                next_id: 1,
+            }),
+        }
+    }
+
+    // Queues a video; returns it and its place in line, or a reason fit for chat
+    pub async fn request(&self, config: &WatchQueueConfig, link: &str, user_id: &str, user_name: &str) -> Result<(WatchItem, usize), String> {
+        if !config.enabled {
+            return Err("the watch queue is closed".to_string());
+        }
+        let video_id = video_id(link).ok_or_else(|| "that isn't a YouTube link".to_string())?;
+        check(&self.state.lock().unwrap(), config, &video_id, user_id)?;
+
+        let title = lookup_title(&video_id).await?;
+
+        // Checked again in case someone queued the same video during the lookup
+        let mut state = self.state.lock().unwrap();
+        check(&state, config, &video_id, user_id)?;
+        let item = WatchItem {
+            id: state.next_id,
+            url: format!("https://youtu.be/{}", video_id),
+            video_id,
+            title,
+            requested_by: user_name.to_string(),
+            requested_at: Utc::now(),
+            requested_by_id: user_id.to_string(),
+        };
+        state.next_id += 1;
+        state.queue.push(item.clone());
+        state.last_request.insert(user_id.to_string(), Instant::now());
+        Ok((item, state.queue.len()))
+    }
+
+    pub fn snapshot(&self) -> WatchQueue {
+        let state = self.state.lock().unwrap();
+        WatchQueue {
+            current: state.current.clone(),
+            queue: state.queue.clone(),
+        }
+    }
+
+    // Moves the next video up to "now watching" and puts it in the chatbox; None once the queue is empty
+    pub async fn next(&self, vrchat_osc: &VRChatOSC, config: &WatchQueueConfig) -> Option<WatchItem> {
+        let current = {
+            let mut state = self.state.lock().unwrap();
+            let next = if state.queue.is_empty() { None } else { Some(state.queue.remove(0)) };
+            if let Some(item) = &next {
+                state.played.push(item.video_id.clone());
+            }
+            state.current = next.clone();
+            next
+        };
+
+        if config.send_to_chatbox {
+            let text = current.as_ref().map(chatbox_message).unwrap_or_default();
+            if let Err(e) = vrchat_osc.send_chatbox_message(&text, true, false).await {
+                error!("Failed to show the watch queue in the chatbox: {:?}", e);
+            }
+        }
+        current
+    }
+
+    pub fn remove(&self, id: u64) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let before = state.queue.len();
+        state.queue.retain(|item| item.id != id);
+        state.queue.len() != before
+    }
+
+    // Empties the queue and forgets what was watched, e.g. before the next movie night
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.current = None;
+        state.queue.clear();
+        state.played.clear();
+        state.last_request.clear();
+    }
+}
+
+pub fn describe(item: &WatchItem) -> String {
+    match &item.title {
+        Some(title) => format!("{} ({})", title, item.url),
+        None => item.url.clone(),
+    }
+}
+
+fn chatbox_message(item: &WatchItem) -> String {
+    let title = item.title.clone().unwrap_or_else(|| item.url.clone());
+    truncate_chars(&format!("▶ {} · from {}", title, item.requested_by), CHATBOX_MAX_CHARS)
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::info;
+use crate::ai::AIClient;
+use crate::config::WorldReactConfig;
+use crate::twitch::TwitchManager;
+
+#[derive(Debug, Clone)]
+pub struct PendingReaction {
+    pub id: u64,
+    pub user_name: String,
+    pub text: String,
+}
+
+#[derive(Default)]
+struct ReactState {
+    last_run: Option<Instant>,
+    user_runs: HashMap<String, Instant>,
+    pending: Vec<PendingReaction>,
+    next_id: u64,
+}
+
+// Cooldowns and reactions waiting on approval, owned by TwitchManager
+#[derive(Default)]
+pub struct WorldReactions {
+    state: Mutex<ReactState>,
+}
+
+fn remaining(since: Option<&Instant>, cooldown_secs: u64) -> Option<u64> {
+    let elapsed = since?.elapsed();
+    let cooldown = Duration::from_secs(cooldown_secs);
+    (elapsed < cooldown).then(|| (cooldown - elapsed).as_secs().max(1))
+}
+
+// Screenshots the program scene and has a vision model react to it
+pub async fn react(
+    twitch_manager: &TwitchManager,
+    ai_client: &AIClient,
+    config: &WorldReactConfig,
+    user_name: &str,
+    request: &str,
+) -> Result<String, String> {
+    let obs_manager = twitch_manager.get_obs_manager().ok_or("OBS isn't set up")?;
+    let screenshot = obs_manager.capture_previews(config.image_width, config.image_quality.min(100)).await
+        .into_iter()
+        .find_map(|preview| preview["image"].as_str().map(String::from))
+        .ok_or("Couldn't get a screenshot from OBS")?;
+
+    let game = twitch_manager.get_current_game().await;
+    let mut vars = vec![
+        ("username", user_name),
+        ("request", if request.is_empty() { "anything you like" } else { request }),
+    ];
+    if let Some(game) = &game {
+        vars.push(("game", game.as_str()));
+    }
+    let prompt = twitch_manager.prompts.render("redeem.world_react", &vars);
+
+    ai_client.generate_vision_response(&prompt, &screenshot).await
+        .map(|text| text.trim().to_string())
+        .map_err(|e| e.to_string())
+}
+
+impl WorldReactions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Claims the cooldowns for this viewer, or returns how many seconds are left
+    pub fn claim_cooldown(&self, config: &WorldReactConfig, user_id: &str) -> Result<(), u64> {
+        let mut state = self.state.lock().unwrap();
+        let wait = [
+            remaining(state.last_run.as_ref(), config.cooldown_secs),
+            remaining(state.user_runs.get(user_id), config.user_cooldown_secs),
+        ].into_iter().flatten().max();
+        if let Some(wait) = wait {
+            return Err(wait);
+        }
+        let now = Instant::now();
+        state.last_run = Some(now);
+        state.user_runs.insert(user_id.to_string(), now);
+        Ok(())
+    }
+
+    // Keeps a reaction back until the broadcaster approves it
+    pub fn hold(&self, user_name: &str, text: String) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let id = state.next_id;
+        info!("Holding world reaction #{} for {} until approved: {}", id, user_name, text);
+        state.pending.push(PendingReaction {
+            id,
+            user_name: user_name.to_string(),
+            text,
+        });
+        id
+    }
+
+    pub fn pending(&self) -> Vec<PendingReaction> {
+        self.state.lock().unwrap().pending.clone()
+    }
+
+    // Takes a held reaction out of the queue; the oldest one when no id is given
+    pub fn take(&self, id: Option<u64>) -> Option<PendingReaction> {
+        let mut state = self.state.lock().unwrap();
+        let index = match id {
+            Some(id) => state.pending.iter().position(|reaction| reaction.id == id)?,
+            None if state.pending.is_empty() => return None,
+            None => 0,
+        };
+        Some(state.pending.remove(index))
+    }
+}
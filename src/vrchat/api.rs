@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+use reqwest::header::{COOKIE, RETRY_AFTER};
+use reqwest::{Client, Method, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{sleep, Instant};
+use crate::vrchat::models::{Avatar, CreateInstanceRequest, CurrentUser, Friend, Group, Instance, Notification, User, VRChatError, World};
+
+pub(crate) const VRCHAT_API_BASE: &str = "https://api.vrchat.cloud/api/1";
+pub(crate) const VRCHAT_USER_AGENT: &str = "kittynvrc/twitchbot";
+
+// VRChat doesn't publish exact limits but asks clients to stay well below one
+// request per second and to back off when they get a 429
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(1500);
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(30);
+const MAX_RETRIES: u32 = 3;
+const FRIENDS_PAGE_SIZE: usize = 100;
+
+// Typed access to the VRChat REST API. Every request goes through here so the
+// auth cookie, user agent and pacing are handled in one place.
+pub struct VRChatApi {
+    client: Client,
+    auth_cookie: Arc<RwLock<String>>,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl VRChatApi {
+    pub fn new(auth_cookie: Arc<RwLock<String>>) -> Result<Self, VRChatError> {
+        let client = Client::builder()
+            .user_agent(VRCHAT_USER_AGENT)
+            .build()
+            .map_err(|e| VRChatError(format!("Failed to build client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            auth_cookie,
+            last_request: Mutex::new(None),
+        })
+    }
+
+    pub async fn set_auth_cookie(&self, auth_cookie: String) {
+        *self.auth_cookie.write().await = auth_cookie;
+    }
+
+    pub async fn current_user(&self) -> Result<CurrentUser, VRChatError> {
+        self.get("/auth/user").await
+    }
+
+    pub async fn get_user(&self, user_id: &str) -> Result<User, VRChatError> {
+        self.get(&format!("/users/{}", user_id)).await
+    }
+
+    pub async fn get_world(&self, world_id: &str) -> Result<World, VRChatError> {
+        self.get(&format!("/worlds/{}", world_id)).await
+    }
+
+    pub async fn get_instance(&self, world_id: &str, instance_id: &str) -> Result<Instance, VRChatError> {
+        self.get(&format!("/instances/{}:{}", world_id, instance_id)).await
+    }
+
+    // Only public avatars and the user's own avatars can be looked up
+    pub async fn get_avatar(&self, avatar_id: &str) -> Result<Avatar, VRChatError> {
+        self.get(&format!("/avatars/{}", avatar_id)).await
+    }
+
+    pub async fn get_friends(&self, offline: bool) -> Result<Vec<Friend>, VRChatError> {
+        let mut friends = Vec::new();
+        loop {
+            let query = [
+                ("offline", offline.to_string()),
+                ("n", FRIENDS_PAGE_SIZE.to_string()),
+                ("offset", friends.len().to_string()),
+            ];
+            let page: Vec<Friend> = self.send(Method::GET, "/auth/user/friends", |request| request.query(&query)).await?;
+            let done = page.len() < FRIENDS_PAGE_SIZE;
+            friends.extend(page);
+            if done {
+                return Ok(friends);
+            }
+        }
+    }
+
+    pub async fn create_instance(&self, request: &CreateInstanceRequest) -> Result<Instance, VRChatError> {
+        self.send(Method::POST, "/instances", |builder| builder.json(request)).await
+    }
+
+    pub async fn get_group(&self, group_id: &str) -> Result<Group, VRChatError> {
+        self.get(&format!("/groups/{}", group_id)).await
+    }
+
+    pub async fn search_groups(&self, query: &str) -> Result<Vec<Group>, VRChatError> {
+        self.send(Method::GET, "/groups", |request| request.query(&[("query", query), ("n", "10")])).await
+    }
+
+    pub async fn get_notifications(&self) -> Result<Vec<Notification>, VRChatError> {
+        self.get("/auth/user/notifications").await
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, VRChatError> {
+        self.send(Method::GET, path, |request| request).await
+    }
+
+    #[tracing::instrument(name = "vrchat_api", level = "debug", skip_all, fields(method = %method, path = path))]
+    async fn send<T, F>(&self, method: Method, path: &str, build: F) -> Result<T, VRChatError>
+    where
+        T: DeserializeOwned,
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let url = format!("{}{}", VRCHAT_API_BASE, path);
+
+        for attempt in 1..=MAX_RETRIES {
+            self.wait_for_turn().await;
+
+            let auth_cookie = self.auth_cookie.read().await.clone();
+            debug!("VRChat API {} {}", method, path);
+            let response = build(self.client.request(method.clone(), &url))
+                .header(COOKIE, auth_cookie)
+                .send()
+                .await
+                .map_err(|e| VRChatError(format!("Failed to send request to {}: {}", path, e)))?;
+
+            match response.status() {
+                StatusCode::TOO_MANY_REQUESTS => {
+                    let retry_after = response.headers().get(RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.parse::<u64>().ok())
+                        .map(Duration::from_secs)
+                        .unwrap_or(DEFAULT_RETRY_AFTER);
+                    warn!("VRChat API rate limited on {} (attempt {}), retrying in {:?}", path, attempt, retry_after);
+                    sleep(retry_after).await;
+                }
+                StatusCode::UNAUTHORIZED => return Err(VRChatError::unauthorized()),
+                status if !status.is_success() => {
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(VRChatError(format!("VRChat API request to {} failed with status {}: {}", path, status, body)));
+                }
+                _ => {
+                    return response.json::<T>().await
+                        .map_err(|e| VRChatError(format!("Failed to parse response from {}: {}", path, e)));
+                }
+            }
+        }
+
+        Err(VRChatError(format!("VRChat API request to {} kept getting rate limited", path)))
+    }
+
+    // Spaces requests out so bursts from different callers don't trip the rate limit
+    async fn wait_for_turn(&self) {
+        let mut last_request = self.last_request.lock().await;
+        if let Some(last) = *last_request {
+            let elapsed = last.elapsed();
+            if elapsed < MIN_REQUEST_INTERVAL {
+                sleep(MIN_REQUEST_INTERVAL - elapsed).await;
+            }
+        }
+        *last_request = Some(Instant::now());
+    }
+}
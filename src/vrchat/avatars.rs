@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+use rosc::{OscPacket, OscType};
+use tokio::net::UdpSocket;
+use tokio::sync::RwLock;
+use crate::config::Config;
+use crate::storage::StorageClient;
+use crate::twitch::TwitchManager;
+use crate::vrchat::VRChatApi;
+use crate::web_ui::websocket::DashboardState;
+
+const AVATAR_CHANGE_ADDRESS: &str = "/avatar/change";
+
+// Listens to VRChat's OSC output for avatar swaps, switches the OSC parameter
+// profile to match and logs/announces the new avatar.
+pub struct AvatarWatcher {
+    config: Arc<RwLock<Config>>,
+    twitch_manager: Arc<TwitchManager>,
+    storage: Arc<RwLock<StorageClient>>,
+    dashboard_state: Arc<RwLock<DashboardState>>,
+    vrchat_api: Option<Arc<VRChatApi>>,
+    current_avatar: RwLock<Option<String>>,
+}
+
+impl AvatarWatcher {
+    pub fn new(
+        config: Arc<RwLock<Config>>,
+        twitch_manager: Arc<TwitchManager>,
+        storage: Arc<RwLock<StorageClient>>,
+        dashboard_state: Arc<RwLock<DashboardState>>,
+        vrchat_api: Option<Arc<VRChatApi>>,
+    ) -> Self {
+        Self {
+            config,
+            twitch_manager,
+            storage,
+            dashboard_state,
+            vrchat_api,
+            current_avatar: RwLock::new(None),
+        }
+    }
+
+    pub async fn run(&self) {
+        let listen_addr = self.config.read().await.avatar_changes.osc_listen_addr.clone();
+        let socket = match UdpSocket::bind(&listen_addr).await {
+            Ok(socket) => socket,
+            Err(e) => {
+                error!("Failed to listen for VRChat OSC on {}: {}", listen_addr, e);
+                return;
+            }
+        };
+        info!("Listening for avatar changes on {}", listen_addr);
+
+        let mut buf = [0u8; rosc::decoder::MTU];
+        loop {
+            let len = match socket.recv(&mut buf).await {
+                Ok(len) => len,
+                Err(e) => {
+                    warn!("Failed to receive OSC packet: {}", e);
+                    continue;
+                }
+            };
+
+            match rosc::decoder::decode_udp(&buf[..len]) {
+                Ok((_, packet)) => {
+                    if let Some(avatar_id) = find_avatar_change(packet) {
+                        self.handle_avatar_change(&avatar_id).await;
+                    }
+                }
+                Err(e) => debug!("Ignoring malformed OSC packet: {:?}", e),
+            }
+        }
+    }
+
+    async fn handle_avatar_change(&self, avatar_id: &str) {
+        // VRChat resends the current avatar on every world join
+        {
+            let mut current_avatar = self.current_avatar.write().await;
+            if current_avatar.as_deref() == Some(avatar_id) {
+                return;
+            }
+            *current_avatar = Some(avatar_id.to_string());
+        }
+
+        let has_profile = self.twitch_manager.get_osc_configs().write().await.set_active_avatar(avatar_id);
+        if has_profile {
+            info!("Switched to the OSC profile for avatar {}", avatar_id);
+        }
+
+        let avatar_name = self.avatar_name(avatar_id).await;
+        info!("Broadcaster switched to avatar {} ({})", avatar_name, avatar_id);
+
+        if let Err(e) = self.storage.read().await.log_avatar_change(avatar_id, &avatar_name) {
+            error!("Failed to log avatar change: {}", e);
+        }
+
+        let (settings, channel) = {
+            let config = self.config.read().await;
+            (config.avatar_changes.clone(), config.twitch_channel_to_join.clone())
+        };
+        if settings.announce_on_dashboard {
+            self.dashboard_state.write().await
+                .add_notification(format!("Avatar changed to {}", avatar_name)).await;
+        }
+
+        if settings.announce_in_chat {
+            if let Some(channel) = channel {
+                let message = format!("New look! Now wearing {} luv", avatar_name);
+                if let Err(e) = self.twitch_manager.send_message_as_bot(&channel, &message).await {
+                    error!("Failed to announce avatar change: {}", e);
+                }
+            }
+        }
+    }
+
+    // Private avatars from other creators can't be looked up, so fall back to the id
+    async fn avatar_name(&self, avatar_id: &str) -> String {
+        match &self.vrchat_api {
+            Some(api) => match api.get_avatar(avatar_id).await {
+                Ok(avatar) => avatar.name,
+                Err(e) => {
+                    debug!("Couldn't look up avatar {}: {}", avatar_id, e);
+                    avatar_id.to_string()
+                }
+            },
+            None => avatar_id.to_string(),
+        }
+    }
+}
+
+fn find_avatar_change(packet: OscPacket) -> Option<String> {
+    match packet {
+        OscPacket::Message(message) if message.addr == AVATAR_CHANGE_ADDRESS => {
+            message.args.into_iter().find_map(|arg| match arg {
+                OscType::String(avatar_id) => Some(avatar_id),
+                _ => None,
+            })
+        }
+        OscPacket::Message(_) => None,
+        OscPacket::Bundle(bundle) => bundle.content.into_iter().find_map(find_avatar_change),
+    }
+}
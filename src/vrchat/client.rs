@@ -1,23 +1,22 @@
 use tokio::time::{sleep, Instant};
+use crate::vrchat::api::{VRChatApi, VRCHAT_API_BASE, VRCHAT_USER_AGENT};
 use crate::vrchat::models::{Friend, VRChatError, VRChatStatus};
 use crate::config::Config;
-use reqwest::{Client, StatusCode};
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT, COOKIE};
-use serde_json::Value;
+use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::io::{self, Write};
 use std::time::Duration;
-use log::{error, info, warn};
+use tracing::{error, info, warn};
 use rpassword::read_password;
 use tokio::sync::mpsc;
 use crate::vrchat::World;
 use crate::web_ui::websocket::WebSocketMessage;
 
-const VRCHAT_API_BASE: &str = "https://api.vrchat.cloud/api/1";
-
 pub struct VRChatClient {
     client: Client,
+    api: Arc<VRChatApi>,
     auth_cookie: Arc<RwLock<String>>,
     config: Arc<RwLock<Config>>,
     current_user_id: Arc<RwLock<Option<String>>>,
@@ -29,7 +28,7 @@ pub struct VRChatClient {
 impl VRChatClient {
     pub async fn new(config: Arc<RwLock<Config>>, websocket_tx: mpsc::UnboundedSender<WebSocketMessage>) -> Result<Self, VRChatError> {
         let client = Client::builder()
-            .user_agent("kittynvrc/twitchbot")
+            .user_agent(VRCHAT_USER_AGENT)
             .build()
             .map_err(|e| VRChatError(format!("Failed to build client: {}", e)))?;
 
@@ -48,9 +47,13 @@ impl VRChatClient {
             }
         };
 
+        let auth_cookie = Arc::new(RwLock::new(auth_cookie));
+        let api = Arc::new(VRChatApi::new(auth_cookie.clone())?);
+
         Ok(VRChatClient {
             client,
-            auth_cookie: Arc::new(RwLock::new(auth_cookie)),
+            api,
+            auth_cookie,
             config,
             current_user_id: Arc::new(RwLock::new(None)),
             current_world: Arc::new(RwLock::new(None)),
@@ -158,55 +161,45 @@ impl VRChatClient {
 
         let mut attempts = 0;
         loop {
-            let auth_cookie = self.auth_cookie.read().await.clone();
-            let url = format!("{}/auth/user", VRCHAT_API_BASE);
-            let response = self.client.get(&url)
-                .header(COOKIE, &auth_cookie)
-                .header(USER_AGENT, "kittynvrc/twitchbot")
-                .send()
-                .await
-                .map_err(|e| VRChatError(format!("Failed to send request: {}", e)))?;
-
-            info!("User info response status: {}", response.status());
-
-            let body = response.text().await
-                .map_err(|e| VRChatError(format!("Failed to get response body: {}", e)))?;
-
-            let user_info: Value = serde_json::from_str(&body)
-                .map_err(|e| VRChatError(format!("Failed to parse JSON: {}", e)))?;
-
-            if let Some(two_factor_types) = user_info["requiresTwoFactorAuth"].as_array() {
-                if two_factor_types.contains(&Value::String("totp".to_string())) {
+            match self.api.current_user().await {
+                Ok(user) if user.requires_two_factor_auth.iter().any(|t| t == "totp") => {
                     println!("Two-factor authentication required.");
+                    let auth_cookie = self.auth_cookie.read().await.clone();
                     let new_auth_cookie = self.handle_2fa(&auth_cookie).await?;
-                    *self.auth_cookie.write().await = new_auth_cookie.clone(); // Changed to write()
-                    let mut config = self.config.write().await;
-                    config.set_vrchat_auth_cookie(new_auth_cookie)?;
+                    self.store_auth_cookie(new_auth_cookie).await?;
                     continue;
                 }
-            }
-
-            if let Some(id) = user_info.get("id").and_then(|id| id.as_str()) {
-                let id = id.to_string();
-                *self.current_user_id.write().await = Some(id.clone());
-                return Ok(id);
-            } else if user_info.get("error").is_some() {
-                warn!("Authentication failed. Attempting to log in again.");
-                let new_auth_cookie = Self::login(&self.client).await?;
-                *self.auth_cookie.write().await = new_auth_cookie.clone(); // Changed to write()
-                let mut config = self.config.write().await;
-                config.set_vrchat_auth_cookie(new_auth_cookie)?;
-                attempts += 1;
-                if attempts >= 3 {
-                    return Err(VRChatError("Failed to authenticate after 3 attempts".to_string()));
+                Ok(user) => {
+                    let id = user.id.ok_or_else(|| VRChatError("Failed to get user ID from response".to_string()))?;
+                    info!("Logged in to VRChat as {}", user.display_name);
+                    *self.current_user_id.write().await = Some(id.clone());
+                    return Ok(id);
                 }
-                continue;
-            } else {
-                return Err(VRChatError("Failed to get user ID from response".to_string()));
+                Err(e) if e.is_unauthorized() => {
+                    warn!("Authentication failed. Attempting to log in again.");
+                    let new_auth_cookie = Self::login(&self.client).await?;
+                    self.store_auth_cookie(new_auth_cookie).await?;
+                    attempts += 1;
+                    if attempts >= 3 {
+                        return Err(VRChatError("Failed to authenticate after 3 attempts".to_string()));
+                    }
+                    continue;
+                }
+                Err(e) => return Err(e),
             }
         }
     }
 
+    async fn store_auth_cookie(&self, auth_cookie: String) -> Result<(), VRChatError> {
+        self.api.set_auth_cookie(auth_cookie.clone()).await;
+        self.config.write().await.set_vrchat_auth_cookie(auth_cookie)?;
+        Ok(())
+    }
+
+    pub fn api(&self) -> Arc<VRChatApi> {
+        self.api.clone()
+    }
+
     pub async fn get_auth_cookie(&self) -> String {
         self.auth_cookie.read().await.clone()
     }
@@ -277,101 +270,46 @@ impl VRChatClient
         for attempt in 1..=3 {  // Try up to 3 times
             info!("Attempt {} to fetch current world", attempt);
 
-            let auth_cookie = self.get_auth_cookie().await;
             let user_id = self.get_current_user_id().await?;
-
-            let url = format!("{}/users/{}", VRCHAT_API_BASE, user_id);
-            info!("Sending request to VRChat API:");
-            info!("URL: {}", url);
-            info!("Method: GET");
-            info!("Headers:");
-            info!("  User-Agent: kittynvrc/twitchbot");
-            info!("  Cookie: auth=<redacted> (length: {})", auth_cookie.len());
-
-            let response = self.client
-                .get(&url)
-                .header(COOKIE, &auth_cookie)
-                .header(USER_AGENT, "kittynvrc/twitchbot")
-                .send()
-                .await
-                .map_err(|e| VRChatError(format!("Failed to send request: {}", e)))?;
-
-            info!("Received response with status: {}", response.status());
-
-            if response.status() == StatusCode::UNAUTHORIZED {
-                error!("Unauthorized response from VRChat API");
-                if attempt < 3 {
-                    warn!("Attempting to refresh VRChat token...");
-                    if let Ok(()) = self.config.write().await.reinitialize_vrchat_token().await {
-                        continue;
+            let user = match self.api.get_user(&user_id).await {
+                Ok(user) => user,
+                Err(e) if e.is_unauthorized() => {
+                    error!("Unauthorized response from VRChat API");
+                    if attempt < 3 {
+                        warn!("Attempting to refresh VRChat token...");
+                        let mut config = self.config.write().await;
+                        if let Ok(()) = config.reinitialize_vrchat_token().await {
+                            if let Some(cookie) = config.vrchat_auth_cookie.clone() {
+                                self.api.set_auth_cookie(cookie).await;
+                            }
+                            continue;
+                        }
                     }
+                    return Err(VRChatError("Failed to authenticate with VRChat API".to_string()));
                 }
-                return Err(VRChatError("Failed to authenticate with VRChat API".to_string()));
-            }
+                Err(e) => return Err(e),
+            };
 
-            if !response.status().is_success() {
-                error!("API request failed with status: {}", response.status());
-                return Err(VRChatError(format!("API request failed with status: {}", response.status())));
-            }
+            info!("VRChat user {}: state {:?}, status {:?}, world {:?}, location {:?}",
+                  user.display_name, user.state, user.status, user.world_id, user.location);
 
-            let body: Value = response.json()
-                .await
-                .map_err(|e| VRChatError(format!("Failed to parse JSON: {}", e)))?;
-
-            info!("Parsed JSON response:");
-            info!("  displayName: {:?}", body.get("displayName"));
-            info!("  state: {:?}", body.get("state"));
-            info!("  worldId: {:?}", body.get("worldId"));
-            info!("  instanceId: {:?}", body.get("instanceId"));
-            info!("  location: {:?}", body.get("location"));
-            info!("  status: {:?}", body.get("status"));
-
-            let state = body["state"].as_str().unwrap_or("unknown");
-            let status = body["status"].as_str().unwrap_or("unknown");
-            let world_id = body["worldId"].as_str();
-            let location = body["location"].as_str();
-
-            if state == "online" || status == "active" || world_id.is_some() || (location.is_some() && location.unwrap() != "offline") {
-                if let Some(world_id) = world_id {
+            if user.is_online() {
+                if let Some(world_id) = &user.world_id {
                     info!("Fetching world details for world_id: {}", world_id);
-                    info!("Sending request to VRChat API:");
-                    info!("URL: https://vrchat.com/api/1/worlds/{}", world_id);
-                    info!("Method: GET");
-                    info!("Headers:");
-                    info!("  User-Agent: kittynvrc/twitchbot");
-                    info!("  Cookie: auth=<redacted> (length: {})", auth_cookie.len());
-
-                    let world_response = self.client
-                        .get(&format!("https://vrchat.com/api/1/worlds/{}", world_id))
-                        .header(COOKIE, &auth_cookie)
-                        .header(USER_AGENT, "kittynvrc/twitchbot")
-                        .send()
-                        .await
-                        .map_err(|e| VRChatError(format!("Failed to fetch world details: {}", e)))?;
-
-                    info!("Received world response with status: {}", world_response.status());
-
-                    if !world_response.status().is_success() {
-                        error!("World API request failed with status: {}", world_response.status());
-                        return Err(VRChatError(format!("World API request failed with status: {}", world_response.status())));
-                    }
-
-                    let world_body: World = world_response.json()
-                        .await
-                        .map_err(|e| VRChatError(format!("Failed to parse world JSON: {}", e)))?;
+                    let world = self.api.get_world(world_id).await?;
 
                     info!("Successfully fetched and parsed world data:");
-                    info!("  World Name: {}", world_body.name);
-                    info!("  Author: {}", world_body.author_name);
-                    info!("  Capacity: {}", world_body.capacity);
+                    info!("  World Name: {}", world.name);
+                    info!("  Author: {}", world.author_name);
+                    info!("  Capacity: {}", world.capacity);
 
-                    return Ok(Some(world_body));
+                    return Ok(Some(world));
                 } else {
                     warn!("User seems to be online but no world_id found");
                     return Ok(None);
                 }
             } else {
-                info!("User is not online or not in a world. State: {}, Status: {}, Location: {:?}", state, status, location);
+                info!("User is not online or not in a world. State: {:?}, Status: {:?}, Location: {:?}", user.state, user.status, user.location);
                 if attempt < 3 {
                     info!("Waiting 5 seconds before retrying...");
                     sleep(Duration::from_secs(5)).await;
@@ -409,8 +347,7 @@ impl VRChatClient
     }
 
     pub async fn get_friends(&self) -> Result<Vec<Friend>, VRChatError> {
-        // Implement actual friend fetching logic here
-        Err(VRChatError("Friend fetching not implemented yet".to_string()))
+        self.api.get_friends(false).await
     }
 
     pub async fn join_world(&self, world_id: &str) -> Result<(), VRChatError> {
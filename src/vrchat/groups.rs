@@ -0,0 +1,56 @@
+use crate::vrchat::api::VRChatApi;
+use crate::vrchat::models::{CreateInstanceRequest, Group, GroupAccessType, Instance, VRChatError};
+
+const DEFAULT_INSTANCE_REGION: &str = "us";
+
+// The configured group link is either https://vrc.group/NAME.1234 or
+// https://vrchat.com/home/group/grp_..., so accept both
+enum GroupRef {
+    Id(String),
+    ShortCode(String),
+}
+
+fn parse_group_link(link: &str) -> Option<GroupRef> {
+    let last = link.trim().trim_end_matches('/').rsplit('/').next()?;
+    if last.starts_with("grp_") {
+        Some(GroupRef::Id(last.to_string()))
+    } else if last.contains('.') {
+        Some(GroupRef::ShortCode(last.to_string()))
+    } else {
+        None
+    }
+}
+
+pub async fn resolve_group(api: &VRChatApi, link: &str) -> Result<Group, VRChatError> {
+    match parse_group_link(link) {
+        Some(GroupRef::Id(id)) => api.get_group(&id).await,
+        Some(GroupRef::ShortCode(code)) => {
+            let (short_code, discriminator) = code.split_once('.').unwrap_or((&code, ""));
+            api.search_groups(short_code).await?
+                .into_iter()
+                .find(|group| group.short_code.eq_ignore_ascii_case(short_code) && group.discriminator == discriminator)
+                .ok_or_else(|| VRChatError(format!("No VRChat group found for {}", code)))
+        }
+        None => Err(VRChatError(format!("Couldn't read a group from the link {}", link))),
+    }
+}
+
+pub async fn create_group_instance(
+    api: &VRChatApi,
+    group: &Group,
+    world_id: &str,
+    access: GroupAccessType,
+) -> Result<Instance, VRChatError> {
+    api.create_instance(&CreateInstanceRequest {
+        world_id: world_id.to_string(),
+        instance_type: "group".to_string(),
+        region: DEFAULT_INSTANCE_REGION.to_string(),
+        owner_id: group.id.clone(),
+        group_access_type: access,
+        queue_enabled: true,
+    }).await
+}
+
+pub fn launch_url(instance: &Instance) -> String {
+    format!("https://vrchat.com/home/launch?worldId={}&instanceId={}", instance.world_id, instance.instance_id)
+}
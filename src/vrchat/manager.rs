@@ -1,14 +1,18 @@
 use std::sync::Arc;
-use log::info;
+use tracing::info;
 use tokio::sync::RwLock;
+use crate::ai::prompts::Prompts;
 use crate::osc::OSCManager;
-use crate::vrchat::{VRChatClient, VRChatError, World};
+use crate::twitch::stream_segments::WorldChanges;
+use crate::vrchat::{VRChatApi, VRChatClient, VRChatError, World};
 use crate::web_ui::websocket::{DashboardState, WebSocketMessage};
 
 pub struct VRChatManager {
     vrchat_client: Arc<VRChatClient>,
     dashboard_state: Arc<RwLock<DashboardState>>,
     osc_manager: Option<Arc<OSCManager>>,
+    world_changes: Arc<WorldChanges>,
+    prompts: Arc<Prompts>,
 }
 
 impl VRChatManager {
@@ -16,11 +20,15 @@ impl VRChatManager {
         vrchat_client: Arc<VRChatClient>,
         dashboard_state: Arc<RwLock<DashboardState>>,
         osc_manager: Option<Arc<OSCManager>>,
+        world_changes: Arc<WorldChanges>,
+        prompts: Arc<Prompts>,
     ) -> Self {
         Self {
             vrchat_client,
             dashboard_state,
             osc_manager,
+            world_changes,
+            prompts,
         }
     }
 
@@ -71,6 +79,8 @@ impl VRChatManager {
     }
 
     pub async fn update_current_world(&self, world: World) -> Result<(), VRChatError> {
+        self.prompts.set_world(Some(world.name.clone()));
+        self.world_changes.world_changed(&world.name);
         let _ = self.vrchat_client.update_current_world(world).await;
         Ok(())
     }
@@ -83,6 +93,10 @@ impl VRChatManager {
         self.vrchat_client.get_current_user_id().await
     }
 
+    pub fn api(&self) -> Arc<VRChatApi> {
+        self.vrchat_client.api()
+    }
+
     pub async fn get_auth_cookie(&self) -> String {
         self.vrchat_client.get_auth_cookie().await
     }
@@ -1,8 +1,12 @@
 mod client;
+pub mod api;
+pub mod avatars;
+pub mod groups;
 pub(crate) mod models;
 pub mod websocket;
 mod manager;
 
 pub use client::VRChatClient;
+pub use api::VRChatApi;
 pub use models::*;
 pub use manager::VRChatManager;
\ No newline at end of file
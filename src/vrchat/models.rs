@@ -14,6 +14,18 @@ impl fmt::Display for VRChatError {
 
 impl Error for VRChatError {}
 
+const UNAUTHORIZED: &str = "VRChat rejected the auth cookie (401 Unauthorized)";
+
+impl VRChatError {
+    pub fn unauthorized() -> Self {
+        VRChatError(UNAUTHORIZED.to_string())
+    }
+
+    pub fn is_unauthorized(&self) -> bool {
+        self.0 == UNAUTHORIZED
+    }
+}
+
 impl From<Box<dyn Error + Send + Sync>> for VRChatError {
     fn from(error: Box<dyn Error + Send + Sync>) -> Self {
         VRChatError(error.to_string())
@@ -54,12 +66,140 @@ pub struct ErrorMessage {
     pub ip: String,
 }
 
+// /auth/user. Before 2FA is completed only requiresTwoFactorAuth is present.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrentUser {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(rename = "displayName", default)]
+    pub display_name: String,
+    #[serde(rename = "requiresTwoFactorAuth", default)]
+    pub requires_two_factor_auth: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    #[serde(rename = "displayName")]
+    pub display_name: String,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    // "wrld_...:12345~private(...)", "offline", "private" or "traveling"
+    #[serde(default)]
+    pub location: Option<String>,
+    #[serde(rename = "worldId", default)]
+    pub world_id: Option<String>,
+    #[serde(rename = "instanceId", default)]
+    pub instance_id: Option<String>,
+}
+
+impl User {
+    pub fn is_online(&self) -> bool {
+        self.state.as_deref() == Some("online")
+            || self.status.as_deref() == Some("active")
+            || self.world_id.is_some()
+            || self.location.as_deref().map_or(false, |location| location != "offline")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    // Full location, e.g. "wrld_...:12345~group(grp_...)~region(us)"
+    pub id: String,
+    #[serde(rename = "instanceId", default)]
+    pub instance_id: String,
+    #[serde(rename = "worldId")]
+    pub world_id: String,
+    // The short number shown in game
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub instance_type: String,
+    #[serde(rename = "n_users", default)]
+    pub user_count: u32,
+    #[serde(default)]
+    pub capacity: u32,
+    #[serde(default)]
+    pub region: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Avatar {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "authorName", default)]
+    pub author_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Group {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "shortCode")]
+    pub short_code: String,
+    #[serde(default)]
+    pub discriminator: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(rename = "memberCount", default)]
+    pub member_count: u32,
+}
+
+impl Group {
+    pub fn url(&self) -> String {
+        format!("https://vrc.group/{}.{}", self.short_code, self.discriminator)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupAccessType {
+    Public,
+    Plus,
+    Members,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreateInstanceRequest {
+    #[serde(rename = "worldId")]
+    pub world_id: String,
+    #[serde(rename = "type")]
+    pub instance_type: String,
+    pub region: String,
+    #[serde(rename = "ownerId")]
+    pub owner_id: String,
+    #[serde(rename = "groupAccessType")]
+    pub group_access_type: GroupAccessType,
+    #[serde(rename = "queueEnabled")]
+    pub queue_enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub notification_type: String,
+    #[serde(rename = "senderUserId", default)]
+    pub sender_user_id: String,
+    #[serde(rename = "senderUsername", default)]
+    pub sender_username: String,
+    #[serde(default)]
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Friend {
     pub id: String,
+    #[serde(default)]
     pub username: String,
+    #[serde(alias = "displayName")]
     pub display_name: String,
+    #[serde(default)]
     pub status: String,
+    #[serde(default)]
     pub location: String,
 }
 
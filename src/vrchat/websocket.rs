@@ -3,7 +3,7 @@ use futures_util::StreamExt;
 use std::sync::Arc;
 use std::time::Duration;
 use chrono::{DateTime, Utc};
-use log::{debug, error, info, warn};
+use tracing::{debug, error, info, warn};
 use tokio::sync::{RwLock};
 use tokio::time::sleep;
 use tokio_tungstenite::{connect_async_tls_with_config, Connector};
@@ -11,13 +11,17 @@ use tokio_tungstenite::tungstenite::protocol::Message as TungsteniteMessage;
 use tokio_tungstenite::tungstenite::http::{Request, Uri};
 use tokio_tungstenite::tungstenite::http::header;
 use crate::vrchat::{VRChatManager, VRChatMessage};
+use crate::vrchat::api::VRCHAT_USER_AGENT;
 use crate::web_ui::websocket::{DashboardState};
+use crate::twitch::connection_monitor::ConnectionHealth;
 
+#[tracing::instrument(name = "vrchat", skip_all, fields(user_id = %current_user_id))]
 pub async fn handler(
     auth_cookie: String,
     current_user_id: String,
     vrchat_manager: Arc<VRChatManager>,
     dashboard_state: Arc<RwLock<DashboardState>>,
+    connections: Arc<ConnectionHealth>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let mut delay = Duration::from_secs(1);
     let max_delay = Duration::from_secs(64);
@@ -28,6 +32,7 @@ pub async fn handler(
             Ok(mut ws_stream) => {
                 info!("WebSocket connection established");
                 dashboard_state.write().await.update_vrchat_status(true).await;
+                connections.report_connected("VRChat");
 
                 while let Some(message) = ws_stream.next().await {
                     match message {
@@ -111,6 +116,7 @@ pub async fn handler(
                         }
                     }
                 }
+                connections.report_disconnected("VRChat");
                 delay = Duration::from_secs(1);
             }
             Err(err) => {
@@ -156,7 +162,7 @@ fn create_request(auth_token: &str) -> Result<Request<()>, VRChatError> {
         .uri(url)
         .header(header::HOST, "pipeline.vrchat.cloud")
         .header(header::ORIGIN, "https://vrchat.com")
-        .header(header::USER_AGENT, "kittynvrc/twitchbot")
+        .header(header::USER_AGENT, VRCHAT_USER_AGENT)
         .header(header::CONNECTION, "Upgrade")
         .header(header::UPGRADE, "websocket")
         .header(header::SEC_WEBSOCKET_VERSION, "13")
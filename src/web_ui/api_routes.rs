@@ -1,19 +1,75 @@
 use warp::Filter;
+use std::collections::HashMap;
 use std::sync::Arc;
-use log::{error, info};
+use chrono::{Duration, Utc};
+use tracing::{error, info};
 use tokio::sync::RwLock;
 use crate::config::Config;
 use crate::storage::StorageClient;
 use serde_json::json;
 use crate::web_ui::storage_ext::StorageClientExt;
 use crate::web_ui::websocket::DashboardState;
+use crate::twitch::irc::triggers::{KeywordTrigger, KeywordTriggerManager};
+use crate::web_ui::leaderboard;
+use crate::twitch::milestones;
+use crate::web_ui::pages::pages_api_route;
+use crate::web_ui::theme::theme_api_routes;
+use crate::web_ui::voice::voice_api_routes;
+use crate::web_ui::short_links::short_link_api_routes;
+use crate::web_ui::prompts::prompt_api_routes;
+use crate::web_ui::jobs::job_api_routes;
+use crate::web_ui::events::event_api_routes;
+use crate::web_ui::command_usage::command_usage_api_routes;
+use crate::web_ui::command_config::command_config_api_routes;
+use crate::web_ui::custom_commands::custom_command_api_routes;
+use crate::web_ui::kv::kv_api_routes;
+use crate::web_ui::api_tokens::{authorize, token_api_routes};
+use crate::web_ui::openapi::openapi_route;
+use crate::web_ui::remote::remote_api_routes;
+use crate::web_ui::tts::tts_api_routes;
+use crate::web_ui::costreams::costream_api_routes;
+use crate::web_ui::raid_train::raid_train_api_routes;
+use crate::web_ui::premiere::premiere_api_routes;
+use crate::web_ui::suggestions::suggestion_api_routes;
+use crate::web_ui::watch_queue::watch_queue_api_routes;
+use crate::ai::AIClient;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::irc::TwitchIRCManager;
+use crate::twitch::TwitchManager;
+use crate::web_ui::redeems::{redeem_api_routes, reward_pricing_routes};
+use crate::twitch::redeems::RedeemManager;
+use crate::web_ui::osc_designer::osc_designer_routes;
+use crate::web_ui::oauth::twitch_auth_api_routes;
+use crate::osc::{OSCConfigurations, VRChatOSC};
+
+// GET routes that only return what's shown on stream, so overlay tokens may read them. Everything
+// else needs read-stats, and anything that changes something needs admin (see api_tokens).
+pub(crate) const OVERLAY_ROUTES: &[&str] = &[
+    "/api/leaderboard",
+    "/api/milestones",
+    "/api/followers/growth",
+    "/api/clips",
+    "/api/quickpoll",
+    "/api/bitsvote",
+    "/api/charity",
+    "/api/redeems/queue",
+    "/api/theme",
+];
 
 pub fn api_routes(
     config: Arc<RwLock<Config>>,
     storage: Arc<RwLock<StorageClient>>,
     dashboard_state: Arc<RwLock<DashboardState>>,
+    keyword_triggers: Arc<KeywordTriggerManager>,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+    osc_configs: Arc<RwLock<OSCConfigurations>>,
+    vrchat_osc: Arc<VRChatOSC>,
+    ai_client: Option<Arc<AIClient>>,
+    api_client: Arc<TwitchAPIClient>,
+    twitch_irc_manager: Arc<TwitchIRCManager>,
+    twitch_manager: Arc<TwitchManager>,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-    warp::path("api").and(
+    warp::path("api").and(authorize(config.clone(), storage.clone())).and(
         start_bot(config.clone())
             .or(stop_bot(config.clone()))
             .or(update_settings(config.clone()))
@@ -23,11 +79,58 @@ pub fn api_routes(
             .or(get_twitch_channel(config.clone()))
             .or(get_twitch_parent(config.clone()))
             .or(get_config(config.clone()))
-            .or(update_config(config.clone()))
+            .or(update_config(config.clone(), twitch_manager.clone()))
+            .or(get_moderation_log(storage.clone()))
+            .or(update_appeal_status(storage.clone()))
+            .or(get_clips(storage.clone()))
+            .or(get_leaderboard(storage.clone()))
+            .or(get_milestones(config.clone(), storage.clone()))
+            .or(get_stream_health(storage.clone()))
+            .or(get_follower_growth(storage.clone()))
+            .or(get_stream_segments(storage.clone()))
+            .or(theme_api_routes(config.clone()))
+            .or(voice_api_routes(config.clone(), twitch_manager.bot_voice.clone()))
+            .or(pages_api_route(config.clone()))
+            .or(get_channel_metrics(twitch_manager.clone(), dashboard_state.clone()))
+            .or(get_command_metrics(twitch_manager.clone()))
+            .or(get_connections(twitch_manager.clone()))
+            .or(get_quick_poll(twitch_manager.clone()))
+            .or(get_bits_vote(twitch_manager.clone()))
+            .or(get_charity(twitch_manager.clone()))
+            .or(short_link_api_routes(storage.clone()))
+            .or(get_keyword_triggers(keyword_triggers.clone()))
+            .or(upsert_keyword_trigger(keyword_triggers.clone()))
+            .or(delete_keyword_trigger(keyword_triggers.clone()))
+            .or(redeem_api_routes(redeem_manager.clone()))
+            .or(reward_pricing_routes(redeem_manager.clone(), storage.clone()))
+            .or(osc_designer_routes(osc_configs, vrchat_osc.clone()))
+            .or(prompt_api_routes(ai_client, twitch_manager.prompts.clone()))
+            .or(job_api_routes(twitch_manager.scheduler.clone()))
+            .or(event_api_routes(config.clone(), storage.clone(), redeem_manager, twitch_manager.profiles.clone()))
+            .or(command_usage_api_routes(storage.clone(), twitch_manager.command_abuse.clone()))
+            .or(command_config_api_routes(twitch_manager.command_overrides.clone(), twitch_manager.commands.clone()))
+            .or(custom_command_api_routes(storage.clone(), twitch_manager.commands.clone()))
+            .or(kv_api_routes(storage.clone()))
+            .or(token_api_routes(storage.clone()))
+            .or(remote_api_routes(config.clone(), twitch_manager.remote_pairing.clone()))
+            .or(tts_api_routes(storage.clone(), dashboard_state.clone(), api_client.clone(), twitch_manager.tts_queue.clone(), twitch_manager.irc_manager.get_output_filter()))
+            .or(costream_api_routes(config.clone(), api_client.clone(), twitch_manager.costreams.clone()))
+            .or(raid_train_api_routes(config.clone(), api_client.clone(), twitch_manager.raid_train.clone(), twitch_manager.costreams.clone()))
+            .or(premiere_api_routes(config.clone(), twitch_manager.clone()))
+            .or(suggestion_api_routes(config.clone(), storage.clone(), api_client.clone(), twitch_irc_manager.clone()))
+            .or(watch_queue_api_routes(config.clone(), vrchat_osc, twitch_irc_manager, twitch_manager.watch_requests.clone()))
+            .or(twitch_auth_api_routes(api_client))
+            .or(openapi_route())
     )
 }
 
-fn with_dashboard_state(
+pub(crate) fn with_keyword_triggers(
+    keyword_triggers: Arc<KeywordTriggerManager>,
+) -> impl Filter<Extract = (Arc<KeywordTriggerManager>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || keyword_triggers.clone())
+}
+
+pub(crate) fn with_dashboard_state(
     dashboard_state: Arc<RwLock<DashboardState>>,
 ) -> impl Filter<Extract = (Arc<RwLock<DashboardState>>,), Error = std::convert::Infallible> + Clone {
     warp::any().map(move || dashboard_state.clone())
@@ -45,6 +148,12 @@ pub fn with_storage(
     warp::any().map(move || storage.clone())
 }
 
+pub(crate) fn with_twitch_manager(
+    twitch_manager: Arc<TwitchManager>,
+) -> impl Filter<Extract = (Arc<TwitchManager>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || twitch_manager.clone())
+}
+
 fn start_bot(
     config: Arc<RwLock<Config>>,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
@@ -131,14 +240,357 @@ fn get_config(
 
 fn update_config(
     config: Arc<RwLock<Config>>,
+    twitch_manager: Arc<TwitchManager>,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path("config")
         .and(warp::post())
         .and(warp::body::json())
         .and(with_config(config))
+        .and(with_twitch_manager(twitch_manager))
         .and_then(handle_update_config)
 }
 
+fn get_moderation_log(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("moderation-log")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage))
+        .and_then(handle_get_moderation_log)
+}
+
+fn update_appeal_status(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("moderation-log" / i64 / "appeal")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage))
+        .and_then(handle_update_appeal_status)
+}
+
+fn get_channel_metrics(
+    twitch_manager: Arc<TwitchManager>,
+    dashboard_state: Arc<RwLock<DashboardState>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("channel-metrics")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_twitch_manager(twitch_manager))
+        .and(with_dashboard_state(dashboard_state))
+        .and_then(|twitch_manager: Arc<TwitchManager>, dashboard_state: Arc<RwLock<DashboardState>>| async move {
+            let metrics: serde_json::Map<_, _> = [
+                twitch_manager.irc_manager.channel_metrics(),
+                dashboard_state.read().await.channel_metrics(),
+            ].into_iter().collect();
+            Ok::<_, warp::Rejection>(warp::reply::json(&metrics))
+        })
+}
+
+fn get_command_metrics(twitch_manager: Arc<TwitchManager>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("command-metrics")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_twitch_manager(twitch_manager))
+        .map(|twitch_manager: Arc<TwitchManager>| warp::reply::json(&twitch_manager.command_latency.snapshot()))
+}
+
+fn get_connections(twitch_manager: Arc<TwitchManager>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("connections")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_twitch_manager(twitch_manager))
+        .map(|twitch_manager: Arc<TwitchManager>| warp::reply::json(&twitch_manager.connections.connection_stats()))
+}
+
+fn get_quick_poll(twitch_manager: Arc<TwitchManager>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("quickpoll")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_twitch_manager(twitch_manager))
+        .map(|twitch_manager: Arc<TwitchManager>| warp::reply::json(&json!({ "poll": crate::twitch::quick_poll::current(&twitch_manager) })))
+}
+
+fn get_bits_vote(twitch_manager: Arc<TwitchManager>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("bitsvote")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_twitch_manager(twitch_manager))
+        .map(|twitch_manager: Arc<TwitchManager>| warp::reply::json(&json!({ "vote": twitch_manager.bits_votes.current() })))
+}
+
+fn get_charity(twitch_manager: Arc<TwitchManager>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("charity")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_twitch_manager(twitch_manager))
+        .map(|twitch_manager: Arc<TwitchManager>| warp::reply::json(&json!({ "campaign": twitch_manager.charity.current() })))
+}
+
+fn get_leaderboard(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("leaderboard")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage))
+        .and_then(handle_get_leaderboard)
+}
+
+fn get_milestones(
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("milestones")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_config(config))
+        .and(with_storage(storage))
+        .and_then(handle_get_milestones)
+}
+
+fn get_stream_health(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("stream-health")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_storage(storage))
+        .and_then(handle_get_stream_health)
+}
+
+fn get_follower_growth(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("followers" / "growth")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage))
+        .and_then(handle_get_follower_growth)
+}
+
+// Time per game and VRChat world: the latest stream's segments and totals over the last ?days (default 30)
+fn get_stream_segments(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("segments")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage))
+        .and_then(handle_get_stream_segments)
+}
+
+fn get_clips(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("clips")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage))
+        .and_then(handle_get_clips)
+}
+
+async fn handle_get_moderation_log(
+    query: HashMap<String, String>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let event_type = query.get("type").map(|s| s.as_str()).filter(|s| !s.is_empty());
+    let user = query.get("user").map(|s| s.as_str()).filter(|s| !s.is_empty());
+    let limit = query.get("limit").and_then(|l| l.parse().ok()).unwrap_or(100);
+
+    let storage = storage.read().await;
+    match storage.get_moderation_log(event_type, user, limit) {
+        Ok(events) => Ok(warp::reply::json(&events)),
+        Err(e) => {
+            error!("Error fetching moderation log: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_get_clips(
+    query: HashMap<String, String>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let limit = query.get("limit").and_then(|l| l.parse().ok()).unwrap_or(200);
+
+    let storage = storage.read().await;
+    match storage.get_clips(limit) {
+        Ok(clips) => Ok(warp::reply::json(&clips)),
+        Err(e) => {
+            error!("Error fetching clips: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_get_leaderboard(
+    query: HashMap<String, String>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let storage = storage.read().await;
+    match leaderboard::load_from_query(&storage, &query) {
+        Ok(leaderboard) => Ok(warp::reply::json(&leaderboard.to_json())),
+        Err(e) => {
+            error!("Error fetching leaderboard: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_get_milestones(
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let milestones_config = config.read().await.milestones.clone();
+    let storage = storage.read().await;
+    match milestones::progress(&storage, &milestones_config) {
+        Ok(progress) => Ok(warp::reply::json(&progress)),
+        Err(e) => {
+            error!("Error fetching milestones: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_get_stream_health(
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let storage = storage.read().await;
+    match storage.get_last_session_health() {
+        Ok(samples) => Ok(warp::reply::json(&samples)),
+        Err(e) => {
+            error!("Error fetching stream health: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_get_follower_growth(
+    query: HashMap<String, String>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let sessions = query.get("sessions").and_then(|s| s.parse().ok()).unwrap_or(30);
+
+    let storage = storage.read().await;
+    match storage.get_follower_growth_by_session(sessions) {
+        Ok(growth) => Ok(warp::reply::json(&growth)),
+        Err(e) => {
+            error!("Error fetching follower growth: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_get_stream_segments(
+    query: HashMap<String, String>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let days = query.get("days").and_then(|d| d.parse().ok()).unwrap_or(30);
+    let since = Utc::now() - Duration::days(days);
+
+    let storage = storage.read().await;
+    let result = storage.get_latest_stream_segments()
+        .and_then(|latest| Ok((latest, storage.get_segment_totals(since)?)));
+    match result {
+        Ok((latest, totals)) => Ok(warp::reply::json(&json!({ "days": days, "latest": latest, "totals": totals }))),
+        Err(e) => {
+            error!("Error fetching stream segments: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_update_appeal_status(
+    id: i64,
+    body: serde_json::Value,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let status = body["appeal_status"].as_str().filter(|s| !s.is_empty());
+    info!("Updating appeal status for moderation event {}: {:?}", id, status);
+
+    let storage = storage.read().await;
+    match storage.set_appeal_status(id, status) {
+        Ok(true) => Ok(warp::reply::json(&json!({"status": "Appeal status updated"}))),
+        Ok(false) => Err(warp::reject::not_found()),
+        Err(e) => {
+            error!("Error updating appeal status: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+fn get_keyword_triggers(
+    keyword_triggers: Arc<KeywordTriggerManager>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("triggers")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_keyword_triggers(keyword_triggers))
+        .and_then(handle_get_keyword_triggers)
+}
+
+fn upsert_keyword_trigger(
+    keyword_triggers: Arc<KeywordTriggerManager>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("triggers")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_keyword_triggers(keyword_triggers))
+        .and_then(handle_upsert_keyword_trigger)
+}
+
+fn delete_keyword_trigger(
+    keyword_triggers: Arc<KeywordTriggerManager>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("triggers" / String)
+        .and(warp::delete())
+        .and(with_keyword_triggers(keyword_triggers))
+        .and_then(handle_delete_keyword_trigger)
+}
+
+async fn handle_get_keyword_triggers(
+    keyword_triggers: Arc<KeywordTriggerManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    Ok(warp::reply::json(&keyword_triggers.get_all().await))
+}
+
+async fn handle_upsert_keyword_trigger(
+    trigger: KeywordTrigger,
+    keyword_triggers: Arc<KeywordTriggerManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    info!("Saving keyword trigger: {}", trigger.name);
+    match keyword_triggers.upsert(trigger).await {
+        Ok(_) => Ok(warp::reply::json(&json!({"status": "Trigger saved"}))),
+        Err(e) => {
+            error!("Failed to save keyword trigger: {:?}", e);
+            Err(warp::reject::custom(ApiError::TriggerUpdateError))
+        }
+    }
+}
+
+async fn handle_delete_keyword_trigger(
+    name: String,
+    keyword_triggers: Arc<KeywordTriggerManager>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let name = percent_encoding::percent_decode_str(&name).decode_utf8_lossy().to_string();
+    info!("Deleting keyword trigger: {}", name);
+    match keyword_triggers.remove(&name).await {
+        Ok(true) => Ok(warp::reply::json(&json!({"status": "Trigger deleted"}))),
+        Ok(false) => Err(warp::reject::not_found()),
+        Err(e) => {
+            error!("Failed to delete keyword trigger: {:?}", e);
+            Err(warp::reject::custom(ApiError::TriggerUpdateError))
+        }
+    }
+}
+
 async fn handle_get_config(
     config: Arc<RwLock<Config>>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
@@ -150,11 +602,15 @@ async fn handle_get_config(
 async fn handle_update_config(
     new_config: Config,
     config: Arc<RwLock<Config>>,
-
+    twitch_manager: Arc<TwitchManager>,
 ) -> Result<impl warp::Reply, warp::Rejection> {
     info!("Updating configuration");
     let mut config_write = config.write().await;
     *config_write = new_config;
+    twitch_manager.bot_voice.configure(&config_write.bot_voice);
+    twitch_manager.irc_manager.get_output_filter().configure(&config_write.output_filter);
+    twitch_manager.profiles.configure(&config_write.profiles, &twitch_manager.bot_voice, &twitch_manager.scheduler);
+    twitch_manager.tts_queue.configure(&config_write.tts_moderation);
     if let Err(e) = config_write.save() {
         error!("Failed to save configuration: {:?}", e);
         return Err(warp::reject::custom(ApiError::ConfigUpdateError));
@@ -255,9 +711,12 @@ async fn handle_get_recent_messages(
 }
 
 #[derive(Debug)]
-enum ApiError {
+pub(crate) enum ApiError {
     DatabaseError,
     ConfigUpdateError,
+    TriggerUpdateError,
+    InvalidInput,
+    Unauthorized,
 }
 
 impl warp::reject::Reject for ApiError {}
\ No newline at end of file
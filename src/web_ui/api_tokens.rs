@@ -0,0 +1,418 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::http::{HeaderMap, Method, StatusCode};
+use warp::path::FullPath;
+use warp::Filter;
+use crate::config::{ApiAccessConfig, Config};
+use crate::storage::StorageClient;
+use crate::web_ui::api_routes::{with_config, with_storage, ApiError, OVERLAY_ROUTES};
+
+const TOKEN_PREFIX: &str = "mew_";
+const TOKEN_LENGTH: usize = 40;
+
+// GET requests here can leak secrets or other tokens, so they need the admin scope
+const ADMIN_ONLY_PATHS: &[&str] = &["/api/config", "/api/tokens"];
+// Added by reverse proxies and tunnels, behind which every request comes from localhost
+const FORWARDING_HEADERS: &[&str] = &["forwarded", "x-forwarded-for", "x-real-ip"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ApiScope {
+    // Only what overlays show: the overlay routes in api_routes and the overlay websocket updates
+    Overlay,
+    // Every GET endpoint and the dashboard websocket updates
+    ReadStats,
+    // Twitch chat through the dashboard websocket
+    SendChat,
+    // OBS control through the dashboard websocket
+    ControlObs,
+    // Running channel point reward handlers through the /compat websocket
+    RunRedeems,
+    // Everything, like the dashboard on localhost
+    Admin,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::Overlay => "overlay",
+            ApiScope::ReadStats => "read-stats",
+            ApiScope::SendChat => "send-chat",
+            ApiScope::ControlObs => "control-obs",
+            ApiScope::RunRedeems => "run-redeems",
+            ApiScope::Admin => "admin",
+        }
+    }
+
+    fn parse(scope: &str) -> Option<Self> {
+        match scope {
+            "overlay" => Some(ApiScope::Overlay),
+            "read-stats" => Some(ApiScope::ReadStats),
+            "send-chat" => Some(ApiScope::SendChat),
+            "control-obs" => Some(ApiScope::ControlObs),
+            "run-redeems" => Some(ApiScope::RunRedeems),
+            "admin" => Some(ApiScope::Admin),
+            _ => None,
+        }
+    }
+}
+
+pub fn allows(scopes: &[ApiScope], required: ApiScope) -> bool {
+    scopes.iter().any(|scope| {
+        *scope == required
+            || *scope == ApiScope::Admin
+            // Anything that can read every stat can read what overlays show
+            || (required == ApiScope::Overlay && *scope == ApiScope::ReadStats)
+    })
+}
+
+// Websocket pushes an overlay-only client gets; the rest (chat, dashboard state) stays private
+const OVERLAY_MODULES: &[&str] = &["alerts", "bitsvote", "poll", "redeem_queue", "stream"];
+
+pub fn receives_websocket_module(scopes: &[ApiScope], module: &str) -> bool {
+    scopes.iter().any(|scope| *scope != ApiScope::Overlay) || OVERLAY_MODULES.contains(&module)
+}
+
+// The scope a dashboard websocket message needs, by its module
+pub fn websocket_scope(module: &str) -> ApiScope {
+    match module {
+        "twitch" => ApiScope::SendChat,
+        "obs" => ApiScope::ControlObs,
+        _ => ApiScope::Admin,
+    }
+}
+
+// The scope a request under /api needs
+pub fn required_scope(method: &Method, path: &str) -> ApiScope {
+    let admin_only = ADMIN_ONLY_PATHS.iter().any(|prefix| path.starts_with(prefix));
+    let overlay = OVERLAY_ROUTES.iter().any(|route| path == *route || path.starts_with(&format!("{}/", route)));
+    if method != Method::GET {
+        ApiScope::Admin
+    } else if overlay {
+        ApiScope::Overlay
+    } else if admin_only {
+        ApiScope::Admin
+    } else {
+        ApiScope::ReadStats
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    sha256::digest(token)
+}
+
+// Returns the id and the token itself; afterwards only its hash exists
+fn create_token(storage: &StorageClient, name: &str, scopes: &[ApiScope]) -> rusqlite::Result<(i64, String)> {
+    let secret: String = rand::thread_rng().sample_iter(&Alphanumeric).take(TOKEN_LENGTH).map(char::from).collect();
+    let token = format!("{}{}", TOKEN_PREFIX, secret);
+    let mut scopes: Vec<&str> = scopes.iter().map(ApiScope::as_str).collect();
+    scopes.sort();
+    scopes.dedup();
+    let id = storage.create_api_token(name, &hash_token(&token), &scopes.join(","))?;
+    info!("Created API token {} ({}) with scopes {}", id, name, scopes.join(", "));
+    Ok((id, token))
+}
+
+// For `mewbot admin-token`: once web_ui_public_url is set, localhost isn't admin any more and
+// the dashboard needs a token to get in at all
+pub fn create_admin_token(storage: &StorageClient, name: &str) -> rusqlite::Result<String> {
+    create_token(storage, name, &[ApiScope::Admin]).map(|(_, token)| token)
+}
+
+// Only someone on this PC talking straight to the bot counts as local. With web_ui_public_url set
+// or a forwarding header present, the request may have come through a proxy or tunnel on this PC.
+fn is_local(addr: Option<SocketAddr>, headers: &HeaderMap, public_url: Option<&str>) -> bool {
+    addr.map_or(false, |addr| addr.ip().is_loopback())
+        && public_url.map_or(true, |url| url.trim().is_empty())
+        && !FORWARDING_HEADERS.iter().any(|name| headers.contains_key(*name))
+}
+
+// "localhost:3333" -> "localhost", "[::1]:3333" -> "[::1]"
+fn host_name(host: &str) -> &str {
+    match host.find(']') {
+        Some(end) => &host[..=end],
+        None => host.split(':').next().unwrap_or(host),
+    }
+}
+
+// Browsers always send Origin on websockets, so a page on another site can't open the dashboard
+// websocket from the streamer's browser. The port isn't compared, for the frontend dev server.
+fn origin_allowed(headers: &HeaderMap, public_url: Option<&str>) -> bool {
+    let origin = match headers.get("origin").and_then(|value| value.to_str().ok()) {
+        Some(origin) => origin.trim_end_matches('/'),
+        None => return true,
+    };
+    let origin_host = origin.split_once("://").map(|(_, rest)| host_name(rest));
+    let host = headers.get("host").and_then(|value| value.to_str().ok()).map(host_name);
+    if origin_host.is_some() && origin_host == host {
+        return true;
+    }
+    public_url.map_or(false, |url| {
+        let url = url.trim().trim_end_matches('/');
+        url == origin || url.starts_with(&format!("{}/", origin))
+    })
+}
+
+// None when the caller isn't allowed in at all. A token always limits the caller to its
+// scopes, even from localhost, so tools can be tested with the access they'll really have.
+async fn resolve_scopes(
+    addr: Option<SocketAddr>,
+    headers: &HeaderMap,
+    token: Option<&str>,
+    config: &RwLock<Config>,
+    storage: &RwLock<StorageClient>,
+) -> Option<Vec<ApiScope>> {
+    if let Some(token) = token {
+        return match storage.read().await.use_api_token(&hash_token(token)) {
+            Ok(Some(scopes)) => Some(scopes.split(',').filter_map(ApiScope::parse).collect()),
+            Ok(None) => None,
+            Err(e) => {
+                error!("Failed to look up API token: {:?}", e);
+                None
+            }
+        };
+    }
+
+    let config = config.read().await;
+    anonymous_scopes(is_local(addr, headers, config.web_ui_public_url.as_deref()), &config.api_access)
+}
+
+// Scopes for a caller without a token
+fn anonymous_scopes(is_local: bool, api_access: &ApiAccessConfig) -> Option<Vec<ApiScope>> {
+    if is_local || !api_access.remote_requires_token {
+        Some(vec![ApiScope::Admin])
+    } else if api_access.anonymous_overlays {
+        Some(vec![ApiScope::Overlay])
+    } else {
+        None
+    }
+}
+
+// Guards everything under /api. Tokens go in an `Authorization: Bearer <token>` header.
+pub fn authorize(
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::addr::remote()
+        .and(warp::header::headers_cloned())
+        .and(warp::method())
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_config(config))
+        .and(with_storage(storage))
+        .and_then(check_request)
+        .untuple_one()
+}
+
+async fn check_request(
+    addr: Option<SocketAddr>,
+    headers: HeaderMap,
+    method: Method,
+    path: FullPath,
+    authorization: Option<String>,
+    query: HashMap<String, String>,
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<(), warp::Rejection> {
+    let required = required_scope(&method, path.as_str());
+
+    // OBS browser sources can't send headers, so overlays may pass `?token=` instead
+    let token = authorization.as_deref().and_then(|value| value.strip_prefix("Bearer "))
+        .or(query.get("token").map(String::as_str));
+    match resolve_scopes(addr, &headers, token, &config, &storage).await {
+        Some(scopes) if allows(&scopes, required) => Ok(()),
+        _ => {
+            warn!("Refused {} {} from {:?}: needs {}", method, path.as_str(), addr, required.as_str());
+            Err(warp::reject::custom(ApiError::Unauthorized))
+        }
+    }
+}
+
+// The dashboard websocket can't send headers from a browser, so the token goes in `?token=`
+pub fn authorize_websocket(
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = (Vec<ApiScope>,), Error = warp::Rejection> + Clone {
+    warp::addr::remote()
+        .and(warp::header::headers_cloned())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_config(config))
+        .and(with_storage(storage))
+        .and_then(|addr: Option<SocketAddr>, headers: HeaderMap, query: HashMap<String, String>, config: Arc<RwLock<Config>>, storage: Arc<RwLock<StorageClient>>| async move {
+            let token = query.get("token").map(String::as_str);
+            // A token can't have leaked to another site, so only connections without one are checked
+            if token.is_none() && !origin_allowed(&headers, config.read().await.web_ui_public_url.as_deref()) {
+                warn!("Refused dashboard websocket from {:?}: origin {:?}", addr, headers.get("origin"));
+                return Err(warp::reject::custom(ApiError::Unauthorized));
+            }
+            match resolve_scopes(addr, &headers, token, &config, &storage).await {
+                Some(scopes) if !scopes.is_empty() => Ok(scopes),
+                _ => {
+                    warn!("Refused dashboard websocket from {:?}", addr);
+                    Err(warp::reject::custom(ApiError::Unauthorized))
+                }
+            }
+        })
+}
+
+// Turns refused requests into a 401 instead of warp's generic rejection
+pub async fn handle_unauthorized(rejection: warp::Rejection) -> Result<impl warp::Reply, warp::Rejection> {
+    match rejection.find::<ApiError>() {
+        Some(ApiError::Unauthorized) => Ok(warp::reply::with_status(
+            warp::reply::json(&json!({ "error": "A valid API token with the right scope is required" })),
+            StatusCode::UNAUTHORIZED,
+        )),
+        _ => Err(rejection),
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct CreateTokenRequest {
+    name: String,
+    scopes: Vec<ApiScope>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct RevokeTokenRequest {
+    id: i64,
+}
+
+//   /api/tokens          GET every token (without the secret), POST {name, scopes} creates one
+//   /api/tokens/revoke   POST {id}
+pub fn token_api_routes(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let list = warp::path("tokens")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_storage(storage.clone()))
+        .and_then(handle_list_tokens);
+
+    let create = warp::path("tokens")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage.clone()))
+        .and_then(handle_create_token);
+
+    let revoke = warp::path!("tokens" / "revoke")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage))
+        .and_then(handle_revoke_token);
+
+    list.or(create).or(revoke)
+}
+
+async fn handle_list_tokens(
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.get_api_tokens() {
+        Ok(tokens) => Ok(warp::reply::json(&json!({ "tokens": tokens }))),
+        Err(e) => {
+            error!("Failed to fetch API tokens: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+// The token itself is only returned here; afterwards only its hash exists
+async fn handle_create_token(
+    request: CreateTokenRequest,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let name = request.name.trim();
+    if name.is_empty() || request.scopes.is_empty() {
+        return Ok(warp::reply::json(&json!({ "success": false, "message": "A token needs a name and at least one scope" })));
+    }
+
+    match create_token(&*storage.read().await, name, &request.scopes) {
+        Ok((id, token)) => Ok(warp::reply::json(&json!({ "success": true, "id": id, "token": token }))),
+        Err(e) => {
+            error!("Failed to create API token: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_revoke_token(
+    request: RevokeTokenRequest,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.revoke_api_token(request.id) {
+        Ok(revoked) => {
+            if revoked {
+                info!("Revoked API token {}", request.id);
+            }
+            Ok(warp::reply::json(&json!({ "success": revoked })))
+        }
+        Err(e) => {
+            error!("Failed to revoke API token {}: {:?}", request.id, e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(remote_requires_token: bool, anonymous_overlays: bool) -> ApiAccessConfig {
+        ApiAccessConfig { remote_requires_token, anonymous_overlays }
+    }
+
+    #[test]
+    fn localhost_is_always_admin() {
+        for (requires_token, overlays) in [(true, true), (true, false), (false, true), (false, false)] {
+            assert_eq!(anonymous_scopes(true, &access(requires_token, overlays)), Some(vec![ApiScope::Admin]));
+        }
+    }
+
+    #[test]
+    fn remote_without_token_requirement_is_admin_even_with_anonymous_overlays() {
+        assert_eq!(anonymous_scopes(false, &access(false, true)), Some(vec![ApiScope::Admin]));
+        assert_eq!(anonymous_scopes(false, &access(false, false)), Some(vec![ApiScope::Admin]));
+    }
+
+    #[test]
+    fn localhost_behind_a_proxy_is_not_local() {
+        let addr = Some(SocketAddr::from(([127, 0, 0, 1], 50000)));
+        let mut headers = HeaderMap::new();
+        assert!(is_local(addr, &headers, None));
+        assert!(is_local(addr, &headers, Some("")));
+        assert!(!is_local(addr, &headers, Some("https://bot.example.com")));
+        headers.insert("x-forwarded-for", "203.0.113.7".parse().unwrap());
+        assert!(!is_local(addr, &headers, None));
+    }
+
+    #[test]
+    fn websocket_origin_must_match_the_host_or_public_url() {
+        let headers = |origin: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("host", "localhost:3333".parse().unwrap());
+            headers.insert("origin", origin.parse().unwrap());
+            headers
+        };
+        assert!(origin_allowed(&HeaderMap::new(), None));
+        assert!(origin_allowed(&headers("http://localhost:3000"), None));
+        assert!(!origin_allowed(&headers("https://evil.example.com"), None));
+        assert!(!origin_allowed(&headers("null"), None));
+        assert!(origin_allowed(&headers("https://bot.example.com"), Some("https://bot.example.com/")));
+        assert!(!origin_allowed(&headers("https://bot.example.co"), Some("https://bot.example.com")));
+    }
+
+    #[test]
+    fn anonymous_overlays_only_open_the_overlay_routes() {
+        assert_eq!(anonymous_scopes(false, &access(true, true)), Some(vec![ApiScope::Overlay]));
+        assert_eq!(anonymous_scopes(false, &access(true, false)), None);
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use serde_json::json;
+use warp::Filter;
+use crate::twitch::bits_vote::BitsVotes;
+
+// Browser source for OBS, public like the leaderboard so it works without an API token:
+//   /bitsvote/overlay  live tallies with a countdown, then the winner
+//   /bitsvote/json     the running vote, or the last result
+pub fn bits_vote_overlay_routes(bits_votes: Arc<BitsVotes>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let overlay = warp::path!("bitsvote" / "overlay")
+        .and(warp::get())
+        .map(|| warp::reply::html(OVERLAY_PAGE));
+
+    let json = warp::path!("bitsvote" / "json")
+        .and(warp::get())
+        .map(move || {
+            let vote = bits_votes.latest();
+            let winner = vote.as_ref().and_then(|vote| vote.winner());
+            warp::reply::with_header(
+                warp::reply::json(&json!({ "vote": vote, "winner": winner })),
+                "Cache-Control",
+                "no-store",
+            )
+        });
+
+    overlay.or(json)
+}
+
+const OVERLAY_PAGE: &str = r#"<!DOCTYPE html><html><head><meta charset="utf-8"><style>
+html,body{background:transparent;margin:0;font-family:sans-serif;color:#fff}
+#card{display:none;width:420px;background:rgba(17,24,39,.85);border-radius:10px;padding:1rem}
+h2{margin:0 0 .6rem;font-size:1.3rem}.row{margin:.4rem 0}.label{display:flex;justify-content:space-between}
+.bar{height:.7rem;background:#374151;border-radius:4px;overflow:hidden}.fill{height:100%;background:#9146FF;transition:width .5s}
+.win .fill{background:#22c55e}#footer{margin-top:.6rem;color:#d1d5db;font-size:.9rem}
+</style></head><body><div id="card"><h2 id="question"></h2><div id="options"></div><div id="footer"></div></div>
+<script>
+// A closed vote stays on screen this long before the overlay hides itself
+const SHOW_RESULT_MS = 15000;
+const card = document.getElementById('card');
+let closedSeen = null;
+
+function render(data) {
+    const vote = data.vote;
+    if (!vote) { card.style.display = 'none'; return; }
+    if (vote.closed) {
+        if (closedSeen === null || closedSeen.id !== vote.id) closedSeen = { id: vote.id, at: Date.now() };
+        if (Date.now() - closedSeen.at > SHOW_RESULT_MS) { card.style.display = 'none'; return; }
+    }
+    card.style.display = 'block';
+    document.getElementById('question').textContent = vote.question;
+    const options = document.getElementById('options');
+    options.innerHTML = '';
+    vote.options.forEach((option, i) => {
+        const share = vote.total_bits ? option.bits * 100 / vote.total_bits : 0;
+        const row = document.createElement('div');
+        row.className = 'row' + (vote.closed && data.winner === i + 1 ? ' win' : '');
+        const label = document.createElement('div');
+        label.className = 'label';
+        const name = document.createElement('span');
+        name.textContent = (i + 1) + ') ' + option.name;
+        const bits = document.createElement('span');
+        bits.textContent = option.bits + ' bits';
+        label.append(name, bits);
+        const bar = document.createElement('div');
+        bar.className = 'bar';
+        bar.innerHTML = '<div class="fill"></div>';
+        bar.firstChild.style.width = share + '%';
+        row.append(label, bar);
+        options.appendChild(row);
+    });
+    const footer = document.getElementById('footer');
+    if (vote.closed) {
+        footer.textContent = data.winner ? 'Winner: ' + vote.options[data.winner - 1].name : 'No winner';
+    } else {
+        const left = Math.max(0, Math.round((new Date(vote.ends_at) - Date.now()) / 1000));
+        footer.textContent = 'Cheer with the option name or number · ' + left + 's left';
+    }
+}
+
+async function poll() {
+    try {
+        const res = await fetch('/bitsvote/json');
+        render(await res.json());
+    } catch (e) {
+        // The bot restarting shouldn't break the browser source; try again next tick
+    }
+}
+
+poll();
+setInterval(poll, 1000);
+</script></body></html>"#;
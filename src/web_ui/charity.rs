@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use serde_json::json;
+use warp::Filter;
+use crate::twitch::charity::Charity;
+
+// Browser source for OBS, public like the bits vote overlay:
+//   /charity/overlay  progress bar for the running charity campaign
+//   /charity/json     the campaign totals, or the last campaign's final ones
+pub fn charity_overlay_routes(charity: Arc<Charity>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let overlay = warp::path!("charity" / "overlay")
+        .and(warp::get())
+        .map(|| warp::reply::html(OVERLAY_PAGE));
+
+    let json = warp::path!("charity" / "json")
+        .and(warp::get())
+        .map(move || {
+            warp::reply::with_header(
+                warp::reply::json(&json!({ "campaign": charity.current() })),
+                "Cache-Control",
+                "no-store",
+            )
+        });
+
+    overlay.or(json)
+}
+
+const OVERLAY_PAGE: &str = r#"<!DOCTYPE html><html><head><meta charset="utf-8"><style>
+html,body{background:transparent;margin:0;font-family:sans-serif;color:#fff}
+#card{display:none;width:420px;background:rgba(17,24,39,.85);border-radius:10px;padding:1rem}
+#header{display:flex;align-items:center;gap:.6rem;margin-bottom:.6rem}#logo{width:36px;height:36px;border-radius:6px}
+h2{margin:0;font-size:1.2rem}.bar{height:.9rem;background:#374151;border-radius:4px;overflow:hidden}
+#fill{height:100%;width:0;background:#22c55e;transition:width 1s}#footer{margin-top:.5rem;color:#d1d5db;font-size:.95rem}
+</style></head><body><div id="card"><div id="header"><img id="logo" alt=""><h2 id="name"></h2></div>
+<div class="bar"><div id="fill"></div></div><div id="footer"></div></div>
+<script>
+const card = document.getElementById('card');
+
+function money(amount, currency) {
+    try {
+        return new Intl.NumberFormat(undefined, { style: 'currency', currency }).format(amount);
+    } catch (e) {
+        return amount.toFixed(2) + ' ' + currency;
+    }
+}
+
+function render(campaign) {
+    if (!campaign || !campaign.active) { card.style.display = 'none'; return; }
+    card.style.display = 'block';
+    const logo = document.getElementById('logo');
+    logo.style.display = campaign.charity_logo ? 'block' : 'none';
+    logo.src = campaign.charity_logo;
+    document.getElementById('name').textContent = campaign.charity_name;
+    const percent = campaign.target > 0 ? Math.min(100, campaign.current * 100 / campaign.target) : 0;
+    document.getElementById('fill').style.width = percent + '%';
+    document.getElementById('footer').textContent =
+        money(campaign.current, campaign.currency) + ' of ' + money(campaign.target, campaign.currency) + ' (' + Math.floor(percent) + '%)';
+}
+
+async function poll() {
+    try {
+        const res = await fetch('/charity/json');
+        render((await res.json()).campaign);
+    } catch (e) {
+        // The bot restarting shouldn't break the browser source; try again next tick
+    }
+}
+
+poll();
+setInterval(poll, 3000);
+</script></body></html>"#;
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use warp::Filter;
+use crate::twitch::command_config::{CommandConfig, CommandOverrides};
+use crate::twitch::irc::command_system::CommandRegistry;
+use crate::twitch::roles::UserRole;
+
+// A command's defaults next to whatever the dashboard has overridden
+#[derive(Debug, Serialize, JsonSchema)]
+pub(crate) struct CommandSettings {
+    name: String,
+    description: String,
+    // The plugin that added it, if it isn't built in
+    plugin: Option<String>,
+    default_role: UserRole,
+    default_cooldown_secs: u64,
+    #[serde(flatten)]
+    settings: CommandConfig,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct CommandSettingsRequest {
+    name: String,
+    #[serde(flatten)]
+    settings: CommandConfig,
+}
+
+//   /api/commands/config  GET every command with its overrides, POST {name, enabled, cooldown_secs, required_role}
+//                         to change one; null cooldown_secs or required_role goes back to the command's default
+pub fn command_config_api_routes(
+    overrides: Arc<CommandOverrides>,
+    commands: Arc<CommandRegistry>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_overrides = warp::any().map(move || overrides.clone());
+    let with_commands = warp::any().map(move || commands.clone());
+
+    let list = warp::path!("commands" / "config")
+        .and(warp::get())
+        .and(with_overrides.clone())
+        .and(with_commands.clone())
+        .map(|overrides: Arc<CommandOverrides>, commands: Arc<CommandRegistry>| {
+            let overrides = overrides.all();
+            let commands: Vec<CommandSettings> = commands.describe()
+                .into_iter()
+                .map(|command| CommandSettings {
+                    settings: overrides.get(&command.name.to_lowercase()).cloned().unwrap_or_default(),
+                    name: command.name,
+                    description: command.description,
+                    plugin: command.plugin,
+                    default_role: command.required_role,
+                    default_cooldown_secs: command.cooldown_secs,
+                })
+                .collect();
+            warp::reply::json(&json!({ "commands": commands }))
+        });
+
+    let update = warp::path!("commands" / "config")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_overrides)
+        .and(with_commands)
+        .map(|request: CommandSettingsRequest, overrides: Arc<CommandOverrides>, commands: Arc<CommandRegistry>| {
+            let exists = commands.describe().iter().any(|command| command.name.eq_ignore_ascii_case(&request.name));
+            warp::reply::json(&if exists {
+                overrides.set(&request.name, request.settings);
+                json!({ "success": true })
+            } else {
+                json!({ "success": false, "message": format!("There's no command called {}", request.name) })
+            })
+        });
+
+    list.or(update)
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::error;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::storage::StorageClient;
+use crate::twitch::command_usage::CommandAbuse;
+use crate::web_ui::api_routes::{with_storage, ApiError};
+
+const DEFAULT_DAYS: i64 = 7;
+const TOP_USERS: usize = 10;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct LiftSoftBanRequest {
+    user_id: String,
+}
+
+//   /api/commands/usage   GET per-command counts, top users and current soft-bans (?days=7)
+//   /api/commands/unban   POST {user_id}, lifts a soft-ban early
+pub fn command_usage_api_routes(
+    storage: Arc<RwLock<StorageClient>>,
+    abuse: Arc<CommandAbuse>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_abuse = warp::any().map(move || abuse.clone());
+
+    let usage = warp::path!("commands" / "usage")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage))
+        .and(with_abuse.clone())
+        .and_then(handle_command_usage);
+
+    let unban = warp::path!("commands" / "unban")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_abuse)
+        .map(|request: LiftSoftBanRequest, abuse: Arc<CommandAbuse>| {
+            warp::reply::json(&json!({ "success": abuse.lift_soft_ban(&request.user_id) }))
+        });
+
+    usage.or(unban)
+}
+
+async fn handle_command_usage(
+    query: HashMap<String, String>,
+    storage: Arc<RwLock<StorageClient>>,
+    abuse: Arc<CommandAbuse>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let days = query.get("days").and_then(|d| d.parse().ok()).unwrap_or(DEFAULT_DAYS);
+    let since = Utc::now() - Duration::days(days);
+
+    let storage = storage.read().await;
+    let report = storage.get_command_usage(since)
+        .and_then(|commands| Ok((commands, storage.get_top_command_users(since, TOP_USERS)?)));
+    match report {
+        Ok((commands, top_users)) => Ok(warp::reply::json(&json!({
+            "days": days,
+            "commands": commands,
+            "top_users": top_users,
+            "soft_bans": abuse.soft_bans(),
+        }))),
+        Err(e) => {
+            error!("Failed to fetch command usage: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::{CommandTier, Config};
+use crate::twitch::irc::command_system::CommandRegistry;
+use crate::twitch::command_config::CommandOverrides;
+use crate::twitch::irc::KeywordTriggerManager;
+use crate::twitch::roles::UserRole;
+use crate::web_ui::api_routes::{with_config, with_keyword_triggers};
+use crate::web_ui::leaderboard::escape_html;
+use crate::web_ui::pages::render_page;
+
+struct CommandRow {
+    name: String,
+    description: String,
+    permission: String,
+    cooldown: String,
+}
+
+fn permission(role: &UserRole, tier: Option<CommandTier>) -> String {
+    match tier {
+        Some(CommandTier::Subscriber) if *role == UserRole::Viewer => "Subscriber".to_string(),
+        Some(CommandTier::Vip) if *role == UserRole::Viewer => "VIP".to_string(),
+        _ => role.to_string(),
+    }
+}
+
+fn render_table(rows: &[CommandRow]) -> String {
+    let body: String = rows.iter()
+        .map(|row| format!(
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&row.name), escape_html(&row.description), row.permission, row.cooldown
+        ))
+        .collect();
+    format!("<table><tr><th>Command</th><th>Description</th><th>Who can use it</th><th>Cooldown</th></tr>{}</table>", body)
+}
+
+// Public list of every chat command, generated from the command registry and
+// the keyword triggers so it never goes stale. !commands links here.
+pub fn commands_page_route(
+    config: Arc<RwLock<Config>>,
+    keyword_triggers: Arc<KeywordTriggerManager>,
+    overrides: Arc<CommandOverrides>,
+    commands: Arc<CommandRegistry>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("commands")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_config(config))
+        .and(with_keyword_triggers(keyword_triggers))
+        .and(warp::any().map(move || overrides.clone()))
+        .and(warp::any().map(move || commands.clone()))
+        .and_then(handle_commands_page)
+}
+
+async fn handle_commands_page(
+    config: Arc<RwLock<Config>>,
+    keyword_triggers: Arc<KeywordTriggerManager>,
+    overrides: Arc<CommandOverrides>,
+    commands: Arc<CommandRegistry>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (theme, tier_overrides) = {
+        let config = config.read().await;
+        (config.ui_theme.clone(), config.command_tiers.tiers.clone())
+    };
+
+    let settings = overrides.all();
+    let builtin: Vec<CommandRow> = commands.describe()
+        .into_iter()
+        .filter_map(|command| {
+            let overrides = settings.get(&command.name.to_lowercase()).cloned().unwrap_or_default();
+            if !overrides.enabled {
+                return None;
+            }
+            let tier = tier_overrides.get(&command.name).copied().or(command.tier);
+            let role = overrides.required_role.unwrap_or(command.required_role);
+            let cooldown_secs = overrides.cooldown_secs.unwrap_or(command.cooldown_secs);
+            Some(CommandRow {
+                permission: permission(&role, tier),
+                name: command.name,
+                description: command.description,
+                cooldown: if cooldown_secs > 0 { format!("{}s", cooldown_secs) } else { "—".to_string() },
+            })
+        })
+        .collect();
+
+    // Keyword triggers that look like commands are the custom commands
+    let mut custom: Vec<CommandRow> = keyword_triggers.get_all().await
+        .into_iter()
+        .filter(|trigger| trigger.enabled && !trigger.is_regex && trigger.pattern.starts_with('!'))
+        .map(|trigger| CommandRow {
+            name: trigger.pattern,
+            description: trigger.name,
+            permission: trigger.required_role.to_string(),
+            cooldown: if trigger.cooldown_secs > 0 { format!("{}s", trigger.cooldown_secs) } else { "—".to_string() },
+        })
+        .collect();
+    custom.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut body = format!("<h2>Commands</h2>{}", render_table(&builtin));
+    if !custom.is_empty() {
+        body.push_str(&format!("<h2>Custom commands</h2>{}", render_table(&custom)));
+    }
+
+    Ok(warp::reply::html(render_page(&theme, "Chat commands", &body)))
+}
@@ -0,0 +1,328 @@
+// Streamer.bot-compatible websocket API at /compat, so automation written for
+// Streamer.bot (or SAMMI through its Streamer.bot bridge) can drive the bot while
+// a setup is being migrated. Only a subset of the protocol is implemented:
+//
+// Requests (JSON with "request" and a client-chosen "id" echoed in the reply):
+//   GetInfo                       bot name and version
+//   GetEvents                     events that can be subscribed to
+//   Subscribe / UnSubscribe       {"events": {"Twitch": ["ChatMessage", ...]}}
+//   GetActions                    channel point rewards, exposed as actions
+//   DoAction                      {"action": {"id" or "name"}, "args": {"user", "rawInput"}}
+//                                 runs the reward's handler like !testredeem does
+//
+// Connecting needs the read-stats scope and DoAction also needs run-redeems. Remote
+// clients pass their API token as `?token=`, like the dashboard websocket.
+//
+// Events are sent as {"timeStamp", "event": {"source", "type"}, "data"}, where data
+// is the chat message or the raw EventSub event. Supported Twitch events:
+// ChatMessage, Follow, Sub, ReSub, GiftSub, Cheer, Raid, RewardRedemption,
+// StreamOnline, StreamOffline, CharityDonation, CharityStarted, CharityProgress,
+// CharityCompleted.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use chrono::Utc;
+use futures_util::{SinkExt, StreamExt};
+use tracing::{debug, info, warn};
+use serde_json::{json, Value};
+use tokio::sync::{broadcast, RwLock};
+use warp::ws::{Message, WebSocket};
+use warp::Filter;
+use crate::config::Config;
+use crate::hooks::BotEvent;
+use crate::twitch::redeems::RedeemManager;
+use crate::storage::StorageClient;
+use crate::web_ui::api_routes::{with_config, ApiError};
+use crate::web_ui::api_tokens::{self, allows, ApiScope};
+use crate::web_ui::redeems::with_redeem_manager;
+
+const TWITCH_EVENTS: &[&str] = &[
+    "ChatMessage",
+    "Follow",
+    "Sub",
+    "ReSub",
+    "GiftSub",
+    "Cheer",
+    "Raid",
+    "RewardRedemption",
+    "StreamOnline",
+    "StreamOffline",
+    "CharityDonation",
+    "CharityStarted",
+    "CharityProgress",
+    "CharityCompleted",
+];
+
+#[derive(Debug, Clone)]
+struct CompatEvent {
+    source: &'static str,
+    event_type: &'static str,
+    data: Value,
+}
+
+// Events for connected compat clients, owned by TwitchManager
+pub struct CompatEvents {
+    sender: broadcast::Sender<CompatEvent>,
+}
+
+impl Default for CompatEvents {
+    fn default() -> Self {
+        Self { sender: broadcast::channel(256).0 }
+    }
+}
+
+impl CompatEvents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Registered as an event hook; translates bot events into Streamer.bot events
+    pub fn forward_event(&self, event: &BotEvent) {
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+
+        let compat_event = match event {
+            BotEvent::ChatMessage(msg) => CompatEvent {
+                source: "Twitch",
+                event_type: "ChatMessage",
+                data: json!({
+                    "message": {
+                        "msgId": msg.message_id,
+                        "userId": msg.sender.id,
+                        "username": msg.sender.login,
+                        "displayName": msg.sender.name,
+                        "channel": msg.channel_login,
+                        "message": msg.message_text,
+                    }
+                }),
+            },
+            BotEvent::EventSub { event_type, payload } => {
+                let compat_type = match event_type.as_str() {
+                    "channel.follow" => "Follow",
+                    "channel.subscribe" => "Sub",
+                    "channel.subscription.message" => "ReSub",
+                    "channel.subscription.gift" => "GiftSub",
+                    "channel.cheer" => "Cheer",
+                    "channel.raid" => "Raid",
+                    "channel.channel_points_custom_reward_redemption.add" => "RewardRedemption",
+                    "stream.online" => "StreamOnline",
+                    "stream.offline" => "StreamOffline",
+                    "channel.charity_campaign.donate" => "CharityDonation",
+                    "channel.charity_campaign.start" => "CharityStarted",
+                    "channel.charity_campaign.progress" => "CharityProgress",
+                    "channel.charity_campaign.stop" => "CharityCompleted",
+                    _ => return,
+                };
+                CompatEvent {
+                    source: "Twitch",
+                    event_type: compat_type,
+                    data: payload.clone(),
+                }
+            }
+        };
+
+        let _ = self.sender.send(compat_event);
+    }
+}
+
+pub fn compat_route(
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+    events: Arc<CompatEvents>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("compat")
+        .and(warp::path::end())
+        .and(with_config(config.clone()))
+        .and_then(|config: Arc<RwLock<Config>>| async move {
+            if config.read().await.compat_api.enabled {
+                Ok(())
+            } else {
+                Err(warp::reject::not_found())
+            }
+        })
+        .untuple_one()
+        .and(api_tokens::authorize_websocket(config, storage))
+        .and_then(|scopes: Vec<ApiScope>| async move {
+            if allows(&scopes, ApiScope::ReadStats) {
+                Ok(scopes)
+            } else {
+                warn!("Refused compat API client: needs {}", ApiScope::ReadStats.as_str());
+                Err(warp::reject::custom(ApiError::Unauthorized))
+            }
+        })
+        .and(warp::ws())
+        .and(with_redeem_manager(redeem_manager))
+        .and(warp::any().map(move || events.clone()))
+        .map(|scopes: Vec<ApiScope>, ws: warp::ws::Ws, redeem_manager, events: Arc<CompatEvents>| {
+            ws.on_upgrade(move |socket| handle_connection(socket, redeem_manager, events, scopes))
+        })
+        .recover(api_tokens::handle_unauthorized)
+}
+
+fn info_json() -> Value {
+    json!({
+        "instanceId": "mewbot",
+        "name": "mewbot",
+        "os": std::env::consts::OS,
+        "version": env!("CARGO_PKG_VERSION"),
+        "source": "websocketServer",
+    })
+}
+
+async fn handle_connection(ws: WebSocket, redeem_manager: Arc<RwLock<Option<RedeemManager>>>, events: Arc<CompatEvents>, scopes: Vec<ApiScope>) {
+    info!("Compat API client connected");
+    let (mut ws_send, mut ws_recv) = ws.split();
+    let mut events = events.sender.subscribe();
+    let mut subscriptions: HashSet<(String, String)> = HashSet::new();
+
+    let hello = json!({
+        "timeStamp": Utc::now().to_rfc3339(),
+        "request": "Hello",
+        "info": info_json(),
+    });
+    if ws_send.send(Message::text(hello.to_string())).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            message = ws_recv.next() => {
+                let message = match message {
+                    Some(Ok(message)) => message,
+                    _ => break,
+                };
+                if message.is_close() {
+                    break;
+                }
+                let text = match message.to_str() {
+                    Ok(text) => text,
+                    Err(_) => continue,
+                };
+
+                let response = match serde_json::from_str::<Value>(text) {
+                    Ok(request) => handle_request(&request, &mut subscriptions, &redeem_manager, &scopes).await,
+                    Err(e) => json!({ "status": "error", "error": format!("Invalid JSON: {}", e) }),
+                };
+                if ws_send.send(Message::text(response.to_string())).await.is_err() {
+                    break;
+                }
+            }
+            event = events.recv() => {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Compat API client fell behind, dropped {} events", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                if !subscriptions.contains(&(event.source.to_string(), event.event_type.to_string())) {
+                    continue;
+                }
+
+                let message = json!({
+                    "timeStamp": Utc::now().to_rfc3339(),
+                    "event": { "source": event.source, "type": event.event_type },
+                    "data": event.data,
+                });
+                if ws_send.send(Message::text(message.to_string())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    info!("Compat API client disconnected");
+}
+
+async fn handle_request(
+    request: &Value,
+    subscriptions: &mut HashSet<(String, String)>,
+    redeem_manager: &Arc<RwLock<Option<RedeemManager>>>,
+    scopes: &[ApiScope],
+) -> Value {
+    let id = request["id"].clone();
+    let request_type = request["request"].as_str().unwrap_or("");
+    debug!("Compat API request: {}", request_type);
+
+    match request_type {
+        "GetInfo" => json!({ "id": id, "status": "ok", "info": info_json() }),
+        "GetEvents" => json!({ "id": id, "status": "ok", "events": { "Twitch": TWITCH_EVENTS } }),
+        "Subscribe" | "UnSubscribe" => {
+            for (source, event_type) in requested_events(&request["events"]) {
+                if request_type == "Subscribe" {
+                    subscriptions.insert((source, event_type));
+                } else {
+                    subscriptions.remove(&(source, event_type));
+                }
+            }
+            json!({ "id": id, "status": "ok", "events": subscriptions_json(subscriptions) })
+        }
+        "GetActions" => {
+            let rewards = match redeem_manager.read().await.as_ref() {
+                Some(redeem_manager) => redeem_manager.testable_rewards().await,
+                None => Vec::new(),
+            };
+            let actions: Vec<Value> = rewards.iter()
+                .map(|title| json!({
+                    "id": title,
+                    "name": title,
+                    "group": "Channel Point Rewards",
+                    "enabled": true,
+                    "subaction_count": 0,
+                }))
+                .collect();
+            json!({ "id": id, "status": "ok", "count": actions.len(), "actions": actions })
+        }
+        "DoAction" => {
+            if !allows(scopes, ApiScope::RunRedeems) {
+                return json!({ "id": id, "status": "error", "error": "DoAction needs the run-redeems scope" });
+            }
+            let action = &request["action"];
+            let title = match action["name"].as_str().or_else(|| action["id"].as_str()) {
+                Some(title) => title,
+                None => return json!({ "id": id, "status": "error", "error": "Missing action id or name" }),
+            };
+            let user_name = request["args"]["user"].as_str().unwrap_or("compat");
+            let user_input = request["args"]["rawInput"].as_str()
+                .filter(|input| !input.trim().is_empty())
+                .map(String::from);
+
+            let result = match redeem_manager.read().await.as_ref() {
+                Some(redeem_manager) => redeem_manager.test_redemption(title, user_name, user_input).await,
+                None => return json!({ "id": id, "status": "error", "error": "Redeems are not initialized" }),
+            };
+            if result.success {
+                json!({ "id": id, "status": "ok" })
+            } else {
+                json!({ "id": id, "status": "error", "error": result.message })
+            }
+        }
+        _ => json!({ "id": id, "status": "error", "error": format!("Unsupported request: {}", request_type) }),
+    }
+}
+
+// {"Twitch": ["Follow", "Cheer"]} -> [("Twitch", "Follow"), ("Twitch", "Cheer")]
+fn requested_events(events: &Value) -> Vec<(String, String)> {
+    events.as_object()
+        .map(|sources| {
+            sources.iter()
+                .flat_map(|(source, types)| {
+                    types.as_array().into_iter().flatten()
+                        .filter_map(|event_type| event_type.as_str())
+                        .map(move |event_type| (source.clone(), event_type.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn subscriptions_json(subscriptions: &HashSet<(String, String)>) -> Value {
+    let mut by_source: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (source, event_type) in subscriptions {
+        by_source.entry(source.as_str()).or_default().push(event_type.as_str());
+    }
+    json!(by_source)
+}
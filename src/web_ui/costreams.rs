@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use tracing::{error, info};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::costreams::{self, CoStreamCache, MAX_COSTREAMS};
+use crate::web_ui::api_routes::{with_config, ApiError};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct CoStreamsUpdate {
+    // Logins or channel URLs
+    streams: Vec<String>,
+}
+
+//   /api/costreams  GET live status of each additional stream
+//   /api/costreams  POST {streams}, replaces the list (up to 4)
+pub fn costream_api_routes(
+    config: Arc<RwLock<Config>>,
+    api_client: Arc<TwitchAPIClient>,
+    cache: Arc<CoStreamCache>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_cache = warp::any().map(move || cache.clone());
+
+    let get_streams = warp::path("costreams")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_config(config.clone()))
+        .and(warp::any().map(move || api_client.clone()))
+        .and(with_cache.clone())
+        .and_then(handle_get_costreams);
+
+    let update_streams = warp::path("costreams")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_config(config))
+        .and(with_cache)
+        .and_then(handle_update_costreams);
+
+    get_streams.or(update_streams)
+}
+
+async fn handle_get_costreams(
+    config: Arc<RwLock<Config>>,
+    api_client: Arc<TwitchAPIClient>,
+    cache: Arc<CoStreamCache>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let additional_streams = config.read().await.additional_streams.clone();
+    match cache.statuses(&api_client, &additional_streams).await {
+        Ok(streams) => Ok(warp::reply::json(&json!({ "streams": streams }))),
+        Err(e) => {
+            error!("Failed to check co-streamers: {:?}", e);
+            Ok(warp::reply::json(&json!({ "streams": [], "error": "Couldn't reach Twitch to check who's live" })))
+        }
+    }
+}
+
+async fn handle_update_costreams(
+    update: CoStreamsUpdate,
+    config: Arc<RwLock<Config>>,
+    cache: Arc<CoStreamCache>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if update.streams.iter().any(|entry| !entry.trim().is_empty() && costreams::normalize_login(entry).is_none()) {
+        return Err(warp::reject::custom(ApiError::InvalidInput));
+    }
+    let logins = costreams::logins(&update.streams);
+    if logins.len() > MAX_COSTREAMS {
+        return Err(warp::reject::custom(ApiError::InvalidInput));
+    }
+
+    // Padded with blanks like the setup wizard writes it
+    let mut additional_streams = logins.clone();
+    additional_streams.resize(MAX_COSTREAMS, String::new());
+
+    let mut config = config.write().await;
+    config.additional_streams = additional_streams;
+    if let Err(e) = config.save() {
+        error!("Failed to save additional streams: {:?}", e);
+        return Err(warp::reject::custom(ApiError::ConfigUpdateError));
+    }
+    cache.invalidate();
+
+    info!("Co-streamers set to {:?}", logins);
+    Ok(warp::reply::json(&json!({ "success": true, "streams": logins })))
+}
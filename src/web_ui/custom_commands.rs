@@ -0,0 +1,121 @@
+use std::sync::Arc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use tracing::error;
+use warp::Filter;
+use crate::storage::StorageClient;
+use crate::twitch::irc::command_system::CommandRegistry;
+use crate::twitch::custom_commands::{check_name, check_response, normalize_name};
+use crate::web_ui::api_routes::{with_storage, ApiError};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct CustomCommandRequest {
+    // With or without the !
+    name: String,
+    response: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct RemoveCustomCommandRequest {
+    name: String,
+}
+
+//   /api/custom-commands         GET every command added with !addcom, POST {name, response} to add one
+//   /api/custom-commands/edit    POST {name, response}
+//   /api/custom-commands/remove  POST {name}
+pub fn custom_command_api_routes(
+    storage: Arc<RwLock<StorageClient>>,
+    commands: Arc<CommandRegistry>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_commands = warp::any().map(move || commands.clone());
+
+    let list = warp::path!("custom-commands")
+        .and(warp::get())
+        .and(with_storage(storage.clone()))
+        .and_then(handle_list);
+
+    let add = warp::path!("custom-commands")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage.clone()))
+        .and(with_commands.clone())
+        .and_then(|request: CustomCommandRequest, storage: Arc<RwLock<StorageClient>>, commands: Arc<CommandRegistry>| async move {
+            let name = normalize_name(&request.name);
+            let reply = match check_name(&commands, &name).and_then(|_| check_response(&request.response)) {
+                Ok(()) => match storage.read().await.add_custom_command(&name, request.response.trim(), "dashboard") {
+                    Ok(true) => {
+                        commands.set_custom(&name, request.response.trim());
+                        json!({ "success": true, "name": name })
+                    }
+                    Ok(false) => json!({ "success": false, "message": format!("{} already exists", name) }),
+                    Err(e) => {
+                        error!("Failed to add custom command {}: {:?}", name, e);
+                        return Err(warp::reject::custom(ApiError::DatabaseError));
+                    }
+                },
+                Err(reason) => json!({ "success": false, "message": reason }),
+            };
+            Ok::<_, warp::Rejection>(warp::reply::json(&reply))
+        });
+
+    let edit = warp::path!("custom-commands" / "edit")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage.clone()))
+        .and(with_commands.clone())
+        .and_then(|request: CustomCommandRequest, storage: Arc<RwLock<StorageClient>>, commands: Arc<CommandRegistry>| async move {
+            let name = normalize_name(&request.name);
+            let reply = match check_response(&request.response) {
+                Ok(()) => match storage.read().await.edit_custom_command(&name, request.response.trim()) {
+                    Ok(true) => {
+                        commands.set_custom(&name, request.response.trim());
+                        json!({ "success": true })
+                    }
+                    Ok(false) => json!({ "success": false, "message": format!("There's no {} command", name) }),
+                    Err(e) => {
+                        error!("Failed to edit custom command {}: {:?}", name, e);
+                        return Err(warp::reject::custom(ApiError::DatabaseError));
+                    }
+                },
+                Err(reason) => json!({ "success": false, "message": reason }),
+            };
+            Ok::<_, warp::Rejection>(warp::reply::json(&reply))
+        });
+
+    let remove = warp::path!("custom-commands" / "remove")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage))
+        .and(with_commands)
+        .and_then(|request: RemoveCustomCommandRequest, storage: Arc<RwLock<StorageClient>>, commands: Arc<CommandRegistry>| async move {
+            let name = normalize_name(&request.name);
+            match storage.read().await.remove_custom_command(&name) {
+                Ok(removed) => {
+                    if removed {
+                        commands.remove_custom(&name);
+                    }
+                    Ok(warp::reply::json(&json!({ "success": removed })))
+                }
+                Err(e) => {
+                    error!("Failed to remove custom command {}: {:?}", name, e);
+                    Err(warp::reject::custom(ApiError::DatabaseError))
+                }
+            }
+        });
+
+    list.or(add).or(edit).or(remove)
+}
+
+async fn handle_list(
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.get_custom_commands() {
+        Ok(commands) => Ok(warp::reply::json(&json!({ "commands": commands }))),
+        Err(e) => {
+            error!("Failed to fetch custom commands: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
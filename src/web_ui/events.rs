@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::error;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::storage::StorageClient;
+use crate::twitch::eventsub::inspector;
+use crate::twitch::profiles::StreamProfiles;
+use crate::twitch::redeems::RedeemManager;
+use crate::web_ui::api_routes::{with_config, with_storage, ApiError};
+use crate::web_ui::redeems::with_redeem_manager;
+
+const DEFAULT_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ReplayRequest {
+    id: i64,
+}
+
+//   /api/events          GET stored EventSub notifications, newest first (?type=channel.follow&limit=50)
+//   /api/events/replay   POST {id}, dry-runs a stored notification and returns the trace
+pub fn event_api_routes(
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+    profiles: Arc<StreamProfiles>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let list = warp::path("events")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage.clone()))
+        .and_then(handle_list_events);
+
+    let replay = warp::path!("events" / "replay")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_config(config))
+        .and(with_storage(storage))
+        .and(with_redeem_manager(redeem_manager))
+        .and(warp::any().map(move || profiles.clone()))
+        .and_then(handle_replay_event);
+
+    list.or(replay)
+}
+
+async fn handle_list_events(
+    query: HashMap<String, String>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let event_type = query.get("type").map(String::as_str).filter(|t| !t.is_empty());
+    let limit = query.get("limit").and_then(|l| l.parse().ok()).unwrap_or(DEFAULT_LIMIT);
+    match storage.read().await.get_eventsub_events(event_type, limit) {
+        Ok(events) => Ok(warp::reply::json(&json!({ "events": events }))),
+        Err(e) => {
+            error!("Failed to fetch stored EventSub events: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_replay_event(
+    request: ReplayRequest,
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+    profiles: Arc<StreamProfiles>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let event = match storage.read().await.get_eventsub_event(request.id) {
+        Ok(Some(event)) => event,
+        Ok(None) => return Ok(warp::reply::json(&json!({ "success": false, "message": format!("No stored event #{}", request.id) }))),
+        Err(e) => {
+            error!("Failed to fetch stored EventSub event {}: {:?}", request.id, e);
+            return Err(warp::reject::custom(ApiError::DatabaseError));
+        }
+    };
+
+    let config = config.read().await;
+    let redeem_manager = redeem_manager.read().await;
+    Ok(warp::reply::json(&match inspector::dry_run(&config, redeem_manager.as_ref(), &profiles, &event.message).await {
+        Ok(trace) => json!({ "success": true, "trace": trace }),
+        Err(message) => json!({ "success": false, "message": message }),
+    }))
+}
@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use warp::Filter;
+use crate::scheduler::{JobSettings, Scheduler};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct UpdateJobRequest {
+    name: String,
+    settings: JobSettings,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct RunJobRequest {
+    name: String,
+}
+
+fn with_scheduler(
+    scheduler: Arc<Scheduler>,
+) -> impl Filter<Extract = (Arc<Scheduler>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || scheduler.clone())
+}
+
+fn result_json(result: Result<(), String>) -> warp::reply::Json {
+    match result {
+        Ok(()) => warp::reply::json(&json!({ "success": true })),
+        Err(message) => warp::reply::json(&json!({ "success": false, "message": message })),
+    }
+}
+
+//   /api/jobs          GET every registered job with its schedule and last result
+//   /api/jobs/update   POST {name, settings}
+//   /api/jobs/run      POST {name}, runs the job now
+pub fn job_api_routes(scheduler: Arc<Scheduler>) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let list = warp::path("jobs")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_scheduler(scheduler.clone()))
+        .map(|scheduler: Arc<Scheduler>| warp::reply::json(&json!({ "jobs": scheduler.list() })));
+
+    let update = warp::path!("jobs" / "update")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_scheduler(scheduler.clone()))
+        .map(|request: UpdateJobRequest, scheduler: Arc<Scheduler>| result_json(scheduler.update(&request.name, request.settings)));
+
+    let run = warp::path!("jobs" / "run")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_scheduler(scheduler))
+        .map(|request: RunJobRequest, scheduler: Arc<Scheduler>| result_json(scheduler.run_now(&request.name)));
+
+    list.or(update).or(run)
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::RwLock;
+use tracing::error;
+use warp::Filter;
+use crate::storage::{KeyValueStore, StorageClient};
+use crate::web_ui::api_routes::{with_storage, ApiError};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct KvSetRequest {
+    key: String,
+    value: Value,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct KvKeyRequest {
+    key: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct KvIncrementRequest {
+    key: String,
+    #[serde(default = "default_step")]
+    by: i64,
+}
+
+fn default_step() -> i64 {
+    1
+}
+
+//   /api/kv                        GET the namespaces with their key counts
+//   /api/kv/<namespace>            GET every key and value, POST {key, value} to set one
+//   /api/kv/<namespace>/delete     POST {key}
+//   /api/kv/<namespace>/increment  POST {key, by}, by defaults to 1; replies with the new value
+pub fn kv_api_routes(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let namespaces = warp::path!("kv")
+        .and(warp::get())
+        .and(with_storage(storage.clone()))
+        .and_then(handle_namespaces);
+
+    let list = warp::path!("kv" / String)
+        .and(warp::get())
+        .and(with_storage(storage.clone()))
+        .and_then(handle_list);
+
+    let set = warp::path!("kv" / String)
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage.clone()))
+        .and_then(|namespace: String, request: KvSetRequest, storage: Arc<RwLock<StorageClient>>| async move {
+            let result = storage.read().await.kv_set(&namespace, &request.key, &request.value);
+            Ok::<_, warp::Rejection>(reply(result.map(|_| json!({}))))
+        });
+
+    let delete = warp::path!("kv" / String / "delete")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage.clone()))
+        .and_then(|namespace: String, request: KvKeyRequest, storage: Arc<RwLock<StorageClient>>| async move {
+            let result = storage.read().await.kv_delete(&namespace, &request.key);
+            Ok::<_, warp::Rejection>(reply(result.map(|deleted| json!({ "deleted": deleted }))))
+        });
+
+    let increment = warp::path!("kv" / String / "increment")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage))
+        .and_then(|namespace: String, request: KvIncrementRequest, storage: Arc<RwLock<StorageClient>>| async move {
+            let result = storage.read().await.kv_increment(&namespace, &request.key, request.by);
+            Ok::<_, warp::Rejection>(reply(result.map(|value| json!({ "value": value }))))
+        });
+
+    namespaces.or(list).or(set).or(delete).or(increment)
+}
+
+// Bad names and oversized values come back as a message rather than a rejection
+fn reply(result: Result<Value, Box<dyn std::error::Error + Send + Sync>>) -> warp::reply::Json {
+    warp::reply::json(&match result {
+        Ok(mut extra) => {
+            extra["success"] = json!(true);
+            extra
+        }
+        Err(e) => json!({ "success": false, "message": e.to_string() }),
+    })
+}
+
+async fn handle_namespaces(
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.kv_namespaces() {
+        Ok(namespaces) => Ok(warp::reply::json(&json!({
+            "namespaces": namespaces.into_iter()
+                .map(|(namespace, keys)| json!({ "namespace": namespace, "keys": keys }))
+                .collect::<Vec<_>>(),
+        }))),
+        Err(e) => {
+            error!("Failed to fetch key-value namespaces: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_list(
+    namespace: String,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.kv_list(&namespace) {
+        Ok(entries) => Ok(warp::reply::json(&json!({ "entries": entries }))),
+        Err(e) => {
+            error!("Failed to list key-value namespace {}: {:?}", namespace, e);
+            Err(warp::reject::custom(ApiError::InvalidInput))
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use tracing::error;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::storage::{LeaderboardEntry, StorageClient};
+use crate::web_ui::api_routes::{with_storage, ApiError};
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+
+pub struct Leaderboard {
+    pub period: &'static str,
+    pub top_chatters: Vec<LeaderboardEntry>,
+    pub top_point_spenders: Vec<LeaderboardEntry>,
+    pub top_redeemers: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub fn load(storage: &StorageClient, period: &str, limit: usize) -> rusqlite::Result<Self> {
+        let (period, lookback) = match period {
+            "week" => ("week", Duration::days(7)),
+            "month" => ("month", Duration::days(30)),
+            _ => ("day", Duration::days(1)),
+        };
+        let since = Utc::now() - lookback;
+        let limit = limit.clamp(1, MAX_LIMIT);
+
+        Ok(Self {
+            period,
+            top_chatters: storage.get_top_chatters(since, limit)?,
+            top_point_spenders: storage.get_top_point_spenders(since, limit)?,
+            top_redeemers: storage.get_top_redeemers(since, limit)?,
+        })
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "period": self.period,
+            "top_chatters": self.top_chatters,
+            "top_point_spenders": self.top_point_spenders,
+            "top_redeemers": self.top_redeemers,
+        })
+    }
+
+    fn sections(&self) -> [(&'static str, &'static str, &Vec<LeaderboardEntry>); 3] {
+        [
+            ("Top Chatters", "messages", &self.top_chatters),
+            ("Top Point Spenders", "points", &self.top_point_spenders),
+            ("Top Redeemers", "redeems", &self.top_redeemers),
+        ]
+    }
+
+    fn render_page(&self) -> String {
+        let mut body = String::new();
+        for (title, unit, entries) in self.sections() {
+            body.push_str(&format!("<section><h2>{}</h2><ol>", title));
+            if entries.is_empty() {
+                body.push_str("<li class=\"empty\">Nobody yet</li>");
+            }
+            for entry in entries.iter() {
+                body.push_str(&format!(
+                    "<li><span class=\"name\">{}</span><span class=\"value\">{} {}</span></li>",
+                    escape_html(&entry.user_name), entry.value, unit
+                ));
+            }
+            body.push_str("</ol></section>");
+        }
+
+        format!(
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Leaderboard</title><style>{}</style></head>\
+             <body><h1>Leaderboard &middot; this {}</h1>\
+             <nav><a href=\"?period=day\">Day</a> <a href=\"?period=week\">Week</a> <a href=\"?period=month\">Month</a></nav>\
+             <main>{}</main></body></html>",
+            PAGE_STYLE, self.period, body
+        )
+    }
+
+    // Transparent, auto-scrolling variant meant for an OBS browser source (end-of-stream credits)
+    fn render_embed(&self) -> String {
+        let mut body = String::new();
+        for (title, unit, entries) in self.sections() {
+            if entries.is_empty() {
+                continue;
+            }
+            body.push_str(&format!("<h2>{}</h2>", title));
+            for entry in entries.iter() {
+                body.push_str(&format!(
+                    "<p><span class=\"name\">{}</span> &middot; {} {}</p>",
+                    escape_html(&entry.user_name), entry.value, unit
+                ));
+            }
+        }
+
+        format!(
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><style>{}</style></head>\
+             <body><div class=\"credits\"><h1>Thanks for watching!</h1>{}</div></body></html>",
+            EMBED_STYLE, body
+        )
+    }
+}
+
+const PAGE_STYLE: &str = "body{background:#111827;color:#fff;font-family:sans-serif;max-width:960px;margin:0 auto;padding:2rem}\
+    h1{color:#a78bfa}nav a{color:#c4b5fd;margin-right:1rem}main{display:grid;grid-template-columns:repeat(auto-fit,minmax(260px,1fr));gap:1.5rem}\
+    section{background:#1f2937;border-radius:8px;padding:1rem}li{display:flex;justify-content:space-between;padding:.25rem 0}\
+    .value{color:#9ca3af}.empty{color:#6b7280}";
+
+const EMBED_STYLE: &str = "html,body{background:transparent;margin:0;overflow:hidden;height:100%}\
+    body{color:#fff;font-family:sans-serif;text-align:center;text-shadow:0 0 6px #000}\
+    h1{font-size:3rem}h2{color:#c4b5fd;margin-top:3rem}p{font-size:1.5rem;margin:.4rem}\
+    .credits{position:absolute;width:100%;animation:scroll 40s linear infinite}\
+    @keyframes scroll{from{transform:translateY(100vh)}to{transform:translateY(-100%)}}";
+
+pub(crate) fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+// Public leaderboard page, served outside /api so it can be shared or added to OBS:
+//   /leaderboard?period=week
+//   /leaderboard/embed?period=day
+pub fn leaderboard_routes(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let page = warp::path("leaderboard")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage.clone()))
+        .and_then(|query: HashMap<String, String>, storage: Arc<RwLock<StorageClient>>| handle_leaderboard_html(query, storage, false));
+
+    let embed = warp::path!("leaderboard" / "embed")
+        .and(warp::get())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(with_storage(storage))
+        .and_then(|query: HashMap<String, String>, storage: Arc<RwLock<StorageClient>>| handle_leaderboard_html(query, storage, true));
+
+    page.or(embed)
+}
+
+pub fn load_from_query(storage: &StorageClient, query: &HashMap<String, String>) -> rusqlite::Result<Leaderboard> {
+    let period = query.get("period").map(String::as_str).unwrap_or("day");
+    let limit = query.get("limit").and_then(|l| l.parse().ok()).unwrap_or(DEFAULT_LIMIT);
+    Leaderboard::load(storage, period, limit)
+}
+
+async fn handle_leaderboard_html(
+    query: HashMap<String, String>,
+    storage: Arc<RwLock<StorageClient>>,
+    embed: bool,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let storage = storage.read().await;
+    match load_from_query(&storage, &query) {
+        Ok(leaderboard) => Ok(warp::reply::html(if embed { leaderboard.render_embed() } else { leaderboard.render_page() })),
+        Err(e) => {
+            error!("Error building leaderboard: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
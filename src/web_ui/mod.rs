@@ -3,8 +3,41 @@ pub(crate) mod websocket;
 mod api_routes;
 mod storage_ext;
 mod config;
+mod leaderboard;
+mod theme;
+mod voice;
+mod short_links;
+mod prompts;
+mod jobs;
+mod events;
+mod command_usage;
+mod command_config;
+mod custom_commands;
+mod kv;
+mod api_tokens;
+mod openapi;
+pub(crate) mod remote;
+pub(crate) mod voice_commands;
+mod soundboard;
+mod costreams;
+mod raid_train;
+mod premiere;
+mod suggestions;
+mod watch_queue;
+mod bits_vote;
+mod charity;
+pub(crate) mod status_page;
+mod tts;
+mod pages;
+mod commands_page;
+mod redeems;
+mod osc_designer;
+pub(crate) mod oauth;
+pub(crate) mod compat;
+pub(crate) mod ticker;
 
 pub use server::WebUI;
 pub use config::WebUIConfig;
+pub use api_tokens::create_admin_token;
 
 // Re-export any other items that need to be public
\ No newline at end of file
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use serde_json::json;
+use tracing::{error, warn};
+use warp::Filter;
+use warp::path::FullPath;
+use crate::twitch::api::oauth::PendingAuthorizations;
+use crate::twitch::api::TwitchAPIClient;
+
+//   <twitch_redirect_path> GET ?code&state, where Twitch sends the broadcaster after authorizing.
+//   Public, like the pages: the state has to match an authorization the bot started.
+pub(crate) fn oauth_callback_route(
+    redirect_path: &str,
+    authorizations: Arc<PendingAuthorizations>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let redirect_path = format!("/{}", redirect_path.trim_start_matches('/'));
+    warp::get()
+        .and(warp::path::full())
+        .and_then(move |full: FullPath| {
+            let matches = full.as_str().trim_end_matches('/') == redirect_path.trim_end_matches('/');
+            async move {
+                if matches { Ok(()) } else { Err(warp::reject::not_found()) }
+            }
+        })
+        .untuple_one()
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::any().map(move || authorizations.clone()))
+        .map(handle_callback)
+}
+
+fn handle_callback(params: HashMap<String, String>, authorizations: Arc<PendingAuthorizations>) -> String {
+    let state = params.get("state").map(String::as_str).unwrap_or_default();
+    let result = match (params.get("code"), params.get("error")) {
+        (Some(code), _) => Ok(code.clone()),
+        (None, Some(error)) => Err(params.get("error_description").unwrap_or(error).clone()),
+        (None, None) => Err("Twitch didn't send a code back".to_string()),
+    };
+
+    let failure = result.as_ref().err().cloned();
+    if !authorizations.complete(state, result) {
+        warn!("Ignoring a Twitch authorization redirect nothing was waiting for");
+        return "This authorization link has expired. Start again from the bot.".to_string();
+    }
+    match failure {
+        None => "Authorization successful! You can close this window now.".to_string(),
+        Some(error) => format!("Authorization failed: {}. Please try again.", error),
+    }
+}
+
+//   /api/twitch/reauthorize  POST, starts a new authorization while the bot runs and returns the
+//   URL to open; the new tokens replace the old ones once Twitch redirects back
+pub fn twitch_auth_api_routes(
+    api_client: Arc<TwitchAPIClient>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("twitch" / "reauthorize")
+        .and(warp::post())
+        .and(warp::any().map(move || api_client.clone()))
+        .and_then(handle_reauthorize)
+}
+
+async fn handle_reauthorize(api_client: Arc<TwitchAPIClient>) -> Result<impl warp::Reply, warp::Rejection> {
+    match api_client.reauthorize() {
+        Ok(url) => Ok(warp::reply::json(&json!({ "success": true, "url": url }))),
+        Err(e) => {
+            error!("Failed to start Twitch authorization: {}", e);
+            Ok(warp::reply::json(&json!({ "success": false, "message": e.to_string() })))
+        }
+    }
+}
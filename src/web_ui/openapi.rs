@@ -0,0 +1,518 @@
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+use warp::http::Method;
+use warp::Filter;
+use crate::ai::prompts::PromptInfo;
+use crate::scheduler::JobInfo;
+use crate::storage::{ApiToken, ArchivedClip, CommandUsageStats, CustomCommand, FollowerGrowth, KvEntry, LeaderboardEntry, ModerationEvent, RewardPriceChange, SegmentTotal, ShortLink, StoredEvent, StreamHealthSample, StreamSegment, StreamSuggestion};
+use crate::twitch::command_metrics::CommandMetrics;
+use crate::twitch::command_usage::SoftBan;
+use crate::twitch::costreams::CoStream;
+use crate::twitch::raid_train::PendingRaid;
+use crate::twitch::premiere::Premiere;
+use crate::twitch::watch_queue::WatchItem;
+use crate::twitch::connection_monitor::ConnectionStats;
+use crate::twitch::eventsub::inspector::TraceStep;
+use crate::twitch::irc::triggers::KeywordTrigger;
+use crate::twitch::models::{ChainStep, DynamicPricing, RedeemAnnouncements};
+use crate::twitch::quick_poll::PollSummary;
+use crate::twitch::bits_vote::BitsVoteSummary;
+use crate::twitch::charity::CharityCampaign;
+use crate::twitch::soundboard::{Sound, SoundStatus};
+use crate::twitch::tts::PendingTts;
+use crate::config::UiTheme;
+use crate::web_ui::api_tokens::{required_scope, CreateTokenRequest, RevokeTokenRequest};
+use crate::web_ui::command_usage::LiftSoftBanRequest;
+use crate::web_ui::command_config::{CommandSettings, CommandSettingsRequest};
+use crate::web_ui::custom_commands::{CustomCommandRequest, RemoveCustomCommandRequest};
+use crate::web_ui::kv::{KvIncrementRequest, KvKeyRequest, KvSetRequest};
+use crate::web_ui::costreams::CoStreamsUpdate;
+use crate::web_ui::suggestions::SuggestionRequest;
+use crate::web_ui::watch_queue::WatchItemRequest;
+use crate::web_ui::events::ReplayRequest;
+use crate::web_ui::jobs::{RunJobRequest, UpdateJobRequest};
+use crate::web_ui::prompts::{RevertPromptRequest, SavePromptRequest, TestPromptRequest};
+use crate::web_ui::tts::{TtsItemRequest, TtsUnbanRequest};
+use crate::web_ui::redeems::{QueueEntryRequest, SaveAnnouncementsRequest, SaveChainRequest, TestRedeemRequest};
+use crate::web_ui::soundboard::SoundNameRequest;
+use crate::web_ui::theme::ThemeUpdate;
+use crate::web_ui::voice::VoiceUpdate;
+
+// Collects the operations; schemas for Rust types end up under components/schemas
+struct Spec {
+    gen: SchemaGenerator,
+    paths: Map<String, Value>,
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer" })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn object(properties: &[(&str, Value)]) -> Value {
+    let properties: Map<String, Value> = properties.iter().map(|(name, schema)| (name.to_string(), schema.clone())).collect();
+    json!({ "type": "object", "properties": properties })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn described(description: &str) -> Value {
+    json!({ "type": "object", "description": description, "additionalProperties": true })
+}
+
+// {"status": "..."}, what the older endpoints reply with
+fn status() -> Value {
+    object(&[("status", string())])
+}
+
+// {"success": bool, "message": "..."}; message is only there when something went wrong
+fn outcome(extra: &[(&str, Value)]) -> Value {
+    let mut properties = vec![("success", boolean()), ("message", string())];
+    properties.extend(extra.iter().cloned());
+    object(&properties)
+}
+
+fn query(name: &str, schema: Value, description: &str) -> Value {
+    json!({ "name": name, "in": "query", "required": false, "schema": schema, "description": description })
+}
+
+fn path_param(name: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "path", "required": true, "schema": schema })
+}
+
+impl Spec {
+    fn new() -> Self {
+        Spec {
+            gen: SchemaSettings::openapi3().into_generator(),
+            paths: Map::new(),
+        }
+    }
+
+    fn schema<T: JsonSchema>(&mut self) -> Value {
+        json!(self.gen.subschema_for::<T>())
+    }
+
+    fn list<T: JsonSchema>(&mut self) -> Value {
+        array(self.schema::<T>())
+    }
+
+    // `path` is relative to /api, with {placeholders} for path parameters
+    fn operation(&mut self, method: Method, path: &str, summary: &str, parameters: Vec<Value>, body: Option<Value>, reply: Value) -> &mut Value {
+        let scope = required_scope(&method, &format!("/api{}", path));
+        let mut operation = json!({
+            "summary": summary,
+            "description": format!("Needs the `{}` scope.", scope.as_str()),
+            "x-required-scope": scope.as_str(),
+            "security": [{ "bearer": [] }],
+            "responses": {
+                "200": { "description": "OK", "content": { "application/json": { "schema": reply } } },
+                "401": { "description": "Missing or invalid token, or the token lacks the scope" },
+            },
+        });
+        if !parameters.is_empty() {
+            operation["parameters"] = Value::Array(parameters);
+        }
+        if let Some(body) = body {
+            operation["requestBody"] = json!({ "required": true, "content": { "application/json": { "schema": body } } });
+        }
+
+        let item = self.paths.entry(format!("/api{}", path)).or_insert_with(|| json!({}));
+        let method = method.as_str().to_lowercase();
+        item[&method] = operation;
+        &mut item[&method]
+    }
+
+    fn get(&mut self, path: &str, summary: &str, parameters: Vec<Value>, reply: Value) {
+        self.operation(Method::GET, path, summary, parameters, None, reply);
+    }
+
+    fn post(&mut self, path: &str, summary: &str, body: Value, reply: Value) {
+        self.operation(Method::POST, path, summary, Vec::new(), Some(body), reply);
+    }
+}
+
+fn build_spec() -> Value {
+    let mut spec = Spec::new();
+
+    // Bot
+    spec.post("/start-bot", "Start the bot", json!({}), status());
+    spec.post("/stop-bot", "Stop the bot", json!({}), status());
+    spec.post("/update-settings", "Update bot settings", described("Settings to change"), status());
+    spec.get("/bot-status", "Bot status and uptime", Vec::new(), object(&[
+        ("status", json!({ "type": "string", "enum": ["online", "offline"] })),
+        ("uptime", string()),
+        ("active_modules", array(string())),
+    ]));
+    spec.get("/user-list", "Users seen in chat", Vec::new(), array(string()));
+    spec.get("/recent-messages", "The last chat messages", Vec::new(), array(string()));
+    spec.get("/twitch-channel", "The channel the bot joins", Vec::new(), object(&[("channel", string())]));
+    spec.get("/twitch-parent", "Allowed parents for the Twitch embed", Vec::new(), object(&[("parents", array(string()))]));
+    spec.get("/config", "The whole config", Vec::new(), described("Every section of the config file"));
+    spec.post("/config", "Replace the config", described("Every section of the config file"), status());
+
+    // Stats
+    let moderation_log = spec.list::<ModerationEvent>();
+    spec.get("/moderation-log", "Bans, timeouts and other moderation events", vec![
+        query("type", string(), "Only this event type"),
+        query("user", string(), "Only events for this user"),
+        query("limit", integer(), "Defaults to 100"),
+    ], moderation_log);
+    spec.operation(
+        Method::POST,
+        "/moderation-log/{id}/appeal",
+        "Set the appeal status of a moderation event",
+        vec![path_param("id", integer())],
+        Some(object(&[("appeal_status", json!({ "type": "string", "nullable": true }))])),
+        status(),
+    );
+    let clips = spec.list::<ArchivedClip>();
+    spec.get("/clips", "Archived clips", vec![query("limit", integer(), "Defaults to 200")], clips);
+    let entries = spec.list::<LeaderboardEntry>();
+    spec.get("/leaderboard", "Top chatters, point spenders and redeemers", vec![
+        query("period", json!({ "type": "string", "enum": ["day", "week", "month"] }), "Defaults to day"),
+        query("limit", integer(), "Entries per list"),
+    ], object(&[
+        ("period", string()),
+        ("top_chatters", entries.clone()),
+        ("top_point_spenders", entries.clone()),
+        ("top_redeemers", entries),
+    ]));
+    spec.get("/milestones", "Progress towards the next milestones", Vec::new(), object(&[
+        ("enabled", boolean()),
+        ("progress", array(object(&[
+            ("kind", string()),
+            ("current", integer()),
+            ("previous", json!({ "type": "integer", "nullable": true })),
+            ("next", json!({ "type": "integer", "nullable": true })),
+        ]))),
+        ("reached", array(object(&[("kind", string()), ("threshold", integer()), ("reached_at", string())]))),
+    ]));
+    let samples = spec.list::<StreamHealthSample>();
+    spec.get("/stream-health", "OBS health samples from the last stream", Vec::new(), samples);
+    let growth = spec.list::<FollowerGrowth>();
+    spec.get("/followers/growth", "Followers gained per stream session, oldest first", vec![query("sessions", integer(), "Defaults to 30")], growth);
+    let latest_segments = spec.list::<StreamSegment>();
+    let segment_totals = spec.list::<SegmentTotal>();
+    spec.get("/segments", "Time spent per game and VRChat world", vec![query("days", integer(), "How far back the totals go, defaults to 30")], object(&[
+        ("days", integer()),
+        ("latest", latest_segments),
+        ("totals", segment_totals),
+    ]));
+    spec.get("/channel-metrics", "Broadcast channel metrics by channel name", Vec::new(), json!({
+        "type": "object",
+        "additionalProperties": object(&[
+            ("sent", integer()),
+            ("lag_events", integer()),
+            ("lagged_messages", integer()),
+            ("blocked_sends", integer()),
+            ("block_timeouts", integer()),
+        ]),
+    }));
+    let command_metrics = spec.schema::<CommandMetrics>();
+    spec.get("/command-metrics", "Latency histograms for chat messages and each command", Vec::new(), command_metrics);
+    let connections = spec.list::<ConnectionStats>();
+    spec.get("/connections", "Uptime of each connection", Vec::new(), connections);
+    let poll = spec.schema::<PollSummary>();
+    spec.get("/quickpoll", "The running chat poll, if any", Vec::new(), object(&[("poll", poll)]));
+    let bits_vote = spec.schema::<BitsVoteSummary>();
+    spec.get("/bitsvote", "The running bits vote, if any", Vec::new(), object(&[("vote", bits_vote)]));
+    let charity = spec.schema::<CharityCampaign>();
+    spec.get("/charity", "The charity campaign's totals, if one has run", Vec::new(), object(&[("campaign", charity)]));
+    let links = spec.list::<ShortLink>();
+    spec.get("/links", "Short links and their clicks", Vec::new(), links);
+
+    // Appearance
+    let theme = object(&[
+        ("theme", spec.schema::<UiTheme>()),
+        ("accent_color", string()),
+        ("logo_url", json!({ "type": "string", "nullable": true })),
+    ]);
+    spec.get("/theme", "Dashboard theme", Vec::new(), theme.clone());
+    let theme_update = spec.schema::<ThemeUpdate>();
+    spec.post("/theme", "Change the dashboard theme", theme_update, theme.clone());
+    let upload = spec.operation(Method::POST, "/theme/logo", "Upload a logo", Vec::new(), None, theme);
+    upload["requestBody"] = json!({ "required": true, "content": { "image/*": { "schema": { "type": "string", "format": "binary" } } } });
+    let logo = spec.operation(Method::GET, "/theme/logo", "The uploaded logo", Vec::new(), None, json!({}));
+    logo["responses"]["200"] = json!({ "description": "OK", "content": { "image/*": { "schema": { "type": "string", "format": "binary" } } } });
+    let voice = object(&[("active", string()), ("voices", array(string()))]);
+    spec.get("/voice", "TTS voices", Vec::new(), voice.clone());
+    let voice_update = spec.schema::<VoiceUpdate>();
+    spec.post("/voice", "Switch the TTS voice", voice_update, voice);
+    spec.get("/pages", "Markdown pages", Vec::new(), object(&[
+        ("pages_dir", string()),
+        ("pages", array(object(&[("title", string()), ("url", string()), ("slug", string())]))),
+    ]));
+
+    // Triggers and redeems
+    let triggers = spec.list::<KeywordTrigger>();
+    spec.get("/triggers", "Keyword triggers", Vec::new(), triggers);
+    let trigger = spec.schema::<KeywordTrigger>();
+    spec.post("/triggers", "Add or replace a keyword trigger", trigger, status());
+    spec.operation(Method::DELETE, "/triggers/{name}", "Delete a keyword trigger", vec![path_param("name", string())], None, status());
+    spec.get("/redeems/testable", "Rewards that can be dry-run", Vec::new(), object(&[("rewards", array(string()))]));
+    let test_redeem = spec.schema::<TestRedeemRequest>();
+    spec.post("/redeems/test", "Run a reward as if it was redeemed", test_redeem, outcome(&[]));
+    let steps = spec.list::<ChainStep>();
+    spec.get("/redeems/chains", "Action chains", Vec::new(), object(&[
+        ("chains", array(object(&[("title", string()), ("actions", steps)]))),
+    ]));
+    let save_chain = spec.schema::<SaveChainRequest>();
+    spec.post("/redeems/chain", "Save a reward's action chain", save_chain, outcome(&[]));
+    let announcements = spec.schema::<RedeemAnnouncements>();
+    spec.get("/redeems/announcements", "Chat announcement templates by reward", Vec::new(), object(&[
+        ("rewards", array(object(&[("title", string()), ("announcements", announcements)]))),
+    ]));
+    let save_announcements = spec.schema::<SaveAnnouncementsRequest>();
+    spec.post("/redeems/announcements", "Save a reward's announcement templates", save_announcements, outcome(&[]));
+    spec.get("/redeems/queue", "Unfulfilled redemptions, next up first", Vec::new(), object(&[
+        ("entries", array(object(&[
+            ("number", integer()),
+            ("redemption", object(&[("id", string()), ("user_name", string()), ("reward_title", string()), ("user_input", string())])),
+        ]))),
+    ]));
+    let queue_entry = spec.schema::<QueueEntryRequest>();
+    spec.post("/redeems/queue/move", "Move a queued redemption to a position", queue_entry.clone(), outcome(&[]));
+    spec.post("/redeems/queue/bump", "Move a queued redemption to the front", queue_entry.clone(), outcome(&[]));
+    spec.post("/redeems/queue/skip", "Move a queued redemption to the back", queue_entry, outcome(&[]));
+    let pricing = spec.schema::<DynamicPricing>();
+    let price_changes = spec.list::<RewardPriceChange>();
+    spec.get("/redeems/pricing", "Dynamically priced rewards and their latest price changes", Vec::new(), object(&[
+        ("rewards", array(object(&[("title", string()), ("cost", integer()), ("pricing", pricing)]))),
+        ("changes", price_changes),
+    ]));
+
+    // OSC designer
+    spec.get("/osc/configs", "OSC configs by name", Vec::new(), object(&[("configs", described("OSC configs by name"))]));
+    spec.post("/osc/configs", "Save an OSC config", object(&[("key", string()), ("config", described("OSC config"))]), outcome(&[]));
+    spec.operation(
+        Method::POST,
+        "/osc/configs/{action}",
+        "Delete, rename or duplicate an OSC config",
+        vec![path_param("action", json!({ "type": "string", "enum": ["delete", "rename", "duplicate"] }))],
+        Some(object(&[("key", string()), ("new_key", string())])),
+        outcome(&[]),
+    );
+    spec.post("/osc/test", "Send an OSC config to the avatar without saving it", object(&[("config", described("OSC config"))]), outcome(&[]));
+
+    // AI prompts
+    let prompts = spec.list::<PromptInfo>();
+    spec.get("/prompts", "AI prompts with their history", Vec::new(), object(&[("prompts", prompts)]));
+    let save_prompt = spec.schema::<SavePromptRequest>();
+    spec.post("/prompts", "Save a new version of a prompt", save_prompt, outcome(&[]));
+    let revert_prompt = spec.schema::<RevertPromptRequest>();
+    spec.post("/prompts/revert", "Bring back an older version of a prompt", revert_prompt, outcome(&[]));
+    let test_prompt = spec.schema::<TestPromptRequest>();
+    spec.post("/prompts/test", "Render a prompt and optionally run it", test_prompt, outcome(&[
+        ("rendered", string()),
+        ("response", string()),
+    ]));
+
+    // Jobs
+    let jobs = spec.list::<JobInfo>();
+    spec.get("/jobs", "Scheduled jobs", Vec::new(), object(&[("jobs", jobs)]));
+    let update_job = spec.schema::<UpdateJobRequest>();
+    spec.post("/jobs/update", "Change a job's schedule", update_job, outcome(&[]));
+    let run_job = spec.schema::<RunJobRequest>();
+    spec.post("/jobs/run", "Run a job now", run_job, outcome(&[]));
+
+    // EventSub
+    let events = spec.list::<StoredEvent>();
+    spec.get("/events", "Stored EventSub notifications, newest first", vec![
+        query("type", string(), "Only this subscription type"),
+        query("limit", integer(), "Defaults to 100"),
+    ], object(&[("events", events)]));
+    let replay = spec.schema::<ReplayRequest>();
+    let trace = spec.list::<TraceStep>();
+    spec.post("/events/replay", "Dry-run a stored notification", replay, outcome(&[("trace", trace)]));
+
+    // Commands
+    let commands = spec.list::<CommandUsageStats>();
+    let top_users = spec.list::<LeaderboardEntry>();
+    let soft_bans = spec.list::<SoftBan>();
+    spec.get("/commands/usage", "Command usage and soft-bans", vec![
+        query("days", integer(), "How far back to count, defaults to 7"),
+    ], object(&[
+        ("days", integer()),
+        ("commands", commands),
+        ("top_users", top_users),
+        ("soft_bans", soft_bans),
+    ]));
+    let unban = spec.schema::<LiftSoftBanRequest>();
+    spec.post("/commands/unban", "Lift a soft-ban", unban, object(&[("success", boolean())]));
+    let command_settings = spec.list::<CommandSettings>();
+    spec.get("/commands/config", "Every command's defaults and overrides", Vec::new(), object(&[("commands", command_settings)]));
+    let command_settings_request = spec.schema::<CommandSettingsRequest>();
+    spec.post("/commands/config", "Turn a command off or override its cooldown or role", command_settings_request, outcome(&[]));
+    let custom_commands = spec.list::<CustomCommand>();
+    spec.get("/custom-commands", "Commands added with !addcom", Vec::new(), object(&[("commands", custom_commands)]));
+    let custom_command = spec.schema::<CustomCommandRequest>();
+    spec.post("/custom-commands", "Add a response command", custom_command.clone(), outcome(&[("name", string())]));
+    spec.post("/custom-commands/edit", "Change a response command", custom_command, outcome(&[]));
+    let remove_custom_command = spec.schema::<RemoveCustomCommandRequest>();
+    spec.post("/custom-commands/remove", "Remove a response command", remove_custom_command, outcome(&[]));
+
+    // Key-value store
+    spec.get("/kv", "Key-value namespaces", Vec::new(), object(&[
+        ("namespaces", array(object(&[("namespace", string()), ("keys", integer())]))),
+    ]));
+    let entries = spec.list::<KvEntry>();
+    spec.get("/kv/{namespace}", "Every key in a namespace", vec![path_param("namespace", string())], object(&[("entries", entries)]));
+    let kv_set = spec.schema::<KvSetRequest>();
+    spec.operation(Method::POST, "/kv/{namespace}", "Set a key to any JSON value", vec![path_param("namespace", string())], Some(kv_set), outcome(&[]));
+    let kv_key = spec.schema::<KvKeyRequest>();
+    spec.operation(Method::POST, "/kv/{namespace}/delete", "Delete a key", vec![path_param("namespace", string())], Some(kv_key), outcome(&[("deleted", boolean())]));
+    let kv_increment = spec.schema::<KvIncrementRequest>();
+    spec.operation(Method::POST, "/kv/{namespace}/increment", "Add to a whole-number key, counting a missing key as 0", vec![path_param("namespace", string())], Some(kv_increment), outcome(&[("value", integer())]));
+
+    // Tokens
+    let tokens = spec.list::<ApiToken>();
+    spec.get("/tokens", "API tokens", Vec::new(), object(&[("tokens", tokens)]));
+    let create_token = spec.schema::<CreateTokenRequest>();
+    spec.post("/tokens", "Create a token; it's only shown this once", create_token, outcome(&[
+        ("id", integer()),
+        ("token", string()),
+    ]));
+    let revoke_token = spec.schema::<RevokeTokenRequest>();
+    spec.post("/tokens/revoke", "Revoke a token", revoke_token, object(&[("success", boolean())]));
+    spec.post("/twitch/reauthorize", "Start a new Twitch authorization and get the URL to open", json!({}), outcome(&[("url", string())]));
+
+    // Remote
+    spec.post("/remote/pair", "Make a pairing code and QR code for the phone remote", json!({}), outcome(&[
+        ("url", string()),
+        ("qr_svg", string()),
+        ("expires_in", integer()),
+        ("warning", json!({ "type": "string", "nullable": true })),
+    ]));
+    spec.post("/remote/unpair", "End every paired remote session", json!({}), object(&[("success", boolean()), ("ended", integer())]));
+
+    // Soundboard
+    let sounds = spec.list::<SoundStatus>();
+    spec.get("/soundboard", "Soundboard sounds with their cooldowns", Vec::new(), object(&[
+        ("enabled", boolean()),
+        ("target_loudness_db", json!({ "type": "number" })),
+        ("sounds", sounds),
+    ]));
+    let sound = spec.schema::<Sound>();
+    spec.post("/soundboard", "Add a sound, or replace the one with the same name", sound, outcome(&[]));
+    let sound_name = spec.schema::<SoundNameRequest>();
+    spec.post("/soundboard/remove", "Remove a sound", sound_name.clone(), object(&[("success", boolean())]));
+    spec.post("/soundboard/play", "Play a sound now, skipping cooldowns and costs", sound_name, outcome(&[]));
+
+    // Voice commands
+    spec.post("/voice/heard", "Pass on what the speech recognizer heard", object(&[("text", string())]), outcome(&[
+        ("status", string()),
+    ]));
+
+    // Co-streams
+    let costreams = spec.list::<CoStream>();
+    spec.get("/costreams", "Live status of the additional streams", Vec::new(), object(&[
+        ("streams", costreams),
+        ("error", string()),
+    ]));
+    let costreams_update = spec.schema::<CoStreamsUpdate>();
+    spec.post("/costreams", "Replace the additional streams (up to 4)", costreams_update, outcome(&[("streams", array(string()))]));
+
+    // Raid train
+    let train = spec.list::<CoStream>();
+    let next_stop = spec.schema::<CoStream>();
+    let pending_raid = spec.schema::<PendingRaid>();
+    spec.get("/raid-train", "Who's live on the raid train, the next stop and any raid waiting on confirmation", Vec::new(), object(&[
+        ("enabled", boolean()),
+        ("channels", train),
+        ("next", next_stop),
+        ("pending", pending_raid),
+        ("error", string()),
+    ]));
+    spec.post("/raid-train/confirm", "Raid the pending target now", json!({}), object(&[("success", boolean())]));
+    spec.post("/raid-train/cancel", "Drop the pending raid", json!({}), object(&[("success", boolean())]));
+
+    // Premiere countdown
+    let premiere = spec.schema::<Premiere>();
+    spec.get("/premiere", "The premiere countdown in progress and the scene and title it switches to", Vec::new(), object(&[
+        ("countdown_secs", integer()),
+        ("content_scene", string()),
+        ("title", string()),
+        ("current", premiere.clone()),
+    ]));
+    spec.post("/premiere/start", "Count down in chat, Discord and the VRChat chatbox, then switch the OBS scene and stream title", json!({}), outcome(&[("premiere", premiere)]));
+    spec.post("/premiere/cancel", "Stop the countdown before anything changes", json!({}), object(&[("success", boolean())]));
+
+    // Title and tag suggestions
+    let suggestions = spec.list::<StreamSuggestion>();
+    spec.get("/suggestions", "Viewer title and tag suggestions waiting for approval", Vec::new(), object(&[
+        ("enabled", boolean()),
+        ("pending", suggestions),
+    ]));
+    let suggestion = spec.schema::<SuggestionRequest>();
+    spec.post("/suggestions/approve", "Apply a suggestion to the channel and credit the viewer in chat", suggestion.clone(), outcome(&[]));
+    spec.post("/suggestions/reject", "Drop a suggestion", suggestion, object(&[("success", boolean())]));
+
+    // Watch queue
+    let watch_queue = spec.list::<WatchItem>();
+    let now_watching = spec.schema::<WatchItem>();
+    spec.get("/watch", "The video being watched and the queue", Vec::new(), object(&[
+        ("enabled", boolean()),
+        ("current", now_watching.clone()),
+        ("queue", watch_queue),
+    ]));
+    spec.post("/watch/next", "Move the next video up and show it in the chatbox and chat", json!({}), object(&[
+        ("success", boolean()),
+        ("current", now_watching),
+    ]));
+    let watch_item = spec.schema::<WatchItemRequest>();
+    spec.post("/watch/remove", "Take a video out of the queue", watch_item, object(&[("success", boolean())]));
+    spec.post("/watch/clear", "Empty the queue and forget what was watched", json!({}), object(&[("success", boolean())]));
+
+    // TTS moderation
+    let pending_tts = spec.list::<PendingTts>();
+    spec.get("/tts", "Held TTS messages and users banned from TTS", Vec::new(), object(&[
+        ("pending", pending_tts),
+        ("bans", array(string())),
+    ]));
+    let tts_item = spec.schema::<TtsItemRequest>();
+    spec.post("/tts/approve", "Play a held TTS message now", tts_item.clone(), object(&[("success", boolean())]));
+    spec.post("/tts/reject", "Drop a held TTS message, refunding redeems if configured", tts_item, object(&[("success", boolean())]));
+    spec.post("/tts/skip", "Stop the TTS message being read out", json!({}), outcome(&[("listeners", integer())]));
+    let tts_unban = spec.schema::<TtsUnbanRequest>();
+    spec.post("/tts/unban", "Let a user use TTS again", tts_unban, object(&[("success", boolean())]));
+
+    spec.get("/openapi.json", "This document", Vec::new(), described("OpenAPI 3.0 document"));
+
+    let schemas = spec.gen.take_definitions();
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "mewbot dashboard API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "Requests from localhost need no token. Anything else sends `Authorization: Bearer <token>` \
+                (or `?token=`); GET requests need `read-stats`, except the overlay routes which `overlay` tokens can \
+                also read, and everything else needs `admin`.",
+        },
+        "paths": spec.paths,
+        "components": {
+            "schemas": schemas,
+            "securitySchemes": {
+                "bearer": { "type": "http", "scheme": "bearer" },
+            },
+        },
+    })
+}
+
+// /api/openapi.json (GET)
+pub fn openapi_route() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("openapi.json")
+        .and(warp::path::end())
+        .and(warp::get())
+        .map(|| warp::reply::json(&build_spec()))
+}
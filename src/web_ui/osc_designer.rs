@@ -0,0 +1,152 @@
+use std::sync::Arc;
+use tracing::{error, info};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::osc::osc_config::{osc_config_path, validate_config};
+use crate::osc::{OSCConfig, OSCConfigurations, VRChatOSC};
+
+#[derive(Debug, Deserialize)]
+struct SaveConfigRequest {
+    key: String,
+    config: OSCConfig,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyRequest {
+    key: String,
+    #[serde(default)]
+    new_key: Option<String>,
+}
+
+fn with_osc_configs(
+    osc_configs: Arc<RwLock<OSCConfigurations>>,
+) -> impl Filter<Extract = (Arc<RwLock<OSCConfigurations>>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || osc_configs.clone())
+}
+
+fn with_vrchat_osc(
+    vrchat_osc: Arc<VRChatOSC>,
+) -> impl Filter<Extract = (Arc<VRChatOSC>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || vrchat_osc.clone())
+}
+
+// Editor for osc_config.json. Keys travel in the body since they're event types and reward titles:
+//   /api/osc/configs            GET all, POST {key, config} to create or replace
+//   /api/osc/configs/delete     POST {key}
+//   /api/osc/configs/rename     POST {key, new_key}
+//   /api/osc/configs/duplicate  POST {key, new_key}
+//   /api/osc/test               POST {config}, fires it at the avatar without saving
+pub fn osc_designer_routes(
+    osc_configs: Arc<RwLock<OSCConfigurations>>,
+    vrchat_osc: Arc<VRChatOSC>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let list = warp::path!("osc" / "configs")
+        .and(warp::get())
+        .and(with_osc_configs(osc_configs.clone()))
+        .and_then(handle_list_configs);
+
+    let save = warp::path!("osc" / "configs")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_osc_configs(osc_configs.clone()))
+        .and_then(handle_save_config);
+
+    let modify = warp::path!("osc" / "configs" / String)
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_osc_configs(osc_configs))
+        .and_then(handle_modify_config);
+
+    let test = warp::path!("osc" / "test")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_vrchat_osc(vrchat_osc))
+        .and_then(handle_test_config);
+
+    list.or(save).or(modify).or(test)
+}
+
+fn result_json(result: Result<(), String>) -> warp::reply::Json {
+    match result {
+        Ok(()) => warp::reply::json(&json!({ "success": true })),
+        Err(message) => warp::reply::json(&json!({ "success": false, "message": message })),
+    }
+}
+
+fn save_configs(configs: &OSCConfigurations) -> Result<(), String> {
+    configs.save(&osc_config_path()).map_err(|e| {
+        error!("Failed to save OSC configs: {}", e);
+        "Failed to save osc_config.json".to_string()
+    })
+}
+
+async fn handle_list_configs(
+    osc_configs: Arc<RwLock<OSCConfigurations>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let configs = osc_configs.read().await;
+    Ok(warp::reply::json(&json!({ "configs": configs.configs })))
+}
+
+async fn handle_save_config(
+    request: SaveConfigRequest,
+    osc_configs: Arc<RwLock<OSCConfigurations>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let key = request.key.trim();
+    if key.is_empty() {
+        return Ok(result_json(Err("Name can't be empty".to_string())));
+    }
+    if let Err(message) = validate_config(&request.config) {
+        return Ok(result_json(Err(message)));
+    }
+
+    let mut configs = osc_configs.write().await;
+    configs.add_config(key, request.config);
+    info!("Saved OSC config {}", key);
+    Ok(result_json(save_configs(&configs)))
+}
+
+async fn handle_modify_config(
+    action: String,
+    request: KeyRequest,
+    osc_configs: Arc<RwLock<OSCConfigurations>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let new_key = request.new_key.as_deref().map(str::trim).filter(|k| !k.is_empty());
+
+    let mut configs = osc_configs.write().await;
+    let changed = match (action.as_str(), new_key) {
+        ("delete", _) => configs.remove_config(&request.key),
+        ("rename", Some(new_key)) => configs.rename_config(&request.key, new_key),
+        ("duplicate", Some(new_key)) => configs.duplicate_config(&request.key, new_key),
+        ("rename", None) | ("duplicate", None) => return Ok(result_json(Err("New name can't be empty".to_string()))),
+        _ => return Err(warp::reject::not_found()),
+    };
+    if !changed {
+        return Ok(result_json(Err(format!("Couldn't {} {}: it doesn't exist or the new name is taken", action, request.key))));
+    }
+
+    info!("OSC config {}: {} {:?}", action, request.key, new_key);
+    Ok(result_json(save_configs(&configs)))
+}
+
+async fn handle_test_config(
+    request: serde_json::Value,
+    vrchat_osc: Arc<VRChatOSC>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let config: OSCConfig = match serde_json::from_value(request["config"].clone()) {
+        Ok(config) => config,
+        Err(e) => return Ok(result_json(Err(format!("Invalid config: {}", e)))),
+    };
+    if let Err(message) = validate_config(&config) {
+        return Ok(result_json(Err(message)));
+    }
+
+    // The reset after execution_duration can take a while, so don't hold the request open for it
+    tokio::spawn(async move {
+        if let Err(e) = vrchat_osc.send_osc_message_with_reset(&config).await {
+            error!("Failed to test-fire OSC config {}: {}", config.osc_endpoint, e);
+        }
+    });
+    Ok(result_json(Ok(())))
+}
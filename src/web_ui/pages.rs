@@ -0,0 +1,172 @@
+use std::path::Path;
+use std::sync::Arc;
+use tracing::error;
+use pulldown_cmark::{html, Options, Parser};
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::{Config, UiTheme, UiThemeConfig};
+use crate::web_ui::api_routes::with_config;
+use crate::web_ui::theme::is_valid_accent_color;
+
+// Page names map straight to file names, so only allow characters that can't escape the directory
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty() && slug.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn page_title(slug: &str) -> String {
+    let mut title = slug.replace(['-', '_'], " ");
+    if let Some(first) = title.get(0..1) {
+        title.replace_range(0..1, &first.to_uppercase());
+    }
+    title
+}
+
+async fn list_pages(pages_dir: &str) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut entries = match tokio::fs::read_dir(pages_dir).await {
+        Ok(entries) => entries,
+        Err(_) => return pages,
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        if let Some(slug) = path.file_stem().and_then(|s| s.to_str()) {
+            if is_valid_slug(slug) {
+                pages.push(slug.to_string());
+            }
+        }
+    }
+
+    pages.sort();
+    pages
+}
+
+fn render_markdown(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut output = String::new();
+    html::push_html(&mut output, Parser::new_ext(markdown, options));
+    output
+}
+
+pub(crate) fn render_page(theme: &UiThemeConfig, title: &str, body: &str) -> String {
+    render_page_with_head(theme, title, "", body)
+}
+
+// (background, panel, text, accent) for the configured theme
+pub(crate) fn theme_colors(theme: &UiThemeConfig) -> (&'static str, &'static str, &'static str, &str) {
+    let (background, panel, text) = match theme.theme {
+        UiTheme::Dark => ("#111827", "#1f2937", "#ffffff"),
+        UiTheme::Light => ("#f3f4f6", "#ffffff", "#111827"),
+    };
+    // The config file can be edited by hand, so don't trust the color blindly
+    let accent = if is_valid_accent_color(&theme.accent_color) { theme.accent_color.as_str() } else { "#9146FF" };
+    (background, panel, text, accent)
+}
+
+// Same as render_page with extra tags in <head>, like link preview metadata
+pub(crate) fn render_page_with_head(theme: &UiThemeConfig, title: &str, head: &str, body: &str) -> String {
+    let (background, panel, text, accent) = theme_colors(theme);
+    let logo = if theme.logo_path.is_some() {
+        "<img class=\"logo\" src=\"/theme/logo\" alt=\"\">"
+    } else {
+        ""
+    };
+
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>{title}</title>{head}<style>\
+         body{{background:{background};color:{text};font-family:sans-serif;max-width:860px;margin:0 auto;padding:2rem}}\
+         header{{display:flex;align-items:center;gap:1rem}}.logo{{max-height:64px}}\
+         main{{background:{panel};border-radius:8px;padding:1.5rem}}\
+         h1,h2,h3,a{{color:{accent}}}table{{border-collapse:collapse}}td,th{{border:1px solid {accent};padding:.4rem .8rem}}\
+         code{{background:{background};padding:.1rem .3rem;border-radius:4px}}\
+         </style></head><body><header>{logo}<h1>{title}</h1></header><main>{body}</main></body></html>",
+        title = title,
+        head = head,
+        background = background,
+        panel = panel,
+        text = text,
+        accent = accent,
+        logo = logo,
+        body = body,
+    )
+}
+
+// Public pages rendered from markdown files in the configured directory:
+//   /pages          index of all pages
+//   /pages/commands renders <pages_dir>/commands.md
+pub fn pages_routes(
+    config: Arc<RwLock<Config>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let index = warp::path("pages")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_config(config.clone()))
+        .and_then(handle_pages_index);
+
+    let page = warp::path!("pages" / String)
+        .and(warp::get())
+        .and(with_config(config))
+        .and_then(handle_page);
+
+    index.or(page)
+}
+
+// /api/pages, so the dashboard can link to the available pages
+pub fn pages_api_route(
+    config: Arc<RwLock<Config>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("pages")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_config(config))
+        .and_then(handle_list_pages)
+}
+
+async fn handle_list_pages(config: Arc<RwLock<Config>>) -> Result<impl warp::Reply, warp::Rejection> {
+    let pages_dir = config.read().await.ui_theme.pages_dir.clone();
+    let pages: Vec<_> = list_pages(&pages_dir).await
+        .into_iter()
+        .map(|slug| json!({ "title": page_title(&slug), "url": format!("/pages/{}", slug), "slug": slug }))
+        .collect();
+    Ok(warp::reply::json(&json!({ "pages_dir": pages_dir, "pages": pages })))
+}
+
+async fn handle_pages_index(config: Arc<RwLock<Config>>) -> Result<impl warp::Reply, warp::Rejection> {
+    let theme = config.read().await.ui_theme.clone();
+    let pages = list_pages(&theme.pages_dir).await;
+
+    let body = if pages.is_empty() {
+        "<p>No pages yet.</p>".to_string()
+    } else {
+        let items: String = pages.iter()
+            .map(|slug| format!("<li><a href=\"/pages/{}\">{}</a></li>", slug, page_title(slug)))
+            .collect();
+        format!("<ul>{}</ul>", items)
+    };
+
+    Ok(warp::reply::html(render_page(&theme, "Pages", &body)))
+}
+
+async fn handle_page(slug: String, config: Arc<RwLock<Config>>) -> Result<impl warp::Reply, warp::Rejection> {
+    if !is_valid_slug(&slug) {
+        return Err(warp::reject::not_found());
+    }
+
+    let theme = config.read().await.ui_theme.clone();
+    let path = Path::new(&theme.pages_dir).join(format!("{}.md", slug));
+
+    match tokio::fs::read_to_string(&path).await {
+        Ok(markdown) => Ok(warp::reply::html(render_page(&theme, &page_title(&slug), &render_markdown(&markdown)))),
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                error!("Failed to read page {:?}: {:?}", path, e);
+            }
+            Err(warp::reject::not_found())
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::twitch::{premiere, TwitchManager};
+use crate::web_ui::api_routes::{with_config, with_twitch_manager};
+
+//   /api/premiere         GET the countdown in progress and what happens at zero
+//   /api/premiere/start   POST, counts down in chat, Discord and the chatbox, then switches scene and title
+//   /api/premiere/cancel  POST, stops the countdown before anything changes
+pub fn premiere_api_routes(
+    config: Arc<RwLock<Config>>,
+    twitch_manager: Arc<TwitchManager>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let status = warp::path!("premiere")
+        .and(warp::get())
+        .and(with_config(config.clone()))
+        .and(with_twitch_manager(twitch_manager.clone()))
+        .and_then(handle_get_premiere);
+
+    let start = warp::path!("premiere" / "start")
+        .and(warp::post())
+        .and(with_config(config))
+        .and(with_twitch_manager(twitch_manager.clone()))
+        .and_then(handle_start_premiere);
+
+    let cancel = warp::path!("premiere" / "cancel")
+        .and(warp::post())
+        .and(with_twitch_manager(twitch_manager))
+        .map(|twitch_manager: Arc<TwitchManager>| warp::reply::json(&json!({ "success": twitch_manager.premieres.cancel().is_some() })));
+
+    status.or(start).or(cancel)
+}
+
+async fn handle_get_premiere(config: Arc<RwLock<Config>>, twitch_manager: Arc<TwitchManager>) -> Result<impl warp::Reply, warp::Rejection> {
+    let premiere_config = config.read().await.premiere.clone();
+    Ok(warp::reply::json(&json!({
+        "countdown_secs": premiere_config.countdown_secs,
+        "content_scene": premiere_config.content_scene,
+        "title": premiere_config.title,
+        "current": twitch_manager.premieres.current(),
+    })))
+}
+
+async fn handle_start_premiere(config: Arc<RwLock<Config>>, twitch_manager: Arc<TwitchManager>) -> Result<impl warp::Reply, warp::Rejection> {
+    Ok(match premiere::start(&config, twitch_manager).await {
+        Ok(premiere) => warp::reply::json(&json!({ "success": true, "premiere": premiere })),
+        Err(message) => warp::reply::json(&json!({ "success": false, "message": message })),
+    })
+}
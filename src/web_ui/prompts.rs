@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::error;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use warp::Filter;
+use crate::ai::prompts::Prompts;
+use crate::ai::AIClient;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SavePromptRequest {
+    key: String,
+    text: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct RevertPromptRequest {
+    key: String,
+    version: usize,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct TestPromptRequest {
+    key: String,
+    text: String,
+    #[serde(default)]
+    vars: HashMap<String, String>,
+    // Sent as the viewer's question for redeem prompts
+    #[serde(default)]
+    input: String,
+    // Only render the preview unless this is set, since running it costs an AI request
+    #[serde(default)]
+    run: bool,
+}
+
+fn with_ai_client(
+    ai_client: Option<Arc<AIClient>>,
+) -> impl Filter<Extract = (Option<Arc<AIClient>>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || ai_client.clone())
+}
+
+fn with_prompts(
+    prompts: Arc<Prompts>,
+) -> impl Filter<Extract = (Arc<Prompts>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || prompts.clone())
+}
+
+fn result_json(result: Result<(), String>) -> warp::reply::Json {
+    match result {
+        Ok(()) => warp::reply::json(&json!({ "success": true })),
+        Err(message) => warp::reply::json(&json!({ "success": false, "message": message })),
+    }
+}
+
+//   /api/prompts          GET all with their history, POST {key, text} to save a new version
+//   /api/prompts/revert   POST {key, version}
+//   /api/prompts/test     POST {key, text, vars, input, run}, renders and optionally runs unsaved text
+pub fn prompt_api_routes(
+    ai_client: Option<Arc<AIClient>>,
+    prompts: Arc<Prompts>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let list = warp::path("prompts")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_prompts(prompts.clone()))
+        .map(|prompts: Arc<Prompts>| warp::reply::json(&json!({ "prompts": prompts.list() })));
+
+    let save = warp::path("prompts")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_prompts(prompts.clone()))
+        .map(|request: SavePromptRequest, prompts: Arc<Prompts>| result_json(prompts.save(&request.key, &request.text)));
+
+    let revert = warp::path!("prompts" / "revert")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_prompts(prompts.clone()))
+        .map(|request: RevertPromptRequest, prompts: Arc<Prompts>| result_json(prompts.revert(&request.key, request.version)));
+
+    let test = warp::path!("prompts" / "test")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_ai_client(ai_client))
+        .and(with_prompts(prompts))
+        .and_then(handle_test_prompt);
+
+    list.or(save).or(revert).or(test)
+}
+
+async fn handle_test_prompt(
+    request: TestPromptRequest,
+    ai_client: Option<Arc<AIClient>>,
+    prompts: Arc<Prompts>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let vars: Vec<(&str, &str)> = request.vars.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+    let rendered = prompts.render_text(&request.text, &vars);
+    if !request.run {
+        return Ok(warp::reply::json(&json!({ "success": true, "rendered": rendered })));
+    }
+
+    let ai_client = match ai_client {
+        Some(ai_client) => ai_client,
+        None => return Ok(warp::reply::json(&json!({ "success": false, "rendered": rendered, "message": "No AI provider is configured" }))),
+    };
+
+    // Mirrors how each prompt is used by its redeem or command
+    let result = match request.key.as_str() {
+        "redeem.grok" => ai_client.generate_grok_response(&request.input, &rendered).await,
+        key if key.starts_with("redeem.") => {
+            ai_client.generate_response_without_history(&format!("{}User's question: {}", rendered, request.input)).await
+        }
+        _ => ai_client.generate_response_without_history(&rendered).await,
+    };
+
+    match result {
+        Ok(response) => Ok(warp::reply::json(&json!({ "success": true, "rendered": rendered, "response": response }))),
+        Err(e) => {
+            error!("Prompt test for {} failed: {}", request.key, e);
+            Ok(warp::reply::json(&json!({ "success": false, "rendered": rendered, "message": e.to_string() })))
+        }
+    }
+}
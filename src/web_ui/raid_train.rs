@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use tracing::error;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::costreams::CoStreamCache;
+use crate::twitch::raid_train::{self, RaidTrain};
+use crate::web_ui::api_routes::with_config;
+
+//   /api/raid-train          GET who's live on the train, the next stop and any pending raid
+//   /api/raid-train/confirm  POST, raids the pending target now
+//   /api/raid-train/cancel   POST, drops the pending raid
+// Raids are suggested with !raidtrain in chat, which is where the replies and countdown go.
+pub fn raid_train_api_routes(
+    config: Arc<RwLock<Config>>,
+    api_client: Arc<TwitchAPIClient>,
+    raid_train: Arc<RaidTrain>,
+    costreams: Arc<CoStreamCache>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_raid_train = warp::any().map(move || raid_train.clone());
+
+    let status = warp::path!("raid-train")
+        .and(warp::get())
+        .and(with_config(config))
+        .and(warp::any().map(move || api_client.clone()))
+        .and(with_raid_train.clone())
+        .and(warp::any().map(move || costreams.clone()))
+        .and_then(handle_get_raid_train);
+
+    let confirm = warp::path!("raid-train" / "confirm")
+        .and(warp::post())
+        .and(with_raid_train.clone())
+        .map(|train: Arc<RaidTrain>| warp::reply::json(&json!({ "success": train.confirm().is_some() })));
+
+    let cancel = warp::path!("raid-train" / "cancel")
+        .and(warp::post())
+        .and(with_raid_train)
+        .map(|train: Arc<RaidTrain>| warp::reply::json(&json!({ "success": train.cancel().is_some() })));
+
+    status.or(confirm).or(cancel)
+}
+
+async fn handle_get_raid_train(
+    config: Arc<RwLock<Config>>,
+    api_client: Arc<TwitchAPIClient>,
+    train: Arc<RaidTrain>,
+    costreams: Arc<CoStreamCache>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (train_config, own_login) = {
+        let config = config.read().await;
+        (config.raid_train.clone(), config.twitch_channel_to_join.clone().unwrap_or_default())
+    };
+    let pending = train.pending();
+
+    match raid_train::statuses(&costreams, &api_client, &train_config).await {
+        Ok(channels) => Ok(warp::reply::json(&json!({
+            "enabled": train_config.enabled,
+            "next": raid_train::next_stop(&own_login, &channels),
+            "channels": channels,
+            "pending": pending,
+        }))),
+        Err(e) => {
+            error!("Failed to check the raid train: {:?}", e);
+            Ok(warp::reply::json(&json!({
+                "enabled": train_config.enabled,
+                "next": null,
+                "channels": [],
+                "pending": pending,
+                "error": "Couldn't reach Twitch to check who's live",
+            })))
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use std::sync::Arc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use tracing::error;
+use warp::Filter;
+use crate::storage::StorageClient;
+use crate::twitch::models::{ChainStep, RedeemAnnouncements};
+use crate::twitch::redeems::RedeemManager;
+use crate::web_ui::api_routes::{with_storage, ApiError};
+
+// How much of the price change audit trail /api/redeems/pricing returns
+const PRICE_CHANGE_LIMIT: usize = 100;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct TestRedeemRequest {
+    title: String,
+    #[serde(default)]
+    user_input: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SaveChainRequest {
+    title: String,
+    #[serde(default)]
+    actions: Vec<ChainStep>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SaveAnnouncementsRequest {
+    title: String,
+    #[serde(default)]
+    announcements: RedeemAnnouncements,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct QueueEntryRequest {
+    redemption_id: String,
+    // Only used by /redeems/queue/move; 0 is next up
+    #[serde(default)]
+    position: usize,
+}
+
+pub(crate) fn with_redeem_manager(
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> impl Filter<Extract = (Arc<RwLock<Option<RedeemManager>>>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || redeem_manager.clone())
+}
+
+// /api/redeems/testable (GET) and /api/redeems/test (POST), for dry-running rewards from the dashboard,
+// /api/redeems/chains (GET) and /api/redeems/chain (POST) for editing action chains,
+// /api/redeems/announcements (GET, POST) for each reward's chat announcement templates,
+// and /api/redeems/queue (GET) with /move, /bump and /skip (POST) to reorder unfulfilled redemptions
+pub fn redeem_api_routes(
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let testable = warp::path!("redeems" / "testable")
+        .and(warp::get())
+        .and(with_redeem_manager(redeem_manager.clone()))
+        .and_then(handle_testable_rewards);
+
+    let test = warp::path!("redeems" / "test")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_redeem_manager(redeem_manager.clone()))
+        .and_then(handle_test_redeem);
+
+    let chains = warp::path!("redeems" / "chains")
+        .and(warp::get())
+        .and(with_redeem_manager(redeem_manager.clone()))
+        .and_then(handle_get_chains);
+
+    let save_chain = warp::path!("redeems" / "chain")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_redeem_manager(redeem_manager.clone()))
+        .and_then(handle_save_chain);
+
+    let announcements = warp::path!("redeems" / "announcements")
+        .and(warp::get())
+        .and(with_redeem_manager(redeem_manager.clone()))
+        .and_then(handle_get_announcements);
+
+    let save_announcements = warp::path!("redeems" / "announcements")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_redeem_manager(redeem_manager.clone()))
+        .and_then(handle_save_announcements);
+
+    let queue = warp::path!("redeems" / "queue")
+        .and(warp::get())
+        .and(with_redeem_manager(redeem_manager.clone()))
+        .and_then(handle_get_queue);
+
+    let reorder = warp::path!("redeems" / "queue" / String)
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_redeem_manager(redeem_manager))
+        .and_then(handle_reorder_queue);
+
+    testable.or(test).or(chains).or(save_chain).or(announcements).or(save_announcements)
+        .or(queue).or(reorder)
+}
+
+async fn handle_testable_rewards(
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let rewards = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => redeem_manager.testable_rewards().await,
+        None => Vec::new(),
+    };
+    Ok(warp::reply::json(&json!({ "rewards": rewards })))
+}
+
+async fn handle_test_redeem(
+    request: TestRedeemRequest,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let result = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => {
+            let user_input = request.user_input.filter(|input| !input.trim().is_empty());
+            redeem_manager.test_redemption(&request.title, "dashboard", user_input).await
+        }
+        None => return Ok(warp::reply::json(&json!({ "success": false, "message": "Redeems are not initialized" }))),
+    };
+    Ok(warp::reply::json(&json!({ "success": result.success, "message": result.message })))
+}
+
+async fn handle_get_chains(
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let chains = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => redeem_manager.action_chains().await,
+        None => Vec::new(),
+    };
+    let chains: Vec<_> = chains.into_iter()
+        .map(|(title, actions)| json!({ "title": title, "actions": actions }))
+        .collect();
+    Ok(warp::reply::json(&json!({ "chains": chains })))
+}
+
+async fn handle_save_chain(
+    request: SaveChainRequest,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let result = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => redeem_manager.save_action_chain(&request.title, request.actions).await,
+        None => return Ok(warp::reply::json(&json!({ "success": false, "message": "Redeems are not initialized" }))),
+    };
+    Ok(warp::reply::json(&match result {
+        Ok(()) => json!({ "success": true }),
+        Err(e) => json!({ "success": false, "message": e.to_string() }),
+    }))
+}
+
+async fn handle_get_announcements(
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let templates = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => redeem_manager.announcement_templates().await,
+        None => Vec::new(),
+    };
+    let rewards: Vec<_> = templates.into_iter()
+        .map(|(title, announcements)| json!({ "title": title, "announcements": announcements }))
+        .collect();
+    Ok(warp::reply::json(&json!({ "rewards": rewards })))
+}
+
+async fn handle_save_announcements(
+    request: SaveAnnouncementsRequest,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let result = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => redeem_manager.save_announcements(&request.title, request.announcements).await,
+        None => return Ok(warp::reply::json(&json!({ "success": false, "message": "Redeems are not initialized" }))),
+    };
+    Ok(warp::reply::json(&match result {
+        Ok(()) => json!({ "success": true }),
+        Err(e) => json!({ "success": false, "message": e.to_string() }),
+    }))
+}
+
+async fn handle_get_queue(
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let entries = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => redeem_manager.get_queue().get_all().await,
+        None => Vec::new(),
+    };
+    Ok(warp::reply::json(&json!({ "entries": entries })))
+}
+
+async fn handle_reorder_queue(
+    action: String,
+    request: QueueEntryRequest,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+) -> Result<warp::reply::Json, warp::Rejection> {
+    let queue = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => redeem_manager.get_queue(),
+        None => return Ok(warp::reply::json(&json!({ "success": false, "message": "Redeems are not initialized" }))),
+    };
+    let moved = match action.as_str() {
+        "move" => queue.move_to(&request.redemption_id, request.position).await,
+        "bump" => queue.bump(&request.redemption_id).await,
+        "skip" => queue.skip(&request.redemption_id).await,
+        _ => return Err(warp::reject::not_found()),
+    };
+    Ok(warp::reply::json(&if moved {
+        json!({ "success": true })
+    } else {
+        json!({ "success": false, "message": "That redemption isn't in the queue anymore" })
+    }))
+}
+
+//   /api/redeems/pricing  GET the dynamically priced rewards with their current cost, and the latest price changes
+pub fn reward_pricing_routes(
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("redeems" / "pricing")
+        .and(warp::get())
+        .and(with_redeem_manager(redeem_manager))
+        .and(with_storage(storage))
+        .and_then(handle_get_pricing)
+}
+
+async fn handle_get_pricing(
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let prices = match redeem_manager.read().await.as_ref() {
+        Some(redeem_manager) => redeem_manager.dynamic_prices().await,
+        None => Vec::new(),
+    };
+    let changes = match storage.read().await.get_reward_price_changes(PRICE_CHANGE_LIMIT) {
+        Ok(changes) => changes,
+        Err(e) => {
+            error!("Failed to fetch reward price changes: {:?}", e);
+            return Err(warp::reject::custom(ApiError::DatabaseError));
+        }
+    };
+    let rewards: Vec<_> = prices.into_iter()
+        .map(|(title, cost, pricing)| json!({ "title": title, "cost": cost, "pricing": pricing }))
+        .collect();
+    Ok(warp::reply::json(&json!({ "rewards": rewards, "changes": changes })))
+}
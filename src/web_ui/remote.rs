@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use qrcode::render::svg;
+use qrcode::QrCode;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::osc::{OSCConfigurations, VRChatOSC};
+use crate::twitch::api::requests::channel;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::irc::TwitchIRCManager;
+use crate::twitch::tts;
+use crate::web_ui::api_routes::{with_config, ApiError};
+use crate::web_ui::pages::render_page;
+use crate::web_ui::websocket::DashboardState;
+
+const PAIRING_CODE_LENGTH: usize = 8;
+const SESSION_TOKEN_LENGTH: usize = 40;
+
+// Pairing codes and paired sessions, owned by TwitchManager. Both map to when they stop working.
+// Sessions only live in memory, so a restart unpairs every phone.
+#[derive(Default)]
+pub struct RemotePairing {
+    codes: Mutex<HashMap<String, Instant>>,
+    sessions: Mutex<HashMap<String, Instant>>,
+}
+
+impl RemotePairing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn create_code(&self, pairing_secs: u64) -> String {
+        let code = random_string(PAIRING_CODE_LENGTH);
+        let mut codes = self.codes.lock().unwrap();
+        let now = Instant::now();
+        codes.retain(|_, expires| *expires > now);
+        codes.insert(code.clone(), now + Duration::from_secs(pairing_secs));
+        code
+    }
+
+    // Codes work once
+    fn redeem_code(&self, code: &str) -> bool {
+        let mut codes = self.codes.lock().unwrap();
+        matches!(codes.remove(code), Some(expires) if expires > Instant::now())
+    }
+
+    fn start_session(&self, session_hours: u64) -> String {
+        let token = random_string(SESSION_TOKEN_LENGTH);
+        self.sessions.lock().unwrap().insert(token.clone(), Instant::now() + Duration::from_secs(session_hours * 3600));
+        token
+    }
+
+    fn session_valid(&self, token: &str) -> bool {
+        let mut sessions = self.sessions.lock().unwrap();
+        let now = Instant::now();
+        sessions.retain(|_, expires| *expires > now);
+        sessions.contains_key(token)
+    }
+
+    // Returns how many sessions were ended
+    fn end_sessions(&self) -> usize {
+        let mut sessions = self.sessions.lock().unwrap();
+        let ended = sessions.len();
+        sessions.clear();
+        ended
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PairRequest {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum RemoteAction {
+    // Index into remote_control.preset_messages, so a phone can't post arbitrary text
+    Chat { preset: usize },
+    Osc { key: String },
+    SkipTts,
+    Marker,
+    Ad,
+}
+
+// Everything the remote buttons need, cloned once per request
+#[derive(Clone)]
+pub struct RemoteTargets {
+    pub twitch_irc_manager: Arc<TwitchIRCManager>,
+    pub api_client: Arc<TwitchAPIClient>,
+    pub osc_configs: Arc<RwLock<OSCConfigurations>>,
+    pub vrchat_osc: Arc<VRChatOSC>,
+    pub dashboard_state: Arc<RwLock<DashboardState>>,
+}
+
+fn random_string(length: usize) -> String {
+    rand::thread_rng().sample_iter(&Alphanumeric).take(length).map(char::from).collect()
+}
+
+fn with_pairing(
+    pairing: Arc<RemotePairing>,
+) -> impl Filter<Extract = (Arc<RemotePairing>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || pairing.clone())
+}
+
+fn with_session(pairing: Arc<RemotePairing>) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>("authorization")
+        .and(with_pairing(pairing))
+        .and_then(|authorization: Option<String>, pairing: Arc<RemotePairing>| async move {
+            match authorization.as_deref().and_then(|value| value.strip_prefix("Bearer ")) {
+                Some(token) if pairing.session_valid(token) => Ok(()),
+                _ => Err(warp::reject::custom(ApiError::Unauthorized)),
+            }
+        })
+        .untuple_one()
+}
+
+fn with_targets(
+    targets: RemoteTargets,
+) -> impl Filter<Extract = (RemoteTargets,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || targets.clone())
+}
+
+// The remote can be switched off in the config, so every route checks first
+fn with_remote_enabled(
+    config: Arc<RwLock<Config>>,
+) -> impl Filter<Extract = (Arc<RwLock<Config>>,), Error = warp::Rejection> + Clone {
+    with_config(config).and_then(|config: Arc<RwLock<Config>>| async move {
+        if config.read().await.remote_control.enabled {
+            Ok(config)
+        } else {
+            Err(warp::reject::not_found())
+        }
+    })
+}
+
+// Phone-facing routes, outside /api so they only need a paired session:
+//   /remote         GET, the button page; /remote?pair=<code> pairs first
+//   /remote/pair    POST {code}, trades a pairing code for a session token
+//   /remote/state   GET, the presets and OSC configs to show
+//   /remote/action  POST {action, ...}
+pub fn remote_routes(
+    config: Arc<RwLock<Config>>,
+    targets: RemoteTargets,
+    pairing: Arc<RemotePairing>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let page = warp::path("remote")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_remote_enabled(config.clone()))
+        .and_then(handle_page);
+
+    let pair = warp::path!("remote" / "pair")
+        .and(warp::post())
+        .and(with_remote_enabled(config.clone()))
+        .and(warp::body::json())
+        .and(with_pairing(pairing.clone()))
+        .and_then(handle_pair);
+
+    let state = warp::path!("remote" / "state")
+        .and(warp::get())
+        .and(with_remote_enabled(config.clone()))
+        .and(with_session(pairing.clone()))
+        .and(with_targets(targets.clone()))
+        .and_then(handle_state);
+
+    let action = warp::path!("remote" / "action")
+        .and(warp::post())
+        .and(with_remote_enabled(config))
+        .and(with_session(pairing))
+        .and(warp::body::json())
+        .and(with_targets(targets))
+        .and_then(handle_action);
+
+    page.or(pair).or(state).or(action)
+}
+
+// /api/remote/pair (POST) makes a pairing code and its QR code for the dashboard,
+// /api/remote/unpair (POST) ends every paired session
+pub fn remote_api_routes(
+    config: Arc<RwLock<Config>>,
+    pairing: Arc<RemotePairing>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let pair = warp::path!("remote" / "pair")
+        .and(warp::post())
+        .and(warp::header::optional::<String>("host"))
+        .and(with_remote_enabled(config.clone()))
+        .and(with_pairing(pairing.clone()))
+        .and_then(handle_create_pairing);
+
+    let unpair = warp::path!("remote" / "unpair")
+        .and(warp::post())
+        .and(with_remote_enabled(config))
+        .and(with_pairing(pairing))
+        .map(|_, pairing: Arc<RemotePairing>| {
+            let ended = pairing.end_sessions();
+            info!("Ended {} remote control session(s)", ended);
+            warp::reply::json(&json!({ "success": true, "ended": ended }))
+        });
+
+    pair.or(unpair)
+}
+
+async fn handle_create_pairing(
+    host: Option<String>,
+    config: Arc<RwLock<Config>>,
+    pairing: Arc<RemotePairing>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (public_url, pairing_secs) = {
+        let config = config.read().await;
+        (config.remote_control.public_url.trim().to_string(), config.remote_control.pairing_secs)
+    };
+    let base = if public_url.is_empty() {
+        format!("http://{}", host.unwrap_or_else(|| "localhost".to_string()))
+    } else {
+        public_url
+    };
+
+    let code = pairing.create_code(pairing_secs);
+
+    let url = format!("{}/remote?pair={}", base.trim_end_matches('/'), code);
+    let qr_svg = match QrCode::new(url.as_bytes()) {
+        Ok(qr) => qr.render::<svg::Color>().min_dimensions(240, 240).build(),
+        Err(e) => {
+            error!("Failed to render pairing QR code: {:?}", e);
+            return Ok(warp::reply::json(&json!({ "success": false, "message": "Couldn't make a QR code for this address" })));
+        }
+    };
+    let warning = (base.contains("localhost") || base.contains("127.0.0.1"))
+        .then_some("Phones can't reach localhost. Open the dashboard through this PC's network address or set remote_control.public_url.");
+
+    Ok(warp::reply::json(&json!({
+        "success": true,
+        "url": url,
+        "qr_svg": qr_svg,
+        "expires_in": pairing_secs,
+        "warning": warning,
+    })))
+}
+
+async fn handle_pair(
+    config: Arc<RwLock<Config>>,
+    request: PairRequest,
+    pairing: Arc<RemotePairing>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if !pairing.redeem_code(request.code.trim()) {
+        warn!("Refused remote pairing with an unknown or expired code");
+        return Ok(warp::reply::json(&json!({ "success": false, "message": "That code expired. Make a new one on the dashboard." })));
+    }
+
+    let session_hours = config.read().await.remote_control.session_hours;
+    let token = pairing.start_session(session_hours);
+    info!("Paired a remote control for {} hours", session_hours);
+    Ok(warp::reply::json(&json!({ "success": true, "token": token })))
+}
+
+async fn handle_state(
+    config: Arc<RwLock<Config>>,
+    targets: RemoteTargets,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let remote = config.read().await.remote_control.clone();
+    let mut osc: Vec<String> = targets.osc_configs.read().await.configs.keys().cloned().collect();
+    osc.sort();
+    Ok(warp::reply::json(&json!({
+        "presets": remote.preset_messages,
+        "osc": osc,
+        "ad_length_secs": remote.ad_length_secs,
+    })))
+}
+
+async fn handle_action(
+    config: Arc<RwLock<Config>>,
+    action: RemoteAction,
+    targets: RemoteTargets,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let result = run_action(&config, action, &targets).await;
+    if let Err(e) = &result {
+        warn!("Remote control action failed: {}", e);
+    }
+    Ok(warp::reply::json(&match result {
+        Ok(()) => json!({ "success": true }),
+        Err(e) => json!({ "success": false, "message": e.to_string() }),
+    }))
+}
+
+async fn run_action(
+    config: &RwLock<Config>,
+    action: RemoteAction,
+    targets: &RemoteTargets,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    match action {
+        RemoteAction::Chat { preset } => {
+            let (message, bot, channel) = {
+                let config = config.read().await;
+                (
+                    config.remote_control.preset_messages.get(preset).cloned().ok_or("Unknown preset message")?,
+                    config.twitch_bot_username.clone().ok_or("No bot account configured")?,
+                    config.twitch_channel_to_join.clone().ok_or("No channel configured")?,
+                )
+            };
+            targets.twitch_irc_manager.send_message(&bot, &channel, &message).await?;
+        }
+        RemoteAction::Osc { key } => {
+            let osc_config = targets.osc_configs.read().await.get_config(&key).cloned().ok_or("Unknown OSC config")?;
+            let vrchat_osc = targets.vrchat_osc.clone();
+            // Don't hold the request open for the reset after execution_duration
+            tokio::spawn(async move {
+                if let Err(e) = vrchat_osc.send_osc_message_with_reset(&osc_config).await {
+                    error!("Failed to send OSC config {} from the remote: {}", key, e);
+                }
+            });
+        }
+        RemoteAction::SkipTts => {
+            if tts::skip(&targets.dashboard_state).await? == 0 {
+                return Err("No overlay is connected".into());
+            }
+        }
+        RemoteAction::Marker => {
+            let broadcaster_id = targets.api_client.get_broadcaster_id().await?;
+            channel::create_stream_marker(&targets.api_client, &broadcaster_id, "Highlight from the remote").await?;
+        }
+        RemoteAction::Ad => {
+            let length = config.read().await.remote_control.ad_length_secs;
+            let broadcaster_id = targets.api_client.get_broadcaster_id().await?;
+            channel::start_commercial(&targets.api_client, &broadcaster_id, length as i32).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn handle_page(config: Arc<RwLock<Config>>) -> Result<impl warp::Reply, warp::Rejection> {
+    let theme = config.read().await.ui_theme.clone();
+    Ok(warp::reply::html(render_page(&theme, "Remote", REMOTE_BODY)))
+}
+
+const REMOTE_BODY: &str = r#"<style>
+#controls button{display:block;width:100%;font-size:1.3rem;padding:1.1rem;margin:.5rem 0;border:0;border-radius:8px;background:#4b5563;color:#fff}
+#controls button.warn{background:#b91c1c}#controls button:disabled{opacity:.5}
+#status{min-height:1.5rem;margin-bottom:.5rem}.ok{color:#22c55e}.err{color:#ef4444}
+</style>
+<div id="status"></div><div id="controls"></div>
+<script>
+const TOKEN_KEY = 'mewbot_remote_token';
+const statusLine = document.getElementById('status');
+const controls = document.getElementById('controls');
+
+function show(text, ok) {
+    statusLine.textContent = text;
+    statusLine.className = ok ? 'ok' : 'err';
+}
+
+function unpaired() {
+    localStorage.removeItem(TOKEN_KEY);
+    controls.innerHTML = '';
+    show('Not paired. Scan the QR code on the dashboard.', false);
+}
+
+async function call(path, body) {
+    const res = await fetch(path, {
+        method: body ? 'POST' : 'GET',
+        headers: { 'Content-Type': 'application/json', 'Authorization': 'Bearer ' + localStorage.getItem(TOKEN_KEY) },
+        body: body ? JSON.stringify(body) : undefined,
+    });
+    if (res.status === 401) {
+        unpaired();
+        return null;
+    }
+    return res.json();
+}
+
+function heading(text) {
+    const h = document.createElement('h3');
+    h.textContent = text;
+    controls.appendChild(h);
+}
+
+function button(label, action, confirmText) {
+    const b = document.createElement('button');
+    b.textContent = label;
+    if (confirmText) b.className = 'warn';
+    b.onclick = async () => {
+        if (confirmText && !confirm(confirmText)) return;
+        b.disabled = true;
+        const data = await call('/remote/action', action);
+        b.disabled = false;
+        if (data) show(data.success ? label + ' ✓' : data.message, data.success);
+    };
+    controls.appendChild(b);
+}
+
+async function load() {
+    const code = new URLSearchParams(location.search).get('pair');
+    if (code) {
+        const res = await fetch('/remote/pair', {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ code }),
+        });
+        const data = await res.json();
+        if (data.success) localStorage.setItem(TOKEN_KEY, data.token);
+        else show(data.message, false);
+        history.replaceState(null, '', '/remote');
+    }
+    if (!localStorage.getItem(TOKEN_KEY)) {
+        if (!code) unpaired();
+        return;
+    }
+
+    const state = await call('/remote/state');
+    if (!state) return;
+    heading('Stream');
+    button('Skip TTS', { action: 'skip_tts' });
+    button('Mark highlight', { action: 'marker' });
+    button('Run ad (' + state.ad_length_secs + 's)', { action: 'ad' }, 'Start an ad break now?');
+    if (state.presets.length) {
+        heading('Chat');
+        state.presets.forEach((text, preset) => button(text, { action: 'chat', preset }));
+    }
+    if (state.osc.length) {
+        heading('OSC');
+        state.osc.forEach(key => button(key, { action: 'osc', key }));
+    }
+}
+
+load();
+</script>"#;
@@ -1,23 +1,46 @@
+use std::collections::HashMap;
 use std::env;
 use std::future::Future;
 use warp::Filter;
 use std::sync::Arc;
 use std::net::{IpAddr, SocketAddr, TcpListener};
-use futures_util::StreamExt;
-use log::{error, info, warn};
+use futures_util::{SinkExt, StreamExt};
+use tracing::{error, info, warn};
+use uuid::Uuid;
 use tokio::sync::{broadcast, oneshot, RwLock};
-use warp::http::{HeaderMap, HeaderValue};
-use warp::ws::WebSocket;
+use warp::ws::{Message, WebSocket};
 use crate::bot_status::BotStatus;
 use crate::config::Config;
 use crate::storage::StorageClient;
 use crate::twitch::irc::TwitchIRCManager;
 use crate::web_ui::websocket;
-use crate::web_ui::websocket::{DashboardState, update_dashboard_state};
+use crate::web_ui::websocket::{DashboardState, Resume, update_dashboard_state};
 use super::websocket::{handle_websocket, WebSocketMessage};
-use super::api_routes::{api_routes};
+use super::api_routes::{api_routes, with_dashboard_state};
+use super::leaderboard::leaderboard_routes;
+use super::bits_vote::bits_vote_overlay_routes;
+use super::charity::charity_overlay_routes;
+use super::ticker::ticker_routes;
+use super::pages::pages_routes;
+use super::theme::logo_route;
+use super::short_links::short_link_route;
+use super::commands_page::commands_page_route;
+use super::compat::compat_route;
+use super::remote::{remote_routes, RemoteTargets};
+use super::voice_commands::{voice_command_routes, VoiceCommandTargets};
+use super::soundboard::{soundboard_api_routes, soundboard_overlay_routes};
+use super::status_page::{status_page_routes, StatusSources};
+use super::api_tokens::{self, ApiScope};
+use super::oauth::oauth_callback_route;
 use crate::obs::OBSManager;
 use crate::vrchat::VRChatManager;
+use crate::twitch::irc::KeywordTriggerManager;
+use crate::twitch::redeems::RedeemManager;
+use crate::osc::{OSCConfigurations, VRChatOSC};
+use crate::ai::AIClient;
+use crate::twitch::TwitchManager;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::soundboard::Players;
 
 pub struct WebUI {
     config: Arc<RwLock<Config>>,
@@ -25,7 +48,14 @@ pub struct WebUI {
     pub dashboard_state: Arc<RwLock<websocket::DashboardState>>,
     obs_manager: Arc<OBSManager>,
     twitch_irc_manager: Arc<TwitchIRCManager>,
-    vrchat_manager: Arc<VRChatManager>,
+    vrchat_manager: Option<Arc<VRChatManager>>,
+    keyword_triggers: Arc<KeywordTriggerManager>,
+    redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+    osc_configs: Arc<RwLock<OSCConfigurations>>,
+    vrchat_osc: Arc<VRChatOSC>,
+    ai_client: Option<Arc<AIClient>>,
+    api_client: Arc<TwitchAPIClient>,
+    twitch_manager: Arc<TwitchManager>,
 }
 
 impl WebUI {
@@ -36,7 +66,14 @@ impl WebUI {
         twitch_irc_manager: Arc<TwitchIRCManager>,
         dashboard_state: Arc<RwLock<DashboardState>>,
         obs_manager: Arc<OBSManager>,
-        vrchat_manager: Arc<VRChatManager>,
+        vrchat_manager: Option<Arc<VRChatManager>>,
+        keyword_triggers: Arc<KeywordTriggerManager>,
+        redeem_manager: Arc<RwLock<Option<RedeemManager>>>,
+        osc_configs: Arc<RwLock<OSCConfigurations>>,
+        vrchat_osc: Arc<VRChatOSC>,
+        ai_client: Option<Arc<AIClient>>,
+        api_client: Arc<TwitchAPIClient>,
+        twitch_manager: Arc<TwitchManager>,
     ) -> Self {
         WebUI {
             config,
@@ -45,6 +82,13 @@ impl WebUI {
             obs_manager,
             twitch_irc_manager,
             vrchat_manager,
+            keyword_triggers,
+            redeem_manager,
+            osc_configs,
+            vrchat_osc,
+            ai_client,
+            api_client,
+            twitch_manager,
         }
     }
 
@@ -94,17 +138,62 @@ impl WebUI {
             .and(with_obs_manager(self.obs_manager.clone()))
             .and(with_twitch_irc_manager(self.twitch_irc_manager.clone()))
             .and(with_vrchat_manager(self.vrchat_manager.clone()))
-            .map(|ws: warp::ws::Ws, obs_manager, twitch_irc_manager, vrchat_manager| {
+            .and(with_dashboard_state(dashboard_state.clone()))
+            .and(api_tokens::authorize_websocket(config.clone(), storage.clone()))
+            .and(warp::query::<HashMap<String, String>>())
+            .map(|ws: warp::ws::Ws, obs_manager, twitch_irc_manager, vrchat_manager, dashboard_state, scopes, query: HashMap<String, String>| {
+                // ?resume=<seq>&epoch=<epoch> from a client that was connected before
+                let resume = match (query.get("epoch"), query.get("resume").and_then(|seq| seq.parse().ok())) {
+                    (Some(epoch), Some(seq)) => Some((epoch.clone(), seq)),
+                    _ => None,
+                };
                 ws.on_upgrade(move |socket| {
-                    handle_ws_connection(socket, obs_manager, twitch_irc_manager, vrchat_manager)
+                    handle_ws_connection(socket, obs_manager, twitch_irc_manager, vrchat_manager, dashboard_state, scopes, resume)
                 })
-            });
+            })
+            .recover(api_tokens::handle_unauthorized);
 
         let api = api_routes(
             config.clone(),
             storage.clone(),
-            dashboard_state.clone()
-        );
+            dashboard_state.clone(),
+            self.keyword_triggers.clone(),
+            self.redeem_manager.clone(),
+            self.osc_configs.clone(),
+            self.vrchat_osc.clone(),
+            self.ai_client.clone(),
+            self.api_client.clone(),
+            self.twitch_irc_manager.clone(),
+            self.twitch_manager.clone(),
+        ).recover(api_tokens::handle_unauthorized);
+
+        let remote = remote_routes(config.clone(), RemoteTargets {
+            twitch_irc_manager: self.twitch_irc_manager.clone(),
+            api_client: self.api_client.clone(),
+            osc_configs: self.osc_configs.clone(),
+            vrchat_osc: self.vrchat_osc.clone(),
+            dashboard_state: dashboard_state.clone(),
+        }, self.twitch_manager.remote_pairing.clone()).recover(api_tokens::handle_unauthorized);
+
+        // Kept out of api_routes because it drives OBS and VRChat like the dashboard websocket does
+        let voice_commands = warp::path("api")
+            .and(api_tokens::authorize(config.clone(), storage.clone()))
+            .and(voice_command_routes(config.clone(), VoiceCommandTargets {
+                obs_manager: self.obs_manager.clone(),
+                twitch_irc_manager: self.twitch_irc_manager.clone(),
+                vrchat_manager: self.vrchat_manager.clone(),
+            }, self.twitch_manager.pending_voice_command.clone()))
+            .recover(api_tokens::handle_unauthorized);
+
+        let soundboard = warp::path("api")
+            .and(api_tokens::authorize(config.clone(), storage.clone()))
+            .and(soundboard_api_routes(config.clone(), Players {
+                obs_manager: Some(self.obs_manager.clone()),
+                dashboard_state: dashboard_state.clone(),
+            }, self.twitch_manager.soundboard.clone()))
+            .recover(api_tokens::handle_unauthorized);
+
+        let twitch_redirect_path = config.read().await.twitch_redirect_path.clone();
 
         let routes = {
             root_redirect
@@ -112,6 +201,25 @@ impl WebUI {
                 .or(catch_all)
                 .or(ws_route)
                 .or(api)
+                .or(voice_commands)
+                .or(soundboard)
+                .or(leaderboard_routes(storage.clone()))
+                .or(bits_vote_overlay_routes(self.twitch_manager.bits_votes.clone()))
+                .or(charity_overlay_routes(self.twitch_manager.charity.clone()))
+                .or(soundboard_overlay_routes())
+                .or(ticker_routes(config.clone(), self.twitch_manager.ticker.clone()))
+                .or(pages_routes(config.clone()))
+                .or(logo_route(config.clone()))
+                .or(short_link_route(storage.clone()))
+                .or(commands_page_route(config.clone(), self.keyword_triggers.clone(), self.twitch_manager.command_overrides.clone(), self.twitch_manager.commands.clone()))
+                .or(compat_route(config.clone(), storage.clone(), self.redeem_manager.clone(), self.twitch_manager.compat_events.clone()))
+                .or(oauth_callback_route(&twitch_redirect_path, self.api_client.authorizations()))
+                .or(remote)
+                .or(status_page_routes(config.clone(), StatusSources {
+                    api_client: self.api_client.clone(),
+                    dashboard_state: dashboard_state.clone(),
+                    cache: self.twitch_manager.status_cache.clone(),
+                }))
                 .with(warp::log::custom(move |info| {
                     info!("Request: {} {} {}",
                         info.method(),
@@ -119,7 +227,15 @@ impl WebUI {
                         info.status().as_u16()
                     );
                 }))
-                .with(warp::reply::with::headers(header_map()))
+                // Everything logged while handling a request carries its id
+                .with(warp::trace(|info| tracing::info_span!(
+                    "request",
+                    request_id = %Uuid::new_v4(),
+                    method = %info.method(),
+                    path = info.path(),
+                )))
+                // A default so pages meant for embedding elsewhere can set their own
+                .with(warp::reply::with::default_header("Content-Security-Policy", FRAME_ANCESTORS))
         };
 
         let config_read = self.config.read().await;
@@ -140,8 +256,12 @@ impl WebUI {
         let (_, server) = warp::serve(routes).bind_with_graceful_shutdown(addr, shutdown_signal);
 
         let server_handle = tokio::spawn(server);
+        let authorizations = self.api_client.authorizations();
+        authorizations.set_web_ui_listening(true);
 
-        server_handle.await?;
+        let result = server_handle.await;
+        authorizations.set_web_ui_listening(false);
+        result?;
 
         warn!("Stopping dashboard update task...");
         if let Err(e) = update_task_shutdown_tx.send(()) {
@@ -232,37 +352,98 @@ async fn handle_ws_connection(
     ws: WebSocket,
     obs_manager: Arc<OBSManager>,
     twitch_irc_manager: Arc<TwitchIRCManager>,
-    vrchat_manager: Arc<VRChatManager>,
+    vrchat_manager: Option<Arc<VRChatManager>>,
+    dashboard_state: Arc<RwLock<DashboardState>>,
+    scopes: Vec<ApiScope>,
+    resume: Option<(String, u64)>,
 ) {
-    let (_, mut ws_recv) = ws.split();
-
-    while let Some(result) = ws_recv.next().await {
-        match result {
-            Ok(msg) => {
-                warn!("received websocket text {:?}", msg.to_str());
-
-                if let Ok(text) = msg.to_str() {
-                    if let Ok(ws_msg) = serde_json::from_str::<WebSocketMessage>(text) {
-                        handle_websocket(ws_msg, obs_manager.clone(), twitch_irc_manager.clone(), vrchat_manager.clone()).await;
-                    } else {
-                        error!("Failed to parse WebSocket message: {}", text);
+    let (mut ws_send, mut ws_recv) = ws.split();
+    // Holding a receiver also marks the dashboard as open for pushes that are only worth doing when watched
+    let (mut updates, connected) = dashboard_state.read().await
+        .connect(resume.as_ref().map(|(epoch, seq)| (epoch.as_str(), *seq)))
+        .await;
+
+    // The client keeps the epoch and the last seq it saw to resume with next time
+    let (resync, backlog) = match connected.resume {
+        Some(Resume::Replay(backlog)) => (false, backlog),
+        Some(Resume::Resync) => (true, Vec::new()),
+        None => (false, Vec::new()),
+    };
+    let hello = WebSocketMessage {
+        module: "dashboard".to_string(),
+        action: "hello".to_string(),
+        data: serde_json::json!({
+            "epoch": connected.epoch,
+            "seq": connected.last_seq,
+            "resync": resync,
+            "replayed": backlog.len(),
+        }),
+    };
+    let mut opening = vec![serde_json::to_string(&hello)];
+    opening.extend(backlog.iter()
+        .filter(|update| api_tokens::receives_websocket_module(&scopes, &update.message.module))
+        .map(serde_json::to_string));
+    for text in opening {
+        match text {
+            Ok(text) => {
+                if ws_send.send(Message::text(text)).await.is_err() {
+                    return;
+                }
+            }
+            Err(e) => error!("Failed to serialize dashboard update: {:?}", e),
+        }
+    }
+
+    loop {
+        tokio::select! {
+            result = ws_recv.next() => {
+                match result {
+                    Some(Ok(msg)) => {
+                        warn!("received websocket text {:?}", msg.to_str());
+
+                        if let Ok(text) = msg.to_str() {
+                            if let Ok(ws_msg) = serde_json::from_str::<WebSocketMessage>(text) {
+                                if !api_tokens::allows(&scopes, api_tokens::websocket_scope(&ws_msg.module)) {
+                                    warn!("Ignoring {} websocket message the token isn't scoped for", ws_msg.module);
+                                    continue;
+                                }
+                                handle_websocket(ws_msg, obs_manager.clone(), twitch_irc_manager.clone(), vrchat_manager.clone()).await;
+                            } else {
+                                error!("Failed to parse WebSocket message: {}", text);
+                            }
+                        }
                     }
+                    Some(Err(e)) => {
+                        error!("WebSocket error: {:?}", e);
+                        break;
+                    }
+                    None => break,
                 }
             }
-            Err(e) => {
-                error!("WebSocket error: {:?}", e);
-                break;
+            update = updates.recv() => {
+                let update = match update {
+                    Some(update) => update,
+                    None => break,
+                };
+                if !api_tokens::receives_websocket_module(&scopes, &update.message.module) {
+                    continue;
+                }
+                let text = match serde_json::to_string(&update) {
+                    Ok(text) => text,
+                    Err(e) => {
+                        error!("Failed to serialize dashboard update: {:?}", e);
+                        continue;
+                    }
+                };
+                if ws_send.send(Message::text(text)).await.is_err() {
+                    break;
+                }
             }
         }
     }
 }
 
-fn header_map() -> HeaderMap {
-    let mut headers = HeaderMap::new();
-    headers.insert("Content-Security-Policy",
-                   HeaderValue::from_static("frame-ancestors 'self' https://player.twitch.tv http://player.twitch.tv"));
-    headers
-}
+const FRAME_ANCESTORS: &str = "frame-ancestors 'self' https://player.twitch.tv http://player.twitch.tv";
 
 fn with_obs_manager(
     obs_manager: Arc<OBSManager>,
@@ -277,7 +458,7 @@ fn with_twitch_irc_manager(
 }
 
 fn with_vrchat_manager(
-    vrchat_manager: Arc<VRChatManager>,
-) -> impl Filter<Extract = (Arc<VRChatManager>,), Error = std::convert::Infallible> + Clone {
+    vrchat_manager: Option<Arc<VRChatManager>>,
+) -> impl Filter<Extract = (Option<Arc<VRChatManager>>,), Error = std::convert::Infallible> + Clone {
     warp::any().map(move || vrchat_manager.clone())
 }
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use tracing::error;
+use tokio::sync::RwLock;
+use warp::http::Uri;
+use warp::Filter;
+use crate::storage::StorageClient;
+use crate::web_ui::api_routes::{with_storage, ApiError};
+
+// Public redirect for links made with !link, e.g. /l/world
+pub fn short_link_route(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("l" / String)
+        .and(warp::get())
+        .and(with_storage(storage))
+        .and_then(handle_redirect)
+}
+
+// /api/links (GET), the links with their click counts for the dashboard
+pub fn short_link_api_routes(
+    storage: Arc<RwLock<StorageClient>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path("links")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_storage(storage))
+        .and_then(handle_list_links)
+}
+
+async fn handle_redirect(
+    slug: String,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let url = match storage.read().await.resolve_short_link(&slug.to_lowercase()) {
+        Ok(Some(url)) => url,
+        Ok(None) => return Err(warp::reject::not_found()),
+        Err(e) => {
+            error!("Failed to resolve short link {}: {:?}", slug, e);
+            return Err(warp::reject::custom(ApiError::DatabaseError));
+        }
+    };
+
+    let uri = url.parse::<Uri>().map_err(|e| {
+        error!("Short link {} points at an invalid URL {}: {}", slug, url, e);
+        warp::reject::not_found()
+    })?;
+    Ok(warp::redirect::temporary(uri))
+}
+
+async fn handle_list_links(
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.get_short_links() {
+        Ok(links) => Ok(warp::reply::json(&links)),
+        Err(e) => {
+            error!("Failed to fetch short links: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::twitch::soundboard::{Players, Sound, Soundboard};
+use crate::web_ui::api_routes::with_config;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SoundNameRequest {
+    name: String,
+}
+
+//   /api/soundboard         GET every sound with its cooldown, POST a sound to add or replace
+//   /api/soundboard/remove  POST {name}
+//   /api/soundboard/play    POST {name}, a test play that skips cooldowns and costs
+// Kept out of api_routes like voice commands, since it drives OBS.
+pub fn soundboard_api_routes(
+    config: Arc<RwLock<Config>>,
+    players: Players,
+    soundboard: Arc<Soundboard>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_soundboard = warp::any().map(move || soundboard.clone());
+
+    let list = warp::path!("soundboard")
+        .and(warp::get())
+        .and(with_config(config.clone()))
+        .and(with_soundboard.clone())
+        .and_then(|config: Arc<RwLock<Config>>, soundboard: Arc<Soundboard>| async move {
+            let soundboard_config = config.read().await.soundboard.clone();
+            Ok::<_, warp::Rejection>(warp::reply::json(&json!({
+                "enabled": soundboard_config.enabled,
+                "target_loudness_db": soundboard_config.target_loudness_db,
+                "sounds": soundboard.list(),
+            })))
+        });
+
+    let save = warp::path!("soundboard")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_soundboard.clone())
+        .map(|sound: Sound, soundboard: Arc<Soundboard>| {
+            warp::reply::json(&match soundboard.upsert(sound) {
+                Ok(()) => json!({ "success": true }),
+                Err(message) => json!({ "success": false, "message": message }),
+            })
+        });
+
+    let remove = warp::path!("soundboard" / "remove")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_soundboard.clone())
+        .map(|request: SoundNameRequest, soundboard: Arc<Soundboard>| {
+            warp::reply::json(&json!({ "success": soundboard.remove(&request.name).is_some() }))
+        });
+
+    let play = warp::path!("soundboard" / "play")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_config(config))
+        .and(warp::any().map(move || players.clone()))
+        .and(with_soundboard)
+        .and_then(handle_test_play);
+
+    list.or(save).or(remove).or(play)
+}
+
+async fn handle_test_play(
+    request: SoundNameRequest,
+    config: Arc<RwLock<Config>>,
+    players: Players,
+    soundboard: Arc<Soundboard>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let soundboard_config = config.read().await.soundboard.clone();
+    Ok(match soundboard.play(&players, &soundboard_config, &request.name, true).await {
+        Ok(_) => warp::reply::json(&json!({ "success": true })),
+        Err(message) => warp::reply::json(&json!({ "success": false, "message": message })),
+    })
+}
+
+// Public, for OBS browser sources:
+//   /sounds/<file>        the files overlay sounds play
+//   /soundboard/overlay   plays sounds as the bot sends them; add it as a browser source with audio
+//                         routed through OBS ("Control audio via OBS")
+pub fn soundboard_overlay_routes() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let files = warp::path("sounds").and(warp::fs::dir(crate::paths::resolve(crate::paths::SOUNDS_DIR)));
+
+    let overlay = warp::path!("soundboard" / "overlay")
+        .and(warp::get())
+        .map(|| warp::reply::html(OVERLAY_PAGE));
+
+    files.or(overlay)
+}
+
+const OVERLAY_PAGE: &str = r#"<!DOCTYPE html><html><head><meta charset="utf-8">
+<style>html,body{background:transparent;margin:0}</style></head><body>
+<script>
+// Sounds overlap rather than queue, like a real soundboard
+const token = new URLSearchParams(window.location.search).get('token');
+
+function connect() {
+    const protocol = window.location.protocol === 'https:' ? 'wss' : 'ws';
+    const url = protocol + '://' + window.location.host + '/ws' + (token ? '?token=' + encodeURIComponent(token) : '');
+    const socket = new WebSocket(url);
+    socket.onmessage = (event) => {
+        let message;
+        try { message = JSON.parse(event.data); } catch (e) { return; }
+        if (message.module !== 'soundboard' || message.action !== 'play') return;
+        const audio = new Audio(message.data.url);
+        audio.volume = Math.max(0, Math.min(1, message.data.volume));
+        audio.play().catch((e) => console.error('Failed to play ' + message.data.name, e));
+    };
+    socket.onclose = () => setTimeout(connect, 3000);
+}
+
+connect();
+</script></body></html>"#;
@@ -0,0 +1,290 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tracing::error;
+use warp::Filter;
+use crate::config::{Config, SocialLinks};
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::stream_breaks::format_duration;
+use crate::web_ui::api_routes::with_config;
+use crate::web_ui::leaderboard::escape_html;
+use crate::web_ui::pages::render_page_with_head;
+use crate::web_ui::websocket::DashboardState;
+
+// What Twitch says about the channel; reused for cache_secs so a busy Discord link doesn't hammer Helix
+#[derive(Debug, Clone, Default, Serialize)]
+struct TwitchStatus {
+    live: bool,
+    title: Option<String>,
+    game: Option<String>,
+    started_at: Option<DateTime<Utc>>,
+    viewers: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct SocialLink {
+    label: &'static str,
+    url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct StreamStatus {
+    channel: String,
+    #[serde(flatten)]
+    twitch: TwitchStatus,
+    uptime_secs: Option<i64>,
+    world: Option<String>,
+    links: Vec<SocialLink>,
+}
+
+// The last status fetched and when, owned by TwitchManager
+#[derive(Default)]
+pub struct StatusCache {
+    last: Mutex<Option<(Instant, TwitchStatus)>>,
+}
+
+impl StatusCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Clone)]
+pub struct StatusSources {
+    pub api_client: Arc<TwitchAPIClient>,
+    pub dashboard_state: Arc<RwLock<DashboardState>>,
+    pub cache: Arc<StatusCache>,
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Page,
+    Embed,
+    Json,
+}
+
+// Public "is the stream live?" page for linking from Discord and elsewhere:
+//   /status        full page with link preview tags
+//   /status/embed  compact card that other sites may put in an iframe
+//   /status/json   the same data for bots and widgets
+pub fn status_page_routes(
+    config: Arc<RwLock<Config>>,
+    sources: StatusSources,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_sources = warp::any().map(move || sources.clone());
+
+    let page = warp::path("status").and(warp::path::end()).map(|| Format::Page);
+    let embed = warp::path!("status" / "embed").map(|| Format::Embed);
+    let json = warp::path!("status" / "json").map(|| Format::Json);
+
+    page.or(embed).unify().or(json).unify()
+        .and(warp::get())
+        .and(with_config(config))
+        .and(with_sources)
+        .and_then(handle_status)
+}
+
+async fn handle_status(
+    format: Format,
+    config: Arc<RwLock<Config>>,
+    sources: StatusSources,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (enabled, cache_secs, channel, social_links, theme) = {
+        let config = config.read().await;
+        (
+            config.status_page.enabled,
+            config.status_page.cache_secs,
+            config.twitch_channel_to_join.clone().unwrap_or_default(),
+            config.social_links.clone(),
+            config.ui_theme.clone(),
+        )
+    };
+    if !enabled {
+        return Err(warp::reject::not_found());
+    }
+
+    let twitch = twitch_status(&sources.cache, &sources.api_client, Duration::from_secs(cache_secs)).await;
+    let world = match &sources.dashboard_state.read().await.vrchat_world {
+        Some(world) if twitch.live => Some(format!("{} by {}", world.name, world.author_name)),
+        _ => None,
+    };
+    let status = StreamStatus {
+        links: links(&channel, &social_links),
+        uptime_secs: twitch.started_at.map(|started| (Utc::now() - started).num_seconds().max(0)),
+        channel,
+        twitch,
+        world,
+    };
+
+    let reply: Box<dyn warp::Reply> = match format {
+        Format::Json => Box::new(warp::reply::json(&status)),
+        Format::Page => Box::new(warp::reply::html(render_page_with_head(
+            &theme,
+            &escape_html(&headline(&status)),
+            &preview_tags(&status),
+            &render_body(&status),
+        ))),
+        Format::Embed => Box::new(warp::reply::with_header(
+            warp::reply::html(render_embed(&status, &theme.accent_color)),
+            "Content-Security-Policy",
+            "frame-ancestors *",
+        )),
+    };
+    Ok(warp::reply::with_header(reply, "Cache-Control", format!("public, max-age={}", cache_secs)))
+}
+
+async fn twitch_status(cache: &StatusCache, api_client: &TwitchAPIClient, max_age: Duration) -> TwitchStatus {
+    if let Some((fetched_at, status)) = cache.last.lock().unwrap().as_ref() {
+        if fetched_at.elapsed() < max_age {
+            return status.clone();
+        }
+    }
+
+    let status = match fetch_twitch_status(api_client).await {
+        Ok(status) => status,
+        Err(e) => {
+            error!("Failed to fetch stream status for the status page: {:?}", e);
+            // Serve the last known status rather than claiming the stream is offline
+            if let Some((_, status)) = cache.last.lock().unwrap().as_ref() {
+                return status.clone();
+            }
+            TwitchStatus::default()
+        }
+    };
+    *cache.last.lock().unwrap() = Some((Instant::now(), status.clone()));
+    status
+}
+
+async fn fetch_twitch_status(api_client: &TwitchAPIClient) -> Result<TwitchStatus, Box<dyn std::error::Error + Send + Sync>> {
+    let broadcaster_id = api_client.get_broadcaster_id().await?;
+    let stream = api_client.get_stream_info(&broadcaster_id).await?;
+    if let Some(stream) = stream["data"].get(0) {
+        return Ok(TwitchStatus {
+            live: true,
+            title: text(stream, "title"),
+            game: text(stream, "game_name"),
+            started_at: stream["started_at"].as_str().and_then(|s| s.parse().ok()),
+            viewers: stream["viewer_count"].as_u64(),
+        });
+    }
+
+    // Offline: still show what the channel is set up to stream
+    let channel = api_client.get_channel_information(&broadcaster_id).await?;
+    let channel = &channel["data"][0];
+    Ok(TwitchStatus {
+        live: false,
+        title: text(channel, "title"),
+        game: text(channel, "game_name"),
+        started_at: None,
+        viewers: None,
+    })
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value[key].as_str().filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+fn links(channel: &str, social_links: &SocialLinks) -> Vec<SocialLink> {
+    let mut links = Vec::new();
+    if !channel.is_empty() {
+        links.push(SocialLink { label: "Twitch", url: format!("https://twitch.tv/{}", channel) });
+    }
+    let configured = [
+        ("Discord", &social_links.discord),
+        ("X", &social_links.xdotcom),
+        ("VRChat group", &social_links.vrchat_group),
+        ("Business", &social_links.business_url),
+    ];
+    for (label, url) in configured {
+        // Only real links; these end up in href attributes
+        if let Some(url) = url.as_ref().filter(|u| u.starts_with("https://") || u.starts_with("http://")) {
+            links.push(SocialLink { label, url: url.clone() });
+        }
+    }
+    links
+}
+
+fn headline(status: &StreamStatus) -> String {
+    if status.twitch.live {
+        format!("{} is live!", status.channel)
+    } else {
+        format!("{} is offline", status.channel)
+    }
+}
+
+fn details(status: &StreamStatus) -> Vec<String> {
+    let mut details = Vec::new();
+    if let Some(game) = &status.twitch.game {
+        details.push(if status.twitch.live { format!("Playing {}", game) } else { format!("Last in {}", game) });
+    }
+    if let Some(world) = &status.world {
+        details.push(format!("In {}", world));
+    }
+    if let Some(uptime) = status.uptime_secs {
+        details.push(format!("Live for {}", format_duration(uptime)));
+    }
+    if let Some(viewers) = status.twitch.viewers {
+        details.push(format!("{} watching", viewers));
+    }
+    details
+}
+
+fn preview_tags(status: &StreamStatus) -> String {
+    let description = match &status.twitch.title {
+        Some(title) => std::iter::once(title.clone()).chain(details(status)).collect::<Vec<_>>().join(" · "),
+        None => details(status).join(" · "),
+    };
+    format!(
+        "<meta property=\"og:type\" content=\"website\"><meta property=\"og:title\" content=\"{}\">\
+         <meta property=\"og:description\" content=\"{}\"><meta name=\"theme-color\" content=\"{}\">",
+        escape_html(&headline(status)),
+        escape_html(&description),
+        if status.twitch.live { "#eb0400" } else { "#6b7280" },
+    )
+}
+
+fn render_links(status: &StreamStatus) -> String {
+    status.links.iter()
+        .map(|link| format!("<a href=\"{}\" target=\"_blank\" rel=\"noopener\">{}</a>", escape_html(&link.url), link.label))
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+fn render_body(status: &StreamStatus) -> String {
+    let mut body = String::new();
+    if let Some(title) = &status.twitch.title {
+        body.push_str(&format!("<h2>{}</h2>", escape_html(title)));
+    }
+    for detail in details(status) {
+        body.push_str(&format!("<p>{}</p>", escape_html(&detail)));
+    }
+    if !status.links.is_empty() {
+        body.push_str(&format!("<p>{}</p>", render_links(status)));
+    }
+    body
+}
+
+fn render_embed(status: &StreamStatus, accent: &str) -> String {
+    let accent = if crate::web_ui::theme::is_valid_accent_color(accent) { accent } else { "#9146FF" };
+    let dot = if status.twitch.live { "#eb0400" } else { "#6b7280" };
+    let channel_link = status.links.first()
+        .map(|link| format!("<a href=\"{}\" target=\"_blank\" rel=\"noopener\">", escape_html(&link.url)))
+        .unwrap_or_default();
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><style>\
+         html,body{{background:transparent;margin:0;font-family:sans-serif}}\
+         .card{{display:inline-block;background:#1f2937;color:#fff;border-left:4px solid {accent};border-radius:8px;padding:.75rem 1rem}}\
+         .dot{{display:inline-block;width:.6rem;height:.6rem;border-radius:50%;background:{dot};margin-right:.4rem}}\
+         a{{color:inherit;text-decoration:none}}p{{margin:.2rem 0;color:#d1d5db;font-size:.9rem}}\
+         </style></head><body><div class=\"card\">{channel_link}<strong><span class=\"dot\"></span>{headline}</strong>{close}{details}</div></body></html>",
+        accent = accent,
+        dot = dot,
+        channel_link = channel_link,
+        headline = escape_html(&headline(status)),
+        close = if channel_link.is_empty() { "" } else { "</a>" },
+        details = details(status).iter().map(|d| format!("<p>{}</p>", escape_html(d))).collect::<String>(),
+    )
+}
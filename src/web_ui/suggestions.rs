@@ -0,0 +1,123 @@
+use std::sync::Arc;
+use tracing::{error, info};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::storage::StorageClient;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::irc::TwitchIRCManager;
+use crate::twitch::suggestions;
+use crate::web_ui::api_routes::{with_config, with_storage, ApiError};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct SuggestionRequest {
+    id: i64,
+}
+
+// Everything approving a suggestion touches, cloned once per request
+#[derive(Clone)]
+struct SuggestionTargets {
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+    api_client: Arc<TwitchAPIClient>,
+    twitch_irc_manager: Arc<TwitchIRCManager>,
+}
+
+//   /api/suggestions          GET viewer title and tag suggestions waiting for approval
+//   /api/suggestions/approve  POST {id}, applies it to the channel and credits the viewer in chat
+//   /api/suggestions/reject   POST {id}
+pub fn suggestion_api_routes(
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+    api_client: Arc<TwitchAPIClient>,
+    twitch_irc_manager: Arc<TwitchIRCManager>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let queue = warp::path!("suggestions")
+        .and(warp::get())
+        .and(with_config(config.clone()))
+        .and(with_storage(storage.clone()))
+        .and_then(handle_get_suggestions);
+
+    let targets = SuggestionTargets { config, storage: storage.clone(), api_client, twitch_irc_manager };
+    let approve = warp::path!("suggestions" / "approve")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(warp::any().map(move || targets.clone()))
+        .and_then(handle_approve_suggestion);
+
+    let reject = warp::path!("suggestions" / "reject")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage))
+        .and_then(handle_reject_suggestion);
+
+    queue.or(approve).or(reject)
+}
+
+async fn handle_get_suggestions(
+    config: Arc<RwLock<Config>>,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let enabled = config.read().await.suggestions.enabled;
+    match storage.read().await.get_pending_stream_suggestions() {
+        Ok(pending) => Ok(warp::reply::json(&json!({ "enabled": enabled, "pending": pending }))),
+        Err(e) => {
+            error!("Failed to load suggestions: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_approve_suggestion(
+    request: SuggestionRequest,
+    targets: SuggestionTargets,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let suggestion = match targets.storage.read().await.get_pending_stream_suggestions() {
+        Ok(pending) => pending.into_iter().find(|s| s.id == request.id),
+        Err(e) => {
+            error!("Failed to load suggestions: {:?}", e);
+            return Err(warp::reject::custom(ApiError::DatabaseError));
+        }
+    };
+    let suggestion = match suggestion {
+        Some(suggestion) => suggestion,
+        None => return Ok(warp::reply::json(&json!({ "success": false, "message": "That suggestion isn't waiting anymore" }))),
+    };
+
+    if let Err(e) = suggestions::apply(&targets.api_client, &suggestion).await {
+        error!("Failed to apply {} suggestion {}: {:?}", suggestion.kind, suggestion.id, e);
+        return Ok(warp::reply::json(&json!({ "success": false, "message": "Twitch didn't accept the change" })));
+    }
+    if let Err(e) = targets.storage.read().await.resolve_stream_suggestion(suggestion.id, "approved", "dashboard") {
+        error!("Failed to mark suggestion {} approved: {:?}", suggestion.id, e);
+    }
+    info!("Applied {} suggestion from {}", suggestion.kind, suggestion.user_name);
+
+    let (bot, channel) = {
+        let config = targets.config.read().await;
+        (config.twitch_bot_username.clone(), config.twitch_channel_to_join.clone())
+    };
+    if let (Some(bot), Some(channel)) = (bot, channel) {
+        if let Err(e) = targets.twitch_irc_manager.send_message(&bot, &channel, &suggestions::announcement(&suggestion)).await {
+            error!("Failed to announce {}'s suggestion: {}", suggestion.user_name, e);
+        }
+    }
+
+    Ok(warp::reply::json(&json!({ "success": true })))
+}
+
+async fn handle_reject_suggestion(
+    request: SuggestionRequest,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.resolve_stream_suggestion(request.id, "rejected", "dashboard") {
+        Ok(rejected) => Ok(warp::reply::json(&json!({ "success": rejected.is_some() }))),
+        Err(e) => {
+            error!("Failed to reject suggestion {}: {:?}", request.id, e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::hyper::body::Bytes;
+use warp::Filter;
+use crate::config::{Config, UiTheme, UiThemeConfig};
+use crate::web_ui::api_routes::{with_config, ApiError};
+
+fn logo_dir() -> String {
+    crate::paths::resolve(crate::paths::ASSETS_DIR)
+}
+const MAX_LOGO_BYTES: u64 = 2 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct ThemeUpdate {
+    theme: Option<UiTheme>,
+    accent_color: Option<String>,
+}
+
+pub fn is_valid_accent_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn theme_json(theme: &UiThemeConfig) -> serde_json::Value {
+    json!({
+        "theme": theme.theme,
+        "accent_color": theme.accent_color,
+        "logo_url": theme.logo_path.as_ref().map(|_| "/theme/logo"),
+    })
+}
+
+// No SVG: it's served from the dashboard's own origin, where a script inside it could use the API
+fn logo_extension(content_type: &str) -> Option<&'static str> {
+    match content_type {
+        "image/png" => Some("png"),
+        "image/jpeg" => Some("jpg"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        _ => None,
+    }
+}
+
+fn logo_content_type(path: &str) -> &'static str {
+    match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("png") => "image/png",
+        Some("jpg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+// /api/theme (GET, POST) and /api/theme/logo (POST, raw image body)
+pub fn theme_api_routes(
+    config: Arc<RwLock<Config>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let get_theme = warp::path("theme")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_config(config.clone()))
+        .and_then(handle_get_theme);
+
+    let update_theme = warp::path("theme")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_config(config.clone()))
+        .and_then(handle_update_theme);
+
+    let upload_logo = warp::path!("theme" / "logo")
+        .and(warp::post())
+        .and(warp::header::<String>("content-type"))
+        .and(warp::body::content_length_limit(MAX_LOGO_BYTES))
+        .and(warp::body::bytes())
+        .and(with_config(config))
+        .and_then(handle_upload_logo);
+
+    get_theme.or(update_theme).or(upload_logo)
+}
+
+// Public logo, used by the dashboard and the public pages
+pub fn logo_route(
+    config: Arc<RwLock<Config>>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("theme" / "logo")
+        .and(warp::get())
+        .and(with_config(config))
+        .and_then(handle_get_logo)
+}
+
+async fn handle_get_theme(config: Arc<RwLock<Config>>) -> Result<impl warp::Reply, warp::Rejection> {
+    let config = config.read().await;
+    Ok(warp::reply::json(&theme_json(&config.ui_theme)))
+}
+
+async fn handle_update_theme(
+    update: ThemeUpdate,
+    config: Arc<RwLock<Config>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    if let Some(color) = &update.accent_color {
+        if !is_valid_accent_color(color) {
+            return Err(warp::reject::custom(ApiError::InvalidInput));
+        }
+    }
+
+    let mut config = config.write().await;
+    if let Some(theme) = update.theme {
+        config.ui_theme.theme = theme;
+    }
+    if let Some(color) = update.accent_color {
+        config.ui_theme.accent_color = color;
+    }
+    if let Err(e) = config.save() {
+        error!("Failed to save theme: {:?}", e);
+        return Err(warp::reject::custom(ApiError::ConfigUpdateError));
+    }
+
+    info!("Web UI theme updated");
+    Ok(warp::reply::json(&theme_json(&config.ui_theme)))
+}
+
+async fn handle_upload_logo(
+    content_type: String,
+    body: Bytes,
+    config: Arc<RwLock<Config>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let extension = logo_extension(content_type.split(';').next().unwrap_or("").trim())
+        .ok_or_else(|| warp::reject::custom(ApiError::InvalidInput))?;
+    if body.is_empty() {
+        return Err(warp::reject::custom(ApiError::InvalidInput));
+    }
+
+    let logo_dir = logo_dir();
+    let path = format!("{}/logo.{}", logo_dir, extension);
+    let write_result = async {
+        tokio::fs::create_dir_all(&logo_dir).await?;
+        tokio::fs::write(&path, &body).await
+    }.await;
+    if let Err(e) = write_result {
+        error!("Failed to store uploaded logo: {:?}", e);
+        return Err(warp::reject::custom(ApiError::ConfigUpdateError));
+    }
+
+    let mut config = config.write().await;
+    let previous = config.ui_theme.logo_path.replace(path.clone());
+    if let Err(e) = config.save() {
+        error!("Failed to save logo path: {:?}", e);
+        return Err(warp::reject::custom(ApiError::ConfigUpdateError));
+    }
+    // A logo of another type was saved under a different name
+    if let Some(previous) = previous.filter(|previous| *previous != path) {
+        if let Err(e) = tokio::fs::remove_file(&previous).await {
+            warn!("Failed to remove the old logo {}: {:?}", previous, e);
+        }
+    }
+
+    info!("Uploaded new web UI logo ({} bytes)", body.len());
+    Ok(warp::reply::json(&theme_json(&config.ui_theme)))
+}
+
+async fn handle_get_logo(config: Arc<RwLock<Config>>) -> Result<impl warp::Reply, warp::Rejection> {
+    let logo_path = config.read().await.ui_theme.logo_path.clone()
+        .ok_or_else(warp::reject::not_found)?;
+
+    match tokio::fs::read(&logo_path).await {
+        Ok(contents) => Ok(warp::reply::with_header(contents, "content-type", logo_content_type(&logo_path))),
+        Err(e) => {
+            error!("Failed to read logo {}: {:?}", logo_path, e);
+            Err(warp::reject::not_found())
+        }
+    }
+}
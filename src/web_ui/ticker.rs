@@ -0,0 +1,179 @@
+use std::sync::{Arc, Mutex};
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::{Config, SocialLinks, TickerConfig};
+use crate::hooks::BotEvent;
+use crate::web_ui::api_routes::with_config;
+use crate::web_ui::pages::theme_colors;
+
+#[derive(Default)]
+struct TickerState {
+    latest_follower: Option<String>,
+    // (name, bits) of the biggest cheer this stream
+    top_cheer: Option<(String, u64)>,
+}
+
+// Latest follower and top cheer, owned by TwitchManager and kept current by an event hook
+#[derive(Default)]
+pub struct Ticker {
+    state: Mutex<TickerState>,
+}
+
+impl Ticker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_event(&self, event: &BotEvent) {
+        let (event_type, payload) = match event {
+            BotEvent::EventSub { event_type, payload } => (event_type.as_str(), payload),
+            BotEvent::ChatMessage(_) => return,
+        };
+        let mut state = self.state.lock().unwrap();
+        match event_type {
+            "channel.follow" => {
+                if let Some(name) = payload["user_name"].as_str() {
+                    state.latest_follower = Some(name.to_string());
+                }
+            }
+            "channel.cheer" => {
+                let bits = payload["bits"].as_u64().unwrap_or(0);
+                let name = payload["user_name"].as_str().unwrap_or("Anonymous").to_string();
+                if state.top_cheer.as_ref().map_or(true, |(_, top)| bits > *top) {
+                    state.top_cheer = Some((name, bits));
+                }
+            }
+            "stream.online" => state.top_cheer = None,
+            _ => {}
+        }
+    }
+}
+
+fn ticker_lines(ticker: &Ticker, config: &TickerConfig, social_links: &SocialLinks) -> Vec<String> {
+    let mut lines = Vec::new();
+    {
+        let state = ticker.state.lock().unwrap();
+        if config.show_latest_follower {
+            if let Some(name) = &state.latest_follower {
+                lines.push(format!("Latest follower: {}", name));
+            }
+        }
+        if config.show_top_cheer {
+            if let Some((name, bits)) = &state.top_cheer {
+                lines.push(format!("Top cheer: {} with {} bits", name, bits));
+            }
+        }
+    }
+
+    if !config.now_playing_file.is_empty() {
+        // Missing or empty while nothing is playing, which just drops the line
+        if let Ok(song) = std::fs::read_to_string(&config.now_playing_file) {
+            let song = song.trim();
+            if !song.is_empty() {
+                lines.push(format!("Now playing: {}", song));
+            }
+        }
+    }
+
+    if config.show_social_links {
+        let configured = [
+            ("Discord", &social_links.discord),
+            ("X", &social_links.xdotcom),
+            ("VRChat group", &social_links.vrchat_group),
+        ];
+        for (label, url) in configured {
+            if let Some(url) = url.as_ref().filter(|url| !url.is_empty()) {
+                let short = url.trim_start_matches("https://").trim_start_matches("http://").trim_end_matches('/');
+                lines.push(format!("{}: {}", label, short));
+            }
+        }
+    }
+
+    lines.extend(config.custom_lines.iter().filter(|line| !line.trim().is_empty()).cloned());
+    lines
+}
+
+// Browser source for OBS, public like the other overlays:
+//   /ticker/overlay  one line at a time, in the dashboard theme's colors
+//   /ticker/json     the lines it rotates through
+pub fn ticker_routes(
+    config: Arc<RwLock<Config>>,
+    ticker: Arc<Ticker>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let overlay = warp::path!("ticker" / "overlay")
+        .and(warp::get())
+        .and(with_config(config.clone()))
+        .and_then(|config: Arc<RwLock<Config>>| async move {
+            let config = config.read().await;
+            let (_, panel, text, accent) = theme_colors(&config.ui_theme);
+            let page = OVERLAY_PAGE
+                .replace("{panel}", panel)
+                .replace("{text}", text)
+                .replace("{accent}", accent);
+            Ok::<_, warp::Rejection>(warp::reply::html(page))
+        });
+
+    let json = warp::path!("ticker" / "json")
+        .and(warp::get())
+        .and(with_config(config))
+        .and(warp::any().map(move || ticker.clone()))
+        .and_then(|config: Arc<RwLock<Config>>, state: Arc<Ticker>| async move {
+            let (ticker, social_links) = {
+                let config = config.read().await;
+                (config.ticker.clone(), config.social_links.clone())
+            };
+            let lines = if ticker.enabled { ticker_lines(&state, &ticker, &social_links) } else { Vec::new() };
+            Ok::<_, warp::Rejection>(warp::reply::with_header(
+                warp::reply::json(&json!({ "lines": lines, "rotate_secs": ticker.rotate_secs.max(1) })),
+                "Cache-Control",
+                "no-store",
+            ))
+        });
+
+    overlay.or(json)
+}
+
+const OVERLAY_PAGE: &str = r#"<!DOCTYPE html><html><head><meta charset="utf-8"><style>
+html,body{background:transparent;margin:0;font-family:sans-serif}
+#bar{display:none;align-items:center;gap:.8rem;background:{panel};color:{text};border-left:6px solid {accent};
+border-radius:6px;padding:.6rem 1rem;font-size:1.4rem;opacity:.92}
+#line{transition:opacity .4s}
+</style></head><body><div id="bar"><span id="line"></span></div>
+<script>
+const bar = document.getElementById('bar');
+const line = document.getElementById('line');
+let lines = [];
+let index = 0;
+let rotateMs = 8000;
+
+function show() {
+    if (lines.length === 0) { bar.style.display = 'none'; return; }
+    bar.style.display = 'flex';
+    index = index % lines.length;
+    line.style.opacity = 0;
+    setTimeout(() => { line.textContent = lines[index]; line.style.opacity = 1; index++; }, 400);
+}
+
+async function poll() {
+    try {
+        const res = await fetch('/ticker/json');
+        const data = await res.json();
+        const wasEmpty = lines.length === 0;
+        lines = data.lines;
+        rotateMs = data.rotate_secs * 1000;
+        if (wasEmpty) show();
+    } catch (e) {
+        // The bot restarting shouldn't break the browser source; try again next tick
+    }
+}
+
+function rotate() {
+    show();
+    setTimeout(rotate, rotateMs);
+}
+
+poll();
+setInterval(poll, 5000);
+setTimeout(rotate, rotateMs);
+</script></body></html>"#;
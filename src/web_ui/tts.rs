@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use tracing::error;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::output_filter::OutputFilter;
+use crate::storage::StorageClient;
+use crate::twitch::api::TwitchAPIClient;
+use crate::twitch::tts::{self, TtsQueue};
+use crate::web_ui::api_routes::{with_dashboard_state, with_storage, ApiError};
+use crate::web_ui::websocket::DashboardState;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct TtsItemRequest {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct TtsUnbanRequest {
+    user: String,
+}
+
+//   /api/tts          GET held messages and banned users
+//   /api/tts/approve  POST {id}, plays a held message now
+//   /api/tts/reject   POST {id}, drops a held message (refunding redeems if configured)
+//   /api/tts/skip     POST, stops whatever the overlay is reading out
+//   /api/tts/unban    POST {user}
+pub fn tts_api_routes(
+    storage: Arc<RwLock<StorageClient>>,
+    dashboard_state: Arc<RwLock<DashboardState>>,
+    api_client: Arc<TwitchAPIClient>,
+    tts_queue: Arc<TtsQueue>,
+    output_filter: Arc<OutputFilter>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_queue = warp::any().map(move || tts_queue.clone());
+
+    let queue = warp::path!("tts")
+        .and(warp::get())
+        .and(with_storage(storage.clone()))
+        .and(with_queue.clone())
+        .and_then(handle_get_queue);
+
+    let approve = warp::path!("tts" / "approve")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_dashboard_state(dashboard_state.clone()))
+        .and(with_queue.clone())
+        .and(warp::any().map(move || output_filter.clone()))
+        .and_then(|request: TtsItemRequest, dashboard_state: Arc<RwLock<DashboardState>>, queue: Arc<TtsQueue>, output_filter: Arc<OutputFilter>| async move {
+            let success = queue.approve(&dashboard_state, &output_filter, request.id).await;
+            Ok::<_, warp::Rejection>(warp::reply::json(&json!({ "success": success })))
+        });
+
+    let reject = warp::path!("tts" / "reject")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_dashboard_state(dashboard_state.clone()))
+        .and(warp::any().map(move || api_client.clone()))
+        .and(with_queue)
+        .and_then(|request: TtsItemRequest, dashboard_state: Arc<RwLock<DashboardState>>, api_client: Arc<TwitchAPIClient>, queue: Arc<TtsQueue>| async move {
+            let rejected = queue.reject(&dashboard_state, &api_client, request.id).await;
+            Ok::<_, warp::Rejection>(warp::reply::json(&json!({ "success": rejected.is_some() })))
+        });
+
+    let skip = warp::path!("tts" / "skip")
+        .and(warp::post())
+        .and(with_dashboard_state(dashboard_state))
+        .and_then(|dashboard_state: Arc<RwLock<DashboardState>>| async move {
+            let reply = match tts::skip(&dashboard_state).await {
+                Ok(listeners) => json!({ "success": listeners > 0, "listeners": listeners }),
+                Err(e) => json!({ "success": false, "message": e.to_string() }),
+            };
+            Ok::<_, warp::Rejection>(warp::reply::json(&reply))
+        });
+
+    let unban = warp::path!("tts" / "unban")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_storage(storage))
+        .and_then(handle_unban);
+
+    queue.or(approve).or(reject).or(skip).or(unban)
+}
+
+async fn handle_get_queue(
+    storage: Arc<RwLock<StorageClient>>,
+    queue: Arc<TtsQueue>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.get_tts_bans() {
+        Ok(bans) => Ok(warp::reply::json(&json!({
+            "pending": queue.pending(),
+            "bans": bans,
+        }))),
+        Err(e) => {
+            error!("Failed to fetch TTS bans: {:?}", e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
+
+async fn handle_unban(
+    request: TtsUnbanRequest,
+    storage: Arc<RwLock<StorageClient>>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    match storage.read().await.remove_tts_ban(&request.user) {
+        Ok(removed) => Ok(warp::reply::json(&json!({ "success": removed }))),
+        Err(e) => {
+            error!("Failed to unban {} from TTS: {:?}", request.user, e);
+            Err(warp::reject::custom(ApiError::DatabaseError))
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use tracing::{error, info};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::twitch::voice::BotVoice;
+use crate::web_ui::api_routes::{with_config, ApiError};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct VoiceUpdate {
+    active: String,
+}
+
+fn voice_json(voice: &BotVoice) -> serde_json::Value {
+    json!({
+        "active": voice.active(),
+        "voices": voice.available(),
+    })
+}
+
+// /api/voice (GET, POST {active})
+pub fn voice_api_routes(
+    config: Arc<RwLock<Config>>,
+    voice: Arc<BotVoice>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_voice = warp::any().map(move || voice.clone());
+
+    let get_voice = warp::path("voice")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_voice.clone())
+        .and_then(handle_get_voice);
+
+    let update_voice = warp::path("voice")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_config(config))
+        .and(with_voice)
+        .and_then(handle_update_voice);
+
+    get_voice.or(update_voice)
+}
+
+async fn handle_get_voice(voice: Arc<BotVoice>) -> Result<impl warp::Reply, warp::Rejection> {
+    Ok(warp::reply::json(&voice_json(&voice)))
+}
+
+async fn handle_update_voice(
+    update: VoiceUpdate,
+    config: Arc<RwLock<Config>>,
+    voice: Arc<BotVoice>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let name = voice.set_active(&update.active)
+        .ok_or_else(|| warp::reject::custom(ApiError::InvalidInput))?;
+
+    let mut config = config.write().await;
+    config.bot_voice.active = name.clone();
+    if let Err(e) = config.save() {
+        error!("Failed to save bot voice: {:?}", e);
+        return Err(warp::reject::custom(ApiError::ConfigUpdateError));
+    }
+
+    info!("Bot voice set to {}", name);
+    Ok(warp::reply::json(&voice_json(&voice)))
+}
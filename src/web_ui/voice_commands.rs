@@ -0,0 +1,176 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::{Config, VoiceCommand, VoiceCommandsConfig};
+use crate::obs::OBSManager;
+use crate::twitch::irc::TwitchIRCManager;
+use crate::vrchat::VRChatManager;
+use crate::web_ui::api_routes::with_config;
+use crate::web_ui::websocket::{handle_websocket, WebSocketMessage};
+
+const CONFIRM_WORDS: &[&str] = &["confirm", "yes", "do it"];
+const CANCEL_WORDS: &[&str] = &["cancel", "no", "never mind", "nevermind"];
+
+// The command waiting for "confirm", and when it stops waiting. Owned by TwitchManager.
+#[derive(Default)]
+pub struct PendingVoiceCommand {
+    command: Mutex<Option<(VoiceCommand, Instant)>>,
+}
+
+impl PendingVoiceCommand {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HeardRequest {
+    text: String,
+}
+
+// Where control messages go, the same as from the dashboard websocket
+#[derive(Clone)]
+pub struct VoiceCommandTargets {
+    pub obs_manager: Arc<OBSManager>,
+    pub twitch_irc_manager: Arc<TwitchIRCManager>,
+    pub vrchat_manager: Option<Arc<VRChatManager>>,
+}
+
+enum Heard {
+    // No wake word, so it was just talking
+    Ignored,
+    Unknown(String),
+    Run(VoiceCommand),
+    NeedsConfirmation(VoiceCommand),
+    Confirmed(VoiceCommand),
+    Cancelled,
+    NothingPending,
+}
+
+// /voice/heard POST {text}, one recognized utterance from a local speech recognizer or a
+// companion app relaying VRChat voice. Sits under /api, so it needs an admin token off this PC.
+pub fn voice_command_routes(
+    config: Arc<RwLock<Config>>,
+    targets: VoiceCommandTargets,
+    pending: Arc<PendingVoiceCommand>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("voice" / "heard")
+        .and(warp::post())
+        .and(with_config(config))
+        .and(warp::body::json())
+        .and(warp::any().map(move || targets.clone()))
+        .and(warp::any().map(move || pending.clone()))
+        .and_then(handle_heard)
+}
+
+async fn handle_heard(
+    config: Arc<RwLock<Config>>,
+    request: HeardRequest,
+    targets: VoiceCommandTargets,
+    pending: Arc<PendingVoiceCommand>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let voice_config = config.read().await.voice_commands.clone();
+    if !voice_config.enabled {
+        return Ok(warp::reply::json(&json!({ "success": false, "message": "Voice commands are turned off" })));
+    }
+
+    let (status, message) = match interpret(&voice_config, &pending, &request.text) {
+        Heard::Ignored => ("ignored", String::new()),
+        Heard::Unknown(phrase) => ("unknown", format!("No voice command for \"{}\"", phrase)),
+        Heard::Run(command) | Heard::Confirmed(command) => {
+            let message = format!("Running \"{}\"", command.phrases.first().cloned().unwrap_or_default());
+            run(command, &targets).await;
+            ("done", message)
+        }
+        Heard::NeedsConfirmation(command) => (
+            "needs_confirmation",
+            format!(
+                "Say \"{}, confirm\" within {}s to run \"{}\"",
+                voice_config.wake_word, voice_config.confirm_secs, command.phrases.first().cloned().unwrap_or_default()
+            ),
+        ),
+        Heard::Cancelled => ("cancelled", "Cancelled".to_string()),
+        Heard::NothingPending => ("nothing_pending", "There's nothing waiting for confirmation".to_string()),
+    };
+    if status != "ignored" {
+        info!("Voice command \"{}\": {}", request.text.trim(), status);
+    }
+    Ok(warp::reply::json(&json!({ "success": true, "status": status, "message": message })))
+}
+
+// Lowercase words without punctuation, so "Bot, switch to BRB!" and "bot switch to brb" match alike
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_alphanumeric() || c == '\'' { c.to_lowercase().next().unwrap_or(c) } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn contains_phrase(said: &str, phrase: &str) -> bool {
+    let phrase = normalize(phrase);
+    !phrase.is_empty() && format!(" {} ", said).contains(&format!(" {} ", phrase))
+}
+
+fn interpret(config: &VoiceCommandsConfig, pending: &PendingVoiceCommand, text: &str) -> Heard {
+    let said = normalize(text);
+    let wake_word = normalize(&config.wake_word);
+    let said = match said.strip_prefix(&wake_word) {
+        Some(rest) if wake_word.is_empty() || rest.is_empty() || rest.starts_with(' ') => rest.trim().to_string(),
+        _ => return Heard::Ignored,
+    };
+
+    let mut pending = pending.command.lock().unwrap();
+    if matches!(pending.as_ref(), Some((_, expires)) if *expires <= Instant::now()) {
+        *pending = None;
+    }
+    if CONFIRM_WORDS.iter().any(|word| said == *word) {
+        return match pending.take() {
+            Some((command, _)) => Heard::Confirmed(command),
+            None => Heard::NothingPending,
+        };
+    }
+    if CANCEL_WORDS.iter().any(|word| said == *word) {
+        return match pending.take() {
+            Some(_) => Heard::Cancelled,
+            None => Heard::NothingPending,
+        };
+    }
+
+    // The longest matching phrase wins, so "switch to main camera" beats "switch to main"
+    let command = config.commands.iter()
+        .filter_map(|command| {
+            let longest = command.phrases.iter().filter(|phrase| contains_phrase(&said, phrase)).map(|phrase| phrase.len()).max()?;
+            Some((longest, command))
+        })
+        .max_by_key(|(length, _)| *length)
+        .map(|(_, command)| command.clone());
+
+    match command {
+        Some(command) if command.confirm => {
+            *pending = Some((command.clone(), Instant::now() + Duration::from_secs(config.confirm_secs)));
+            Heard::NeedsConfirmation(command)
+        }
+        Some(command) => Heard::Run(command),
+        None => Heard::Unknown(said),
+    }
+}
+
+async fn run(command: VoiceCommand, targets: &VoiceCommandTargets) {
+    let message = WebSocketMessage {
+        module: command.module,
+        action: command.action,
+        data: command.data,
+    };
+    handle_websocket(
+        message,
+        targets.obs_manager.clone(),
+        targets.twitch_irc_manager.clone(),
+        targets.vrchat_manager.clone(),
+    ).await;
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+use tracing::{error, info};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::RwLock;
+use warp::Filter;
+use crate::config::Config;
+use crate::osc::VRChatOSC;
+use crate::twitch::irc::TwitchIRCManager;
+use crate::twitch::watch_queue::{self, WatchRequests};
+use crate::web_ui::api_routes::with_config;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub(crate) struct WatchItemRequest {
+    id: u64,
+}
+
+//   /api/watch         GET what's on and the queue
+//   /api/watch/next    POST, moves the next video up and shows it in the chatbox and chat
+//   /api/watch/remove  POST {id}
+//   /api/watch/clear   POST, empties the queue and forgets what was watched
+pub fn watch_queue_api_routes(
+    config: Arc<RwLock<Config>>,
+    vrchat_osc: Arc<VRChatOSC>,
+    twitch_irc_manager: Arc<TwitchIRCManager>,
+    requests: Arc<WatchRequests>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_requests = warp::any().map(move || requests.clone());
+
+    let queue = warp::path!("watch")
+        .and(warp::get())
+        .and(with_config(config.clone()))
+        .and(with_requests.clone())
+        .and_then(|config: Arc<RwLock<Config>>, requests: Arc<WatchRequests>| async move {
+            let enabled = config.read().await.watch_queue.enabled;
+            let queue = requests.snapshot();
+            Ok::<_, warp::Rejection>(warp::reply::json(&json!({
+                "enabled": enabled,
+                "current": queue.current,
+                "queue": queue.queue,
+            })))
+        });
+
+    let next = warp::path!("watch" / "next")
+        .and(warp::post())
+        .and(with_config(config))
+        .and(warp::any().map(move || (vrchat_osc.clone(), twitch_irc_manager.clone())))
+        .and(with_requests.clone())
+        .and_then(handle_next);
+
+    let remove = warp::path!("watch" / "remove")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_requests.clone())
+        .map(|request: WatchItemRequest, requests: Arc<WatchRequests>| warp::reply::json(&json!({ "success": requests.remove(request.id) })));
+
+    let clear = warp::path!("watch" / "clear")
+        .and(warp::post())
+        .and(with_requests)
+        .map(|requests: Arc<WatchRequests>| {
+            requests.clear();
+            warp::reply::json(&json!({ "success": true }))
+        });
+
+    queue.or(next).or(remove).or(clear)
+}
+
+async fn handle_next(
+    config: Arc<RwLock<Config>>,
+    (vrchat_osc, twitch_irc_manager): (Arc<VRChatOSC>, Arc<TwitchIRCManager>),
+    requests: Arc<WatchRequests>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let (watch_config, bot, channel) = {
+        let config = config.read().await;
+        (config.watch_queue.clone(), config.twitch_bot_username.clone(), config.twitch_channel_to_join.clone())
+    };
+
+    let current = requests.next(&vrchat_osc, &watch_config).await;
+    if let (Some(item), Some(bot), Some(channel)) = (&current, bot, channel) {
+        info!("Now watching {} from {}", item.url, item.requested_by);
+        let message = format!("🍿 Now watching {}, requested by @{}", watch_queue::describe(item), item.requested_by);
+        if let Err(e) = twitch_irc_manager.send_message(&bot, &channel, &message).await {
+            error!("Failed to announce the next video: {}", e);
+        }
+    }
+
+    Ok(warp::reply::json(&json!({ "success": current.is_some(), "current": current })))
+}
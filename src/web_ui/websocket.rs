@@ -1,14 +1,19 @@
+use std::collections::VecDeque;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, to_string, Value};
-use log::{error, info, debug, warn, trace};
-use tokio::sync::broadcast::error::SendError;
+use tracing::{error, info, debug, warn, trace};
+use crate::backpressure::{BroadcastChannelConfig, MonitoredReceiver, MonitoredSender};
 use crate::obs::OBSManager;
 use crate::twitch::{TwitchIRCManager};
 use crate::vrchat::{VRChatManager};
 use crate::bot_status::BotStatus;
 
+const MAX_NOTIFICATIONS: usize = 10;
+// Pushes kept for clients that reconnect; a few minutes of a busy stream
+const REPLAY_CAPACITY: usize = 256;
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct WebSocketMessage {
     pub module: String,
@@ -26,6 +31,54 @@ impl WebSocketMessage {
     }
 }
 
+// What dashboard clients receive. Events carry a sequence number so a client that reconnects with
+// `?resume=<seq>&epoch=<epoch>` is sent what it missed; the full-state "dashboard" updates that go
+// out every few seconds don't, since the next one replaces them anyway.
+#[derive(Clone, Serialize, Debug)]
+pub struct SequencedMessage {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+    #[serde(flatten)]
+    pub message: WebSocketMessage,
+}
+
+// Where a resuming client picks up from
+pub enum Resume {
+    // Everything after the client's last sequence number, oldest first
+    Replay(Vec<SequencedMessage>),
+    // The bot restarted or too much happened since; the client has to reload its state
+    Resync,
+}
+
+struct ReplayBuffer {
+    next_seq: u64,
+    messages: VecDeque<SequencedMessage>,
+}
+
+impl ReplayBuffer {
+    fn resume_from(&self, same_epoch: bool, last_seq: u64) -> Resume {
+        if !same_epoch || last_seq >= self.next_seq {
+            return Resume::Resync;
+        }
+        // The oldest message still kept has to follow on from what the client saw
+        let oldest = self.messages.front().and_then(|message| message.seq).unwrap_or(self.next_seq);
+        if last_seq + 1 < oldest {
+            return Resume::Resync;
+        }
+        Resume::Replay(self.messages.iter()
+            .filter(|message| message.seq.map_or(false, |seq| seq > last_seq))
+            .cloned()
+            .collect())
+    }
+}
+
+pub struct ConnectInfo {
+    pub epoch: String,
+    // The newest sequence number handed out before this client subscribed
+    pub last_seq: u64,
+    pub resume: Option<Resume>,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Default)]
 pub struct DashboardUpdateData {
     pub uptime: String,
@@ -40,6 +93,10 @@ pub struct DashboardUpdateData {
     pub scene_name: Option<String>,
     pub source_name: Option<String>,
     pub enabled: Option<bool>,
+    pub redeem_queue_head: Option<String>,
+    pub redeem_queue_pending: usize,
+    #[serde(default)]
+    pub notifications: Vec<String>,
 }
 
 pub struct DashboardState {
@@ -50,22 +107,29 @@ pub struct DashboardState {
     pub(crate) vrchat_status: bool,
     pub(crate) obs_status: bool,
     recent_messages: Vec<String>,
-    pub(crate) tx: broadcast::Sender<WebSocketMessage>,
+    tx: MonitoredSender<SequencedMessage>,
+    replay: Arc<Mutex<ReplayBuffer>>,
+    // Changes every start, so sequence numbers from before a restart aren't mistaken for current ones
+    epoch: String,
     pub obs_instances: Vec<crate::obs::OBSInstanceState>,
+    pub(crate) redeem_queue_head: Option<String>,
+    pub(crate) redeem_queue_pending: usize,
+    pub(crate) notifications: Vec<String>,
 }
 
 
 impl Default for DashboardState {
     fn default() -> Self {
-        Self::new(Arc::new(RwLock::new(BotStatus::default())))
+        Self::new(Arc::new(RwLock::new(BotStatus::default())), &BroadcastChannelConfig::with_capacity(100))
     }
 }
 
 impl DashboardState {
     pub fn new(
         bot_status: Arc<RwLock<BotStatus>>,
+        broadcast_config: &BroadcastChannelConfig,
     ) -> Self {
-        let (tx, _rx) = broadcast::channel(100); // You can adjust the channel size as needed
+        let tx = MonitoredSender::new("dashboard", broadcast_config);
         Self {
             bot_status,
             vrchat_world: None,
@@ -75,9 +139,19 @@ impl DashboardState {
             obs_status: false,
             recent_messages: Vec::new(),
             tx,
+            replay: Arc::new(Mutex::new(ReplayBuffer { next_seq: 1, messages: VecDeque::new() })),
+            epoch: uuid::Uuid::new_v4().simple().to_string(),
             obs_instances: Vec::new(),
+            redeem_queue_head: None,
+            redeem_queue_pending: 0,
+            notifications: Vec::new(),
         }
     }
+
+    pub fn channel_metrics(&self) -> (String, serde_json::Value) {
+        self.tx.metrics_snapshot()
+    }
+
     pub async fn update_twitch_status(&mut self, status: bool) {
         self.twitch_status = status;
         self.broadcast_update().await;
@@ -98,6 +172,19 @@ impl DashboardState {
         self.broadcast_update().await;
     }
 
+    pub async fn update_redeem_queue(&mut self, head: Option<String>, pending: usize) {
+        self.redeem_queue_head = head;
+        self.redeem_queue_pending = pending;
+        self.broadcast_update().await;
+    }
+
+    // Newest first; only the latest few are kept
+    pub async fn add_notification(&mut self, text: String) {
+        self.notifications.insert(0, text);
+        self.notifications.truncate(MAX_NOTIFICATIONS);
+        self.broadcast_update().await;
+    }
+
     pub async fn update_vrchat_world(&mut self, world: Option<crate::vrchat::models::World>) {
         self.vrchat_world = world;
         self.broadcast_update().await;
@@ -116,16 +203,47 @@ impl DashboardState {
                 "vrchat_status": self.vrchat_status,
                 "obs_status": self.obs_status,
                 "obs_instances": self.obs_instances,
+                "redeem_queue_head": self.redeem_queue_head,
+                "redeem_queue_pending": self.redeem_queue_pending,
+                "notifications": self.notifications,
             }),
         };
 
-        if let Err(e) = self.tx.send(update) {
-            error!("Failed to broadcast dashboard update: {:?}", e);
+        self.send_snapshot(update).await;
+    }
+
+    async fn send_snapshot(&self, message: WebSocketMessage) -> usize {
+        self.tx.send(SequencedMessage { seq: None, message }).await
+    }
+
+    pub async fn broadcast_message(&self, message: WebSocketMessage) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        // Held through the send so sequence numbers go out in order
+        let mut replay = self.replay.lock().await;
+        let message = SequencedMessage { seq: Some(replay.next_seq), message };
+        replay.next_seq += 1;
+        replay.messages.push_back(message.clone());
+        if replay.messages.len() > REPLAY_CAPACITY {
+            replay.messages.pop_front();
         }
+        Ok(self.tx.send(message).await)
     }
 
-    pub async fn broadcast_message(&self, message: WebSocketMessage) -> Result<usize, SendError<WebSocketMessage>> {
-        self.tx.send(message)
+    // For a client connecting, or reconnecting with the epoch and last sequence number it saw.
+    // Subscribing and reading the backlog happen under one lock, so no event falls between the two.
+    pub async fn connect(&self, resume: Option<(&str, u64)>) -> (MonitoredReceiver<SequencedMessage>, ConnectInfo) {
+        let replay = self.replay.lock().await;
+        let updates = self.tx.subscribe();
+        let info = ConnectInfo {
+            epoch: self.epoch.clone(),
+            last_seq: replay.next_seq - 1,
+            resume: resume.map(|(epoch, last_seq)| replay.resume_from(epoch == self.epoch, last_seq)),
+        };
+        (updates, info)
+    }
+
+    // Number of dashboard websocket clients currently connected
+    pub fn viewer_count(&self) -> usize {
+        self.tx.receiver_count()
     }
 }
 
@@ -133,7 +251,7 @@ pub async fn handle_websocket(
     msg: WebSocketMessage,
     obs_manager: Arc<OBSManager>,
     twitch_irc_manager: Arc<TwitchIRCManager>,
-    vrchat_manager: Arc<VRChatManager>,
+    vrchat_manager: Option<Arc<VRChatManager>>,
 ) {
     trace!("received websocket [action] {:?}", msg.action);
     trace!("received websocket [module] {:?}", msg.module);
@@ -150,8 +268,10 @@ pub async fn handle_websocket(
             }
         },
         "vrchat" => {
-            if let Err(e) = vrchat_manager.handle_message(msg).await {
-                debug!("Error handling VRChat message: {:?}", e);
+            if let Some(vrchat_manager) = vrchat_manager {
+                if let Err(e) = vrchat_manager.handle_message(msg).await {
+                    debug!("Error handling VRChat message: {:?}", e);
+                }
             }
         },
         _ => {
@@ -176,9 +296,12 @@ pub async fn send_dashboard_update(
             "vrchat_status": state.vrchat_status,
             "obs_status": state.obs_status,
             "obs_instances": state.obs_instances,
+            "redeem_queue_head": state.redeem_queue_head,
+            "redeem_queue_pending": state.redeem_queue_pending,
+            "notifications": state.notifications,
         }),
     };
-    state.broadcast_message(update).await?;
+    state.send_snapshot(update).await;
     Ok(())
 }
 
@@ -3,6 +3,22 @@ import Layout from './components/Layout';
 import Dashboard from './components/Dashboard';
 import OBSOverlay from './components/OBSOverlay';
 import Settings from './components/Settings';
+import ModerationLog from './components/ModerationLog';
+import KeywordTriggers from './components/KeywordTriggers';
+import ClipsGallery from './components/ClipsGallery';
+import Leaderboard from './components/Leaderboard';
+import Milestones from './components/Milestones';
+import OSCDesigner from './components/OSCDesigner';
+import PromptEditor from './components/PromptEditor';
+import Jobs from './components/Jobs';
+import EventInspector from './components/EventInspector';
+import CommandUsage from './components/CommandUsage';
+import CustomCommands from './components/CustomCommands';
+import ApiTokens from './components/ApiTokens';
+import RemoteControl from './components/RemoteControl';
+import TtsQueue from './components/TtsQueue';
+import Suggestions from './components/Suggestions';
+import WatchQueue from './components/WatchQueue';
 import ErrorBoundary from './components/ErrorBoundary';
 
 const App = () => {
@@ -19,6 +35,38 @@ const App = () => {
                 );
             case 'OBS Overlay':
                 return <ErrorBoundary><OBSOverlay /></ErrorBoundary>;
+            case 'Moderation':
+                return <ErrorBoundary><ModerationLog /></ErrorBoundary>;
+            case 'Triggers':
+                return <ErrorBoundary><KeywordTriggers /></ErrorBoundary>;
+            case 'Clips':
+                return <ErrorBoundary><ClipsGallery /></ErrorBoundary>;
+            case 'Leaderboard':
+                return <ErrorBoundary><Leaderboard /></ErrorBoundary>;
+            case 'Milestones':
+                return <ErrorBoundary><Milestones /></ErrorBoundary>;
+            case 'OSC':
+                return <ErrorBoundary><OSCDesigner /></ErrorBoundary>;
+            case 'Prompts':
+                return <ErrorBoundary><PromptEditor /></ErrorBoundary>;
+            case 'Jobs':
+                return <ErrorBoundary><Jobs /></ErrorBoundary>;
+            case 'Events':
+                return <ErrorBoundary><EventInspector /></ErrorBoundary>;
+            case 'Commands':
+                return <ErrorBoundary><CustomCommands /></ErrorBoundary>;
+            case 'Command Usage':
+                return <ErrorBoundary><CommandUsage /></ErrorBoundary>;
+            case 'API Tokens':
+                return <ErrorBoundary><ApiTokens /></ErrorBoundary>;
+            case 'TTS':
+                return <ErrorBoundary><TtsQueue /></ErrorBoundary>;
+            case 'Suggestions':
+                return <ErrorBoundary><Suggestions /></ErrorBoundary>;
+            case 'Watch Queue':
+                return <ErrorBoundary><WatchQueue /></ErrorBoundary>;
+            case 'Remote':
+                return <ErrorBoundary><RemoteControl /></ErrorBoundary>;
             case 'Settings':
                 return <ErrorBoundary><Settings /></ErrorBoundary>;
             default:
@@ -0,0 +1,28 @@
+// Only needed when the dashboard isn't opened straight from the bot's PC (see `mewbot admin-token`)
+const STORAGE_KEY = 'mewbot_api_token';
+
+export const getApiToken = () => localStorage.getItem(STORAGE_KEY);
+
+// Picks up ?token= from the address once, then sends the token with every /api request
+export const installApiToken = () => {
+    const params = new URLSearchParams(window.location.search);
+    const token = params.get('token');
+    if (token) {
+        localStorage.setItem(STORAGE_KEY, token);
+        params.delete('token');
+        const query = params.toString();
+        window.history.replaceState(null, '', `${window.location.pathname}${query ? `?${query}` : ''}${window.location.hash}`);
+    }
+
+    const originalFetch = window.fetch.bind(window);
+    window.fetch = (input, init = {}) => {
+        const stored = getApiToken();
+        const url = typeof input === 'string' ? input : input.url;
+        if (!stored || !url.startsWith('/api')) {
+            return originalFetch(input, init);
+        }
+        const headers = new Headers(init.headers || {});
+        headers.set('Authorization', `Bearer ${stored}`);
+        return originalFetch(input, { ...init, headers });
+    };
+};
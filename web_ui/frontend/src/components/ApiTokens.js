@@ -0,0 +1,145 @@
+import React, { useState, useEffect } from 'react';
+
+const SCOPES = [
+    { id: 'overlay', label: 'Overlay', description: 'Only what overlays show (leaderboard, polls, alerts, redeem queue); safe for a browser source on another PC' },
+    { id: 'read-stats', label: 'Read stats', description: 'Every GET endpoint and live dashboard updates' },
+    { id: 'send-chat', label: 'Send chat', description: 'Twitch chat through the dashboard websocket' },
+    { id: 'control-obs', label: 'Control OBS', description: 'OBS scenes and sources through the dashboard websocket' },
+    { id: 'run-redeems', label: 'Run redeems', description: 'DoAction on the Streamer.bot-compatible /compat websocket, which also needs Read stats' },
+    { id: 'admin', label: 'Admin', description: 'Everything, including settings and tokens' },
+];
+
+const formatTime = (time) => (time ? new Date(time).toLocaleString() : 'never');
+
+const ApiTokens = () => {
+    const [tokens, setTokens] = useState([]);
+    const [name, setName] = useState('');
+    const [scopes, setScopes] = useState(['read-stats']);
+    const [newToken, setNewToken] = useState(null);
+    const [error, setError] = useState(null);
+
+    const fetchTokens = () => {
+        fetch('/api/tokens')
+            .then((response) => response.json())
+            .then((data) => setTokens(data.tokens))
+            .catch((err) => console.error('Failed to fetch API tokens:', err));
+    };
+
+    useEffect(fetchTokens, []);
+
+    const toggleScope = (scope) => {
+        setScopes(scopes.includes(scope) ? scopes.filter((s) => s !== scope) : [...scopes, scope]);
+    };
+
+    const post = async (url, body) => {
+        const response = await fetch(url, {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(body),
+        });
+        return response.json();
+    };
+
+    const createToken = async () => {
+        setError(null);
+        try {
+            const result = await post('/api/tokens', { name, scopes });
+            if (result.success) {
+                setNewToken(result.token);
+                setName('');
+                fetchTokens();
+            } else {
+                setError(result.message);
+            }
+        } catch (err) {
+            setError(err.message);
+        }
+    };
+
+    const revokeToken = async (id) => {
+        try {
+            await post('/api/tokens/revoke', { id });
+            fetchTokens();
+        } catch (err) {
+            console.error('Failed to revoke API token:', err);
+        }
+    };
+
+    return (
+        <div className="p-4">
+            <h2 className="text-2xl font-bold mb-4">API Tokens</h2>
+            <p className="text-gray-400 text-sm mb-4">
+                Tokens let other tools and machines use the dashboard API. Send them as
+                <code className="mx-1">Authorization: Bearer &lt;token&gt;</code>
+                or add <code className="mx-1">?token=&lt;token&gt;</code> to the URL (for the websocket and OBS browser sources).
+                The endpoints are described in <a href="/api/openapi.json" className="text-purple-400" target="_blank" rel="noopener noreferrer">/api/openapi.json</a>.
+            </p>
+            <div className="bg-gray-800 p-4 rounded-lg mb-4">
+                <h3 className="text-lg font-bold mb-2">New token</h3>
+                <input
+                    type="text"
+                    value={name}
+                    onChange={(e) => setName(e.target.value)}
+                    placeholder="What is it for?"
+                    className="w-full bg-gray-700 text-white rounded p-2 mb-2"
+                />
+                {SCOPES.map((scope) => (
+                    <label key={scope.id} className="block text-gray-300">
+                        <input
+                            type="checkbox"
+                            checked={scopes.includes(scope.id)}
+                            onChange={() => toggleScope(scope.id)}
+                            className="mr-2"
+                        />
+                        {scope.label} <span className="text-gray-500 text-sm">- {scope.description}</span>
+                    </label>
+                ))}
+                <button
+                    onClick={createToken}
+                    className="mt-2 bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded"
+                >
+                    Create token
+                </button>
+                {error && <p className="text-red-500 mt-2">{error}</p>}
+                {newToken && (
+                    <div className="mt-2 p-2 bg-gray-900 rounded">
+                        <p className="text-yellow-400 text-sm">Copy this now, it won't be shown again:</p>
+                        <code className="text-green-400 break-all">{newToken}</code>
+                    </div>
+                )}
+            </div>
+            <div className="bg-gray-800 p-4 rounded-lg overflow-x-auto">
+                <table className="w-full text-left text-gray-300">
+                    <thead>
+                        <tr className="text-gray-400">
+                            <th className="pb-2">Name</th>
+                            <th className="pb-2">Scopes</th>
+                            <th className="pb-2">Created</th>
+                            <th className="pb-2">Last used</th>
+                            <th className="pb-2"></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {tokens.map((token) => (
+                            <tr key={token.id} className={`border-t border-gray-700 ${token.revoked ? 'opacity-50' : ''}`}>
+                                <td className="py-1">{token.name}</td>
+                                <td className="py-1 font-mono text-sm">{token.scopes.join(', ')}</td>
+                                <td className="py-1">{formatTime(token.created_at)}</td>
+                                <td className="py-1">{formatTime(token.last_used_at)}</td>
+                                <td className="py-1">
+                                    {token.revoked ? 'Revoked' : (
+                                        <button onClick={() => revokeToken(token.id)} className="text-red-400 hover:text-red-300">
+                                            Revoke
+                                        </button>
+                                    )}
+                                </td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    );
+};
+
+export default ApiTokens;
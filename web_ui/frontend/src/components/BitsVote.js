@@ -0,0 +1,68 @@
+import React, { useState, useEffect, useCallback } from 'react';
+import useWebSocket from './useWebSocket';
+
+const BitsVote = () => {
+    const [vote, setVote] = useState(null);
+    const [winner, setWinner] = useState(null);
+    const [now, setNow] = useState(Date.now());
+
+    useEffect(() => {
+        fetch('/api/bitsvote')
+            .then((response) => response.json())
+            .then((data) => setVote(data.vote))
+            .catch((err) => console.error('Failed to fetch bits vote:', err));
+    }, []);
+
+    useEffect(() => {
+        const timer = setInterval(() => setNow(Date.now()), 1000);
+        return () => clearInterval(timer);
+    }, []);
+
+    const handleWebSocketMessage = useCallback((data) => {
+        if (data.module === 'bitsvote') {
+            setVote(data.data.vote);
+            setWinner(data.data.winner);
+        }
+    }, []);
+
+    const handleWebSocketError = useCallback((error) => {
+        console.error('WebSocket error:', error);
+    }, []);
+
+    useWebSocket(`ws://${window.location.hostname}:3333/ws`, handleWebSocketMessage, handleWebSocketError);
+
+    if (!vote) {
+        return null;
+    }
+
+    const secondsLeft = Math.max(0, Math.round((new Date(vote.ends_at) - now) / 1000));
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-1 text-white">{vote.closed ? 'Bits vote results' : 'Bits vote'}</h2>
+            <p className="text-gray-300 mb-4">{vote.question}</p>
+            <ul className="space-y-2">
+                {vote.options.map((option, index) => {
+                    const percent = vote.total_bits ? Math.round(option.bits * 100 / vote.total_bits) : 0;
+                    const winning = vote.closed && winner === index + 1;
+                    return (
+                        <li key={index}>
+                            <div className="flex justify-between text-gray-300">
+                                <span className={winning ? 'font-bold text-green-400' : ''}>{index + 1}) {option.name}</span>
+                                <span>{option.bits} bits ({percent}%)</span>
+                            </div>
+                            <div className="w-full bg-gray-700 rounded h-2">
+                                <div className="bg-purple-600 h-2 rounded" style={{ width: `${percent}%` }} />
+                            </div>
+                        </li>
+                    );
+                })}
+            </ul>
+            <p className="text-gray-400 text-sm mt-2">
+                {vote.total_bits} bits{vote.closed ? '' : ` - ${secondsLeft}s left, overlay at /bitsvote/overlay`}
+            </p>
+        </div>
+    );
+};
+
+export default BitsVote;
@@ -0,0 +1,60 @@
+import React, { useState, useEffect } from 'react';
+
+const BotVoiceSettings = () => {
+    const [voice, setVoice] = useState(null);
+    const [status, setStatus] = useState(null);
+    const [error, setError] = useState(null);
+
+    useEffect(() => {
+        fetch('/api/voice')
+            .then((response) => response.json())
+            .then(setVoice)
+            .catch((err) => setError(err.message));
+    }, []);
+
+    const saveVoice = async (active) => {
+        try {
+            const response = await fetch('/api/voice', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ active }),
+            });
+            if (!response.ok) {
+                throw new Error('The server rejected the change');
+            }
+            setVoice(await response.json());
+            setStatus('Bot voice saved');
+            setError(null);
+        } catch (err) {
+            console.error('Failed to save bot voice:', err);
+            setError(err.message);
+        }
+    };
+
+    if (!voice) {
+        return error ? <p className="text-red-500">{error}</p> : null;
+    }
+
+    return (
+        <div className="mt-8">
+            <h3 className="text-xl font-bold mb-4 dark:text-white">Bot voice</h3>
+            {status && <p className="text-green-500 mb-2">{status}</p>}
+            {error && <p className="text-red-500 mb-2">{error}</p>}
+            <label className="block max-w-xs">
+                <span className="text-gray-400">Reply style</span>
+                <select
+                    value={voice.active}
+                    onChange={(e) => saveVoice(e.target.value)}
+                    className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+                >
+                    {voice.voices.map((name) => <option key={name} value={name}>{name}</option>)}
+                </select>
+            </label>
+            <p className="text-gray-400 text-sm mt-2">
+                Moderators can also switch it from chat with <code>!botvoice &lt;name&gt;</code>. Custom packs go under <code>[bot_voice.packs]</code> in the config.
+            </p>
+        </div>
+    );
+};
+
+export default BotVoiceSettings;
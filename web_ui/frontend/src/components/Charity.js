@@ -0,0 +1,56 @@
+import React, { useState, useEffect, useCallback } from 'react';
+import useWebSocket from './useWebSocket';
+
+const money = (amount, currency) => {
+    try {
+        return new Intl.NumberFormat(undefined, { style: 'currency', currency }).format(amount);
+    } catch (err) {
+        return `${amount.toFixed(2)} ${currency}`;
+    }
+};
+
+const Charity = () => {
+    const [campaign, setCampaign] = useState(null);
+
+    useEffect(() => {
+        fetch('/api/charity')
+            .then((response) => response.json())
+            .then((data) => setCampaign(data.campaign))
+            .catch((err) => console.error('Failed to fetch charity campaign:', err));
+    }, []);
+
+    const handleWebSocketMessage = useCallback((data) => {
+        if (data.module === 'charity') {
+            setCampaign(data.data.campaign);
+        }
+    }, []);
+
+    const handleWebSocketError = useCallback((error) => {
+        console.error('WebSocket error:', error);
+    }, []);
+
+    useWebSocket(`ws://${window.location.hostname}:3333/ws`, handleWebSocketMessage, handleWebSocketError);
+
+    if (!campaign) {
+        return null;
+    }
+
+    const percent = campaign.target > 0 ? Math.min(100, Math.floor(campaign.current * 100 / campaign.target)) : 0;
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-1 text-white">{campaign.active ? 'Charity campaign' : 'Last charity campaign'}</h2>
+            <p className="text-gray-300 mb-4">{campaign.charity_name}</p>
+            <div className="flex justify-between text-gray-300">
+                <span>{money(campaign.current, campaign.currency)} of {money(campaign.target, campaign.currency)}</span>
+                <span>{percent}%</span>
+            </div>
+            <div className="w-full bg-gray-700 rounded h-2">
+                <div className="bg-green-500 h-2 rounded" style={{ width: `${percent}%` }} />
+            </div>
+            {campaign.active && <p className="text-gray-400 text-sm mt-2">Overlay at /charity/overlay</p>}
+        </div>
+    );
+};
+
+export default Charity;
@@ -0,0 +1,77 @@
+import React, { useState, useEffect } from 'react';
+
+const groupByStream = (clips) => {
+    const groups = {};
+    clips.forEach((clip) => {
+        const key = clip.stream_id || 'other';
+        if (!groups[key]) {
+            groups[key] = { key, clips: [], latest: clip.timestamp };
+        }
+        groups[key].clips.push(clip);
+    });
+    return Object.values(groups).sort((a, b) => new Date(b.latest) - new Date(a.latest));
+};
+
+const ClipsGallery = () => {
+    const [clips, setClips] = useState([]);
+    const [error, setError] = useState(null);
+
+    useEffect(() => {
+        const fetchClips = async () => {
+            try {
+                const response = await fetch('/api/clips');
+                if (!response.ok) {
+                    throw new Error('Failed to fetch clips');
+                }
+                setClips(await response.json());
+                setError(null);
+            } catch (err) {
+                console.error('Failed to fetch clips:', err);
+                setError(err.message);
+            }
+        };
+        fetchClips();
+    }, []);
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Clips</h2>
+            {error && <p className="text-red-500 mb-4">{error}</p>}
+            {clips.length === 0 && !error && <p className="text-gray-400">No clips archived yet.</p>}
+            {groupByStream(clips).map((group) => (
+                <div key={group.key} className="mb-6">
+                    <h3 className="text-lg font-semibold text-purple-400 mb-2">
+                        {group.key === 'other'
+                            ? 'Other clips'
+                            : `Stream of ${new Date(group.clips[group.clips.length - 1].created_at || group.latest).toLocaleDateString()}`}
+                        <span className="text-gray-400 text-sm ml-2">({group.clips.length})</span>
+                    </h3>
+                    <div className="grid grid-cols-2 md:grid-cols-3 gap-4">
+                        {group.clips.map((clip) => (
+                            <a
+                                key={clip.clip_id}
+                                href={clip.url}
+                                target="_blank"
+                                rel="noopener noreferrer"
+                                className="bg-gray-700 rounded overflow-hidden hover:bg-gray-600"
+                            >
+                                {clip.thumbnail_url && (
+                                    <img src={clip.thumbnail_url} alt={clip.title} className="w-full" />
+                                )}
+                                <div className="p-2">
+                                    <p className="text-white font-medium truncate">{clip.title}</p>
+                                    <p className="text-gray-400 text-sm">
+                                        {clip.broadcaster_name} · clipped by {clip.creator_name} · {Math.round(clip.duration)}s
+                                    </p>
+                                    <p className="text-gray-500 text-xs">Submitted by {clip.submitted_by}</p>
+                                </div>
+                            </a>
+                        ))}
+                    </div>
+                </div>
+            ))}
+        </div>
+    );
+};
+
+export default ClipsGallery;
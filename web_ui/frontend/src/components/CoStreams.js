@@ -0,0 +1,107 @@
+import React, { useState, useEffect, useCallback } from 'react';
+
+const REFRESH_INTERVAL_MS = 60000;
+const MAX_STREAMS = 4;
+
+const CoStreams = ({ onStreamsChange }) => {
+    const [streams, setStreams] = useState([]);
+    const [newStream, setNewStream] = useState('');
+    const [error, setError] = useState(null);
+
+    const fetchStreams = useCallback(() => {
+        fetch('/api/costreams')
+            .then((response) => response.json())
+            .then((data) => {
+                setStreams(data.streams || []);
+                setError(data.error || null);
+            })
+            .catch((err) => console.error('Failed to fetch co-streams:', err));
+    }, []);
+
+    useEffect(() => {
+        fetchStreams();
+        const interval = setInterval(fetchStreams, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, [fetchStreams]);
+
+    const saveStreams = async (logins) => {
+        setError(null);
+        try {
+            const response = await fetch('/api/costreams', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ streams: logins }),
+            });
+            if (!response.ok) {
+                throw new Error("That doesn't look like a Twitch channel");
+            }
+            const result = await response.json();
+            if (onStreamsChange) {
+                onStreamsChange(result.streams);
+            }
+            setNewStream('');
+            fetchStreams();
+        } catch (err) {
+            setError(err.message);
+        }
+    };
+
+    const addStream = (e) => {
+        e.preventDefault();
+        if (newStream.trim()) {
+            saveStreams([...streams.map((stream) => stream.login), newStream.trim()]);
+        }
+    };
+
+    const removeStream = (login) => {
+        saveStreams(streams.map((stream) => stream.login).filter((l) => l !== login));
+    };
+
+    const liveCount = streams.filter((stream) => stream.live).length;
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">
+                Co-streams {streams.length > 0 && <span className="text-sm text-gray-400">({liveCount} of {streams.length} live)</span>}
+            </h2>
+            {error && <p className="text-yellow-400 mb-2">{error}</p>}
+            {streams.length === 0 && <p className="text-gray-400 mb-2">No co-streamers yet. Chat can see who's live with !team.</p>}
+            <ul className="text-gray-300 mb-4">
+                {streams.map((stream) => (
+                    <li key={stream.login} className="flex items-center justify-between py-1">
+                        <span>
+                            <span className={`inline-block w-2 h-2 rounded-full mr-2 ${stream.live ? 'bg-red-500' : 'bg-gray-500'}`} />
+                            <a href={`https://twitch.tv/${stream.login}`} target="_blank" rel="noopener noreferrer" className="hover:underline">
+                                {stream.display_name}
+                            </a>
+                            {stream.live && (
+                                <span className="text-gray-400 text-sm ml-2">
+                                    {stream.game || 'Live'}{stream.viewers !== null ? ` · ${stream.viewers} watching` : ''}
+                                </span>
+                            )}
+                        </span>
+                        <button onClick={() => removeStream(stream.login)} className="text-red-400 hover:text-red-300 text-sm">
+                            Remove
+                        </button>
+                    </li>
+                ))}
+            </ul>
+            {streams.length < MAX_STREAMS && (
+                <form onSubmit={addStream} className="flex gap-2">
+                    <input
+                        type="text"
+                        value={newStream}
+                        onChange={(e) => setNewStream(e.target.value)}
+                        placeholder="Channel name or URL"
+                        className="flex-grow bg-gray-700 text-white px-3 py-2 rounded"
+                    />
+                    <button type="submit" className="bg-purple-600 hover:bg-purple-700 text-white px-4 py-2 rounded">
+                        Add
+                    </button>
+                </form>
+            )}
+        </div>
+    );
+};
+
+export default CoStreams;
@@ -0,0 +1,105 @@
+import React, { useState, useEffect } from 'react';
+
+const ROLES = ['Viewer', 'Subscriber', 'VIP', 'Moderator', 'Broadcaster'];
+
+const CommandSettings = () => {
+    const [commands, setCommands] = useState([]);
+    const [filter, setFilter] = useState('');
+    const [status, setStatus] = useState(null);
+
+    const fetchCommands = () => {
+        fetch('/api/commands/config')
+            .then((response) => response.json())
+            .then((data) => setCommands(data.commands))
+            .catch((err) => console.error('Failed to fetch command settings:', err));
+    };
+
+    useEffect(fetchCommands, []);
+
+    const save = async (command, changes) => {
+        const updated = { ...command, ...changes };
+        setCommands(commands.map((c) => (c.name === command.name ? updated : c)));
+        try {
+            const response = await fetch('/api/commands/config', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({
+                    name: updated.name,
+                    enabled: updated.enabled,
+                    cooldown_secs: updated.cooldown_secs,
+                    required_role: updated.required_role,
+                }),
+            });
+            setStatus(await response.json());
+        } catch (err) {
+            console.error('Failed to save command settings:', err);
+            setStatus({ success: false, message: err.message });
+            fetchCommands();
+        }
+    };
+
+    const shown = commands.filter((command) => command.name.includes(filter.toLowerCase()));
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <div className="flex items-center justify-between mb-4">
+                <h2 className="text-2xl font-bold text-white">Command Settings</h2>
+                <input
+                    value={filter}
+                    onChange={(e) => setFilter(e.target.value)}
+                    placeholder="Filter"
+                    className="bg-gray-700 text-white rounded p-2"
+                />
+            </div>
+            <p className="text-gray-400 text-sm mb-4">Blank fields use the command's default. Mods and the broadcaster skip cooldowns.</p>
+            <div className="max-h-96 overflow-y-auto">
+                <table className="w-full text-left text-gray-300">
+                    <thead>
+                        <tr className="text-gray-400">
+                            <th className="pb-2">Command</th>
+                            <th className="pb-2">On</th>
+                            <th className="pb-2">Who can use it</th>
+                            <th className="pb-2">Cooldown (s)</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {shown.map((command) => (
+                            <tr key={command.name} className={command.enabled ? '' : 'opacity-50'}>
+                                <td className="py-1" title={command.description}>
+                                    {command.name}
+                                    {command.plugin && <span className="text-gray-500 text-xs ml-1">({command.plugin})</span>}
+                                </td>
+                                <td>
+                                    <input type="checkbox" checked={command.enabled} onChange={(e) => save(command, { enabled: e.target.checked })} />
+                                </td>
+                                <td>
+                                    <select
+                                        value={command.required_role ?? ''}
+                                        onChange={(e) => save(command, { required_role: e.target.value || null })}
+                                        className="bg-gray-700 text-white rounded p-1"
+                                    >
+                                        <option value="">Default ({command.default_role})</option>
+                                        {ROLES.map((role) => <option key={role} value={role}>{role}</option>)}
+                                    </select>
+                                </td>
+                                <td>
+                                    <input
+                                        type="number"
+                                        min="0"
+                                        value={command.cooldown_secs ?? ''}
+                                        placeholder={String(command.default_cooldown_secs)}
+                                        onChange={(e) => save(command, { cooldown_secs: e.target.value === '' ? null : Number(e.target.value) })}
+                                        className="bg-gray-700 text-white rounded p-1 w-24"
+                                    />
+                                </td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            </div>
+            {status && !status.success && <p className="mt-2 text-red-500">Failed: {status.message}</p>}
+        </div>
+    );
+};
+
+export default CommandSettings;
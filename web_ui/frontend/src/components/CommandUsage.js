@@ -0,0 +1,154 @@
+import React, { useState, useEffect } from 'react';
+
+const RANGES = [1, 7, 30];
+
+const CommandUsage = () => {
+    const [days, setDays] = useState(7);
+    const [report, setReport] = useState(null);
+    const [latency, setLatency] = useState(null);
+
+    const fetchReport = () => {
+        fetch(`/api/commands/usage?days=${days}`)
+            .then((response) => response.json())
+            .then(setReport)
+            .catch((err) => console.error('Failed to fetch command usage:', err));
+        // Latency is kept in memory since the bot started, so it ignores the range
+        fetch('/api/command-metrics')
+            .then((response) => response.json())
+            .then(setLatency)
+            .catch((err) => console.error('Failed to fetch command latency:', err));
+    };
+
+    useEffect(fetchReport, [days]);
+
+    const liftSoftBan = async (userId) => {
+        try {
+            await fetch('/api/commands/unban', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ user_id: userId }),
+            });
+            fetchReport();
+        } catch (err) {
+            console.error('Failed to lift soft-ban:', err);
+        }
+    };
+
+    const ms = (value) => (value === null || value === undefined ? '-' : `${value}ms`);
+
+    const failureRate = (stats) => (stats.uses > 0 ? Math.round(((stats.errors + stats.denied) * 100) / stats.uses) : 0);
+
+    return (
+        <div className="p-4">
+            <div className="flex items-center justify-between mb-4">
+                <h2 className="text-2xl font-bold">Command Usage</h2>
+                <div className="flex gap-2">
+                    {RANGES.map((range) => (
+                        <button
+                            key={range}
+                            onClick={() => setDays(range)}
+                            className={`py-1 px-3 rounded ${days === range ? 'bg-purple-600 text-white' : 'bg-gray-700 text-gray-300'}`}
+                        >
+                            {range === 1 ? '24h' : `${range} days`}
+                        </button>
+                    ))}
+                </div>
+            </div>
+            {report && (
+                <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+                    <div className="md:col-span-2 bg-gray-800 p-4 rounded-lg overflow-x-auto">
+                        <table className="w-full text-left text-gray-300">
+                            <thead>
+                                <tr className="text-gray-400">
+                                    <th className="pb-2">Command</th>
+                                    <th className="pb-2">Uses</th>
+                                    <th className="pb-2">Users</th>
+                                    <th className="pb-2">Errors</th>
+                                    <th className="pb-2">Denied</th>
+                                    <th className="pb-2">Failure rate</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {report.commands.map((stats) => (
+                                    <tr key={stats.command} className="border-t border-gray-700">
+                                        <td className="py-1 font-mono">{stats.command}</td>
+                                        <td className="py-1">{stats.uses}</td>
+                                        <td className="py-1">{stats.unique_users}</td>
+                                        <td className="py-1">{stats.errors}</td>
+                                        <td className="py-1">{stats.denied}</td>
+                                        <td className={`py-1 ${failureRate(stats) >= 50 ? 'text-red-400' : ''}`}>{failureRate(stats)}%</td>
+                                    </tr>
+                                ))}
+                            </tbody>
+                        </table>
+                        {report.commands.length === 0 && <p className="text-gray-400 mt-2">No commands used in this period.</p>}
+                    </div>
+                    <div className="space-y-4">
+                        <div className="bg-gray-800 p-4 rounded-lg">
+                            <h3 className="text-lg font-bold mb-2">Top users</h3>
+                            <ol className="text-gray-300">
+                                {report.top_users.map((user) => (
+                                    <li key={user.user_id} className="flex justify-between">
+                                        <span>{user.user_name}</span>
+                                        <span>{user.value}</span>
+                                    </li>
+                                ))}
+                            </ol>
+                        </div>
+                        <div className="bg-gray-800 p-4 rounded-lg">
+                            <h3 className="text-lg font-bold mb-2">Soft-banned</h3>
+                            {report.soft_bans.length === 0 && <p className="text-gray-400">Nobody right now.</p>}
+                            {report.soft_bans.map((ban) => (
+                                <div key={ban.user_id} className="flex items-center justify-between text-gray-300">
+                                    <span>{ban.user_name} ({Math.ceil(ban.remaining_secs / 60)}m left)</span>
+                                    <button onClick={() => liftSoftBan(ban.user_id)} className="text-purple-400 hover:text-purple-300">
+                                        Lift
+                                    </button>
+                                </div>
+                            ))}
+                        </div>
+                    </div>
+                </div>
+            )}
+            {latency && (
+                <div className="bg-gray-800 p-4 rounded-lg mt-4 overflow-x-auto">
+                    <h3 className="text-lg font-bold mb-1">Latency since startup</h3>
+                    <p className="text-gray-400 text-sm mb-2">
+                        Chat messages: {latency.messages.count} handled, p95 {ms(latency.messages.p95_ms)}, max {ms(latency.messages.max_ms)}, {latency.messages.slow} slow
+                    </p>
+                    <table className="w-full text-left text-gray-300">
+                        <thead>
+                            <tr className="text-gray-400">
+                                <th className="pb-2">Command</th>
+                                <th className="pb-2">Runs</th>
+                                <th className="pb-2">Avg</th>
+                                <th className="pb-2">p50</th>
+                                <th className="pb-2">p95</th>
+                                <th className="pb-2">p99</th>
+                                <th className="pb-2">Max</th>
+                                <th className="pb-2">Slow</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {latency.commands.map((stats) => (
+                                <tr key={stats.name} className="border-t border-gray-700">
+                                    <td className="py-1 font-mono">{stats.name}</td>
+                                    <td className="py-1">{stats.count}</td>
+                                    <td className="py-1">{ms(stats.avg_ms)}</td>
+                                    <td className="py-1">{ms(stats.p50_ms)}</td>
+                                    <td className="py-1">{ms(stats.p95_ms)}</td>
+                                    <td className="py-1">{ms(stats.p99_ms)}</td>
+                                    <td className="py-1">{ms(stats.max_ms)}</td>
+                                    <td className={`py-1 ${stats.slow > 0 ? 'text-red-400' : ''}`}>{stats.slow}</td>
+                                </tr>
+                            ))}
+                        </tbody>
+                    </table>
+                    {latency.commands.length === 0 && <p className="text-gray-400 mt-2">No commands run yet.</p>}
+                </div>
+            )}
+        </div>
+    );
+};
+
+export default CommandUsage;
@@ -0,0 +1,62 @@
+import React, { useState, useEffect } from 'react';
+
+const REFRESH_INTERVAL_MS = 30000;
+
+const formatDuration = (seconds) => {
+    const hours = Math.floor(seconds / 3600);
+    const minutes = Math.floor((seconds % 3600) / 60);
+    return `${hours}h ${minutes}m`;
+};
+
+const ConnectionHealth = () => {
+    const [connections, setConnections] = useState([]);
+
+    useEffect(() => {
+        const fetchConnections = () => {
+            fetch('/api/connections')
+                .then((response) => response.json())
+                .then(setConnections)
+                .catch((err) => console.error('Failed to fetch connection stats:', err));
+        };
+
+        fetchConnections();
+        const interval = setInterval(fetchConnections, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, []);
+
+    if (connections.length === 0) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Connection Health</h2>
+            <table className="w-full text-left text-gray-300">
+                <thead>
+                    <tr className="text-gray-400">
+                        <th className="pb-2">Connection</th>
+                        <th className="pb-2">Status</th>
+                        <th className="pb-2">Availability</th>
+                        <th className="pb-2">Downtime</th>
+                        <th className="pb-2">Drops (last hour)</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {connections.map((connection) => (
+                        <tr key={connection.name}>
+                            <td className="py-1">{connection.name}</td>
+                            <td className={connection.connected ? 'text-green-500' : 'text-red-500'}>
+                                {connection.connected ? 'Connected' : 'Disconnected'}
+                            </td>
+                            <td>{connection.availability.toFixed(1)}%</td>
+                            <td>{formatDuration(connection.downtime_secs)}</td>
+                            <td>{connection.disconnects} ({connection.disconnects_last_hour})</td>
+                        </tr>
+                    ))}
+                </tbody>
+            </table>
+        </div>
+    );
+};
+
+export default ConnectionHealth;
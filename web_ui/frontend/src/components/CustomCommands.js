@@ -0,0 +1,143 @@
+import React, { useState, useEffect, useCallback } from 'react';
+
+const EMPTY_COMMAND = { name: '', response: '' };
+
+const CustomCommands = () => {
+    const [commands, setCommands] = useState([]);
+    const [draft, setDraft] = useState(EMPTY_COMMAND);
+    // Name of the command being edited, null when adding a new one
+    const [editing, setEditing] = useState(null);
+    const [error, setError] = useState(null);
+
+    const fetchCommands = useCallback(async () => {
+        try {
+            const response = await fetch('/api/custom-commands');
+            if (!response.ok) {
+                throw new Error('Failed to fetch commands');
+            }
+            const data = await response.json();
+            setCommands(data.commands);
+            setError(null);
+        } catch (err) {
+            console.error('Failed to fetch custom commands:', err);
+            setError(err.message);
+        }
+    }, []);
+
+    useEffect(() => {
+        fetchCommands();
+    }, [fetchCommands]);
+
+    const post = async (path, body) => {
+        try {
+            const response = await fetch(path, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body),
+            });
+            if (!response.ok) {
+                throw new Error('Request failed');
+            }
+            const data = await response.json();
+            if (!data.success) {
+                setError(data.message || 'Request failed');
+                return false;
+            }
+            setError(null);
+            fetchCommands();
+            return true;
+        } catch (err) {
+            console.error('Failed to update custom commands:', err);
+            setError(err.message);
+            return false;
+        }
+    };
+
+    const handleSubmit = async (e) => {
+        e.preventDefault();
+        if (!draft.name || !draft.response) return;
+        const path = editing ? '/api/custom-commands/edit' : '/api/custom-commands';
+        if (await post(path, draft)) {
+            setDraft(EMPTY_COMMAND);
+            setEditing(null);
+        }
+    };
+
+    const startEditing = (command) => {
+        setDraft({ name: command.name, response: command.response });
+        setEditing(command.name);
+    };
+
+    const cancelEditing = () => {
+        setDraft(EMPTY_COMMAND);
+        setEditing(null);
+    };
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Commands</h2>
+            <p className="text-gray-400 text-sm mb-4">
+                Responses can use $(user), $(touser), $(args), $(count), $(channel), $(uptime), $(game) and $(title).
+                Mods can also manage these from chat with !addcom, !editcom and !delcom.
+            </p>
+            {error && <p className="text-red-500 mb-4">{error}</p>}
+            <form onSubmit={handleSubmit} className="grid grid-cols-4 gap-4 mb-6">
+                <input
+                    type="text"
+                    value={draft.name}
+                    onChange={(e) => setDraft({ ...draft, name: e.target.value })}
+                    placeholder="!name"
+                    disabled={editing !== null}
+                    className="bg-gray-700 text-white p-2 rounded"
+                />
+                <input
+                    type="text"
+                    value={draft.response}
+                    onChange={(e) => setDraft({ ...draft, response: e.target.value })}
+                    placeholder="Response"
+                    maxLength={400}
+                    className="bg-gray-700 text-white p-2 rounded col-span-3"
+                />
+                <button type="submit" className="bg-purple-600 hover:bg-purple-700 text-white p-2 rounded col-span-3">
+                    {editing ? `Save ${editing}` : 'Add Command'}
+                </button>
+                {editing && (
+                    <button type="button" onClick={cancelEditing} className="bg-gray-600 hover:bg-gray-500 text-white p-2 rounded">
+                        Cancel
+                    </button>
+                )}
+            </form>
+            <table className="w-full text-left text-gray-300">
+                <thead>
+                    <tr>
+                        <th className="p-2">Command</th>
+                        <th className="p-2">Response</th>
+                        <th className="p-2">Uses</th>
+                        <th className="p-2">Added by</th>
+                        <th className="p-2"></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {commands.map((command) => (
+                        <tr key={command.name} className="border-t border-gray-700">
+                            <td className="p-2 font-mono">{command.name}</td>
+                            <td className="p-2">{command.response}</td>
+                            <td className="p-2">{command.uses}</td>
+                            <td className="p-2">{command.created_by}</td>
+                            <td className="p-2 space-x-2">
+                                <button onClick={() => startEditing(command)} className="text-blue-400 hover:text-blue-300">
+                                    Edit
+                                </button>
+                                <button onClick={() => post('/api/custom-commands/remove', { name: command.name })} className="text-red-400 hover:text-red-300">
+                                    Delete
+                                </button>
+                            </td>
+                        </tr>
+                    ))}
+                </tbody>
+            </table>
+        </div>
+    );
+};
+
+export default CustomCommands;
@@ -4,6 +4,23 @@ import VRChatWorldStatus from './VRChatWorldStatus';
 import useWebSocket from './useWebSocket';
 import Chat from './Chat';
 import BotStatus from './BotStatus';
+import RedeemTester from './RedeemTester';
+import RedeemChains from './RedeemChains';
+import RedeemAnnouncements from './RedeemAnnouncements';
+import RedeemQueue from './RedeemQueue';
+import RewardPricing from './RewardPricing';
+import FollowerGrowth from './FollowerGrowth';
+import StreamSegments from './StreamSegments';
+import ConnectionHealth from './ConnectionHealth';
+import QuickPoll from './QuickPoll';
+import BitsVote from './BitsVote';
+import Charity from './Charity';
+import Soundboard from './Soundboard';
+import CommandSettings from './CommandSettings';
+import ShortLinks from './ShortLinks';
+import CoStreams from './CoStreams';
+import RaidTrain from './RaidTrain';
+import Premiere from './Premiere';
 
 const initialState = {
     botStatus: 'Unknown',
@@ -22,6 +39,7 @@ const initialState = {
     vrchatStatus: false,
     obsStatus: false,
     obsInstances: [],
+    notifications: [],
     twitchChannel: '',
     additionalStreams: [],
     additionalStreamToggles: [],
@@ -62,6 +80,8 @@ function reducer(state, action) {
             return { ...state, obsStatus: action.payload };
         case 'SET_OBS_INSTANCES':
             return { ...state, obsInstances: action.payload };
+        case 'SET_NOTIFICATIONS':
+            return { ...state, notifications: action.payload };
         case 'SET_INSTANCE_NAME':
             return { ...state, instanceName: action.payload };
         case 'SET_SCENE_NAME':
@@ -111,7 +131,8 @@ const Dashboard = ({ setTwitchMessages }) => {
                         instance_name,
                         scene_name,
                         source_name,
-                        enabled
+                        enabled,
+                        notifications
                     } = data.update_data;
 
                     dispatch({ type: 'SET_UPTIME', payload: uptime || '-' });
@@ -121,6 +142,9 @@ const Dashboard = ({ setTwitchMessages }) => {
                     dispatch({ type: 'SET_VRCHAT_STATUS', payload: vrchat_status });
                     dispatch({ type: 'SET_OBS_STATUS', payload: obs_status });
 
+                    if (notifications) {
+                        dispatch({ type: 'SET_NOTIFICATIONS', payload: notifications });
+                    }
                     if (recent_messages) {
                         dispatch({ type: 'SET_RECENT_MESSAGES', payload: recent_messages });
                     }
@@ -253,6 +277,67 @@ const Dashboard = ({ setTwitchMessages }) => {
                     vrchatStatus={state.vrchatStatus}
                 />
             </div>
+            {state.notifications.length > 0 && (
+                <div className="bg-gray-800 p-6 rounded-lg shadow-md md:col-span-3">
+                    <h2 className="text-2xl font-bold mb-4 text-white">Notifications</h2>
+                    <ul className="list-disc list-inside text-gray-300">
+                        {state.notifications.map((notification, index) => (
+                            <li key={index}>{notification}</li>
+                        ))}
+                    </ul>
+                </div>
+            )}
+            <div className="md:col-span-3">
+                <RedeemQueue />
+            </div>
+            <div className="md:col-span-3">
+                <CoStreams onStreamsChange={(streams) => dispatch({ type: 'SET_ADDITIONAL_STREAMS', payload: streams })} />
+            </div>
+            <div className="md:col-span-3">
+                <RaidTrain />
+            </div>
+            <div className="md:col-span-3">
+                <Premiere />
+            </div>
+            <div className="md:col-span-3">
+                <QuickPoll />
+            </div>
+            <div className="md:col-span-3">
+                <BitsVote />
+            </div>
+            <div className="md:col-span-3">
+                <Charity />
+            </div>
+            <div className="md:col-span-3">
+                <Soundboard />
+            </div>
+            <div className="md:col-span-3">
+                <CommandSettings />
+            </div>
+            <div className="md:col-span-3">
+                <ConnectionHealth />
+            </div>
+            <div className="md:col-span-3">
+                <FollowerGrowth />
+            </div>
+            <div className="md:col-span-3">
+                <StreamSegments />
+            </div>
+            <div className="md:col-span-3">
+                <ShortLinks />
+            </div>
+            <div className="md:col-span-3">
+                <RedeemTester />
+            </div>
+            <div className="md:col-span-3">
+                <RedeemChains />
+            </div>
+            <div className="md:col-span-3">
+                <RewardPricing />
+            </div>
+            <div className="md:col-span-3">
+                <RedeemAnnouncements />
+            </div>
             <div className="md:col-span-3">
                 <Chat
                     state={state}
@@ -0,0 +1,104 @@
+import React, { useState, useEffect } from 'react';
+
+const EventInspector = () => {
+    const [events, setEvents] = useState([]);
+    // Types seen so far, so filtering by one type doesn't hide the others from the picker
+    const [eventTypes, setEventTypes] = useState([]);
+    const [typeFilter, setTypeFilter] = useState('');
+    const [selected, setSelected] = useState(null);
+    const [trace, setTrace] = useState(null);
+    const [error, setError] = useState(null);
+
+    const fetchEvents = () => {
+        fetch(`/api/events?type=${encodeURIComponent(typeFilter)}`)
+            .then((response) => response.json())
+            .then((data) => {
+                setEvents(data.events);
+                setEventTypes((types) => [...new Set([...types, ...data.events.map((event) => event.event_type)])].sort());
+            })
+            .catch((err) => console.error('Failed to fetch stored events:', err));
+    };
+
+    useEffect(fetchEvents, [typeFilter]);
+
+    const replay = async (event) => {
+        setSelected(event);
+        setTrace(null);
+        setError(null);
+        try {
+            const response = await fetch('/api/events/replay', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ id: event.id }),
+            });
+            const result = await response.json();
+            if (result.success) {
+                setTrace(result.trace);
+            } else {
+                setError(result.message);
+            }
+        } catch (err) {
+            setError(err.message);
+        }
+    };
+
+    return (
+        <div className="p-4">
+            <h2 className="text-2xl font-bold mb-4">Event Inspector</h2>
+            <p className="text-gray-400 text-sm mb-4">
+                Replays a stored EventSub notification as a dry run: nothing is sent, toggled or saved.
+            </p>
+            <div className="flex gap-2 mb-4">
+                <select
+                    value={typeFilter}
+                    onChange={(e) => setTypeFilter(e.target.value)}
+                    className="bg-gray-700 text-white rounded p-2"
+                >
+                    <option value="">All events</option>
+                    {eventTypes.map((type) => (
+                        <option key={type} value={type}>{type}</option>
+                    ))}
+                </select>
+                <button onClick={fetchEvents} className="bg-gray-600 hover:bg-gray-500 text-white py-2 px-4 rounded">
+                    Refresh
+                </button>
+            </div>
+            <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+                <div className="bg-gray-800 p-4 rounded-lg overflow-y-auto max-h-screen">
+                    {events.length === 0 && <p className="text-gray-400">No stored events yet.</p>}
+                    {events.map((event) => (
+                        <button
+                            key={event.id}
+                            onClick={() => replay(event)}
+                            className={`block w-full text-left p-2 rounded mb-1 ${selected && selected.id === event.id ? 'bg-purple-700' : 'hover:bg-gray-700'}`}
+                        >
+                            <span className="font-mono text-sm">{event.event_type}</span>
+                            <span className="text-gray-400 text-sm ml-2">{new Date(event.received_at).toLocaleString()}</span>
+                        </button>
+                    ))}
+                </div>
+                <div className="bg-gray-800 p-4 rounded-lg">
+                    {!selected && <p className="text-gray-400">Pick an event to replay it.</p>}
+                    {error && <p className="text-red-500">{error}</p>}
+                    {trace && (
+                        <ol className="space-y-1 mb-4">
+                            {trace.map((step, i) => (
+                                <li key={i}>
+                                    <span className="inline-block w-24 text-purple-400 font-mono text-sm">{step.stage}</span>
+                                    <span className="text-gray-200">{step.detail}</span>
+                                </li>
+                            ))}
+                        </ol>
+                    )}
+                    {selected && (
+                        <pre className="bg-gray-900 text-gray-300 text-xs p-2 rounded overflow-x-auto">
+                            {JSON.stringify(JSON.parse(selected.message), null, 2)}
+                        </pre>
+                    )}
+                </div>
+            </div>
+        </div>
+    );
+};
+
+export default EventInspector;
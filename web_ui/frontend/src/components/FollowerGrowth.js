@@ -0,0 +1,86 @@
+import React, { useState, useEffect } from 'react';
+
+const CHART_HEIGHT = 160;
+const BAR_WIDTH = 24;
+const BAR_GAP = 8;
+
+const formatDate = (timestamp) => new Date(timestamp).toLocaleDateString();
+
+const FollowerGrowth = () => {
+    const [growth, setGrowth] = useState([]);
+    const [error, setError] = useState(null);
+
+    useEffect(() => {
+        const fetchGrowth = async () => {
+            try {
+                const response = await fetch('/api/followers/growth?sessions=30');
+                if (!response.ok) {
+                    throw new Error('Failed to fetch follower growth');
+                }
+                setGrowth(await response.json());
+                setError(null);
+            } catch (err) {
+                console.error('Failed to fetch follower growth:', err);
+                setError(err.message);
+            }
+        };
+        fetchGrowth();
+        const interval = setInterval(fetchGrowth, 300000);
+        return () => clearInterval(interval);
+    }, []);
+
+    // Bars grow up from the middle for gains and down for losses
+    const largest = Math.max(1, ...growth.map((session) => Math.abs(session.gained)));
+    const hasLosses = growth.some((session) => session.gained < 0);
+    const baseline = hasLosses ? CHART_HEIGHT / 2 : CHART_HEIGHT;
+    const scale = baseline / largest;
+    const width = Math.max(growth.length * (BAR_WIDTH + BAR_GAP), BAR_WIDTH);
+    const total = growth.reduce((sum, session) => sum + session.gained, 0);
+    const latest = growth.length > 0 ? growth[growth.length - 1] : null;
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold text-white mb-4">Follower Growth</h2>
+            {error && <p className="text-red-500 mb-4">{error}</p>}
+            {growth.length === 0 ? (
+                <p className="text-gray-400">No streams with follower snapshots yet. The count is recorded every hour and when a stream starts or ends.</p>
+            ) : (
+                <>
+                    <p className="text-gray-300 mb-4">
+                        {latest.followers_at_end} followers · {total >= 0 ? '+' : ''}{total} over the last {growth.length} streams
+                    </p>
+                    <div className="overflow-x-auto">
+                        <svg width={width} height={CHART_HEIGHT + 20}>
+                            <line x1={0} y1={baseline} x2={width} y2={baseline} stroke="#4b5563" />
+                            {growth.map((session, index) => {
+                                const height = Math.max(Math.abs(session.gained) * scale, 1);
+                                const x = index * (BAR_WIDTH + BAR_GAP);
+                                const y = session.gained >= 0 ? baseline - height : baseline;
+                                return (
+                                    <g key={session.stream_session_id}>
+                                        <rect
+                                            x={x}
+                                            y={y}
+                                            width={BAR_WIDTH}
+                                            height={height}
+                                            fill={session.gained >= 0 ? '#9333ea' : '#ef4444'}
+                                        >
+                                            <title>
+                                                {`${formatDate(session.started_at)}: ${session.gained >= 0 ? '+' : ''}${session.gained} (${session.followers_at_start} → ${session.followers_at_end})${session.ended_at ? '' : ', live now'}`}
+                                            </title>
+                                        </rect>
+                                        <text x={x + BAR_WIDTH / 2} y={CHART_HEIGHT + 14} textAnchor="middle" fontSize="10" fill="#9ca3af">
+                                            {session.gained}
+                                        </text>
+                                    </g>
+                                );
+                            })}
+                        </svg>
+                    </div>
+                </>
+            )}
+        </div>
+    );
+};
+
+export default FollowerGrowth;
@@ -0,0 +1,138 @@
+import React, { useState, useEffect } from 'react';
+
+const REFRESH_INTERVAL_MS = 5000;
+
+const formatTime = (time) => (time ? new Date(time).toLocaleString() : '-');
+
+const JobRow = ({ job, onSaved }) => {
+    const [settings, setSettings] = useState(job.settings);
+    const [message, setMessage] = useState(null);
+
+    const post = async (url, body) => {
+        try {
+            const response = await fetch(url, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body),
+            });
+            const result = await response.json();
+            setMessage(result.success ? null : result.message);
+            onSaved();
+        } catch (err) {
+            setMessage(err.message);
+        }
+    };
+
+    const setSchedule = (schedule) => setSettings({ ...settings, schedule });
+
+    return (
+        <tr className="border-t border-gray-700 align-top">
+            <td className="py-2 pr-4">
+                <div className="font-mono">{job.name}</div>
+                <div className="text-gray-400 text-sm">{job.description}</div>
+            </td>
+            <td className="py-2 pr-4 space-y-1">
+                <select
+                    value={settings.schedule.kind}
+                    onChange={(e) => setSchedule(e.target.value === 'every' ? { kind: 'every', secs: 3600 } : { kind: 'cron', expr: '0 * * * *' })}
+                    className="bg-gray-700 text-white rounded p-1"
+                >
+                    <option value="every">Every (seconds)</option>
+                    <option value="cron">Cron</option>
+                </select>
+                {settings.schedule.kind === 'every' ? (
+                    <input
+                        type="number"
+                        min="1"
+                        value={settings.schedule.secs}
+                        onChange={(e) => setSchedule({ kind: 'every', secs: parseInt(e.target.value, 10) || 0 })}
+                        className="block w-32 bg-gray-700 text-white rounded p-1"
+                    />
+                ) : (
+                    <input
+                        type="text"
+                        value={settings.schedule.expr}
+                        onChange={(e) => setSchedule({ kind: 'cron', expr: e.target.value })}
+                        className="block w-32 bg-gray-700 text-white rounded p-1 font-mono"
+                    />
+                )}
+                <label className="block text-sm text-gray-400">
+                    Jitter (s)
+                    <input
+                        type="number"
+                        min="0"
+                        value={settings.jitter_secs}
+                        onChange={(e) => setSettings({ ...settings, jitter_secs: parseInt(e.target.value, 10) || 0 })}
+                        className="ml-2 w-20 bg-gray-700 text-white rounded p-1"
+                    />
+                </label>
+            </td>
+            <td className="py-2 pr-4 text-sm">
+                <label className="block">
+                    <input type="checkbox" checked={settings.enabled} onChange={(e) => setSettings({ ...settings, enabled: e.target.checked })} /> Enabled
+                </label>
+                <label className="block">
+                    <input type="checkbox" checked={settings.catch_up} onChange={(e) => setSettings({ ...settings, catch_up: e.target.checked })} /> Catch up missed runs
+                </label>
+            </td>
+            <td className="py-2 pr-4 text-sm">
+                <div>Last: {formatTime(job.last_run)}</div>
+                <div>Next: {job.settings.enabled ? formatTime(job.next_run) : 'disabled'}</div>
+                {job.running && <div className="text-yellow-400">Running...</div>}
+                {job.last_error && <div className="text-red-500">{job.last_error}</div>}
+                {message && <div className="text-red-500">{message}</div>}
+            </td>
+            <td className="py-2 space-y-1">
+                <button className="block px-3 py-1 bg-purple-600 rounded hover:bg-purple-700" onClick={() => post('/api/jobs/update', { name: job.name, settings })}>
+                    Save
+                </button>
+                <button className="block px-3 py-1 bg-gray-600 rounded hover:bg-gray-700" disabled={job.running} onClick={() => post('/api/jobs/run', { name: job.name })}>
+                    Run now
+                </button>
+            </td>
+        </tr>
+    );
+};
+
+const Jobs = () => {
+    const [jobs, setJobs] = useState([]);
+
+    const fetchJobs = () => {
+        fetch('/api/jobs')
+            .then((response) => response.json())
+            .then((data) => setJobs(data.jobs))
+            .catch((err) => console.error('Failed to fetch jobs:', err));
+    };
+
+    useEffect(() => {
+        fetchJobs();
+        const interval = setInterval(fetchJobs, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, []);
+
+    return (
+        <div className="p-4">
+            <h2 className="text-2xl font-bold mb-4">Scheduled Jobs</h2>
+            <div className="bg-gray-800 p-4 rounded-lg overflow-x-auto">
+                <table className="w-full text-left text-gray-300">
+                    <thead>
+                        <tr className="text-gray-400">
+                            <th className="pb-2">Job</th>
+                            <th className="pb-2">Schedule</th>
+                            <th className="pb-2">Options</th>
+                            <th className="pb-2">Status</th>
+                            <th className="pb-2"></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {jobs.map((job) => (
+                            <JobRow key={job.name} job={job} onSaved={fetchJobs} />
+                        ))}
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    );
+};
+
+export default Jobs;
@@ -0,0 +1,193 @@
+import React, { useState, useEffect, useCallback } from 'react';
+
+const ACTIONS = ['respond', 'world_info', 'counter'];
+const ROLES = ['Viewer', 'Subscriber', 'VIP', 'Moderator', 'Broadcaster'];
+
+const EMPTY_TRIGGER = {
+    name: '',
+    pattern: '',
+    is_regex: false,
+    action: 'respond',
+    response: '',
+    cooldown_secs: 30,
+    required_role: 'Viewer',
+    enabled: true,
+    count: 0,
+};
+
+const KeywordTriggers = () => {
+    const [triggers, setTriggers] = useState([]);
+    const [draft, setDraft] = useState(EMPTY_TRIGGER);
+    const [error, setError] = useState(null);
+
+    const fetchTriggers = useCallback(async () => {
+        try {
+            const response = await fetch('/api/triggers');
+            if (!response.ok) {
+                throw new Error('Failed to fetch triggers');
+            }
+            setTriggers(await response.json());
+            setError(null);
+        } catch (err) {
+            console.error('Failed to fetch triggers:', err);
+            setError(err.message);
+        }
+    }, []);
+
+    useEffect(() => {
+        fetchTriggers();
+    }, [fetchTriggers]);
+
+    const saveTrigger = async (trigger) => {
+        try {
+            const response = await fetch('/api/triggers', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ ...trigger, response: trigger.response || null }),
+            });
+            if (!response.ok) {
+                throw new Error('Failed to save trigger');
+            }
+            fetchTriggers();
+            return true;
+        } catch (err) {
+            console.error('Failed to save trigger:', err);
+            setError(err.message);
+            return false;
+        }
+    };
+
+    const deleteTrigger = async (name) => {
+        try {
+            const response = await fetch(`/api/triggers/${encodeURIComponent(name)}`, { method: 'DELETE' });
+            if (!response.ok) {
+                throw new Error('Failed to delete trigger');
+            }
+            fetchTriggers();
+        } catch (err) {
+            console.error('Failed to delete trigger:', err);
+            setError(err.message);
+        }
+    };
+
+    const handleSubmit = async (e) => {
+        e.preventDefault();
+        if (!draft.name || !draft.pattern) return;
+        if (await saveTrigger({ ...draft, cooldown_secs: Number(draft.cooldown_secs) || 0 })) {
+            setDraft(EMPTY_TRIGGER);
+        }
+    };
+
+    const updateDraft = (field, value) => setDraft({ ...draft, [field]: value });
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Keyword Triggers</h2>
+            {error && <p className="text-red-500 mb-4">{error}</p>}
+            <form onSubmit={handleSubmit} className="grid grid-cols-2 gap-4 mb-6">
+                <input
+                    type="text"
+                    value={draft.name}
+                    onChange={(e) => updateDraft('name', e.target.value)}
+                    placeholder="Name"
+                    className="bg-gray-700 text-white p-2 rounded"
+                />
+                <input
+                    type="text"
+                    value={draft.pattern}
+                    onChange={(e) => updateDraft('pattern', e.target.value)}
+                    placeholder="Keyword or regex"
+                    className="bg-gray-700 text-white p-2 rounded"
+                />
+                <select
+                    value={draft.action}
+                    onChange={(e) => updateDraft('action', e.target.value)}
+                    className="bg-gray-700 text-white p-2 rounded"
+                >
+                    {ACTIONS.map((action) => (
+                        <option key={action} value={action}>{action}</option>
+                    ))}
+                </select>
+                <select
+                    value={draft.required_role}
+                    onChange={(e) => updateDraft('required_role', e.target.value)}
+                    className="bg-gray-700 text-white p-2 rounded"
+                >
+                    {ROLES.map((role) => (
+                        <option key={role} value={role}>{role}</option>
+                    ))}
+                </select>
+                <input
+                    type="text"
+                    value={draft.response}
+                    onChange={(e) => updateDraft('response', e.target.value)}
+                    placeholder="Response ({user}, {count}, {kv:ns:key})"
+                    className="bg-gray-700 text-white p-2 rounded col-span-2"
+                />
+                <input
+                    type="number"
+                    min="0"
+                    value={draft.cooldown_secs}
+                    onChange={(e) => updateDraft('cooldown_secs', e.target.value)}
+                    placeholder="Cooldown (seconds)"
+                    className="bg-gray-700 text-white p-2 rounded"
+                />
+                <label className="flex items-center text-gray-300">
+                    <input
+                        type="checkbox"
+                        checked={draft.is_regex}
+                        onChange={(e) => updateDraft('is_regex', e.target.checked)}
+                        className="mr-2"
+                    />
+                    Regex
+                </label>
+                <button type="submit" className="bg-purple-600 hover:bg-purple-700 text-white p-2 rounded col-span-2">
+                    Save Trigger
+                </button>
+            </form>
+            <table className="w-full text-left text-gray-300">
+                <thead>
+                    <tr>
+                        <th className="p-2">Name</th>
+                        <th className="p-2">Pattern</th>
+                        <th className="p-2">Action</th>
+                        <th className="p-2">Role</th>
+                        <th className="p-2">Cooldown</th>
+                        <th className="p-2">Count</th>
+                        <th className="p-2">Enabled</th>
+                        <th className="p-2"></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {triggers.map((trigger) => (
+                        <tr key={trigger.name} className="border-t border-gray-700">
+                            <td className="p-2">{trigger.name}</td>
+                            <td className="p-2 font-mono">{trigger.pattern}</td>
+                            <td className="p-2">{trigger.action}</td>
+                            <td className="p-2">{trigger.required_role}</td>
+                            <td className="p-2">{trigger.cooldown_secs}s</td>
+                            <td className="p-2">{trigger.action === 'counter' ? trigger.count : '-'}</td>
+                            <td className="p-2">
+                                <input
+                                    type="checkbox"
+                                    checked={trigger.enabled}
+                                    onChange={(e) => saveTrigger({ ...trigger, enabled: e.target.checked })}
+                                />
+                            </td>
+                            <td className="p-2 space-x-2">
+                                <button onClick={() => setDraft({ ...trigger, response: trigger.response || '' })} className="text-blue-400 hover:text-blue-300">
+                                    Edit
+                                </button>
+                                <button onClick={() => deleteTrigger(trigger.name)} className="text-red-400 hover:text-red-300">
+                                    Delete
+                                </button>
+                            </td>
+                        </tr>
+                    ))}
+                </tbody>
+            </table>
+        </div>
+    );
+};
+
+export default KeywordTriggers;
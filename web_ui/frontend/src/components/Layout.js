@@ -1,13 +1,30 @@
-import React, { useState } from 'react';
-import { Home, Settings, Layout as LayoutIcon, ChevronLeft, ChevronRight, Tv } from 'lucide-react';
+import React, { useState, useEffect } from 'react';
+import { Home, Settings, Layout as LayoutIcon, ChevronLeft, ChevronRight, Tv, Shield, Zap, Film, Trophy, PartyPopper, Radio, MessageSquare, Clock, Search, BarChart2, Terminal, Key, Smartphone, Volume2, Lightbulb, PlayCircle } from 'lucide-react';
 import Chatbox from './Chatbox';
 import OBSControl from './OBSControl';  // Import the new OBSControl component
+import { fetchTheme, onThemeChange } from '../theme';
 
 const Sidebar = ({ activeItem, setActiveItem, isCollapsed, toggleSidebar }) => {
     const menuItems = [
         { name: 'Dashboard', icon: Home },
         { name: 'OBS Overlay', icon: LayoutIcon },
         { name: 'OBS Control', icon: Tv },  // Add the new OBS Control menu item
+        { name: 'Moderation', icon: Shield },
+        { name: 'Triggers', icon: Zap },
+        { name: 'Clips', icon: Film },
+        { name: 'Leaderboard', icon: Trophy },
+        { name: 'Milestones', icon: PartyPopper },
+        { name: 'OSC', icon: Radio },
+        { name: 'Prompts', icon: MessageSquare },
+        { name: 'Jobs', icon: Clock },
+        { name: 'Events', icon: Search },
+        { name: 'Commands', icon: Terminal },
+        { name: 'Command Usage', icon: BarChart2 },
+        { name: 'API Tokens', icon: Key },
+        { name: 'TTS', icon: Volume2 },
+        { name: 'Suggestions', icon: Lightbulb },
+        { name: 'Watch Queue', icon: PlayCircle },
+        { name: 'Remote', icon: Smartphone },
         { name: 'Settings', icon: Settings },
     ];
 
@@ -40,6 +57,14 @@ const Sidebar = ({ activeItem, setActiveItem, isCollapsed, toggleSidebar }) => {
 const Layout = ({ children, activeView, setActiveView }) => {
     const [isCollapsed, setIsCollapsed] = useState(false);
     const [messages] = useState([]); // Remove setMessages if it's not being used
+    const [logoUrl, setLogoUrl] = useState(null);
+
+    useEffect(() => {
+        fetchTheme()
+            .then((theme) => setLogoUrl(theme.logo_url))
+            .catch(() => setLogoUrl(null));
+        return onThemeChange((theme) => setLogoUrl(theme.logo_url ? `${theme.logo_url}?t=${Date.now()}` : null));
+    }, []);
 
     const toggleSidebar = () => setIsCollapsed(!isCollapsed);
 
@@ -52,7 +77,8 @@ const Layout = ({ children, activeView, setActiveView }) => {
                 toggleSidebar={toggleSidebar}
             />
             <div className="flex-1 flex flex-col overflow-hidden">
-                <header className="bg-gray-800 shadow-md p-4">
+                <header className="bg-gray-800 shadow-md p-4 flex items-center gap-3">
+                    {logoUrl && <img src={logoUrl} alt="" className="h-8" />}
                     <h1 className="text-2xl font-bold text-white">MewBot Web UI</h1>
                 </header>
                 <div className="flex-1 flex overflow-hidden">
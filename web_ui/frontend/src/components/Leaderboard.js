@@ -0,0 +1,77 @@
+import React, { useState, useEffect } from 'react';
+
+const PERIODS = ['day', 'week', 'month'];
+
+const SECTIONS = [
+    { key: 'top_chatters', title: 'Top Chatters', unit: 'messages' },
+    { key: 'top_point_spenders', title: 'Top Point Spenders', unit: 'points' },
+    { key: 'top_redeemers', title: 'Top Redeemers', unit: 'redeems' },
+];
+
+const Leaderboard = () => {
+    const [period, setPeriod] = useState('day');
+    const [leaderboard, setLeaderboard] = useState(null);
+    const [error, setError] = useState(null);
+
+    useEffect(() => {
+        const fetchLeaderboard = async () => {
+            try {
+                const response = await fetch(`/api/leaderboard?period=${period}`);
+                if (!response.ok) {
+                    throw new Error('Failed to fetch leaderboard');
+                }
+                setLeaderboard(await response.json());
+                setError(null);
+            } catch (err) {
+                console.error('Failed to fetch leaderboard:', err);
+                setError(err.message);
+            }
+        };
+        fetchLeaderboard();
+    }, [period]);
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <div className="flex justify-between items-center mb-4">
+                <h2 className="text-2xl font-bold text-white">Leaderboard</h2>
+                <div className="flex gap-2">
+                    {PERIODS.map((p) => (
+                        <button
+                            key={p}
+                            onClick={() => setPeriod(p)}
+                            className={`px-3 py-1 rounded capitalize ${p === period ? 'bg-purple-600 text-white' : 'bg-gray-700 text-gray-300 hover:bg-gray-600'}`}
+                        >
+                            {p}
+                        </button>
+                    ))}
+                </div>
+            </div>
+            <p className="text-gray-400 text-sm mb-4">
+                Public page: <a href={`/leaderboard?period=${period}`} className="text-purple-400" target="_blank" rel="noopener noreferrer">/leaderboard?period={period}</a>
+                {' · '}
+                OBS credits: <a href={`/leaderboard/embed?period=${period}`} className="text-purple-400" target="_blank" rel="noopener noreferrer">/leaderboard/embed?period={period}</a>
+            </p>
+            {error && <p className="text-red-500 mb-4">{error}</p>}
+            {leaderboard && (
+                <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+                    {SECTIONS.map((section) => (
+                        <div key={section.key} className="bg-gray-700 rounded p-4">
+                            <h3 className="text-lg font-semibold text-purple-400 mb-2">{section.title}</h3>
+                            {leaderboard[section.key].length === 0 && <p className="text-gray-400">Nobody yet</p>}
+                            <ol className="space-y-1">
+                                {leaderboard[section.key].map((entry, index) => (
+                                    <li key={entry.user_id} className="flex justify-between text-white">
+                                        <span>{index + 1}. {entry.user_name}</span>
+                                        <span className="text-gray-400">{entry.value} {section.unit}</span>
+                                    </li>
+                                ))}
+                            </ol>
+                        </div>
+                    ))}
+                </div>
+            )}
+        </div>
+    );
+};
+
+export default Leaderboard;
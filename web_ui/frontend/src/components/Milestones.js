@@ -0,0 +1,79 @@
+import React, { useState, useEffect } from 'react';
+
+const LABELS = {
+    followers: 'Followers',
+    subs: 'Total Subs',
+    hours_streamed: 'Hours Streamed',
+};
+
+const Milestones = () => {
+    const [milestones, setMilestones] = useState(null);
+    const [error, setError] = useState(null);
+
+    useEffect(() => {
+        const fetchMilestones = async () => {
+            try {
+                const response = await fetch('/api/milestones');
+                if (!response.ok) {
+                    throw new Error('Failed to fetch milestones');
+                }
+                setMilestones(await response.json());
+                setError(null);
+            } catch (err) {
+                console.error('Failed to fetch milestones:', err);
+                setError(err.message);
+            }
+        };
+        fetchMilestones();
+        const interval = setInterval(fetchMilestones, 60000);
+        return () => clearInterval(interval);
+    }, []);
+
+    const percentTo = (item) => {
+        if (item.next === null) {
+            return 100;
+        }
+        const start = item.previous || 0;
+        return Math.min(100, Math.round(((item.current - start) / (item.next - start)) * 100));
+    };
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold text-white mb-4">Milestones</h2>
+            {error && <p className="text-red-500 mb-4">{error}</p>}
+            {milestones && !milestones.enabled && (
+                <p className="text-yellow-400 mb-4">Milestone celebrations are disabled in the config.</p>
+            )}
+            {milestones && (
+                <>
+                    <div className="grid grid-cols-1 md:grid-cols-3 gap-4 mb-6">
+                        {milestones.progress.map((item) => (
+                            <div key={item.kind} className="bg-gray-700 rounded p-4">
+                                <h3 className="text-lg font-semibold text-purple-400">{LABELS[item.kind] || item.kind}</h3>
+                                <p className="text-3xl font-bold text-white">{item.current}</p>
+                                <p className="text-gray-400 text-sm mb-2">
+                                    {item.next !== null ? `Next milestone: ${item.next}` : 'All milestones reached!'}
+                                </p>
+                                <div className="w-full bg-gray-600 rounded h-2">
+                                    <div className="bg-purple-500 h-2 rounded" style={{ width: `${percentTo(item)}%` }} />
+                                </div>
+                            </div>
+                        ))}
+                    </div>
+                    <h3 className="text-xl font-semibold text-white mb-2">Reached</h3>
+                    {milestones.reached.length === 0 && <p className="text-gray-400">No milestones reached yet</p>}
+                    <ul className="space-y-1">
+                        {milestones.reached.map((milestone) => (
+                            <li key={`${milestone.kind}-${milestone.threshold}`} className="flex justify-between text-white">
+                                <span>{milestone.threshold} {(LABELS[milestone.kind] || milestone.kind).toLowerCase()}</span>
+                                <span className="text-gray-400">{new Date(milestone.reached_at).toLocaleDateString()}</span>
+                            </li>
+                        ))}
+                    </ul>
+                </>
+            )}
+        </div>
+    );
+};
+
+export default Milestones;
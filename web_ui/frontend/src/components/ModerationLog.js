@@ -0,0 +1,112 @@
+import React, { useState, useEffect, useCallback } from 'react';
+
+const APPEAL_STATUSES = ['', 'pending', 'accepted', 'denied'];
+
+const ModerationLog = () => {
+    const [events, setEvents] = useState([]);
+    const [typeFilter, setTypeFilter] = useState('');
+    const [userFilter, setUserFilter] = useState('');
+    const [error, setError] = useState(null);
+
+    const fetchLog = useCallback(async () => {
+        try {
+            const params = new URLSearchParams();
+            if (typeFilter) params.append('type', typeFilter);
+            if (userFilter) params.append('user', userFilter);
+            const response = await fetch(`/api/moderation-log?${params.toString()}`);
+            if (!response.ok) {
+                throw new Error('Failed to fetch moderation log');
+            }
+            setEvents(await response.json());
+            setError(null);
+        } catch (err) {
+            console.error('Failed to fetch moderation log:', err);
+            setError(err.message);
+        }
+    }, [typeFilter, userFilter]);
+
+    useEffect(() => {
+        fetchLog();
+    }, [fetchLog]);
+
+    const updateAppeal = async (id, appealStatus) => {
+        try {
+            const response = await fetch(`/api/moderation-log/${id}/appeal`, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ appeal_status: appealStatus }),
+            });
+            if (!response.ok) {
+                throw new Error('Failed to update appeal status');
+            }
+            fetchLog();
+        } catch (err) {
+            console.error('Failed to update appeal status:', err);
+            setError(err.message);
+        }
+    };
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Moderation Log</h2>
+            {error && <p className="text-red-500 mb-4">{error}</p>}
+            <div className="flex gap-4 mb-4">
+                <select
+                    value={typeFilter}
+                    onChange={(e) => setTypeFilter(e.target.value)}
+                    className="bg-gray-700 text-white p-2 rounded"
+                >
+                    <option value="">All actions</option>
+                    <option value="ban">Bans</option>
+                    <option value="timeout">Timeouts</option>
+                    <option value="unban">Unbans</option>
+                </select>
+                <input
+                    type="text"
+                    value={userFilter}
+                    onChange={(e) => setUserFilter(e.target.value)}
+                    placeholder="User or moderator"
+                    className="bg-gray-700 text-white p-2 rounded flex-1"
+                />
+            </div>
+            <table className="w-full text-left text-gray-300">
+                <thead>
+                    <tr>
+                        <th className="p-2">Time</th>
+                        <th className="p-2">Action</th>
+                        <th className="p-2">User</th>
+                        <th className="p-2">Moderator</th>
+                        <th className="p-2">Reason</th>
+                        <th className="p-2">Appeal</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {events.map((event) => (
+                        <tr key={event.id} className="border-t border-gray-700">
+                            <td className="p-2">{new Date(event.timestamp).toLocaleString()}</td>
+                            <td className="p-2">{event.event_type}</td>
+                            <td className="p-2">{event.user_name}</td>
+                            <td className="p-2">{event.moderator_name}</td>
+                            <td className="p-2">{event.reason || '-'}</td>
+                            <td className="p-2">
+                                {event.event_type !== 'unban' && (
+                                    <select
+                                        value={event.appeal_status || ''}
+                                        onChange={(e) => updateAppeal(event.id, e.target.value)}
+                                        className="bg-gray-700 text-white p-1 rounded"
+                                    >
+                                        {APPEAL_STATUSES.map((status) => (
+                                            <option key={status} value={status}>{status || 'none'}</option>
+                                        ))}
+                                    </select>
+                                )}
+                            </td>
+                        </tr>
+                    ))}
+                </tbody>
+            </table>
+        </div>
+    );
+};
+
+export default ModerationLog;
@@ -3,9 +3,14 @@ import useWebSocket from './useWebSocket';
 
 const OBSControl = () => {
     const [obsInstances, setOBSInstances] = useState([]);
+    const [previews, setPreviews] = useState({});
     const [error, setError] = useState(null);
 
     const handleWebSocketMessage = useCallback((data) => {
+        if (data.module === 'obs' && data.action === 'preview') {
+            setPreviews(prev => ({ ...prev, [data.data.instance_name]: data.data }));
+            return;
+        }
         console.log("Full WebSocket message in OBSControl:", data);
         if (data.type === 'update' && data.update_data && data.update_data.obs_instances) {
             const processedInstances = data.update_data.obs_instances.map(instance => ({
@@ -79,6 +84,19 @@ const OBSControl = () => {
                 <div key={instance.id} className="bg-gray-800 p-6 rounded-lg shadow-md">
                     <h2 className="text-2xl font-bold mb-4 text-white">{instance.name}</h2>
 
+                    {previews[instance.name] && (
+                        <div className="mb-4">
+                            <img
+                                src={previews[instance.name].image}
+                                alt={`Preview of ${previews[instance.name].scene_name}`}
+                                className="w-full rounded"
+                            />
+                            <p className="mt-1 text-sm text-gray-400">
+                                Program: <span className="font-semibold">{previews[instance.name].scene_name}</span>
+                            </p>
+                        </div>
+                    )}
+
                     <div className="mb-4">
                         <h3 className="text-xl font-semibold mb-2 text-white">Scenes</h3>
                         <div className="flex items-center">
@@ -0,0 +1,244 @@
+import React, { useState, useEffect } from 'react';
+
+const TYPES = ['Boolean', 'Integer', 'Float', 'String'];
+
+const emptyConfig = {
+    uses_osc: true,
+    osc_endpoint: '/avatar/parameters/',
+    osc_type: 'Boolean',
+    osc_value: { Boolean: true },
+    default_value: { Boolean: false },
+    execution_duration: 300,
+    send_chat_message: false,
+};
+
+// OSCValue is serialized as {"Float": 0.5}; the form works with the bare value
+const valueOf = (value) => Object.values(value || {})[0];
+
+const toValue = (type, raw) => {
+    switch (type) {
+        case 'Boolean':
+            return { Boolean: raw === true || raw === 'true' };
+        case 'Integer':
+            return { Integer: parseInt(raw, 10) || 0 };
+        case 'Float':
+            return { Float: parseFloat(raw) || 0 };
+        default:
+            return { String: String(raw ?? '') };
+    }
+};
+
+const ValueInput = ({ type, value, onChange }) => {
+    if (type === 'Boolean') {
+        return (
+            <select
+                value={String(valueOf(value))}
+                onChange={(e) => onChange(toValue(type, e.target.value))}
+                className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+            >
+                <option value="true">true</option>
+                <option value="false">false</option>
+            </select>
+        );
+    }
+    return (
+        <input
+            type={type === 'String' ? 'text' : 'number'}
+            step={type === 'Float' ? '0.01' : '1'}
+            value={valueOf(value) ?? ''}
+            onChange={(e) => onChange(toValue(type, e.target.value))}
+            className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+        />
+    );
+};
+
+const OSCDesigner = () => {
+    const [configs, setConfigs] = useState({});
+    const [key, setKey] = useState('');
+    const [form, setForm] = useState(emptyConfig);
+    const [status, setStatus] = useState(null);
+
+    const fetchConfigs = () => {
+        fetch('/api/osc/configs')
+            .then((response) => response.json())
+            .then((data) => setConfigs(data.configs))
+            .catch((err) => console.error('Failed to fetch OSC configs:', err));
+    };
+
+    useEffect(fetchConfigs, []);
+
+    const post = async (url, body, successMessage) => {
+        try {
+            const response = await fetch(url, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body),
+            });
+            const result = await response.json();
+            setStatus(result.success ? { ok: true, message: successMessage } : { ok: false, message: result.message });
+            if (result.success) {
+                fetchConfigs();
+            }
+            return result.success;
+        } catch (err) {
+            console.error('OSC config request failed:', err);
+            setStatus({ ok: false, message: err.message });
+            return false;
+        }
+    };
+
+    const edit = (name) => {
+        setKey(name);
+        setForm({ ...emptyConfig, ...configs[name] });
+        setStatus(null);
+    };
+
+    const changeType = (type) => {
+        setForm({
+            ...form,
+            osc_type: type,
+            osc_value: toValue(type, valueOf(form.osc_value)),
+            default_value: toValue(type, valueOf(form.default_value)),
+        });
+    };
+
+    const rename = async (name) => {
+        const newKey = window.prompt(`Rename ${name} to`, name);
+        if (newKey && newKey !== name && await post('/api/osc/configs/rename', { key: name, new_key: newKey }, `Renamed to ${newKey}`)) {
+            if (key === name) {
+                setKey(newKey);
+            }
+        }
+    };
+
+    const duplicate = (name) => {
+        const newKey = window.prompt(`Copy ${name} as`, `${name} copy`);
+        if (newKey) {
+            post('/api/osc/configs/duplicate', { key: name, new_key: newKey }, `Copied to ${newKey}`);
+        }
+    };
+
+    const remove = (name) => {
+        if (window.confirm(`Delete ${name}?`)) {
+            post('/api/osc/configs/delete', { key: name }, `Deleted ${name}`);
+        }
+    };
+
+    return (
+        <div className="p-4">
+            <h2 className="text-2xl font-bold mb-4">OSC Parameters</h2>
+            <p className="text-gray-400 text-sm mb-4">
+                Keys are EventSub event types (e.g. <code>channel.follow</code>) or channel point reward titles.
+            </p>
+            <div className="grid grid-cols-1 lg:grid-cols-2 gap-6">
+                <div className="bg-gray-800 p-4 rounded-lg">
+                    <table className="w-full text-left">
+                        <thead>
+                            <tr className="text-gray-400">
+                                <th className="p-2">Name</th>
+                                <th className="p-2">Address</th>
+                                <th className="p-2">Type</th>
+                                <th className="p-2"></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {Object.keys(configs).sort().map((name) => (
+                                <tr key={name} className="border-t border-gray-700">
+                                    <td className="p-2">{name}</td>
+                                    <td className="p-2 font-mono text-sm">{configs[name].osc_endpoint}</td>
+                                    <td className="p-2">{configs[name].osc_type}</td>
+                                    <td className="p-2 whitespace-nowrap">
+                                        <button className="text-purple-400 mr-2" onClick={() => edit(name)}>Edit</button>
+                                        <button className="text-purple-400 mr-2" onClick={() => rename(name)}>Rename</button>
+                                        <button className="text-purple-400 mr-2" onClick={() => duplicate(name)}>Copy</button>
+                                        <button className="text-red-400" onClick={() => remove(name)}>Delete</button>
+                                    </td>
+                                </tr>
+                            ))}
+                        </tbody>
+                    </table>
+                </div>
+                <div className="bg-gray-800 p-4 rounded-lg space-y-3">
+                    <label className="block">
+                        <span className="text-gray-400">Name</span>
+                        <input
+                            type="text"
+                            value={key}
+                            onChange={(e) => setKey(e.target.value)}
+                            className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+                        />
+                    </label>
+                    <label className="block">
+                        <span className="text-gray-400">Address</span>
+                        <input
+                            type="text"
+                            value={form.osc_endpoint}
+                            onChange={(e) => setForm({ ...form, osc_endpoint: e.target.value })}
+                            className="mt-1 block w-full bg-gray-700 text-white rounded p-2 font-mono"
+                        />
+                    </label>
+                    <label className="block">
+                        <span className="text-gray-400">Type</span>
+                        <select
+                            value={form.osc_type}
+                            onChange={(e) => changeType(e.target.value)}
+                            className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+                        >
+                            {TYPES.map((type) => <option key={type} value={type}>{type}</option>)}
+                        </select>
+                    </label>
+                    <div className="grid grid-cols-2 gap-4">
+                        <label className="block">
+                            <span className="text-gray-400">Value</span>
+                            <ValueInput type={form.osc_type} value={form.osc_value} onChange={(value) => setForm({ ...form, osc_value: value })} />
+                        </label>
+                        <label className="block">
+                            <span className="text-gray-400">Reset to</span>
+                            <ValueInput type={form.osc_type} value={form.default_value} onChange={(value) => setForm({ ...form, default_value: value })} />
+                        </label>
+                    </div>
+                    <label className="block">
+                        <span className="text-gray-400">Reset after (frames at 60fps, empty to never reset)</span>
+                        <input
+                            type="number"
+                            value={form.execution_duration ?? ''}
+                            onChange={(e) => setForm({ ...form, execution_duration: e.target.value === '' ? null : parseInt(e.target.value, 10) })}
+                            className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+                        />
+                    </label>
+                    <label className="flex items-center gap-2">
+                        <input type="checkbox" checked={form.uses_osc} onChange={(e) => setForm({ ...form, uses_osc: e.target.checked })} />
+                        <span>Enabled</span>
+                    </label>
+                    <label className="flex items-center gap-2">
+                        <input type="checkbox" checked={form.send_chat_message} onChange={(e) => setForm({ ...form, send_chat_message: e.target.checked })} />
+                        <span>Send chat message</span>
+                    </label>
+                    {status && <p className={status.ok ? 'text-green-500' : 'text-red-500'}>{status.message}</p>}
+                    <div className="flex gap-2">
+                        <button
+                            className="px-4 py-2 bg-purple-600 rounded hover:bg-purple-700"
+                            onClick={() => post('/api/osc/configs', { key, config: form }, `Saved ${key}`)}
+                        >
+                            Save
+                        </button>
+                        <button
+                            className="px-4 py-2 bg-gray-600 rounded hover:bg-gray-700"
+                            onClick={() => post('/api/osc/test', { config: form }, 'Sent to avatar')}
+                        >
+                            Test
+                        </button>
+                        <button
+                            className="px-4 py-2 bg-gray-600 rounded hover:bg-gray-700"
+                            onClick={() => { setKey(''); setForm(emptyConfig); setStatus(null); }}
+                        >
+                            New
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    );
+};
+
+export default OSCDesigner;
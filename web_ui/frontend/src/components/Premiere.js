@@ -0,0 +1,86 @@
+import React, { useState, useEffect, useCallback } from 'react';
+
+const REFRESH_INTERVAL_MS = 15000;
+
+const formatRemaining = (secs) => `${Math.floor(secs / 60)}:${String(secs % 60).padStart(2, '0')}`;
+
+const Premiere = () => {
+    const [premiere, setPremiere] = useState(null);
+    const [error, setError] = useState(null);
+    const [now, setNow] = useState(Date.now());
+
+    const fetchPremiere = useCallback(() => {
+        fetch('/api/premiere')
+            .then((response) => response.json())
+            .then(setPremiere)
+            .catch((err) => console.error('Failed to fetch premiere:', err));
+    }, []);
+
+    useEffect(() => {
+        fetchPremiere();
+        const interval = setInterval(fetchPremiere, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, [fetchPremiere]);
+
+    // Ticks the on-screen countdown; the bot keeps its own time
+    useEffect(() => {
+        if (!premiere || !premiere.current) {
+            return undefined;
+        }
+        const interval = setInterval(() => setNow(Date.now()), 1000);
+        return () => clearInterval(interval);
+    }, [premiere]);
+
+    const act = (action) => {
+        fetch(`/api/premiere/${action}`, { method: 'POST' })
+            .then((response) => response.json())
+            .then((result) => {
+                setError(result.success ? null : result.message);
+                fetchPremiere();
+            })
+            .catch((err) => console.error(`Failed to ${action} premiere:`, err));
+    };
+
+    const remaining = premiere && premiere.current
+        ? Math.max(0, Math.ceil((new Date(premiere.current.starts_at).getTime() - now) / 1000))
+        : null;
+
+    // Picks up the finished premiere once the bot has switched over
+    useEffect(() => {
+        if (remaining !== 0) {
+            return undefined;
+        }
+        const timeout = setTimeout(fetchPremiere, 2000);
+        return () => clearTimeout(timeout);
+    }, [remaining, fetchPremiere]);
+
+    if (!premiere) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Premiere</h2>
+            {error && <p className="text-red-500 mb-2">{error}</p>}
+            <p className="text-gray-400 text-sm mb-4">
+                Counts down {formatRemaining(premiere.countdown_secs)} in chat, Discord and the VRChat chatbox, then
+                {premiere.content_scene ? ` switches OBS to "${premiere.content_scene}"` : ' leaves the OBS scene'}
+                {premiere.title ? ` and sets the title to "${premiere.title}".` : ' and keeps the title.'}
+            </p>
+            {premiere.current ? (
+                <div className="flex items-center gap-2">
+                    <p className="text-white text-3xl font-mono flex-grow">{formatRemaining(remaining)}</p>
+                    <button onClick={() => act('cancel')} className="bg-gray-600 hover:bg-gray-700 text-white px-4 py-2 rounded">
+                        Cancel
+                    </button>
+                </div>
+            ) : (
+                <button onClick={() => act('start')} className="bg-purple-600 hover:bg-purple-700 text-white px-4 py-2 rounded">
+                    Start premiere
+                </button>
+            )}
+        </div>
+    );
+};
+
+export default Premiere;
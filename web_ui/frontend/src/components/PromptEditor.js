@@ -0,0 +1,172 @@
+import React, { useState, useEffect } from 'react';
+
+const PromptEditor = () => {
+    const [prompts, setPrompts] = useState([]);
+    const [selected, setSelected] = useState(null);
+    const [text, setText] = useState('');
+    const [vars, setVars] = useState({ username: 'chatter', game: 'VRChat', world: 'The Black Cat', link: 'https://example.com' });
+    const [input, setInput] = useState('What is your favourite snack?');
+    const [preview, setPreview] = useState(null);
+    const [status, setStatus] = useState(null);
+
+    const fetchPrompts = (keepKey) => {
+        fetch('/api/prompts')
+            .then((response) => response.json())
+            .then((data) => {
+                setPrompts(data.prompts);
+                const current = data.prompts.find((p) => p.key === keepKey) || data.prompts[0];
+                if (current) {
+                    setSelected(current.key);
+                    setText(current.text);
+                }
+            })
+            .catch((err) => console.error('Failed to fetch prompts:', err));
+    };
+
+    useEffect(() => fetchPrompts(null), []);
+
+    const prompt = prompts.find((p) => p.key === selected);
+
+    const post = async (url, body) => {
+        const response = await fetch(url, {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(body),
+        });
+        return response.json();
+    };
+
+    const select = (key) => {
+        const next = prompts.find((p) => p.key === key);
+        setSelected(key);
+        setText(next.text);
+        setPreview(null);
+        setStatus(null);
+    };
+
+    const save = async (body, url, message) => {
+        try {
+            const result = await post(url, body);
+            setStatus(result.success ? { ok: true, message } : { ok: false, message: result.message });
+            if (result.success) {
+                fetchPrompts(selected);
+            }
+        } catch (err) {
+            setStatus({ ok: false, message: err.message });
+        }
+    };
+
+    const test = async (run) => {
+        try {
+            setPreview({ rendered: '', response: run ? 'Waiting for the AI...' : null });
+            const result = await post('/api/prompts/test', { key: selected, text, vars, input, run });
+            setPreview({ rendered: result.rendered, response: result.success ? result.response : result.message });
+        } catch (err) {
+            setPreview({ rendered: '', response: err.message });
+        }
+    };
+
+    if (!prompt) {
+        return <div className="p-4">Loading prompts...</div>;
+    }
+
+    return (
+        <div className="p-4">
+            <h2 className="text-2xl font-bold mb-4">AI Prompts</h2>
+            <div className="grid grid-cols-1 lg:grid-cols-3 gap-6">
+                <div className="bg-gray-800 p-4 rounded-lg">
+                    <ul className="space-y-2">
+                        {prompts.map((p) => (
+                            <li key={p.key}>
+                                <button
+                                    className={`text-left w-full p-2 rounded ${p.key === selected ? 'bg-purple-600' : 'hover:bg-gray-700'}`}
+                                    onClick={() => select(p.key)}
+                                >
+                                    <div className="font-mono text-sm">{p.key}</div>
+                                    <div className="text-gray-400 text-xs">{p.description}</div>
+                                </button>
+                            </li>
+                        ))}
+                    </ul>
+                </div>
+                <div className="bg-gray-800 p-4 rounded-lg space-y-3 lg:col-span-2">
+                    <p className="text-gray-400 text-sm">
+                        Placeholders: {prompt.variables.map((v) => <code key={v} className="mr-2">{`{${v}}`}</code>)}
+                    </p>
+                    <textarea
+                        value={text}
+                        onChange={(e) => setText(e.target.value)}
+                        rows={8}
+                        className="block w-full bg-gray-700 text-white rounded p-2 font-mono text-sm"
+                    />
+                    {status && <p className={status.ok ? 'text-green-500' : 'text-red-500'}>{status.message}</p>}
+                    <div className="flex gap-2">
+                        <button className="px-4 py-2 bg-purple-600 rounded hover:bg-purple-700" onClick={() => save({ key: selected, text }, '/api/prompts', 'Saved a new version')}>
+                            Save
+                        </button>
+                        <button className="px-4 py-2 bg-gray-600 rounded hover:bg-gray-700" onClick={() => setText(prompt.default_text)}>
+                            Load default
+                        </button>
+                    </div>
+                    <h3 className="text-lg font-semibold pt-2">Test</h3>
+                    <div className="grid grid-cols-2 gap-2">
+                        {prompt.variables.map((v) => (
+                            <label key={v} className="block">
+                                <span className="text-gray-400 text-sm">{v}</span>
+                                <input
+                                    type="text"
+                                    value={vars[v] || ''}
+                                    onChange={(e) => setVars({ ...vars, [v]: e.target.value })}
+                                    className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+                                />
+                            </label>
+                        ))}
+                        {selected.startsWith('redeem.') && (
+                            <label className="block col-span-2">
+                                <span className="text-gray-400 text-sm">Viewer's message</span>
+                                <input
+                                    type="text"
+                                    value={input}
+                                    onChange={(e) => setInput(e.target.value)}
+                                    className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+                                />
+                            </label>
+                        )}
+                    </div>
+                    <div className="flex gap-2">
+                        <button className="px-4 py-2 bg-gray-600 rounded hover:bg-gray-700" onClick={() => test(false)}>Preview</button>
+                        <button className="px-4 py-2 bg-gray-600 rounded hover:bg-gray-700" onClick={() => test(true)}>Run with AI</button>
+                    </div>
+                    {preview && (
+                        <div className="space-y-2">
+                            {preview.rendered && <pre className="whitespace-pre-wrap bg-gray-900 p-2 rounded text-sm">{preview.rendered}</pre>}
+                            {preview.response && <p className="bg-gray-900 p-2 rounded">{preview.response}</p>}
+                        </div>
+                    )}
+                    <h3 className="text-lg font-semibold pt-2">History</h3>
+                    {prompt.versions.length === 0 && <p className="text-gray-400">Using the built-in default</p>}
+                    <ul className="space-y-1">
+                        {prompt.versions.map((version, index) => (
+                            <li key={index} className="flex justify-between items-center text-sm">
+                                <span className="text-gray-300 truncate mr-4">
+                                    v{index + 1} · {new Date(version.saved_at).toLocaleString()}
+                                    {index === prompt.versions.length - 1 && ' (current)'}
+                                </span>
+                                <span className="whitespace-nowrap">
+                                    <button className="text-purple-400 mr-2" onClick={() => setText(version.text)}>Load</button>
+                                    {index !== prompt.versions.length - 1 && (
+                                        <button className="text-purple-400" onClick={() => save({ key: selected, version: index }, '/api/prompts/revert', `Reverted to v${index + 1}`)}>
+                                            Revert
+                                        </button>
+                                    )}
+                                </span>
+                            </li>
+                        ))}
+                    </ul>
+                </div>
+            </div>
+        </div>
+    );
+};
+
+export default PromptEditor;
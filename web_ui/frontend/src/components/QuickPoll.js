@@ -0,0 +1,60 @@
+import React, { useState, useEffect, useCallback } from 'react';
+import useWebSocket from './useWebSocket';
+
+const QuickPoll = () => {
+    const [poll, setPoll] = useState(null);
+
+    useEffect(() => {
+        fetch('/api/quickpoll')
+            .then((response) => response.json())
+            .then((data) => setPoll(data.poll))
+            .catch((err) => console.error('Failed to fetch quick poll:', err));
+    }, []);
+
+    const handleWebSocketMessage = useCallback((data) => {
+        if (data.module === 'poll') {
+            setPoll(data.data);
+        }
+    }, []);
+
+    const handleWebSocketError = useCallback((error) => {
+        console.error('WebSocket error:', error);
+    }, []);
+
+    useWebSocket(`ws://${window.location.hostname}:3333/ws`, handleWebSocketMessage, handleWebSocketError);
+
+    if (!poll) {
+        return null;
+    }
+
+    const top = Math.max(...poll.options.map((option) => option.votes));
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-1 text-white">{poll.closed ? 'Poll results' : 'Poll'}</h2>
+            <p className="text-gray-300 mb-4">{poll.question}</p>
+            <ul className="space-y-2">
+                {poll.options.map((option, index) => {
+                    const percent = poll.total_votes ? Math.round(option.votes * 100 / poll.total_votes) : 0;
+                    const winning = poll.closed && option.votes > 0 && option.votes === top;
+                    return (
+                        <li key={index}>
+                            <div className="flex justify-between text-gray-300">
+                                <span className={winning ? 'font-bold text-green-400' : ''}>{index + 1}) {option.name}</span>
+                                <span>{option.votes} ({percent}%)</span>
+                            </div>
+                            <div className="w-full bg-gray-700 rounded h-2">
+                                <div className="bg-purple-600 h-2 rounded" style={{ width: `${percent}%` }} />
+                            </div>
+                        </li>
+                    );
+                })}
+            </ul>
+            <p className="text-gray-400 text-sm mt-2">
+                {poll.total_votes} votes{poll.closed ? '' : ' - vote with !vote <number>'}
+            </p>
+        </div>
+    );
+};
+
+export default QuickPoll;
@@ -0,0 +1,67 @@
+import React, { useState, useEffect, useCallback } from 'react';
+
+const REFRESH_INTERVAL_MS = 15000;
+
+const RaidTrain = () => {
+    const [train, setTrain] = useState(null);
+
+    const fetchTrain = useCallback(() => {
+        fetch('/api/raid-train')
+            .then((response) => response.json())
+            .then(setTrain)
+            .catch((err) => console.error('Failed to fetch raid train:', err));
+    }, []);
+
+    useEffect(() => {
+        fetchTrain();
+        const interval = setInterval(fetchTrain, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, [fetchTrain]);
+
+    const decide = (action) => {
+        fetch(`/api/raid-train/${action}`, { method: 'POST' })
+            .then(() => fetchTrain())
+            .catch((err) => console.error(`Failed to ${action} raid:`, err));
+    };
+
+    if (!train || !train.enabled) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Raid Train</h2>
+            {train.error && <p className="text-yellow-400 mb-2">{train.error}</p>}
+            <ol className="text-gray-300 mb-4 list-decimal list-inside">
+                {train.channels.map((stream) => (
+                    <li key={stream.login} className={train.next && train.next.login === stream.login ? 'font-bold text-white' : ''}>
+                        <span className={`inline-block w-2 h-2 rounded-full mr-2 ${stream.live ? 'bg-red-500' : 'bg-gray-500'}`} />
+                        {stream.display_name}
+                        {stream.live && stream.game && <span className="text-gray-400 text-sm ml-2">{stream.game}</span>}
+                    </li>
+                ))}
+            </ol>
+            {train.pending ? (
+                <div className="flex items-center gap-2">
+                    <p className="text-gray-300 flex-grow">
+                        {train.pending.automatic ? 'Raiding' : 'Suggested raid:'} {train.pending.target.display_name}
+                        {train.pending.automatic && ` at ${new Date(train.pending.expires_at).toLocaleTimeString()}`}
+                    </p>
+                    <button onClick={() => decide('confirm')} className="bg-purple-600 hover:bg-purple-700 text-white px-4 py-2 rounded">
+                        Raid now
+                    </button>
+                    <button onClick={() => decide('cancel')} className="bg-gray-600 hover:bg-gray-700 text-white px-4 py-2 rounded">
+                        Cancel
+                    </button>
+                </div>
+            ) : (
+                <p className="text-gray-400 text-sm">
+                    {train.next ? `Next stop: ${train.next.display_name}. ` : 'Nobody else is live. '}
+                    Use !raidtrain in chat at the end of stream.
+                </p>
+            )}
+        </div>
+    );
+};
+
+export default RaidTrain;
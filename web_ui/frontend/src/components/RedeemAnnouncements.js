@@ -0,0 +1,115 @@
+import React, { useState, useEffect } from 'react';
+
+const OUTCOMES = [
+    { key: 'success', label: 'Success' },
+    { key: 'failure', label: 'Failure' },
+    { key: 'queued', label: 'Queued' },
+    { key: 'refunded', label: 'Refunded at end of stream' },
+];
+
+const RedeemAnnouncements = () => {
+    const [rewards, setRewards] = useState([]);
+    const [title, setTitle] = useState('');
+    const [templates, setTemplates] = useState({});
+    const [status, setStatus] = useState(null);
+
+    const fetchRewards = () => {
+        fetch('/api/redeems/announcements')
+            .then((response) => response.json())
+            .then((data) => setRewards(data.rewards))
+            .catch((err) => console.error('Failed to fetch redeem announcements:', err));
+    };
+
+    useEffect(fetchRewards, []);
+
+    const selectReward = (newTitle) => {
+        setTitle(newTitle);
+        setStatus(null);
+        const reward = rewards.find((r) => r.title === newTitle);
+        setTemplates(reward ? reward.announcements : {});
+    };
+
+    const saveTemplates = async () => {
+        try {
+            const response = await fetch('/api/redeems/announcements', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ title, announcements: templates }),
+            });
+            const data = await response.json();
+            setStatus(data);
+            if (data.success) {
+                fetchRewards();
+            }
+        } catch (err) {
+            console.error('Failed to save redeem announcements:', err);
+            setStatus({ success: false, message: err.message });
+        }
+    };
+
+    if (rewards.length === 0) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Redeem Announcements</h2>
+            <p className="text-gray-400 text-sm mb-4">
+                Chat messages after a redemption. Placeholders: {'{user}'}, {'{reward}'}, {'{input}'}, {'{message}'},
+                {' '}{'{number}'} and {'{position}'}. Tick "default" to use the config's template, or leave a box
+                empty for no message.
+            </p>
+            <select
+                value={title}
+                onChange={(e) => selectReward(e.target.value)}
+                className="bg-gray-700 text-white rounded p-2 mb-4"
+            >
+                <option value="">Select a reward</option>
+                {rewards.map((reward) => (
+                    <option key={reward.title} value={reward.title}>{reward.title}</option>
+                ))}
+            </select>
+            {title && (
+                <>
+                    {OUTCOMES.map(({ key, label }) => {
+                        const usesDefault = templates[key] === undefined || templates[key] === null;
+                        return (
+                            <div key={key} className="flex flex-wrap items-center gap-2 mb-2">
+                                <span className="text-white w-20">{label}</span>
+                                <label className="text-gray-300 text-sm flex items-center gap-1">
+                                    <input
+                                        type="checkbox"
+                                        checked={usesDefault}
+                                        onChange={(e) => setTemplates({ ...templates, [key]: e.target.checked ? null : '' })}
+                                    />
+                                    default
+                                </label>
+                                <input
+                                    type="text"
+                                    value={templates[key] ?? ''}
+                                    disabled={usesDefault}
+                                    onChange={(e) => setTemplates({ ...templates, [key]: e.target.value })}
+                                    placeholder={usesDefault ? 'From the config' : 'No message'}
+                                    className="bg-gray-700 text-white rounded p-2 flex-1"
+                                />
+                            </div>
+                        );
+                    })}
+                    <button
+                        onClick={saveTemplates}
+                        className="bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded mt-2"
+                    >
+                        Save announcements
+                    </button>
+                    {status && (
+                        <p className={`mt-2 ${status.success ? 'text-green-500' : 'text-red-500'}`}>
+                            {status.success ? 'Saved' : `Failed: ${status.message}`}
+                        </p>
+                    )}
+                </>
+            )}
+        </div>
+    );
+};
+
+export default RedeemAnnouncements;
@@ -0,0 +1,157 @@
+import React, { useState, useEffect } from 'react';
+
+const NEW_STEPS = {
+    osc: { type: 'osc', delay_ms: 0, config: '' },
+    chat: { type: 'chat', delay_ms: 0, message: '' },
+    obs: { type: 'obs', delay_ms: 0, instance_name: '', scene_name: '', source_name: '', filter_name: null, duration_secs: 10 },
+    sound: { type: 'sound', delay_ms: 0, name: '' },
+};
+
+const RedeemChains = () => {
+    const [chains, setChains] = useState([]);
+    const [title, setTitle] = useState('');
+    const [steps, setSteps] = useState([]);
+    const [status, setStatus] = useState(null);
+
+    const fetchChains = () => {
+        fetch('/api/redeems/chains')
+            .then((response) => response.json())
+            .then((data) => setChains(data.chains))
+            .catch((err) => console.error('Failed to fetch redeem chains:', err));
+    };
+
+    useEffect(fetchChains, []);
+
+    const selectReward = (newTitle) => {
+        setTitle(newTitle);
+        setStatus(null);
+        const chain = chains.find((c) => c.title === newTitle);
+        setSteps(chain ? chain.actions : []);
+    };
+
+    const updateStep = (index, field, value) => {
+        setSteps(steps.map((step, i) => (i === index ? { ...step, [field]: value } : step)));
+    };
+
+    const moveStep = (index, offset) => {
+        const target = index + offset;
+        if (target < 0 || target >= steps.length) {
+            return;
+        }
+        const reordered = [...steps];
+        [reordered[index], reordered[target]] = [reordered[target], reordered[index]];
+        setSteps(reordered);
+    };
+
+    const saveChain = async () => {
+        try {
+            const response = await fetch('/api/redeems/chain', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ title, actions: steps }),
+            });
+            const data = await response.json();
+            setStatus(data);
+            if (data.success) {
+                fetchChains();
+            }
+        } catch (err) {
+            console.error('Failed to save redeem chain:', err);
+            setStatus({ success: false, message: err.message });
+        }
+    };
+
+    const field = (index, name, placeholder, type = 'text') => (
+        <input
+            type={type}
+            value={steps[index][name] ?? ''}
+            onChange={(e) => {
+                const value = type === 'number' ? Number(e.target.value) : e.target.value;
+                updateStep(index, name, name === 'filter_name' && value === '' ? null : value);
+            }}
+            placeholder={placeholder}
+            className="bg-gray-700 text-white rounded p-2"
+        />
+    );
+
+    if (chains.length === 0) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Redeem Chains</h2>
+            <p className="text-gray-400 text-sm mb-4">
+                Steps run in order after their delay. If a step fails the rest are skipped.
+                Chat messages can use {'{user}'}, {'{input}'} and key-value placeholders like {'{kv+:ns:key}'}.
+            </p>
+            <select
+                value={title}
+                onChange={(e) => selectReward(e.target.value)}
+                className="bg-gray-700 text-white rounded p-2 mb-4"
+            >
+                <option value="">Select a reward</option>
+                {chains.map((chain) => (
+                    <option key={chain.title} value={chain.title}>
+                        {chain.title}{chain.actions.length > 0 ? ` (${chain.actions.length} steps)` : ''}
+                    </option>
+                ))}
+            </select>
+            {title && (
+                <>
+                    {steps.map((step, index) => (
+                        <div key={index} className="flex flex-wrap items-center gap-2 mb-2">
+                            <span className="text-gray-400 w-6">{index + 1}.</span>
+                            <span className="text-white uppercase text-sm w-12">{step.type}</span>
+                            {field(index, 'delay_ms', 'Delay (ms)', 'number')}
+                            {step.type === 'osc' && field(index, 'config', 'OSC config name')}
+                            {step.type === 'chat' && field(index, 'message', 'Message')}
+                            {step.type === 'sound' && field(index, 'name', 'Sound name')}
+                            {step.type === 'obs' && (
+                                <>
+                                    {field(index, 'instance_name', 'OBS instance')}
+                                    {field(index, 'scene_name', 'Scene')}
+                                    {field(index, 'source_name', 'Source')}
+                                    {field(index, 'filter_name', 'Filter (optional)')}
+                                    {field(index, 'duration_secs', 'Seconds', 'number')}
+                                </>
+                            )}
+                            <button onClick={() => moveStep(index, -1)} className="text-gray-300 hover:text-white px-2">↑</button>
+                            <button onClick={() => moveStep(index, 1)} className="text-gray-300 hover:text-white px-2">↓</button>
+                            <button
+                                onClick={() => setSteps(steps.filter((_, i) => i !== index))}
+                                className="text-red-400 hover:text-red-300 px-2"
+                            >
+                                Remove
+                            </button>
+                        </div>
+                    ))}
+                    <div className="flex gap-2 mt-4">
+                        {Object.keys(NEW_STEPS).map((type) => (
+                            <button
+                                key={type}
+                                onClick={() => setSteps([...steps, { ...NEW_STEPS[type] }])}
+                                className="bg-gray-600 hover:bg-gray-500 text-white py-2 px-4 rounded"
+                            >
+                                + {type.toUpperCase()}
+                            </button>
+                        ))}
+                        <button
+                            onClick={saveChain}
+                            className="bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded"
+                        >
+                            Save chain
+                        </button>
+                    </div>
+                    {status && (
+                        <p className={`mt-2 ${status.success ? 'text-green-500' : 'text-red-500'}`}>
+                            {status.success ? 'Saved' : `Failed: ${status.message}`}
+                        </p>
+                    )}
+                </>
+            )}
+        </div>
+    );
+};
+
+export default RedeemChains;
@@ -0,0 +1,117 @@
+import React, { useState, useEffect, useCallback } from 'react';
+import useWebSocket from './useWebSocket';
+
+const RedeemQueue = () => {
+    const [entries, setEntries] = useState([]);
+    const [dragged, setDragged] = useState(null);
+    const [message, setMessage] = useState(null);
+
+    const load = useCallback(async () => {
+        try {
+            const response = await fetch('/api/redeems/queue');
+            const data = await response.json();
+            setEntries(data.entries || []);
+        } catch (err) {
+            setMessage(err.message);
+        }
+    }, []);
+
+    useEffect(() => {
+        load();
+    }, [load]);
+
+    const handleWebSocketMessage = useCallback((data) => {
+        if (data.module === 'redeem_queue' && data.action === 'update') {
+            setEntries(data.data.entries || []);
+        }
+    }, []);
+
+    const handleWebSocketError = useCallback((error) => {
+        console.error('WebSocket error:', error);
+    }, []);
+
+    useWebSocket(`ws://${window.location.hostname}:3333/ws`, handleWebSocketMessage, handleWebSocketError);
+
+    const post = async (action, body) => {
+        setMessage(null);
+        try {
+            const response = await fetch(`/api/redeems/queue/${action}`, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body),
+            });
+            const result = await response.json();
+            if (!result.success) {
+                setMessage(result.message || 'That redemption is already gone');
+            }
+            await load();
+        } catch (err) {
+            setMessage(err.message);
+        }
+    };
+
+    const dropOn = (position) => {
+        if (dragged === null || dragged === position) {
+            setDragged(null);
+            return;
+        }
+        const id = entries[dragged].redemption.id;
+        // Show the new order right away; the server's update follows
+        const reordered = [...entries];
+        const [moved] = reordered.splice(dragged, 1);
+        reordered.splice(position, 0, moved);
+        setEntries(reordered);
+        setDragged(null);
+        post('move', { redemption_id: id, position });
+    };
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Redeem Queue</h2>
+            <p className="text-gray-400 text-sm mb-4">Drag to reorder. The top one shows in the chatbox ticker.</p>
+            {message && <p className="text-yellow-400 mb-2">{message}</p>}
+            {entries.length === 0 ? (
+                <p className="text-gray-400">Nothing waiting.</p>
+            ) : (
+                <ul className="space-y-2">
+                    {entries.map((entry, index) => (
+                        <li
+                            key={entry.redemption.id}
+                            draggable
+                            onDragStart={() => setDragged(index)}
+                            onDragOver={(e) => e.preventDefault()}
+                            onDrop={() => dropOn(index)}
+                            onDragEnd={() => setDragged(null)}
+                            className={`flex items-center gap-2 bg-gray-700 p-2 rounded cursor-move ${dragged === index ? 'opacity-50' : ''}`}
+                        >
+                            <span className="text-gray-400 w-10">#{entry.number}</span>
+                            <div className="flex-1 text-white">
+                                <span className="font-semibold">{entry.redemption.reward_title}</span>
+                                <span className="text-gray-400"> from {entry.redemption.user_name}</span>
+                                {entry.redemption.user_input && (
+                                    <p className="text-sm text-gray-300 break-words">{entry.redemption.user_input}</p>
+                                )}
+                            </div>
+                            <button
+                                onClick={() => post('bump', { redemption_id: entry.redemption.id })}
+                                disabled={index === 0}
+                                className="bg-purple-600 hover:bg-purple-700 disabled:opacity-50 text-white text-sm px-3 py-1 rounded"
+                            >
+                                Bump
+                            </button>
+                            <button
+                                onClick={() => post('skip', { redemption_id: entry.redemption.id })}
+                                disabled={index === entries.length - 1}
+                                className="bg-gray-600 hover:bg-gray-500 disabled:opacity-50 text-white text-sm px-3 py-1 rounded"
+                            >
+                                Skip
+                            </button>
+                        </li>
+                    ))}
+                </ul>
+            )}
+        </div>
+    );
+};
+
+export default RedeemQueue;
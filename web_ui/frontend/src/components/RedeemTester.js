@@ -0,0 +1,81 @@
+import React, { useState, useEffect } from 'react';
+
+const RedeemTester = () => {
+    const [rewards, setRewards] = useState([]);
+    const [title, setTitle] = useState('');
+    const [userInput, setUserInput] = useState('');
+    const [result, setResult] = useState(null);
+    const [isRunning, setIsRunning] = useState(false);
+
+    useEffect(() => {
+        fetch('/api/redeems/testable')
+            .then((response) => response.json())
+            .then((data) => {
+                setRewards(data.rewards);
+                if (data.rewards.length > 0) {
+                    setTitle(data.rewards[0]);
+                }
+            })
+            .catch((err) => console.error('Failed to fetch testable rewards:', err));
+    }, []);
+
+    const runTest = async () => {
+        setIsRunning(true);
+        try {
+            const response = await fetch('/api/redeems/test', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify({ title, user_input: userInput }),
+            });
+            setResult(await response.json());
+        } catch (err) {
+            console.error('Failed to test redeem:', err);
+            setResult({ success: false, message: err.message });
+        } finally {
+            setIsRunning(false);
+        }
+    };
+
+    if (rewards.length === 0) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Test Redeem</h2>
+            <p className="text-gray-400 text-sm mb-4">Runs the reward's actions without creating or completing a redemption on Twitch.</p>
+            <div className="flex flex-col md:flex-row gap-2 mb-4">
+                <select
+                    value={title}
+                    onChange={(e) => setTitle(e.target.value)}
+                    className="bg-gray-700 text-white rounded p-2"
+                >
+                    {rewards.map((reward) => (
+                        <option key={reward} value={reward}>{reward}</option>
+                    ))}
+                </select>
+                <input
+                    type="text"
+                    value={userInput}
+                    onChange={(e) => setUserInput(e.target.value)}
+                    placeholder="User input (optional)"
+                    className="flex-grow bg-gray-700 text-white rounded p-2"
+                />
+                <button
+                    onClick={runTest}
+                    disabled={isRunning}
+                    className="bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded disabled:opacity-50"
+                >
+                    {isRunning ? 'Running...' : 'Test redeem'}
+                </button>
+            </div>
+            {result && (
+                <p className={result.success ? 'text-green-500' : 'text-red-500'}>
+                    {result.success ? 'Succeeded' : 'Failed'}{result.message ? `: ${result.message}` : ''}
+                </p>
+            )}
+        </div>
+    );
+};
+
+export default RedeemTester;
@@ -0,0 +1,77 @@
+import React, { useState, useEffect } from 'react';
+
+const RemoteControl = () => {
+    const [pairing, setPairing] = useState(null);
+    const [secondsLeft, setSecondsLeft] = useState(0);
+    const [message, setMessage] = useState(null);
+
+    useEffect(() => {
+        if (secondsLeft <= 0) return undefined;
+        const timer = setTimeout(() => setSecondsLeft(secondsLeft - 1), 1000);
+        return () => clearTimeout(timer);
+    }, [secondsLeft]);
+
+    const post = async (url) => {
+        const response = await fetch(url, { method: 'POST' });
+        return response.json();
+    };
+
+    const createPairing = async () => {
+        setMessage(null);
+        try {
+            const result = await post('/api/remote/pair');
+            if (result.success) {
+                setPairing(result);
+                setSecondsLeft(result.expires_in);
+            } else {
+                setMessage(result.message);
+            }
+        } catch (err) {
+            setMessage(err.message);
+        }
+    };
+
+    const unpairAll = async () => {
+        try {
+            const result = await post('/api/remote/unpair');
+            setMessage(`Unpaired ${result.ended} phone(s)`);
+        } catch (err) {
+            setMessage(err.message);
+        }
+    };
+
+    const expired = pairing && secondsLeft <= 0;
+
+    return (
+        <div className="p-4">
+            <h2 className="text-2xl font-bold mb-4">Remote</h2>
+            <p className="text-gray-400 text-sm mb-4">
+                Big buttons for your phone while you're in VR: preset chat messages, OSC toggles, skipping TTS,
+                highlight markers and ads. Scan the code with a phone on the same network to pair it.
+            </p>
+            <div className="bg-gray-800 p-4 rounded-lg mb-4">
+                <div className="flex gap-2 mb-4">
+                    <button onClick={createPairing} className="bg-purple-600 hover:bg-purple-700 text-white px-4 py-2 rounded">
+                        {pairing ? 'New code' : 'Pair a phone'}
+                    </button>
+                    <button onClick={unpairAll} className="bg-red-600 hover:bg-red-700 text-white px-4 py-2 rounded">
+                        Unpair all
+                    </button>
+                </div>
+                {message && <p className="text-yellow-400 mb-2">{message}</p>}
+                {pairing && !expired && (
+                    <div>
+                        <div className="bg-white inline-block p-2 rounded" dangerouslySetInnerHTML={{ __html: pairing.qr_svg }} />
+                        <p className="text-sm mt-2">
+                            Expires in {secondsLeft}s · <a href={pairing.url} className="text-purple-400" target="_blank" rel="noopener noreferrer">{pairing.url}</a>
+                        </p>
+                        {pairing.warning && <p className="text-yellow-400 text-sm mt-2">{pairing.warning}</p>}
+                    </div>
+                )}
+                {expired && <p className="text-gray-400">The code expired. Make a new one to pair another phone.</p>}
+            </div>
+        </div>
+    );
+};
+
+export default RemoteControl;
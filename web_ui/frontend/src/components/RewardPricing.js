@@ -0,0 +1,65 @@
+import React, { useState, useEffect } from 'react';
+
+const REFRESH_INTERVAL_MS = 30000;
+
+const RewardPricing = () => {
+    const [rewards, setRewards] = useState([]);
+    const [changes, setChanges] = useState([]);
+
+    useEffect(() => {
+        const fetchPricing = () => {
+            fetch('/api/redeems/pricing')
+                .then((response) => response.json())
+                .then((data) => {
+                    setRewards(data.rewards);
+                    setChanges(data.changes);
+                })
+                .catch((err) => console.error('Failed to fetch reward pricing:', err));
+        };
+        fetchPricing();
+        const interval = setInterval(fetchPricing, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, []);
+
+    if (rewards.length === 0) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Reward Pricing</h2>
+            <table className="w-full text-left text-gray-300 mb-4">
+                <thead>
+                    <tr className="text-gray-400">
+                        <th className="pb-2">Reward</th>
+                        <th className="pb-2">Cost</th>
+                        <th className="pb-2">Range</th>
+                        <th className="pb-2">Per redeem</th>
+                        <th className="pb-2">Decay</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {rewards.map(({ title, cost, pricing }) => (
+                        <tr key={title}>
+                            <td className="py-1">{title}</td>
+                            <td>{cost}</td>
+                            <td>{pricing.floor ?? 'base'} - {pricing.ceiling}</td>
+                            <td>+{pricing.step}</td>
+                            <td>-{pricing.decay_step} every {pricing.decay_interval_secs}s</td>
+                        </tr>
+                    ))}
+                </tbody>
+            </table>
+            <h3 className="text-lg font-semibold mb-2 text-white">Recent changes</h3>
+            <ul className="max-h-48 overflow-y-auto text-sm text-gray-400">
+                {changes.map((change, i) => (
+                    <li key={i}>
+                        {new Date(change.changed_at).toLocaleTimeString()} {change.reward_title}: {change.old_cost} → {change.new_cost} ({change.reason})
+                    </li>
+                ))}
+            </ul>
+        </div>
+    );
+};
+
+export default RewardPricing;
@@ -0,0 +1,54 @@
+import React, { useState, useEffect } from 'react';
+
+const REFRESH_INTERVAL_MS = 30000;
+
+const ShortLinks = () => {
+    const [links, setLinks] = useState([]);
+
+    useEffect(() => {
+        const fetchLinks = () => {
+            fetch('/api/links')
+                .then((response) => response.json())
+                .then(setLinks)
+                .catch((err) => console.error('Failed to fetch short links:', err));
+        };
+
+        fetchLinks();
+        const interval = setInterval(fetchLinks, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, []);
+
+    if (links.length === 0) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-4 text-white">Short Links</h2>
+            <table className="w-full text-left text-gray-300">
+                <thead>
+                    <tr className="text-gray-400">
+                        <th className="pb-2">Link</th>
+                        <th className="pb-2">Goes to</th>
+                        <th className="pb-2">Added by</th>
+                        <th className="pb-2">Clicks</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {links.map((link) => (
+                        <tr key={link.slug}>
+                            <td className="py-1">
+                                <a href={`/l/${link.slug}`} className="text-purple-400" target="_blank" rel="noopener noreferrer">/l/{link.slug}</a>
+                            </td>
+                            <td className="truncate max-w-xs">{link.url}</td>
+                            <td>{link.created_by}</td>
+                            <td>{link.clicks}</td>
+                        </tr>
+                    ))}
+                </tbody>
+            </table>
+        </div>
+    );
+};
+
+export default ShortLinks;
@@ -0,0 +1,199 @@
+import React, { useState, useEffect } from 'react';
+
+const NEW_SOUNDS = {
+    overlay: { type: 'overlay', file: '' },
+    obs: { type: 'obs', instance_name: '', scene_name: '', source_name: '', filter_name: null, duration_secs: 5 },
+};
+
+const emptySound = () => ({
+    name: '',
+    playback: { ...NEW_SOUNDS.overlay },
+    cost: 0,
+    cooldown_secs: 30,
+    loudness_db: null,
+    enabled: true,
+});
+
+// RMS loudness of the whole file in dBFS, the same measure target_loudness_db uses
+const measureLoudness = async (url) => {
+    const response = await fetch(url);
+    if (!response.ok) {
+        throw new Error(`Couldn't load ${url}`);
+    }
+    const context = new (window.AudioContext || window.webkitAudioContext)();
+    try {
+        const buffer = await context.decodeAudioData(await response.arrayBuffer());
+        let sum = 0;
+        let count = 0;
+        for (let channel = 0; channel < buffer.numberOfChannels; channel++) {
+            const samples = buffer.getChannelData(channel);
+            for (let i = 0; i < samples.length; i++) {
+                sum += samples[i] * samples[i];
+            }
+            count += samples.length;
+        }
+        const rms = Math.sqrt(sum / Math.max(count, 1));
+        return Math.round(20 * Math.log10(Math.max(rms, 1e-6)) * 10) / 10;
+    } finally {
+        context.close();
+    }
+};
+
+const Soundboard = () => {
+    const [soundboard, setSoundboard] = useState(null);
+    const [editing, setEditing] = useState(null);
+    const [status, setStatus] = useState(null);
+
+    const fetchSounds = () => {
+        fetch('/api/soundboard')
+            .then((response) => response.json())
+            .then(setSoundboard)
+            .catch((err) => console.error('Failed to fetch the soundboard:', err));
+    };
+
+    useEffect(() => {
+        fetchSounds();
+        const interval = setInterval(fetchSounds, 10000);
+        return () => clearInterval(interval);
+    }, []);
+
+    const post = async (path, body) => {
+        try {
+            const response = await fetch(path, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body),
+            });
+            const data = await response.json();
+            setStatus(data);
+            fetchSounds();
+            return data;
+        } catch (err) {
+            console.error(`Failed to call ${path}:`, err);
+            setStatus({ success: false, message: err.message });
+            return null;
+        }
+    };
+
+    const saveSound = async () => {
+        const { cooldown_remaining_secs, times_played, ...sound } = editing;
+        const data = await post('/api/soundboard', sound);
+        if (data && data.success) {
+            setEditing(null);
+        }
+    };
+
+    const measure = async () => {
+        try {
+            const loudness = await measureLoudness(`/sounds/${encodeURIComponent(editing.playback.file)}`);
+            setEditing({ ...editing, loudness_db: loudness });
+        } catch (err) {
+            setStatus({ success: false, message: err.message });
+        }
+    };
+
+    const update = (field, value) => setEditing({ ...editing, [field]: value });
+    const updatePlayback = (field, value) => setEditing({ ...editing, playback: { ...editing.playback, [field]: value } });
+
+    const input = (value, onChange, placeholder, type = 'text') => (
+        <input
+            type={type}
+            value={value ?? ''}
+            onChange={(e) => onChange(type === 'number' ? (e.target.value === '' ? null : Number(e.target.value)) : e.target.value)}
+            placeholder={placeholder}
+            className="bg-gray-700 text-white rounded p-2"
+        />
+    );
+
+    if (!soundboard) {
+        return null;
+    }
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <h2 className="text-2xl font-bold mb-1 text-white">Soundboard</h2>
+            <p className="text-gray-400 text-sm mb-4">
+                {soundboard.enabled ? 'Chat plays these with !sound <name>; costs are in drop points.' : 'The soundboard is turned off in the config.'}
+                {' '}Overlay sounds play from the sounds folder through the /soundboard/overlay browser source.
+            </p>
+            {soundboard.sounds.length > 0 && (
+                <table className="w-full text-left text-gray-300 mb-4">
+                    <thead>
+                        <tr className="text-gray-400">
+                            <th className="pb-2">Sound</th>
+                            <th className="pb-2">Plays</th>
+                            <th className="pb-2">Cost</th>
+                            <th className="pb-2">Cooldown</th>
+                            <th className="pb-2">Loudness</th>
+                            <th className="pb-2">Played</th>
+                            <th />
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {soundboard.sounds.map((sound) => (
+                            <tr key={sound.name} className={sound.enabled ? '' : 'opacity-50'}>
+                                <td className="py-1">{sound.name}</td>
+                                <td>{sound.playback.type === 'obs' ? `OBS: ${sound.playback.source_name}` : sound.playback.file}</td>
+                                <td>{sound.cost}</td>
+                                <td>{sound.cooldown_remaining_secs > 0 ? `${sound.cooldown_remaining_secs}s left` : `${sound.cooldown_secs}s`}</td>
+                                <td>
+                                    {sound.loudness_db === null
+                                        ? 'not measured'
+                                        : `${sound.loudness_db} dB (${(soundboard.target_loudness_db - sound.loudness_db).toFixed(1)} dB gain)`}
+                                </td>
+                                <td>{sound.times_played}</td>
+                                <td className="flex gap-2">
+                                    <button onClick={() => post('/api/soundboard/play', { name: sound.name })} className="text-green-400 hover:text-green-300">Play</button>
+                                    <button onClick={() => setEditing({ ...sound })} className="text-purple-400 hover:text-purple-300">Edit</button>
+                                    <button onClick={() => post('/api/soundboard/remove', { name: sound.name })} className="text-red-400 hover:text-red-300">Remove</button>
+                                </td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            )}
+            {editing ? (
+                <div className="flex flex-wrap items-center gap-2">
+                    {input(editing.name, (value) => update('name', value), 'Name')}
+                    <select
+                        value={editing.playback.type}
+                        onChange={(e) => update('playback', { ...NEW_SOUNDS[e.target.value] })}
+                        className="bg-gray-700 text-white rounded p-2"
+                    >
+                        <option value="overlay">Overlay file</option>
+                        <option value="obs">OBS media source</option>
+                    </select>
+                    {editing.playback.type === 'overlay' ? (
+                        <>
+                            {input(editing.playback.file, (value) => updatePlayback('file', value), 'File in the sounds folder')}
+                            <button onClick={measure} className="bg-gray-600 hover:bg-gray-500 text-white py-2 px-4 rounded">Measure loudness</button>
+                        </>
+                    ) : (
+                        <>
+                            {input(editing.playback.instance_name, (value) => updatePlayback('instance_name', value), 'OBS instance')}
+                            {input(editing.playback.scene_name, (value) => updatePlayback('scene_name', value), 'Scene')}
+                            {input(editing.playback.source_name, (value) => updatePlayback('source_name', value), 'Media source')}
+                            {input(editing.playback.duration_secs, (value) => updatePlayback('duration_secs', value ?? 0), 'Length (s)', 'number')}
+                        </>
+                    )}
+                    {input(editing.cost, (value) => update('cost', value ?? 0), 'Cost', 'number')}
+                    {input(editing.cooldown_secs, (value) => update('cooldown_secs', value ?? 0), 'Cooldown (s)', 'number')}
+                    {input(editing.loudness_db, (value) => update('loudness_db', value), 'Loudness (dBFS)', 'number')}
+                    <label className="text-gray-300">
+                        <input type="checkbox" checked={editing.enabled} onChange={(e) => update('enabled', e.target.checked)} className="mr-1" />
+                        Enabled
+                    </label>
+                    <button onClick={saveSound} className="bg-purple-600 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded">Save</button>
+                    <button onClick={() => setEditing(null)} className="text-gray-300 hover:text-white px-2">Cancel</button>
+                </div>
+            ) : (
+                <button onClick={() => setEditing(emptySound())} className="bg-gray-600 hover:bg-gray-500 text-white py-2 px-4 rounded">
+                    + Sound
+                </button>
+            )}
+            {status && !status.success && <p className="mt-2 text-red-500">Failed: {status.message}</p>}
+        </div>
+    );
+};
+
+export default Soundboard;
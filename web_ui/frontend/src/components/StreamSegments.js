@@ -0,0 +1,88 @@
+import React, { useState, useEffect } from 'react';
+
+const formatDuration = (seconds) => {
+    const hours = Math.floor(seconds / 3600);
+    const minutes = Math.floor((seconds % 3600) / 60);
+    return hours > 0 ? `${hours}h ${minutes}m` : `${minutes}m`;
+};
+
+const formatTime = (timestamp) => new Date(timestamp).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' });
+
+const StreamSegments = () => {
+    const [latest, setLatest] = useState([]);
+    const [totals, setTotals] = useState([]);
+    const [days, setDays] = useState(30);
+    const [error, setError] = useState(null);
+
+    useEffect(() => {
+        const fetchSegments = async () => {
+            try {
+                const response = await fetch(`/api/segments?days=${days}`);
+                if (!response.ok) {
+                    throw new Error('Failed to fetch stream segments');
+                }
+                const data = await response.json();
+                setLatest(data.latest);
+                setTotals(data.totals);
+                setError(null);
+            } catch (err) {
+                console.error('Failed to fetch stream segments:', err);
+                setError(err.message);
+            }
+        };
+        fetchSegments();
+        const interval = setInterval(fetchSegments, 60000);
+        return () => clearInterval(interval);
+    }, [days]);
+
+    const longest = Math.max(1, ...totals.map((total) => total.seconds));
+
+    return (
+        <div className="bg-gray-800 p-6 rounded-lg shadow-md">
+            <div className="flex items-center justify-between mb-4">
+                <h2 className="text-2xl font-bold text-white">Stream Segments</h2>
+                <select value={days} onChange={(e) => setDays(Number(e.target.value))} className="bg-gray-700 text-white rounded p-2">
+                    <option value={7}>7 days</option>
+                    <option value={30}>30 days</option>
+                    <option value={90}>90 days</option>
+                </select>
+            </div>
+            {error && <p className="text-red-500 mb-4">{error}</p>}
+            {latest.length === 0 && totals.length === 0 ? (
+                <p className="text-gray-400">No segments yet. They're recorded whenever the game or VRChat world changes during a stream.</p>
+            ) : (
+                <div className="grid md:grid-cols-2 gap-6">
+                    <div>
+                        <h3 className="text-lg font-semibold text-white mb-2">Latest stream</h3>
+                        <ul className="text-gray-300 space-y-1">
+                            {latest.map((segment) => (
+                                <li key={`${segment.kind}-${segment.started_at}`} className={segment.kind === 'world' ? 'pl-4 text-gray-400' : ''}>
+                                    {formatTime(segment.started_at)} · {segment.name} · {formatDuration(segment.seconds)}
+                                    {!segment.ended_at && <span className="text-green-400 ml-1">(now)</span>}
+                                </li>
+                            ))}
+                        </ul>
+                    </div>
+                    <div>
+                        <h3 className="text-lg font-semibold text-white mb-2">Last {days} days</h3>
+                        <ul className="space-y-2">
+                            {totals.map((total) => (
+                                <li key={`${total.kind}-${total.name}`} className="text-gray-300">
+                                    <div className="flex justify-between text-sm">
+                                        <span>{total.name} <span className="text-gray-500">({total.kind})</span></span>
+                                        <span>{formatDuration(total.seconds)} · {total.streams} {total.streams === 1 ? 'stream' : 'streams'}</span>
+                                    </div>
+                                    <div className="bg-gray-700 rounded h-2">
+                                        <div className="bg-purple-500 rounded h-2" style={{ width: `${(total.seconds / longest) * 100}%` }} />
+                                    </div>
+                                </li>
+                            ))}
+                        </ul>
+                    </div>
+                </div>
+            )}
+        </div>
+    );
+};
+
+export default StreamSegments;
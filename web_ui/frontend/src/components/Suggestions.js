@@ -0,0 +1,87 @@
+import React, { useState, useEffect, useCallback } from 'react';
+
+const REFRESH_INTERVAL_MS = 15000;
+
+const Suggestions = () => {
+    const [pending, setPending] = useState([]);
+    const [enabled, setEnabled] = useState(true);
+    const [message, setMessage] = useState(null);
+
+    const load = useCallback(async () => {
+        try {
+            const response = await fetch('/api/suggestions');
+            const data = await response.json();
+            setPending(data.pending || []);
+            setEnabled(data.enabled);
+        } catch (err) {
+            setMessage(err.message);
+        }
+    }, []);
+
+    useEffect(() => {
+        load();
+        const interval = setInterval(load, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, [load]);
+
+    const post = async (url, body) => {
+        setMessage(null);
+        try {
+            const response = await fetch(url, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body),
+            });
+            const result = await response.json();
+            if (!result.success) {
+                setMessage(result.message || 'Nothing to do, it may already be gone');
+            }
+            await load();
+        } catch (err) {
+            setMessage(err.message);
+        }
+    };
+
+    return (
+        <div className="p-4">
+            <h2 className="text-2xl font-bold mb-4">Suggestions</h2>
+            {!enabled && <p className="text-gray-400 mb-2">Suggestions are turned off, so viewers can't add new ones.</p>}
+            {message && <p className="text-yellow-400 mb-2">{message}</p>}
+            <div className="bg-gray-800 p-4 rounded-lg">
+                <p className="text-sm text-gray-400 mb-2">
+                    Viewers suggest with !suggest title ... or !suggest tags ..., or the "suggest a title" and "suggest tags" redeems.
+                    Approving applies it to the channel right away.
+                </p>
+                {pending.length === 0 && <p className="text-gray-400">Nothing waiting.</p>}
+                {pending.map(item => (
+                    <div key={item.id} className="flex items-start justify-between border-b border-gray-700 py-2">
+                        <div>
+                            <p className="text-sm text-gray-400">
+                                {item.user_name} · {item.kind} · {new Date(item.created_at).toLocaleString()}
+                            </p>
+                            {item.kind === 'tags' ? (
+                                <div className="flex flex-wrap gap-1 mt-1">
+                                    {item.value.split(',').map(tag => (
+                                        <span key={tag} className="bg-gray-700 px-2 py-0.5 rounded text-sm">{tag}</span>
+                                    ))}
+                                </div>
+                            ) : (
+                                <p>{item.value}</p>
+                            )}
+                        </div>
+                        <div className="flex gap-2 ml-4">
+                            <button onClick={() => post('/api/suggestions/approve', { id: item.id })} className="bg-green-600 hover:bg-green-700 text-white px-3 py-1 rounded">
+                                Apply
+                            </button>
+                            <button onClick={() => post('/api/suggestions/reject', { id: item.id })} className="bg-red-600 hover:bg-red-700 text-white px-3 py-1 rounded">
+                                Reject
+                            </button>
+                        </div>
+                    </div>
+                ))}
+            </div>
+        </div>
+    );
+};
+
+export default Suggestions;
@@ -0,0 +1,118 @@
+import React, { useState, useEffect } from 'react';
+import { applyTheme, fetchTheme } from '../theme';
+
+const ThemeSettings = () => {
+    const [theme, setTheme] = useState(null);
+    const [pages, setPages] = useState({ pages_dir: '', pages: [] });
+    const [status, setStatus] = useState(null);
+    const [error, setError] = useState(null);
+
+    useEffect(() => {
+        fetchTheme().then(setTheme).catch((err) => setError(err.message));
+        fetch('/api/pages')
+            .then((response) => response.json())
+            .then(setPages)
+            .catch((err) => console.error('Failed to fetch pages:', err));
+    }, []);
+
+    const handleThemeResponse = async (response, message) => {
+        if (!response.ok) {
+            throw new Error('The server rejected the change');
+        }
+        const updated = await response.json();
+        setTheme(updated);
+        applyTheme(updated);
+        setStatus(message);
+        setError(null);
+    };
+
+    const saveTheme = async (changes) => {
+        try {
+            const response = await fetch('/api/theme', {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(changes),
+            });
+            await handleThemeResponse(response, 'Theme saved');
+        } catch (err) {
+            console.error('Failed to save theme:', err);
+            setError(err.message);
+        }
+    };
+
+    const uploadLogo = async (event) => {
+        const file = event.target.files[0];
+        if (!file) {
+            return;
+        }
+        try {
+            const response = await fetch('/api/theme/logo', {
+                method: 'POST',
+                headers: { 'Content-Type': file.type },
+                body: file,
+            });
+            await handleThemeResponse(response, 'Logo uploaded');
+        } catch (err) {
+            console.error('Failed to upload logo:', err);
+            setError(`${err.message} (PNG, JPEG, GIF or WebP up to 2 MB)`);
+        }
+    };
+
+    if (!theme) {
+        return error ? <p className="text-red-500">{error}</p> : null;
+    }
+
+    return (
+        <div className="mt-8">
+            <h3 className="text-xl font-bold mb-4 dark:text-white">Appearance</h3>
+            {status && <p className="text-green-500 mb-2">{status}</p>}
+            {error && <p className="text-red-500 mb-2">{error}</p>}
+            <div className="grid grid-cols-1 md:grid-cols-3 gap-4 mb-6">
+                <label className="block">
+                    <span className="text-gray-400">Theme</span>
+                    <select
+                        value={theme.theme}
+                        onChange={(e) => saveTheme({ theme: e.target.value })}
+                        className="mt-1 block w-full bg-gray-700 text-white rounded p-2"
+                    >
+                        <option value="dark">Dark</option>
+                        <option value="light">Light</option>
+                    </select>
+                </label>
+                <label className="block">
+                    <span className="text-gray-400">Accent color</span>
+                    <input
+                        type="color"
+                        value={theme.accent_color}
+                        onChange={(e) => saveTheme({ accent_color: e.target.value })}
+                        className="mt-1 block w-full h-10 bg-gray-700 rounded"
+                    />
+                </label>
+                <label className="block">
+                    <span className="text-gray-400">Logo</span>
+                    <input
+                        type="file"
+                        accept="image/png,image/jpeg,image/gif,image/webp"
+                        onChange={uploadLogo}
+                        className="mt-1 block w-full text-gray-300"
+                    />
+                </label>
+            </div>
+            {theme.logo_url && <img src={`${theme.logo_url}?t=${Date.now()}`} alt="Logo" className="max-h-16 mb-6" />}
+            <h4 className="text-lg font-semibold mb-2 dark:text-white">Custom pages</h4>
+            <p className="text-gray-400 text-sm mb-2">
+                Markdown files in <code>{pages.pages_dir}</code> are published at <a href="/pages" className="text-purple-400" target="_blank" rel="noopener noreferrer">/pages</a>.
+            </p>
+            {pages.pages.length === 0 && <p className="text-gray-400">No pages yet</p>}
+            <ul className="space-y-1">
+                {pages.pages.map((page) => (
+                    <li key={page.slug}>
+                        <a href={page.url} className="text-purple-400" target="_blank" rel="noopener noreferrer">{page.title}</a>
+                    </li>
+                ))}
+            </ul>
+        </div>
+    );
+};
+
+export default ThemeSettings;
@@ -0,0 +1,102 @@
+import React, { useState, useEffect, useCallback } from 'react';
+import useWebSocket from './useWebSocket';
+
+const TtsQueue = () => {
+    const [pending, setPending] = useState([]);
+    const [bans, setBans] = useState([]);
+    const [message, setMessage] = useState(null);
+
+    const load = useCallback(async () => {
+        try {
+            const response = await fetch('/api/tts');
+            const data = await response.json();
+            setPending(data.pending || []);
+            setBans(data.bans || []);
+        } catch (err) {
+            setMessage(err.message);
+        }
+    }, []);
+
+    useEffect(() => {
+        load();
+    }, [load]);
+
+    const handleWebSocketMessage = useCallback((data) => {
+        if (data.module === 'tts' && data.action === 'queue') {
+            setPending(data.data.pending || []);
+        }
+    }, []);
+
+    const handleWebSocketError = useCallback((error) => {
+        console.error('WebSocket error:', error);
+    }, []);
+
+    useWebSocket(`ws://${window.location.hostname}:3333/ws`, handleWebSocketMessage, handleWebSocketError);
+
+    const post = async (url, body) => {
+        setMessage(null);
+        try {
+            const response = await fetch(url, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body || {}),
+            });
+            const result = await response.json();
+            if (!result.success) {
+                setMessage(result.message || 'Nothing to do, it may already be gone');
+            }
+            await load();
+        } catch (err) {
+            setMessage(err.message);
+        }
+    };
+
+    return (
+        <div className="p-4">
+            <div className="flex items-center justify-between mb-4">
+                <h2 className="text-2xl font-bold">TTS</h2>
+                <button onClick={() => post('/api/tts/skip')} className="bg-red-600 hover:bg-red-700 text-white px-4 py-2 rounded">
+                    Skip current
+                </button>
+            </div>
+            {message && <p className="text-yellow-400 mb-2">{message}</p>}
+            <div className="bg-gray-800 p-4 rounded-lg mb-4">
+                <h3 className="text-lg font-semibold mb-2">Held messages</h3>
+                {pending.length === 0 && <p className="text-gray-400">Nothing waiting.</p>}
+                {pending.map(item => (
+                    <div key={item.id} className="flex items-start justify-between border-b border-gray-700 py-2">
+                        <div>
+                            <p className="text-sm text-gray-400">
+                                {item.user_name} · {item.source}{item.voice ? ` · ${item.voice}` : ''} · {new Date(item.queued_at).toLocaleTimeString()}
+                            </p>
+                            <p>{item.text}</p>
+                        </div>
+                        <div className="flex gap-2 ml-4">
+                            <button onClick={() => post('/api/tts/approve', { id: item.id })} className="bg-green-600 hover:bg-green-700 text-white px-3 py-1 rounded">
+                                Play
+                            </button>
+                            <button onClick={() => post('/api/tts/reject', { id: item.id })} className="bg-red-600 hover:bg-red-700 text-white px-3 py-1 rounded">
+                                Reject
+                            </button>
+                        </div>
+                    </div>
+                ))}
+            </div>
+            <div className="bg-gray-800 p-4 rounded-lg">
+                <h3 className="text-lg font-semibold mb-2">Banned from TTS</h3>
+                <p className="text-gray-400 text-sm mb-2">Mods can add people with !ttsban &lt;user&gt; in chat.</p>
+                {bans.length === 0 && <p className="text-gray-400">Nobody.</p>}
+                {bans.map(user => (
+                    <div key={user} className="flex items-center justify-between py-1">
+                        <span>{user}</span>
+                        <button onClick={() => post('/api/tts/unban', { user })} className="text-purple-400 hover:text-purple-300">
+                            Unban
+                        </button>
+                    </div>
+                ))}
+            </div>
+        </div>
+    );
+};
+
+export default TtsQueue;
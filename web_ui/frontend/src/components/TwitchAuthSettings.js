@@ -0,0 +1,43 @@
+import React, { useState } from 'react';
+
+const TwitchAuthSettings = () => {
+    const [authUrl, setAuthUrl] = useState(null);
+    const [error, setError] = useState(null);
+
+    const reauthorize = async () => {
+        setError(null);
+        try {
+            const response = await fetch('/api/twitch/reauthorize', { method: 'POST' });
+            const result = await response.json();
+            if (!result.success) {
+                throw new Error(result.message || 'Couldn\'t start the authorization');
+            }
+            setAuthUrl(result.url);
+            window.open(result.url, '_blank', 'noopener,noreferrer');
+        } catch (err) {
+            console.error('Failed to start Twitch authorization:', err);
+            setError(err.message);
+        }
+    };
+
+    return (
+        <div className="mt-8">
+            <h3 className="text-xl font-bold mb-4 dark:text-white">Twitch authorization</h3>
+            <p className="text-gray-400 text-sm mb-4">
+                Authorize the bot again without restarting, e.g. after it starts asking for new permissions.
+                Twitch sends you back to this web UI, so its address has to be registered as an OAuth Redirect URL for your app.
+            </p>
+            <button onClick={reauthorize} className="bg-purple-600 hover:bg-purple-700 text-white px-4 py-2 rounded">
+                Re-authorize with Twitch
+            </button>
+            {authUrl && (
+                <p className="text-gray-300 text-sm mt-2">
+                    If no tab opened, <a href={authUrl} className="text-purple-400" target="_blank" rel="noopener noreferrer">open the Twitch authorization page</a>. The link works for 5 minutes.
+                </p>
+            )}
+            {error && <p className="text-red-500 mt-2">{error}</p>}
+        </div>
+    );
+};
+
+export default TwitchAuthSettings;
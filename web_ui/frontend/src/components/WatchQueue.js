@@ -0,0 +1,101 @@
+import React, { useState, useEffect, useCallback } from 'react';
+
+const REFRESH_INTERVAL_MS = 10000;
+
+const WatchQueue = () => {
+    const [state, setState] = useState({ enabled: true, current: null, queue: [] });
+    const [message, setMessage] = useState(null);
+
+    const load = useCallback(async () => {
+        try {
+            const response = await fetch('/api/watch');
+            setState(await response.json());
+        } catch (err) {
+            setMessage(err.message);
+        }
+    }, []);
+
+    useEffect(() => {
+        load();
+        const interval = setInterval(load, REFRESH_INTERVAL_MS);
+        return () => clearInterval(interval);
+    }, [load]);
+
+    const post = async (url, body) => {
+        setMessage(null);
+        try {
+            const response = await fetch(url, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(body || {}),
+            });
+            const result = await response.json();
+            if (!result.success) {
+                setMessage('Nothing left in the queue');
+            }
+            await load();
+        } catch (err) {
+            setMessage(err.message);
+        }
+    };
+
+    const clearQueue = () => {
+        if (window.confirm('Empty the queue and forget what was watched?')) {
+            post('/api/watch/clear');
+        }
+    };
+
+    const label = (item) => item.title || item.url;
+
+    return (
+        <div className="p-4">
+            <div className="flex items-center justify-between mb-4">
+                <h2 className="text-2xl font-bold">Watch Queue</h2>
+                <div className="flex gap-2">
+                    <button onClick={() => post('/api/watch/next')} className="bg-purple-600 hover:bg-purple-700 text-white px-4 py-2 rounded">
+                        Play next
+                    </button>
+                    <button onClick={clearQueue} className="bg-gray-600 hover:bg-gray-700 text-white px-4 py-2 rounded">
+                        Clear
+                    </button>
+                </div>
+            </div>
+            {!state.enabled && <p className="text-gray-400 mb-2">The queue is closed, so !watch won't take new videos.</p>}
+            {message && <p className="text-yellow-400 mb-2">{message}</p>}
+            <div className="bg-gray-800 p-4 rounded-lg mb-4">
+                <h3 className="text-lg font-semibold mb-2">Now watching</h3>
+                {state.current ? (
+                    <div>
+                        <a href={state.current.url} target="_blank" rel="noopener noreferrer" className="hover:underline">{label(state.current)}</a>
+                        <p className="text-sm text-gray-400">from {state.current.requested_by}</p>
+                        <input
+                            readOnly
+                            value={state.current.url}
+                            onFocus={(e) => e.target.select()}
+                            className="mt-2 w-full bg-gray-700 text-white px-3 py-1 rounded text-sm"
+                        />
+                    </div>
+                ) : (
+                    <p className="text-gray-400">Nothing yet.</p>
+                )}
+            </div>
+            <div className="bg-gray-800 p-4 rounded-lg">
+                <h3 className="text-lg font-semibold mb-2">Up next ({state.queue.length})</h3>
+                {state.queue.length === 0 && <p className="text-gray-400">The queue is empty. Viewers add videos with !watch &lt;youtube link&gt;.</p>}
+                {state.queue.map((item, index) => (
+                    <div key={item.id} className="flex items-start justify-between border-b border-gray-700 py-2">
+                        <div>
+                            <p className="text-sm text-gray-400">#{index + 1} · {item.requested_by} · {new Date(item.requested_at).toLocaleTimeString()}</p>
+                            <a href={item.url} target="_blank" rel="noopener noreferrer" className="hover:underline">{label(item)}</a>
+                        </div>
+                        <button onClick={() => post('/api/watch/remove', { id: item.id })} className="bg-red-600 hover:bg-red-700 text-white px-3 py-1 rounded ml-4">
+                            Remove
+                        </button>
+                    </div>
+                ))}
+            </div>
+        </div>
+    );
+};
+
+export default WatchQueue;
@@ -1,10 +1,14 @@
 import { useEffect, useRef, useCallback, useState } from 'react';
+import { getApiToken } from '../apiToken';
 
 function useWebSocket(url, onMessage, onError) {
     const [isConnected, setIsConnected] = useState(false);
     const socketRef = useRef(null);
     const reconnectTimeoutRef = useRef(null);
     const reconnectAttempts = useRef(0);
+    // Sent back on reconnect so the server replays whatever was missed while disconnected
+    const epochRef = useRef(null);
+    const lastSeqRef = useRef(0);
     const maxReconnectDelay = 30000; // 30 seconds
     const maxReconnectAttempts = 10;
 
@@ -19,7 +23,16 @@ function useWebSocket(url, onMessage, onError) {
 
         console.log(`Attempting to connect WebSocket (Attempt ${reconnectAttempts.current + 1})`);
 
-        socketRef.current = new WebSocket(url);
+        const connectUrl = new URL(url);
+        if (epochRef.current) {
+            connectUrl.searchParams.set('epoch', epochRef.current);
+            connectUrl.searchParams.set('resume', lastSeqRef.current);
+        }
+        const token = getApiToken();
+        if (token) {
+            connectUrl.searchParams.set('token', token);
+        }
+        socketRef.current = new WebSocket(connectUrl.toString());
 
         socketRef.current.onopen = () => {
             console.log('WebSocket connected');
@@ -36,6 +49,22 @@ function useWebSocket(url, onMessage, onError) {
 
             try {
                 const data = JSON.parse(event.data);
+                if (data.module === 'dashboard' && data.action === 'hello') {
+                    const resumed = epochRef.current === data.data.epoch && !data.data.resync;
+                    epochRef.current = data.data.epoch;
+                    if (!resumed) {
+                        lastSeqRef.current = data.data.seq;
+                    }
+                    // The bot restarted or too much was missed to replay; listeners should reload their state
+                    if (data.data.resync) {
+                        onMessage({ module: 'dashboard', action: 'resync', data: {} });
+                    }
+                    return;
+                }
+                if (data.seq !== undefined) {
+                    if (data.seq <= lastSeqRef.current) return;
+                    lastSeqRef.current = data.seq;
+                }
                 onMessage(data);
             } catch (error) {
                 console.error('Failed to parse WebSocket message:', error, 'Raw message:', event.data);
@@ -4,9 +4,12 @@ import { HashRouter } from 'react-router-dom';
 import './index.css';
 import App from './App';
 import ErrorBoundary from './components/ErrorBoundary';
+import { loadTheme } from './theme';
+import { installApiToken } from './apiToken';
 
-// Force dark mode
+installApiToken();
 document.documentElement.classList.add('dark');
+loadTheme();
 
 ReactDOM.render(
     <React.StrictMode>
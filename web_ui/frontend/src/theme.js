@@ -0,0 +1,37 @@
+// Applies the theme configured in mewbot.conf ([ui_theme]) to the dashboard.
+// Dark is the default and is applied before the first fetch to avoid a flash.
+
+const THEME_EVENT = 'mewbot-theme';
+
+export const applyTheme = (theme) => {
+    const root = document.documentElement;
+    const isLight = theme.theme === 'light';
+    root.classList.toggle('dark', !isLight);
+    root.classList.toggle('theme-light', isLight);
+    if (theme.accent_color) {
+        root.style.setProperty('--accent-color', theme.accent_color);
+    }
+    window.dispatchEvent(new CustomEvent(THEME_EVENT, { detail: theme }));
+};
+
+export const fetchTheme = async () => {
+    const response = await fetch('/api/theme');
+    if (!response.ok) {
+        throw new Error('Failed to fetch theme');
+    }
+    return response.json();
+};
+
+export const loadTheme = async () => {
+    try {
+        applyTheme(await fetchTheme());
+    } catch (err) {
+        console.error('Failed to load theme:', err);
+    }
+};
+
+export const onThemeChange = (callback) => {
+    const listener = (event) => callback(event.detail);
+    window.addEventListener(THEME_EVENT, listener);
+    return () => window.removeEventListener(THEME_EVENT, listener);
+};